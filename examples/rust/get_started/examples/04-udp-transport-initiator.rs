@@ -1,7 +1,7 @@
 // This node routes a message, to a worker on a different node, over the udp transport.
 
 use ockam::{node, route, Context, Result};
-use ockam_transport_udp::{UdpTransportExtension, UDP};
+use ockam_transport_udp::{UdpConnectionOptions, UdpTransportExtension};
 
 #[ockam::node]
 async fn main(ctx: Context) -> Result<()> {
@@ -9,10 +9,13 @@ async fn main(ctx: Context) -> Result<()> {
     let mut node = node(ctx);
 
     // Initialize the UDP Transport
-    let _udp = node.create_udp_transport().await?;
+    let udp = node.create_udp_transport().await?;
+
+    // Bind a local socket to exchange datagrams with the other node.
+    let connection = udp.connect("localhost:4000", UdpConnectionOptions::new()).await?;
 
     // Send a message to the "echoer" worker, on a different node, over an udp transport.
-    let r = route![(UDP, "localhost:4000"), "echoer"];
+    let r = route![connection, "echoer"];
     node.send(r, "Hello Ockam!".to_string()).await?;
 
     // Wait to receive a reply and print it.
@@ -4,7 +4,7 @@
 use hello_ockam::Echoer;
 use ockam::access_control::AllowAll;
 use ockam::{node, Context, Result};
-use ockam_transport_udp::UdpTransportExtension;
+use ockam_transport_udp::{UdpListenerOptions, UdpTransportExtension};
 
 #[ockam::node]
 async fn main(ctx: Context) -> Result<()> {
@@ -15,7 +15,7 @@ async fn main(ctx: Context) -> Result<()> {
     let udp = node.create_udp_transport().await?;
 
     // Create a UDP listener and wait for incoming datagrams.
-    udp.listen("127.0.0.1:4000").await?;
+    udp.listen("127.0.0.1:4000", UdpListenerOptions::new()).await?;
 
     // Create an echoer worker
     node.start_worker("echoer", Echoer, AllowAll, AllowAll).await?;
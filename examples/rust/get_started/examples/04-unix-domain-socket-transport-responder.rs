@@ -3,7 +3,7 @@
 
 use hello_ockam::Echoer;
 use ockam::{access_control::AllowAll, node, Context, Result};
-use ockam_transport_uds::UdsTransportExtension;
+use ockam_transport_uds::{UdsListenerOptions, UdsTransportExtension};
 
 #[ockam::node]
 async fn main(ctx: Context) -> Result<()> {
@@ -14,7 +14,8 @@ async fn main(ctx: Context) -> Result<()> {
     let uds = node.create_uds_transport().await?;
 
     // Create a Uds listener and wait for incoming connections.
-    uds.listen("/tmp/ockam-example-echoer").await?;
+    uds.listen("/tmp/ockam-example-echoer", UdsListenerOptions::new())
+        .await?;
 
     // Create an echoer worker
     node.start_worker("echoer", Echoer, AllowAll, AllowAll).await?;
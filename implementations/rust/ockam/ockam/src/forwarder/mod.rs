@@ -0,0 +1,11 @@
+//! [`ForwardingService`] registers remote workers under local aliases. Static aliases are
+//! owned by the identity which registered them, and expire when their heartbeats stop.
+
+mod options;
+mod registry;
+mod service;
+mod worker;
+
+pub use options::*;
+pub use registry::*;
+pub use service::*;
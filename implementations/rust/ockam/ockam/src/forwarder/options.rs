@@ -0,0 +1,77 @@
+use crate::forwarder::ForwarderRegistry;
+use core::time::Duration;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, AllowAll, IncomingAccessControl, RelayMessage, Result};
+
+/// Decide whether a registration message may claim a given forwarder alias
+#[async_trait]
+pub trait AliasAccessControl: Send + Sync + 'static {
+    /// Return true if the sender of the registration message is allowed to use the alias
+    async fn is_authorized(&self, alias: &str, relay_msg: &RelayMessage) -> Result<bool>;
+}
+
+/// Options for a [`ForwardingService`](super::ForwardingService)
+pub struct ForwardingServiceOptions {
+    pub(super) service_incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) forwarders_incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) alias_access_control: Option<Arc<dyn AliasAccessControl>>,
+    pub(super) heartbeat_timeout: Option<Duration>,
+    pub(super) registry: ForwarderRegistry,
+}
+
+impl ForwardingServiceOptions {
+    /// Default options: every message is allowed, any alias can be claimed and
+    /// forwarders never expire.
+    /// Should only be used for testing purposes
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            service_incoming_access_control: Arc::new(AllowAll),
+            forwarders_incoming_access_control: Arc::new(AllowAll),
+            alias_access_control: None,
+            heartbeat_timeout: None,
+            registry: ForwarderRegistry::default(),
+        }
+    }
+
+    /// Set the access control for registration messages sent to the service
+    pub fn with_service_incoming_access_control(
+        mut self,
+        access_control: impl IncomingAccessControl,
+    ) -> Self {
+        self.service_incoming_access_control = Arc::new(access_control);
+        self
+    }
+
+    /// Set the access control for messages sent to the created forwarders
+    pub fn with_forwarders_incoming_access_control(
+        mut self,
+        access_control: impl IncomingAccessControl,
+    ) -> Self {
+        self.forwarders_incoming_access_control = Arc::new(access_control);
+        self
+    }
+
+    /// Restrict which aliases can be claimed by a registration message
+    pub fn with_alias_access_control(
+        mut self,
+        access_control: Arc<dyn AliasAccessControl>,
+    ) -> Self {
+        self.alias_access_control = Some(access_control);
+        self
+    }
+
+    /// Remove a static forwarder when it hasn't been refreshed by its owner for this duration.
+    /// Forwarders registered without a secure channel have no owner and don't expire
+    pub fn with_heartbeat_timeout(mut self, timeout: Duration) -> Self {
+        self.heartbeat_timeout = Some(timeout);
+        self
+    }
+
+    /// Use a registry shared with the caller, to list and delete the created forwarders
+    pub fn with_registry(mut self, registry: ForwarderRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+}
@@ -0,0 +1,177 @@
+use crate::Context;
+use core::time::Duration;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+use ockam_core::{Address, Result, Route};
+use ockam_identity::{IdentityIdentifier, Timestamp};
+use tracing::info;
+
+/// A forwarder created by a [`ForwardingService`](super::ForwardingService)
+#[derive(Clone, Debug)]
+pub struct ForwarderEntry {
+    alias: String,
+    owner: Option<IdentityIdentifier>,
+    forward_route: Route,
+    worker_address: Address,
+    created_at: Option<Timestamp>,
+    last_seen: Option<Timestamp>,
+    expires_after: Option<Duration>,
+}
+
+impl ForwarderEntry {
+    pub(super) fn new(
+        alias: String,
+        owner: Option<IdentityIdentifier>,
+        forward_route: Route,
+        worker_address: Address,
+        expires_after: Option<Duration>,
+    ) -> Self {
+        let now = Timestamp::now();
+        Self {
+            alias,
+            owner,
+            forward_route,
+            worker_address,
+            created_at: now,
+            last_seen: now,
+            expires_after,
+        }
+    }
+
+    /// Alias under which the forwarder is registered
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// Identity which registered the forwarder over a secure channel, if any
+    pub fn owner(&self) -> Option<&IdentityIdentifier> {
+        self.owner.as_ref()
+    }
+
+    /// Route to which messages sent to the forwarder are forwarded
+    pub fn forward_route(&self) -> &Route {
+        &self.forward_route
+    }
+
+    /// Address of the forwarder worker
+    pub fn worker_address(&self) -> &Address {
+        &self.worker_address
+    }
+
+    /// Time of the first registration
+    pub fn created_at(&self) -> Option<Timestamp> {
+        self.created_at
+    }
+
+    /// Time of the last registration or heartbeat
+    pub fn last_seen(&self) -> Option<Timestamp> {
+        self.last_seen
+    }
+
+    /// Duration without heartbeat after which the forwarder is removed
+    pub fn expires_after(&self) -> Option<Duration> {
+        self.expires_after
+    }
+
+    /// Return true if the forwarder missed its heartbeats.
+    /// A forwarder never expires when the current time is not available.
+    pub fn is_expired(&self) -> bool {
+        match (self.expires_after, self.last_seen, Timestamp::now()) {
+            (Some(expires_after), Some(last_seen), Some(now)) => now
+                .elapsed(last_seen)
+                .map(|elapsed| elapsed > expires_after)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    pub(super) fn refresh(&mut self, forward_route: Route) {
+        self.forward_route = forward_route;
+        self.last_seen = Timestamp::now();
+    }
+}
+
+/// Forwarders created by a [`ForwardingService`](super::ForwardingService), indexed by alias
+#[derive(Clone, Default)]
+pub struct ForwarderRegistry {
+    entries: Arc<Mutex<BTreeMap<String, ForwarderEntry>>>,
+}
+
+impl ForwarderRegistry {
+    /// Return the forwarders which are not expired
+    pub fn list(&self) -> Vec<ForwarderEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|e| !e.is_expired())
+            .cloned()
+            .collect()
+    }
+
+    /// Return the forwarder registered under an alias, if it is not expired
+    pub fn get(&self, alias: &str) -> Option<ForwarderEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(alias)
+            .filter(|e| !e.is_expired())
+            .cloned()
+    }
+
+    /// Remove a forwarder and stop its worker
+    pub async fn delete(&self, ctx: &Context, alias: &str) -> Result<Option<ForwarderEntry>> {
+        let entry = self.entries.lock().unwrap().remove(alias);
+        if let Some(entry) = &entry {
+            ctx.stop_worker(entry.worker_address.clone()).await?;
+        }
+        Ok(entry)
+    }
+
+    /// Remove the expired forwarders and stop their workers
+    pub async fn remove_expired(&self, ctx: &Context) -> Result<()> {
+        let expired: Vec<ForwarderEntry> = {
+            let mut entries = self.entries.lock().unwrap();
+            let aliases: Vec<String> = entries
+                .values()
+                .filter(|e| e.is_expired())
+                .map(|e| e.alias.clone())
+                .collect();
+            aliases
+                .iter()
+                .filter_map(|alias| entries.remove(alias))
+                .collect()
+        };
+        for entry in expired {
+            info!("Forwarder {} expired", entry.alias);
+            ctx.stop_worker(entry.worker_address).await?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn insert(&self, entry: ForwarderEntry) {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(entry.alias.clone(), entry);
+    }
+
+    pub(super) fn refresh(&self, alias: &str, forward_route: Route) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(alias) {
+            entry.refresh(forward_route)
+        }
+    }
+
+    /// Return the current route of the forwarder with the given alias and worker address,
+    /// or `None` if that forwarder was deleted, replaced or is expired
+    pub(super) fn forward_route(&self, alias: &str, worker_address: &Address) -> Option<Route> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(alias)
+            .filter(|e| &e.worker_address == worker_address && !e.is_expired())
+            .map(|e| e.forward_route.clone())
+    }
+}
@@ -0,0 +1,220 @@
+use crate::forwarder::worker::{AllowForwardRoutes, Forwarder};
+use crate::forwarder::{
+    AliasAccessControl, ForwarderEntry, ForwarderRegistry, ForwardingServiceOptions,
+};
+use crate::Context;
+use core::time::Duration;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
+use ockam_core::{
+    Address, AllowSourceAddress, Any, Decodable, DenyAll, IncomingAccessControl, LocalMessage,
+    Mailbox, Mailboxes, RelayMessage, Result, Routed, TransportMessage, Worker,
+};
+use ockam_identity::IdentitySecureChannelLocalInfo;
+use ockam_node::{DelayedEvent, WorkerBuilder};
+use tracing::{debug, warn};
+
+/// Alias worker to register remote workers under local names.
+///
+/// To talk with this worker, you can use the
+/// [`RemoteForwarder`](crate::remote::RemoteForwarder) which is a
+/// compatible client for this server.
+///
+/// A static alias belongs to the identity which registered it over a secure channel:
+/// registering the same alias again only refreshes its route and expiration time when
+/// it is done by the same identity, and is answered like a new registration. Expired
+/// aliases are removed periodically.
+///
+/// An alias registered without a secure channel has no owner which could be
+/// authenticated: it can't be refreshed or taken over by another registration, and it
+/// doesn't expire, so that clients which don't send heartbeats keep working. It stays
+/// registered until it is deleted from the [`ForwarderRegistry`].
+#[non_exhaustive]
+pub struct ForwardingService {
+    forwarders_incoming_access_control: Arc<dyn IncomingAccessControl>,
+    alias_access_control: Option<Arc<dyn AliasAccessControl>>,
+    heartbeat_timeout: Option<Duration>,
+    registry: ForwarderRegistry,
+    sweep_address: Address,
+    sweep: Option<DelayedEvent<Vec<u8>>>,
+}
+
+impl ForwardingService {
+    /// Start a forwarding service
+    pub async fn create(
+        ctx: &Context,
+        address: impl Into<Address>,
+        service_incoming_access_control: impl IncomingAccessControl,
+        forwarders_incoming_access_control: impl IncomingAccessControl,
+    ) -> Result<()> {
+        let options = ForwardingServiceOptions::new()
+            .with_service_incoming_access_control(service_incoming_access_control)
+            .with_forwarders_incoming_access_control(forwarders_incoming_access_control);
+        Self::create_with_options(ctx, address, options).await
+    }
+
+    /// Start a forwarding service with the given options
+    pub async fn create_with_options(
+        ctx: &Context,
+        address: impl Into<Address>,
+        options: ForwardingServiceOptions,
+    ) -> Result<()> {
+        let main_mailbox = Mailbox::new(
+            address.into(),
+            options.service_incoming_access_control,
+            Arc::new(AllowForwardRoutes {
+                registry: options.registry.clone(),
+            }),
+        );
+        let mut additional_mailboxes = vec![];
+
+        // Expired forwarders are swept periodically, even when no registration is received
+        let sweep_address = Address::random_tagged("ForwardingService.sweep");
+        let sweep = match options.heartbeat_timeout {
+            Some(_) => {
+                let sweep = DelayedEvent::create(ctx, sweep_address.clone(), vec![]).await?;
+                additional_mailboxes.push(Mailbox::new(
+                    sweep_address.clone(),
+                    Arc::new(AllowSourceAddress(sweep.address())),
+                    Arc::new(DenyAll),
+                ));
+                Some(sweep)
+            }
+            None => None,
+        };
+
+        let s = Self {
+            forwarders_incoming_access_control: options.forwarders_incoming_access_control,
+            alias_access_control: options.alias_access_control,
+            heartbeat_timeout: options.heartbeat_timeout,
+            registry: options.registry,
+            sweep_address,
+            sweep,
+        };
+        WorkerBuilder::with_mailboxes(Mailboxes::new(main_mailbox, additional_mailboxes), s)
+            .start(ctx)
+            .await?;
+        Ok(())
+    }
+
+    /// Schedule the next sweep of the expired forwarders
+    async fn schedule_sweep(&mut self) -> Result<()> {
+        if let (Some(sweep), Some(heartbeat_timeout)) = (&mut self.sweep, self.heartbeat_timeout) {
+            sweep.schedule(heartbeat_timeout).await?;
+        }
+        Ok(())
+    }
+}
+
+#[crate::worker]
+impl Worker for ForwardingService {
+    type Context = Context;
+    type Message = Any;
+
+    async fn initialize(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+        self.schedule_sweep().await
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        self.registry.remove_expired(ctx).await?;
+
+        if msg.msg_addr() == self.sweep_address {
+            return self.schedule_sweep().await;
+        }
+
+        let forward_route = msg.return_route();
+        let payload = msg.payload().to_vec();
+
+        // An ephemeral forwarder gets a random alias which can't be claimed by anyone else,
+        // a static one is registered under the alias sent in the payload
+        let alias = match String::decode(&payload) {
+            Ok(alias) if alias != "register" => Some(alias),
+            _ => None,
+        };
+        let owner = IdentitySecureChannelLocalInfo::find_info(msg.local_message())
+            .map(|info| info.their_identity_id())
+            .ok();
+
+        let alias = match alias {
+            None => {
+                let address = Address::random_tagged("Forwarder.service");
+                let entry = ForwarderEntry::new(
+                    address.address().to_string(),
+                    owner,
+                    forward_route,
+                    address,
+                    None,
+                );
+                return Forwarder::create(
+                    ctx,
+                    entry,
+                    self.registry.clone(),
+                    payload,
+                    self.forwarders_incoming_access_control.clone(),
+                )
+                .await;
+            }
+            Some(alias) => alias,
+        };
+
+        if let Some(alias_access_control) = &self.alias_access_control {
+            let relay_msg =
+                RelayMessage::new(msg.src_addr(), msg.msg_addr(), msg.local_message().clone());
+            if !alias_access_control
+                .is_authorized(&alias, &relay_msg)
+                .await?
+            {
+                warn!("Registration of the alias {} was denied", alias);
+                return Ok(());
+            }
+        }
+
+        if let Some(entry) = self.registry.get(&alias) {
+            match (entry.owner(), owner.as_ref()) {
+                (Some(entry_owner), Some(owner)) if entry_owner == owner => {
+                    debug!("Refreshing the alias {}", alias);
+                    self.registry.refresh(&alias, forward_route.clone());
+                }
+                // The same client registering its alias again, there is nothing to refresh
+                (None, None) if entry.forward_route() == &forward_route => {}
+                _ => {
+                    warn!(
+                        "The alias {} is already registered by another client",
+                        alias
+                    );
+                    return Ok(());
+                }
+            }
+            // Answer as for a new registration, since the client may be creating its
+            // forwarder again. Heartbeats ignore this answer
+            let msg = TransportMessage::v1(forward_route, entry.worker_address().clone(), payload);
+            return ctx.forward(LocalMessage::new(msg, Vec::new())).await;
+        }
+
+        // The alias is free or its previous forwarder expired.
+        // Only the forwarders of an authenticated owner expire
+        self.registry.delete(ctx, &alias).await?;
+        let expires_after = owner.as_ref().and(self.heartbeat_timeout);
+        let entry = ForwarderEntry::new(
+            alias.clone(),
+            owner,
+            forward_route,
+            Address::from_string(alias),
+            expires_after,
+        );
+        Forwarder::create(
+            ctx,
+            entry,
+            self.registry.clone(),
+            payload,
+            self.forwarders_incoming_access_control.clone(),
+        )
+        .await
+    }
+}
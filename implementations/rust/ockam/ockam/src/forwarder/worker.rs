@@ -0,0 +1,165 @@
+use crate::forwarder::{ForwarderEntry, ForwarderRegistry};
+use crate::Context;
+use core::fmt::{self, Debug, Formatter};
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::{boxed::Box, vec::Vec};
+use ockam_core::{
+    async_trait, Any, IncomingAccessControl, LocalMessage, OutgoingAccessControl, RelayMessage,
+    Result, Routed, TransportMessage, Worker,
+};
+use ockam_node::WorkerBuilder;
+use tracing::{info, warn};
+
+pub(super) struct Forwarder {
+    alias: String,
+    registry: ForwarderRegistry,
+    // this option will be `None` after this worker is initialized, because
+    // while initializing, the worker will send the payload contained in this
+    // field to the forward route, to indicate a successful connection
+    payload: Option<Vec<u8>>,
+}
+
+impl Forwarder {
+    /// Start a forwarder for a registry entry and add that entry to the registry
+    pub(super) async fn create(
+        ctx: &Context,
+        entry: ForwarderEntry,
+        registry: ForwarderRegistry,
+        registration_payload: Vec<u8>,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Result<()> {
+        info!(
+            "Created new alias {} for {}",
+            entry.alias(),
+            entry.forward_route()
+        );
+
+        let alias = entry.alias().to_string();
+        let address = entry.worker_address().clone();
+        registry.insert(entry);
+
+        let forwarder = Self {
+            alias: alias.clone(),
+            registry: registry.clone(),
+            payload: Some(registration_payload),
+        };
+
+        WorkerBuilder::with_access_control(
+            incoming_access_control,
+            Arc::new(AllowForwardRoute { registry, alias }),
+            address,
+            forwarder,
+        )
+        .start(ctx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[crate::worker]
+impl Worker for Forwarder {
+    type Context = Context;
+    type Message = Any;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        let payload = self
+            .payload
+            .take()
+            .expect("payload must be available on init");
+        let forward_route = match self.registry.forward_route(&self.alias, &ctx.address()) {
+            Some(forward_route) => forward_route,
+            None => return Ok(()),
+        };
+        let msg = TransportMessage::v1(forward_route, ctx.address(), payload);
+
+        ctx.forward(LocalMessage::new(msg, Vec::new())).await?;
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        // The route is read for every message since the owner of the alias can update it
+        let forward_route = match self.registry.forward_route(&self.alias, &ctx.address()) {
+            Some(forward_route) => forward_route,
+            None => {
+                warn!("Forwarder {} is not registered anymore", self.alias);
+                return Ok(());
+            }
+        };
+
+        let mut message = msg.into_local_message();
+        let transport_message = message.transport_mut();
+
+        // Remove my address from the onward_route
+        transport_message.onward_route.step()?;
+
+        // Prepend forward route
+        transport_message
+            .onward_route
+            .modify()
+            .prepend_route(forward_route);
+
+        ctx.forward(message).await
+    }
+}
+
+/// Allow messages whose next hop is the next hop of the current route of a forwarder
+struct AllowForwardRoute {
+    registry: ForwarderRegistry,
+    alias: String,
+}
+
+impl Debug for AllowForwardRoute {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllowForwardRoute")
+            .field("alias", &self.alias)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl OutgoingAccessControl for AllowForwardRoute {
+    async fn is_authorized(&self, relay_msg: &RelayMessage) -> Result<bool> {
+        let forward_route = match self.registry.forward_route(&self.alias, relay_msg.source()) {
+            Some(forward_route) => forward_route,
+            None => return crate::deny(),
+        };
+
+        // Further hops are not checked
+        if forward_route.next()? != relay_msg.onward_route().next()? {
+            return crate::deny();
+        }
+
+        crate::allow()
+    }
+}
+
+/// Allow messages whose next hop is the next hop of the current route of any forwarder,
+/// so that a forwarding service can answer the registrations of forwarders
+pub(super) struct AllowForwardRoutes {
+    pub(super) registry: ForwarderRegistry,
+}
+
+impl Debug for AllowForwardRoutes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllowForwardRoutes").finish()
+    }
+}
+
+#[async_trait]
+impl OutgoingAccessControl for AllowForwardRoutes {
+    async fn is_authorized(&self, relay_msg: &RelayMessage) -> Result<bool> {
+        let next = relay_msg.onward_route().next()?;
+        for entry in self.registry.list() {
+            if entry.forward_route().next().ok() == Some(next) {
+                return crate::allow();
+            }
+        }
+        crate::deny()
+    }
+}
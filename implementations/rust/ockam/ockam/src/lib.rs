@@ -60,8 +60,8 @@ pub use ockam_macros::{node, test};
 
 // Export node implementation
 pub use ockam_node::{
-    debugger, Context, DelayedEvent, Executor, MessageReceiveOptions, MessageSendReceiveOptions,
-    NodeBuilder, WorkerBuilder,
+    debugger, Context, DelayedEvent, Executor, MessagePriority, MessageReceiveOptions,
+    MessageSendReceiveOptions, NodeBuilder, WorkerBuilder,
 };
 // ---
 
@@ -74,7 +74,10 @@ mod system;
 mod unique;
 
 pub use error::OckamError;
-pub use forwarder::ForwardingService;
+pub use forwarder::{
+    AliasAccessControl, ForwarderEntry, ForwarderRegistry, ForwardingService,
+    ForwardingServiceOptions,
+};
 pub use metadata::OckamMessage;
 pub use system::{SystemBuilder, SystemHandler, WorkerSystem};
 pub use unique::unique_with_prefix;
@@ -120,7 +120,7 @@ pub struct StreamMessage {
 
 /// The index return payload, to an
 /// [`IndexRequest`](super::requests::IndexRequest).
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Message)]
 pub struct IndexResponse {
     /// The client id
     pub client_id: String,
@@ -130,6 +130,27 @@ pub struct IndexResponse {
     pub index: Option<Uint>,
 }
 
+impl IndexResponse {
+    /// Create a [`ProtocolPayload`] responding to an
+    /// [`IndexRequest`](super::requests::IndexRequest).
+    //noinspection RsExternalLinter
+    #[allow(dead_code, clippy::new_ret_no_self)]
+    pub fn new<S: Into<String>>(
+        client_id: S,
+        stream_name: S,
+        index: Option<u64>,
+    ) -> ProtocolPayload {
+        ProtocolPayload::new(
+            "stream_index",
+            Self {
+                client_id: client_id.into(),
+                stream_name: stream_name.into(),
+                index: index.map(Uint::from),
+            },
+        )
+    }
+}
+
 /// A convenience enum to wrap all possible response types
 ///
 /// In your worker you will want to match this enum, given to you via
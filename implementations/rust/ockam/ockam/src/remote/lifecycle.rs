@@ -87,6 +87,28 @@ impl RemoteForwarder {
         hub_route: impl Into<Route>,
         alias: impl Into<String>,
         options: RemoteForwarderOptions,
+    ) -> Result<RemoteForwarderInfo> {
+        let registration_route = route![hub_route.into(), "static_forwarding_service"];
+        Self::create_static_with_heartbeats(ctx, registration_route, alias.into(), options).await
+    }
+
+    /// Create and start static RemoteForwarder at predefined address on a rust node.
+    /// The registration is refreshed with heartbeats so that the alias doesn't expire.
+    pub async fn create_static_at_rust_node(
+        ctx: &Context,
+        node_route: impl Into<Route>,
+        alias: impl Into<String>,
+        options: RemoteForwarderOptions,
+    ) -> Result<RemoteForwarderInfo> {
+        let registration_route = route![node_route.into(), "forwarding_service"];
+        Self::create_static_with_heartbeats(ctx, registration_route, alias.into(), options).await
+    }
+
+    async fn create_static_with_heartbeats(
+        ctx: &Context,
+        registration_route: Route,
+        alias: String,
+        options: RemoteForwarderOptions,
     ) -> Result<RemoteForwarderInfo> {
         let addresses = Addresses::generate(ForwardType::Static);
 
@@ -98,8 +120,6 @@ impl RemoteForwarder {
             ))
             .await?;
 
-        let registration_route = route![hub_route.into(), "static_forwarding_service"];
-
         let heartbeat = DelayedEvent::create(ctx, addresses.heartbeat.clone(), vec![]).await?;
         let heartbeat_source_address = heartbeat.address();
 
@@ -109,7 +129,7 @@ impl RemoteForwarder {
         let forwarder = Self::new(
             addresses.clone(),
             registration_route,
-            alias.into(),
+            alias,
             Some(heartbeat),
             Duration::from_secs(5),
         );
@@ -176,9 +196,10 @@ impl RemoteForwarder {
     }
 
     /// Create and start new static RemoteForwarder without heart beats
-    /// This is a temporary kind of RemoteForwarder that will only run on
-    /// rust nodes (hence the `forwarding_service` addr to create static forwarders).
-    /// We will use it while we don't have heartbeats implemented on rust nodes.
+    /// This kind of RemoteForwarder will only run on rust nodes (hence the
+    /// `forwarding_service` addr to create static forwarders).
+    /// When it is registered over a secure channel, its alias expires on nodes which
+    /// require heartbeats, see [`RemoteForwarder::create_static_at_rust_node`].
     pub async fn create_static_without_heartbeats(
         ctx: &Context,
         hub_route: impl Into<Route>,
@@ -0,0 +1,91 @@
+use crate::protocols::stream::{requests::*, responses::*};
+use crate::protocols::{ProtocolParser, ProtocolPayload};
+use crate::{Context, OckamError};
+use ockam_core::compat::{boxed::Box, collections::BTreeMap, string::String, sync::Arc};
+use ockam_core::{
+    Address, AllowAll, Any, Decodable, IncomingAccessControl, Result, Routed, Worker,
+};
+use ockam_node::{KeyValueStorage, WorkerBuilder};
+
+/// Consumer indices of a stream, by client id
+pub type StreamIndices = BTreeMap<String, u64>;
+
+/// Index service tracking the position of each consumer in a stream
+///
+/// This is the service that a [`Stream`](crate::stream::Stream) consumer
+/// asks for its last saved index when it starts, and which it updates once
+/// it has received messages. Indices are kept in a [`KeyValueStorage`]
+/// keyed by stream name, use a
+/// [`FileKeyValueStorage`](ockam_node::FileKeyValueStorage) to persist
+/// them across restarts.
+pub struct IndexService {
+    storage: Arc<dyn KeyValueStorage<String, StreamIndices>>,
+}
+
+impl IndexService {
+    /// Start an index service storing the consumer indices in `storage`
+    pub async fn create(
+        ctx: &Context,
+        address: impl Into<Address>,
+        storage: Arc<dyn KeyValueStorage<String, StreamIndices>>,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Result<()> {
+        WorkerBuilder::with_access_control(
+            incoming_access_control,
+            Arc::new(AllowAll),
+            address.into(),
+            Self { storage },
+        )
+        .start(ctx)
+        .await?;
+        Ok(())
+    }
+}
+
+#[crate::worker]
+impl Worker for IndexService {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let return_route = msg.return_route();
+        let pp = ProtocolPayload::decode(msg.payload())?;
+        if !Request::check_id(pp.protocol.as_str()) {
+            return Err(OckamError::NoSuchProtocol.into());
+        }
+
+        match Request::parse(pp)? {
+            Request::Index(IndexRequest::Get {
+                client_id,
+                stream_name,
+            }) => {
+                let index = self
+                    .storage
+                    .get(&stream_name)
+                    .await?
+                    .and_then(|indices| indices.get(&client_id).copied());
+                debug!(
+                    "Index of client '{}' for stream '{}': {:?}",
+                    client_id, stream_name, index
+                );
+                ctx.send(
+                    return_route,
+                    IndexResponse::new(client_id, stream_name, index),
+                )
+                .await
+            }
+            // Saving an index is not acknowledged, consumers keep pulling
+            // from the index they saved
+            Request::Index(IndexRequest::Save {
+                client_id,
+                stream_name,
+                index,
+            }) => {
+                let mut indices = self.storage.get(&stream_name).await?.unwrap_or_default();
+                indices.insert(client_id, index.u64());
+                self.storage.put(stream_name, indices).await
+            }
+            _ => Err(OckamError::NoSuchProtocol.into()),
+        }
+    }
+}
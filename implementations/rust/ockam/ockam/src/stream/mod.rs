@@ -8,6 +8,19 @@ use consumer::StreamConsumer;
 mod producer;
 use producer::StreamProducer;
 
+#[cfg(feature = "std")]
+mod index_service;
+#[cfg(feature = "std")]
+mod service;
+#[cfg(feature = "std")]
+mod storage;
+#[cfg(feature = "std")]
+pub use index_service::*;
+#[cfg(feature = "std")]
+pub use service::{is_valid_stream_name, StreamService};
+#[cfg(feature = "std")]
+pub use storage::StreamStorageOptions;
+
 use crate::{
     protocols::stream::responses::*, Address, Context, Message, Result, Route, Routed,
     TransportMessage,
@@ -159,7 +172,8 @@ impl Stream {
     ///
     /// The `route` parameter is the route to a remote which hosts a
     /// `stream_service` and `stream_index_service`, such as
-    /// hub.ockam.io.
+    /// hub.ockam.io, or a node running a [`StreamService`] and an
+    /// [`IndexService`].
     ///
     /// Streams that do not already exists will be created, and
     /// existing stream identifiers will automatically be re-used.
@@ -0,0 +1,207 @@
+use crate::protocols::stream::{requests::*, responses::*};
+use crate::protocols::{ProtocolParser, ProtocolPayload};
+use crate::stream::storage::{StreamStorage, StreamStorageOptions};
+use crate::{Context, OckamError};
+use ockam_core::compat::rand::{self, Rng};
+use ockam_core::compat::{boxed::Box, collections::BTreeMap, string::String, sync::Arc};
+use ockam_core::{
+    route, Address, AllowAll, Any, Decodable, IncomingAccessControl, Result, Routed, Worker,
+};
+use ockam_node::WorkerBuilder;
+use std::path::PathBuf;
+
+/// Stream service storing the messages of each stream on disk
+///
+/// This is a local implementation of the service that
+/// [`Stream`](crate::stream::Stream) producers and consumers talk to.
+/// A [`CreateStreamRequest`] starts a worker dedicated to the requested
+/// stream, or re-uses the existing one, which then serves the push and
+/// pull requests of that stream. The messages of a stream are appended to
+/// segment files in a sub-directory named after the stream.
+pub struct StreamService {
+    path: PathBuf,
+    options: StreamStorageOptions,
+    streams: BTreeMap<String, Address>,
+    incoming_access_control: Arc<dyn IncomingAccessControl>,
+}
+
+impl StreamService {
+    /// Start a stream service storing its streams in the directory at `path`
+    pub async fn create(
+        ctx: &Context,
+        address: impl Into<Address>,
+        path: impl Into<PathBuf>,
+        options: StreamStorageOptions,
+        incoming_access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Result<()> {
+        let s = Self {
+            path: path.into(),
+            options,
+            streams: BTreeMap::new(),
+            incoming_access_control: incoming_access_control.clone(),
+        };
+        WorkerBuilder::with_access_control(
+            incoming_access_control,
+            Arc::new(AllowAll),
+            address.into(),
+            s,
+        )
+        .start(ctx)
+        .await?;
+        Ok(())
+    }
+
+    /// Return the address of the worker serving a stream, starting it if necessary
+    async fn stream_worker(&mut self, ctx: &Context, stream_name: &str) -> Result<Address> {
+        if let Some(address) = self.streams.get(stream_name) {
+            return Ok(address.clone());
+        }
+
+        let storage =
+            StreamStorage::open(self.path.join(stream_name), self.options.clone()).await?;
+        let address = Address::random_tagged("StreamService.stream");
+
+        // The stream worker is reachable from wherever the service is
+        let flow_controls = ctx.flow_controls();
+        for flow_control_id in flow_controls.get_flow_controls_with_consumer(&ctx.address()) {
+            if let Some(policy) = flow_controls
+                .get_consumers_info(&flow_control_id)
+                .consumers()
+                .get(&ctx.address())
+            {
+                flow_controls.add_consumer(&address, &flow_control_id, *policy);
+            }
+        }
+
+        let worker = StreamWorker {
+            stream_name: stream_name.into(),
+            storage,
+        };
+        WorkerBuilder::with_access_control(
+            self.incoming_access_control.clone(),
+            Arc::new(AllowAll),
+            address.clone(),
+            worker,
+        )
+        .start(ctx)
+        .await?;
+
+        info!("Opened stream '{}' at {}", stream_name, address);
+        self.streams.insert(stream_name.into(), address.clone());
+
+        Ok(address)
+    }
+}
+
+/// Stream names are used as directory names, so they are restricted to a
+/// safe set of characters
+pub fn is_valid_stream_name(stream_name: &str) -> bool {
+    !stream_name.is_empty()
+        && !stream_name.starts_with('.')
+        && stream_name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+#[crate::worker]
+impl Worker for StreamService {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let pp = ProtocolPayload::decode(msg.payload())?;
+        if !Request::check_id(pp.protocol.as_str()) {
+            return Err(OckamError::NoSuchProtocol.into());
+        }
+
+        let stream_name = match Request::parse(pp)? {
+            Request::Create(CreateStreamRequest { stream_name }) => {
+                stream_name.unwrap_or_else(|| {
+                    let random: [u8; 16] = rand::thread_rng().gen();
+                    hex::encode(random)
+                })
+            }
+            _ => {
+                warn!("Stream service only handles stream creation requests");
+                return Err(OckamError::NoSuchProtocol.into());
+            }
+        };
+
+        if !is_valid_stream_name(&stream_name) {
+            warn!("Invalid stream name '{}'", stream_name);
+            return Err(OckamError::InvalidParameter.into());
+        }
+
+        // Let the stream worker answer, so that the requester learns its route
+        let address = self.stream_worker(ctx, &stream_name).await?;
+        let mut message = msg.into_local_message();
+        message.transport_mut().onward_route = route![address];
+        ctx.forward(message).await
+    }
+}
+
+/// Worker serving the requests of a single stream
+struct StreamWorker {
+    stream_name: String,
+    storage: StreamStorage,
+}
+
+#[crate::worker]
+impl Worker for StreamWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let return_route = msg.return_route();
+        let pp = ProtocolPayload::decode(msg.payload())?;
+        if !Request::check_id(pp.protocol.as_str()) {
+            return Err(OckamError::NoSuchProtocol.into());
+        }
+
+        match Request::parse(pp)? {
+            Request::Create(_) => {
+                ctx.send(return_route, InitResponse::new(self.stream_name.clone()))
+                    .await
+            }
+            Request::Push(PushRequest { request_id, data }) => {
+                let response = match self.storage.append(data).await {
+                    Ok(index) => PushConfirm::new(request_id.u64(), Status::Ok, index),
+                    Err(err) => {
+                        error!(
+                            "Failed to append message to stream '{}': {}",
+                            self.stream_name, err
+                        );
+                        PushConfirm::new(request_id.u64(), Status::Error, 0)
+                    }
+                };
+                ctx.send(return_route, response).await
+            }
+            Request::Pull(PullRequest {
+                request_id,
+                index,
+                limit,
+            }) => {
+                // The pull protocol has no status, a failed read is answered without
+                // any message so that the consumer pulls again
+                let messages = match self.storage.read(index.u64(), limit.u64()).await {
+                    Ok(messages) => messages,
+                    Err(err) => {
+                        error!(
+                            "Failed to read messages from stream '{}': {}",
+                            self.stream_name, err
+                        );
+                        vec![]
+                    }
+                };
+                trace!(
+                    "Pulled {} message(s) from stream '{}'",
+                    messages.len(),
+                    self.stream_name
+                );
+                ctx.send(return_route, PullResponse::new(request_id.u64(), messages))
+                    .await
+            }
+            Request::Index(_) => Err(OckamError::NoSuchProtocol.into()),
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use crate::protocols::stream::responses::StreamMessage;
+use core::time::Duration;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+use ockam_node::tokio::task::{self, JoinError};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Extension of the files containing the messages of a stream
+const SEGMENT_EXTENSION: &str = "segment";
+
+/// Segmentation and retention settings of the streams stored by a
+/// [`StreamService`](crate::stream::StreamService)
+///
+/// Messages are appended to segment files which are rolled over once they
+/// reach [`segment_size`](Self::with_segment_size) bytes. Retention limits
+/// are applied by removing whole segments, the segment currently being
+/// written to is never removed.
+#[derive(Clone, Debug)]
+pub struct StreamStorageOptions {
+    pub(crate) segment_size: u64,
+    pub(crate) max_age: Option<Duration>,
+    pub(crate) max_size: Option<u64>,
+}
+
+impl StreamStorageOptions {
+    /// Segments of 16 MiB, messages are kept forever
+    pub fn new() -> Self {
+        Self {
+            segment_size: 16 * 1024 * 1024,
+            max_age: None,
+            max_size: None,
+        }
+    }
+
+    /// Size in bytes after which a new segment is started
+    pub fn with_segment_size(mut self, segment_size: u64) -> Self {
+        self.segment_size = segment_size;
+        self
+    }
+
+    /// Remove segments whose last message is older than `max_age`
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Remove the oldest segments while a stream takes more than `max_size` bytes
+    pub fn with_max_size(mut self, max_size: u64) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+}
+
+impl Default for StreamStorageOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A segment file, containing consecutive messages starting at `base`
+///
+/// Each message is stored as a little endian `u32` length followed by the
+/// message data.
+struct Segment {
+    base: u64,
+    count: u64,
+    size: u64,
+    path: PathBuf,
+}
+
+impl Segment {
+    fn path(dir: &Path, base: u64) -> PathBuf {
+        dir.join(format!("{:020}.{}", base, SEGMENT_EXTENSION))
+    }
+
+    /// Count the messages of an existing segment, dropping a trailing
+    /// message which was only partially written
+    fn load(path: PathBuf, base: u64) -> Result<Self> {
+        let file_len = fs::metadata(&path).map(|m| m.len()).map_err(map_io_err)?;
+        let mut reader = BufReader::new(File::open(&path).map_err(map_io_err)?);
+        let mut count = 0;
+        let mut size = 0;
+        while let Some(len) = read_len(&mut reader)? {
+            if size + 4 + len > file_len {
+                break;
+            }
+            reader.seek_relative(len as i64).map_err(map_io_err)?;
+            count += 1;
+            size += 4 + len;
+        }
+
+        if size < file_len {
+            warn!(
+                "Truncating partially written message at the end of {}",
+                path.display()
+            );
+            OpenOptions::new()
+                .write(true)
+                .open(&path)
+                .and_then(|f| f.set_len(size))
+                .map_err(map_io_err)?;
+        }
+
+        Ok(Self {
+            base,
+            count,
+            size,
+            path,
+        })
+    }
+
+    fn next_index(&self) -> u64 {
+        self.base + self.count
+    }
+}
+
+/// Append-only storage of the messages of a single stream
+///
+/// The segment files are accessed on the blocking thread pool, so that
+/// slow disks don't hold the async runtime.
+pub(crate) struct StreamStorage {
+    segments: Arc<Mutex<Segments>>,
+}
+
+impl StreamStorage {
+    /// Open the stream stored in the given directory, creating it if necessary
+    pub(crate) async fn open(path: PathBuf, options: StreamStorageOptions) -> Result<Self> {
+        let segments = task::spawn_blocking(move || Segments::open(path, options))
+            .await
+            .map_err(map_join_err)??;
+        Ok(Self {
+            segments: Arc::new(Mutex::new(segments)),
+        })
+    }
+
+    /// Append a message and return its index
+    pub(crate) async fn append(&self, data: Vec<u8>) -> Result<u64> {
+        self.with_segments(move |segments| segments.append(&data))
+            .await
+    }
+
+    /// Read up to `limit` messages starting at `index`, once the retention
+    /// limits are applied
+    ///
+    /// Zero is used as a sentinel to indicate all messages.
+    pub(crate) async fn read(&self, index: u64, limit: u64) -> Result<Vec<StreamMessage>> {
+        self.with_segments(move |segments| {
+            segments.apply_retention()?;
+            segments.read(index, limit)
+        })
+        .await
+    }
+
+    async fn with_segments<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Segments) -> Result<R> + Send + 'static,
+    ) -> Result<R> {
+        let segments = self.segments.clone();
+        task::spawn_blocking(move || f(&mut segments.lock().unwrap()))
+            .await
+            .map_err(map_join_err)?
+    }
+}
+
+/// Segment files of a stream
+struct Segments {
+    path: PathBuf,
+    options: StreamStorageOptions,
+    /// Segments ordered by base index, the last one is being written to
+    segments: Vec<Segment>,
+}
+
+impl Segments {
+    /// Open the stream stored in the given directory, creating it if necessary
+    fn open(path: PathBuf, options: StreamStorageOptions) -> Result<Self> {
+        fs::create_dir_all(&path).map_err(map_io_err)?;
+
+        let mut bases = Vec::new();
+        for entry in fs::read_dir(&path).map_err(map_io_err)? {
+            let entry_path = entry.map_err(map_io_err)?.path();
+            if entry_path.extension().and_then(|e| e.to_str()) != Some(SEGMENT_EXTENSION) {
+                continue;
+            }
+            if let Some(base) = entry_path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.parse::<u64>().ok())
+            {
+                bases.push(base);
+            }
+        }
+        bases.sort_unstable();
+
+        let mut segments = Vec::with_capacity(bases.len());
+        for base in bases {
+            segments.push(Segment::load(Segment::path(&path, base), base)?);
+        }
+
+        let mut storage = Self {
+            path,
+            options,
+            segments,
+        };
+        storage.apply_retention()?;
+
+        Ok(storage)
+    }
+
+    /// Index of the oldest message still stored
+    fn first_index(&self) -> u64 {
+        self.segments.first().map(|s| s.base).unwrap_or(0)
+    }
+
+    /// Index that the next appended message will have
+    fn next_index(&self) -> u64 {
+        self.segments.last().map(Segment::next_index).unwrap_or(0)
+    }
+
+    /// Append a message and return its index
+    fn append(&mut self, data: &[u8]) -> Result<u64> {
+        let len = u32::try_from(data.len())
+            .map_err(|_| Error::new(Origin::Ockam, Kind::Invalid, "stream message is too large"))?;
+
+        let roll_over = match self.segments.last() {
+            Some(segment) => segment.size > 0 && segment.size >= self.options.segment_size,
+            None => true,
+        };
+        if roll_over {
+            let base = self.next_index();
+            let path = Segment::path(&self.path, base);
+            File::create(&path).map_err(map_io_err)?;
+            self.segments.push(Segment {
+                base,
+                count: 0,
+                size: 0,
+                path,
+            });
+            self.apply_retention()?;
+        }
+
+        let segment = self
+            .segments
+            .last_mut()
+            .expect("a segment was just created");
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&segment.path)
+            .map_err(map_io_err)?;
+        let mut record = Vec::with_capacity(4 + data.len());
+        record.extend_from_slice(&len.to_le_bytes());
+        record.extend_from_slice(data);
+        file.write_all(&record).map_err(map_io_err)?;
+        file.sync_data().map_err(map_io_err)?;
+
+        let index = segment.next_index();
+        segment.count += 1;
+        segment.size += record.len() as u64;
+
+        Ok(index)
+    }
+
+    /// Read up to `limit` messages starting at `index`
+    ///
+    /// Zero is used as a sentinel to indicate all messages. Messages which
+    /// were removed by the retention policy are skipped.
+    fn read(&self, index: u64, limit: u64) -> Result<Vec<StreamMessage>> {
+        let limit = if limit == 0 { u64::MAX } else { limit };
+        let mut index = index.max(self.first_index());
+        let mut messages = Vec::new();
+
+        for segment in &self.segments {
+            if messages.len() as u64 >= limit {
+                break;
+            }
+            if segment.next_index() <= index {
+                continue;
+            }
+
+            let mut reader = BufReader::new(File::open(&segment.path).map_err(map_io_err)?);
+            for _ in segment.base..index {
+                let len = read_len(&mut reader)?.ok_or_else(truncated_segment)?;
+                reader.seek_relative(len as i64).map_err(map_io_err)?;
+            }
+            while index < segment.next_index() && (messages.len() as u64) < limit {
+                let len = read_len(&mut reader)?.ok_or_else(truncated_segment)?;
+                let mut data = vec![0; len as usize];
+                reader.read_exact(&mut data).map_err(map_io_err)?;
+                messages.push(StreamMessage {
+                    index: index.into(),
+                    data,
+                });
+                index += 1;
+            }
+        }
+
+        Ok(messages)
+    }
+
+    /// Remove the oldest segments exceeding the retention limits
+    fn apply_retention(&mut self) -> Result<()> {
+        let now = SystemTime::now();
+        let mut total_size: u64 = self.segments.iter().map(|s| s.size).sum();
+
+        while self.segments.len() > 1 {
+            let oldest = &self.segments[0];
+            let too_large = self.options.max_size.map_or(false, |max| total_size > max);
+            let too_old = match self.options.max_age {
+                Some(max_age) => fs::metadata(&oldest.path)
+                    .and_then(|m| m.modified())
+                    .map(|modified| now.duration_since(modified).unwrap_or_default() > max_age)
+                    .map_err(map_io_err)?,
+                None => false,
+            };
+            if !too_large && !too_old {
+                break;
+            }
+
+            debug!(
+                "Removing segment {} holding messages {}..{}",
+                oldest.path.display(),
+                oldest.base,
+                oldest.next_index()
+            );
+            fs::remove_file(&oldest.path).map_err(map_io_err)?;
+            total_size -= oldest.size;
+            self.segments.remove(0);
+        }
+
+        Ok(())
+    }
+}
+
+/// Read the length of the next message, or `None` at the end of the segment
+fn read_len(reader: &mut impl Read) -> Result<Option<u64>> {
+    let mut len = [0u8; 4];
+    match reader.read_exact(&mut len) {
+        Ok(()) => Ok(Some(u32::from_le_bytes(len) as u64)),
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(err) => Err(map_io_err(err)),
+    }
+}
+
+fn truncated_segment() -> Error {
+    Error::new(Origin::Ockam, Kind::Io, "stream segment is truncated")
+}
+
+fn map_join_err(err: JoinError) -> Error {
+    Error::new(Origin::Ockam, Kind::Io, err)
+}
+
+fn map_io_err(err: io::Error) -> Error {
+    Error::new(Origin::Ockam, Kind::Io, err)
+}
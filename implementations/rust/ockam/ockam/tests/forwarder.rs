@@ -1,6 +1,6 @@
 use ockam::remote::{RemoteForwarder, RemoteForwarderOptions};
 use ockam::workers::Echoer;
-use ockam::ForwardingService;
+use ockam::{ForwarderRegistry, ForwardingService, ForwardingServiceOptions};
 use ockam_core::flow_control::{FlowControlPolicy, FlowControls};
 use ockam_core::{route, Address, AllowAll, Result};
 use ockam_identity::{secure_channels, SecureChannelListenerOptions, SecureChannelOptions};
@@ -312,3 +312,237 @@ async fn test4(ctx: &mut Context) -> Result<()> {
 
     ctx.stop().await
 }
+
+// Node creates a Forwarding service and a static Remote Forwarder, the alias is listed in the
+// registry, refreshed by heartbeats, and the Forwarder is stopped when the alias is deleted
+#[ockam_macros::test]
+async fn test5(ctx: &mut Context) -> Result<()> {
+    let registry = ForwarderRegistry::default();
+    let options = ForwardingServiceOptions::new()
+        .with_heartbeat_timeout(Duration::from_secs(15))
+        .with_registry(registry.clone());
+    ForwardingService::create_with_options(ctx, "forwarding_service", options).await?;
+
+    ctx.start_worker("echoer", Echoer, AllowAll, AllowAll)
+        .await?;
+
+    let remote_info = RemoteForwarder::create_static_at_rust_node(
+        ctx,
+        route![],
+        "echoer_alias",
+        RemoteForwarderOptions::new(),
+    )
+    .await?;
+    assert_eq!(remote_info.remote_address(), "echoer_alias");
+
+    let entries = registry.list();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].alias(), "echoer_alias");
+    // an alias registered without a secure channel has no owner and doesn't expire
+    assert!(entries[0].owner().is_none());
+    assert_eq!(entries[0].expires_after(), None);
+
+    let resp = ctx
+        .send_and_receive::<String>(route!["echoer_alias", "echoer"], "Hello".to_string())
+        .await?;
+    assert_eq!(resp, "Hello");
+
+    assert!(registry.delete(ctx, "echoer_alias").await?.is_some());
+    assert!(registry.list().is_empty());
+
+    let res = ctx
+        .send_and_receive_extended::<String>(
+            route!["echoer_alias", "echoer"],
+            "Hello".to_string(),
+            MessageSendReceiveOptions::new().with_timeout(Duration::from_secs(1)),
+        )
+        .await;
+    assert!(res.is_err());
+
+    ctx.stop().await
+}
+
+// Two clients register the same alias without secure channels: the second registration
+// doesn't take over the alias of the first client
+#[ockam_macros::test]
+async fn test6(ctx: &mut Context) -> Result<()> {
+    let registry = ForwarderRegistry::default();
+    let options = ForwardingServiceOptions::new()
+        .with_heartbeat_timeout(Duration::from_secs(15))
+        .with_registry(registry.clone());
+    ForwardingService::create_with_options(ctx, "forwarding_service", options).await?;
+    let cloud_tcp = TcpTransport::create(ctx).await?;
+    let (socket_addr, _) = cloud_tcp
+        .listen("127.0.0.1:0", TcpListenerOptions::new())
+        .await?;
+
+    // First client
+    ctx.start_worker("echoer", Echoer, AllowAll, AllowAll)
+        .await?;
+    let first_tcp = TcpTransport::create(ctx).await?;
+    let first_connection = first_tcp
+        .connect(socket_addr.to_string(), TcpConnectionOptions::new())
+        .await?;
+    RemoteForwarder::create_static_at_rust_node(
+        ctx,
+        first_connection,
+        "echoer_alias",
+        RemoteForwarderOptions::new(),
+    )
+    .await?;
+    let first_route = registry
+        .get("echoer_alias")
+        .unwrap()
+        .forward_route()
+        .clone();
+
+    // Second client
+    let second_tcp = TcpTransport::create(ctx).await?;
+    let second_connection = second_tcp
+        .connect(socket_addr.to_string(), TcpConnectionOptions::new())
+        .await?;
+    let mut second_client = ctx
+        .new_detached("second_client", AllowAll, AllowAll)
+        .await?;
+    second_client
+        .send(
+            route![second_connection, "forwarding_service"],
+            "echoer_alias".to_string(),
+        )
+        .await?;
+
+    // The registration is not acknowledged and the alias still leads to the first client
+    let res = second_client
+        .receive_extended::<String>(
+            MessageReceiveOptions::new().with_timeout(Duration::from_secs(1)),
+        )
+        .await;
+    assert!(res.is_err());
+
+    let entry = registry.get("echoer_alias").unwrap();
+    assert_eq!(entry.forward_route(), &first_route);
+
+    let resp = ctx
+        .send_and_receive::<String>(route!["echoer_alias", "echoer"], "Hello".to_string())
+        .await?;
+    assert_eq!(resp, "Hello");
+
+    ctx.stop().await
+}
+
+// An alias registered over a secure channel is removed once it isn't refreshed anymore,
+// without waiting for another registration
+#[ockam_macros::test]
+async fn test7(ctx: &mut Context) -> Result<()> {
+    let registry = ForwarderRegistry::default();
+    let options = ForwardingServiceOptions::new()
+        .with_heartbeat_timeout(Duration::from_secs(1))
+        .with_registry(registry.clone());
+    ForwardingService::create_with_options(ctx, "forwarding_service", options).await?;
+
+    let secure_channels = secure_channels();
+    let identity = secure_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?;
+    secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &identity.identifier(),
+            "listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+    let channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            &identity.identifier(),
+            route!["listener"],
+            SecureChannelOptions::new(),
+        )
+        .await?;
+
+    // Register the alias once, without heartbeats
+    let mut client = ctx.new_detached("client", AllowAll, AllowAll).await?;
+    client
+        .send(route![channel, "forwarding_service"], "alias".to_string())
+        .await?;
+    client.receive::<String>().await?;
+
+    let entry = registry.get("alias").unwrap();
+    assert_eq!(entry.owner(), Some(&identity.identifier()));
+    assert!(ctx.list_workers().await?.contains(&"alias".into()));
+
+    ctx.sleep(Duration::from_secs(3)).await;
+    assert!(registry.list().is_empty());
+    assert!(!ctx.list_workers().await?.contains(&"alias".into()));
+
+    ctx.stop().await
+}
+
+// The owner of an alias creates its forwarder again, for example after a restart:
+// the registration is answered and the alias leads to the new forwarder
+#[ockam_macros::test]
+async fn test8(ctx: &mut Context) -> Result<()> {
+    let registry = ForwarderRegistry::default();
+    let options = ForwardingServiceOptions::new()
+        .with_heartbeat_timeout(Duration::from_secs(15))
+        .with_registry(registry.clone());
+    ForwardingService::create_with_options(ctx, "forwarding_service", options).await?;
+
+    ctx.start_worker("echoer", Echoer, AllowAll, AllowAll)
+        .await?;
+
+    let secure_channels = secure_channels();
+    let identity = secure_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?;
+    secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &identity.identifier(),
+            "listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+    let channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            &identity.identifier(),
+            route!["listener"],
+            SecureChannelOptions::new(),
+        )
+        .await?;
+
+    let first = RemoteForwarder::create_static_at_rust_node(
+        ctx,
+        route![channel.clone()],
+        "echoer_alias",
+        RemoteForwarderOptions::new(),
+    )
+    .await?;
+    let second = RemoteForwarder::create_static_at_rust_node(
+        ctx,
+        route![channel],
+        "echoer_alias",
+        RemoteForwarderOptions::new(),
+    )
+    .await?;
+    assert_eq!(first.remote_address(), "echoer_alias");
+    assert_eq!(second.remote_address(), "echoer_alias");
+    assert_ne!(first.worker_address(), second.worker_address());
+
+    let entries = registry.list();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].owner(), Some(&identity.identifier()));
+
+    let resp = ctx
+        .send_and_receive::<String>(route!["echoer_alias", "echoer"], "Hello".to_string())
+        .await?;
+    assert_eq!(resp, "Hello");
+
+    ctx.stop().await
+}
@@ -0,0 +1,97 @@
+use ockam::stream::{IndexService, Stream, StreamService, StreamStorageOptions};
+use ockam::Context;
+use ockam_core::compat::rand::{thread_rng, RngCore};
+use ockam_core::{route, AllowAll, Result};
+use ockam_node::FileKeyValueStorage;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn create_temp_dir() -> PathBuf {
+    let mut bytes = [0u8; 16];
+    thread_rng().fill_bytes(&mut bytes);
+    let dir = std::env::temp_dir().join(hex::encode(bytes));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+async fn start_services(ctx: &Context, dir: &Path) -> Result<()> {
+    StreamService::create(
+        ctx,
+        "stream",
+        dir.join("streams"),
+        StreamStorageOptions::new().with_segment_size(64),
+        Arc::new(AllowAll),
+    )
+    .await?;
+    let indices = FileKeyValueStorage::create(dir.join("indices.json").as_path()).await?;
+    IndexService::create(ctx, "stream_index", Arc::new(indices), Arc::new(AllowAll)).await
+}
+
+#[ockam::test]
+async fn stream_service_send_and_receive(ctx: &mut Context) -> Result<()> {
+    let dir = create_temp_dir();
+    start_services(ctx, &dir).await?;
+
+    let (tx, mut rx) = Stream::new(ctx)
+        .await?
+        .with_interval(Duration::from_millis(50))
+        .connect(route![], "test-stream", "test-stream")
+        .await?;
+
+    for i in 0..5 {
+        ctx.send(tx.to_route(), format!("Hello {}", i)).await?;
+    }
+
+    for i in 0..5 {
+        let msg = rx.next::<String>().await?;
+        assert_eq!(msg.body(), format!("Hello {}", i));
+    }
+
+    let _ = std::fs::remove_dir_all(dir);
+    ctx.stop().await
+}
+
+#[ockam::test]
+async fn stream_service_restore_consumer_index(ctx: &mut Context) -> Result<()> {
+    let dir = create_temp_dir();
+    start_services(ctx, &dir).await?;
+
+    let (tx, mut rx) = Stream::new(ctx)
+        .await?
+        .with_interval(Duration::from_millis(50))
+        .client_id("client")
+        .connect(route![], "requests", "requests")
+        .await?;
+
+    ctx.send(tx.to_route(), "first".to_string()).await?;
+    assert_eq!(rx.next::<String>().await?.body(), "first");
+
+    // Let the consumer save its index
+    ctx.sleep(Duration::from_millis(250)).await;
+
+    // A consumer with the same client id resumes after the saved index
+    let (_, mut resumed_rx) = Stream::new(ctx)
+        .await?
+        .with_interval(Duration::from_millis(50))
+        .client_id("client")
+        .connect(route![], "responses", "requests")
+        .await?;
+
+    ctx.send(tx.to_route(), "second".to_string()).await?;
+    assert_eq!(resumed_rx.next::<String>().await?.body(), "second");
+
+    // A consumer with a new client id starts from the beginning of the stream
+    let (_, mut new_rx) = Stream::new(ctx)
+        .await?
+        .with_interval(Duration::from_millis(50))
+        .client_id("other-client")
+        .connect(route![], "responses", "requests")
+        .await?;
+
+    assert_eq!(new_rx.next::<String>().await?.body(), "first");
+    assert_eq!(new_rx.next::<String>().await?.body(), "second");
+
+    let _ = std::fs::remove_dir_all(dir);
+    ctx.stop().await
+}
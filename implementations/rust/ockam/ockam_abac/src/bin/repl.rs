@@ -1,6 +1,7 @@
 use ockam_abac::{eval, parse, Env, Expr};
 use rustyline::error::ReadlineError;
 use rustyline::highlight::MatchingBracketHighlighter;
+use rustyline::history::DefaultHistory;
 use rustyline::validate::MatchingBracketValidator;
 use rustyline::{Config, EditMode, Editor, Result};
 use rustyline_derive::{Completer, Helper, Highlighter, Hinter, Validator};
@@ -9,8 +10,20 @@ const HELP: &str = r#"Available commands:
   :def <id> <expression>  -- Add an expression to the environment.
   :env                    -- Show all current environment entries.
   :clear                  -- Remove all bindings from the environment.
+  :builtins               -- Show the available operators.
   :help | :h | :?         -- Show this help message."#;
 
+const BUILTINS: &str = r#"Available operators:
+  (and <bool> ...) (or <bool> ...) (not <bool>) (if <test> <then> <else>)
+  (= <x> <y> ...) (!= <x> <y> ...) (< <x> <y> ...) (> <x> <y> ...)
+  (member? <x> <seq>) (exists? <id> ...)
+  (starts-with? <str> <prefix>) (ends-with? <str> <suffix>)
+  (contains? <str> <substring>) (matches? <str> <regex>)
+  (subset? <set> <set>) (intersect? <set> <set>)
+    -- sets are sequences or comma-separated strings, e.g. "admin,dev"
+  (number <str>)  -- Parse a string into an integer or a float.
+  (now)           -- The current time in seconds since the unix epoch."#;
+
 #[derive(Completer, Helper, Highlighter, Hinter, Validator)]
 struct ReplHelper {
     #[rustyline(Highlighter)]
@@ -27,7 +40,7 @@ fn main() -> Result<()> {
         .build();
 
     let mut env = Env::new();
-    let mut repl = Editor::<ReplHelper>::with_config(c)?;
+    let mut repl = Editor::<ReplHelper, DefaultHistory>::with_config(c)?;
     repl.set_helper(Some(ReplHelper {
         highlighter: MatchingBracketHighlighter::new(),
         validator: MatchingBracketValidator::new(),
@@ -86,6 +99,7 @@ fn on_command(line: &str, env: &mut Env) {
             }
         }
         (":clear", _) => env.clear(),
+        (":builtins", _) => println!("{BUILTINS}"),
         (":help" | ":h" | ":?", _) => println!("{HELP}"),
         (cmd, _) => eprintln!("unknown command {cmd}"),
     }
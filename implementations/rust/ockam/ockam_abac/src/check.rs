@@ -0,0 +1,476 @@
+use crate::env::Env;
+use crate::eval::eval;
+use crate::expr::Expr;
+use core::fmt;
+use core::str::FromStr;
+use minicbor::{Decode, Encode};
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::format;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::vec::Vec;
+
+/// The type of an attribute or expression, as known by the checker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(index_only)]
+pub enum Type {
+    #[n(0)] Any,
+    #[n(1)] Str,
+    #[n(2)] Int,
+    #[n(3)] Float,
+    #[n(4)] Bool,
+    #[n(5)] Seq,
+}
+
+impl Type {
+    fn of(e: &Expr) -> Self {
+        match e {
+            Expr::Str(_) => Type::Str,
+            Expr::Int(_) => Type::Int,
+            Expr::Float(_) => Type::Float,
+            Expr::Bool(_) => Type::Bool,
+            Expr::Seq(_) => Type::Seq,
+            Expr::Ident(_) | Expr::List(_) => Type::Any,
+        }
+    }
+
+    /// Check if a value of this type may be used where `other` is expected.
+    fn fits(self, other: &[Type]) -> bool {
+        self == Type::Any || other.contains(&self)
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Any => f.write_str("any"),
+            Type::Str => f.write_str("str"),
+            Type::Int => f.write_str("int"),
+            Type::Float => f.write_str("float"),
+            Type::Bool => f.write_str("bool"),
+            Type::Seq => f.write_str("seq"),
+        }
+    }
+}
+
+impl FromStr for Type {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(Type::Any),
+            "str" | "string" => Ok(Type::Str),
+            "int" => Ok(Type::Int),
+            "float" => Ok(Type::Float),
+            "bool" => Ok(Type::Bool),
+            "seq" => Ok(Type::Seq),
+            other => Err(format!("unknown type '{other}'")),
+        }
+    }
+}
+
+/// The attributes a policy may refer to, with their types.
+#[derive(Debug, Clone, Default, Encode, Decode)]
+#[cbor(transparent)]
+pub struct Schema(#[n(0)] BTreeMap<String, Type>);
+
+impl Schema {
+    pub fn new() -> Self {
+        Schema(BTreeMap::new())
+    }
+
+    pub fn with<K: Into<String>>(mut self, k: K, t: Type) -> Self {
+        self.put(k, t);
+        self
+    }
+
+    pub fn put<K: Into<String>>(&mut self, k: K, t: Type) -> &mut Self {
+        self.0.insert(k.into(), t);
+        self
+    }
+
+    pub fn get(&self, k: &str) -> Option<Type> {
+        self.0.get(k).copied()
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, Type)> {
+        self.0.iter().map(|(k, t)| (k.as_str(), *t))
+    }
+
+    /// Add all entries of `other` which are not already part of this schema.
+    pub fn merge_left(&mut self, other: Schema) {
+        for (k, t) in other.0.into_iter() {
+            self.0.entry(k).or_insert(t);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(index_only)]
+pub enum Severity {
+    #[n(0)] Error,
+    #[n(1)] Warning,
+}
+
+/// A problem found in a policy expression.
+#[derive(Debug, Clone, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct Diagnostic {
+    #[n(1)] severity: Severity,
+    #[n(2)] expr: Expr,
+    #[n(3)] message: String,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// The sub-expression the diagnostic refers to.
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Error => write!(f, "error: {} in {}", self.message, self.expr),
+            Severity::Warning => write!(f, "warning: {} in {}", self.message, self.expr),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Report(Vec<Diagnostic>);
+
+impl Report {
+    fn error<S: Into<String>>(&mut self, e: &Expr, m: S) {
+        self.add(Severity::Error, e, m)
+    }
+
+    fn warning<S: Into<String>>(&mut self, e: &Expr, m: S) {
+        self.add(Severity::Warning, e, m)
+    }
+
+    fn add<S: Into<String>>(&mut self, s: Severity, e: &Expr, m: S) {
+        self.0.push(Diagnostic {
+            severity: s,
+            expr: e.clone(),
+            message: m.into(),
+        })
+    }
+}
+
+/// What the checker knows about an expression.
+struct Info {
+    typ: Type,
+    /// The value of the expression if it does not depend on any attribute.
+    value: Option<Expr>,
+}
+
+/// Check a policy expression against a schema of attributes.
+///
+/// Returns errors for expressions which would fail to evaluate, e.g.
+/// because of unknown identifiers or operators, wrong arities or type
+/// mismatches, and warnings for sub-expressions which do not depend on
+/// any attribute and branches which can never be evaluated.
+#[rustfmt::skip]
+pub fn check(expr: &Expr, schema: &Schema) -> Vec<Diagnostic> {
+    enum Op<'a> {
+        Visit(&'a Expr),
+        Combine(&'a Expr),
+    }
+
+    let mut report = Report::default();
+
+    // Control stack.
+    let mut ctrl: Vec<Op> = Vec::new();
+    // Information about checked sub-expressions.
+    let mut infos: Vec<Info> = Vec::new();
+
+    ctrl.push(Op::Visit(expr));
+
+    while let Some(x) = ctrl.pop() {
+        match x {
+            Op::Visit(Expr::Ident(id)) => {
+                let typ = schema.get(id).unwrap_or_else(|| {
+                    report.error(&Expr::Ident(id.clone()), "unknown identifier");
+                    Type::Any
+                });
+                infos.push(Info { typ, value: None })
+            }
+            Op::Visit(e @ Expr::Seq(xs)) => {
+                ctrl.push(Op::Combine(e));
+                for x in xs.iter().rev() {
+                    ctrl.push(Op::Visit(x))
+                }
+            }
+            Op::Visit(e @ Expr::List(xs)) => match &xs[..] {
+                [] => infos.push(Info { typ: Type::Any, value: Some(e.clone()) }),
+                [Expr::Ident(id), args @ ..] if id == "exists?" => {
+                    for x in args {
+                        match x {
+                            Expr::Ident(id) => if schema.get(id).is_none() {
+                                report.warning(x, "identifier is not part of the schema")
+                            }
+                            other => report.error(other, "'exists?' expects identifiers as arguments")
+                        }
+                    }
+                    infos.push(Info { typ: Type::Bool, value: None })
+                }
+                [Expr::Ident(_), args @ ..] => {
+                    ctrl.push(Op::Combine(e));
+                    for x in args.iter().rev() {
+                        ctrl.push(Op::Visit(x))
+                    }
+                }
+                [other, ..] => {
+                    report.error(other, "expected (op ...)");
+                    infos.push(Info { typ: Type::Any, value: None })
+                }
+            }
+            Op::Visit(e) => infos.push(Info { typ: Type::of(e), value: Some(e.clone()) }),
+            Op::Combine(e @ Expr::Seq(xs)) => {
+                let args = infos.split_off(infos.len() - xs.len());
+                let value = args.iter().all(|a| a.value.is_some()).then(|| e.clone());
+                infos.push(Info { typ: Type::Seq, value })
+            }
+            Op::Combine(e @ Expr::List(xs)) => {
+                let (op, args) = match &xs[..] {
+                    [Expr::Ident(op), args @ ..] => (op.as_str(), args),
+                    _ => unreachable!("only operator applications are combined")
+                };
+                let infos_ = infos.split_off(infos.len() - args.len());
+                let errors = report.0.iter().filter(|d| d.is_error()).count();
+                let typ = check_op(op, e, args, &infos_, &mut report);
+
+                // Report maximal sub-expressions which do not depend on attributes.
+                for (x, i) in args.iter().zip(&infos_) {
+                    if let (Expr::List(_), Some(Expr::Bool(b))) = (x, &i.value) {
+                        report.warning(x, format!("expression is always {b}"))
+                    }
+                }
+
+                let constant = op != "now"
+                    && infos_.iter().all(|i| i.value.is_some())
+                    && errors == report.0.iter().filter(|d| d.is_error()).count();
+                let value = if constant {
+                    match eval(e, &Env::new()) {
+                        Ok(v) => Some(v),
+                        Err(err) => {
+                            report.error(e, err.to_string());
+                            None
+                        }
+                    }
+                } else {
+                    None
+                };
+                infos.push(Info { typ, value })
+            }
+            Op::Combine(_) => unreachable!("only sequences and lists are combined")
+        }
+    }
+
+    if let (Expr::List(_), Some(Info { value: Some(Expr::Bool(b)), .. })) = (expr, infos.last()) {
+        report.warning(expr, format!("expression is always {b}"))
+    }
+
+    report.0
+}
+
+/// Check the arguments of an operator application and return its type.
+#[rustfmt::skip]
+fn check_op(op: &str, e: &Expr, args: &[Expr], infos: &[Info], report: &mut Report) -> Type {
+    let arity = |report: &mut Report, ok: bool, msg: &str| {
+        if !ok {
+            report.error(e, msg)
+        }
+        ok
+    };
+    let expect = |report: &mut Report, i: usize, ts: &[Type], msg: &str| {
+        if !infos[i].typ.fits(ts) {
+            report.error(&args[i], format!("{msg}, found {}", infos[i].typ))
+        }
+    };
+    let same_types = |report: &mut Report| {
+        let mut known = infos.iter().zip(args).filter(|(i, _)| i.typ != Type::Any);
+        if let Some((first, _)) = known.next() {
+            for (i, x) in known {
+                if i.typ != first.typ {
+                    report.error(x, format!("expected {}, found {}", first.typ, i.typ))
+                }
+            }
+        }
+    };
+
+    match op {
+        "and" | "or" => {
+            for i in 0 .. args.len() {
+                expect(report, i, &[Type::Bool], "expected bool")
+            }
+            // Arguments following a constant which determines the result are never evaluated.
+            let stop = Expr::Bool(op == "or");
+            if let Some(p) = infos.iter().position(|i| matches!(&i.value, Some(v) if v.equals(&stop).unwrap_or(false))) {
+                if p + 1 < args.len() {
+                    report.warning(e, format!("arguments after {} are unreachable", args[p]))
+                }
+            }
+            Type::Bool
+        }
+        "not" => {
+            if arity(report, args.len() == 1, "'not' requires one argument") {
+                expect(report, 0, &[Type::Bool], "expected bool")
+            }
+            Type::Bool
+        }
+        "if" => {
+            if !arity(report, args.len() == 3, "'if' requires three arguments") {
+                return Type::Any
+            }
+            expect(report, 0, &[Type::Bool], "expected bool");
+            match infos[0].value {
+                Some(Expr::Bool(true))  => report.warning(&args[2], "branch is unreachable"),
+                Some(Expr::Bool(false)) => report.warning(&args[1], "branch is unreachable"),
+                _ => {}
+            }
+            if infos[1].typ == infos[2].typ { infos[1].typ } else { Type::Any }
+        }
+        "=" | "!=" | "<" | ">" => {
+            if op != "!=" {
+                arity(report, args.len() >= 2, &format!("'{op}' requires at least two arguments"));
+            }
+            same_types(report);
+            Type::Bool
+        }
+        "member?" => {
+            if arity(report, args.len() == 2, "'member?' requires two arguments") {
+                expect(report, 1, &[Type::Seq], "expected seq")
+            }
+            Type::Bool
+        }
+        "starts-with?" | "ends-with?" | "contains?" | "matches?" => {
+            if arity(report, args.len() == 2, &format!("'{op}' requires two arguments")) {
+                expect(report, 0, &[Type::Str], "expected str");
+                expect(report, 1, &[Type::Str], "expected str")
+            }
+            Type::Bool
+        }
+        "subset?" | "intersect?" => {
+            if arity(report, args.len() == 2, &format!("'{op}' requires two arguments")) {
+                expect(report, 0, &[Type::Seq, Type::Str], "expected seq or str");
+                expect(report, 1, &[Type::Seq, Type::Str], "expected seq or str")
+            }
+            Type::Bool
+        }
+        "number" => {
+            if arity(report, args.len() == 1, "'number' requires one argument") {
+                expect(report, 0, &[Type::Str, Type::Int, Type::Float], "expected str or number")
+            }
+            Type::Any
+        }
+        "now" => {
+            arity(report, args.is_empty(), "'now' does not take any arguments");
+            Type::Int
+        }
+        _ => {
+            report.error(e, format!("unknown operator: {op}"));
+            Type::Any
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{check, Schema, Severity, Type};
+    use crate::parser::parse;
+    use ockam_core::compat::vec::Vec;
+
+    fn diagnostics(s: &str) -> Vec<(Severity, String)> {
+        let schema = Schema::new()
+            .with("subject.role", Type::Str)
+            .with("subject.age", Type::Int)
+            .with("resource.id", Type::Str);
+        check(&parse(s).unwrap().unwrap(), &schema)
+            .into_iter()
+            .map(|d| (d.severity(), d.message().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn well_typed() {
+        assert!(diagnostics(r#"(and (= subject.role "admin") (> subject.age 18))"#).is_empty());
+        assert!(
+            diagnostics(r#"(or (exists? subject.role) (member? resource.id ["a" "b"]))"#)
+                .is_empty()
+        );
+        assert!(diagnostics("true").is_empty())
+    }
+
+    #[test]
+    fn errors() {
+        let d = diagnostics(r#"(= subject.name "alice")"#);
+        assert_eq!(d, vec![(Severity::Error, "unknown identifier".to_string())]);
+        let d = diagnostics(r#"(= subject.age "18")"#);
+        assert_eq!(
+            d,
+            vec![(Severity::Error, "expected int, found str".to_string())]
+        );
+        let d = diagnostics(r#"(and subject.role)"#);
+        assert_eq!(
+            d,
+            vec![(Severity::Error, "expected bool, found str".to_string())]
+        );
+        let d = diagnostics(r#"(frobnicate subject.role)"#);
+        assert_eq!(
+            d,
+            vec![(Severity::Error, "unknown operator: frobnicate".to_string())]
+        );
+        let d = diagnostics(r#"(not)"#);
+        assert_eq!(
+            d,
+            vec![(Severity::Error, "'not' requires one argument".to_string())]
+        );
+        let d = diagnostics(r#"(< subject.age (number "abc"))"#);
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].0, Severity::Error)
+    }
+
+    #[test]
+    fn warnings() {
+        let d = diagnostics(r#"(= 1 1)"#);
+        assert_eq!(
+            d,
+            vec![(Severity::Warning, "expression is always true".to_string())]
+        );
+        let d = diagnostics(r#"(and (= subject.role "admin") (> 1 2))"#);
+        assert_eq!(
+            d,
+            vec![(Severity::Warning, "expression is always false".to_string())]
+        );
+        let d = diagnostics(r#"(if true (= subject.role "admin") false)"#);
+        assert_eq!(
+            d,
+            vec![(Severity::Warning, "branch is unreachable".to_string())]
+        );
+        let d = diagnostics(r#"(or (= subject.role "admin") true (> subject.age 18))"#);
+        assert_eq!(
+            d,
+            vec![(
+                Severity::Warning,
+                "arguments after true are unreachable".to_string()
+            )]
+        );
+        assert!(diagnostics(r#"(< subject.age (now))"#).is_empty())
+    }
+}
@@ -3,7 +3,7 @@ use core::cmp::Ordering;
 use crate::env::Env;
 use crate::error::EvalError;
 use crate::expr::{unit, Expr};
-use ockam_core::compat::string::ToString;
+use ockam_core::compat::string::{String, ToString};
 use ockam_core::compat::vec::Vec;
 
 #[rustfmt::skip]
@@ -24,6 +24,14 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
         Lt(usize),
         Member,
         Seq(usize),
+        StartsWith,
+        EndsWith,
+        Contains,
+        #[cfg(feature = "std")]
+        Matches,
+        Subset,
+        Intersect,
+        Number,
     }
 
     // Control stack.
@@ -132,6 +140,65 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                             }
                             ctrl.push(Op::Member)
                         }
+                        "starts-with?" => {
+                            if nargs != 2 {
+                                let msg = "'starts-with?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::StartsWith)
+                        }
+                        "ends-with?" => {
+                            if nargs != 2 {
+                                let msg = "'ends-with?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::EndsWith)
+                        }
+                        "contains?" => {
+                            if nargs != 2 {
+                                let msg = "'contains?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Contains)
+                        }
+                        #[cfg(feature = "std")]
+                        "matches?" => {
+                            if nargs != 2 {
+                                let msg = "'matches?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Matches)
+                        }
+                        "subset?" => {
+                            if nargs != 2 {
+                                let msg = "'subset?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Subset)
+                        }
+                        "intersect?" => {
+                            if nargs != 2 {
+                                let msg = "'intersect?' requires two arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Intersect)
+                        }
+                        "number" => {
+                            if nargs != 1 {
+                                let msg = "'number' requires one argument";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            ctrl.push(Op::Number)
+                        }
+                        #[cfg(feature = "std")]
+                        "now" => {
+                            if nargs != 0 {
+                                let msg = "'now' does not take any arguments";
+                                return Err(EvalError::malformed(msg))
+                            }
+                            args.push(now()?);
+                            continue
+                        }
                         "exists?" => {
                             let mut b = true;
                             for x in &xs[1 ..] {
@@ -259,6 +326,63 @@ pub fn eval(expr: &Expr, env: &Env) -> Result<Expr, EvalError> {
                 let s = args.split_off(args.len() - n);
                 args.push(Expr::Seq(s))
             }
+            Op::StartsWith => {
+                let (s, p) = pop_strings(&mut args, "'starts-with?' expects string arguments")?;
+                args.push(Expr::Bool(s.starts_with(&p)))
+            }
+            Op::EndsWith => {
+                let (s, p) = pop_strings(&mut args, "'ends-with?' expects string arguments")?;
+                args.push(Expr::Bool(s.ends_with(&p)))
+            }
+            Op::Contains => {
+                let (s, p) = pop_strings(&mut args, "'contains?' expects string arguments")?;
+                args.push(Expr::Bool(s.contains(&p)))
+            }
+            #[cfg(feature = "std")]
+            Op::Matches => {
+                let (s, p) = pop_strings(&mut args, "'matches?' expects string arguments")?;
+                let r = regex::Regex::new(&p).map_err(|e| {
+                    EvalError::malformed(format!("invalid regular expression {p:?}: {e}"))
+                })?;
+                args.push(Expr::Bool(r.is_match(&s)))
+            }
+            Op::Subset => {
+                let b = to_set(pop(&mut args), "'subset?' expects sequences or strings")?;
+                let a = to_set(pop(&mut args), "'subset?' expects sequences or strings")?;
+                args.push(Expr::Bool(is_subset(&a, &b)?))
+            }
+            Op::Intersect => {
+                let b = to_set(pop(&mut args), "'intersect?' expects sequences or strings")?;
+                let a = to_set(pop(&mut args), "'intersect?' expects sequences or strings")?;
+                let mut r = false;
+                for x in &a {
+                    if contains(&b, x)? {
+                        r = true;
+                        break
+                    }
+                }
+                args.push(Expr::Bool(r))
+            }
+            Op::Number => {
+                match pop(&mut args) {
+                    x @ (Expr::Int(_) | Expr::Float(_)) => args.push(x),
+                    Expr::Str(s) => {
+                        let t = s.trim();
+                        if let Ok(i) = t.parse::<i64>() {
+                            args.push(Expr::Int(i))
+                        } else if let Ok(f) = t.parse::<f64>() {
+                            args.push(Expr::Float(f))
+                        } else {
+                            let msg = "'number' expects a numeric string";
+                            return Err(EvalError::InvalidType(Expr::Str(s), msg))
+                        }
+                    }
+                    other => {
+                        let msg = "'number' expects a string or a number";
+                        return Err(EvalError::InvalidType(other, msg))
+                    }
+                }
+            }
         }
     }
 
@@ -275,6 +399,65 @@ fn pop<T>(s: &mut Vec<T>) -> T {
     s.pop().expect("stack is not empty")
 }
 
+/// Pop off the two topmost arguments, which must be strings.
+///
+/// The first element of the returned pair is the first argument
+/// of the operation, i.e. the one below the topmost stack value.
+fn pop_strings(args: &mut Vec<Expr>, msg: &'static str) -> Result<(String, String), EvalError> {
+    let b = pop(args);
+    let a = pop(args);
+    match (a, b) {
+        (Expr::Str(a), Expr::Str(b)) => Ok((a, b)),
+        (Expr::Str(_), other) | (other, _) => Err(EvalError::InvalidType(other, msg)),
+    }
+}
+
+/// Turn an expression into a list of set elements.
+///
+/// Sequences are used as they are, strings are treated as comma-separated
+/// lists (e.g. attribute values such as `"admin, dev"`).
+fn to_set(x: Expr, msg: &'static str) -> Result<Vec<Expr>, EvalError> {
+    match x {
+        Expr::Seq(xs) => Ok(xs),
+        Expr::Str(s) => Ok(s
+            .split(',')
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(|x| Expr::Str(x.to_string()))
+            .collect()),
+        other => Err(EvalError::InvalidType(other, msg)),
+    }
+}
+
+/// Check if a set contains a value.
+fn contains(s: &[Expr], y: &Expr) -> Result<bool, EvalError> {
+    for x in s {
+        if x.equals(y)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Check if every element of `a` is an element of `b`.
+fn is_subset(a: &[Expr], b: &[Expr]) -> Result<bool, EvalError> {
+    for x in a {
+        if !contains(b, x)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The current time, as the number of seconds since the unix epoch.
+#[cfg(feature = "std")]
+fn now() -> Result<Expr, EvalError> {
+    let t = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| EvalError::malformed(format!("invalid system time: {e}")))?;
+    Ok(Expr::Int(t.as_secs() as i64))
+}
+
 /// Evaluate a predicate against the `n` topmost arguments.
 fn eval_predicate<F>(n: usize, args: &mut Vec<Expr>, f: F) -> Result<(), EvalError>
 where
@@ -292,3 +475,70 @@ where
     args.push(Expr::Bool(b));
     Ok(())
 }
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::eval;
+    use crate::{parser::parse, Env, EvalError, Expr};
+
+    fn run(s: &str, env: &Env) -> Result<Expr, EvalError> {
+        eval(&parse(s).unwrap().unwrap(), env)
+    }
+
+    fn holds(s: &str, env: &Env) -> bool {
+        matches!(run(s, env), Ok(Expr::Bool(true)))
+    }
+
+    #[test]
+    fn string_operators() {
+        let mut env = Env::new();
+        env.put("subject.email", Expr::Str("alice@example.com".into()));
+        assert!(holds(r#"(starts-with? subject.email "alice")"#, &env));
+        assert!(holds(r#"(ends-with? subject.email "@example.com")"#, &env));
+        assert!(holds(r#"(contains? subject.email "@")"#, &env));
+        assert!(!holds(r#"(contains? subject.email "bob")"#, &env));
+        assert!(holds(
+            r#"(matches? subject.email "^[a-z]+@example[.]com$")"#,
+            &env
+        ));
+        assert!(matches!(
+            run(r#"(starts-with? subject.email 1)"#, &env),
+            Err(EvalError::InvalidType(Expr::Int(1), _))
+        ));
+        assert!(parse(r#"(matches? subject.email "[a-z")"#).is_err());
+        assert!(matches!(
+            run(r#"(matches? subject.email (if true "[a-z" "a"))"#, &env),
+            Err(EvalError::Malformed(_))
+        ))
+    }
+
+    #[test]
+    fn set_operators() {
+        let mut env = Env::new();
+        env.put("subject.roles", Expr::Str("admin, dev".into()));
+        assert!(holds(r#"(subset? "dev" subject.roles)"#, &env));
+        assert!(holds(r#"(subset? ["dev" "admin"] subject.roles)"#, &env));
+        assert!(!holds(r#"(subset? ["dev" "ops"] subject.roles)"#, &env));
+        assert!(holds(r#"(intersect? "ops,dev" subject.roles)"#, &env));
+        assert!(!holds(r#"(intersect? "ops" subject.roles)"#, &env));
+        assert!(holds(r#"(subset? "" subject.roles)"#, &env));
+        assert!(matches!(
+            run(r#"(subset? 1 subject.roles)"#, &env),
+            Err(EvalError::InvalidType(Expr::Int(1), _))
+        ))
+    }
+
+    #[test]
+    fn numbers_and_time() {
+        let mut env = Env::new();
+        env.put("subject.expires", Expr::Str("4102444800".into()));
+        env.put("subject.score", Expr::Str(" 0.5 ".into()));
+        assert!(holds(r#"(> (number subject.expires) (now))"#, &env));
+        assert!(holds(r#"(< (number subject.score) 1.0)"#, &env));
+        assert!(matches!(run("(now 1)", &env), Err(EvalError::Malformed(_))));
+        assert!(matches!(
+            run(r#"(number "abc")"#, &env),
+            Err(EvalError::InvalidType(Expr::Str(_), _))
+        ))
+    }
+}
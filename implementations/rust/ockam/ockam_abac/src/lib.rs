@@ -6,10 +6,12 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod check;
 mod env;
 mod error;
 mod eval;
 mod policy;
+mod trace;
 mod traits;
 mod types;
 
@@ -22,11 +24,13 @@ pub mod mem;
 mod storage;
 
 pub use attribute_access_control::AbacAccessControl;
+pub use check::{check, Diagnostic, Schema, Severity, Type};
 pub use env::Env;
 pub use error::{EvalError, ParseError};
 pub use eval::eval;
 pub use expr::Expr;
 pub use policy::PolicyAccessControl;
+pub use trace::{dry_run, Outcome, TraceEntry};
 pub use traits::PolicyStorage;
 pub use types::{Action, Resource, Subject};
 
@@ -123,6 +123,14 @@ pub fn parse(s: &str) -> Result<Option<Expr>, ParseError> {
                     }
                 }
                 v.reverse();
+                if let [Expr::Ident(op), _, Expr::Str(re)] = &v[..] {
+                    if op == "matches?" {
+                        if let Err(e) = Regex::new(re) {
+                            let msg = format!("invalid regular expression {re:?}: {e}");
+                            return Err(ParseError::message(msg))
+                        }
+                    }
+                }
                 ctrl.push(Op::Value(Expr::List(v)));
                 ctrl.push(Op::Next)
             }
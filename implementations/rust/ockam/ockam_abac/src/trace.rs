@@ -0,0 +1,171 @@
+use crate::env::Env;
+use crate::eval::eval;
+use crate::expr::Expr;
+use minicbor::{Decode, Encode};
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::vec::Vec;
+
+/// The evaluation of a sub-expression during a dry-run.
+#[derive(Debug, Clone, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct TraceEntry {
+    /// Nesting level of the sub-expression, the policy itself being at level 0.
+    #[n(1)] depth: u32,
+    #[n(2)] expr: Expr,
+    /// The outcome of the evaluation, `None` if the sub-expression is not evaluated.
+    #[n(3)] outcome: Option<Outcome>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+#[rustfmt::skip]
+pub enum Outcome {
+    #[n(0)] Value (#[n(0)] Expr),
+    #[n(1)] Error (#[n(0)] String),
+}
+
+impl TraceEntry {
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    pub fn outcome(&self) -> Option<&Outcome> {
+        self.outcome.as_ref()
+    }
+}
+
+/// Evaluate a policy expression and record how each of its
+/// sub-expressions evaluates, in evaluation order.
+///
+/// Literals are not recorded. Sub-expressions which are skipped, like the
+/// branch of an `if` which is not taken or the remaining arguments of an
+/// `and` after a false argument, are recorded without an outcome.
+#[rustfmt::skip]
+pub fn dry_run(expr: &Expr, env: &Env) -> Vec<TraceEntry> {
+    let mut trace = Vec::new();
+
+    // Control stack of sub-expressions with their depth and
+    // whether they are actually evaluated.
+    let mut ctrl: Vec<(&Expr, u32, bool)> = Vec::new();
+
+    ctrl.push((expr, 0, true));
+
+    while let Some((e, depth, reached)) = ctrl.pop() {
+        let outcome = reached.then(|| match eval(e, env) {
+            Ok(v)  => Outcome::Value(v),
+            Err(e) => Outcome::Error(e.to_string())
+        });
+        let args = match e {
+            Expr::List(xs) => match &xs[..] {
+                [Expr::Ident(op), args @ ..] if op != "exists?" => Some((op.as_str(), args)),
+                _ => None
+            }
+            Expr::Ident(_) | Expr::Seq(_) => None,
+            _ if depth > 0 => continue, // literal argument
+            _ => None
+        };
+        trace.push(TraceEntry { depth, expr: e.clone(), outcome });
+
+        let (op, args) = match args {
+            Some(x) => x,
+            None    => continue
+        };
+
+        // Determine which arguments are evaluated.
+        let mut reachable = Vec::with_capacity(args.len());
+        match op {
+            "and" | "or" if reached => {
+                let stop = op == "or";
+                let mut go = true;
+                for x in args {
+                    reachable.push(go);
+                    if go {
+                        go = !matches!(eval(x, env), Ok(Expr::Bool(b)) if b == stop)
+                    }
+                }
+            }
+            "if" if reached && args.len() == 3 => {
+                let test = eval(&args[0], env);
+                reachable.push(true);
+                reachable.push(matches!(test, Ok(Expr::Bool(true))));
+                reachable.push(matches!(test, Ok(Expr::Bool(false))))
+            }
+            _ => reachable.resize(args.len(), reached)
+        }
+
+        for (x, r) in args.iter().zip(reachable).rev() {
+            ctrl.push((x, depth + 1, r))
+        }
+    }
+
+    trace
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{dry_run, Outcome};
+    use crate::parser::parse;
+    use crate::{Env, Expr};
+
+    fn summary(s: &str, env: &Env) -> Vec<(u32, String, Option<String>)> {
+        dry_run(&parse(s).unwrap().unwrap(), env)
+            .into_iter()
+            .map(|t| {
+                let o = t.outcome().map(|o| match o {
+                    Outcome::Value(v) => v.to_string(),
+                    Outcome::Error(_) => "error".to_string(),
+                });
+                (t.depth(), t.expr().to_string(), o)
+            })
+            .collect()
+    }
+
+    fn entry(depth: u32, e: &str, o: Option<&str>) -> (u32, String, Option<String>) {
+        (depth, e.to_string(), o.map(|o| o.to_string()))
+    }
+
+    #[test]
+    fn lazy_operators_are_traced() {
+        let mut env = Env::new();
+        env.put("subject.role", Expr::Str("dev".into()));
+        let s = summary(
+            r#"(and (= subject.role "admin") (= subject.role "dev"))"#,
+            &env,
+        );
+        assert_eq!(
+            s[1..],
+            [
+                entry(1, r#"(= subject.role "admin")"#, Some("false")),
+                entry(2, "subject.role", Some(r#""dev""#)),
+                entry(1, r#"(= subject.role "dev")"#, None),
+                entry(2, "subject.role", None),
+            ]
+        );
+        assert_eq!(s[0].2.as_deref(), Some("false"));
+
+        let s = summary(r#"(if (exists? subject.role) true subject.missing)"#, &env);
+        assert_eq!(
+            s[1..],
+            [
+                entry(1, "(exists? subject.role)", Some("true")),
+                entry(1, "subject.missing", None),
+            ]
+        )
+    }
+
+    #[test]
+    fn errors_are_traced() {
+        let s = summary("(= subject.role 1)", &Env::new());
+        assert_eq!(
+            s,
+            [
+                entry(0, "(= subject.role 1)", Some("error")),
+                entry(1, "subject.role", Some("error")),
+            ]
+        )
+    }
+}
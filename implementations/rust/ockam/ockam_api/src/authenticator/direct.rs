@@ -1,26 +1,27 @@
+pub mod enrollment_tokens;
 pub mod types;
 
 use core::str;
-use lru::LruCache;
 use minicbor::Decoder;
-use ockam::identity::{AttributesEntry, IdentityAttributesWriter};
+use ockam::identity::{AttributesEntry, IdentityAttributesReader, IdentityAttributesWriter};
 use ockam::identity::{IdentityIdentifier, IdentitySecureChannelLocalInfo};
-use ockam::identity::{OneTimeCode, Timestamp};
+use ockam::identity::{OneTimeCode, Storage, Timestamp};
 use ockam_core::api::{self, Method, Request, Response, Status};
-use ockam_core::compat::sync::{Arc, RwLock};
+use ockam_core::compat::sync::Arc;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{self, CowStr, Result, Routed, Worker};
-use ockam_identity::{secure_channel_required, LEGACY_ID, TRUST_CONTEXT_ID};
+use ockam_identity::{
+    secure_channel_required, AuthorityRevocationList, LEGACY_ID, TRUST_CONTEXT_ID,
+};
 use ockam_node::{Context, RpcClient};
 use std::collections::HashMap;
-use std::num::NonZeroUsize;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 use tracing::{trace, warn};
-use types::AddMember;
+use types::{AddMember, CreateToken, EnrollmentToken, UpdateMember};
 
-use crate::authenticator::direct::types::CreateToken;
-
-const MAX_TOKEN_DURATION: Duration = Duration::from_secs(600);
+use crate::authenticator::direct::enrollment_tokens::{
+    EnrollmentTokens, PresentedToken, DEFAULT_TOKEN_DURATION,
+};
 
 /// Schema identifier for a project membership credential.
 ///
@@ -149,25 +150,67 @@ impl Worker for LegacyApiConverter {
 pub struct DirectAuthenticator {
     trust_context: String,
     attributes_writer: Arc<dyn IdentityAttributesWriter>,
+    attributes_reader: Arc<dyn IdentityAttributesReader>,
+    revocation_list: Option<AuthorityRevocationList>,
 }
 
 impl DirectAuthenticator {
     pub async fn new(
         trust_context: String,
         attributes_writer: Arc<dyn IdentityAttributesWriter>,
+        attributes_reader: Arc<dyn IdentityAttributesReader>,
     ) -> Result<Self> {
         Ok(Self {
             trust_context,
             attributes_writer,
+            attributes_reader,
+            revocation_list: None,
         })
     }
 
+    /// Revoke the credentials of removed members
+    pub fn with_revocation_list(mut self, revocation_list: AuthorityRevocationList) -> Self {
+        self.revocation_list = Some(revocation_list);
+        self
+    }
+
     async fn add_member<'a>(
         &self,
         enroller: &IdentityIdentifier,
         id: &IdentityIdentifier,
         attrs: &HashMap<CowStr<'a>, CowStr<'a>>,
+        ttl: Option<Duration>,
     ) -> Result<()> {
+        let entry = self.make_entry(enroller, attrs, ttl)?;
+        self.attributes_writer.put_attributes(id, entry).await
+    }
+
+    /// Replace the attributes and the expiration time of an existing member.
+    /// Return false if the identity is not a member
+    async fn update_member<'a>(
+        &self,
+        enroller: &IdentityIdentifier,
+        id: &IdentityIdentifier,
+        attrs: &HashMap<CowStr<'a>, CowStr<'a>>,
+        ttl: Option<Duration>,
+    ) -> Result<bool> {
+        if self.attributes_reader.get_attributes(id).await?.is_none() {
+            return Ok(false);
+        }
+        let entry = self.make_entry(enroller, attrs, ttl)?;
+        self.attributes_writer.put_attributes(id, entry).await?;
+        Ok(true)
+    }
+
+    fn make_entry<'a>(
+        &self,
+        enroller: &IdentityIdentifier,
+        attrs: &HashMap<CowStr<'a>, CowStr<'a>>,
+        ttl: Option<Duration>,
+    ) -> Result<AttributesEntry> {
+        let now = Timestamp::now().ok_or_else(|| {
+            ockam_core::Error::new(Origin::Core, Kind::Internal, "invalid system time")
+        })?;
         let auth_attrs = attrs
             .iter()
             .map(|(k, v)| (k.to_string(), v.as_bytes().to_vec()))
@@ -182,13 +225,20 @@ impl DirectAuthenticator {
                 .into_iter(),
             )
             .collect();
-        let entry = AttributesEntry::new(
+        Ok(AttributesEntry::new(
             auth_attrs,
-            Timestamp::now().unwrap(),
-            None,
+            now,
+            ttl.map(|ttl| now.add_seconds(ttl.as_secs())),
             Some(enroller.clone()),
-        );
-        self.attributes_writer.put_attributes(id, entry).await
+        ))
+    }
+
+    async fn delete_member(&self, id: &IdentityIdentifier) -> Result<()> {
+        self.attributes_writer.delete(id).await?;
+        if let Some(revocation_list) = &self.revocation_list {
+            revocation_list.revoke_subject(id).await?;
+        }
+        Ok(())
     }
 }
 
@@ -211,13 +261,42 @@ impl Worker for DirectAuthenticator {
                 body   = %req.has_body(),
                 "request"
             }
-            let res = match (req.method(), req.path()) {
-                (Some(Method::Post), "/") | (Some(Method::Post), "/members") => {
+            let path_segments = req.path_segments::<3>();
+            let res = match (req.method(), path_segments.as_slice()) {
+                (Some(Method::Post), [""]) | (Some(Method::Post), ["members"]) => {
                     let add: AddMember = dec.decode()?;
-                    self.add_member(&from, add.member(), add.attributes())
+                    self.add_member(&from, add.member(), add.attributes(), add.ttl())
                         .await?;
                     Response::ok(req.id()).to_vec()?
                 }
+                (Some(Method::Get), ["members"]) => {
+                    let members = self.attributes_reader.list().await?;
+                    Response::ok(req.id()).body(members).to_vec()?
+                }
+                (Some(Method::Get), ["members", id]) => {
+                    let id = IdentityIdentifier::try_from(*id)?;
+                    match self.attributes_reader.get_attributes(&id).await? {
+                        Some(entry) => Response::ok(req.id()).body(entry).to_vec()?,
+                        None => Response::not_found(req.id()).to_vec()?,
+                    }
+                }
+                (Some(Method::Put), ["members", id]) => {
+                    let id = IdentityIdentifier::try_from(*id)?;
+                    let update: UpdateMember = dec.decode()?;
+                    if self
+                        .update_member(&from, &id, update.attributes(), update.ttl())
+                        .await?
+                    {
+                        Response::ok(req.id()).to_vec()?
+                    } else {
+                        Response::not_found(req.id()).to_vec()?
+                    }
+                }
+                (Some(Method::Delete), ["members", id]) => {
+                    let id = IdentityIdentifier::try_from(*id)?;
+                    self.delete_member(&id).await?;
+                    Response::ok(req.id()).to_vec()?
+                }
                 _ => api::unknown_path(&req).to_vec()?,
             };
             c.send(m.return_route(), res).await
@@ -230,7 +309,7 @@ impl Worker for DirectAuthenticator {
 #[derive(Clone)]
 pub struct EnrollmentTokenAuthenticator {
     trust_context: String,
-    tokens: Arc<RwLock<LruCache<[u8; 32], Token>>>,
+    tokens: EnrollmentTokens,
 }
 
 pub struct EnrollmentTokenIssuer(EnrollmentTokenAuthenticator);
@@ -241,15 +320,16 @@ pub struct EnrollmentTokenAcceptor(
 );
 
 impl EnrollmentTokenAuthenticator {
+    /// Create the workers issuing and accepting enrollment tokens.
+    /// The tokens are persisted in the given storage so that they survive a restart
     pub fn new_worker_pair(
         trust_context: String,
         attributes_writer: Arc<dyn IdentityAttributesWriter>,
+        tokens_storage: Arc<dyn Storage>,
     ) -> (EnrollmentTokenIssuer, EnrollmentTokenAcceptor) {
         let base = Self {
             trust_context,
-            tokens: Arc::new(RwLock::new(LruCache::new(
-                NonZeroUsize::new(128).expect("0 < 128"),
-            ))),
+            tokens: EnrollmentTokens::new(tokens_storage),
         };
         (
             EnrollmentTokenIssuer(base.clone()),
@@ -262,28 +342,19 @@ impl EnrollmentTokenIssuer {
     async fn issue_token(
         &self,
         enroller: &IdentityIdentifier,
-        attrs: HashMap<String, String>,
+        create: CreateToken<'_>,
     ) -> Result<OneTimeCode> {
-        let otc = OneTimeCode::new();
-        let tkn = Token {
-            attrs,
-            generated_by: enroller.clone(),
-            time: Instant::now(),
-        };
+        let duration = create.ttl().unwrap_or(DEFAULT_TOKEN_DURATION);
+        let usage_count = create.usage_count().unwrap_or(1);
         self.0
             .tokens
-            .write()
-            .map(|mut r| {
-                r.put(*otc.code(), tkn);
-                otc
-            })
-            .map_err(|_| {
-                ockam_core::Error::new(
-                    Origin::Other,
-                    Kind::Internal,
-                    "failed to get read lock on tokens table",
-                )
-            })
+            .issue(
+                enroller,
+                create.into_owned_attributes(),
+                duration,
+                usage_count,
+            )
+            .await
     }
 }
 
@@ -306,12 +377,29 @@ impl Worker for EnrollmentTokenIssuer {
                 body   = %req.has_body(),
                 "request"
             }
-            let res = match (req.method(), req.path()) {
-                (Some(Method::Post), "/") | (Some(Method::Post), "/tokens") => {
+            let path_segments = req.path_segments::<3>();
+            let res = match (req.method(), path_segments.as_slice()) {
+                (Some(Method::Post), [""]) | (Some(Method::Post), ["tokens"]) => {
                     let att: CreateToken = dec.decode()?;
-                    match self.issue_token(&from, att.into_owned_attributes()).await {
-                        Ok(otc) => Response::ok(req.id()).body(&otc).to_vec()?,
-                        Err(error) => api::internal_error(&req, &error.to_string()).to_vec()?,
+                    if att.usage_count() == Some(0) {
+                        api::bad_request(&req, "the usage count of a token must be greater than 0")
+                            .to_vec()?
+                    } else {
+                        match self.issue_token(&from, att).await {
+                            Ok(otc) => Response::ok(req.id()).body(&otc).to_vec()?,
+                            Err(error) => api::internal_error(&req, &error.to_string()).to_vec()?,
+                        }
+                    }
+                }
+                (Some(Method::Get), ["tokens"]) => match self.0.tokens.list().await {
+                    Ok(tokens) => Response::ok(req.id()).body(tokens).to_vec()?,
+                    Err(error) => api::internal_error(&req, &error.to_string()).to_vec()?,
+                },
+                (Some(Method::Delete), ["tokens", id]) => {
+                    if self.0.tokens.revoke(id).await? {
+                        Response::ok(req.id()).to_vec()?
+                    } else {
+                        Response::not_found(req.id()).to_vec()?
                     }
                 }
                 _ => api::unknown_path(&req).to_vec()?,
@@ -346,29 +434,22 @@ impl Worker for EnrollmentTokenAcceptor {
                 (Some(Method::Post), "/") | (Some(Method::Post), "/credential") => {
                     //TODO: move out of the worker handle_message implementation
                     let otc: OneTimeCode = dec.decode()?;
-                    let token = match self.0.tokens.write() {
-                        Ok(mut r) => {
-                            if let Some(tkn) = r.pop(otc.code()) {
-                                if tkn.time.elapsed() > MAX_TOKEN_DURATION {
-                                    Err(api::forbidden(&req, "expired token"))
-                                } else {
-                                    Ok(tkn)
-                                }
-                            } else {
-                                Err(api::forbidden(&req, "unknown token"))
-                            }
+                    let token = match self.0.tokens.present(&otc).await {
+                        Ok(PresentedToken::Valid(tkn)) => Ok(tkn),
+                        Ok(PresentedToken::Expired) => {
+                            Err(api::forbidden(&req, "expired token").to_vec()?)
                         }
-                        Err(_) => Err(api::internal_error(
-                            &req,
-                            "Failed to get read lock on tokens table",
-                        )),
+                        Ok(PresentedToken::Unknown) => {
+                            Err(api::forbidden(&req, "unknown token").to_vec()?)
+                        }
+                        Err(error) => Err(api::internal_error(&req, &error.to_string()).to_vec()?),
                     };
                     match token {
                         Ok(tkn) => {
                             //TODO: fixme:  unify use of hashmap vs btreemap
                             let trust_context = self.0.trust_context.as_bytes().to_vec();
                             let attrs = tkn
-                                .attrs
+                                .attributes()
                                 .iter()
                                 .map(|(k, v)| (k.to_string(), v.as_bytes().to_vec()))
                                 .chain(
@@ -383,12 +464,12 @@ impl Worker for EnrollmentTokenAcceptor {
                                 attrs,
                                 Timestamp::now().unwrap(),
                                 None,
-                                Some(tkn.generated_by),
+                                Some(tkn.created_by().clone()),
                             );
                             self.1.put_attributes(&from, entry).await?;
                             Response::ok(req.id()).to_vec()?
                         }
-                        Err(err) => err.to_vec()?,
+                        Err(err) => err,
                     }
                 }
                 _ => api::unknown_path(&req).to_vec()?,
@@ -400,12 +481,6 @@ impl Worker for EnrollmentTokenAcceptor {
     }
 }
 
-struct Token {
-    attrs: HashMap<String, String>,
-    generated_by: IdentityIdentifier,
-    time: Instant,
-}
-
 pub struct DirectAuthenticatorClient(RpcClient);
 
 impl DirectAuthenticatorClient {
@@ -424,6 +499,52 @@ impl DirectAuthenticatorClient {
             )
             .await
     }
+
+    /// Add a member which is removed after the given duration
+    pub async fn add_member_with_ttl(
+        &self,
+        id: IdentityIdentifier,
+        attributes: HashMap<&str, &str>,
+        ttl: Duration,
+    ) -> Result<()> {
+        let body = AddMember::new(id).with_attributes(attributes).with_ttl(ttl);
+        self.0
+            .request_no_resp_body(&Request::post("/members").body(body))
+            .await
+    }
+
+    pub async fn list_members(&self) -> Result<Vec<(IdentityIdentifier, AttributesEntry)>> {
+        self.0.request(&Request::get("/members")).await
+    }
+
+    /// Return the attributes of a member, or None if the identity is not a member
+    pub async fn get_member(&self, id: IdentityIdentifier) -> Result<Option<AttributesEntry>> {
+        self.0
+            .request_maybe(&Request::get(format!("/members/{id}")))
+            .await
+    }
+
+    /// Replace the attributes and the expiration time of an existing member
+    pub async fn update_member(
+        &self,
+        id: IdentityIdentifier,
+        attributes: HashMap<&str, &str>,
+        ttl: Option<Duration>,
+    ) -> Result<()> {
+        let mut body = UpdateMember::new().with_attributes(attributes);
+        if let Some(ttl) = ttl {
+            body = body.with_ttl(ttl)
+        }
+        self.0
+            .request_no_resp_body(&Request::put(format!("/members/{id}")).body(body))
+            .await
+    }
+
+    pub async fn delete_member(&self, id: IdentityIdentifier) -> Result<()> {
+        self.0
+            .request_no_resp_body(&Request::delete(format!("/members/{id}")))
+            .await
+    }
 }
 
 pub struct TokenIssuerClient(RpcClient);
@@ -438,6 +559,36 @@ impl TokenIssuerClient {
             .request(&Request::post("/").body(CreateToken::new().with_attributes(attributes)))
             .await
     }
+
+    /// Create a token which expires after `ttl` and which can be used to enroll
+    /// `usage_count` members
+    pub async fn create_token_with_options(
+        &self,
+        attributes: HashMap<&str, &str>,
+        ttl: Option<Duration>,
+        usage_count: Option<u64>,
+    ) -> Result<OneTimeCode> {
+        let mut body = CreateToken::new().with_attributes(attributes);
+        if let Some(ttl) = ttl {
+            body = body.with_ttl(ttl)
+        }
+        if let Some(usage_count) = usage_count {
+            body = body.with_usage_count(usage_count)
+        }
+        self.0.request(&Request::post("/tokens").body(body)).await
+    }
+
+    /// Return the tokens which can still be used
+    pub async fn list_tokens(&self) -> Result<Vec<EnrollmentToken>> {
+        self.0.request(&Request::get("/tokens")).await
+    }
+
+    /// Revoke a token so that it cannot be used anymore
+    pub async fn revoke_token(&self, id: &str) -> Result<()> {
+        self.0
+            .request_no_resp_body(&Request::delete(format!("/tokens/{id}")))
+            .await
+    }
 }
 
 pub struct TokenAcceptorClient(RpcClient);
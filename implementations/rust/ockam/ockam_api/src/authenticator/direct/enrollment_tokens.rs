@@ -0,0 +1,216 @@
+use crate::authenticator::direct::types::EnrollmentToken;
+use ockam::identity::credential::Timestamp;
+use ockam::identity::{IdentityIdentifier, OneTimeCode, Storage};
+use ockam_core::compat::sync::Arc;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+use ockam_node::compat::asynchronous::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Default lifetime of an enrollment token
+pub const DEFAULT_TOKEN_DURATION: Duration = Duration::from_secs(600);
+
+/// Storage key used to persist enrollment tokens
+const ENROLLMENT_TOKEN_KEY: &str = "enrollment_token";
+
+/// Result of the presentation of an enrollment token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PresentedToken {
+    /// The token is valid and has been used once more
+    Valid(EnrollmentToken),
+    /// The token has expired
+    Expired,
+    /// The token has never been issued, has been revoked or has been used up
+    Unknown,
+}
+
+/// Enrollment tokens issued by an authority and persisted in a storage
+///
+/// Tokens are stored under the hash of their code, so that the storage
+/// does not contain the secret which is handed over to future members.
+#[derive(Clone)]
+pub struct EnrollmentTokens {
+    storage: Arc<dyn Storage>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl EnrollmentTokens {
+    /// Create a new set of enrollment tokens persisted in the given storage
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self {
+            storage,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Issue a new token which can be used `usage_count` times before it expires
+    pub async fn issue(
+        &self,
+        created_by: &IdentityIdentifier,
+        attributes: HashMap<String, String>,
+        duration: Duration,
+        usage_count: u64,
+    ) -> Result<OneTimeCode> {
+        if usage_count == 0 {
+            return Err(Error::new(
+                Origin::Application,
+                Kind::Invalid,
+                "the usage count of a token must be greater than 0",
+            ));
+        }
+        let now = now()?;
+        let otc = OneTimeCode::new();
+        let token = EnrollmentToken::new(
+            Self::token_id(&otc),
+            attributes.into_iter().collect(),
+            created_by.clone(),
+            now,
+            now.add_seconds(duration.as_secs()),
+            usage_count,
+        );
+        self.put(&token).await?;
+        Ok(otc)
+    }
+
+    /// Return the tokens which are still valid
+    pub async fn list(&self) -> Result<Vec<EnrollmentToken>> {
+        let now = now()?;
+        let mut tokens = vec![];
+        for id in self.storage.keys(ENROLLMENT_TOKEN_KEY).await? {
+            if let Some(token) = self.get(&id).await? {
+                if token.is_expired(now) {
+                    self.delete(&id).await?;
+                } else {
+                    tokens.push(token)
+                }
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// Revoke a token so that it cannot be used anymore.
+    /// Return false if the token does not exist
+    pub async fn revoke(&self, id: &str) -> Result<bool> {
+        let _guard = self.lock.lock().await;
+        if self.get(id).await?.is_none() {
+            return Ok(false);
+        }
+        self.delete(id).await?;
+        Ok(true)
+    }
+
+    /// Use a token to enroll a new member.
+    /// The token is removed once it has expired or once it has been used up
+    pub async fn present(&self, otc: &OneTimeCode) -> Result<PresentedToken> {
+        let _guard = self.lock.lock().await;
+        let id = Self::token_id(otc);
+        let mut token = match self.get(&id).await? {
+            Some(token) => token,
+            None => return Ok(PresentedToken::Unknown),
+        };
+        if token.is_expired(now()?) {
+            self.delete(&id).await?;
+            return Ok(PresentedToken::Expired);
+        }
+        token.increment_usage();
+        if token.remaining_uses() == 0 {
+            self.delete(&id).await?;
+        } else {
+            self.put(&token).await?;
+        }
+        Ok(PresentedToken::Valid(token))
+    }
+
+    /// Return the identifier of the token corresponding to a one-time code
+    pub fn token_id(otc: &OneTimeCode) -> String {
+        hex::encode(Sha256::digest(otc.code()))
+    }
+
+    async fn get(&self, id: &str) -> Result<Option<EnrollmentToken>> {
+        match self.storage.get(id, ENROLLMENT_TOKEN_KEY).await? {
+            Some(bytes) => Ok(Some(minicbor::decode(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn put(&self, token: &EnrollmentToken) -> Result<()> {
+        self.storage
+            .set(
+                token.id(),
+                ENROLLMENT_TOKEN_KEY.to_string(),
+                minicbor::to_vec(token)?,
+            )
+            .await
+    }
+
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.storage.del(id, ENROLLMENT_TOKEN_KEY).await
+    }
+}
+
+fn now() -> Result<Timestamp> {
+    Timestamp::now().ok_or_else(|| Error::new(Origin::Core, Kind::Internal, "invalid system time"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam::identity::InMemoryStorage;
+
+    #[tokio::test]
+    async fn test_multi_use_token() -> Result<()> {
+        let tokens = EnrollmentTokens::new(InMemoryStorage::create());
+        let enroller = IdentityIdentifier::from_key_id("enroller");
+        let attributes = HashMap::from([("role".to_string(), "member".to_string())]);
+        let otc = tokens
+            .issue(&enroller, attributes, DEFAULT_TOKEN_DURATION, 2)
+            .await?;
+
+        let listed = tokens.list().await?;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id(), EnrollmentTokens::token_id(&otc));
+        assert_eq!(listed[0].created_by(), &enroller);
+        assert_eq!(listed[0].remaining_uses(), 2);
+
+        match tokens.present(&otc).await? {
+            PresentedToken::Valid(token) => {
+                assert_eq!(token.attributes().get("role"), Some(&"member".to_string()));
+                assert_eq!(token.remaining_uses(), 1);
+            }
+            other => panic!("unexpected token {other:?}"),
+        }
+        assert!(matches!(
+            tokens.present(&otc).await?,
+            PresentedToken::Valid(_)
+        ));
+        assert_eq!(tokens.present(&otc).await?, PresentedToken::Unknown);
+        assert!(tokens.list().await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_expired_and_revoked_tokens() -> Result<()> {
+        let tokens = EnrollmentTokens::new(InMemoryStorage::create());
+        let enroller = IdentityIdentifier::from_key_id("enroller");
+
+        let expired = tokens
+            .issue(&enroller, HashMap::new(), Duration::from_secs(0), 1)
+            .await?;
+        assert_eq!(tokens.present(&expired).await?, PresentedToken::Expired);
+
+        let revoked = tokens
+            .issue(&enroller, HashMap::new(), DEFAULT_TOKEN_DURATION, 1)
+            .await?;
+        assert!(tokens.revoke(&EnrollmentTokens::token_id(&revoked)).await?);
+        assert!(!tokens.revoke(&EnrollmentTokens::token_id(&revoked)).await?);
+        assert_eq!(tokens.present(&revoked).await?, PresentedToken::Unknown);
+
+        assert!(tokens
+            .issue(&enroller, HashMap::new(), DEFAULT_TOKEN_DURATION, 0)
+            .await
+            .is_err());
+        Ok(())
+    }
+}
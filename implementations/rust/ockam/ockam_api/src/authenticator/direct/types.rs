@@ -1,7 +1,10 @@
 use minicbor::{Decode, Encode};
+use ockam::identity::credential::Timestamp;
 use ockam::identity::IdentityIdentifier;
 use ockam_core::CowStr;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
 
 #[cfg(feature = "tag")]
 use ockam_core::TypeTag;
@@ -14,6 +17,7 @@ pub struct AddMember<'a> {
     #[n(0)] tag: TypeTag<2820828>,
     #[n(1)] member: IdentityIdentifier,
     #[b(2)] attributes: HashMap<CowStr<'a>, CowStr<'a>>,
+    #[n(3)] ttl: Option<u64>,
 }
 
 impl<'a> AddMember<'a> {
@@ -23,6 +27,7 @@ impl<'a> AddMember<'a> {
             tag: TypeTag,
             member,
             attributes: HashMap::new(),
+            ttl: None,
         }
     }
 
@@ -34,6 +39,12 @@ impl<'a> AddMember<'a> {
         self
     }
 
+    /// Remove the member after the given duration
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl.as_secs());
+        self
+    }
+
     pub fn member(&self) -> &IdentityIdentifier {
         &self.member
     }
@@ -41,6 +52,54 @@ impl<'a> AddMember<'a> {
     pub fn attributes(&self) -> &HashMap<CowStr, CowStr> {
         &self.attributes
     }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl.map(Duration::from_secs)
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct UpdateMember<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<6372294>,
+    #[b(1)] attributes: HashMap<CowStr<'a>, CowStr<'a>>,
+    #[n(2)] ttl: Option<u64>,
+}
+
+impl<'a> UpdateMember<'a> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        UpdateMember {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            attributes: HashMap::new(),
+            ttl: None,
+        }
+    }
+
+    pub fn with_attributes<S: Into<CowStr<'a>>>(mut self, attributes: HashMap<S, S>) -> Self {
+        self.attributes = attributes
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        self
+    }
+
+    /// Remove the member after the given duration
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl.as_secs());
+        self
+    }
+
+    pub fn attributes(&self) -> &HashMap<CowStr<'_>, CowStr<'_>> {
+        &self.attributes
+    }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl.map(Duration::from_secs)
+    }
 }
 
 #[derive(Debug, Decode, Encode)]
@@ -50,6 +109,8 @@ pub struct CreateToken<'a> {
     #[cfg(feature = "tag")]
     #[n(0)] tag: TypeTag<2502742>,
     #[b(1)] attributes: HashMap<CowStr<'a>, CowStr<'a>>,
+    #[n(2)] ttl: Option<u64>,
+    #[n(3)] usage_count: Option<u64>,
 }
 
 impl<'a> CreateToken<'a> {
@@ -59,6 +120,8 @@ impl<'a> CreateToken<'a> {
             #[cfg(feature = "tag")]
             tag: TypeTag,
             attributes: HashMap::new(),
+            ttl: None,
+            usage_count: None,
         }
     }
 
@@ -70,6 +133,26 @@ impl<'a> CreateToken<'a> {
         self
     }
 
+    /// Expire the token after the given duration
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl.as_secs());
+        self
+    }
+
+    /// Allow the token to be used to enroll a given number of members
+    pub fn with_usage_count(mut self, usage_count: u64) -> Self {
+        self.usage_count = Some(usage_count);
+        self
+    }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl.map(Duration::from_secs)
+    }
+
+    pub fn usage_count(&self) -> Option<u64> {
+        self.usage_count
+    }
+
     pub fn into_owned_attributes(self) -> HashMap<String, String> {
         self.attributes
             .into_iter()
@@ -77,3 +160,87 @@ impl<'a> CreateToken<'a> {
             .collect()
     }
 }
+
+/// An enrollment token issued by an authority.
+///
+/// This does not contain the one-time code of the token, which is only
+/// returned to the enroller who created the token.
+#[derive(Debug, Clone, PartialEq, Eq, Decode, Encode, Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct EnrollmentToken {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<4925831>,
+    #[n(1)] id: String,
+    #[n(2)] attributes: BTreeMap<String, String>,
+    #[n(3)] created_by: IdentityIdentifier,
+    #[n(4)] created_at: Timestamp,
+    #[n(5)] expires_at: Timestamp,
+    #[n(6)] usage_count: u64,
+    #[n(7)] used: u64,
+}
+
+impl EnrollmentToken {
+    pub fn new(
+        id: String,
+        attributes: BTreeMap<String, String>,
+        created_by: IdentityIdentifier,
+        created_at: Timestamp,
+        expires_at: Timestamp,
+        usage_count: u64,
+    ) -> Self {
+        EnrollmentToken {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            id,
+            attributes,
+            created_by,
+            created_at,
+            expires_at,
+            usage_count,
+            used: 0,
+        }
+    }
+
+    /// Identifier of the token, which can be used to revoke it
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Attributes given to the members enrolled with this token
+    pub fn attributes(&self) -> &BTreeMap<String, String> {
+        &self.attributes
+    }
+
+    /// Identity of the enroller who created the token
+    pub fn created_by(&self) -> &IdentityIdentifier {
+        &self.created_by
+    }
+
+    pub fn created_at(&self) -> Timestamp {
+        self.created_at
+    }
+
+    pub fn expires_at(&self) -> Timestamp {
+        self.expires_at
+    }
+
+    /// Number of members which can be enrolled with this token
+    pub fn usage_count(&self) -> u64 {
+        self.usage_count
+    }
+
+    /// Number of members which can still be enrolled with this token
+    pub fn remaining_uses(&self) -> u64 {
+        self.usage_count.saturating_sub(self.used)
+    }
+
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expires_at <= now
+    }
+
+    pub(crate) fn increment_usage(&mut self) {
+        self.used += 1;
+    }
+}
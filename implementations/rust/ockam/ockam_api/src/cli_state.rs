@@ -221,7 +221,7 @@ fn file_stem(path: &Path) -> Result<String> {
 mod tests {
     use super::*;
     use crate::config::cli::TrustContextConfig;
-    use ockam_identity::IdentitiesVault;
+    use ockam_identity::{IdentitiesVault, IdentityHistoryComparison};
 
     #[tokio::test]
     async fn test_create_default_identity_state() {
@@ -266,6 +266,37 @@ mod tests {
         assert_eq!(identity1.path(), identity2.path());
     }
 
+    #[tokio::test]
+    async fn test_rotate_identity_state() {
+        let state = CliState::test().unwrap();
+        let vault = Vault::create();
+        let mut identity_state = state
+            .create_identity_state(Some("alice"), vault.clone())
+            .await
+            .unwrap();
+        let identity = identity_state.config().identity();
+
+        let rotated = identity_state.rotate_root_key(vault).await.unwrap();
+        assert_eq!(rotated.identifier(), identity.identifier());
+        assert_eq!(rotated.compare(&identity), IdentityHistoryComparison::Newer);
+
+        // the new change history is persisted in the state and in the repository
+        let reloaded = state.identities.get("alice").unwrap().config().identity();
+        assert_eq!(reloaded.compare(&rotated), IdentityHistoryComparison::Equal);
+        let stored = state
+            .identities
+            .identities_repository()
+            .await
+            .unwrap()
+            .get_identity(&identity.identifier())
+            .await
+            .unwrap();
+        assert_eq!(stored.compare(&rotated), IdentityHistoryComparison::Equal);
+
+        // an older change history is rejected
+        assert!(identity_state.update_identity(identity).is_err());
+    }
+
     #[ockam_macros::test(crate = "ockam")]
     async fn integration(ctx: &mut ockam::Context) -> ockam::Result<()> {
         let sut = CliState::test()?;
@@ -489,4 +520,61 @@ mod tests {
         ctx.stop().await?;
         Ok(())
     }
+
+    #[test]
+    fn test_node_setup_resources() {
+        let setup = NodeSetupConfig::default()
+            .add_resource(NodeResourceConfig::new(
+                NodeResourceKind::Inlet,
+                "inlet",
+                "/node/inlet",
+                vec![1],
+            ))
+            .add_resource(NodeResourceConfig::new(
+                NodeResourceKind::Outlet,
+                "outlet",
+                "/node/outlet",
+                vec![2],
+            ))
+            .add_resource(NodeResourceConfig::new(
+                NodeResourceKind::Service,
+                "echo",
+                "/node/services/echo",
+                vec![3],
+            ))
+            .add_resource(NodeResourceConfig::new(
+                NodeResourceKind::Inlet,
+                "inlet",
+                "/node/inlet",
+                vec![4],
+            ));
+
+        // resources are recreated in dependency order, a resource is recorded only once
+        let resources: Vec<(&str, &[u8])> = setup
+            .resources()
+            .into_iter()
+            .map(|r| (r.name.as_str(), r.body.as_slice()))
+            .collect();
+        assert_eq!(
+            resources,
+            vec![
+                ("echo", [3].as_slice()),
+                ("outlet", [2].as_slice()),
+                ("inlet", [4].as_slice())
+            ]
+        );
+
+        // resources survive a round trip to the setup file
+        let json = serde_json::to_string(&setup).unwrap();
+        let reloaded: NodeSetupConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(reloaded, setup);
+
+        let setup = setup.remove_resource(NodeResourceKind::Outlet, "outlet");
+        assert_eq!(setup.resources().len(), 2);
+
+        // setup files written before resources were recorded can still be read
+        let setup: NodeSetupConfig =
+            serde_json::from_str(r#"{"verbose":0,"transports":[]}"#).unwrap();
+        assert!(setup.resources().is_empty());
+    }
 }
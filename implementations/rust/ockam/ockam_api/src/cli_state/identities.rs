@@ -94,6 +94,38 @@ impl IdentityState {
         self.persist()
     }
 
+    /// Replace the identity with a newer version of its change history,
+    /// for example after one of its keys has been rotated
+    pub fn update_identity(&mut self, identity: Identity) -> Result<()> {
+        match identity.compare(&self.config.identity) {
+            IdentityHistoryComparison::Equal => Ok(()),
+            IdentityHistoryComparison::Newer => {
+                self.config.identity = identity;
+                self.persist()
+            }
+            _ => Err(CliStateError::Invalid(format!(
+                "The change history of identity '{}' can only be replaced by a newer one",
+                &self.name
+            ))),
+        }
+    }
+
+    /// Rotate the root key of the identity with a new key created in the given vault.
+    /// The new change history is stored in the identities repository, which is shared with
+    /// the running nodes, and in this state
+    pub async fn rotate_root_key(&mut self, vault: Arc<dyn IdentitiesVault>) -> Result<Identity> {
+        let identities = self.make_identities(vault).await?;
+        identities
+            .repository()
+            .update_identity(&self.config.identity)
+            .await?;
+        let identity = identities
+            .rotate_root_key(&self.config.identity.identifier())
+            .await?;
+        self.update_identity(identity.clone())?;
+        Ok(identity)
+    }
+
     fn build_data_path(path: &Path) -> PathBuf {
         path.parent().expect("Should have parent").join("data")
     }
@@ -6,10 +6,13 @@ use crate::cli_state::{
 use crate::config::lookup::ProjectLookup;
 use crate::nodes::models::transport::{CreateTransportJson, TransportMode, TransportType};
 use nix::errno::Errno;
+use ockam::stream::StreamIndices;
 use ockam_identity::{IdentitiesVault, Identity, LmdbStorage};
+use ockam_node::FileKeyValueStorage;
 use ockam_vault::Vault;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
@@ -153,6 +156,23 @@ impl NodeState {
         Ok(LmdbStorage::new(self.paths.policies_storage()).await?)
     }
 
+    pub async fn enrollment_tokens_storage(&self) -> Result<LmdbStorage> {
+        Ok(LmdbStorage::new(self.paths.enrollment_tokens_storage()).await?)
+    }
+
+    /// Directory of the streams of the stream service at the given address
+    pub fn streams_dir(&self, service: &str) -> PathBuf {
+        self.paths.streams(service)
+    }
+
+    /// Indices of the consumers of the stream service at the given address
+    pub async fn stream_indices_storage(
+        &self,
+        service: &str,
+    ) -> Result<FileKeyValueStorage<String, StreamIndices>> {
+        Ok(FileKeyValueStorage::create(&self.paths.stream_indices_storage(service)).await?)
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -305,11 +325,15 @@ pub struct NodeSetupConfig {
     pub authority_node: Option<bool>,
     pub project: Option<ProjectLookup>,
     transports: Vec<CreateTransportJson>,
-    // TODO
-    // secure_channels: ?,
-    // inlets: ?,
-    // outlets: ?,
-    // services: ?,
+    /// Address of the metrics endpoint, if the node exposes one.
+    /// The field might be missing in previous configuration files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    metrics_address: Option<SocketAddr>,
+    /// Resources created through the node API, recreated when the node is started again.
+    /// Policies don't need to be recorded here since they are kept in the policies storage.
+    /// The field might be missing in previous configuration files.
+    #[serde(default)]
+    resources: Vec<NodeResourceConfig>,
 }
 
 impl NodeSetupConfig {
@@ -323,6 +347,15 @@ impl NodeSetupConfig {
         self
     }
 
+    pub fn set_metrics_address(mut self, metrics_address: Option<SocketAddr>) -> Self {
+        self.metrics_address = metrics_address;
+        self
+    }
+
+    pub fn metrics_address(&self) -> Option<&SocketAddr> {
+        self.metrics_address.as_ref()
+    }
+
     pub fn set_project(&mut self, project: ProjectLookup) -> &mut Self {
         self.project = Some(project);
         self
@@ -339,6 +372,71 @@ impl NodeSetupConfig {
         self.transports.push(transport);
         self
     }
+
+    /// Record a resource, replacing any resource of the same kind with the same name
+    pub fn add_resource(mut self, resource: NodeResourceConfig) -> Self {
+        self.resources
+            .retain(|r| r.kind != resource.kind || r.name != resource.name);
+        self.resources.push(resource);
+        self
+    }
+
+    pub fn remove_resource(mut self, kind: NodeResourceKind, name: &str) -> Self {
+        self.resources.retain(|r| r.kind != kind || r.name != name);
+        self
+    }
+
+    /// Recorded resources in the order in which they must be recreated: by kind,
+    /// then in the order in which they were created
+    pub fn resources(&self) -> Vec<&NodeResourceConfig> {
+        let mut resources: Vec<&NodeResourceConfig> = self.resources.iter().collect();
+        resources.sort_by_key(|r| r.kind);
+        resources
+    }
+}
+
+/// A resource created through the node API, recorded with the request which created it
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct NodeResourceConfig {
+    pub kind: NodeResourceKind,
+    /// Name of the resource within its kind: the alias of an inlet or an outlet,
+    /// the remote address of a forwarder or the address of a listener or a service
+    pub name: String,
+    /// Path of the request which created the resource
+    pub path: String,
+    /// CBOR encoded body of the request which created the resource
+    #[serde(with = "hex")]
+    pub body: Vec<u8>,
+}
+
+impl NodeResourceConfig {
+    pub fn new(
+        kind: NodeResourceKind,
+        name: impl Into<String>,
+        path: impl Into<String>,
+        body: Vec<u8>,
+    ) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            path: path.into(),
+            body,
+        }
+    }
+}
+
+/// Kinds of the resources recorded in the node setup, in dependency order:
+/// services and outlets must exist before the forwarders and inlets reaching them
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeResourceKind {
+    SecureChannelListener,
+    Service,
+    Outlet,
+    UdpOutlet,
+    Forwarder,
+    Inlet,
+    UdpInlet,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -384,6 +482,18 @@ impl NodePaths {
     fn policies_storage(&self) -> PathBuf {
         self.path.join("policies_storage.lmdb")
     }
+
+    fn enrollment_tokens_storage(&self) -> PathBuf {
+        self.path.join("enrollment_tokens_storage.lmdb")
+    }
+
+    fn streams(&self, service: &str) -> PathBuf {
+        self.path.join("streams").join(service)
+    }
+
+    fn stream_indices_storage(&self, service: &str) -> PathBuf {
+        self.path.join(format!("stream_indices_{service}.json"))
+    }
 }
 
 mod traits {
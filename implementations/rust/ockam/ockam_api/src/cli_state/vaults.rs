@@ -1,7 +1,9 @@
 use super::Result;
 use crate::cli_state::traits::StateItemTrait;
 use crate::cli_state::{CliStateError, StateDirTrait};
+use ockam_core::env::get_env;
 use ockam_identity::IdentitiesVault;
+use ockam_vault::storage::{EncryptedPersistentStorage, VaultStorageKey};
 use ockam_vault::Vault;
 use ockam_vault_aws::{AwsKmsConfig, AwsSecurityModule};
 use serde::{Deserialize, Serialize};
@@ -37,6 +39,15 @@ pub struct VaultState {
     config: VaultConfig,
 }
 
+/// Environment variable containing the hex-encoded 256 bits key of encrypted vaults
+pub const OCKAM_VAULT_KEY: &str = "OCKAM_VAULT_KEY";
+/// Environment variable containing the passphrase of encrypted vaults
+pub const OCKAM_VAULT_PASSPHRASE: &str = "OCKAM_VAULT_PASSPHRASE";
+/// Environment variable containing the new hex-encoded key when re-keying a vault
+pub const OCKAM_VAULT_NEW_KEY: &str = "OCKAM_VAULT_NEW_KEY";
+/// Environment variable containing the new passphrase when re-keying a vault
+pub const OCKAM_VAULT_NEW_PASSPHRASE: &str = "OCKAM_VAULT_NEW_PASSPHRASE";
+
 impl VaultState {
     pub async fn get(&self) -> Result<Arc<Vault>> {
         if self.config.aws_kms {
@@ -44,6 +55,13 @@ impl VaultState {
             Ok(Vault::create_with_security_module(
                 AwsSecurityModule::create(config).await?,
             ))
+        } else if self.config.encrypted {
+            let vault = Vault::create_with_encrypted_persistent_storage_path(
+                self.vault_file_path().as_path(),
+                Self::storage_key()?,
+            )
+            .await?;
+            Ok(vault)
         } else {
             let vault =
                 Vault::create_with_persistent_storage_path(self.vault_file_path().as_path())
@@ -52,6 +70,53 @@ impl VaultState {
         }
     }
 
+    /// Encrypt the vault file at rest in place, using the key provided by the environment
+    pub async fn encrypt(&mut self) -> Result<()> {
+        if self.config.aws_kms {
+            return Err(CliStateError::Invalid(
+                "an AWS KMS vault can't be encrypted".to_string(),
+            ));
+        }
+        if self.config.encrypted {
+            return Err(CliStateError::Invalid(
+                "the vault is already encrypted".to_string(),
+            ));
+        }
+        EncryptedPersistentStorage::create(self.vault_file_path(), Self::storage_key()?).await?;
+        self.config.encrypted = true;
+        self.persist()
+    }
+
+    /// Encrypt the vault file with a new key. The current key is provided by the environment
+    pub async fn rekey(&self, new_key: VaultStorageKey) -> Result<()> {
+        if !self.config.encrypted {
+            return Err(CliStateError::Invalid(
+                "the vault is not encrypted".to_string(),
+            ));
+        }
+        EncryptedPersistentStorage::rekey(self.vault_file_path(), Self::storage_key()?, new_key)
+            .await?;
+        Ok(())
+    }
+
+    /// Return the key used to encrypt vaults at rest, as provided by the environment
+    pub fn storage_key() -> Result<VaultStorageKey> {
+        Self::storage_key_from_env(OCKAM_VAULT_KEY, OCKAM_VAULT_PASSPHRASE)
+    }
+
+    /// Return a vault key from either a hex-encoded key or a passphrase environment variable
+    pub fn storage_key_from_env(key_var: &str, passphrase_var: &str) -> Result<VaultStorageKey> {
+        if let Some(key) = get_env::<String>(key_var)? {
+            return Ok(VaultStorageKey::from_hex(&key)?);
+        }
+        if let Some(passphrase) = get_env::<String>(passphrase_var)? {
+            return Ok(VaultStorageKey::passphrase(passphrase));
+        }
+        Err(CliStateError::Invalid(format!(
+            "the vault is encrypted, please provide its key with {key_var} or {passphrase_var}"
+        )))
+    }
+
     fn build_data_path(name: &str, path: &Path) -> PathBuf {
         path.parent()
             .expect("Should have parent")
@@ -59,11 +124,18 @@ impl VaultState {
             .join(format!("{name}-storage.json"))
     }
 
+    pub fn config(&self) -> &VaultConfig {
+        &self.config
+    }
+
     pub fn vault_file_path(&self) -> &PathBuf {
         &self.data_path
     }
 
     pub async fn identities_vault(&self) -> Result<Arc<dyn IdentitiesVault>> {
+        if self.config.encrypted {
+            return Ok(self.get().await?);
+        }
         let path = self.vault_file_path().clone();
         let vault = Vault::create_with_persistent_storage_path(path.as_path()).await?;
         Ok(vault)
@@ -85,6 +157,9 @@ impl Display for VaultState {
                 false => "OCKAM",
             }
         )?;
+        if self.config.is_encrypted() {
+            writeln!(f, "Encrypted: true")?;
+        }
         Ok(())
     }
 }
@@ -93,16 +168,27 @@ impl Display for VaultState {
 pub struct VaultConfig {
     #[serde(default)]
     aws_kms: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    encrypted: bool,
 }
 
 impl VaultConfig {
-    pub fn new(aws_kms: bool) -> Result<Self> {
-        Ok(Self { aws_kms })
+    pub fn new(aws_kms: bool, encrypted: bool) -> Result<Self> {
+        if aws_kms && encrypted {
+            return Err(CliStateError::Invalid(
+                "an AWS KMS vault can't be encrypted".to_string(),
+            ));
+        }
+        Ok(Self { aws_kms, encrypted })
     }
 
     pub fn is_aws(&self) -> bool {
         self.aws_kms
     }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
 }
 
 mod traits {
@@ -12,7 +12,8 @@ use ockam_identity::credential::Credential;
 use ockam_identity::{
     identities, AuthorityService, CredentialsMemoryRetriever, CredentialsRetriever, Identities,
     Identity, IdentityIdentifier, RemoteCredentialsRetriever, RemoteCredentialsRetrieverInfo,
-    SecureChannels, TrustContext,
+    RevocationListRetriever, SecureChannels, TrustContext,
+    DEFAULT_REVOCATION_LIST_REFRESH_INTERVAL,
 };
 use ockam_multiaddr::MultiAddr;
 use ockam_transport_tcp::TcpTransport;
@@ -20,6 +21,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use super::lookup::ProjectLookup;
 
@@ -78,6 +80,9 @@ impl OckamConfig {
 pub struct TrustContextConfig {
     id: String,
     authority: Option<TrustAuthorityConfig>,
+    /// Other authorities trusted within this trust context
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    additional_authorities: Vec<TrustAuthorityConfig>,
     path: Option<PathBuf>,
 }
 
@@ -86,6 +91,7 @@ impl TrustContextConfig {
         Self {
             id,
             authority,
+            additional_authorities: vec![],
             path: None,
         }
     }
@@ -108,36 +114,41 @@ impl TrustContextConfig {
             .ok_or_else(|| ApiError::generic("Missing authority on trust context config"))
     }
 
+    /// Return the authorities trusted in addition to the main authority
+    pub fn additional_authorities(&self) -> &[TrustAuthorityConfig] {
+        &self.additional_authorities
+    }
+
+    /// Trust an additional authority, for example while migrating to a new authority node
+    pub fn with_additional_authority(mut self, authority: TrustAuthorityConfig) -> Self {
+        self.additional_authorities.push(authority);
+        self
+    }
+
     pub async fn to_trust_context(
         &self,
         secure_channels: Arc<SecureChannels>,
         tcp_transport: Option<TcpTransport>,
     ) -> Result<TrustContext> {
-        let authority =
-            if let Some(authority_config) = self.authority.as_ref() {
-                let identity = authority_config.identity().await?;
-                let credential_retriever =
-                    if let Some(retriever_type) = &authority_config.own_credential {
-                        Some(retriever_type.to_credential_retriever(
-                            secure_channels.clone(),
-                            tcp_transport,
-                            Default::default(), /* FIXME: Replace with proper shared instance */
-                        ).await?)
-                    } else {
-                        None
-                    };
-
-                Some(AuthorityService::new(
-                    secure_channels.identities().identities_reader(),
-                    secure_channels.identities().credentials(),
-                    identity.identifier(),
-                    credential_retriever,
-                ))
-            } else {
-                None
-            };
+        let authority = if let Some(authority_config) = self.authority.as_ref() {
+            Some(
+                authority_config
+                    .to_authority_service(secure_channels.clone(), tcp_transport.as_ref())
+                    .await?,
+            )
+        } else {
+            None
+        };
 
-        Ok(TrustContext::new(self.id.clone(), authority))
+        let mut trust_context = TrustContext::new(self.id.clone(), authority);
+        for authority_config in &self.additional_authorities {
+            trust_context = trust_context.with_authority(
+                authority_config
+                    .to_authority_service(secure_channels.clone(), tcp_transport.as_ref())
+                    .await?,
+            );
+        }
+        Ok(trust_context)
     }
 
     pub fn from_authority_identity(
@@ -226,6 +237,12 @@ impl TryFrom<ProjectLookup> for TrustContextConfig {
 pub struct TrustAuthorityConfig {
     identity: String,
     own_credential: Option<CredentialRetrieverConfig>,
+    /// Interval after which the revocation list of the authority is retrieved again
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    revocation_refresh_interval: Option<Duration>,
+    /// Names of the attributes this authority is trusted for. All attributes if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    allowed_attributes: Option<Vec<String>>,
 }
 
 impl TrustAuthorityConfig {
@@ -233,9 +250,30 @@ impl TrustAuthorityConfig {
         Self {
             identity,
             own_credential,
+            revocation_refresh_interval: None,
+            allowed_attributes: None,
         }
     }
 
+    pub fn with_allowed_attributes(mut self, attributes: Vec<String>) -> Self {
+        self.allowed_attributes = Some(attributes);
+        self
+    }
+
+    pub fn allowed_attributes(&self) -> Option<&[String]> {
+        self.allowed_attributes.as_deref()
+    }
+
+    pub fn with_revocation_refresh_interval(mut self, interval: Duration) -> Self {
+        self.revocation_refresh_interval = Some(interval);
+        self
+    }
+
+    pub fn revocation_refresh_interval(&self) -> Duration {
+        self.revocation_refresh_interval
+            .unwrap_or(DEFAULT_REVOCATION_LIST_REFRESH_INTERVAL)
+    }
+
     pub fn identity_str(&self) -> &str {
         &self.identity
     }
@@ -255,6 +293,52 @@ impl TrustAuthorityConfig {
             .as_ref()
             .ok_or_else(|| ApiError::generic("Missing own credential on trust authority config"))
     }
+
+    async fn to_authority_service(
+        &self,
+        secure_channels: Arc<SecureChannels>,
+        tcp_transport: Option<&TcpTransport>,
+    ) -> Result<AuthorityService> {
+        let identity = self.identity().await?;
+        let credential_retriever = if let Some(retriever_type) = &self.own_credential {
+            Some(
+                retriever_type
+                    .to_credential_retriever(
+                        secure_channels.clone(),
+                        tcp_transport,
+                        Default::default(), /* FIXME: Replace with proper shared instance */
+                    )
+                    .await?,
+            )
+        } else {
+            None
+        };
+        let revocation_list_retriever = if let Some(retriever_type) = &self.own_credential {
+            retriever_type
+                .to_revocation_list_retriever(
+                    secure_channels.clone(),
+                    Default::default(), /* FIXME: Replace with proper shared instance */
+                )
+                .await?
+        } else {
+            None
+        };
+
+        let mut authority = AuthorityService::new(
+            secure_channels.identities().identities_reader(),
+            secure_channels.identities().credentials(),
+            identity.identifier(),
+            credential_retriever,
+        );
+        if let Some(retriever) = revocation_list_retriever {
+            authority =
+                authority.with_revocation_list(retriever, self.revocation_refresh_interval());
+        }
+        if let Some(allowed_attributes) = &self.allowed_attributes {
+            authority = authority.with_allowed_attributes(allowed_attributes.clone());
+        }
+        Ok(authority)
+    }
 }
 
 /// Type of credential retriever
@@ -272,7 +356,7 @@ impl CredentialRetrieverConfig {
     async fn to_credential_retriever(
         &self,
         secure_channels: Arc<SecureChannels>,
-        tcp_transport: Option<TcpTransport>,
+        tcp_transport: Option<&TcpTransport>,
         flow_controls: FlowControls,
     ) -> Result<Arc<dyn CredentialsRetriever>> {
         match self {
@@ -284,20 +368,30 @@ impl CredentialRetrieverConfig {
             )),
             CredentialRetrieverConfig::FromCredentialIssuer(issuer_config) => {
                 let _ = tcp_transport.ok_or_else(|| ApiError::generic("TCP Transport was not provided when credential retriever was defined as an issuer."))?;
-                let credential_issuer_info = RemoteCredentialsRetrieverInfo::new(
-                    issuer_config.resolve_identity().await?.identifier(),
-                    issuer_config.resolve_route().await?,
-                    DefaultAddress::CREDENTIAL_ISSUER.into(),
-                );
-
-                Ok(Arc::new(RemoteCredentialsRetriever::new(
-                    secure_channels,
-                    credential_issuer_info,
-                    flow_controls,
-                )))
+                Ok(Arc::new(
+                    issuer_config
+                        .to_remote_retriever(secure_channels, flow_controls)
+                        .await?,
+                ))
             }
         }
     }
+
+    /// Only a credential issuer publishes a revocation list
+    async fn to_revocation_list_retriever(
+        &self,
+        secure_channels: Arc<SecureChannels>,
+        flow_controls: FlowControls,
+    ) -> Result<Option<Arc<dyn RevocationListRetriever>>> {
+        match self {
+            CredentialRetrieverConfig::FromCredentialIssuer(issuer_config) => Ok(Some(Arc::new(
+                issuer_config
+                    .to_remote_retriever(secure_channels, flow_controls)
+                    .await?,
+            ))),
+            _ => Ok(None),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -388,4 +482,22 @@ impl CredentialIssuerConfig {
             .decode_identity(&encoded)
             .await
     }
+
+    async fn to_remote_retriever(
+        &self,
+        secure_channels: Arc<SecureChannels>,
+        flow_controls: FlowControls,
+    ) -> Result<RemoteCredentialsRetriever> {
+        let credential_issuer_info = RemoteCredentialsRetrieverInfo::new(
+            self.resolve_identity().await?.identifier(),
+            self.resolve_route().await?,
+            DefaultAddress::CREDENTIAL_ISSUER.into(),
+        );
+
+        Ok(RemoteCredentialsRetriever::new(
+            secure_channels,
+            credential_issuer_info,
+            flow_controls,
+        ))
+    }
 }
@@ -1,4 +1,4 @@
-use ockam::{Any, Context, Result, Routed, Worker};
+use ockam::{Any, Context, MessagePriority, Result, Routed, Worker};
 use ockam_core::NeutralMessage;
 use tracing as log;
 
@@ -11,7 +11,13 @@ impl Worker for Echoer {
 
     async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
         log::debug!(src = %msg.src_addr(), from = %msg.sender()?, to = %msg.return_route().step()?, "echoing back");
-        ctx.send(msg.return_route(), NeutralMessage::from(msg.take_payload()))
-            .await
+        // Pings of the sessions are echoed back with their priority
+        let priority = MessagePriority::of(msg.local_message());
+        ctx.send_with_priority(
+            msg.return_route(),
+            NeutralMessage::from(msg.take_payload()),
+            priority,
+        )
+        .await
     }
 }
@@ -3,7 +3,7 @@ mod test {
     use std::sync::atomic::{AtomicBool, Ordering};
     use std::time::Duration;
 
-    use bytes::{Buf, BufMut, BytesMut};
+    use bytes::{Buf, BufMut, Bytes, BytesMut};
     use indexmap::IndexMap;
     use kafka_protocol::messages::produce_request::{PartitionProduceData, TopicProduceData};
     use kafka_protocol::messages::{
@@ -47,6 +47,7 @@ mod test {
     use crate::kafka::{
         KafkaInletController, KafkaPortalListener, KafkaSecureChannelControllerImpl,
     };
+    use crate::nodes::models::services::{KafkaKeyEncryption, KafkaRecordEncryption};
     use crate::nodes::registry::KafkaServiceKind;
     use crate::test::NodeManagerHandle;
     use crate::DefaultAddress;
@@ -79,6 +80,7 @@ mod test {
         listener_address: Address,
         outlet_address: Address,
         kind: KafkaServiceKind,
+        record_encryption: KafkaRecordEncryption,
     ) -> ockam::Result<u16> {
         let flow_controls = &handle.flow_controls;
         let flow_control_id = flow_controls.generate_id();
@@ -149,6 +151,7 @@ mod test {
             secure_channel_controller.into_trait(),
             listener_address,
             flow_controls.clone(),
+            record_encryption,
         )
         .await?;
 
@@ -160,6 +163,97 @@ mod test {
     async fn producer__flow_with_mock_kafka__content_encryption_and_decryption(
         context: &mut Context,
     ) -> ockam::Result<()> {
+        let (encrypted_record, plain_record) = produce_and_fetch_with_mock_kafka(
+            context,
+            Default::default(),
+            create_record(None, Default::default()),
+        )
+        .await?;
+
+        assert_ne!(
+            encrypted_record.value.as_ref().unwrap(),
+            "hello world!".as_bytes()
+        );
+        assert_eq!(
+            plain_record.value.as_ref().unwrap(),
+            "hello world!".as_bytes()
+        );
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[ockam_macros::test(timeout = 5_000)]
+    async fn producer__flow_with_mock_kafka__key_and_headers_encryption(
+        context: &mut Context,
+    ) -> ockam::Result<()> {
+        let mut headers = IndexMap::new();
+        headers.insert(
+            StrBytes::from_str("customer-name"),
+            Some(Bytes::from("Alice")),
+        );
+        //null header values can't be used: kafka-protocol doesn't decode the ones it encodes
+        headers.insert(StrBytes::from_str("empty"), Some(Bytes::new()));
+
+        let (encrypted_record, plain_record) = produce_and_fetch_with_mock_kafka(
+            context,
+            KafkaRecordEncryption {
+                encrypt_headers: true,
+                keys: KafkaKeyEncryption::Deterministic(b"secret".to_vec()),
+            },
+            create_record(Some(Bytes::from("customer-42")), headers.clone()),
+        )
+        .await?;
+
+        //the broker can partition by key without knowing it
+        let encrypted_key = encrypted_record.key.as_ref().unwrap();
+        assert_ne!(encrypted_key, "customer-42".as_bytes());
+        assert_eq!(encrypted_key.len(), 32);
+        assert_eq!(encrypted_record.headers.len(), 2);
+        assert!(encrypted_record
+            .headers
+            .keys()
+            .all(|name| name.starts_with("ockam.encrypted.")));
+
+        assert_eq!(
+            plain_record.value.as_ref().unwrap(),
+            "hello world!".as_bytes()
+        );
+        assert_eq!(plain_record.key.as_ref().unwrap(), "customer-42".as_bytes());
+        assert_eq!(plain_record.headers, headers);
+        Ok(())
+    }
+
+    #[allow(non_snake_case)]
+    #[ockam_macros::test(timeout = 5_000)]
+    async fn producer__flow_with_mock_kafka__randomized_key_encryption(
+        context: &mut Context,
+    ) -> ockam::Result<()> {
+        let (encrypted_record, plain_record) = produce_and_fetch_with_mock_kafka(
+            context,
+            KafkaRecordEncryption {
+                encrypt_headers: false,
+                keys: KafkaKeyEncryption::Randomized,
+            },
+            create_record(Some(Bytes::from("customer-42")), IndexMap::new()),
+        )
+        .await?;
+
+        assert_ne!(
+            encrypted_record.key.as_ref().unwrap(),
+            "customer-42".as_bytes()
+        );
+        assert_eq!(plain_record.key.as_ref().unwrap(), "customer-42".as_bytes());
+        assert!(plain_record.headers.is_empty());
+        Ok(())
+    }
+
+    //produces a record through a producer service and fetches it back through
+    //a consumer service, returns the record as seen by kafka and by the consumer
+    async fn produce_and_fetch_with_mock_kafka(
+        context: &mut Context,
+        producer_encryption: KafkaRecordEncryption,
+        produced_record: Record,
+    ) -> ockam::Result<(Record, Record)> {
         let handler = crate::util::test::start_manager_for_tests(context).await?;
 
         let consumer_bootstrap_port = create_kafka_service(
@@ -168,6 +262,7 @@ mod test {
             Address::from_string("kafka_consumer_listener"),
             Address::from_string("kafka_consumer_outlet"),
             KafkaServiceKind::Consumer,
+            Default::default(),
         )
         .await?;
 
@@ -177,6 +272,7 @@ mod test {
             Address::from_string("kafka_producer_listener"),
             Address::from_string("kafka_producer_outlet"),
             KafkaServiceKind::Producer,
+            producer_encryption,
         )
         .await?;
 
@@ -215,6 +311,7 @@ mod test {
         let request = simulate_kafka_producer_and_read_request(
             producer_bootstrap_port,
             &mut producer_mock_kafka,
+            produced_record,
         )
         .await;
 
@@ -233,12 +330,9 @@ mod test {
             .unwrap();
 
         let mut encrypted_body = BytesMut::from(encrypted_body.as_ref());
-        let records = RecordBatchDecoder::decode(&mut encrypted_body).unwrap();
-
-        assert_ne!(
-            records.get(0).unwrap().value.as_ref().unwrap(),
-            "hello world!".as_bytes()
-        );
+        let encrypted_record = RecordBatchDecoder::decode(&mut encrypted_body)
+            .unwrap()
+            .remove(0);
 
         let mut consumer_mock_kafka = TcpServerSimulator::start("127.0.0.1:0").await;
         handler
@@ -270,28 +364,26 @@ mod test {
             .unwrap();
 
         let mut plain_content = BytesMut::from(plain_content.as_ref());
-        let records = RecordBatchDecoder::decode(&mut plain_content).unwrap();
-
-        assert_eq!(
-            records.get(0).as_ref().unwrap().value.as_ref().unwrap(),
-            "hello world!".as_bytes()
-        );
+        let plain_record = RecordBatchDecoder::decode(&mut plain_content)
+            .unwrap()
+            .remove(0);
 
         context.stop().await?;
         consumer_mock_kafka.destroy_and_wait().await;
         producer_mock_kafka.destroy_and_wait().await;
-        Ok(())
+        Ok((encrypted_record, plain_record))
     }
 
     async fn simulate_kafka_producer_and_read_request(
         producer_bootstrap_port: u16,
         producer_mock_kafka: &mut TcpServerSimulator,
+        record: Record,
     ) -> ProduceRequest {
         let mut kafka_client_connection =
             TcpStream::connect(format!("127.0.0.1:{producer_bootstrap_port}"))
                 .await
                 .unwrap();
-        send_kafka_produce_request(&mut kafka_client_connection).await;
+        send_kafka_produce_request(&mut kafka_client_connection, record).await;
         read_kafka_request::<&mut DuplexStream, RequestHeader, ProduceRequest>(
             producer_mock_kafka.stream(),
             ApiKey::ProduceKey,
@@ -299,7 +391,24 @@ mod test {
         .await
     }
 
-    async fn send_kafka_produce_request(stream: &mut TcpStream) {
+    fn create_record(key: Option<Bytes>, headers: IndexMap<StrBytes, Option<Bytes>>) -> Record {
+        Record {
+            transactional: false,
+            control: false,
+            partition_leader_epoch: 0,
+            producer_id: 0,
+            producer_epoch: 0,
+            timestamp_type: TimestampType::Creation,
+            offset: 0,
+            sequence: 0,
+            timestamp: 0,
+            key,
+            value: Some(BytesMut::from("hello world!").freeze()),
+            headers,
+        }
+    }
+
+    async fn send_kafka_produce_request(stream: &mut TcpStream, record: Record) {
         let header = RequestHeader::builder()
             .request_api_key(ApiKey::ProduceKey as i16)
             .request_api_version(TEST_KAFKA_API_VERSION)
@@ -312,21 +421,7 @@ mod test {
         let mut encoded = BytesMut::new();
         RecordBatchEncoder::encode(
             &mut encoded,
-            vec![Record {
-                transactional: false,
-                control: false,
-                partition_leader_epoch: 0,
-                producer_id: 0,
-                producer_epoch: 0,
-                timestamp_type: TimestampType::Creation,
-                offset: 0,
-                sequence: 0,
-                timestamp: 0,
-                key: None,
-                value: Some(BytesMut::from("hello world!").freeze()),
-                headers: Default::default(),
-            }]
-            .iter(),
+            vec![record].iter(),
             &RecordEncodeOptions {
                 version: 2,
                 compression: Compression::None,
@@ -9,6 +9,7 @@ use crate::kafka::inlet_controller::KafkaInletController;
 use crate::kafka::portal_worker::KafkaPortalWorker;
 use crate::kafka::protocol_aware::TopicUuidMap;
 use crate::kafka::secure_channel_map::KafkaSecureChannelController;
+use crate::nodes::models::services::KafkaRecordEncryption;
 
 ///First point of ingress of kafka connections, at the first message it spawns new stateful workers
 /// to take care of the connection.
@@ -17,6 +18,7 @@ pub(crate) struct KafkaPortalListener {
     secure_channel_controller: Arc<dyn KafkaSecureChannelController>,
     uuid_to_name: TopicUuidMap,
     flow_controls: FlowControls,
+    record_encryption: KafkaRecordEncryption,
 }
 
 #[ockam::worker]
@@ -55,6 +57,7 @@ impl Worker for KafkaPortalListener {
             &self.flow_controls,
             flow_control_id,
             route![inlet_responder_address],
+            self.record_encryption.clone(),
         )
         .await?;
 
@@ -84,6 +87,7 @@ impl KafkaPortalListener {
         secure_channel_controller: Arc<dyn KafkaSecureChannelController>,
         listener_address: Address,
         flow_control: FlowControls,
+        record_encryption: KafkaRecordEncryption,
     ) -> ockam_core::Result<()> {
         context
             .start_worker(
@@ -93,6 +97,7 @@ impl KafkaPortalListener {
                     secure_channel_controller,
                     uuid_to_name: Default::default(),
                     flow_controls: flow_control,
+                    record_encryption,
                 },
                 AllowAll,
                 AllowAll,
@@ -15,6 +15,7 @@ use crate::kafka::inlet_controller::KafkaInletController;
 use crate::kafka::length_delimited::{length_encode, KafkaMessageDecoder};
 use crate::kafka::protocol_aware::{Interceptor, TopicUuidMap};
 use crate::kafka::secure_channel_map::KafkaSecureChannelController;
+use crate::nodes::models::services::KafkaRecordEncryption;
 
 ///by default kafka supports up to 1MB messages, 16MB is the maximum suggested
 pub(crate) const MAX_KAFKA_MESSAGE_SIZE: u32 = 16 * 1024 * 1024;
@@ -129,9 +130,13 @@ impl Worker for KafkaPortalWorker {
                     context.stop_worker(context.address()).await?;
                 }
             }
-            PortalMessage::Ping | PortalMessage::Pong => {
-                self.forward(context, routed_message).await?
-            }
+            PortalMessage::Ping
+            | PortalMessage::Pong
+            | PortalMessage::WriteClose
+            | PortalMessage::ReadClose => self.forward(context, routed_message).await?,
+            // Since there is no relation between incoming and outgoing payload messages
+            // credits can't be forwarded, without them both sides don't enforce a window
+            PortalMessage::Credit(_) => {}
         }
 
         Ok(())
@@ -273,8 +278,10 @@ impl KafkaPortalWorker {
         flow_controls: &FlowControls,
         flow_control_id: Option<FlowControlId>,
         inlet_responder_route: Route,
+        record_encryption: KafkaRecordEncryption,
     ) -> ockam_core::Result<Address> {
-        let shared_protocol_state = Interceptor::new(secure_channel_controller, uuid_to_name);
+        let shared_protocol_state =
+            Interceptor::new(secure_channel_controller, uuid_to_name, record_encryption);
 
         let requests_worker_address = Address::random_tagged("KafkaPortalWorker.requests");
         let responses_worker_address = Address::random_tagged("KafkaPortalWorker.responses");
@@ -648,6 +655,7 @@ mod test {
             &flow_controls,
             None,
             route![context.address()],
+            Default::default(),
         )
         .await
         .unwrap()
@@ -712,6 +720,7 @@ mod test {
             &flow_controls,
             None,
             route![context.address()],
+            Default::default(),
         )
         .await?;
 
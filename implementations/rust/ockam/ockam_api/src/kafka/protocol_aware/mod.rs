@@ -1,6 +1,11 @@
+use crate::kafka::portal_worker::InterceptError;
+use crate::kafka::protocol_aware::utils::{hmac_sha256, string_to_str_bytes};
 use crate::kafka::secure_channel_map::{KafkaSecureChannelController, UniqueSecureChannelId};
+use crate::nodes::models::services::{KafkaKeyEncryption, KafkaRecordEncryption};
+use bytes::Bytes;
 use kafka_protocol::messages::ApiKey;
-use minicbor::{Decode, Encode};
+use kafka_protocol::records::Record;
+use minicbor::{Decode, Decoder, Encode};
 use ockam_core::compat::{
     collections::HashMap,
     fmt::Debug,
@@ -9,6 +14,8 @@ use ockam_core::compat::{
 use ockam_core::AsyncTryClone;
 #[cfg(feature = "tag")]
 use ockam_core::TypeTag;
+use ockam_node::Context;
+use std::io::{Error, ErrorKind};
 
 mod request;
 mod response;
@@ -23,6 +30,17 @@ struct RequestInfo {
 
 type CorrelationId = i32;
 
+/// Record header replacing the headers of a record when they are encrypted
+const ENCRYPTED_HEADERS_HEADER: &str = "ockam.encrypted.headers";
+
+/// Record header set when the key of a record is encrypted. It holds the
+/// encrypted key when the record key was replaced by a keyed hash, and is
+/// empty when the record key itself is encrypted.
+///
+/// The header value is never null: kafka-protocol doesn't decode the null
+/// header values it encodes.
+const ENCRYPTED_KEY_HEADER: &str = "ockam.encrypted.key";
+
 /// map shared across all kafka workers, since the client might request it
 /// only from one connection
 pub(super) type TopicUuidMap = Arc<Mutex<HashMap<String, String>>>;
@@ -32,6 +50,7 @@ pub(crate) struct Interceptor {
     request_map: Arc<Mutex<HashMap<CorrelationId, RequestInfo>>>,
     uuid_to_name: TopicUuidMap,
     secure_channel_controller: Arc<dyn KafkaSecureChannelController>,
+    record_encryption: KafkaRecordEncryption,
 }
 
 #[derive(Debug, Clone, Decode, Encode)]
@@ -45,15 +64,202 @@ struct MessageWrapper {
     #[b(2)] content: Vec<u8>
 }
 
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+///Headers of a record, encrypted within a single header
+struct RecordHeaders {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<7460917>,
+    #[b(1)] headers: Vec<(String, Option<Vec<u8>>)>
+}
+
 impl Interceptor {
     pub(crate) fn new(
         secure_channel_controller: Arc<dyn KafkaSecureChannelController>,
         uuid_to_name: TopicUuidMap,
+        record_encryption: KafkaRecordEncryption,
     ) -> Interceptor {
         Self {
             request_map: Arc::new(Mutex::new(Default::default())),
             uuid_to_name,
             secure_channel_controller,
+            record_encryption,
+        }
+    }
+
+    /// Encrypt some content for the consumer of a topic partition and wrap it
+    /// with the identifier of the secure channel used for the encryption
+    async fn encrypt_and_wrap(
+        &self,
+        context: &mut Context,
+        topic_name: &str,
+        partition_id: i32,
+        content: Vec<u8>,
+    ) -> Result<Bytes, InterceptError> {
+        let encrypted_content = self
+            .secure_channel_controller
+            .encrypt_content_for(context, topic_name, partition_id, content)
+            .await
+            .map_err(InterceptError::Ockam)?;
+
+        //TODO: to target multiple consumers we could duplicate
+        // the content with a dedicated encryption for each consumer
+        let wrapper = MessageWrapper {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            secure_channel_identifier: encrypted_content.secure_channel_id,
+            content: encrypted_content.content,
+        };
+
+        let mut write_buffer = Vec::with_capacity(1024);
+        let mut encoder = minicbor::Encoder::new(&mut write_buffer);
+        encoder
+            .encode(wrapper)
+            .map_err(|_err| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?;
+        Ok(write_buffer.into())
+    }
+
+    /// Unwrap content produced by `encrypt_and_wrap` and decrypt it
+    async fn unwrap_and_decrypt(
+        &self,
+        context: &mut Context,
+        wrapped: &[u8],
+    ) -> Result<Vec<u8>, InterceptError> {
+        let message_wrapper: MessageWrapper = Decoder::new(wrapped)
+            .decode()
+            .map_err(|_| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?;
+
+        self.secure_channel_controller
+            .decrypt_content_for(
+                context,
+                message_wrapper.secure_channel_identifier,
+                message_wrapper.content,
+            )
+            .await
+            .map_err(InterceptError::Ockam)
+    }
+
+    /// Encrypt the value of a record and, depending on the record encryption
+    /// options, its key and headers
+    async fn encrypt_record(
+        &self,
+        context: &mut Context,
+        topic_name: &str,
+        partition_id: i32,
+        record: &mut Record,
+    ) -> Result<(), InterceptError> {
+        if let Some(record_value) = record.value.take() {
+            record.value = Some(
+                self.encrypt_and_wrap(context, topic_name, partition_id, record_value.to_vec())
+                    .await?,
+            );
+        }
+
+        let encrypted_key = match (&self.record_encryption.keys, record.key.take()) {
+            (KafkaKeyEncryption::Randomized, Some(key)) => {
+                record.key = Some(
+                    self.encrypt_and_wrap(context, topic_name, partition_id, key.to_vec())
+                        .await?,
+                );
+                Some(Bytes::new())
+            }
+            (KafkaKeyEncryption::Deterministic(secret), Some(key)) => {
+                record.key = Some(hmac_sha256(secret, &key).into());
+                Some(
+                    self.encrypt_and_wrap(context, topic_name, partition_id, key.to_vec())
+                        .await?,
+                )
+            }
+            (_, key) => {
+                record.key = key;
+                None
+            }
+        };
+
+        if self.record_encryption.encrypt_headers && !record.headers.is_empty() {
+            let headers = RecordHeaders {
+                #[cfg(feature = "tag")]
+                tag: TypeTag,
+                headers: record
+                    .headers
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.as_ref().map(|v| v.to_vec())))
+                    .collect(),
+            };
+            let encoded = minicbor::to_vec(headers)
+                .map_err(|_| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?;
+            let encrypted_headers = self
+                .encrypt_and_wrap(context, topic_name, partition_id, encoded)
+                .await?;
+
+            record.headers.clear();
+            record.headers.insert(
+                string_to_str_bytes(ENCRYPTED_HEADERS_HEADER.to_string()),
+                Some(encrypted_headers),
+            );
         }
+
+        //the key header is never part of the encrypted headers, since the
+        //consumer needs it to know how the key was encrypted
+        if let Some(encrypted_key) = encrypted_key {
+            record.headers.insert(
+                string_to_str_bytes(ENCRYPTED_KEY_HEADER.to_string()),
+                Some(encrypted_key),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Decrypt a record encrypted with `encrypt_record`. The consumer doesn't
+    /// need any option since the headers of the record describe what was encrypted
+    async fn decrypt_record(
+        &self,
+        context: &mut Context,
+        record: &mut Record,
+    ) -> Result<(), InterceptError> {
+        if let Some(record_value) = record.value.take() {
+            record.value = Some(
+                self.unwrap_and_decrypt(context, &record_value)
+                    .await?
+                    .into(),
+            );
+        }
+
+        if let Some(encrypted_key) = record
+            .headers
+            .shift_remove(&string_to_str_bytes(ENCRYPTED_KEY_HEADER.to_string()))
+        {
+            let wrapped_key = match encrypted_key {
+                //the key was replaced by its hash, the original key is in the header
+                Some(wrapped_key) if !wrapped_key.is_empty() => wrapped_key,
+                //the key itself was encrypted
+                _ => record
+                    .key
+                    .take()
+                    .ok_or_else(|| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?,
+            };
+            record.key = Some(self.unwrap_and_decrypt(context, &wrapped_key).await?.into());
+        }
+
+        if let Some(encrypted_headers) = record
+            .headers
+            .shift_remove(&string_to_str_bytes(ENCRYPTED_HEADERS_HEADER.to_string()))
+        {
+            let encrypted_headers = encrypted_headers
+                .ok_or_else(|| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?;
+            let decrypted = self.unwrap_and_decrypt(context, &encrypted_headers).await?;
+            let headers: RecordHeaders = minicbor::decode(&decrypted)
+                .map_err(|_| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?;
+
+            for (name, value) in headers.headers {
+                record
+                    .headers
+                    .insert(string_to_str_bytes(name), value.map(Bytes::from));
+            }
+        }
+
+        Ok(())
     }
 }
@@ -8,9 +8,6 @@ use kafka_protocol::protocol::Decodable;
 use kafka_protocol::records::{
     Compression, RecordBatchDecoder, RecordBatchEncoder, RecordEncodeOptions,
 };
-use minicbor::encode::Encoder;
-#[cfg(feature = "tag")]
-use ockam_core::TypeTag;
 use ockam_node::Context;
 use std::convert::TryFrom;
 use std::io::{Error, ErrorKind};
@@ -18,7 +15,7 @@ use tracing::warn;
 
 use crate::kafka::portal_worker::InterceptError;
 use crate::kafka::protocol_aware::utils::{decode_body, encode_request};
-use crate::kafka::protocol_aware::{Interceptor, MessageWrapper, RequestInfo};
+use crate::kafka::protocol_aware::{Interceptor, RequestInfo};
 
 impl Interceptor {
     ///Parse request and map request <=> response
@@ -162,7 +159,7 @@ impl Interceptor {
 
         //the content can be set in multiple topics and partitions in a single message
         //for each we wrap the content and add the secure channel identifier of
-        //the encrypted content
+        //the encrypted content, keys and headers are encrypted as well when enabled
         for (topic_name, topic) in request.topic_data.iter_mut() {
             for data in &mut topic.partition_data {
                 if let Some(content) = data.records.take() {
@@ -171,35 +168,8 @@ impl Interceptor {
                         .map_err(|_| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?;
 
                     for record in records.iter_mut() {
-                        if let Some(record_value) = record.value.take() {
-                            let encrypted_content = self
-                                .secure_channel_controller
-                                .encrypt_content_for(
-                                    context,
-                                    topic_name,
-                                    data.index,
-                                    record_value.to_vec(),
-                                )
-                                .await
-                                .map_err(InterceptError::Ockam)?;
-
-                            //TODO: to target multiple consumers we could duplicate
-                            // the content with a dedicated encryption for each consumer
-                            let wrapper = MessageWrapper {
-                                #[cfg(feature = "tag")]
-                                tag: TypeTag,
-                                secure_channel_identifier: encrypted_content.secure_channel_id,
-                                content: encrypted_content.content,
-                            };
-
-                            let mut write_buffer = Vec::with_capacity(1024);
-                            let mut encoder = Encoder::new(&mut write_buffer);
-                            encoder.encode(wrapper).map_err(|_err| {
-                                InterceptError::Io(Error::from(ErrorKind::InvalidData))
-                            })?;
-
-                            record.value = Some(write_buffer.into());
-                        }
+                        self.encrypt_record(context, topic_name, data.index, record)
+                            .await?;
                     }
 
                     let mut encoded = BytesMut::new();
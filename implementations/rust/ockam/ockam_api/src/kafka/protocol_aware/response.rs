@@ -12,14 +12,13 @@ use kafka_protocol::protocol::Decodable;
 use kafka_protocol::records::{
     Compression, RecordBatchDecoder, RecordBatchEncoder, RecordEncodeOptions,
 };
-use minicbor::decode::Decoder;
 use ockam_node::Context;
 use tracing::{trace, warn};
 
 use crate::kafka::inlet_controller::KafkaInletController;
 use crate::kafka::portal_worker::InterceptError;
 use crate::kafka::protocol_aware::utils::{decode_body, encode_response, string_to_str_bytes};
-use crate::kafka::protocol_aware::{Interceptor, MessageWrapper, RequestInfo};
+use crate::kafka::protocol_aware::{Interceptor, RequestInfo};
 
 impl Interceptor {
     pub(crate) async fn intercept_response(
@@ -230,24 +229,7 @@ impl Interceptor {
                         .map_err(|_| InterceptError::Io(Error::from(ErrorKind::InvalidData)))?;
 
                     for record in records.iter_mut() {
-                        if let Some(record_value) = record.value.take() {
-                            let message_wrapper: MessageWrapper =
-                                Decoder::new(record_value.as_ref()).decode().map_err(|_| {
-                                    InterceptError::Io(Error::from(ErrorKind::InvalidData))
-                                })?;
-
-                            let decrypted_content = self
-                                .secure_channel_controller
-                                .decrypt_content_for(
-                                    context,
-                                    message_wrapper.secure_channel_identifier,
-                                    message_wrapper.content,
-                                )
-                                .await
-                                .map_err(InterceptError::Ockam)?;
-
-                            record.value = Some(decrypted_content.into());
-                        }
+                        self.decrypt_record(context, record).await?;
                     }
 
                     let mut encoded = BytesMut::new();
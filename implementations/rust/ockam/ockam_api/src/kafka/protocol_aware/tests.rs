@@ -60,6 +60,7 @@ mod test {
         let interceptor = Interceptor::new(
             Arc::new(DummySecureChannelController {}),
             Default::default(),
+            Default::default(),
         );
 
         let inlet_map = KafkaInletController::new(
@@ -1,8 +1,10 @@
 use crate::kafka::portal_worker::InterceptError;
 use bytes::BytesMut;
+use hmac::{Hmac, Mac};
 use kafka_protocol::messages::ApiKey;
 use kafka_protocol::protocol::buf::ByteBuf;
 use kafka_protocol::protocol::{Decodable, Encodable, StrBytes};
+use sha2::Sha256;
 use std::io::{Error, ErrorKind};
 
 pub(crate) fn decode_body<T, B>(buffer: &mut B, api_version: i16) -> Result<T, InterceptError>
@@ -58,3 +60,62 @@ pub(super) fn string_to_str_bytes(ip_address: String) -> StrBytes {
     //TryFrom is broken, ugly but effective
     unsafe { StrBytes::from_utf8_unchecked(bytes::Bytes::from(ip_address)) }
 }
+
+/// HMAC-SHA256 of `message` with `secret`, used to hide record keys while
+/// keeping equal keys equal for the broker
+pub(super) fn hmac_sha256(secret: &[u8], message: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("any key length is valid");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod test {
+    use super::hmac_sha256;
+
+    /// Test cases of RFC 4231, section 4, for HMAC-SHA-256.
+    /// Test case 5, which truncates the output, doesn't apply
+    #[test]
+    fn hmac_sha256_rfc4231_test_vectors() {
+        let cases: [(Vec<u8>, Vec<u8>, &str); 6] = [
+            (
+                vec![0x0b; 20],
+                b"Hi There".to_vec(),
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ),
+            (
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                vec![0xaa; 20],
+                vec![0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            ),
+            (
+                (0x01..=0x19).collect(),
+                vec![0xcd; 50],
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            ),
+            (
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+            (
+                vec![0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger than \
+                  block-size data. The key needs to be hashed before being used by the \
+                  HMAC algorithm."
+                    .to_vec(),
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            ),
+        ];
+
+        for (key, data, expected) in cases {
+            assert_eq!(hex::encode(hmac_sha256(&key, &data)), expected);
+        }
+    }
+}
@@ -12,15 +12,16 @@ pub mod error;
 pub mod hop;
 pub mod identity;
 pub mod kafka;
+pub mod metrics;
 pub mod nodes;
 pub mod okta;
 pub mod port_range;
 pub mod rpc_proxy;
+pub mod session;
 pub mod uppercase;
 pub mod verifier;
 
 mod schema;
-mod session;
 mod util;
 
 pub use util::*;
@@ -48,12 +49,15 @@ impl DefaultAddress {
     pub const KAFKA_CONSUMER: &'static str = "kafka_consumer";
     pub const KAFKA_PRODUCER: &'static str = "kafka_producer";
     pub const RPC_PROXY: &'static str = "rpc_proxy_service";
+    pub const STREAM_SERVICE: &'static str = "stream";
+    pub const STREAM_INDEX_SERVICE: &'static str = "stream_index";
 }
 
 pub mod actions {
     use ockam_abac::Action;
 
     pub const HANDLE_MESSAGE: Action = Action::assert_inline("handle_message");
+    pub const REGISTER: Action = Action::assert_inline("register");
 }
 
 pub mod resources {
@@ -61,6 +65,9 @@ pub mod resources {
 
     pub const INLET: Resource = Resource::assert_inline("tcp-inlet");
     pub const OUTLET: Resource = Resource::assert_inline("tcp-outlet");
+    pub const UDP_INLET: Resource = Resource::assert_inline("udp-inlet");
+    pub const UDP_OUTLET: Resource = Resource::assert_inline("udp-outlet");
+    pub const FORWARDER: Resource = Resource::assert_inline("forwarder");
 }
 
 use core::fmt;
@@ -0,0 +1,141 @@
+//! HTTP endpoint exposing the metrics of a node to Prometheus
+
+use crate::error::ApiError;
+use ockam_core::compat::sync::Arc;
+use ockam_core::Result;
+use ockam_node::metrics::PrometheusRecorder;
+use ockam_node::tokio::io::{AsyncReadExt, AsyncWriteExt};
+use ockam_node::tokio::net::{TcpListener, TcpStream};
+use ockam_node::tokio::runtime::Handle;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tracing::{debug, info, warn};
+
+/// Path at which the metrics are served
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Maximum size of the head of a request
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// Maximum time to wait for the head of a request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Serve the metrics collected by `recorder` in the Prometheus text format
+/// on `GET /metrics` at the given address.
+///
+/// Returns the address the endpoint is actually bound to, which is useful
+/// when binding to port 0.
+pub async fn start_metrics_endpoint(
+    rt: &Handle,
+    addr: SocketAddr,
+    recorder: Arc<PrometheusRecorder>,
+) -> Result<SocketAddr> {
+    let listener = TcpListener::bind(addr).await.map_err(|e| {
+        ApiError::generic(&format!(
+            "failed to bind the metrics endpoint to {addr}: {e}"
+        ))
+    })?;
+    let local_addr = listener
+        .local_addr()
+        .map_err(|e| ApiError::generic(&e.to_string()))?;
+    info!(%local_addr, "serving metrics on {METRICS_PATH}");
+
+    rt.spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, peer)) => {
+                    debug!(%peer, "metrics request");
+                    let recorder = recorder.clone();
+                    ockam_node::tokio::spawn(async move {
+                        if let Err(e) = serve(stream, &recorder).await {
+                            debug!(%peer, %e, "failed to serve metrics")
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!(%e, "metrics endpoint stopped");
+                    break;
+                }
+            }
+        }
+    });
+
+    Ok(local_addr)
+}
+
+/// Answer a single HTTP request and close the connection
+async fn serve(mut stream: TcpStream, recorder: &PrometheusRecorder) -> std::io::Result<()> {
+    let mut buf = Vec::new();
+    ockam_node::tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream, &mut buf))
+        .await
+        .map_err(|_| std::io::Error::from(std::io::ErrorKind::TimedOut))??;
+
+    let head = String::from_utf8_lossy(&buf);
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    let (status, body) = match (method, path.split('?').next().unwrap_or_default()) {
+        ("GET", METRICS_PATH) => ("200 OK", recorder.render()),
+        (_, METRICS_PATH) => ("405 Method Not Allowed", String::new()),
+        _ => ("404 Not Found", String::new()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+         Content-Type: text/plain; version=0.0.4\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read the head of a request, up to the empty line ending it
+async fn read_head(stream: &mut TcpStream, buf: &mut Vec<u8>) -> std::io::Result<()> {
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST_SIZE {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam_node::metrics::MetricsRecorder;
+
+    async fn get(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn metrics_are_served() {
+        let recorder = Arc::new(PrometheusRecorder::new());
+        recorder.increment_counter("requests_total", &[], 3);
+
+        let addr =
+            start_metrics_endpoint(&Handle::current(), "127.0.0.1:0".parse().unwrap(), recorder)
+                .await
+                .unwrap();
+
+        let response = get(addr, "GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("requests_total 3\n"));
+
+        let response = get(addr, "GET /other HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = get(addr, "POST /metrics HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
@@ -3,8 +3,8 @@ use std::path::Path;
 use tracing::info;
 
 use ockam::identity::{
-    Identities, IdentitiesRepository, IdentitiesStorage, IdentitiesVault, IdentityAttributesWriter,
-    SecureChannelListenerOptions, SecureChannels, TrustEveryonePolicy,
+    Identities, IdentitiesRepository, IdentitiesStorage, IdentitiesVault, IdentityAttributesReader,
+    IdentityAttributesWriter, SecureChannelListenerOptions, SecureChannels, TrustEveryonePolicy,
 };
 use ockam_abac::expr::{and, eq, ident, str};
 use ockam_abac::{AbacAccessControl, Env};
@@ -12,13 +12,20 @@ use ockam_core::compat::sync::Arc;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::flow_control::{FlowControlId, FlowControlPolicy, FlowControls};
 use ockam_core::{Address, AllowAll, Error, Message, Result, Worker};
-use ockam_identity::{CredentialsIssuer, IdentityIdentifier, LmdbStorage};
+#[cfg(feature = "sqlite")]
+use ockam_identity::KeyValueStorageAdapter;
+use ockam_identity::{
+    AuthorityRevocationList, CredentialsIssuer, IdentityIdentifier, LmdbStorage, Storage,
+};
+#[cfg(feature = "sqlite")]
+use ockam_node::SqliteKeyValueStorage;
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_tcp::{TcpListenerOptions, TcpTransport};
 use ockam_vault::Vault;
 
 use crate::authenticator::direct::EnrollmentTokenAuthenticator;
 use crate::bootstrapped_identities_store::BootstrapedIdentityStore;
+use crate::cli_state::VaultState;
 use crate::echoer::Echoer;
 use crate::nodes::authority_node::authority::EnrollerCheck::{AnyMember, EnrollerOnly};
 use crate::nodes::authority_node::Configuration;
@@ -34,6 +41,8 @@ use crate::{actions, DefaultAddress};
 pub struct Authority {
     identifier: IdentityIdentifier,
     secure_channels: Arc<SecureChannels>,
+    revocation_list: AuthorityRevocationList,
+    storage: Arc<dyn Storage>,
 }
 
 /// Public functions to:
@@ -51,7 +60,9 @@ impl Authority {
     pub async fn create(configuration: &Configuration) -> Result<Authority> {
         debug!(?configuration, "creating the authority");
         let vault = Self::create_secure_channels_vault(configuration).await?;
-        let repository = Self::create_identities_repository(configuration).await?;
+        let storage = Self::create_storage(configuration).await?;
+        let repository = Self::create_identities_repository(storage.clone(), configuration);
+        let revocation_list = AuthorityRevocationList::new(storage.clone());
         let secure_channels = SecureChannels::builder()
             .with_identities_vault(vault)
             .with_identities_repository(repository)
@@ -63,6 +74,8 @@ impl Authority {
         Ok(Authority {
             identifier,
             secure_channels,
+            revocation_list,
+            storage,
         })
     }
 
@@ -125,8 +138,10 @@ impl Authority {
         let direct = crate::authenticator::direct::DirectAuthenticator::new(
             configuration.clone().trust_context_identifier(),
             self.attributes_writer(),
+            self.attributes_reader(),
         )
-        .await?;
+        .await?
+        .with_revocation_list(self.revocation_list.clone());
 
         let name = configuration.clone().authenticator_name();
         flow_controls.add_consumer(
@@ -157,6 +172,7 @@ impl Authority {
         let (issuer, acceptor) = EnrollmentTokenAuthenticator::new_worker_pair(
             configuration.trust_context_identifier(),
             self.attributes_writer(),
+            self.storage.clone(),
         );
 
         // start an enrollment token issuer with an abac policy checking that
@@ -217,7 +233,8 @@ impl Authority {
             self.identifier(),
             configuration.trust_context_identifier(),
         )
-        .await?;
+        .await?
+        .with_revocation_list(self.revocation_list.clone());
 
         let address = DefaultAddress::CREDENTIAL_ISSUER.to_string();
         flow_controls.add_consumer(
@@ -303,25 +320,92 @@ impl Authority {
         self.identities_repository().as_attributes_writer().clone()
     }
 
-    /// Create an identity vault backed by a FileStorage
+    /// Return the attributes reader used by the authority
+    fn attributes_reader(&self) -> Arc<dyn IdentityAttributesReader> {
+        self.identities_repository().as_attributes_reader()
+    }
+
+    /// Create an identity vault backed by a FileStorage, encrypted at rest if the
+    /// configured vault is
     async fn create_secure_channels_vault(
         configuration: &Configuration,
     ) -> Result<Arc<dyn IdentitiesVault>> {
         let vault_path = &configuration.vault_path;
         Self::create_ockam_directory_if_necessary(vault_path)?;
-        let vault = Vault::create_with_persistent_storage_path(vault_path).await?;
+        let vault = if configuration.vault_encrypted {
+            Vault::create_with_encrypted_persistent_storage_path(
+                vault_path,
+                VaultState::storage_key()?,
+            )
+            .await?
+        } else {
+            Vault::create_with_persistent_storage_path(vault_path).await?
+        };
         Ok(vault)
     }
 
-    /// Create an authenticated storage backed by a Lmdb database
-    async fn create_identities_repository(
-        configuration: &Configuration,
-    ) -> Result<Arc<dyn IdentitiesRepository>> {
+    /// Create a storage backed by a Lmdb database
+    #[cfg(not(feature = "sqlite"))]
+    async fn create_storage(configuration: &Configuration) -> Result<Arc<dyn Storage>> {
+        let storage_path = &configuration.storage_path;
+        Self::create_ockam_directory_if_necessary(storage_path)?;
+        Ok(Arc::new(LmdbStorage::new(&storage_path).await?))
+    }
+
+    /// Create a storage backed by a SQLite database, next to the Lmdb database used
+    /// without the `sqlite` feature.
+    /// The entries of an existing Lmdb database are copied the first time the SQLite
+    /// database is created.
+    #[cfg(feature = "sqlite")]
+    async fn create_storage(configuration: &Configuration) -> Result<Arc<dyn Storage>> {
         let storage_path = &configuration.storage_path;
         Self::create_ockam_directory_if_necessary(storage_path)?;
-        let storage = Arc::new(LmdbStorage::new(&storage_path).await?);
+        let sqlite_path = storage_path.with_extension("sqlite");
+        if !sqlite_path.exists() && storage_path.exists() {
+            Self::migrate_storage(storage_path, &sqlite_path).await?;
+        }
+        let storage = Arc::new(SqliteKeyValueStorage::create(&sqlite_path).await?);
+        Ok(KeyValueStorageAdapter::create(storage))
+    }
+
+    /// Copy the entries of a Lmdb database to a new SQLite database.
+    /// The entries are first copied to a temporary database which is only renamed
+    /// once the copy succeeded, so that an interrupted migration is started again
+    /// on the next start instead of leaving an incomplete database.
+    #[cfg(feature = "sqlite")]
+    async fn migrate_storage(lmdb_path: &Path, sqlite_path: &Path) -> Result<()> {
+        let migration_path = sqlite_path.with_extension("sqlite.migration");
+        for path in [
+            migration_path.clone(),
+            migration_path.with_extension("migration-wal"),
+            migration_path.with_extension("migration-shm"),
+        ] {
+            if path.exists() {
+                std::fs::remove_file(&path).map_err(|e| Error::new(Origin::Node, Kind::Io, e))?;
+            }
+        }
+
+        let migration: SqliteKeyValueStorage<(String, String), Vec<u8>> =
+            SqliteKeyValueStorage::create(&migration_path).await?;
+        let count = LmdbStorage::new(lmdb_path)
+            .await?
+            .migrate_to(&migration)
+            .await?;
+        // close the database before renaming it
+        drop(migration);
+        std::fs::rename(&migration_path, sqlite_path)
+            .map_err(|e| Error::new(Origin::Node, Kind::Io, e))?;
+        info!("copied {count} entries from {lmdb_path:?} to {sqlite_path:?}");
+        Ok(())
+    }
+
+    /// Create an authenticated storage backed by the authority storage
+    fn create_identities_repository(
+        storage: Arc<dyn Storage>,
+        configuration: &Configuration,
+    ) -> Arc<dyn IdentitiesRepository> {
         let repository = Arc::new(IdentitiesStorage::new(storage));
-        Ok(Self::bootstrap_repository(repository, configuration))
+        Self::bootstrap_repository(repository, configuration)
     }
 
     /// Create a directory to save storage files if they haven't been  created before
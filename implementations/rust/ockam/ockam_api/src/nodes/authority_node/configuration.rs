@@ -21,6 +21,10 @@ pub struct Configuration {
     /// path where secrets should be persisted
     pub vault_path: PathBuf,
 
+    /// If true the vault file is encrypted, with the key provided by the environment
+    #[serde(default)]
+    pub vault_encrypted: bool,
+
     /// Project identifier on the Orchestrator node
     pub project_identifier: String,
 
@@ -1,5 +1,4 @@
 use crate::nodes::authority_node::{Authority, Configuration};
-use ockam_core::flow_control::FlowControls;
 use ockam_core::Result;
 use ockam_node::Context;
 use tracing::info;
@@ -15,7 +14,7 @@ pub async fn start_node(ctx: &Context, configuration: &Configuration) -> Result<
 
     debug!("starting services");
     // start a secure channel listener (this also starts a TCP transport)
-    let flow_controls = FlowControls::default();
+    let flow_controls = ctx.flow_controls().clone();
     let secure_channel_flow_control_id = authority
         .start_secure_channel_listener(ctx, &flow_controls, configuration)
         .await?;
@@ -1,4 +1,5 @@
 mod plain_tcp;
+mod plain_transport;
 mod project;
 mod secure;
 
@@ -7,13 +8,16 @@ use ockam_core::flow_control::FlowControlPolicy::ProducerAllowMultiple;
 use ockam_core::flow_control::{FlowControlId, FlowControls};
 use ockam_core::{async_trait, route, Address, CowStr, Route, LOCAL};
 use ockam_identity::IdentityIdentifier;
-use ockam_multiaddr::proto::Service;
+use ockam_multiaddr::proto::{Relay, Service};
 use ockam_multiaddr::{Match, MultiAddr, Protocol};
 use ockam_node::Context;
 use std::fmt::{Debug, Formatter};
 use std::time::Duration;
 
+use crate::relay_address;
+
 pub(crate) use plain_tcp::PlainTcpInstantiator;
+pub(crate) use plain_transport::PlainTransportInstantiator;
 pub(crate) use project::ProjectInstantiator;
 pub(crate) use secure::SecureChannelInstantiator;
 
@@ -263,19 +267,24 @@ impl ConnectionInstanceBuilder {
         let mut route = Route::new();
         let mut peekable = self.current_multiaddr.iter().peekable();
         while let Some(protocol) = peekable.next() {
-            if protocol.code() == Service::CODE {
-                if let Some(service) = protocol.cast::<Service>() {
-                    let address = Address::new(LOCAL, &*service);
-                    let is_last = peekable.peek().is_none();
-                    // we usually want to skip the last entry since it's normally the destination
-                    // but when a suffix route is appended (like in the inlet) is used
-                    // the last piece could actually be a transport, in this case we allow
-                    // last piece only if it's a just created secure channel
-                    if is_last && !self.secure_channel_encryptors.contains(&address) {
-                        break;
-                    }
-                    route = route.append(address);
+            let address = match protocol.code() {
+                Service::CODE => protocol
+                    .cast::<Service>()
+                    .map(|service| Address::new(LOCAL, &*service)),
+                // a relay hop is the forwarder registered for its alias
+                Relay::CODE => protocol.cast::<Relay>().map(|alias| relay_address(&alias)),
+                _ => None,
+            };
+            if let Some(address) = address {
+                let is_last = peekable.peek().is_none();
+                // we usually want to skip the last entry since it's normally the destination
+                // but when a suffix route is appended (like in the inlet) is used
+                // the last piece could actually be a transport, in this case we allow
+                // last piece only if it's a just created secure channel
+                if is_last && !self.secure_channel_encryptors.contains(&address) {
+                    break;
                 }
+                route = route.append(address);
             }
         }
 
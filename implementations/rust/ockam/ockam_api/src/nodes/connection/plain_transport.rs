@@ -0,0 +1,92 @@
+use crate::error::ApiError;
+use crate::nodes::connection::{Changes, ConnectionInstanceBuilder, Instantiator};
+use crate::{multiaddr_to_route, route_to_multiaddr};
+
+use ockam_core::{async_trait, Error};
+use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Tcp, Udp, Unix, Ws};
+use ockam_multiaddr::{Match, Protocol};
+use ockam_transport_tcp::TcpTransport;
+
+/// Creates the connection of a transport other than TCP: UDP, WebSocket or Unix domain socket.
+///
+/// The transport worker is created by the transport registered on the node context.
+pub(crate) struct PlainTransportInstantiator {
+    tcp_transport: TcpTransport,
+    matches: Vec<Match>,
+}
+
+impl PlainTransportInstantiator {
+    /// Matches `/tcp/<port>/ws` after a host
+    ///
+    /// It must be used before the [`PlainTcpInstantiator`](super::PlainTcpInstantiator)
+    pub(crate) fn websocket(tcp_transport: TcpTransport) -> Self {
+        Self {
+            tcp_transport,
+            matches: vec![
+                Match::any([DnsAddr::CODE, Ip4::CODE, Ip6::CODE]),
+                Tcp::CODE.into(),
+                Ws::CODE.into(),
+            ],
+        }
+    }
+
+    /// Matches `/udp/<port>` after a host
+    pub(crate) fn udp(tcp_transport: TcpTransport) -> Self {
+        Self {
+            tcp_transport,
+            matches: vec![
+                Match::any([DnsAddr::CODE, Ip4::CODE, Ip6::CODE]),
+                Udp::CODE.into(),
+            ],
+        }
+    }
+
+    /// Matches `/unix/<path>`
+    pub(crate) fn unix(tcp_transport: TcpTransport) -> Self {
+        Self {
+            tcp_transport,
+            matches: vec![Unix::CODE.into()],
+        }
+    }
+}
+
+#[async_trait]
+impl Instantiator for PlainTransportInstantiator {
+    fn matches(&self) -> Vec<Match> {
+        self.matches.clone()
+    }
+
+    async fn instantiate(
+        &self,
+        builder: &ConnectionInstanceBuilder,
+        match_start: usize,
+    ) -> Result<Changes, Error> {
+        let (before, transport_piece, after) = ConnectionInstanceBuilder::extract(
+            &builder.current_multiaddr,
+            match_start,
+            self.matches.len(),
+        );
+
+        let transport = multiaddr_to_route(
+            &transport_piece,
+            &self.tcp_transport,
+            &builder.flow_controls,
+        )
+        .await
+        .ok_or_else(|| ApiError::generic("invalid multiaddr"))?;
+
+        let multiaddr = route_to_multiaddr(&transport.route)
+            .ok_or_else(|| ApiError::generic("invalid transport route"))?;
+
+        let current_multiaddr = ConnectionInstanceBuilder::combine(before, multiaddr, after)?;
+
+        Ok(Changes {
+            current_multiaddr,
+            flow_control_id: transport.flow_control_id,
+            secure_channel_encryptors: vec![],
+            //since we only pass the piece regarding the transport
+            //we can be sure the next step is the transport worker
+            tcp_worker: Some(transport.route.next()?.clone()),
+        })
+    }
+}
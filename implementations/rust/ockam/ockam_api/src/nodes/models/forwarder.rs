@@ -2,7 +2,7 @@ use minicbor::{Decode, Encode};
 
 use ockam::identity::IdentityIdentifier;
 use ockam::remote::RemoteForwarderInfo;
-use ockam::route;
+use ockam::{route, ForwarderEntry};
 use ockam_core::CowStr;
 use ockam_multiaddr::MultiAddr;
 
@@ -120,3 +120,55 @@ impl<'a> From<RemoteForwarderInfo> for ForwarderInfo<'a> {
         }
     }
 }
+
+/// A forwarder registered at the forwarding service of a node
+#[derive(Debug, Clone, Decode, Encode, serde::Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RegisteredForwarder {
+    #[cfg(feature = "tag")]
+    #[serde(skip)]
+    #[n(0)] tag: TypeTag<1309872>,
+    #[n(1)] alias: String,
+    #[n(2)] owner: Option<IdentityIdentifier>,
+    #[n(3)] forwarding_route: String,
+    #[n(4)] last_seen: Option<u64>,
+    #[n(5)] expires_after_secs: Option<u64>,
+}
+
+impl RegisteredForwarder {
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    pub fn owner(&self) -> Option<&IdentityIdentifier> {
+        self.owner.as_ref()
+    }
+
+    pub fn forwarding_route(&self) -> &str {
+        &self.forwarding_route
+    }
+
+    /// Unix time of the last registration or heartbeat
+    pub fn last_seen(&self) -> Option<u64> {
+        self.last_seen
+    }
+
+    pub fn expires_after_secs(&self) -> Option<u64> {
+        self.expires_after_secs
+    }
+}
+
+impl From<ForwarderEntry> for RegisteredForwarder {
+    fn from(entry: ForwarderEntry) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: Default::default(),
+            alias: entry.alias().to_string(),
+            owner: entry.owner().cloned(),
+            forwarding_route: entry.forward_route().to_string(),
+            last_seen: entry.last_seen().map(|t| t.unix_time()),
+            expires_after_secs: entry.expires_after().map(|d| d.as_secs()),
+        }
+    }
+}
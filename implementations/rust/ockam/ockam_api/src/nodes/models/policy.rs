@@ -1,5 +1,6 @@
 use minicbor::{Decode, Encode};
-use ockam_abac::{Action, Expr};
+use ockam_abac::{Action, Diagnostic, Expr, Schema, TraceEntry};
+use std::collections::BTreeMap;
 
 #[cfg(feature = "tag")]
 use ockam_core::TypeTag;
@@ -49,3 +50,116 @@ impl PolicyList {
         &self.expressions
     }
 }
+
+/// Request to check a policy against a schema of attributes.
+///
+/// If no expression is given, the stored policy is checked.
+#[derive(Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct CheckPolicy {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<6614520>,
+    #[n(1)] expression: Option<Expr>,
+    #[n(2)] schema: Schema,
+}
+
+impl CheckPolicy {
+    pub fn new(expression: Option<Expr>, schema: Schema) -> Self {
+        CheckPolicy {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            expression,
+            schema,
+        }
+    }
+
+    pub fn expression(&self) -> Option<&Expr> {
+        self.expression.as_ref()
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PolicyDiagnostics {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<1867223>,
+    #[n(1)] diagnostics: Vec<Diagnostic>,
+}
+
+impl PolicyDiagnostics {
+    pub fn new(diagnostics: Vec<Diagnostic>) -> Self {
+        PolicyDiagnostics {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            diagnostics,
+        }
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// Request to evaluate a policy against a set of attributes, without
+/// authorizing anything.
+///
+/// Attribute names are fully qualified, e.g. `subject.role`. If no
+/// expression is given, the stored policy is evaluated.
+#[derive(Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct DryRunPolicy {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<4409318>,
+    #[n(1)] expression: Option<Expr>,
+    #[n(2)] attributes: BTreeMap<String, String>,
+}
+
+impl DryRunPolicy {
+    pub fn new(expression: Option<Expr>, attributes: BTreeMap<String, String>) -> Self {
+        DryRunPolicy {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            expression,
+            attributes,
+        }
+    }
+
+    pub fn expression(&self) -> Option<&Expr> {
+        self.expression.as_ref()
+    }
+
+    pub fn attributes(&self) -> &BTreeMap<String, String> {
+        &self.attributes
+    }
+}
+
+#[derive(Debug, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct PolicyTrace {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<2930071>,
+    #[n(1)] entries: Vec<TraceEntry>,
+}
+
+impl PolicyTrace {
+    pub fn new(entries: Vec<TraceEntry>) -> Self {
+        PolicyTrace {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            entries,
+        }
+    }
+
+    /// The evaluated sub-expressions, the first entry being the policy itself.
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+}
@@ -15,6 +15,7 @@ use serde::ser::SerializeStruct;
 use serde::Serialize;
 
 use crate::error::ApiError;
+use crate::nodes::models::session::SessionRetryOptions;
 use crate::route_to_multiaddr;
 
 /// Request body to create an inlet
@@ -44,6 +45,10 @@ pub struct CreateInlet<'a> {
     #[n(6)] suffix_route: Route,
     /// The maximum duration to wait for an outlet to be available
     #[n(7)] wait_for_outlet_duration: Option<Duration>,
+    /// A Unix domain socket path the portal should listen at instead of `listen_addr`
+    #[b(8)] unix_path: Option<CowStr<'a>>,
+    /// How the session to the outlet is monitored and replaced
+    #[n(9)] session_options: Option<SessionRetryOptions>,
 }
 
 impl<'a> CreateInlet<'a> {
@@ -63,6 +68,8 @@ impl<'a> CreateInlet<'a> {
             prefix_route,
             suffix_route,
             wait_for_outlet_duration: None,
+            unix_path: None,
+            session_options: None,
         }
     }
 
@@ -83,6 +90,8 @@ impl<'a> CreateInlet<'a> {
             prefix_route,
             suffix_route,
             wait_for_outlet_duration: None,
+            unix_path: None,
+            session_options: None,
         }
     }
 
@@ -94,6 +103,14 @@ impl<'a> CreateInlet<'a> {
         self.wait_for_outlet_duration = Some(Duration::from_millis(ms))
     }
 
+    pub fn set_unix_path(&mut self, path: impl Into<Cow<'a, str>>) {
+        self.unix_path = Some(CowStr(path.into()))
+    }
+
+    pub fn set_session_options(&mut self, options: SessionRetryOptions) {
+        self.session_options = Some(options)
+    }
+
     pub fn listen_addr(&self) -> SocketAddr {
         self.listen_addr
     }
@@ -121,6 +138,14 @@ impl<'a> CreateInlet<'a> {
     pub fn wait_for_outlet_duration(&self) -> Option<Duration> {
         self.wait_for_outlet_duration
     }
+
+    pub fn unix_path(&self) -> Option<&str> {
+        self.unix_path.as_deref()
+    }
+
+    pub fn session_options(&self) -> Option<&SessionRetryOptions> {
+        self.session_options.as_ref()
+    }
 }
 
 /// Request body to create an outlet
@@ -136,6 +161,8 @@ pub struct CreateOutlet<'a> {
     #[b(2)] pub worker_addr: Cow<'a, str>,
     /// A human-friendly alias for this portal endpoint
     #[b(3)] pub alias: Option<CowStr<'a>>,
+    /// A Unix domain socket path the portal should connect to instead of `tcp_addr`
+    #[b(4)] pub unix_path: Option<CowStr<'a>>,
 }
 
 impl<'a> CreateOutlet<'a> {
@@ -150,8 +177,13 @@ impl<'a> CreateOutlet<'a> {
             tcp_addr: tcp_addr.into(),
             worker_addr: worker_addr.into(),
             alias: alias.into(),
+            unix_path: None,
         }
     }
+
+    pub fn set_unix_path(&mut self, path: impl Into<Cow<'a, str>>) {
+        self.unix_path = Some(CowStr(path.into()))
+    }
 }
 
 /// Response body when interacting with a portal endpoint
@@ -228,6 +260,8 @@ pub struct OutletStatus<'a> {
     #[b(3)] pub alias: CowStr<'a>,
     /// An optional status payload
     #[b(4)] pub payload: Option<CowStr<'a>>,
+    /// The Unix domain socket path the outlet connects to, instead of `tcp_addr`
+    #[b(5)] pub unix_path: Option<CowStr<'a>>,
 }
 
 impl<'a> OutletStatus<'a> {
@@ -239,6 +273,7 @@ impl<'a> OutletStatus<'a> {
             worker_addr: "".into(),
             alias: "".into(),
             payload: Some(reason.into()),
+            unix_path: None,
         }
     }
 
@@ -255,9 +290,15 @@ impl<'a> OutletStatus<'a> {
             worker_addr: worker_addr.into(),
             alias: alias.into(),
             payload: payload.into(),
+            unix_path: None,
         }
     }
 
+    pub fn with_unix_path(mut self, unix_path: impl Into<Option<CowStr<'a>>>) -> Self {
+        self.unix_path = unix_path.into();
+        self
+    }
+
     pub fn worker_address(&self) -> Result<MultiAddr, ockam_core::Error> {
         route_to_multiaddr(&route![self.worker_addr.to_string()])
             .ok_or_else(|| ApiError::generic("Invalid Worker Address"))
@@ -37,6 +37,32 @@ impl<'a, T> StartServiceRequest<'a, T> {
     }
 }
 
+/// How the parts of Kafka records other than their values, which are always
+/// encrypted, are protected
+#[derive(Debug, Clone, Default, PartialEq, Eq, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct KafkaRecordEncryption {
+    /// Encrypt the headers of the records
+    #[n(1)] pub encrypt_headers: bool,
+    #[n(2)] pub keys: KafkaKeyEncryption,
+}
+
+/// How the keys of Kafka records are protected
+#[derive(Debug, Clone, Default, PartialEq, Eq, Decode, Encode)]
+#[rustfmt::skip]
+pub enum KafkaKeyEncryption {
+    /// Keys are sent as they are
+    #[default]
+    #[n(0)] Plain,
+    /// Keys are encrypted, equal keys don't result in equal encrypted keys
+    #[n(1)] Randomized,
+    /// Keys are replaced by their HMAC-SHA256 with a secret shared by all the
+    /// producers, so that equal keys stay equal for the broker and partitioning
+    /// still works. The original keys are sent encrypted in a record header.
+    #[n(2)] Deterministic(#[cbor(n(0), with = "minicbor::bytes")] Vec<u8>),
+}
+
 #[derive(Debug, Clone, Decode, Encode)]
 #[rustfmt::skip]
 #[cbor(map)]
@@ -44,6 +70,7 @@ pub struct StartKafkaConsumerRequest<'a> {
     #[b(1)] pub bootstrap_server_addr: SocketAddr,
     #[n(2)] brokers_port_range: (u16, u16),
     #[b(3)] project_route: CowStr<'a>,
+    #[n(4)] record_encryption: Option<KafkaRecordEncryption>,
 }
 
 impl<'a> StartKafkaConsumerRequest<'a> {
@@ -56,9 +83,15 @@ impl<'a> StartKafkaConsumerRequest<'a> {
             bootstrap_server_addr,
             brokers_port_range: brokers_port_range.into(),
             project_route: project_route.to_string().into(),
+            record_encryption: None,
         }
     }
 
+    pub fn with_record_encryption(mut self, record_encryption: KafkaRecordEncryption) -> Self {
+        self.record_encryption = Some(record_encryption);
+        self
+    }
+
     pub fn bootstrap_server_addr(&self) -> SocketAddr {
         self.bootstrap_server_addr
     }
@@ -68,6 +101,9 @@ impl<'a> StartKafkaConsumerRequest<'a> {
     pub fn project_route(&self) -> &CowStr<'a> {
         &self.project_route
     }
+    pub fn record_encryption(&self) -> KafkaRecordEncryption {
+        self.record_encryption.clone().unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone, Decode, Encode)]
@@ -77,6 +113,7 @@ pub struct StartKafkaProducerRequest<'a> {
     #[b(1)] pub bootstrap_server_addr: SocketAddr,
     #[n(2)] brokers_port_range: (u16, u16),
     #[b(3)] project_route: CowStr<'a>,
+    #[n(4)] record_encryption: Option<KafkaRecordEncryption>,
 }
 
 impl<'a> StartKafkaProducerRequest<'a> {
@@ -89,9 +126,15 @@ impl<'a> StartKafkaProducerRequest<'a> {
             bootstrap_server_addr,
             brokers_port_range: brokers_port_range.into(),
             project_route: project_route.to_string().into(),
+            record_encryption: None,
         }
     }
 
+    pub fn with_record_encryption(mut self, record_encryption: KafkaRecordEncryption) -> Self {
+        self.record_encryption = Some(record_encryption);
+        self
+    }
+
     pub fn bootstrap_server_addr(&self) -> SocketAddr {
         self.bootstrap_server_addr
     }
@@ -101,6 +144,9 @@ impl<'a> StartKafkaProducerRequest<'a> {
     pub fn project_route(&self) -> &CowStr<'a> {
         &self.project_route
     }
+    pub fn record_encryption(&self) -> KafkaRecordEncryption {
+        self.record_encryption.clone().unwrap_or_default()
+    }
 }
 
 /// Request body when instructing a node to start an Identity service
@@ -203,6 +249,53 @@ impl<'a> StartHopServiceRequest<'a> {
     }
 }
 
+/// Request body when instructing a node to start a Stream service
+/// together with its Index service
+#[derive(Debug, Clone, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct StartStreamServiceRequest<'a> {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<4312087>,
+    #[b(1)] pub addr: CowStr<'a>,
+    #[b(2)] pub index_addr: CowStr<'a>,
+    /// Size in bytes after which a new segment file is started
+    #[n(3)] pub segment_size: Option<u64>,
+    /// Remove segments older than this number of seconds
+    #[n(4)] pub max_age_secs: Option<u64>,
+    /// Remove the oldest segments while a stream takes more than this number of bytes
+    #[n(5)] pub max_size: Option<u64>,
+}
+
+impl<'a> StartStreamServiceRequest<'a> {
+    pub fn new(addr: impl Into<CowStr<'a>>, index_addr: impl Into<CowStr<'a>>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            addr: addr.into(),
+            index_addr: index_addr.into(),
+            segment_size: None,
+            max_age_secs: None,
+            max_size: None,
+        }
+    }
+
+    pub fn with_segment_size(mut self, segment_size: Option<u64>) -> Self {
+        self.segment_size = segment_size;
+        self
+    }
+
+    pub fn with_max_age_secs(mut self, max_age_secs: Option<u64>) -> Self {
+        self.max_age_secs = max_age_secs;
+        self
+    }
+
+    pub fn with_max_size(mut self, max_size: Option<u64>) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
 #[derive(Debug, Clone, Decode, Encode)]
 #[rustfmt::skip]
 #[cbor(map)]
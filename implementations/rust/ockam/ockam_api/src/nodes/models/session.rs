@@ -0,0 +1,128 @@
+//! Session monitoring request/response types
+
+use std::time::Duration;
+
+use minicbor::{Decode, Encode};
+#[cfg(feature = "tag")]
+use ockam_core::TypeTag;
+use serde::Serialize;
+
+/// Options controlling how a session is pinged, and how it is replaced when
+/// it becomes unresponsive. Unset options keep their default value.
+#[derive(Clone, Debug, Default, Decode, Encode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct SessionRetryOptions {
+    #[cfg(feature = "tag")]
+    #[n(0)] tag: TypeTag<6190283>,
+    /// Interval between two pings of the session
+    #[n(1)] pub ping_interval: Option<Duration>,
+    /// Number of unanswered pings after which the session is replaced
+    #[n(2)] pub max_missed_pings: Option<u32>,
+    /// Delay before the first replacement attempt, doubled after each failed attempt
+    #[n(3)] pub initial_backoff: Option<Duration>,
+    /// Maximum delay between two replacement attempts
+    #[n(4)] pub max_backoff: Option<Duration>,
+    /// Number of failed replacement attempts after which the session is given up
+    #[n(5)] pub max_attempts: Option<u32>,
+}
+
+impl SessionRetryOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_ping_interval(mut self, interval: Duration) -> Self {
+        self.ping_interval = Some(interval);
+        self
+    }
+
+    pub fn with_max_missed_pings(mut self, max: u32) -> Self {
+        self.max_missed_pings = Some(max);
+        self
+    }
+
+    pub fn with_initial_backoff(mut self, delay: Duration) -> Self {
+        self.initial_backoff = Some(delay);
+        self
+    }
+
+    pub fn with_max_backoff(mut self, delay: Duration) -> Self {
+        self.max_backoff = Some(delay);
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max: u32) -> Self {
+        self.max_attempts = Some(max);
+        self
+    }
+}
+
+/// Response body describing the health of a session
+#[derive(Clone, Debug, Decode, Encode, Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct SessionStatus {
+    #[cfg(feature = "tag")]
+    #[serde(skip_serializing)]
+    #[n(0)] tag: TypeTag<3872014>,
+    #[n(1)] pub key: String,
+    /// One of `up`, `degraded` (being replaced) or `down`
+    #[n(2)] pub status: String,
+    #[n(3)] pub ping_route: String,
+    /// Number of replacement attempts which failed since the session was last up
+    #[n(4)] pub failed_attempts: u32,
+    /// True if the session is down and is not replaced anymore
+    #[n(5)] pub given_up: bool,
+    /// Most recent events first
+    #[n(6)] pub history: Vec<SessionEventInfo>,
+}
+
+impl SessionStatus {
+    pub fn new(
+        key: impl Into<String>,
+        status: impl Into<String>,
+        ping_route: impl Into<String>,
+        failed_attempts: u32,
+        given_up: bool,
+        history: Vec<SessionEventInfo>,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            key: key.into(),
+            status: status.into(),
+            ping_route: ping_route.into(),
+            failed_attempts,
+            given_up,
+            history,
+        }
+    }
+}
+
+/// An event in the history of a session
+#[derive(Clone, Debug, Decode, Encode, Serialize)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct SessionEventInfo {
+    #[cfg(feature = "tag")]
+    #[serde(skip_serializing)]
+    #[n(0)] tag: TypeTag<5527641>,
+    /// Seconds since the unix epoch
+    #[n(1)] pub timestamp: u64,
+    /// One of `unresponsive`, `replacement_failed`, `replaced` or `gave_up`
+    #[n(2)] pub kind: String,
+    #[n(3)] pub details: Option<String>,
+}
+
+impl SessionEventInfo {
+    pub fn new(timestamp: u64, kind: impl Into<String>, details: Option<String>) -> Self {
+        Self {
+            #[cfg(feature = "tag")]
+            tag: TypeTag,
+            timestamp,
+            kind: kind.into(),
+            details,
+        }
+    }
+}
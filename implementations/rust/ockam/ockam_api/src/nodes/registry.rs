@@ -1,9 +1,11 @@
+use crate::nodes::models::portal::OutletStatus;
 use crate::nodes::service::Alias;
+use crate::session::Key;
 use ockam::identity::IdentityIdentifier;
 use ockam::remote::RemoteForwarderInfo;
 use ockam_core::compat::collections::BTreeMap;
 use ockam_core::flow_control::FlowControlId;
-use ockam_core::{Address, Route};
+use ockam_core::{Address, CowStr, Route};
 
 #[derive(Default)]
 pub(crate) struct SecureChannelRegistry {
@@ -130,6 +132,20 @@ pub(crate) struct CredentialsServiceInfo {}
 #[derive(Default)]
 pub(crate) struct AuthenticatorServiceInfo {}
 
+pub(crate) struct StreamServiceInfo {
+    index_addr: Address,
+}
+
+impl StreamServiceInfo {
+    pub fn new(index_addr: Address) -> Self {
+        Self { index_addr }
+    }
+
+    pub fn index_addr(&self) -> &Address {
+        &self.index_addr
+    }
+}
+
 pub(crate) enum KafkaServiceKind {
     Consumer,
     Producer,
@@ -154,6 +170,8 @@ pub(crate) struct InletInfo {
     pub(crate) bind_addr: String,
     pub(crate) worker_addr: Address,
     pub(crate) outlet_route: Route,
+    /// Session monitoring the connection to the outlet, if any
+    pub(crate) session: Option<Key>,
 }
 
 impl InletInfo {
@@ -170,13 +188,21 @@ impl InletInfo {
             bind_addr: bind_addr.to_owned(),
             worker_addr,
             outlet_route: outlet_route.to_owned(),
+            session: None,
         }
     }
+
+    /// Return true if the inlet was created, and not only recorded after a failure
+    pub(crate) fn is_created(&self) -> bool {
+        !self.worker_addr.address().is_empty()
+    }
 }
 
 #[derive(Clone)]
 pub(crate) struct OutletInfo {
     pub(crate) tcp_addr: String,
+    /// Path of the Unix domain socket the outlet connects to, instead of `tcp_addr`
+    pub(crate) unix_path: Option<String>,
     pub(crate) worker_addr: Address,
 }
 
@@ -188,9 +214,35 @@ impl OutletInfo {
         };
         Self {
             tcp_addr: tcp_addr.to_owned(),
+            unix_path: None,
             worker_addr,
         }
     }
+
+    /// Return true if the outlet was created, and not only recorded after a failure
+    pub(crate) fn is_created(&self) -> bool {
+        !self.worker_addr.address().is_empty()
+    }
+
+    pub(crate) fn with_unix_path(mut self, unix_path: Option<String>) -> Self {
+        self.unix_path = unix_path;
+        self
+    }
+
+    /// Return the status of this outlet, registered under the given alias
+    pub(crate) fn status<'a>(
+        &self,
+        alias: impl Into<CowStr<'a>>,
+        payload: impl Into<Option<CowStr<'a>>>,
+    ) -> OutletStatus<'a> {
+        OutletStatus::new(
+            self.tcp_addr.clone(),
+            self.worker_addr.to_string(),
+            alias,
+            payload,
+        )
+        .with_unix_path(self.unix_path.clone().map(CowStr::from))
+    }
 }
 
 #[derive(Default)]
@@ -206,6 +258,7 @@ pub(crate) struct Registry {
     pub(crate) hop_services: BTreeMap<Address, HopServiceInfo>,
     pub(crate) verifier_services: BTreeMap<Address, VerifierServiceInfo>,
     pub(crate) credentials_services: BTreeMap<Address, CredentialsServiceInfo>,
+    pub(crate) stream_services: BTreeMap<Address, StreamServiceInfo>,
     #[cfg(feature = "direct-authenticator")]
     pub(crate) authenticator_service: BTreeMap<Address, AuthenticatorServiceInfo>,
 
@@ -213,4 +266,6 @@ pub(crate) struct Registry {
     pub(crate) forwarders: BTreeMap<String, RemoteForwarderInfo>,
     pub(crate) inlets: BTreeMap<Alias, InletInfo>,
     pub(crate) outlets: BTreeMap<Alias, OutletInfo>,
+    pub(crate) udp_inlets: BTreeMap<Alias, InletInfo>,
+    pub(crate) udp_outlets: BTreeMap<Alias, OutletInfo>,
 }
@@ -12,8 +12,11 @@ use ockam::identity::{
     Credentials, CredentialsServer, CredentialsServerModule, Identities, IdentitiesRepository,
     IdentitiesVault, IdentityAttributesReader, IdentityAttributesWriter,
 };
-use ockam::identity::{IdentityIdentifier, SecureChannels};
-use ockam::{Address, Context, ForwardingService, Result, Routed, TcpTransport, Worker};
+use ockam::identity::{IdentityIdentifier, IdentitySecureChannelLocalInfo, SecureChannels};
+use ockam::{
+    Address, Context, ForwarderRegistry, ForwardingService, ForwardingServiceOptions, Result,
+    Routed, TcpTransport, Worker,
+};
 use ockam_abac::expr::{and, eq, ident, str};
 use ockam_abac::{Action, Env, Expr, PolicyAccessControl, PolicyStorage, Resource};
 use ockam_core::api::{Error, Method, Request, Response, ResponseBuilder, Status};
@@ -27,11 +30,17 @@ use ockam_core::flow_control::{FlowControlId, FlowControls};
 use ockam_core::IncomingAccessControl;
 use ockam_core::{AllowAll, AsyncTryClone, LOCAL};
 use ockam_identity::TrustContext;
-use ockam_multiaddr::proto::Service;
+use ockam_multiaddr::proto::{Relay, Service};
 use ockam_multiaddr::{MultiAddr, Protocol};
 use ockam_node::compat::asynchronous::RwLock;
 use ockam_node::tokio;
+use ockam_node::tokio::sync::broadcast;
 use ockam_node::tokio::task::JoinHandle;
+use ockam_node::{MessagePriority, WorkerBuilder};
+use ockam_transport_udp::UdpTransport;
+#[cfg(unix)]
+use ockam_transport_uds::UdsTransport;
+use ockam_transport_websocket::WebSocketTransport;
 
 use crate::bootstrapped_identities_store::BootstrapedIdentityStore;
 use crate::bootstrapped_identities_store::PreTrustedIdentities;
@@ -41,30 +50,38 @@ use crate::config::lookup::ProjectLookup;
 use crate::error::ApiError;
 use crate::nodes::connection::{
     Connection, ConnectionInstance, ConnectionInstanceBuilder, PlainTcpInstantiator,
-    ProjectInstantiator, SecureChannelInstantiator,
+    PlainTransportInstantiator, ProjectInstantiator, SecureChannelInstantiator,
 };
 use crate::nodes::models::base::NodeStatus;
 use crate::nodes::models::transport::{TransportMode, TransportType};
 use crate::nodes::models::workers::{WorkerList, WorkerStatus};
 use crate::rpc_proxy::RpcProxyService;
 use crate::session::sessions::Sessions;
-use crate::session::Medic;
-use crate::{local_worker, DefaultAddress};
+use crate::session::{record_session_events, Medic, SessionEvent};
+use crate::{local_worker, relay_address, DefaultAddress};
 
+use self::forwarder::{ForwarderAliasPolicy, FORWARDER_HEARTBEAT_TIMEOUT};
+use self::persistence::ResourceChange;
 use super::registry::Registry;
 
 mod credentials;
 mod forwarder;
+mod identity;
 pub mod message;
 mod node_identities;
 mod node_services;
+mod persistence;
 mod policy;
 mod portals;
 mod secure_channel;
 mod transport;
+mod udp_portals;
 
 const TARGET: &str = "ockam_api::nodemanager::service";
 
+/// Number of high priority requests which can wait for the node manager
+const PRIORITY_LANE_CAPACITY: usize = 16;
+
 pub(crate) type Alias = String;
 
 /// Generate a new alias for some user created extension
@@ -91,6 +108,7 @@ pub struct NodeManager {
     node_name: String,
     transports: Transports,
     pub(crate) tcp_transport: TcpTransport,
+    pub(crate) udp_transport: UdpTransport,
     pub(crate) controller_identity_id: IdentityIdentifier,
     skip_defaults: bool,
     enable_credential_checks: bool,
@@ -99,13 +117,21 @@ pub struct NodeManager {
     projects: Arc<BTreeMap<String, ProjectLookup>>,
     trust_context: Option<TrustContext>,
     pub(crate) registry: Registry,
+    forwarder_registry: ForwarderRegistry,
     sessions: Arc<Mutex<Sessions>>,
+    session_events: broadcast::Sender<SessionEvent>,
     medic: JoinHandle<Result<(), ockam_core::Error>>,
     policies: Arc<dyn PolicyStorage>,
     pub(crate) flow_controls: FlowControls,
 }
 
 impl NodeManager {
+    /// Subscribe to the events recorded when the sessions of inlets and relays
+    /// become unresponsive and are replaced
+    pub fn subscribe_session_events(&self) -> broadcast::Receiver<SessionEvent> {
+        self.session_events.subscribe()
+    }
+
     pub(super) fn identifier(&self) -> IdentityIdentifier {
         self.identifier.clone()
     }
@@ -157,6 +183,16 @@ impl NodeManagerWorker {
     pub fn get(&mut self) -> &mut Arc<RwLock<NodeManager>> {
         &mut self.node_manager
     }
+
+    /// Start the node manager at the given address.
+    /// Its requests sent with [`MessagePriority::High`] are handled first,
+    /// and answered with the same priority
+    pub async fn start(self, ctx: &Context, address: impl Into<Address>) -> Result<Address> {
+        WorkerBuilder::with_access_control(Arc::new(AllowAll), Arc::new(AllowAll), address, self)
+            .with_priority_lane(PRIORITY_LANE_CAPACITY)
+            .start(ctx)
+            .await
+    }
 }
 
 pub struct IdentityOverride {
@@ -341,15 +377,28 @@ impl NodeManager {
         // make sure that the configured identity exists in the repository
         identities_repository.update_identity(&identity).await?;
 
-        let flow_controls = FlowControls::default();
+        let flow_controls = ctx.flow_controls().clone();
         let medic = Medic::new(flow_controls.clone());
         let sessions = medic.sessions();
+        let session_events = medic.events();
+        // Session events are counted in the node metrics, to monitor the health of sessions
+        tokio::spawn(record_session_events(
+            session_events.subscribe(),
+            ctx.metrics().clone(),
+        ));
+
+        // Register the WebSocket and Unix domain socket transports on the node so that
+        // `/tcp/<port>/ws` and `/unix/<path>` addresses can be resolved to routes
+        WebSocketTransport::create(ctx).await?;
+        #[cfg(unix)]
+        UdsTransport::create(ctx).await?;
 
         let mut s = Self {
             cli_state,
             node_name: general_options.node_name,
             transports,
             tcp_transport: transport_options.tcp_transport,
+            udp_transport: UdpTransport::create(ctx).await?,
             controller_identity_id: Self::load_controller_identity_id()?,
             skip_defaults: general_options.skip_defaults,
             enable_credential_checks: trust_options.trust_context_config.is_some()
@@ -364,11 +413,13 @@ impl NodeManager {
             projects: Arc::new(projects_options.projects),
             trust_context: None,
             registry: Default::default(),
+            forwarder_registry: Default::default(),
             medic: {
                 let ctx = ctx.async_try_clone().await?;
                 tokio::spawn(medic.start(ctx))
             },
             sessions,
+            session_events,
             policies,
             flow_controls,
         };
@@ -410,13 +461,16 @@ impl NodeManager {
         self.start_hop_service_impl(ctx, DefaultAddress::HOP_SERVICE.into())
             .await?;
 
-        ForwardingService::create(
-            ctx,
-            DefaultAddress::FORWARDING_SERVICE,
-            AllowAll, // FIXME: @ac
-            AllowAll, // FIXME: @ac
-        )
-        .await?;
+        let alias_access_control =
+            ForwarderAliasPolicy::new(self.policies.clone(), self.identities_repository());
+        let options = ForwardingServiceOptions::new()
+            .with_service_incoming_access_control(AllowAll) // FIXME: @ac
+            .with_forwarders_incoming_access_control(AllowAll) // FIXME: @ac
+            .with_alias_access_control(Arc::new(alias_access_control))
+            .with_heartbeat_timeout(FORWARDER_HEARTBEAT_TIMEOUT)
+            .with_registry(self.forwarder_registry.clone());
+        ForwardingService::create_with_options(ctx, DefaultAddress::FORWARDING_SERVICE, options)
+            .await?;
 
         self.create_secure_channel_listener_impl(
             DefaultAddress::SECURE_CHANNEL_LISTENER.into(),
@@ -441,7 +495,7 @@ impl NodeManager {
         Ok(())
     }
 
-    /// Resolve project ID (if any), create secure channel (if needed) and create a tcp connection
+    /// Resolve project ID (if any), create secure channel (if needed) and create a transport connection
     /// Returns [`ConnectionInstance`]
     pub(crate) async fn connect(
         node_manager: Arc<RwLock<NodeManager>>,
@@ -458,6 +512,12 @@ impl NodeManager {
                     .ok_or_else(|| ApiError::generic("invalid service address"))?;
                 intermediary_services.push(Address::new(LOCAL, &*local));
             }
+            if protocol_value.code() == Relay::CODE {
+                let alias = protocol_value
+                    .cast::<Relay>()
+                    .ok_or_else(|| ApiError::generic("invalid relay address"))?;
+                intermediary_services.push(relay_address(&alias));
+            }
 
             if !local_worker(&protocol_value.code())? {
                 break;
@@ -482,7 +542,19 @@ impl NodeManager {
                     connection.identity_name.map(|x| x.to_string()),
                 ))
                 .await?
-                .instantiate(PlainTcpInstantiator::new(tcp_transport))
+                .instantiate(PlainTransportInstantiator::websocket(
+                    tcp_transport.async_try_clone().await?,
+                ))
+                .await?
+                .instantiate(PlainTcpInstantiator::new(
+                    tcp_transport.async_try_clone().await?,
+                ))
+                .await?
+                .instantiate(PlainTransportInstantiator::udp(
+                    tcp_transport.async_try_clone().await?,
+                ))
+                .await?
+                .instantiate(PlainTransportInstantiator::unix(tcp_transport))
                 .await?
                 .instantiate(SecureChannelInstantiator::new(
                     context.clone(),
@@ -536,6 +608,7 @@ impl NodeManagerWorker {
         ctx: &mut Context,
         req: &Request<'_>,
         dec: &mut Decoder<'_>,
+        requester: Option<&IdentityIdentifier>,
     ) -> Result<Vec<u8>> {
         debug! {
             target: TARGET,
@@ -554,6 +627,9 @@ impl NodeManagerWorker {
             None => todo!(),
         };
 
+        let resource_change =
+            ResourceChange::from_request(method, path, path_segments.as_slice(), dec);
+
         let r = match (method, path_segments.as_slice()) {
             // ==*== Basic node information ==*==
             // TODO: create, delete, destroy remote nodes
@@ -615,6 +691,11 @@ impl NodeManagerWorker {
                 self.present_credential(req, dec, ctx).await?.to_vec()?
             }
 
+            // ==*== Identity ==*==
+            (Post, ["node", "identity", "actions", "rotate"]) => {
+                self.rotate_identity(req).await?.to_vec()?
+            }
+
             // ==*== Secure channels ==*==
             // TODO: Change to RequestBuilder format
             (Get, ["node", "secure_channel"]) => {
@@ -667,6 +748,9 @@ impl NodeManagerWorker {
             (Post, ["node", "services", DefaultAddress::HOP_SERVICE]) => {
                 self.start_hop_service(ctx, req, dec).await?.to_vec()?
             }
+            (Post, ["node", "services", DefaultAddress::STREAM_SERVICE]) => {
+                self.start_stream_service(ctx, req, dec).await?.to_vec()?
+            }
             (Post, ["node", "services", DefaultAddress::DIRECT_AUTHENTICATOR]) => self
                 .start_authenticator_service(ctx, req, dec)
                 .await?
@@ -711,6 +795,14 @@ impl NodeManagerWorker {
                 .await?
                 .to_vec()?,
             (Post, ["node", "forwarder"]) => self.create_forwarder(ctx, req.id(), dec).await?,
+            (Get, ["node", "forwarding_service", "forwarders"]) => self
+                .list_registered_forwarders(req, requester)
+                .await
+                .to_vec()?,
+            (Delete, ["node", "forwarding_service", "forwarders", alias]) => self
+                .delete_registered_forwarder(ctx, req, alias, requester)
+                .await?
+                .to_vec()?,
 
             // ==*== Inlets & Outlets ==*==
             (Get, ["node", "inlet"]) => {
@@ -721,6 +813,9 @@ impl NodeManagerWorker {
                 self.get_inlets(req, inlet_registry).to_vec()?
             }
             (Get, ["node", "inlet", alias]) => self.show_inlet(req, alias).await?.to_vec()?,
+            (Get, ["node", "inlet", alias, "session"]) => {
+                self.show_inlet_session(req, alias).await?.to_vec()?
+            }
             (Get, ["node", "outlet"]) => {
                 let outlet_registry = {
                     let node_manager = self.node_manager.read().await;
@@ -736,6 +831,32 @@ impl NodeManagerWorker {
             }
             (Delete, ["node", "inlet", alias]) => self.delete_inlet(req, alias).await?.to_vec()?,
             (Delete, ["node", "portal"]) => todo!(),
+            (Get, ["node", "udp", "inlet"]) => {
+                let inlet_registry = {
+                    let node_manager = self.node_manager.read().await;
+                    &node_manager.registry.udp_inlets.clone()
+                };
+                self.get_inlets(req, inlet_registry).to_vec()?
+            }
+            (Get, ["node", "udp", "outlet"]) => {
+                let outlet_registry = {
+                    let node_manager = self.node_manager.read().await;
+                    &node_manager.registry.udp_outlets.clone()
+                };
+                self.get_outlets(req, outlet_registry).to_vec()?
+            }
+            (Post, ["node", "udp", "inlet"]) => {
+                self.create_udp_inlet(req, dec, ctx).await?.to_vec()?
+            }
+            (Post, ["node", "udp", "outlet"]) => {
+                self.create_udp_outlet(req, dec).await?.to_vec()?
+            }
+            (Delete, ["node", "udp", "inlet", alias]) => {
+                self.delete_udp_inlet(req, alias).await?.to_vec()?
+            }
+            (Delete, ["node", "udp", "outlet", alias]) => {
+                self.delete_udp_outlet(req, alias).await?.to_vec()?
+            }
 
             // ==*== Workers ==*==
             (Get, ["node", "workers"]) => {
@@ -772,6 +893,20 @@ impl NodeManagerWorker {
                 .get_policy(req, resource, action)
                 .await?
                 .either(ResponseBuilder::to_vec, ResponseBuilder::to_vec)?,
+            (Post, ["policy", resource, action, "check"]) => self
+                .node_manager
+                .read()
+                .await
+                .check_policy(req, resource, action, dec)
+                .await?
+                .either(ResponseBuilder::to_vec, ResponseBuilder::to_vec)?,
+            (Post, ["policy", resource, action, "dry-run"]) => self
+                .node_manager
+                .read()
+                .await
+                .dry_run_policy(req, resource, action, dec)
+                .await?
+                .either(ResponseBuilder::to_vec, ResponseBuilder::to_vec)?,
             (Delete, ["policy", resource, action]) => self
                 .node_manager
                 .read()
@@ -833,6 +968,12 @@ impl NodeManagerWorker {
                     .to_vec()?
             }
         };
+
+        if let Some(change) = resource_change {
+            if let Err(err) = self.record_resource_change(change, &r).await {
+                warn!(%path, %err, "failed to record the node resource");
+            }
+        }
         Ok(r)
     }
 }
@@ -862,6 +1003,11 @@ impl Worker for NodeManagerWorker {
         )
         .await?;
 
+        // Recreate the resources which existed before the node was stopped,
+        // requests are handled as if they were sent again to the node
+        drop(node_manager);
+        self.restore_resources(ctx).await?;
+
         Ok(())
     }
 
@@ -881,7 +1027,16 @@ impl Worker for NodeManagerWorker {
             }
         };
 
-        let r = match self.handle_request(ctx, &req, &mut dec).await {
+        // Requests received over a secure channel are made on behalf of the remote identity,
+        // local requests are made on behalf of the node itself
+        let requester = IdentitySecureChannelLocalInfo::find_info(msg.local_message())
+            .map(|info| info.their_identity_id())
+            .ok();
+
+        let r = match self
+            .handle_request(ctx, &req, &mut dec, requester.as_ref())
+            .await
+        {
             Ok(r) => r,
             Err(err) => {
                 error! {
@@ -907,6 +1062,8 @@ impl Worker for NodeManagerWorker {
             path   = %req.path(),
             "responding"
         }
-        ctx.send(msg.return_route(), r).await
+        let priority = MessagePriority::of(msg.local_message());
+        ctx.send_with_priority(msg.return_route(), r, priority)
+            .await
     }
 }
@@ -91,14 +91,11 @@ impl NodeManagerWorker {
         } else {
             node_manager
                 .credentials_service()
-                .present_credential_mutual(
+                .present_credential_mutual_with_trust_context(
                     ctx,
                     route,
-                    node_manager
-                        .trust_context()?
-                        .authorities()
-                        .await?
-                        .as_slice(),
+                    node_manager.trust_context()?,
+                    &node_manager.identifier(),
                     credential,
                     MessageSendReceiveOptions::new().with_flow_control(&node_manager.flow_controls),
                 )
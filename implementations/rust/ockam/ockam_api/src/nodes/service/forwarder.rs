@@ -1,14 +1,17 @@
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use minicbor::Decoder;
 use ockam::compat::asynchronous::RwLock;
 use ockam::compat::sync::Mutex;
-use ockam::identity::IdentityIdentifier;
+use ockam::identity::{IdentitiesRepository, IdentityIdentifier};
 use ockam::remote::{RemoteForwarder, RemoteForwarderInfo, RemoteForwarderOptions};
-use ockam::Result;
+use ockam::{AliasAccessControl, Result};
+use ockam_abac::expr::str;
+use ockam_abac::{Env, PolicyAccessControl, PolicyStorage};
 use ockam_core::api::{Id, Request, Response, ResponseBuilder, Status};
-use ockam_core::AsyncTryClone;
+use ockam_core::{async_trait, AsyncTryClone, IncomingAccessControl, RelayMessage};
 use ockam_multiaddr::MultiAddr;
 use ockam_node::tokio::time::timeout;
 use ockam_node::Context;
@@ -16,12 +19,66 @@ use ockam_node::Context;
 use crate::error::ApiError;
 use crate::local_multiaddr_to_route;
 use crate::nodes::connection::{Connection, ConnectionInstance};
-use crate::nodes::models::forwarder::{CreateForwarder, ForwarderInfo};
+use crate::nodes::models::forwarder::{CreateForwarder, ForwarderInfo, RegisteredForwarder};
 use crate::session::sessions::{Replacer, Session};
 use crate::session::sessions::{MAX_CONNECT_TIME, MAX_RECOVERY_TIME};
+use crate::{actions, resources};
 
 use super::{NodeManager, NodeManagerWorker};
 
+/// Static forwarders registered at this node over a secure channel are removed after
+/// 3 missed heartbeats
+pub(super) const FORWARDER_HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Check the aliases registered at the forwarding service of a node against the
+/// policy of the `forwarder` resource for the `register` action.
+/// The alias is available to the policy as the `resource.alias` attribute.
+/// Any alias can be registered as long as no policy is set.
+pub(super) struct ForwarderAliasPolicy {
+    policies: Arc<dyn PolicyStorage>,
+    repository: Arc<dyn IdentitiesRepository>,
+}
+
+impl ForwarderAliasPolicy {
+    pub(super) fn new(
+        policies: Arc<dyn PolicyStorage>,
+        repository: Arc<dyn IdentitiesRepository>,
+    ) -> Self {
+        Self {
+            policies,
+            repository,
+        }
+    }
+}
+
+#[async_trait]
+impl AliasAccessControl for ForwarderAliasPolicy {
+    async fn is_authorized(&self, alias: &str, relay_msg: &RelayMessage) -> Result<bool> {
+        if self
+            .policies
+            .get_policy(&resources::FORWARDER, &actions::REGISTER)
+            .await?
+            .is_none()
+        {
+            return Ok(true);
+        }
+
+        let mut env = Env::new();
+        env.put("resource.id", str(resources::FORWARDER.as_str()));
+        env.put("action.id", str(actions::REGISTER.as_str()));
+        env.put("resource.alias", str(alias));
+        PolicyAccessControl::new(
+            self.policies.clone(),
+            self.repository.clone(),
+            resources::FORWARDER,
+            actions::REGISTER,
+            env,
+        )
+        .is_authorized(relay_msg)
+        .await
+    }
+}
+
 impl NodeManagerWorker {
     pub(super) async fn create_forwarder(
         &mut self,
@@ -49,7 +106,7 @@ impl NodeManagerWorker {
 
         let forwarder = if req.at_rust_node() {
             if let Some(alias) = req.alias() {
-                RemoteForwarder::create_static_without_heartbeats(ctx, route, alias, options).await
+                RemoteForwarder::create_static_at_rust_node(ctx, route, alias, options).await
             } else {
                 RemoteForwarder::create(ctx, route, options).await
             }
@@ -172,6 +229,58 @@ impl NodeManagerWorker {
     }
 }
 
+impl NodeManagerWorker {
+    /// List the forwarders registered at this node. A remote requester only gets
+    /// the forwarders it owns, local requests get all the forwarders.
+    pub(super) async fn list_registered_forwarders(
+        &self,
+        req: &Request<'_>,
+        requester: Option<&IdentityIdentifier>,
+    ) -> ResponseBuilder<Vec<RegisteredForwarder>> {
+        debug!("Handling ListRegisteredForwarders request");
+        let node_manager = self.node_manager.read().await;
+        Response::ok(req.id()).body(
+            node_manager
+                .forwarder_registry
+                .list()
+                .into_iter()
+                .filter(|entry| requester.is_none() || entry.owner() == requester)
+                .map(RegisteredForwarder::from)
+                .collect(),
+        )
+    }
+
+    /// Delete a forwarder registered at this node. A remote requester can only
+    /// delete the forwarders it owns, local requests can delete any forwarder.
+    pub(super) async fn delete_registered_forwarder(
+        &self,
+        ctx: &Context,
+        req: &Request<'_>,
+        alias: &str,
+        requester: Option<&IdentityIdentifier>,
+    ) -> Result<ResponseBuilder<Option<RegisteredForwarder>>> {
+        debug!(%alias, "Handling DeleteRegisteredForwarder request");
+        let forwarder_registry = self.node_manager.read().await.forwarder_registry.clone();
+
+        let entry = match forwarder_registry.get(alias) {
+            Some(entry) => entry,
+            None => {
+                error!(%alias, "Forwarder not found in the forwarding service");
+                return Ok(Response::not_found(req.id()).body(None));
+            }
+        };
+        if let Some(requester) = requester {
+            if entry.owner() != Some(requester) {
+                warn!(%alias, %requester, "Forwarder is not owned by the requester");
+                return Ok(Response::forbidden(req.id()).body(None));
+            }
+        }
+
+        let deleted = forwarder_registry.delete(ctx, alias).await?;
+        Ok(Response::ok(req.id()).body(deleted.map(RegisteredForwarder::from)))
+    }
+}
+
 /// Create a session replacer.
 ///
 /// This returns a function that accepts the previous ping address (e.g.
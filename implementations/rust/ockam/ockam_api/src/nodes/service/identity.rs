@@ -0,0 +1,30 @@
+use ockam::Result;
+use ockam_core::api::{Request, Response, ResponseBuilder};
+
+use crate::nodes::models::identity::LongIdentityResponse;
+
+use super::NodeManagerWorker;
+
+impl NodeManagerWorker {
+    /// Rotate the root key of the node identity and return its new change history.
+    /// The identity state of the node is updated as well, so that the node uses
+    /// the rotated identity when it is restarted
+    pub(super) async fn rotate_identity(
+        &mut self,
+        req: &Request<'_>,
+    ) -> Result<ResponseBuilder<LongIdentityResponse<'_>>> {
+        let node_manager = self.node_manager.write().await;
+        let identity = node_manager
+            .identities()
+            .rotate_root_key(&node_manager.identifier())
+            .await?;
+
+        let mut identity_state = node_manager
+            .cli_state
+            .identities
+            .get_by_identifier(&identity.identifier())?;
+        identity_state.update_identity(identity.clone())?;
+
+        Ok(Response::ok(req.id()).body(LongIdentityResponse::new(identity.export()?)))
+    }
+}
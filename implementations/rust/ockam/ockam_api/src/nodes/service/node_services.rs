@@ -1,7 +1,9 @@
 use std::net::IpAddr;
+use std::time::Duration;
 
 use minicbor::Decoder;
 
+use ockam::stream::{is_valid_stream_name, IndexService, StreamService, StreamStorageOptions};
 use ockam::{Address, Context, Result};
 use ockam_abac::expr::{and, eq, ident, str};
 use ockam_abac::{Action, Env, Expr, PolicyAccessControl, Resource};
@@ -16,6 +18,7 @@ use ockam_node::WorkerBuilder;
 
 use crate::auth::Server;
 use crate::authenticator::direct::EnrollmentTokenAuthenticator;
+use crate::cli_state::traits::StateDirTrait;
 use crate::echoer::Echoer;
 use crate::error::ApiError;
 use crate::hop::Hop;
@@ -26,15 +29,15 @@ use crate::kafka::{
 };
 use crate::nodes::models::portal::CreateInlet;
 use crate::nodes::models::services::{
-    ServiceList, ServiceStatus, StartAuthenticatedServiceRequest, StartAuthenticatorRequest,
-    StartCredentialsService, StartEchoerServiceRequest, StartHopServiceRequest,
-    StartIdentityServiceRequest, StartKafkaConsumerRequest, StartKafkaProducerRequest,
-    StartOktaIdentityProviderRequest, StartServiceRequest, StartUppercaseServiceRequest,
-    StartVerifierService,
+    KafkaRecordEncryption, ServiceList, ServiceStatus, StartAuthenticatedServiceRequest,
+    StartAuthenticatorRequest, StartCredentialsService, StartEchoerServiceRequest,
+    StartHopServiceRequest, StartIdentityServiceRequest, StartKafkaConsumerRequest,
+    StartKafkaProducerRequest, StartOktaIdentityProviderRequest, StartServiceRequest,
+    StartStreamServiceRequest, StartUppercaseServiceRequest, StartVerifierService,
 };
 use crate::nodes::registry::{
     AuthenticatorServiceInfo, CredentialsServiceInfo, KafkaServiceInfo, KafkaServiceKind, Registry,
-    VerifierServiceInfo,
+    StreamServiceInfo, VerifierServiceInfo,
 };
 use crate::nodes::NodeManager;
 use crate::port_range::PortRange;
@@ -175,6 +178,62 @@ impl NodeManager {
         Ok(())
     }
 
+    pub(super) async fn start_stream_service_impl(
+        &mut self,
+        ctx: &Context,
+        addr: Address,
+        index_addr: Address,
+        options: StreamStorageOptions,
+    ) -> Result<()> {
+        if self.registry.stream_services.contains_key(&addr) {
+            return Err(ApiError::generic("Stream service exists at this address"));
+        }
+        // The address of the service names the files storing its streams
+        if !is_valid_stream_name(addr.address()) {
+            return Err(ApiError::generic(
+                "The address of a stream service can only contain letters, digits, '-', '_' and '.'",
+            ));
+        }
+
+        let node_state = self.cli_state.nodes.get(&self.node_name)?;
+        let indices_storage = node_state.stream_indices_storage(addr.address()).await?;
+
+        let maybe_trust_context_id = self.trust_context.as_ref().map(|c| c.id());
+        let resource = Resource::assert_inline(addr.address());
+        let ac = self
+            .access_control(
+                &resource,
+                &actions::HANDLE_MESSAGE,
+                maybe_trust_context_id,
+                None,
+            )
+            .await?;
+
+        StreamService::create(
+            ctx,
+            addr.clone(),
+            node_state.streams_dir(addr.address()),
+            options,
+            ac.clone(),
+        )
+        .await?;
+        if let Err(err) =
+            IndexService::create(ctx, index_addr.clone(), Arc::new(indices_storage), ac).await
+        {
+            // Don't leave a stream service which is not registered behind
+            if let Err(err) = ctx.stop_worker(addr.clone()).await {
+                warn!(%addr, %err, "failed to stop the stream service");
+            }
+            return Err(err);
+        }
+
+        self.registry
+            .stream_services
+            .insert(addr, StreamServiceInfo::new(index_addr));
+
+        Ok(())
+    }
+
     pub(super) async fn start_hop_service_impl(
         &mut self,
         ctx: &Context,
@@ -273,6 +332,7 @@ impl NodeManager {
         let direct = crate::authenticator::direct::DirectAuthenticator::new(
             project.clone(),
             self.attributes_writer(),
+            self.attributes_reader(),
         )
         .await?;
 
@@ -315,9 +375,16 @@ impl NodeManager {
         }
         let action = actions::HANDLE_MESSAGE;
         let resource = Resource::new(&issuer_addr.to_string());
+        let tokens_storage = self
+            .cli_state
+            .nodes
+            .get(&self.node_name)?
+            .enrollment_tokens_storage()
+            .await?;
         let (issuer, acceptor) = EnrollmentTokenAuthenticator::new_worker_pair(
             project.clone(),
             self.attributes_writer(),
+            Arc::new(tokens_storage),
         );
         let rule = and([
             eq([ident("resource.project_id"), ident("subject.project_id")]),
@@ -444,6 +511,34 @@ impl NodeManagerWorker {
         Ok(Response::ok(req.id()))
     }
 
+    pub(super) async fn start_stream_service(
+        &mut self,
+        ctx: &Context,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder> {
+        let mut node_manager = self.node_manager.write().await;
+        let req_body: StartStreamServiceRequest = dec.decode()?;
+        let addr = req_body.addr.to_string().into();
+        let index_addr = req_body.index_addr.to_string().into();
+
+        let mut options = StreamStorageOptions::new();
+        if let Some(segment_size) = req_body.segment_size {
+            options = options.with_segment_size(segment_size);
+        }
+        if let Some(max_age_secs) = req_body.max_age_secs {
+            options = options.with_max_age(Duration::from_secs(max_age_secs));
+        }
+        if let Some(max_size) = req_body.max_size {
+            options = options.with_max_size(max_size);
+        }
+
+        node_manager
+            .start_stream_service_impl(ctx, addr, index_addr, options)
+            .await?;
+        Ok(Response::ok(req.id()))
+    }
+
     pub(super) async fn start_hop_service(
         &mut self,
         ctx: &Context,
@@ -618,6 +713,7 @@ impl NodeManagerWorker {
             body_req.brokers_port_range(),
             project_route,
             KafkaServiceKind::Consumer,
+            body_req.record_encryption(),
         )
         .await?;
 
@@ -653,6 +749,7 @@ impl NodeManagerWorker {
             body_req.brokers_port_range(),
             body_req.project_route().to_string().parse()?,
             KafkaServiceKind::Producer,
+            body_req.record_encryption(),
         )
         .await?;
 
@@ -671,6 +768,7 @@ impl NodeManagerWorker {
         brokers_port_range: (u16, u16),
         project_multiaddr: MultiAddr,
         kind: KafkaServiceKind,
+        record_encryption: KafkaRecordEncryption,
     ) -> Result<()> {
         debug!("project_multiaddr: {}", project_multiaddr.to_string());
 
@@ -746,6 +844,7 @@ impl NodeManagerWorker {
             secure_channel_controller.into_trait(),
             local_interceptor_address.clone(),
             flow_controls,
+            record_encryption,
         )
         .await?;
 
@@ -816,6 +915,16 @@ impl NodeManagerWorker {
                 DefaultAddress::CREDENTIALS_SERVICE,
             ))
         });
+        registry.stream_services.iter().for_each(|(addr, info)| {
+            list.push(ServiceStatus::new(
+                addr.address(),
+                DefaultAddress::STREAM_SERVICE,
+            ));
+            list.push(ServiceStatus::new(
+                info.index_addr().address(),
+                DefaultAddress::STREAM_INDEX_SERVICE,
+            ));
+        });
         registry.kafka_services.iter().for_each(|(address, info)| {
             list.push(ServiceStatus::new(
                 address.address(),
@@ -0,0 +1,254 @@
+use minicbor::{Decode, Decoder};
+use ockam::{Context, Result};
+use ockam_core::api::{Method, Request, Response, Status};
+
+use crate::cli_state::{NodeResourceConfig, NodeResourceKind, StateDirTrait, StateItemTrait};
+use crate::nodes::models::forwarder::ForwarderInfo;
+use crate::nodes::models::portal::{InletStatus, OutletStatus};
+use crate::nodes::models::secure_channel::{
+    CreateSecureChannelListenerRequest, DeleteSecureChannelListenerRequest,
+};
+use crate::nodes::models::services::{
+    KafkaKeyEncryption, StartKafkaConsumerRequest, StartKafkaProducerRequest, StartServiceRequest,
+};
+use crate::nodes::NodeManager;
+use crate::DefaultAddress;
+
+use super::NodeManagerWorker;
+
+/// A request creating or deleting a resource which is recorded in the node setup
+pub(super) enum ResourceChange {
+    Create {
+        kind: NodeResourceKind,
+        path: String,
+        body: Vec<u8>,
+    },
+    Delete {
+        kind: NodeResourceKind,
+        name: String,
+    },
+}
+
+impl ResourceChange {
+    /// Return the change made by a request if it creates or deletes a recorded resource
+    pub(super) fn from_request(
+        method: Method,
+        path: &str,
+        segments: &[&str],
+        dec: &Decoder<'_>,
+    ) -> Option<Self> {
+        use Method::*;
+        use NodeResourceKind::*;
+
+        let create = |kind| {
+            Some(ResourceChange::Create {
+                kind,
+                path: path.to_string(),
+                body: dec.input()[dec.position()..].to_vec(),
+            })
+        };
+        let delete = |kind, name: &str| {
+            Some(ResourceChange::Delete {
+                kind,
+                name: name.to_string(),
+            })
+        };
+
+        match (method, segments) {
+            (Post, ["node", "inlet"]) => create(Inlet),
+            (Post, ["node", "outlet"]) => create(Outlet),
+            (Post, ["node", "udp", "inlet"]) => create(UdpInlet),
+            (Post, ["node", "udp", "outlet"]) => create(UdpOutlet),
+            (Post, ["node", "forwarder"]) => create(Forwarder),
+            (Post, ["node", "secure_channel_listener"]) => create(SecureChannelListener),
+            // the secret used to hash the record keys must not be written to disk
+            (Post, ["node", "services", service]) if has_key_hash_secret(service, dec) => {
+                warn!(%service, "the service uses a key hash secret, it won't be restored when the node restarts");
+                None
+            }
+            (Post, ["node", "services", _]) => create(Service),
+            (Delete, ["node", "inlet", alias]) => delete(Inlet, alias),
+            (Delete, ["node", "outlet", alias]) => delete(Outlet, alias),
+            (Delete, ["node", "udp", "inlet", alias]) => delete(UdpInlet, alias),
+            (Delete, ["node", "udp", "outlet", alias]) => delete(UdpOutlet, alias),
+            (Delete, ["node", "forwarder", remote_address]) => delete(Forwarder, remote_address),
+            (Delete, ["node", "secure_channel_listener"]) => {
+                let request: DeleteSecureChannelListenerRequest =
+                    Decoder::new(&dec.input()[dec.position()..]).decode().ok()?;
+                delete(SecureChannelListener, &request.addr)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Return true if a request starting a Kafka service carries the secret used to hash the keys
+fn has_key_hash_secret(service: &str, dec: &Decoder<'_>) -> bool {
+    let mut dec = Decoder::new(&dec.input()[dec.position()..]);
+    let record_encryption = match service {
+        DefaultAddress::KAFKA_CONSUMER => dec
+            .decode::<StartServiceRequest<StartKafkaConsumerRequest>>()
+            .map(|body| body.request().record_encryption()),
+        DefaultAddress::KAFKA_PRODUCER => dec
+            .decode::<StartServiceRequest<StartKafkaProducerRequest>>()
+            .map(|body| body.request().record_encryption()),
+        _ => return false,
+    };
+    matches!(
+        record_encryption.map(|encryption| encryption.keys),
+        Ok(KafkaKeyEncryption::Deterministic(_))
+    )
+}
+
+/// Body of a `StartServiceRequest` for any kind of service, only its address is decoded
+struct AnyServiceRequest;
+
+impl<'b, C> Decode<'b, C> for AnyServiceRequest {
+    fn decode(d: &mut Decoder<'b>, _: &mut C) -> Result<Self, minicbor::decode::Error> {
+        d.skip()?;
+        Ok(AnyServiceRequest)
+    }
+}
+
+impl NodeManagerWorker {
+    /// Record the change made by a request in the node setup, once the request succeeded
+    pub(super) async fn record_resource_change(
+        &self,
+        change: ResourceChange,
+        response: &[u8],
+    ) -> Result<()> {
+        let mut dec = Decoder::new(response);
+        let header: Response = dec.decode()?;
+        if header.status() != Some(Status::Ok) {
+            return Ok(());
+        }
+
+        let node_manager = self.node_manager.read().await;
+        match change {
+            ResourceChange::Create { kind, path, body } => {
+                let name = match kind {
+                    NodeResourceKind::Inlet | NodeResourceKind::UdpInlet => {
+                        dec.decode::<InletStatus>()?.alias.to_string()
+                    }
+                    NodeResourceKind::Outlet | NodeResourceKind::UdpOutlet => {
+                        dec.decode::<OutletStatus>()?.alias.to_string()
+                    }
+                    NodeResourceKind::Forwarder => {
+                        dec.decode::<ForwarderInfo>()?.remote_address().to_string()
+                    }
+                    NodeResourceKind::SecureChannelListener => Decoder::new(&body)
+                        .decode::<CreateSecureChannelListenerRequest>()?
+                        .addr
+                        .to_string(),
+                    NodeResourceKind::Service => Decoder::new(&body)
+                        .decode::<StartServiceRequest<AnyServiceRequest>>()?
+                        .address()
+                        .to_string(),
+                };
+                node_manager.record_resource(NodeResourceConfig::new(kind, name, path, body))
+            }
+            ResourceChange::Delete { kind, name } => node_manager.forget_resource(kind, &name),
+        }
+    }
+
+    /// Recreate the resources recorded in the node setup. A resource which can't be
+    /// recreated stays recorded, so that it is recreated the next time the node starts.
+    pub(super) async fn restore_resources(&mut self, ctx: &mut Context) -> Result<()> {
+        let resources: Vec<NodeResourceConfig> = {
+            let node_manager = self.node_manager.read().await;
+            let node_state = node_manager.cli_state.nodes.get(&node_manager.node_name)?;
+            node_state
+                .config()
+                .setup()
+                .resources()
+                .into_iter()
+                .cloned()
+                .collect()
+        };
+
+        for resource in resources {
+            info!(kind = ?resource.kind, name = %resource.name, "restoring resource");
+
+            // the resource is recorded again under its new name when it is recreated,
+            // inlets and outlets created without an alias get a different one
+            self.node_manager
+                .read()
+                .await
+                .forget_resource(resource.kind, &resource.name)?;
+
+            let mut request = minicbor::to_vec(Request::new(Method::Post, &resource.path, true))?;
+            request.extend_from_slice(&resource.body);
+            let mut dec = Decoder::new(&request);
+            let req: Request = dec.decode()?;
+
+            let restored = match self.handle_request(ctx, &req, &mut dec, None).await {
+                Ok(response) => Decoder::new(&response)
+                    .decode::<Response>()
+                    .map(|header| header.status() == Some(Status::Ok))
+                    .unwrap_or(false),
+                Err(_) => false,
+            };
+            if !restored {
+                warn!(kind = ?resource.kind, name = %resource.name, "failed to restore resource");
+                self.node_manager.read().await.record_resource(resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl NodeManager {
+    fn record_resource(&self, resource: NodeResourceConfig) -> Result<()> {
+        let node_state = self.cli_state.nodes.get(&self.node_name)?;
+        node_state.set_setup(&node_state.config().setup_mut().add_resource(resource))?;
+        Ok(())
+    }
+
+    fn forget_resource(&self, kind: NodeResourceKind, name: &str) -> Result<()> {
+        let node_state = self.cli_state.nodes.get(&self.node_name)?;
+        node_state.set_setup(&node_state.config().setup_mut().remove_resource(kind, name))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes::models::services::KafkaRecordEncryption;
+    use ockam_multiaddr::MultiAddr;
+    use std::str::FromStr;
+
+    fn start_kafka_producer(keys: KafkaKeyEncryption) -> Option<ResourceChange> {
+        let request = StartKafkaProducerRequest::new(
+            "127.0.0.1:9092".parse().unwrap(),
+            (4000, 4100),
+            MultiAddr::from_str("/project/default").unwrap(),
+        )
+        .with_record_encryption(KafkaRecordEncryption {
+            encrypt_headers: false,
+            keys,
+        });
+        let body = minicbor::to_vec(StartServiceRequest::new(
+            request,
+            DefaultAddress::KAFKA_PRODUCER,
+        ))
+        .unwrap();
+        let path = format!("/node/services/{}", DefaultAddress::KAFKA_PRODUCER);
+        let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+        ResourceChange::from_request(Method::Post, &path, &segments, &Decoder::new(&body))
+    }
+
+    #[test]
+    fn a_key_hash_secret_is_not_recorded() {
+        assert!(matches!(
+            start_kafka_producer(KafkaKeyEncryption::Randomized),
+            Some(ResourceChange::Create {
+                kind: NodeResourceKind::Service,
+                ..
+            })
+        ));
+        assert!(
+            start_kafka_producer(KafkaKeyEncryption::Deterministic(b"secret".to_vec())).is_none()
+        );
+    }
+}
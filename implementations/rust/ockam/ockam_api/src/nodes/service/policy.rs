@@ -1,7 +1,10 @@
-use crate::nodes::models::policy::{Policy, PolicyList};
+use crate::nodes::models::policy::{
+    CheckPolicy, DryRunPolicy, Policy, PolicyDiagnostics, PolicyList, PolicyTrace,
+};
 use either::Either;
 use minicbor::Decoder;
-use ockam_abac::{Action, Resource};
+use ockam_abac::expr::str;
+use ockam_abac::{check, dry_run, Action, Env, Expr, Resource, Schema, Type};
 use ockam_core::api::{Error, Request, Response, ResponseBuilder};
 use ockam_core::Result;
 
@@ -33,11 +36,7 @@ impl NodeManager {
         if let Some(e) = self.policies.get_policy(&r, &a).await? {
             Ok(Either::Right(Response::ok(req.id()).body(Policy::new(e))))
         } else {
-            let mut err = Error::new(req.path()).with_message("policy not found");
-            if let Some(m) = req.method() {
-                err.set_method(m)
-            }
-            Ok(Either::Left(Response::not_found(req.id()).body(err)))
+            Ok(Either::Left(policy_not_found(req)))
         }
     }
 
@@ -62,4 +61,83 @@ impl NodeManager {
         self.policies.del_policy(&r, &a).await?;
         Ok(Response::ok(req.id()))
     }
+
+    pub(super) async fn check_policy<'a>(
+        &self,
+        req: &'a Request<'_>,
+        resource: &str,
+        action: &str,
+        dec: &mut Decoder<'_>,
+    ) -> Result<Either<ResponseBuilder<Error<'a>>, ResponseBuilder<PolicyDiagnostics>>> {
+        let body: CheckPolicy = dec.decode()?;
+        let r = Resource::new(resource);
+        let a = Action::new(action);
+        let expr = match self.policy_expression(&r, &a, body.expression()).await? {
+            Some(e) => e,
+            None => return Ok(Either::Left(policy_not_found(req))),
+        };
+        let mut schema = body.schema().clone();
+        schema.merge_left(known_attributes());
+        let diagnostics = check(&expr, &schema);
+        Ok(Either::Right(
+            Response::ok(req.id()).body(PolicyDiagnostics::new(diagnostics)),
+        ))
+    }
+
+    pub(super) async fn dry_run_policy<'a>(
+        &self,
+        req: &'a Request<'_>,
+        resource: &str,
+        action: &str,
+        dec: &mut Decoder<'_>,
+    ) -> Result<Either<ResponseBuilder<Error<'a>>, ResponseBuilder<PolicyTrace>>> {
+        let body: DryRunPolicy = dec.decode()?;
+        let r = Resource::new(resource);
+        let a = Action::new(action);
+        let expr = match self.policy_expression(&r, &a, body.expression()).await? {
+            Some(e) => e,
+            None => return Ok(Either::Left(policy_not_found(req))),
+        };
+        let mut env = Env::new();
+        env.put("resource.id", str(r.as_str()));
+        env.put("action.id", str(a.as_str()));
+        for (k, v) in body.attributes() {
+            env.put(k, str(v.as_str()));
+        }
+        let trace = dry_run(&expr, &env);
+        Ok(Either::Right(
+            Response::ok(req.id()).body(PolicyTrace::new(trace)),
+        ))
+    }
+
+    /// Return the given expression or else the stored policy.
+    async fn policy_expression(
+        &self,
+        r: &Resource,
+        a: &Action,
+        expr: Option<&Expr>,
+    ) -> Result<Option<Expr>> {
+        match expr {
+            Some(e) => Ok(Some(e.clone())),
+            None => self.policies.get_policy(r, a).await,
+        }
+    }
+}
+
+/// Attributes which are always provided when a node evaluates a policy.
+fn known_attributes() -> Schema {
+    Schema::new()
+        .with("resource.id", Type::Str)
+        .with("action.id", Type::Str)
+        .with("resource.project_id", Type::Str)
+        .with("resource.trust_context_id", Type::Str)
+        .with("subject.identifier", Type::Str)
+}
+
+fn policy_not_found<'a>(req: &'a Request<'_>) -> ResponseBuilder<Error<'a>> {
+    let mut err = Error::new(req.path()).with_message("policy not found");
+    if let Some(m) = req.method() {
+        err.set_method(m)
+    }
+    Response::not_found(req.id()).body(err)
 }
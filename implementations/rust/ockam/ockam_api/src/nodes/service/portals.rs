@@ -4,9 +4,12 @@ use crate::nodes::connection::{Connection, ConnectionInstance};
 use crate::nodes::models::portal::{
     CreateInlet, CreateOutlet, InletList, InletStatus, OutletList, OutletStatus,
 };
+use crate::nodes::models::session::{SessionEventInfo, SessionStatus};
 use crate::nodes::registry::{InletInfo, OutletInfo};
 use crate::nodes::service::random_alias;
-use crate::session::sessions::{Replacer, Session, MAX_CONNECT_TIME, MAX_RECOVERY_TIME};
+use crate::session::sessions::{
+    Replacer, RetryPolicy, Session, MAX_CONNECT_TIME, MAX_RECOVERY_TIME,
+};
 use crate::{actions, resources, DefaultAddress};
 use minicbor::Decoder;
 use ockam::compat::tokio::time::timeout;
@@ -14,15 +17,15 @@ use ockam::identity::IdentityIdentifier;
 use ockam::{Address, AsyncTryClone, Result};
 
 use ockam_abac::Resource;
-use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_core::api::{Request, Response, ResponseBuilder, Status};
 use ockam_core::compat::sync::Arc;
 use ockam_core::flow_control::FlowControlPolicy;
-use ockam_core::{route, IncomingAccessControl, Route};
+use ockam_core::{route, CowStr, IncomingAccessControl, Route};
 use ockam_multiaddr::proto::Project;
 use ockam_multiaddr::MultiAddr;
 use ockam_node::compat::asynchronous::RwLock;
 use ockam_node::Context;
-use ockam_transport_tcp::{TcpInletOptions, TcpOutletOptions};
+use ockam_transport_tcp::{TcpInletOptions, TcpOutletOptions, TcpTransport};
 use std::collections::BTreeMap;
 use std::sync::Mutex;
 use std::time::Duration;
@@ -59,9 +62,7 @@ impl NodeManagerWorker {
         Response::ok(req.id()).body(OutletList::new(
             outlet_registry
                 .iter()
-                .map(|(alias, info)| {
-                    OutletStatus::new(&info.tcp_addr, info.worker_addr.to_string(), alias, None)
-                })
+                .map(|(alias, info)| info.status(alias, None))
                 .collect(),
         ))
     }
@@ -85,7 +86,11 @@ impl NodeManagerWorker {
     ) -> Result<ResponseBuilder<InletStatus<'a>>> {
         let manager = self.node_manager.clone();
 
-        let listen_addr = req.listen_addr().to_string();
+        let is_unix = req.unix_path().is_some();
+        let listen_addr = req
+            .unix_path()
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| req.listen_addr().to_string());
         let alias = req
             .alias()
             .map(|a| a.to_string())
@@ -93,8 +98,23 @@ impl NodeManagerWorker {
 
         info!("Handling request to create inlet portal");
 
+        if let Some(inlet) = self.node_manager.read().await.registry.inlets.get(&alias) {
+            if inlet.is_created() {
+                let message = format!("An inlet with alias {alias} already exists");
+                return Ok(
+                    Response::builder(rid, Status::Conflict).body(InletStatus::new(
+                        inlet.bind_addr.clone(),
+                        inlet.worker_addr.to_string(),
+                        alias,
+                        Some(message.into()),
+                        inlet.outlet_route.to_string(),
+                    )),
+                );
+            }
+        }
+
         debug! {
-            listen_addr = %req.listen_addr(),
+            %listen_addr,
             outlet_addr = %req.outlet_addr(),
             %alias,
             "Creating inlet portal"
@@ -176,24 +196,24 @@ impl NodeManagerWorker {
             .with_incoming_access_control(access_control.clone())
             .as_consumer(&flow_controls);
 
-        let res = node_manager
-            .tcp_transport
-            .create_inlet(listen_addr.clone(), outlet_route.clone(), options)
-            .await;
+        let res = start_inlet(
+            &node_manager.tcp_transport,
+            &listen_addr,
+            is_unix,
+            outlet_route.clone(),
+            options,
+        )
+        .await;
 
         Ok(match res {
-            Ok((socket_address, worker_addr)) => {
-                //when using 0 port, the chosen port will be populated
-                //in the returned socket address
-                let listen_addr = socket_address.to_string();
-
-                // TODO: Use better way to store inlets?
-                node_manager.registry.inlets.insert(
-                    alias.clone(),
-                    InletInfo::new(&listen_addr, Some(&worker_addr), &outlet_route),
-                );
+            Ok((listen_addr, worker_addr)) => {
+                let mut inlet_info =
+                    InletInfo::new(&listen_addr, Some(&worker_addr), &outlet_route);
                 if !connection_instance.normalized_addr.is_empty() {
                     let mut session = Session::new(connection_instance.transport_route.clone());
+                    if let Some(options) = req.session_options() {
+                        session.set_policy(RetryPolicy::from(options));
+                    }
 
                     let ctx = Arc::new(ctx.async_try_clone().await?);
                     let repl = replacer(
@@ -201,6 +221,7 @@ impl NodeManagerWorker {
                         connection_instance,
                         worker_addr.clone(),
                         listen_addr.clone(),
+                        is_unix,
                         req.outlet_addr().clone(),
                         req.prefix_route().clone(),
                         req.suffix_route().clone(),
@@ -209,8 +230,13 @@ impl NodeManagerWorker {
                         ctx,
                     );
                     session.set_replacer(repl);
-                    node_manager.sessions.lock().unwrap().add(session);
+                    inlet_info.session = Some(node_manager.sessions.lock().unwrap().add(session));
                 }
+                // TODO: Use better way to store inlets?
+                node_manager
+                    .registry
+                    .inlets
+                    .insert(alias.clone(), inlet_info);
 
                 Response::ok(rid).body(InletStatus::new(
                     listen_addr,
@@ -249,6 +275,9 @@ impl NodeManagerWorker {
         info!(%alias, "Handling request to delete inlet portal");
         if let Some(inlet_to_delete) = node_manager.registry.inlets.remove(alias) {
             debug!(%alias, "Sucessfully removed inlet from node registry");
+            if let Some(key) = &inlet_to_delete.session {
+                node_manager.sessions.lock().unwrap().remove(key);
+            }
             let was_stopped = node_manager
                 .tcp_transport
                 .stop_inlet(inlet_to_delete.worker_addr.clone())
@@ -314,6 +343,40 @@ impl NodeManagerWorker {
         }
     }
 
+    pub(super) async fn show_inlet_session(
+        &mut self,
+        req: &Request<'_>,
+        alias: &str,
+    ) -> Result<ResponseBuilder<Option<SessionStatus>>> {
+        let node_manager = self.node_manager.read().await;
+
+        debug!(%alias, "Handling request to show inlet session");
+        let key = node_manager
+            .registry
+            .inlets
+            .get(alias)
+            .and_then(|inlet| inlet.session);
+        let sessions = node_manager.sessions.lock().unwrap();
+        if let Some(session) = key.and_then(|k| sessions.session(&k)) {
+            let history = session
+                .history()
+                .rev()
+                .map(|e| SessionEventInfo::new(e.timestamp, e.kind.name(), e.kind.details()))
+                .collect();
+            Ok(Response::ok(req.id()).body(Some(SessionStatus::new(
+                session.key().to_string(),
+                session.status().to_string(),
+                session.ping_route().to_string(),
+                session.attempts(),
+                session.has_given_up(),
+                history,
+            ))))
+        } else {
+            debug!(%alias, "No session found for inlet");
+            Ok(Response::not_found(req.id()).body(None))
+        }
+    }
+
     pub(super) async fn create_outlet<'a>(
         &mut self,
         req: &Request<'_>,
@@ -324,9 +387,11 @@ impl NodeManagerWorker {
             tcp_addr,
             worker_addr,
             alias,
+            unix_path,
             ..
         } = dec.decode()?;
         let tcp_addr = tcp_addr.to_string();
+        let unix_path = unix_path.map(|p| p.to_string());
         let resource = alias
             .as_deref()
             .map(Resource::new)
@@ -334,6 +399,14 @@ impl NodeManagerWorker {
         let alias = alias.map(|a| a.0.into()).unwrap_or_else(random_alias);
 
         info!("Handling request to create outlet portal");
+
+        if let Some(outlet) = node_manager.registry.outlets.get(&alias) {
+            if outlet.is_created() {
+                let message = format!("An outlet with alias {alias} already exists");
+                return Ok(Response::builder(req.id(), Status::Conflict)
+                    .body(outlet.status(alias, Some(message.into()))));
+            }
+        }
         let worker_addr = Address::from(worker_addr.as_ref());
 
         let check_credential = node_manager.enable_credential_checks;
@@ -362,39 +435,43 @@ impl NodeManagerWorker {
             options
         };
 
-        let res = node_manager
-            .tcp_transport
-            .create_outlet(worker_addr.clone(), tcp_addr.clone(), options)
-            .await;
+        let res = if let Some(unix_path) = &unix_path {
+            create_unix_outlet(
+                &node_manager.tcp_transport,
+                worker_addr.clone(),
+                unix_path,
+                options,
+            )
+            .await
+        } else {
+            node_manager
+                .tcp_transport
+                .create_outlet(worker_addr.clone(), tcp_addr.clone(), options)
+                .await
+        };
 
         Ok(match res {
             Ok(_) => {
                 // TODO: Use better way to store outlets?
-                node_manager.registry.outlets.insert(
-                    alias.clone(),
-                    OutletInfo::new(&tcp_addr, Some(&worker_addr)),
-                );
+                let info = OutletInfo::new(&tcp_addr, Some(&worker_addr)).with_unix_path(unix_path);
+                let status = info.status(alias.clone(), None);
+                node_manager.registry.outlets.insert(alias, info);
 
-                Response::ok(req.id()).body(OutletStatus::new(
-                    tcp_addr,
-                    worker_addr.to_string(),
-                    alias,
-                    None,
-                ))
+                Response::ok(req.id()).body(status)
             }
             Err(e) => {
                 // TODO: Use better way to store outlets?
-                node_manager
-                    .registry
-                    .outlets
-                    .insert(alias.clone(), OutletInfo::new(&tcp_addr, None));
-
-                Response::bad_request(req.id()).body(OutletStatus::new(
-                    tcp_addr,
+                let status = OutletStatus::new(
+                    tcp_addr.clone(),
                     worker_addr.to_string(),
-                    alias,
+                    alias.clone(),
                     Some(e.to_string().into()),
-                ))
+                )
+                .with_unix_path(unix_path.clone().map(CowStr::from));
+                let info = OutletInfo::new(&tcp_addr, None).with_unix_path(unix_path);
+                node_manager.registry.outlets.insert(alias, info);
+
+                Response::bad_request(req.id()).body(status)
             }
         })
     }
@@ -416,20 +493,15 @@ impl NodeManagerWorker {
                 .is_ok();
             if was_stopped {
                 debug!(%alias, "Successfully stopped outlet");
-                Ok(Response::ok(req.id()).body(OutletStatus::new(
-                    outlet_to_delete.tcp_addr,
-                    outlet_to_delete.worker_addr.to_string(),
-                    alias,
-                    None,
-                )))
+                Ok(Response::ok(req.id()).body(outlet_to_delete.status(alias, None)))
             } else {
                 error!(%alias, "Failed to remove outlet from node registry");
-                Ok(Response::internal_error(req.id()).body(OutletStatus::new(
-                    outlet_to_delete.tcp_addr,
-                    outlet_to_delete.worker_addr.to_string(),
-                    alias,
-                    Some(format!("Failed to remove outlet with alias {alias}").into()),
-                )))
+                Ok(
+                    Response::internal_error(req.id()).body(outlet_to_delete.status(
+                        alias,
+                        Some(format!("Failed to remove outlet with alias {alias}").into()),
+                    )),
+                )
             }
         } else {
             error!(%alias, "Outlet not found in the node registry");
@@ -452,12 +524,7 @@ impl NodeManagerWorker {
         info!(%alias, "Handling request to show outlet portal");
         if let Some(outlet_to_show) = node_manager.registry.outlets.get(alias) {
             debug!(%alias, "Outlet not found in node registry");
-            Ok(Response::ok(req.id()).body(OutletStatus::new(
-                outlet_to_show.tcp_addr.to_string(),
-                outlet_to_show.worker_addr.to_string(),
-                alias,
-                None,
-            )))
+            Ok(Response::ok(req.id()).body(outlet_to_show.status(alias, None)))
         } else {
             error!(%alias, "Outlet not found in the node registry");
             Ok(Response::not_found(req.id()).body(OutletStatus::new(
@@ -481,6 +548,7 @@ fn replacer(
     connection_instance: ConnectionInstance,
     inlet_address: Address,
     bind: String,
+    is_unix: bool,
     addr: MultiAddr,
     prefix_route: Route,
     suffix_route: Route,
@@ -524,7 +592,8 @@ fn replacer(
                     }
                 }
 
-                // The previous inlet worker needs to be stopped:
+                // The previous inlet worker needs to be stopped, which also
+                // releases its Unix domain socket path, if any:
                 if let Err(error) = node_manager
                     .tcp_transport
                     .stop_inlet(inlet_address.clone())
@@ -564,11 +633,15 @@ fn replacer(
                     .as_consumer(&flow_controls);
 
                 // Finally attempt to create a new inlet using the new route:
-                let new_inlet_address = node_manager
-                    .tcp_transport
-                    .create_inlet(bind, normalized_route, options)
-                    .await?
-                    .1;
+                let new_inlet_address = start_inlet(
+                    &node_manager.tcp_transport,
+                    &bind,
+                    is_unix,
+                    normalized_route,
+                    options,
+                )
+                .await?
+                .1;
                 *inlet_address_arc.lock().unwrap() = new_inlet_address;
 
                 Ok(new_connection_instance.transport_route.clone())
@@ -589,3 +662,112 @@ fn replacer(
         })
     })
 }
+
+/// Start an inlet listening either at a TCP socket address or at a Unix domain socket path.
+///
+/// Returns the address the inlet is actually bound to, which is only different from `bind`
+/// when listening on TCP port 0, and the address of the inlet worker.
+async fn start_inlet(
+    tcp: &TcpTransport,
+    bind: &str,
+    is_unix: bool,
+    outlet_route: Route,
+    options: TcpInletOptions,
+) -> Result<(String, Address)> {
+    if is_unix {
+        #[cfg(unix)]
+        return tcp
+            .create_unix_inlet(bind, outlet_route, options)
+            .await
+            .map(|worker_addr| (bind.to_string(), worker_addr));
+        #[cfg(not(unix))]
+        return Err(ApiError::generic(
+            "unix domain sockets are not supported on this platform",
+        ));
+    }
+    tcp.create_inlet(bind, outlet_route, options)
+        .await
+        .map(|(socket_addr, worker_addr)| (socket_addr.to_string(), worker_addr))
+}
+
+/// Start an outlet connecting to a Unix domain socket path
+#[cfg_attr(not(unix), allow(unused_variables))]
+async fn create_unix_outlet(
+    tcp: &TcpTransport,
+    worker_addr: Address,
+    path: &str,
+    options: TcpOutletOptions,
+) -> Result<()> {
+    #[cfg(unix)]
+    return tcp.create_unix_outlet(worker_addr, path, options).await;
+    #[cfg(not(unix))]
+    return Err(ApiError::generic(
+        "unix domain sockets are not supported on this platform",
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nodes::models::session::SessionStatus;
+    use crate::nodes::registry::InletInfo;
+    use crate::nodes::NODEMANAGER_ADDR;
+    use crate::session::sessions::Session;
+    use crate::session::SessionEventKind;
+    use crate::util::test::start_manager_for_tests;
+    use minicbor::Decoder;
+    use ockam_core::api::{Request, Response, Status};
+    use ockam_core::{route, Result};
+    use ockam_node::Context;
+
+    async fn show_inlet_session(
+        ctx: &mut Context,
+        alias: &str,
+    ) -> Result<(Option<Status>, Option<SessionStatus>)> {
+        let mut buf = vec![];
+        Request::get(format!("/node/inlet/{alias}/session")).encode(&mut buf)?;
+        let response: Vec<u8> = ctx.send_and_receive(route![NODEMANAGER_ADDR], buf).await?;
+        let mut dec = Decoder::new(&response);
+        let header = dec.decode::<Response>()?;
+        if header.status() != Some(Status::Ok) {
+            return Ok((header.status(), None));
+        }
+        Ok((header.status(), dec.decode::<Option<SessionStatus>>()?))
+    }
+
+    #[ockam_macros::test]
+    async fn show_inlet_session_returns_the_most_recent_events_first(
+        ctx: &mut Context,
+    ) -> Result<()> {
+        let handle = start_manager_for_tests(ctx).await?;
+
+        let (status, _) = show_inlet_session(ctx, "unknown").await?;
+        assert_eq!(status, Some(Status::NotFound));
+
+        {
+            let mut node_manager = handle.node_manager.write().await;
+            let mut session = Session::new(route!["outlet"]);
+            session.record(SessionEventKind::Unresponsive);
+            session.record(SessionEventKind::Replaced(route!["new_outlet"]));
+            let key = node_manager.sessions.lock().unwrap().add(session);
+
+            let mut inlet = InletInfo::new("127.0.0.1:0", None, &route!["outlet"]);
+            inlet.session = Some(key);
+            node_manager
+                .registry
+                .inlets
+                .insert("inlet".to_string(), inlet);
+        }
+
+        let (status, session) = show_inlet_session(ctx, "inlet").await?;
+        assert_eq!(status, Some(Status::Ok));
+        let session = session.expect("the inlet has a session");
+        assert_eq!(session.status, "up");
+        assert_eq!(session.failed_attempts, 0);
+        assert!(!session.given_up);
+        let kinds: Vec<&str> = session.history.iter().map(|e| e.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["replaced", "unresponsive"]);
+        assert_eq!(session.history[0].details.as_deref(), Some("0#new_outlet"));
+
+        ctx.stop().await
+    }
+}
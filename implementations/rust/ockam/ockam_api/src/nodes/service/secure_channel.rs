@@ -173,10 +173,11 @@ impl NodeManager {
                 };
 
                 self.credentials_service()
-                    .present_credential_mutual(
+                    .present_credential_mutual_with_trust_context(
                         ctx,
                         route![sc_addr.clone(), DefaultAddress::CREDENTIALS_SERVICE],
-                        self.trust_context()?.authorities().await?.as_slice(),
+                        self.trust_context()?,
+                        &identifier,
                         credential,
                         MessageSendReceiveOptions::new().with_flow_control(&self.flow_controls),
                     )
@@ -247,6 +248,18 @@ impl NodeManager {
             FlowControlPolicy::SpawnerAllowMultipleMessages,
         );
 
+        self.flow_controls.add_consumer(
+            &DefaultAddress::STREAM_SERVICE.into(),
+            &flow_control_id,
+            FlowControlPolicy::SpawnerAllowMultipleMessages,
+        );
+
+        self.flow_controls.add_consumer(
+            &DefaultAddress::STREAM_INDEX_SERVICE.into(),
+            &flow_control_id,
+            FlowControlPolicy::SpawnerAllowMultipleMessages,
+        );
+
         self.flow_controls.add_consumer(
             &KAFKA_SECURE_CHANNEL_CONTROLLER_ADDRESS.into(),
             &flow_control_id,
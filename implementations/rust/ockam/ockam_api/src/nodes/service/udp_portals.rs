@@ -0,0 +1,296 @@
+use crate::error::ApiError;
+use crate::local_multiaddr_to_route;
+use crate::nodes::connection::Connection;
+use crate::nodes::models::portal::{CreateInlet, CreateOutlet, InletStatus, OutletStatus};
+use crate::nodes::registry::{InletInfo, OutletInfo};
+use crate::nodes::service::random_alias;
+use crate::{actions, resources, DefaultAddress};
+use minicbor::Decoder;
+use ockam::{Address, Result};
+use ockam_abac::Resource;
+use ockam_core::api::{Request, Response, ResponseBuilder};
+use ockam_core::flow_control::FlowControlPolicy;
+use ockam_core::route;
+use ockam_multiaddr::proto::Project;
+use ockam_node::Context;
+use ockam_transport_udp::{UdpInletOptions, UdpOutletOptions};
+use std::time::Duration;
+
+use super::{NodeManager, NodeManagerWorker};
+
+impl NodeManagerWorker {
+    /// Create a UDP inlet. Unlike TCP inlets, UDP inlets are not recreated
+    /// when the connection to their outlet is lost
+    pub(super) async fn create_udp_inlet<'a>(
+        &mut self,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+        ctx: &Context,
+    ) -> Result<ResponseBuilder<InletStatus<'a>>> {
+        let rid = req.id();
+        let req: CreateInlet = dec.decode()?;
+        let manager = self.node_manager.clone();
+
+        let listen_addr = req.listen_addr().to_string();
+        let alias = req
+            .alias()
+            .map(|a| a.to_string())
+            .unwrap_or_else(random_alias);
+
+        info!("Handling request to create udp inlet portal");
+
+        let flow_controls = self.node_manager.read().await.flow_controls.clone();
+
+        let connection_instance = {
+            let duration = req
+                .wait_for_outlet_duration()
+                .unwrap_or(Duration::from_secs(5));
+
+            let connection = Connection::new(ctx, req.outlet_addr(), &flow_controls)
+                .with_authorized_identity(req.authorized())
+                .with_timeout(duration);
+
+            NodeManager::connect(manager, connection).await?
+        };
+
+        let outlet_route = match local_multiaddr_to_route(&connection_instance.normalized_addr) {
+            Some(route) => route,
+            None => {
+                return Ok(Response::bad_request(rid)
+                    .body(InletStatus::bad_request("invalid outlet route")))
+            }
+        };
+
+        for address in req.prefix_route().iter() {
+            connection_instance.add_consumer(address);
+        }
+
+        let outlet_route = route![
+            req.prefix_route().clone(),
+            outlet_route,
+            req.suffix_route().clone()
+        ];
+
+        let resource = req
+            .alias()
+            .map(Resource::new)
+            .unwrap_or(resources::UDP_INLET);
+
+        let mut node_manager = self.node_manager.write().await;
+        let project_id = if node_manager.enable_credential_checks {
+            let pid = req
+                .outlet_addr()
+                .first()
+                .and_then(|p| {
+                    if let Some(p) = p.cast::<Project>() {
+                        node_manager.projects.get(&*p).map(|info| &*info.id)
+                    } else {
+                        None
+                    }
+                })
+                .or_else(|| Some(node_manager.trust_context().ok()?.id()));
+            if pid.is_none() {
+                return Err(ApiError::generic("credential check requires project"));
+            }
+            pid
+        } else {
+            None
+        };
+
+        let access_control = node_manager
+            .access_control(&resource, &actions::HANDLE_MESSAGE, project_id, None)
+            .await?;
+
+        let options = UdpInletOptions::new()
+            .with_incoming_access_control(access_control)
+            .as_consumer(&flow_controls);
+
+        let res = node_manager
+            .udp_transport
+            .create_inlet(&listen_addr, outlet_route.clone(), options)
+            .await;
+
+        Ok(match res {
+            Ok((socket_address, worker_addr)) => {
+                let listen_addr = socket_address.to_string();
+                node_manager.registry.udp_inlets.insert(
+                    alias.clone(),
+                    InletInfo::new(&listen_addr, Some(&worker_addr), &outlet_route),
+                );
+
+                Response::ok(rid).body(InletStatus::new(
+                    listen_addr,
+                    worker_addr.to_string(),
+                    alias,
+                    None,
+                    outlet_route.to_string(),
+                ))
+            }
+            Err(e) => {
+                warn!(to = %req.outlet_addr(), err = %e, "failed to create udp inlet");
+                Response::bad_request(rid).body(InletStatus::new(
+                    listen_addr,
+                    "",
+                    alias,
+                    Some(e.to_string().into()),
+                    outlet_route.to_string(),
+                ))
+            }
+        })
+    }
+
+    pub(super) async fn delete_udp_inlet<'a>(
+        &mut self,
+        req: &Request<'_>,
+        alias: &'a str,
+    ) -> Result<ResponseBuilder<InletStatus<'a>>> {
+        let mut node_manager = self.node_manager.write().await;
+
+        info!(%alias, "Handling request to delete udp inlet portal");
+        match node_manager.registry.udp_inlets.remove(alias) {
+            Some(inlet) => {
+                let payload = match node_manager
+                    .udp_transport
+                    .stop_inlet(inlet.worker_addr.clone())
+                    .await
+                {
+                    Ok(()) => None,
+                    Err(e) => Some(e.to_string().into()),
+                };
+                let response = if payload.is_none() {
+                    Response::ok(req.id())
+                } else {
+                    Response::internal_error(req.id())
+                };
+                Ok(response.body(InletStatus::new(
+                    inlet.bind_addr,
+                    inlet.worker_addr.to_string(),
+                    alias,
+                    payload,
+                    inlet.outlet_route.to_string(),
+                )))
+            }
+            None => Ok(Response::not_found(req.id()).body(InletStatus::new(
+                "",
+                "",
+                alias,
+                Some(format!("UDP inlet with alias {alias} not found").into()),
+                "",
+            ))),
+        }
+    }
+
+    pub(super) async fn create_udp_outlet<'a>(
+        &mut self,
+        req: &Request<'_>,
+        dec: &mut Decoder<'_>,
+    ) -> Result<ResponseBuilder<OutletStatus<'a>>> {
+        let mut node_manager = self.node_manager.write().await;
+        let CreateOutlet {
+            tcp_addr: udp_addr,
+            worker_addr,
+            alias,
+            ..
+        } = dec.decode()?;
+        let udp_addr = udp_addr.to_string();
+        let resource = alias
+            .as_deref()
+            .map(Resource::new)
+            .unwrap_or(resources::UDP_OUTLET);
+        let alias = alias.map(|a| a.0.into()).unwrap_or_else(random_alias);
+
+        info!("Handling request to create udp outlet portal");
+        let worker_addr = Address::from(worker_addr.as_ref());
+
+        let trust_context_id = if node_manager.enable_credential_checks {
+            Some(node_manager.trust_context()?.id())
+        } else {
+            None
+        };
+
+        let access_control = node_manager
+            .access_control(&resource, &actions::HANDLE_MESSAGE, trust_context_id, None)
+            .await?;
+        let options = UdpOutletOptions::new().with_incoming_access_control(access_control);
+
+        // Accept messages from the default secure channel listener
+        let options = if let Some(flow_control_id) = node_manager
+            .flow_controls
+            .get_flow_control_with_spawner(&DefaultAddress::SECURE_CHANNEL_LISTENER.into())
+        {
+            options.as_consumer(
+                &node_manager.flow_controls,
+                &flow_control_id,
+                FlowControlPolicy::SpawnerAllowMultipleMessages,
+            )
+        } else {
+            options
+        };
+
+        let res = node_manager
+            .udp_transport
+            .create_outlet(worker_addr.clone(), udp_addr.clone(), options)
+            .await;
+
+        Ok(match res {
+            Ok(()) => {
+                node_manager.registry.udp_outlets.insert(
+                    alias.clone(),
+                    OutletInfo::new(&udp_addr, Some(&worker_addr)),
+                );
+
+                Response::ok(req.id()).body(OutletStatus::new(
+                    udp_addr,
+                    worker_addr.to_string(),
+                    alias,
+                    None,
+                ))
+            }
+            Err(e) => Response::bad_request(req.id()).body(OutletStatus::new(
+                udp_addr,
+                worker_addr.to_string(),
+                alias,
+                Some(e.to_string().into()),
+            )),
+        })
+    }
+
+    pub(super) async fn delete_udp_outlet<'a>(
+        &mut self,
+        req: &Request<'_>,
+        alias: &'a str,
+    ) -> Result<ResponseBuilder<OutletStatus<'a>>> {
+        let mut node_manager = self.node_manager.write().await;
+
+        info!(%alias, "Handling request to delete udp outlet portal");
+        match node_manager.registry.udp_outlets.remove(alias) {
+            Some(outlet) => {
+                let payload = match node_manager
+                    .udp_transport
+                    .stop_outlet(outlet.worker_addr.clone())
+                    .await
+                {
+                    Ok(()) => None,
+                    Err(e) => Some(e.to_string().into()),
+                };
+                let response = if payload.is_none() {
+                    Response::ok(req.id())
+                } else {
+                    Response::internal_error(req.id())
+                };
+                Ok(response.body(OutletStatus::new(
+                    outlet.tcp_addr,
+                    outlet.worker_addr.to_string(),
+                    alias,
+                    payload,
+                )))
+            }
+            None => Ok(Response::not_found(req.id()).body(OutletStatus::new(
+                "",
+                "",
+                alias,
+                Some(format!("UDP outlet with alias {alias} not found").into()),
+            ))),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub(crate) mod sessions;
 
-use crate::session::sessions::{Key, Ping, Sessions, Status};
+pub use sessions::{Key, SessionEvent, SessionEventKind};
+
+use crate::session::sessions::{Ping, Session, Sessions, Status};
 use crate::DefaultAddress;
 use minicbor::{Decode, Encode};
 use ockam::{LocalMessage, Route, TransportMessage, Worker};
@@ -9,25 +11,35 @@ use ockam_core::flow_control::{FlowControlPolicy, FlowControls};
 use ockam_core::{
     route, Address, AllowAll, Decodable, DenyAll, Encodable, Error, Result, Routed, LOCAL,
 };
+use ockam_node::metrics::Metrics;
 use ockam_node::tokio;
-use ockam_node::tokio::sync::mpsc;
+use ockam_node::tokio::sync::{broadcast, mpsc};
 use ockam_node::tokio::task::JoinSet;
 use ockam_node::tokio::time::{sleep, timeout, Duration};
-use ockam_node::Context;
+use ockam_node::{Context, MessagePriority, WorkerBuilder};
+use std::time::Instant;
 use tracing as log;
 
-const MAX_FAILURES: usize = 3;
-const RETRY_DELAY: Duration = Duration::from_secs(5);
+/// Maximum delay between two checks of the sessions
 const DELAY: Duration = Duration::from_secs(3);
 
+/// Number of session events buffered for slow subscribers
+const EVENTS_CAPACITY: usize = 64;
+
+/// Number of pongs which can wait in the priority lane of the collector
+const PONGS_CAPACITY: usize = 32;
+
+/// Number of session events, by session and kind of event
+pub const SESSION_EVENTS: &str = "ockam_api_session_events_total";
+
 #[derive(Debug)]
 pub struct Medic {
-    retry_delay: Duration,
     delay: Duration,
     sessions: Arc<Mutex<Sessions>>,
     pings: JoinSet<(Key, Result<(), Error>)>,
     replacements: JoinSet<(Key, Result<Route, Error>)>,
     flow_controls: FlowControls,
+    events: broadcast::Sender<SessionEvent>,
 }
 
 #[derive(Debug, Copy, Clone, Encode, Decode)]
@@ -39,13 +51,14 @@ pub struct Message {
 
 impl Medic {
     pub fn new(flow_controls: FlowControls) -> Self {
+        let (events, _) = broadcast::channel(EVENTS_CAPACITY);
         Self {
-            retry_delay: RETRY_DELAY,
             delay: DELAY,
             sessions: Arc::new(Mutex::new(Sessions::new())),
             pings: JoinSet::new(),
             replacements: JoinSet::new(),
             flow_controls,
+            events,
         }
     }
 
@@ -53,13 +66,28 @@ impl Medic {
         self.sessions.clone()
     }
 
+    /// Sender of the events recorded when sessions become unresponsive and
+    /// are replaced, used to subscribe to them
+    pub fn events(&self) -> broadcast::Sender<SessionEvent> {
+        self.events.clone()
+    }
+
     pub async fn start(self, ctx: Context) -> Result<(), Error> {
         let ctx = ctx
             .new_detached(Address::random_tagged("Medic.ctx"), DenyAll, AllowAll)
             .await?;
         let (tx, rx) = mpsc::channel(32);
-        ctx.start_worker(Collector::address(), Collector(tx), AllowAll, DenyAll)
-            .await?;
+        // Pongs are received before the other messages of the collector, so
+        // that a busy node does not mark its sessions as unresponsive
+        WorkerBuilder::with_access_control(
+            Arc::new(AllowAll),
+            Arc::new(DenyAll),
+            Collector::address(),
+            Collector(tx),
+        )
+        .with_priority_lane(PONGS_CAPACITY)
+        .start(&ctx)
+        .await?;
         self.go(ctx, rx).await
     }
 
@@ -71,12 +99,25 @@ impl Medic {
         let ctx = Arc::new(ctx);
         loop {
             log::trace!("check sessions");
+            let delay;
             {
+                let now = Instant::now();
                 let mut sessions = self.sessions.lock().unwrap();
+                delay = sessions
+                    .iter()
+                    .map(|(_, s)| s.policy().ping_interval())
+                    .fold(self.delay, Duration::min);
                 for (&key, session) in sessions.iter_mut() {
-                    if session.pings().len() < MAX_FAILURES {
+                    if session.has_given_up() {
+                        continue;
+                    }
+                    if session.pings().len() < session.policy().max_missed_pings() {
+                        if !session.ping_due(now) {
+                            continue;
+                        }
                         let m = Message::new(session.key());
                         session.add_ping(m.ping);
+                        session.set_last_ping(now);
                         let l = {
                             let v = Encodable::encode(&m).expect("message can be encoded");
                             let echo_route =
@@ -110,7 +151,7 @@ impl Medic {
                                 );
                             }
                             let t = TransportMessage::v1(echo_route, Collector::address(), v);
-                            LocalMessage::new(t, Vec::new())
+                            LocalMessage::new(t, vec![MessagePriority::high()])
                         };
                         let sender = ctx.clone();
                         self.pings
@@ -118,12 +159,16 @@ impl Medic {
                     } else {
                         match session.status() {
                             Status::Up | Status::Down => {
-                                log::warn!(%key, "session unresponsive");
+                                if session.status() == Status::Up {
+                                    log::warn!(%key, "session unresponsive");
+                                    notify(&self.events, session, SessionEventKind::Unresponsive);
+                                }
                                 let f = session.replacement(session.ping_route().clone());
+                                let backoff = session.policy().backoff(session.attempts());
                                 session.set_status(Status::Degraded);
-                                log::info!(%key, "replacing session");
+                                log::info!(%key, attempt = session.attempts() + 1, "replacing session");
                                 self.replacements.spawn(async move {
-                                    sleep(self.retry_delay).await;
+                                    sleep(backoff).await;
                                     (key, f.await)
                                 });
                             }
@@ -135,7 +180,7 @@ impl Medic {
                 }
             }
 
-            let _ = timeout(self.delay, self.get_results(&mut rx)).await;
+            let _ = timeout(delay, self.get_results(&mut rx)).await;
         }
     }
 
@@ -155,7 +200,13 @@ impl Medic {
                         log::warn!(key = %k, err = %e, "replacing session failed");
                         let mut sessions = self.sessions.lock().unwrap();
                         if let Some(s) = sessions.session_mut(&k) {
-                           s.set_status(Status::Down);
+                            s.set_status(Status::Down);
+                            s.add_failed_attempt();
+                            notify(&self.events, s, SessionEventKind::ReplacementFailed(e.to_string()));
+                            if s.has_given_up() {
+                                log::error!(key = %k, attempts = s.attempts(), "giving up on session");
+                                notify(&self.events, s, SessionEventKind::GaveUp);
+                            }
                         }
                     }
                     Some(Ok((k, Ok(ping_route)))) => {
@@ -163,8 +214,10 @@ impl Medic {
                         if let Some(s) = sessions.session_mut(&k) {
                             log::info!(key = %k, ping_route = %ping_route, "replacement is up");
                             s.set_status(Status::Up);
-                            s.set_ping_address(ping_route);
+                            s.set_ping_address(ping_route.clone());
                             s.clear_pings();
+                            s.reset_attempts();
+                            notify(&self.events, s, SessionEventKind::Replaced(ping_route));
                         }
                     }
                 },
@@ -182,6 +235,39 @@ impl Medic {
     }
 }
 
+/// Count the session events in the metrics of the node, until the sender is dropped
+pub(crate) async fn record_session_events(
+    mut events: broadcast::Receiver<SessionEvent>,
+    metrics: Metrics,
+) {
+    loop {
+        match events.recv().await {
+            Ok(event) => {
+                log::debug!(key = %event.key, kind = event.kind.name(), "session event");
+                metrics.increment_counter(
+                    SESSION_EVENTS,
+                    &[
+                        ("key", event.key.to_string()),
+                        ("kind", event.kind.name().to_string()),
+                    ],
+                    1,
+                )
+            }
+            Err(broadcast::error::RecvError::Lagged(n)) => {
+                log::warn!("{n} session events were not recorded")
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
+/// Record an event in the history of a session and send it to the subscribers
+fn notify(events: &broadcast::Sender<SessionEvent>, session: &mut Session, kind: SessionEventKind) {
+    let event = session.record(kind);
+    // Sending only fails when nobody is subscribed
+    let _ = events.send(event);
+}
+
 impl Message {
     fn new(k: Key) -> Self {
         Self {
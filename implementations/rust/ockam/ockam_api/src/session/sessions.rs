@@ -1,7 +1,8 @@
 use core::fmt;
 use core::future::Future;
 use core::pin::Pin;
-use std::time::Duration;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use minicbor::bytes::ByteArray;
 use minicbor::{Decode, Encode};
@@ -11,6 +12,8 @@ use ockam_core::compat::collections::HashMap;
 use ockam_core::compat::rand;
 use ockam_core::{Error, Route};
 
+use crate::nodes::models::session::SessionRetryOptions;
+
 //most sessions replacer are dependent on the node manager, if many session
 //fails concurrently, which is the common scenario we need extra time
 //to account for the lock contention
@@ -20,6 +23,15 @@ pub const MAX_CONNECT_TIME: Duration = Duration::from_secs(5);
 pub type Replacement = Pin<Box<dyn Future<Output = Result<Route, Error>> + Send>>;
 pub type Replacer = Box<dyn FnMut(Route) -> Replacement + Send>;
 
+/// Number of events kept in the history of a session
+const MAX_HISTORY: usize = 32;
+
+/// Maximum fraction of a backoff delay which is randomly removed
+const JITTER: f64 = 0.2;
+
+/// Shortest interval between two pings, sessions are checked at least that often
+const MIN_PING_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Debug)]
 pub struct Sessions {
     map: HashMap<Key, Session>,
@@ -31,6 +43,10 @@ pub struct Session {
     status: Status,
     replace: Replacer,
     pings: Vec<Ping>,
+    policy: RetryPolicy,
+    last_ping: Option<Instant>,
+    attempts: u32,
+    history: VecDeque<SessionEvent>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -40,6 +56,121 @@ pub enum Status {
     Up,
 }
 
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Down => f.write_str("down"),
+            Status::Degraded => f.write_str("degraded"),
+            Status::Up => f.write_str("up"),
+        }
+    }
+}
+
+/// How a session is monitored, and how it is replaced when it is unresponsive
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    ping_interval: Duration,
+    max_missed_pings: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    max_attempts: Option<u32>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            ping_interval: Duration::from_secs(3),
+            max_missed_pings: 3,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(60),
+            max_attempts: None,
+        }
+    }
+}
+
+impl From<&SessionRetryOptions> for RetryPolicy {
+    fn from(options: &SessionRetryOptions) -> Self {
+        let default = Self::default();
+        Self {
+            ping_interval: options
+                .ping_interval
+                .map(|d| d.max(MIN_PING_INTERVAL))
+                .unwrap_or(default.ping_interval),
+            max_missed_pings: options
+                .max_missed_pings
+                .map(|n| n.max(1) as usize)
+                .unwrap_or(default.max_missed_pings),
+            initial_backoff: options.initial_backoff.unwrap_or(default.initial_backoff),
+            max_backoff: options.max_backoff.unwrap_or(default.max_backoff),
+            max_attempts: options.max_attempts.or(default.max_attempts),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn ping_interval(&self) -> Duration {
+        self.ping_interval
+    }
+
+    pub fn max_missed_pings(&self) -> usize {
+        self.max_missed_pings
+    }
+
+    /// Delay before a replacement attempt, given the number of attempts which
+    /// already failed
+    ///
+    /// The delay doubles after each failure, up to the maximum backoff, and is
+    /// randomized so that sessions failing together are not replaced in lockstep.
+    pub fn backoff(&self, failed_attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failed_attempts);
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        delay.saturating_sub(delay.mul_f64(JITTER * rand::random::<f64>()))
+    }
+}
+
+/// Something which happened to a session
+#[derive(Debug, Clone)]
+pub struct SessionEvent {
+    pub key: Key,
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub kind: SessionEventKind,
+}
+
+#[derive(Debug, Clone)]
+pub enum SessionEventKind {
+    /// Too many pings were left unanswered
+    Unresponsive,
+    /// A replacement attempt failed
+    ReplacementFailed(String),
+    /// The session was replaced, pings now go through the given route
+    Replaced(Route),
+    /// The maximum number of replacement attempts was reached
+    GaveUp,
+}
+
+impl SessionEventKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SessionEventKind::Unresponsive => "unresponsive",
+            SessionEventKind::ReplacementFailed(_) => "replacement_failed",
+            SessionEventKind::Replaced(_) => "replaced",
+            SessionEventKind::GaveUp => "gave_up",
+        }
+    }
+
+    pub fn details(&self) -> Option<String> {
+        match self {
+            SessionEventKind::ReplacementFailed(e) => Some(e.clone()),
+            SessionEventKind::Replaced(r) => Some(r.to_string()),
+            SessionEventKind::Unresponsive | SessionEventKind::GaveUp => None,
+        }
+    }
+}
+
 impl fmt::Debug for Session {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Session")
@@ -47,6 +178,8 @@ impl fmt::Debug for Session {
             .field("ping_route", &self.ping_route)
             .field("status", &self.status)
             .field("pings", &self.pings)
+            .field("policy", &self.policy)
+            .field("attempts", &self.attempts)
             .finish()
     }
 }
@@ -70,7 +203,6 @@ impl Sessions {
         k
     }
 
-    #[allow(unused)]
     pub fn session(&self, k: &Key) -> Option<&Session> {
         self.map.get(k)
     }
@@ -79,7 +211,10 @@ impl Sessions {
         self.map.get_mut(k)
     }
 
-    #[allow(unused)]
+    pub fn remove(&mut self, k: &Key) -> Option<Session> {
+        self.map.remove(k)
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = (&Key, &Session)> + '_ {
         self.map.iter()
     }
@@ -97,6 +232,10 @@ impl Session {
             status: Status::Up,
             replace: Box::new(move |r| Box::pin(async move { Ok(r) })),
             pings: Vec::new(),
+            policy: RetryPolicy::default(),
+            last_ping: None,
+            attempts: 0,
+            history: VecDeque::new(),
         }
     }
 
@@ -139,6 +278,71 @@ impl Session {
     pub fn clear_pings(&mut self) {
         self.pings.clear()
     }
+
+    pub fn policy(&self) -> &RetryPolicy {
+        &self.policy
+    }
+
+    pub fn set_policy(&mut self, policy: RetryPolicy) {
+        self.policy = policy
+    }
+
+    /// Return true if a ping is due according to the ping interval
+    pub fn ping_due(&self, now: Instant) -> bool {
+        self.last_ping
+            .map(|t| now.duration_since(t) >= self.policy.ping_interval)
+            .unwrap_or(true)
+    }
+
+    pub fn set_last_ping(&mut self, now: Instant) {
+        self.last_ping = Some(now)
+    }
+
+    /// Number of replacement attempts which failed since the session was last up
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn add_failed_attempt(&mut self) {
+        self.attempts = self.attempts.saturating_add(1)
+    }
+
+    pub fn reset_attempts(&mut self) {
+        self.attempts = 0
+    }
+
+    /// Return true if the session is down and must not be replaced anymore
+    pub fn has_given_up(&self) -> bool {
+        self.status == Status::Down
+            && self
+                .policy
+                .max_attempts
+                .map(|max| self.attempts >= max)
+                .unwrap_or(false)
+    }
+
+    /// Add an event to the history of the session, dropping the oldest ones
+    pub fn record(&mut self, kind: SessionEventKind) -> SessionEvent {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let event = SessionEvent {
+            key: self.key,
+            timestamp,
+            kind,
+        };
+        if self.history.len() == MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(event.clone());
+        event
+    }
+
+    /// Recorded events, oldest first
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &SessionEvent> + '_ {
+        self.history.iter()
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Encode, Decode)]
@@ -172,3 +376,59 @@ impl fmt::Display for Ping {
         write!(f, "{:x}", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam_core::route;
+
+    #[test]
+    fn backoff_is_exponential_and_bounded() {
+        let options = SessionRetryOptions::new()
+            .with_initial_backoff(Duration::from_secs(10))
+            .with_max_backoff(Duration::from_secs(60));
+        let policy = RetryPolicy::from(&options);
+
+        for (attempts, expected) in [(0, 10), (1, 20), (2, 40), (3, 60), (30, 60)] {
+            let expected = Duration::from_secs(expected);
+            let backoff = policy.backoff(attempts);
+            assert!(backoff <= expected, "{backoff:?} > {expected:?}");
+            assert!(backoff >= expected.mul_f64(1.0 - JITTER));
+        }
+    }
+
+    #[test]
+    fn ping_interval_is_bounded() {
+        let options = SessionRetryOptions::new().with_ping_interval(Duration::ZERO);
+        let policy = RetryPolicy::from(&options);
+        assert_eq!(policy.ping_interval(), MIN_PING_INTERVAL);
+    }
+
+    #[test]
+    fn session_gives_up_after_max_attempts() {
+        let mut session = Session::new(route![]);
+        session.set_policy(RetryPolicy::from(
+            &SessionRetryOptions::new().with_max_attempts(2),
+        ));
+        session.set_status(Status::Down);
+        session.add_failed_attempt();
+        assert!(!session.has_given_up());
+        session.add_failed_attempt();
+        assert!(session.has_given_up());
+
+        session.set_status(Status::Up);
+        session.reset_attempts();
+        assert!(!session.has_given_up());
+    }
+
+    #[test]
+    fn session_history_is_bounded() {
+        let mut session = Session::new(route![]);
+        for _ in 0..MAX_HISTORY + 5 {
+            session.record(SessionEventKind::Unresponsive);
+        }
+        session.record(SessionEventKind::GaveUp);
+        assert_eq!(session.history().count(), MAX_HISTORY);
+        assert_eq!(session.history().last().unwrap().kind.name(), "gave_up");
+    }
+}
@@ -1,18 +1,31 @@
-use std::net::{SocketAddrV4, SocketAddrV6};
+use std::iter::Peekable;
+use std::net::SocketAddr;
 
 use anyhow::anyhow;
 
 use ockam::TcpTransport;
 use ockam_core::flow_control::{FlowControlId, FlowControls};
-use ockam_core::{Address, Error, Result, Route, TransportType, LOCAL};
+use ockam_core::{route, Address, Error, Result, Route, LOCAL};
 use ockam_multiaddr::proto::{
-    DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp, Worker,
+    DnsAddr, Ip4, Ip6, Node, Project, Relay, Secure, Service, Space, Tcp, Udp, Unix, Worker, Ws,
 };
-use ockam_multiaddr::{Code, MultiAddr, Protocol};
+use ockam_multiaddr::{Code, MultiAddr, ProtoIter, Protocol};
 use ockam_transport_tcp::{TcpConnectionOptions, TCP};
+use ockam_transport_udp::UDP;
+#[cfg(unix)]
+use ockam_transport_uds::UDS;
+use ockam_transport_websocket::WS;
 
 use crate::error::ApiError;
 
+/// Prefix of the address of the forwarder created for a relay
+pub const RELAY_ADDRESS_PREFIX: &str = "forward_to_";
+
+/// Return the local address of the forwarder created for the relay `alias`
+pub fn relay_address(alias: &str) -> Address {
+    Address::new(LOCAL, format!("{RELAY_ADDRESS_PREFIX}{alias}"))
+}
+
 /// Try to convert a multi-address to an Ockam route.
 pub fn local_multiaddr_to_route(ma: &MultiAddr) -> Option<Route> {
     let mut rb = Route::new();
@@ -31,6 +44,10 @@ pub fn local_multiaddr_to_route(ma: &MultiAddr) -> Option<Route> {
                 let local = p.cast::<Secure>()?;
                 rb = rb.append(Address::new(LOCAL, &*local))
             }
+            Relay::CODE => {
+                let alias = p.cast::<Relay>()?;
+                rb = rb.append(relay_address(&alias))
+            }
 
             // If your code crashes here then the front-end CLI isn't
             // properly calling `clean_multiaddr` before passing it to
@@ -50,6 +67,7 @@ pub fn local_multiaddr_to_route(ma: &MultiAddr) -> Option<Route> {
 pub struct MultiAddrToRouteResult {
     pub flow_control_id: Option<FlowControlId>,
     pub route: Route,
+    /// Address of the transport worker (TCP, UDP, WebSocket or UDS) created for the route
     pub tcp_worker: Option<Address>,
 }
 
@@ -62,86 +80,87 @@ pub async fn multiaddr_to_route(
     let mut it = ma.iter().peekable();
 
     let mut flow_control_id = None;
-    let mut number_of_tcp_hops = 0;
+    let mut number_of_transport_hops = 0;
     let mut tcp_worker = None;
 
     while let Some(p) = it.next() {
         match p.code() {
             Ip4::CODE => {
-                if number_of_tcp_hops >= 1 {
-                    return None; // Only 1 TCP hop is allowed
+                if number_of_transport_hops >= 1 {
+                    return None; // Only 1 transport hop is allowed
                 }
 
                 let ip4 = p.cast::<Ip4>()?;
-                let port = it.next()?.cast::<Tcp>()?;
-                let socket_addr = SocketAddrV4::new(*ip4, *port);
-
-                let options = if socket_addr.ip().is_loopback() {
-                    // TODO: Enable FlowControl for loopback addresses as well
-                    TcpConnectionOptions::insecure()
-                } else {
-                    let id = flow_controls.generate_id();
-                    flow_control_id = Some(id.clone());
-                    TcpConnectionOptions::as_producer(flow_controls, &id)
-                };
-
-                let addr = tcp.connect(socket_addr.to_string(), options).await.ok()?;
+                let (addr, id) = connect_to_host(
+                    (*ip4).to_string(),
+                    ip4.is_loopback(),
+                    &mut it,
+                    tcp,
+                    flow_controls,
+                )
+                .await?;
+                flow_control_id = id;
                 tcp_worker = Some(addr.clone());
 
-                number_of_tcp_hops += 1;
+                number_of_transport_hops += 1;
                 rb = rb.append(addr)
             }
             Ip6::CODE => {
-                if number_of_tcp_hops >= 1 {
-                    return None; // Only 1 TCP hop is allowed
+                if number_of_transport_hops >= 1 {
+                    return None; // Only 1 transport hop is allowed
                 }
 
                 let ip6 = p.cast::<Ip6>()?;
-                let port = it.next()?.cast::<Tcp>()?;
-                let socket_addr = SocketAddrV6::new(*ip6, *port, 0, 0);
-
-                let options = if socket_addr.ip().is_loopback() {
-                    // TODO: Enable FlowControl for loopback addresses as well
-                    TcpConnectionOptions::insecure()
-                } else {
-                    let id = flow_controls.generate_id();
-                    flow_control_id = Some(id.clone());
-                    TcpConnectionOptions::as_producer(flow_controls, &id)
-                };
-
-                let addr = tcp.connect(socket_addr.to_string(), options).await.ok()?;
+                let (addr, id) = connect_to_host(
+                    format!("[{}]", *ip6),
+                    ip6.is_loopback(),
+                    &mut it,
+                    tcp,
+                    flow_controls,
+                )
+                .await?;
+                flow_control_id = id;
                 tcp_worker = Some(addr.clone());
 
-                number_of_tcp_hops += 1;
+                number_of_transport_hops += 1;
                 rb = rb.append(addr)
             }
             DnsAddr::CODE => {
-                if number_of_tcp_hops >= 1 {
-                    return None; // Only 1 TCP hop is allowed
+                if number_of_transport_hops >= 1 {
+                    return None; // Only 1 transport hop is allowed
                 }
 
                 let host = p.cast::<DnsAddr>()?;
                 if let Some(p) = it.peek() {
-                    if p.code() == Tcp::CODE {
-                        let port = p.cast::<Tcp>()?;
-
-                        let id = flow_controls.generate_id();
-                        flow_control_id = Some(id.clone());
-                        let options = TcpConnectionOptions::as_producer(flow_controls, &id);
-
-                        let addr = tcp
-                            .connect(format!("{}:{}", &*host, *port), options)
-                            .await
-                            .ok()?;
+                    if p.code() == Tcp::CODE || p.code() == Udp::CODE {
+                        let (addr, id) =
+                            connect_to_host(host.to_string(), false, &mut it, tcp, flow_controls)
+                                .await?;
+                        flow_control_id = id;
                         tcp_worker = Some(addr.clone());
 
-                        number_of_tcp_hops += 1;
+                        number_of_transport_hops += 1;
                         rb = rb.append(addr);
-                        let _ = it.next();
                         continue;
                     }
                 }
             }
+            #[cfg(unix)]
+            Unix::CODE => {
+                if number_of_transport_hops >= 1 {
+                    return None; // Only 1 transport hop is allowed
+                }
+
+                let path = p.cast::<Unix>()?;
+                let (addr, id) =
+                    resolve_transport_address(tcp, flow_controls, Address::new(UDS, &*path))
+                        .await?;
+                flow_control_id = id;
+                tcp_worker = Some(addr.clone());
+
+                number_of_transport_hops += 1;
+                rb = rb.append(addr)
+            }
             Worker::CODE => {
                 let local = p.cast::<Worker>()?;
                 rb = rb.append(Address::new(LOCAL, &*local))
@@ -154,6 +173,10 @@ pub async fn multiaddr_to_route(
                 let local = p.cast::<Secure>()?;
                 rb = rb.append(Address::new(LOCAL, &*local))
             }
+            Relay::CODE => {
+                let alias = p.cast::<Relay>()?;
+                rb = rb.append(relay_address(&alias))
+            }
             other => {
                 error!(target: "ockam_api", code = %other, "unsupported protocol");
                 return None;
@@ -168,6 +191,84 @@ pub async fn multiaddr_to_route(
     })
 }
 
+/// Connect to the transport address which follows a host in a multiaddr,
+/// see [`host_transport_address`]
+///
+/// Return the address of the created transport worker and its flow control id, if any.
+async fn connect_to_host(
+    host: String,
+    is_loopback: bool,
+    it: &mut Peekable<ProtoIter<'_>>,
+    tcp: &TcpTransport,
+    flow_controls: &FlowControls,
+) -> Option<(Address, Option<FlowControlId>)> {
+    let address = host_transport_address(&host, it)?;
+    if address.transport_type() != TCP {
+        return resolve_transport_address(tcp, flow_controls, address).await;
+    }
+
+    let mut flow_control_id = None;
+    let options = if is_loopback {
+        // TODO: Enable FlowControl for loopback addresses as well
+        TcpConnectionOptions::insecure()
+    } else {
+        let id = flow_controls.generate_id();
+        flow_control_id = Some(id.clone());
+        TcpConnectionOptions::as_producer(flow_controls, &id)
+    };
+
+    let addr = tcp.connect(address.address(), options).await.ok()?;
+    Some((addr, flow_control_id))
+}
+
+/// Create a worker for a transport address with the transports registered on the node,
+/// and return the address of that worker and its flow control id
+async fn resolve_transport_address(
+    tcp: &TcpTransport,
+    flow_controls: &FlowControls,
+    address: Address,
+) -> Option<(Address, Option<FlowControlId>)> {
+    let route = tcp
+        .ctx()
+        .resolve_transport_route(flow_controls, route![address])
+        .await
+        .ok()?;
+    let addr = route.next().ok()?.clone();
+    let flow_control_id = flow_controls
+        .find_flow_control_with_producer_address(&addr)
+        .map(|info| info.flow_control_id().clone());
+    Some((addr, flow_control_id))
+}
+
+/// Read the protocols which follow a host in a multiaddr and return the transport
+/// address they represent:
+///  - `/tcp/<port>` is a TCP address
+///  - `/tcp/<port>/ws` is a WebSocket address
+///  - `/udp/<port>` is a UDP address
+fn host_transport_address(host: &str, it: &mut Peekable<ProtoIter<'_>>) -> Option<Address> {
+    let p = it.next()?;
+    match p.code() {
+        Tcp::CODE => {
+            let port = p.cast::<Tcp>()?;
+            match it.peek().map(|p| p.code()) {
+                Some(Ws::CODE) => {
+                    let _ = it.next();
+                    Some(Address::new(WS, format!("{host}:{}", *port)))
+                }
+                _ => Some(Address::new(TCP, format!("{host}:{}", *port))),
+            }
+        }
+        Udp::CODE => {
+            let port = p.cast::<Udp>()?;
+            Some(Address::new(UDP, format!("{host}:{}", *port)))
+        }
+        other => {
+            error!(target: "ockam_api", code = %other, "unsupported protocol");
+            None
+        }
+    }
+}
+
 /// Resolve all the multiaddresses which represent transport addresses
 /// For example /tcp/127.0.0.1/port/4000 is transformed to the Address (TCP, "127.0.0.1:4000")
 /// The creation of a TCP worker and the substitution of that transport address to a worker address
@@ -180,28 +281,26 @@ pub fn multiaddr_to_transport_route(ma: &MultiAddr) -> Option<Route> {
         match p.code() {
             Ip4::CODE => {
                 let ip4 = p.cast::<Ip4>()?;
-                let port = it.next()?.cast::<Tcp>()?;
-                let socket_addr = SocketAddrV4::new(*ip4, *port);
-                route = route.append(Address::new(TCP, socket_addr.to_string()))
+                route = route.append(host_transport_address(&(*ip4).to_string(), &mut it)?)
             }
             Ip6::CODE => {
                 let ip6 = p.cast::<Ip6>()?;
-                let port = it.next()?.cast::<Tcp>()?;
-                let socket_addr = SocketAddrV6::new(*ip6, *port, 0, 0);
-                route = route.append(Address::new(TransportType::new(1), socket_addr.to_string()))
+                route = route.append(host_transport_address(&format!("[{}]", *ip6), &mut it)?)
             }
             DnsAddr::CODE => {
                 let host = p.cast::<DnsAddr>()?;
                 if let Some(p) = it.peek() {
-                    if p.code() == Tcp::CODE {
-                        let port = p.cast::<Tcp>()?;
-                        let addr = format!("{}:{}", &*host, *port);
-                        route = route.append(Address::new(TransportType::new(1), addr));
-                        let _ = it.next();
+                    if p.code() == Tcp::CODE || p.code() == Udp::CODE {
+                        route = route.append(host_transport_address(&host, &mut it)?);
                         continue;
                     }
                 }
             }
+            #[cfg(unix)]
+            Unix::CODE => {
+                let path = p.cast::<Unix>()?;
+                route = route.append(Address::new(UDS, &*path))
+            }
             Worker::CODE => {
                 let local = p.cast::<Worker>()?;
                 route = route.append(Address::new(LOCAL, &*local))
@@ -214,6 +313,10 @@ pub fn multiaddr_to_transport_route(ma: &MultiAddr) -> Option<Route> {
                 let local = p.cast::<Secure>()?;
                 route = route.append(Address::new(LOCAL, &*local))
             }
+            Relay::CODE => {
+                let alias = p.cast::<Relay>()?;
+                route = route.append(relay_address(&alias))
+            }
             other => {
                 error!(target: "ockam_api", code = %other, "unsupported protocol");
                 return None;
@@ -259,6 +362,21 @@ pub fn try_address_to_multiaddr(a: &Address) -> Result<MultiAddr, Error> {
     let mut ma = MultiAddr::default();
     match a.transport_type() {
         LOCAL => ma.push_back(Service::new(a.address()))?,
+        TCP => {
+            let port = push_host(&mut ma, a.address())?;
+            ma.push_back(Tcp::new(port))?
+        }
+        UDP => {
+            let port = push_host(&mut ma, a.address())?;
+            ma.push_back(Udp::new(port))?
+        }
+        WS => {
+            let port = push_host(&mut ma, a.address())?;
+            ma.push_back(Tcp::new(port))?;
+            ma.push_back(Ws)?
+        }
+        #[cfg(unix)]
+        UDS => ma.push_back(Unix::new(a.address()))?,
         other => {
             error!(target: "ockam_api", transport = %other, "unsupported transport type");
             return Err(ApiError::message(format!(
@@ -269,6 +387,23 @@ pub fn try_address_to_multiaddr(a: &Address) -> Result<MultiAddr, Error> {
     Ok(ma)
 }
 
+/// Push the host of a "host:port" transport address to a MultiAddr and return its port
+fn push_host(ma: &mut MultiAddr, address: &str) -> Result<u16, Error> {
+    if let Ok(socket_addr) = address.parse::<SocketAddr>() {
+        match socket_addr {
+            SocketAddr::V4(socket_addr) => ma.push_back(Ip4::new(*socket_addr.ip()))?,
+            SocketAddr::V6(socket_addr) => ma.push_back(Ip6::new(*socket_addr.ip()))?,
+        }
+        return Ok(socket_addr.port());
+    }
+
+    let invalid = || ApiError::message(format!("invalid transport address: {address}"));
+    let (host, port) = address.rsplit_once(':').ok_or_else(invalid)?;
+    let port = port.parse::<u16>().map_err(|_| invalid())?;
+    ma.push_back(DnsAddr::new(host))?;
+    Ok(port)
+}
+
 /// Try to convert an Ockam Address into a MultiAddr.
 pub fn addr_to_multiaddr<T: Into<Address>>(a: T) -> Option<MultiAddr> {
     let r: Route = Route::from(a);
@@ -311,6 +446,10 @@ pub fn is_local_node(ma: &MultiAddr) -> anyhow::Result<bool> {
                     .map(|ip6| ip6.is_loopback())
                     .ok_or_else(|| anyhow!("Invalid \"ip6\" value"))?;
             }
+            // A "/unix" socket is always on the local machine
+            Unix::CODE => {
+                at_rust_node = true;
+            }
             // A MultiAddr starting with "/service" could reference both local and remote nodes.
             _ => {
                 return Err(anyhow!("Invalid address, protocol not supported"));
@@ -332,8 +471,11 @@ pub fn local_worker(code: &Code) -> Result<bool> {
         | Ip4::CODE
         | Ip6::CODE
         | Tcp::CODE
+        | Udp::CODE
+        | Ws::CODE
+        | Unix::CODE
         | Secure::CODE => Ok(false),
-        Worker::CODE | Service::CODE => Ok(true),
+        Worker::CODE | Service::CODE | Relay::CODE => Ok(true),
 
         _ => Err(ApiError::message(format!("unknown transport type: {code}"))),
     }
@@ -440,14 +582,7 @@ pub mod test {
         let node_manager = node_manager_worker.get().clone();
         let flow_controls = node_manager.read().await.flow_controls.clone();
         let secure_channels = node_manager.read().await.secure_channels.clone();
-        context
-            .start_worker(
-                NODEMANAGER_ADDR,
-                node_manager_worker,
-                ockam_core::AllowAll,
-                ockam_core::AllowAll,
-            )
-            .await?;
+        node_manager_worker.start(context, NODEMANAGER_ADDR).await?;
 
         Ok(NodeManagerHandle {
             cli_state,
@@ -0,0 +1,194 @@
+use minicbor::Decoder;
+use ockam::identity::identities;
+use ockam::route;
+use ockam_api::authenticator::direct::types::CreateToken;
+use ockam_api::authenticator::direct::{
+    DirectAuthenticator, DirectAuthenticatorClient, EnrollmentTokenAuthenticator,
+};
+use ockam_core::api::{Request, Response, Status};
+use ockam_core::compat::rand::random_string;
+use ockam_core::{AllowAll, Result};
+use ockam_identity::{
+    CredentialsIssuer, CredentialsIssuerClient, InMemoryStorage, SecureChannelListenerOptions,
+    SecureChannelOptions, SecureChannels,
+};
+use ockam_node::{Context, RpcClient};
+use std::collections::HashMap;
+use std::time::Duration;
+
+#[ockam_macros::test]
+async fn manage_members(ctx: &mut Context) -> Result<()> {
+    let listener_addr = random_string();
+    let authenticator_addr = random_string();
+    let issuer_addr = random_string();
+
+    let identities = identities();
+    let secure_channels = SecureChannels::builder()
+        .with_identities(identities.clone())
+        .build();
+    let creation = identities.identities_creation();
+    let authority = creation.create_identity().await?;
+    let enroller = creation.create_identity().await?;
+    let member1 = creation.create_identity().await?;
+    let member2 = creation.create_identity().await?;
+
+    secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &authority.identifier(),
+            &listener_addr,
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+    let repository = identities.repository();
+    let authenticator = DirectAuthenticator::new(
+        "project42".into(),
+        repository.as_attributes_writer(),
+        repository.as_attributes_reader(),
+    )
+    .await?;
+    ctx.start_worker(&authenticator_addr, authenticator, AllowAll, AllowAll)
+        .await?;
+    let issuer = CredentialsIssuer::new(
+        identities.clone(),
+        authority.identifier(),
+        "project42".into(),
+    )
+    .await?;
+    ctx.start_worker(&issuer_addr, issuer, AllowAll, AllowAll)
+        .await?;
+
+    // Add two members, the second one for one hour only
+    let e2a = secure_channels
+        .create_secure_channel(
+            ctx,
+            &enroller.identifier(),
+            &listener_addr,
+            SecureChannelOptions::new(),
+        )
+        .await?;
+    let client = DirectAuthenticatorClient::new(
+        RpcClient::new(route![e2a.address(), &authenticator_addr], ctx).await?,
+    );
+    client
+        .add_member(member1.identifier(), HashMap::from([("role", "admin")]))
+        .await?;
+    client
+        .add_member_with_ttl(
+            member2.identifier(),
+            HashMap::from([("role", "guest")]),
+            Duration::from_secs(3600),
+        )
+        .await?;
+
+    let members = client.list_members().await?;
+    assert_eq!(members.len(), 2);
+
+    let entry = client.get_member(member1.identifier()).await?.unwrap();
+    assert_eq!(entry.attrs().get("role"), Some(&b"admin".to_vec()));
+    assert_eq!(entry.expires(), None);
+    assert_eq!(entry.attested_by(), Some(enroller.identifier()));
+
+    let entry = client.get_member(member2.identifier()).await?.unwrap();
+    let expires = entry.expires().unwrap();
+    assert_eq!(
+        expires.elapsed(entry.added()),
+        Some(Duration::from_secs(3600))
+    );
+
+    // The credential of a member expires with its membership
+    let m2a = secure_channels
+        .create_secure_channel(
+            ctx,
+            &member2.identifier(),
+            &listener_addr,
+            SecureChannelOptions::new(),
+        )
+        .await?;
+    let credential = CredentialsIssuerClient::new(route![m2a.address(), &issuer_addr], ctx)
+        .await?
+        .credential()
+        .await?;
+    let data = identities
+        .credentials()
+        .verify_credential(&member2.identifier(), &[authority.clone()], credential)
+        .await?;
+    assert!(data.expires_at() <= expires);
+    assert_eq!(Some(b"guest".as_slice()), data.attributes().get("role"));
+
+    // Update the attributes of a member and make its membership permanent
+    client
+        .update_member(
+            member2.identifier(),
+            HashMap::from([("role", "member")]),
+            None,
+        )
+        .await?;
+    let entry = client.get_member(member2.identifier()).await?.unwrap();
+    assert_eq!(entry.attrs().get("role"), Some(&b"member".to_vec()));
+    assert_eq!(entry.expires(), None);
+
+    // Only existing members can be updated
+    let unknown = creation.create_identity().await?;
+    assert!(client
+        .update_member(unknown.identifier(), HashMap::new(), None)
+        .await
+        .is_err());
+    assert!(client.get_member(unknown.identifier()).await?.is_none());
+
+    // Remove a member
+    client.delete_member(member1.identifier()).await?;
+    assert!(client.get_member(member1.identifier()).await?.is_none());
+    assert_eq!(client.list_members().await?.len(), 1);
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn reject_tokens_which_cannot_be_used(ctx: &mut Context) -> Result<()> {
+    let listener_addr = random_string();
+    let issuer_addr = random_string();
+
+    let identities = identities();
+    let secure_channels = SecureChannels::builder()
+        .with_identities(identities.clone())
+        .build();
+    let creation = identities.identities_creation();
+    let authority = creation.create_identity().await?;
+    let enroller = creation.create_identity().await?;
+
+    secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &authority.identifier(),
+            &listener_addr,
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+    let (issuer, _acceptor) = EnrollmentTokenAuthenticator::new_worker_pair(
+        "project42".into(),
+        identities.repository().as_attributes_writer(),
+        InMemoryStorage::create(),
+    );
+    ctx.start_worker(&issuer_addr, issuer, AllowAll, AllowAll)
+        .await?;
+
+    let e2a = secure_channels
+        .create_secure_channel(
+            ctx,
+            &enroller.identifier(),
+            &listener_addr,
+            SecureChannelOptions::new(),
+        )
+        .await?;
+    let request = Request::post("/tokens")
+        .body(CreateToken::new().with_usage_count(0))
+        .to_vec()?;
+    let response: Vec<u8> = ctx
+        .send_and_receive(route![e2a.address(), &issuer_addr], request)
+        .await?;
+    let response: Response = Decoder::new(&response).decode()?;
+    assert_eq!(response.status(), Some(Status::BadRequest));
+
+    ctx.stop().await
+}
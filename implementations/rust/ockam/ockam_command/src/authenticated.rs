@@ -9,7 +9,6 @@ use ockam::identity::{AttributesEntry, IdentityIdentifier};
 use ockam::{Context, TcpTransport};
 use ockam_api::auth;
 use ockam_api::is_local_node;
-use ockam_core::flow_control::FlowControls;
 use ockam_multiaddr::MultiAddr;
 use termimad::{minimad::TextTemplate, MadSkin};
 
@@ -123,7 +122,7 @@ fn print_entries(entries: &[(IdentityIdentifier, AttributesEntry)]) {
 }
 
 async fn client(ctx: &Context, tcp: &TcpTransport, addr: &MultiAddr) -> Result<auth::Client> {
-    let flow_controls = FlowControls::default();
+    let flow_controls = ctx.flow_controls().clone();
     let route = ockam_api::multiaddr_to_route(addr, tcp, &flow_controls)
         .await
         .ok_or_else(|| anyhow!("failed to parse address: {addr}"))?;
@@ -298,10 +298,12 @@ async fn start_authority_node(
 
     let trusted_identities = cmd.trusted_identities(&identity.identifier())?;
 
+    let vault = opts.state.vaults.default()?;
     let configuration = authority_node::Configuration {
         identity,
         storage_path: opts.state.identities.identities_repository_path()?,
-        vault_path: opts.state.vaults.default()?.vault_file_path().clone(),
+        vault_path: vault.vault_file_path().clone(),
+        vault_encrypted: vault.config().is_encrypted(),
         project_identifier: cmd.project_identifier.clone(),
         trust_context_identifier: cmd.project_identifier,
         tcp_listener_address: cmd.tcp_listener_address,
@@ -40,6 +40,10 @@ CLI Behavior
 - OCKAM_LOG: a `string` that defines the verbosity of the logs when the `--verbose` argument is not passed.
 - OCKAM_LOG_MAX_SIZE_MB: an `integer` that defines the maximum size of a log file in MB.
 - OCKAM_LOG_MAX_FILES: an `integer` that defines the maximum number of log files to keep per node.
+- OCKAM_VAULT_KEY: a `string` containing the hex-encoded 256 bits key used to unlock encrypted vaults.
+- OCKAM_VAULT_PASSPHRASE: a `string` containing the passphrase used to unlock encrypted vaults.
+- OCKAM_VAULT_NEW_KEY: a `string` containing the new hex-encoded key used by `ockam vault rekey`.
+- OCKAM_VAULT_NEW_PASSPHRASE: a `string` containing the new passphrase used by `ockam vault rekey`.
 
 Devs Usage
 - OCKAM_HELP_SHOW_HIDDEN: a `boolean` to control the visibility of hidden commands.
@@ -0,0 +1,82 @@
+use crate::util::{api, extract_address_value, node_rpc, Rpc};
+use crate::{docs, CommandGlobalOpts};
+use clap::Args;
+use ockam::identity::{IdentitiesVault, Identity};
+use ockam::Context;
+use ockam_api::cli_state::traits::StateDirTrait;
+use ockam_api::nodes::models::identity::LongIdentityResponse;
+use ockam_core::compat::sync::Arc;
+use ockam_identity::identities;
+
+const LONG_ABOUT: &str = include_str!("./static/rotate/long_about.txt");
+const AFTER_LONG_HELP: &str = include_str!("./static/rotate/after_long_help.txt");
+
+/// Rotate the root key of an identity
+#[derive(Clone, Debug, Args)]
+#[command(
+long_about = docs::about(LONG_ABOUT),
+after_long_help = docs::after_help(AFTER_LONG_HELP)
+)]
+pub struct RotateCommand {
+    /// Name of the identity to rotate. The default identity is used if not specified
+    #[arg(conflicts_with = "at")]
+    name: Option<String>,
+
+    /// Vault name storing the identity key. The default vault is used if not specified
+    #[arg(long, conflicts_with = "at")]
+    vault: Option<String>,
+
+    /// Rotate the identity of a running node, using the vault of that node
+    #[arg(long, value_name = "NODE")]
+    at: Option<String>,
+}
+
+impl RotateCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(run_impl, (options, self))
+    }
+}
+
+async fn run_impl(
+    ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, RotateCommand),
+) -> crate::Result<()> {
+    let identity = match &cmd.at {
+        Some(at) => rotate_on_node(&ctx, &opts, at).await?,
+        None => {
+            let mut state = opts.state.identities.get_or_default(cmd.name.as_deref())?;
+            let vault: Arc<dyn IdentitiesVault> = match &cmd.vault {
+                Some(vault) => opts.state.vaults.get(vault)?.get().await?,
+                None => opts.state.vaults.default()?.get().await?,
+            };
+            state.rotate_root_key(vault).await?
+        }
+    };
+
+    opts.terminal
+        .stdout()
+        .plain(format!("Identity rotated: {}", identity.identifier()))
+        .machine(identity.identifier())
+        .json(serde_json::json!({ "identity": {
+            "identifier": &identity.identifier(),
+            "change_history": identity.export_hex()?,
+        }}))
+        .write_line()?;
+    Ok(())
+}
+
+/// Ask a running node to rotate its own identity, since the node holds the identity key
+async fn rotate_on_node(
+    ctx: &Context,
+    opts: &CommandGlobalOpts,
+    at: &str,
+) -> crate::Result<Identity> {
+    let node = extract_address_value(at)?;
+    let mut rpc = Rpc::background(ctx, opts, &node)?;
+    rpc.request(api::rotate_identity()).await?;
+    let response = rpc.parse_response::<LongIdentityResponse>()?;
+    Ok(identities()
+        .identities_creation()
+        .decode_identity(&response.identity)
+        .await?)
+}
@@ -15,7 +15,7 @@ use crate::{
     fmt_log, fmt_ok,
     kafka::{
         kafka_consumer_default_addr, kafka_default_consumer_port_range,
-        kafka_default_consumer_server, kafka_default_project_route,
+        kafka_default_consumer_server, kafka_default_project_route, RecordEncryptionOpts,
     },
     node::NodeOpts,
     service::start::start_service_impl,
@@ -44,6 +44,8 @@ pub struct CreateCommand {
     /// The route to the project in ockam orchestrator, expected something like /project/<name>
     #[arg(long, default_value_t = kafka_default_project_route())]
     project_route: MultiAddr,
+    #[command(flatten)]
+    record_encryption: RecordEncryptionOpts,
 }
 
 impl CreateCommand {
@@ -61,13 +63,15 @@ async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, CreateCommand)) -> R
         bootstrap_server,
         brokers_port_range,
         project_route,
+        record_encryption,
     } = cmd;
     let is_finished = Mutex::new(false);
     let send_req = async {
         let tcp = TcpTransport::create(&ctx).await?;
 
         let payload =
-            StartKafkaConsumerRequest::new(bootstrap_server, brokers_port_range, project_route);
+            StartKafkaConsumerRequest::new(bootstrap_server, brokers_port_range, project_route)
+                .with_record_encryption(record_encryption.record_encryption()?);
         let payload = StartServiceRequest::new(payload, &addr);
         let req = Request::post("/node/services/kafka_consumer").body(payload);
 
@@ -1,6 +1,11 @@
+use std::path::PathBuf;
 use std::{net::SocketAddr, str::FromStr};
 
+use anyhow::anyhow;
+use clap::{Args, ValueEnum};
+use ockam_api::nodes::models::services::{KafkaKeyEncryption, KafkaRecordEncryption};
 use ockam_api::{port_range::PortRange, DefaultAddress};
+use ockam_core::env::get_env;
 use ockam_multiaddr::MultiAddr;
 
 pub(crate) mod consumer;
@@ -12,6 +17,9 @@ const KAFKA_DEFAULT_CONSUMER_PORT_RANGE: &str = "4001-4100";
 const KAFKA_DEFAULT_PRODUCER_SERVER: &str = "127.0.0.1:5000";
 const KAFKA_DEFAULT_PRODUCER_PORT_RANGE: &str = "5001-5100";
 
+/// Environment variable holding the hex encoded secret used to hash the keys of the records
+const OCKAM_KAFKA_KEY_HASH_SECRET: &str = "OCKAM_KAFKA_KEY_HASH_SECRET";
+
 fn kafka_consumer_default_addr() -> String {
     DefaultAddress::KAFKA_CONSUMER.to_string()
 }
@@ -43,3 +51,74 @@ fn kafka_default_producer_port_range() -> PortRange {
     PortRange::from_str(KAFKA_DEFAULT_PRODUCER_PORT_RANGE)
         .expect("Failed to parse default producer port range")
 }
+
+/// Options controlling which parts of the Kafka records are encrypted, on top of their values.
+/// Consumers decrypt records transparently, whatever the options used by the producers.
+#[derive(Clone, Debug, Args)]
+pub(crate) struct RecordEncryptionOpts {
+    /// Encrypt the headers of the records
+    #[arg(long)]
+    encrypt_headers: bool,
+    /// Encrypt the keys of the records. With `deterministic`, keys are replaced by a keyed hash
+    /// so that records with the same key still go to the same partition
+    #[arg(long, value_enum, value_name = "MODE")]
+    encrypt_keys: Option<KeyEncryptionMode>,
+    /// Hex encoded secret used to hash the keys, it must be shared by all the producers of a topic.
+    /// It can also be read from a file with `--key-hash-secret-file`, or from the
+    /// OCKAM_KAFKA_KEY_HASH_SECRET environment variable, so that it doesn't show in the process list
+    #[arg(long, value_name = "HEX", value_parser = hex_secret_parser)]
+    key_hash_secret: Option<Vec<u8>>,
+    /// Path of a file containing the hex encoded secret used to hash the keys
+    #[arg(long, value_name = "PATH", conflicts_with = "key_hash_secret")]
+    key_hash_secret_file: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, ValueEnum, PartialEq, Eq)]
+enum KeyEncryptionMode {
+    Randomized,
+    Deterministic,
+}
+
+impl RecordEncryptionOpts {
+    fn record_encryption(&self) -> crate::Result<KafkaRecordEncryption> {
+        let keys = match &self.encrypt_keys {
+            Some(KeyEncryptionMode::Randomized) => KafkaKeyEncryption::Randomized,
+            Some(KeyEncryptionMode::Deterministic) => {
+                KafkaKeyEncryption::Deterministic(self.key_hash_secret()?)
+            }
+            None => KafkaKeyEncryption::Plain,
+        };
+        Ok(KafkaRecordEncryption {
+            encrypt_headers: self.encrypt_headers,
+            keys,
+        })
+    }
+
+    /// Return the secret used to hash the keys, from the command line, a file or the environment
+    fn key_hash_secret(&self) -> crate::Result<Vec<u8>> {
+        if let Some(secret) = &self.key_hash_secret {
+            return Ok(secret.clone());
+        }
+        if let Some(path) = &self.key_hash_secret_file {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| anyhow!("Cannot read the key hash secret from {path:?}: {e}"))?;
+            return hex_secret_parser(contents.trim());
+        }
+        if let Some(secret) = get_env::<String>(OCKAM_KAFKA_KEY_HASH_SECRET)? {
+            return hex_secret_parser(secret.trim());
+        }
+        Err(anyhow!(
+            "Deterministic key encryption requires a secret, use --key-hash-secret, \
+             --key-hash-secret-file or the {OCKAM_KAFKA_KEY_HASH_SECRET} environment variable"
+        )
+        .into())
+    }
+}
+
+fn hex_secret_parser(input: &str) -> crate::Result<Vec<u8>> {
+    let secret = hex::decode(input).map_err(|_| anyhow!("Invalid hex secret"))?;
+    if secret.is_empty() {
+        return Err(anyhow!("The key hash secret can't be empty").into());
+    }
+    Ok(secret)
+}
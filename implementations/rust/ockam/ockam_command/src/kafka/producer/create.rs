@@ -15,7 +15,7 @@ use crate::{
     fmt_log, fmt_ok,
     kafka::{
         kafka_default_producer_port_range, kafka_default_producer_server,
-        kafka_default_project_route, kafka_producer_default_addr,
+        kafka_default_project_route, kafka_producer_default_addr, RecordEncryptionOpts,
     },
     node::NodeOpts,
     service::start::start_service_impl,
@@ -44,6 +44,8 @@ pub struct CreateCommand {
     /// The route to the project in ockam orchestrator, expected something like /project/<name>
     #[arg(long, default_value_t = kafka_default_project_route())]
     project_route: MultiAddr,
+    #[command(flatten)]
+    record_encryption: RecordEncryptionOpts,
 }
 
 impl CreateCommand {
@@ -62,6 +64,7 @@ async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, CreateCommand)) -> c
         bootstrap_server,
         brokers_port_range,
         project_route,
+        record_encryption,
     } = cmd;
     let is_finished = Mutex::new(false);
 
@@ -69,7 +72,8 @@ async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, CreateCommand)) -> c
         let tcp = TcpTransport::create(&ctx).await?;
 
         let payload =
-            StartKafkaProducerRequest::new(bootstrap_server, brokers_port_range, project_route);
+            StartKafkaProducerRequest::new(bootstrap_server, brokers_port_range, project_route)
+                .with_record_encryption(record_encryption.record_encryption()?);
         let payload = StartServiceRequest::new(payload, &addr);
         let req = Request::post("/node/services/kafka_producer").body(payload);
         start_service_impl(
@@ -34,6 +34,7 @@ mod subscription;
 mod tcp;
 mod terminal;
 mod trust_context;
+mod udp;
 mod upgrade;
 mod util;
 mod vault;
@@ -83,6 +84,7 @@ use tcp::{
 };
 use terminal::OckamColor;
 use trust_context::TrustContextCommand;
+use udp::{inlet::UdpInletCommand, outlet::UdpOutletCommand};
 use upgrade::check_if_an_upgrade_is_available;
 use util::{exitcode, exitcode::ExitCode, OckamConfig};
 use vault::VaultCommand;
@@ -240,6 +242,9 @@ pub enum OckamSubcommand {
     TcpOutlet(TcpOutletCommand),
     TcpInlet(TcpInletCommand),
 
+    UdpOutlet(UdpOutletCommand),
+    UdpInlet(UdpInletCommand),
+
     KafkaConsumer(KafkaConsumerCommand),
     KafkaProducer(KafkaProducerCommand),
 
@@ -342,6 +347,8 @@ impl OckamCommand {
             OckamSubcommand::TcpConnection(c) => c.run(options),
             OckamSubcommand::TcpOutlet(c) => c.run(options),
             OckamSubcommand::TcpInlet(c) => c.run(options),
+            OckamSubcommand::UdpOutlet(c) => c.run(options),
+            OckamSubcommand::UdpInlet(c) => c.run(options),
 
             OckamSubcommand::KafkaConsumer(c) => c.run(options),
             OckamSubcommand::KafkaProducer(c) => c.run(options),
@@ -19,7 +19,7 @@ use crate::service::config::Config;
 use crate::util::api::{TrustContextConfigBuilder, TrustContextOpts};
 use crate::util::node_rpc;
 use crate::util::{api, parse_node_name, RpcBuilder};
-use crate::util::{bind_to_port_check, embedded_node_that_is_not_stopped, exitcode};
+use crate::util::{bind_to_port_check, embedded_node_with_metrics_that_is_not_stopped, exitcode};
 use crate::{
     docs, identity, node::show::print_query_status, util::find_available_port, CommandGlobalOpts,
     Result,
@@ -27,6 +27,7 @@ use crate::{
 use ockam::{Address, AsyncTryClone, TcpListenerOptions};
 use ockam::{Context, TcpTransport};
 use ockam_api::cli_state::traits::{StateDirTrait, StateItemTrait};
+use ockam_api::metrics::start_metrics_endpoint;
 use ockam_api::nodes::authority_node;
 use ockam_api::nodes::models::transport::CreateTransportJson;
 use ockam_api::nodes::service::{ApiTransport, NodeManagerTrustOptions};
@@ -41,7 +42,9 @@ use ockam_api::{
     },
 };
 use ockam_core::api::{RequestBuilder, Response, Status};
+use ockam_core::compat::sync::Arc;
 use ockam_core::{route, AllowAll, LOCAL};
+use ockam_node::metrics::{MetricsRecorder, PrometheusRecorder};
 
 use super::show::is_node_up;
 use super::util::check_default;
@@ -78,6 +81,11 @@ pub struct CreateCommand {
     )]
     pub tcp_listener_address: String,
 
+    /// Serve the metrics of the node in the Prometheus text format on
+    /// `/metrics` at this address
+    #[arg(display_order = 900, long, value_name = "SOCKET_ADDRESS")]
+    pub metrics_address: Option<SocketAddr>,
+
     /// ockam_command started a child process to run this node in foreground.
     #[arg(display_order = 900, long, hide = true)]
     pub child_process: bool,
@@ -119,6 +127,7 @@ impl Default for CreateCommand {
             node_name: hex::encode(random::<[u8; 4]>()),
             exit_on_eof: false,
             tcp_listener_address: "127.0.0.1:0".to_string(),
+            metrics_address: None,
             foreground: false,
             child_process: false,
             launch_config: None,
@@ -212,13 +221,24 @@ async fn run_impl(
 
 fn create_foreground_node(opts: &CommandGlobalOpts, cmd: &CreateCommand) -> crate::Result<()> {
     let cmd = cmd.overwrite_addr()?;
-    embedded_node_that_is_not_stopped(run_foreground_node, (opts.clone(), cmd))?;
+    let recorder = cmd
+        .metrics_address
+        .map(|_| Arc::new(PrometheusRecorder::new()));
+    embedded_node_with_metrics_that_is_not_stopped(
+        run_foreground_node,
+        (opts.clone(), cmd, recorder.clone()),
+        recorder.map(|r| r as Arc<dyn MetricsRecorder>),
+    )?;
     Ok(())
 }
 
 async fn run_foreground_node(
     mut ctx: Context,
-    (opts, cmd): (CommandGlobalOpts, CreateCommand),
+    (opts, cmd, recorder): (
+        CommandGlobalOpts,
+        CreateCommand,
+        Option<Arc<PrometheusRecorder>>,
+    ),
 ) -> crate::Result<()> {
     let cfg = &opts.config;
     let node_name = parse_node_name(&cmd.node_name)?;
@@ -254,12 +274,17 @@ async fn run_foreground_node(
     // TODO: This is only listening on loopback address, but should use FlowControls anyways
     let (socket_addr, listener_addr) = tcp.listen(&bind, TcpListenerOptions::insecure()).await?;
 
+    if let (Some(addr), Some(recorder)) = (cmd.metrics_address, recorder) {
+        start_metrics_endpoint(ctx.runtime(), addr, recorder).await?;
+    }
+
     let node_state = opts.state.nodes.get(&node_name)?;
     node_state.set_setup(
         &node_state
             .config()
             .setup_mut()
             .set_verbose(opts.global_args.verbose)
+            .set_metrics_address(cmd.metrics_address)
             .add_transport(CreateTransportJson::new(
                 TransportType::Tcp,
                 TransportMode::Listen,
@@ -294,8 +319,7 @@ async fn run_foreground_node(
     .await?;
     let node_manager_worker = NodeManagerWorker::new(node_man);
 
-    ctx.start_worker(NODEMANAGER_ADDR, node_manager_worker, AllowAll, AllowAll)
-        .await?;
+    node_manager_worker.start(&ctx, NODEMANAGER_ADDR).await?;
 
     if let Some(config) = &cmd.launch_config {
         if start_services(&ctx, config).await.is_err() {
@@ -467,6 +491,7 @@ async fn spawn_background_node(
         opts.global_args.verbose,
         &node_name,
         &cmd.tcp_listener_address,
+        cmd.metrics_address.as_ref(),
         cmd.trust_context_opts.project_path.as_ref(),
         cmd.trusted_identities.as_ref(),
         cmd.trusted_identities_file.as_ref(),
@@ -519,10 +544,12 @@ async fn start_authority_node(
             .map(|ts| ts.unwrap_or(PreTrustedIdentities::Fixed(Default::default())))
             .map_err(|e| crate::Error::new(exitcode::CONFIG, anyhow!("{e}")))?;
 
+        let vault = opts.state.vaults.default()?;
         let configuration = authority_node::Configuration {
             identity,
             storage_path: opts.state.identities.identities_repository_path()?,
-            vault_path: opts.state.vaults.default()?.vault_file_path().clone(),
+            vault_path: vault.vault_file_path().clone(),
+            vault_encrypted: vault.config().is_encrypted(),
             project_identifier: authenticator_config.project.clone(),
             trust_context_identifier: authenticator_config.project,
             tcp_listener_address: cmd.tcp_listener_address,
@@ -135,7 +135,10 @@ fn print_node_info(
         println!("  Outlets:");
         for e in &outlets.list {
             println!("    Outlet:");
-            println!("      Forward Address: {}", e.tcp_addr);
+            match &e.unix_path {
+                Some(path) => println!("      Forward Unix Socket: {path}"),
+                None => println!("      Forward Address: {}", e.tcp_addr),
+            }
 
             if let Some(ma) = addr_to_multiaddr(e.worker_addr.as_ref()) {
                 println!("      Address: {ma}");
@@ -61,6 +61,7 @@ async fn run_impl(
         node_setup.verbose, // Previously user-chosen verbosity level
         node_name,          // The selected node name
         &node_setup.default_tcp_listener()?.addr.to_string(), // The selected node api address
+        node_setup.metrics_address(), // Previously user-chosen metrics endpoint
         None,               // No project information available
         None,               // No trusted identities
         None,               // "
@@ -15,6 +15,7 @@ use ockam_api::nodes::{NodeManager, NodeManagerWorker, NODEMANAGER_ADDR};
 use ockam_core::AllowAll;
 use std::env::current_exe;
 use std::fs::OpenOptions;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::process::Command;
 use tracing::{debug, info};
@@ -92,13 +93,8 @@ pub async fn start_embedded_node_with_vault_and_identity(
 
     let node_manager_worker = NodeManagerWorker::new(node_man);
 
-    ctx.start_worker(
-        NODEMANAGER_ADDR,
-        node_manager_worker,
-        AllowAll, // FIXME: @ac
-        AllowAll, // FIXME: @ac
-    )
-    .await?;
+    // FIXME: @ac
+    node_manager_worker.start(ctx, NODEMANAGER_ADDR).await?;
 
     Ok(cmd.node_name.clone())
 }
@@ -200,6 +196,7 @@ pub fn spawn_node(
     verbose: u8,
     name: &str,
     address: &str,
+    metrics_address: Option<&SocketAddr>,
     project: Option<&PathBuf>,
     trusted_identities: Option<&String>,
     trusted_identities_file: Option<&PathBuf>,
@@ -224,6 +221,11 @@ pub fn spawn_node(
         "--child-process".to_string(),
     ];
 
+    if let Some(metrics_address) = metrics_address {
+        args.push("--metrics-address".to_string());
+        args.push(metrics_address.to_string());
+    }
+
     if let Some(path) = project {
         args.push("--project-path".to_string());
         let p = path
@@ -0,0 +1,70 @@
+use crate::policy::policy_path;
+use crate::util::{extract_address_value, node_rpc, Rpc};
+use crate::{CommandGlobalOpts, Result};
+use anyhow::{anyhow, Context as _};
+use clap::Args;
+use ockam::Context;
+use ockam_abac::{Action, Expr, Resource, Schema, Type};
+use ockam_api::nodes::models::policy::{CheckPolicy, PolicyDiagnostics};
+use ockam_core::api::Request;
+
+/// Check a policy for unknown attributes, type errors and
+/// sub-expressions which are always true or false
+#[derive(Clone, Debug, Args)]
+pub struct CheckCommand {
+    /// Node on which the policy is defined.
+    #[arg(long, display_order = 900, id = "NODE")]
+    at: String,
+
+    #[arg(short, long)]
+    resource: Resource,
+
+    #[arg(short, long, default_value = "handle_message")]
+    action: Action,
+
+    /// Expression to check instead of the stored policy
+    #[arg(short, long)]
+    expression: Option<Expr>,
+
+    /// Attributes in `name:type` format which the policy may refer to,
+    /// e.g. `subject.role:str`. Types are `str`, `int`, `float`, `bool`, `seq` and `any`
+    #[arg(long = "attribute", value_name = "ATTRIBUTE")]
+    attributes: Vec<String>,
+}
+
+impl CheckCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+
+    fn schema(&self) -> Result<Schema> {
+        let mut schema = Schema::new();
+        for attr in &self.attributes {
+            let (name, typ) = attr.split_once(':').context("type expected")?;
+            let typ: Type = typ.parse().map_err(|e: String| anyhow!(e))?;
+            schema.put(name, typ);
+        }
+        Ok(schema)
+    }
+}
+
+async fn rpc(mut ctx: Context, (opts, cmd): (CommandGlobalOpts, CheckCommand)) -> Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(ctx: &mut Context, opts: CommandGlobalOpts, cmd: CheckCommand) -> Result<()> {
+    let node = extract_address_value(&cmd.at)?;
+    let bdy = CheckPolicy::new(cmd.expression.clone(), cmd.schema()?);
+    let path = format!("{}/check", policy_path(&cmd.resource, &cmd.action));
+    let req = Request::post(path).body(bdy);
+    let mut rpc = Rpc::background(ctx, &opts, &node)?;
+    rpc.request(req).await?;
+    let res: PolicyDiagnostics = rpc.parse_response()?;
+    for d in res.diagnostics() {
+        println!("{d}")
+    }
+    if res.diagnostics().iter().any(|d| d.is_error()) {
+        return Err(anyhow!("the policy has errors").into());
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::policy::policy_path;
+use crate::util::{extract_address_value, node_rpc, Rpc};
+use crate::{CommandGlobalOpts, Result};
+use anyhow::Context as _;
+use clap::Args;
+use ockam::Context;
+use ockam_abac::{Action, Expr, Outcome, Resource};
+use ockam_api::nodes::models::policy::{DryRunPolicy, PolicyTrace};
+use ockam_core::api::Request;
+use std::collections::BTreeMap;
+
+/// Evaluate a policy against a set of attributes and show
+/// how each of its sub-expressions evaluates
+#[derive(Clone, Debug, Args)]
+pub struct DryRunCommand {
+    /// Node on which the policy is defined.
+    #[arg(long, display_order = 900, id = "NODE")]
+    at: String,
+
+    #[arg(short, long)]
+    resource: Resource,
+
+    #[arg(short, long, default_value = "handle_message")]
+    action: Action,
+
+    /// Expression to evaluate instead of the stored policy
+    #[arg(short, long)]
+    expression: Option<Expr>,
+
+    /// Attributes in `name=value` format, e.g. `subject.role=admin`
+    #[arg(long = "attribute", value_name = "ATTRIBUTE")]
+    attributes: Vec<String>,
+}
+
+impl DryRunCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+
+    fn attributes(&self) -> Result<BTreeMap<String, String>> {
+        let mut attributes = BTreeMap::new();
+        for attr in &self.attributes {
+            let (key, value) = attr.split_once('=').context("value expected")?;
+            attributes.insert(key.to_string(), value.to_string());
+        }
+        Ok(attributes)
+    }
+}
+
+async fn rpc(mut ctx: Context, (opts, cmd): (CommandGlobalOpts, DryRunCommand)) -> Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(ctx: &mut Context, opts: CommandGlobalOpts, cmd: DryRunCommand) -> Result<()> {
+    let node = extract_address_value(&cmd.at)?;
+    let bdy = DryRunPolicy::new(cmd.expression.clone(), cmd.attributes()?);
+    let path = format!("{}/dry-run", policy_path(&cmd.resource, &cmd.action));
+    let req = Request::post(path).body(bdy);
+    let mut rpc = Rpc::background(ctx, &opts, &node)?;
+    rpc.request(req).await?;
+    let res: PolicyTrace = rpc.parse_response()?;
+    for t in res.entries() {
+        let indent = "  ".repeat(t.depth() as usize);
+        match t.outcome() {
+            Some(Outcome::Value(v)) => println!("{indent}{} => {v}", t.expr()),
+            Some(Outcome::Error(e)) => println!("{indent}{} => error: {e}", t.expr()),
+            None => println!("{indent}{} => not evaluated", t.expr()),
+        }
+    }
+    Ok(())
+}
@@ -1,8 +1,12 @@
+mod check;
 mod create;
 mod delete;
+mod dry_run;
 mod list;
 mod show;
+use crate::policy::check::CheckCommand;
 use crate::policy::delete::DeleteCommand;
+use crate::policy::dry_run::DryRunCommand;
 use crate::policy::list::ListCommand;
 use crate::policy::show::ShowCommand;
 use crate::{policy::create::CreateCommand, util::Rpc};
@@ -28,6 +32,8 @@ pub enum PolicySubcommand {
     Show(ShowCommand),
     Delete(DeleteCommand),
     List(ListCommand),
+    Check(CheckCommand),
+    DryRun(DryRunCommand),
 }
 
 impl PolicyCommand {
@@ -37,6 +43,8 @@ impl PolicyCommand {
             PolicySubcommand::Show(c) => c.run(opts),
             PolicySubcommand::Delete(c) => c.run(opts),
             PolicySubcommand::List(c) => c.run(opts),
+            PolicySubcommand::Check(c) => c.run(opts),
+            PolicySubcommand::DryRun(c) => c.run(opts),
         }
     }
 }
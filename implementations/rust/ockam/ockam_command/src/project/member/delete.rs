@@ -0,0 +1,40 @@
+use clap::Args;
+
+use ockam::identity::IdentityIdentifier;
+use ockam::Context;
+
+use crate::node::util::delete_embedded_node;
+use crate::project::member::{authenticator_client, AuthorityOpts};
+use crate::util::node_rpc;
+use crate::{CommandGlobalOpts, Result};
+
+/// Remove a member from a project and revoke its credentials
+#[derive(Clone, Debug, Args)]
+pub struct DeleteCommand {
+    /// Identifier of the member
+    member: IdentityIdentifier,
+}
+
+impl DeleteCommand {
+    pub fn run(self, opts: CommandGlobalOpts, authority_opts: AuthorityOpts) {
+        node_rpc(run_impl, (opts, authority_opts, self));
+    }
+}
+
+async fn run_impl(
+    ctx: Context,
+    (opts, authority_opts, cmd): (CommandGlobalOpts, AuthorityOpts, DeleteCommand),
+) -> Result<()> {
+    let (node_name, client) = authenticator_client(&ctx, &opts, &authority_opts).await?;
+    let result = client.delete_member(cmd.member.clone()).await;
+    delete_embedded_node(&opts, &node_name).await;
+    result?;
+
+    opts.terminal
+        .stdout()
+        .plain(format!("Member {} removed", cmd.member))
+        .machine(&cmd.member)
+        .json(serde_json::json!({ "member": { "identifier": &cmd.member } }))
+        .write_line()?;
+    Ok(())
+}
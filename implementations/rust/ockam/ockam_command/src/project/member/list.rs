@@ -0,0 +1,34 @@
+use clap::Args;
+
+use ockam::Context;
+
+use crate::node::util::delete_embedded_node;
+use crate::project::member::{authenticator_client, AuthorityOpts, Member};
+use crate::util::{node_rpc, println_output};
+use crate::{CommandGlobalOpts, Result};
+
+/// List the members of a project
+#[derive(Clone, Debug, Args)]
+pub struct ListCommand {}
+
+impl ListCommand {
+    pub fn run(self, opts: CommandGlobalOpts, authority_opts: AuthorityOpts) {
+        node_rpc(run_impl, (opts, authority_opts));
+    }
+}
+
+async fn run_impl(
+    ctx: Context,
+    (opts, authority_opts): (CommandGlobalOpts, AuthorityOpts),
+) -> Result<()> {
+    let (node_name, client) = authenticator_client(&ctx, &opts, &authority_opts).await?;
+    let members: Vec<Member> = client
+        .list_members()
+        .await?
+        .iter()
+        .map(|(identifier, entry)| Member::new(identifier, entry))
+        .collect();
+    println_output(members, &opts.global_args.output_format)?;
+    delete_embedded_node(&opts, &node_name).await;
+    Ok(())
+}
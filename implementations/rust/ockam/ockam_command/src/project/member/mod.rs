@@ -0,0 +1,168 @@
+mod delete;
+mod list;
+mod show;
+mod update;
+
+use core::fmt::Write;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use ockam::identity::{AttributesEntry, IdentityIdentifier};
+use ockam::Context;
+use ockam_api::authenticator::direct::DirectAuthenticatorClient;
+use ockam_api::cloud::ORCHESTRATOR_RESTART_TIMEOUT;
+use ockam_api::DefaultAddress;
+use ockam_multiaddr::MultiAddr;
+use ockam_node::RpcClient;
+
+use crate::node::util::start_embedded_node;
+use crate::project::member::delete::DeleteCommand;
+use crate::project::member::list::ListCommand;
+use crate::project::member::show::ShowCommand;
+use crate::project::member::update::UpdateCommand;
+use crate::project::util::{authority_service_route, create_secure_channel_to_project_authority};
+use crate::util::api::{CloudOpts, TrustContextOpts};
+use crate::util::output::{human_readable_time, Output};
+use crate::{docs, CommandGlobalOpts, Result};
+
+const LONG_ABOUT: &str = include_str!("../static/member/long_about.txt");
+const AFTER_LONG_HELP: &str = include_str!("../static/member/after_long_help.txt");
+
+/// Manage the members of a project as an authorised enroller
+#[derive(Clone, Debug, Args)]
+#[command(
+    arg_required_else_help = true,
+    subcommand_required = true,
+    long_about = docs::about(LONG_ABOUT),
+    after_long_help = docs::after_help(AFTER_LONG_HELP)
+)]
+pub struct MemberCommand {
+    #[command(subcommand)]
+    subcommand: MemberSubcommand,
+
+    #[command(flatten)]
+    authority_opts: AuthorityOpts,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum MemberSubcommand {
+    List(ListCommand),
+    Show(ShowCommand),
+    Update(UpdateCommand),
+    Delete(DeleteCommand),
+}
+
+impl MemberCommand {
+    pub fn run(self, opts: CommandGlobalOpts) {
+        match self.subcommand {
+            MemberSubcommand::List(c) => c.run(opts, self.authority_opts),
+            MemberSubcommand::Show(c) => c.run(opts, self.authority_opts),
+            MemberSubcommand::Update(c) => c.run(opts, self.authority_opts),
+            MemberSubcommand::Delete(c) => c.run(opts, self.authority_opts),
+        }
+    }
+}
+
+/// Options used to reach the authority of a project
+#[derive(Clone, Debug, Args)]
+pub struct AuthorityOpts {
+    #[command(flatten)]
+    cloud_opts: CloudOpts,
+
+    #[command(flatten)]
+    trust_opts: TrustContextOpts,
+
+    /// Address of the project
+    #[arg(global = true, long, short, default_value = "/project/default")]
+    to: MultiAddr,
+}
+
+/// Start an embedded node and return its name, together with a client for the
+/// direct authenticator service of the project authority
+async fn authenticator_client(
+    ctx: &Context,
+    opts: &CommandGlobalOpts,
+    authority_opts: &AuthorityOpts,
+) -> Result<(String, DirectAuthenticatorClient)> {
+    let node_name = start_embedded_node(ctx, opts, Some(&authority_opts.trust_opts)).await?;
+    let (base_addr, _, _) = create_secure_channel_to_project_authority(
+        ctx,
+        opts,
+        &node_name,
+        &authority_opts.cloud_opts,
+        &authority_opts.trust_opts,
+        &authority_opts.to,
+    )
+    .await?;
+    let client = DirectAuthenticatorClient::new(
+        RpcClient::new(
+            authority_service_route(&base_addr, DefaultAddress::DIRECT_AUTHENTICATOR)?,
+            ctx,
+        )
+        .await?
+        .with_timeout(Duration::from_secs(ORCHESTRATOR_RESTART_TIMEOUT)),
+    );
+    Ok((node_name, client))
+}
+
+/// A project member, as displayed by the member commands
+#[derive(Serialize)]
+struct Member {
+    identifier: String,
+    attributes: BTreeMap<String, String>,
+    added: u64,
+    expires: Option<u64>,
+    attested_by: Option<String>,
+}
+
+impl Member {
+    fn new(identifier: &IdentityIdentifier, entry: &AttributesEntry) -> Self {
+        Self {
+            identifier: identifier.to_string(),
+            attributes: entry
+                .attrs()
+                .iter()
+                .map(|(k, v)| (k.clone(), String::from_utf8_lossy(v).to_string()))
+                .collect(),
+            added: entry.added().unix_time(),
+            expires: entry.expires().map(|t| t.unix_time()),
+            attested_by: entry.attested_by().map(|i| i.to_string()),
+        }
+    }
+}
+
+impl Output for Member {
+    fn output(&self) -> Result<String> {
+        let mut w = String::new();
+        write!(w, "Member:")?;
+        write!(w, "\n  Identifier: {}", self.identifier)?;
+        write!(w, "\n  Attributes:")?;
+        for (k, v) in &self.attributes {
+            write!(w, "\n    {k}: {v}")?;
+        }
+        write!(w, "\n  Added: {}", human_readable_time(self.added))?;
+        if let Some(expires) = self.expires {
+            write!(w, "\n  Expires: {}", human_readable_time(expires))?;
+        }
+        if let Some(attested_by) = &self.attested_by {
+            write!(w, "\n  Attested by: {attested_by}")?;
+        }
+        Ok(w)
+    }
+}
+
+impl Output for Vec<Member> {
+    fn output(&self) -> Result<String> {
+        if self.is_empty() {
+            return Ok("No members found".to_string());
+        }
+        let members = self
+            .iter()
+            .map(|member| member.output())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(members.join("\n"))
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::anyhow;
+use clap::Args;
+
+use ockam::identity::IdentityIdentifier;
+use ockam::Context;
+
+use crate::node::util::delete_embedded_node;
+use crate::project::member::{authenticator_client, AuthorityOpts, Member};
+use crate::util::{node_rpc, println_output};
+use crate::{CommandGlobalOpts, Result};
+
+/// Show the attributes of a project member
+#[derive(Clone, Debug, Args)]
+pub struct ShowCommand {
+    /// Identifier of the member
+    member: IdentityIdentifier,
+}
+
+impl ShowCommand {
+    pub fn run(self, opts: CommandGlobalOpts, authority_opts: AuthorityOpts) {
+        node_rpc(run_impl, (opts, authority_opts, self));
+    }
+}
+
+async fn run_impl(
+    ctx: Context,
+    (opts, authority_opts, cmd): (CommandGlobalOpts, AuthorityOpts, ShowCommand),
+) -> Result<()> {
+    let (node_name, client) = authenticator_client(&ctx, &opts, &authority_opts).await?;
+    let entry = client.get_member(cmd.member.clone()).await;
+    delete_embedded_node(&opts, &node_name).await;
+    match entry? {
+        Some(entry) => {
+            println_output(
+                Member::new(&cmd.member, &entry),
+                &opts.global_args.output_format,
+            )?;
+            Ok(())
+        }
+        None => Err(anyhow!("{} is not a member of the project", cmd.member).into()),
+    }
+}
@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use clap::Args;
+
+use ockam::identity::IdentityIdentifier;
+use ockam::Context;
+
+use crate::node::util::delete_embedded_node;
+use crate::project::member::{authenticator_client, AuthorityOpts};
+use crate::project::util::parse_attributes;
+use crate::util::node_rpc;
+use crate::util::parsers::duration_parser;
+use crate::{CommandGlobalOpts, Result};
+
+/// Replace the attributes of a project member
+#[derive(Clone, Debug, Args)]
+pub struct UpdateCommand {
+    /// Identifier of the member
+    member: IdentityIdentifier,
+
+    /// Attributes in `key=value` format to be attached to the member
+    #[arg(short, long = "attribute", value_name = "ATTRIBUTE")]
+    attributes: Vec<String>,
+
+    /// Remove the member after this duration, for example `30m`, `12h` or `7d`.
+    /// The membership does not expire if not specified
+    #[arg(long, value_name = "DURATION", value_parser = duration_parser)]
+    member_ttl: Option<Duration>,
+}
+
+impl UpdateCommand {
+    pub fn run(self, opts: CommandGlobalOpts, authority_opts: AuthorityOpts) {
+        node_rpc(run_impl, (opts, authority_opts, self));
+    }
+}
+
+async fn run_impl(
+    ctx: Context,
+    (opts, authority_opts, cmd): (CommandGlobalOpts, AuthorityOpts, UpdateCommand),
+) -> Result<()> {
+    let (node_name, client) = authenticator_client(&ctx, &opts, &authority_opts).await?;
+    let result = client
+        .update_member(
+            cmd.member.clone(),
+            parse_attributes(&cmd.attributes)?,
+            cmd.member_ttl,
+        )
+        .await;
+    delete_embedded_node(&opts, &node_name).await;
+    result?;
+
+    opts.terminal
+        .stdout()
+        .plain(format!("Member {} updated", cmd.member))
+        .machine(&cmd.member)
+        .json(serde_json::json!({ "member": { "identifier": &cmd.member } }))
+        .write_line()?;
+    Ok(())
+}
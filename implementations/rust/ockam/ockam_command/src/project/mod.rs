@@ -4,6 +4,7 @@ mod create;
 mod delete;
 mod info;
 mod list;
+mod member;
 mod show;
 mod ticket;
 pub mod util;
@@ -19,6 +20,7 @@ pub use create::CreateCommand;
 pub use delete::DeleteCommand;
 pub use info::InfoCommand;
 pub use list::ListCommand;
+pub use member::MemberCommand;
 pub use show::ShowCommand;
 pub use ticket::TicketCommand;
 
@@ -48,6 +50,7 @@ pub enum ProjectSubcommand {
     Show(ShowCommand),
     Information(InfoCommand),
     Ticket(TicketCommand),
+    Member(MemberCommand),
     Addon(AddonCommand),
     Authenticate(AuthenticateCommand),
 }
@@ -60,6 +63,7 @@ impl ProjectCommand {
             ProjectSubcommand::List(c) => c.run(options),
             ProjectSubcommand::Show(c) => c.run(options),
             ProjectSubcommand::Ticket(c) => c.run(options),
+            ProjectSubcommand::Member(c) => c.run(options),
             ProjectSubcommand::Information(c) => c.run(options),
             ProjectSubcommand::Addon(c) => c.run(options),
             ProjectSubcommand::Authenticate(c) => c.run(options),
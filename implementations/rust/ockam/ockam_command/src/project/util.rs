@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use anyhow::{anyhow, Context as _};
@@ -10,13 +11,15 @@ use tracing::debug;
 use ockam::identity::IdentityIdentifier;
 use ockam::TcpTransport;
 use ockam_api::cloud::project::Project;
-use ockam_api::config::lookup::{LookupMeta, ProjectAuthority, ProjectLookup};
-use ockam_api::multiaddr_to_addr;
+use ockam_api::config::cli::{CredentialRetrieverConfig, TrustContextConfig};
+use ockam_api::config::lookup::{ConfigLookup, LookupMeta, ProjectAuthority, ProjectLookup};
 use ockam_api::nodes::models::{self, secure_channel::*};
+use ockam_api::{local_multiaddr_to_route, multiaddr_to_addr, DefaultAddress};
 use ockam_core::flow_control::FlowControlId;
-use ockam_multiaddr::{MultiAddr, Protocol};
+use ockam_core::{route, Route};
+use ockam_multiaddr::{proto, MultiAddr, Protocol};
 
-use crate::util::api::CloudOpts;
+use crate::util::api::{CloudOpts, TrustContextOpts};
 use crate::util::{api, RpcBuilder};
 use crate::{CommandGlobalOpts, OckamConfig, Result};
 
@@ -154,6 +157,102 @@ pub async fn create_secure_channel_to_authority(
     Ok((addr, res.flow_control_id()))
 }
 
+/// Create a secure channel to the authority of a trust context if one is given, or to
+/// the authority of the project targeted by `to`.
+///
+/// Return the address of the secure channel, or `to` itself if no authority could be found,
+/// together with the project and the trust context of the authority.
+pub async fn create_secure_channel_to_project_authority(
+    ctx: &ockam::Context,
+    opts: &CommandGlobalOpts,
+    node_name: &str,
+    cloud_opts: &CloudOpts,
+    trust_opts: &TrustContextOpts,
+    to: &MultiAddr,
+) -> crate::Result<(MultiAddr, Option<ProjectLookup>, Option<TrustContextConfig>)> {
+    if let Some(tc) = trust_opts.trust_context.as_ref() {
+        let addr = match tc.authority()?.own_credential()? {
+            CredentialRetrieverConfig::FromCredentialIssuer(c) => &c.multiaddr,
+            _ => {
+                return Err(
+                    anyhow!("Trust context must be configured with a credential issuer").into(),
+                );
+            }
+        };
+        let (sc_addr, _) = create_secure_channel_to_authority(
+            ctx,
+            opts,
+            node_name,
+            tc.authority()?.identity().await?.identifier().clone(),
+            addr,
+            Some(cloud_opts.identity.clone()),
+        )
+        .await?;
+        Ok((sc_addr, None, Some(tc.clone())))
+    } else if let (Some(p), Some(a)) = get_project(to, &opts.config.lookup())? {
+        let (sc_addr, _) = create_secure_channel_to_authority(
+            ctx,
+            opts,
+            node_name,
+            a.identity_id().clone(),
+            a.address(),
+            Some(cloud_opts.identity.clone()),
+        )
+        .await?;
+        Ok((sc_addr, Some(p), None))
+    } else {
+        Ok((to.clone(), None, None))
+    }
+}
+
+/// Return the route to a service of an authority, reached with a secure channel
+pub fn authority_service_route(secure_channel: &MultiAddr, service: &str) -> crate::Result<Route> {
+    let service = MultiAddr::try_from(format!("/service/{service}").as_str())?;
+    let mut addr = secure_channel.clone();
+    for proto in service.iter() {
+        addr.push_back_value(&proto)?;
+    }
+    let service_route =
+        local_multiaddr_to_route(&addr).context(format!("Invalid MultiAddr {addr}"))?;
+    Ok(route![DefaultAddress::RPC_PROXY, service_route])
+}
+
+/// Get the project authority from the first address protocol.
+///
+/// If the first protocol is a `/project`, look up the project's config.
+fn get_project(
+    input: &MultiAddr,
+    map: &ConfigLookup,
+) -> crate::Result<(Option<ProjectLookup>, Option<ProjectAuthority>)> {
+    if let Some(proto) = input.first() {
+        if proto.code() == proto::Project::CODE {
+            let proj = proto.cast::<proto::Project>().expect("project protocol");
+            if let Some(p) = map.get_project(&proj) {
+                if let Some(a) = &p.authority {
+                    return Ok((Some(p.clone()), Some(a.clone())));
+                } else {
+                    return Err(anyhow!("missing authority in project {:?}", &*proj).into());
+                }
+            } else {
+                return Err(anyhow!("unknown project {}", &*proj).into());
+            }
+        }
+    }
+    Ok((None, None))
+}
+
+/// Parse a list of attributes in the `key=value` format
+pub fn parse_attributes(attributes: &[String]) -> crate::Result<HashMap<&str, &str>> {
+    let mut parsed = HashMap::new();
+    for attr in attributes {
+        let mut parts = attr.splitn(2, '=');
+        let key = parts.next().context("key expected")?;
+        let value = parts.next().context("value expected)")?;
+        parsed.insert(key, value);
+    }
+    Ok(parsed)
+}
+
 async fn delete_secure_channel<'a>(
     ctx: &ockam::Context,
     opts: &CommandGlobalOpts,
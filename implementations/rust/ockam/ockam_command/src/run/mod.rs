@@ -1,18 +1,41 @@
 mod parser;
+mod plan;
+mod state;
 
+use crate::run::parser::ConfigRunner;
+use crate::run::plan::Plan;
+use crate::run::state::CurrentState;
 use crate::util::node_rpc;
-use crate::{CommandGlobalOpts, Result};
+use crate::{fmt_err, fmt_info, fmt_log, fmt_ok, CommandGlobalOpts, Result};
 use anyhow::{anyhow, Context as _};
 use clap::Args;
+use colorful::Colorful;
 use ockam::Context;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often the configuration file is checked for changes in watch mode
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Create nodes given a declarative configuration file
+///
+/// The nodes declared in the configuration file are reconciled with the
+/// running ones: missing nodes are created, stopped nodes are started and
+/// their inlets, outlets, relays and policies are created, updated or
+/// deleted to match the configuration.
 #[derive(Clone, Debug, Args)]
 pub struct RunCommand {
     /// Path to the configuration file
     #[arg(long)]
     pub config_path: Option<PathBuf>,
+
+    /// Only show the changes which would be applied
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Keep running and apply the configuration again whenever the file changes
+    #[arg(long, conflicts_with = "dry_run")]
+    pub watch: bool,
 }
 
 impl RunCommand {
@@ -25,7 +48,7 @@ async fn rpc(ctx: Context, (opts, cmd): (CommandGlobalOpts, RunCommand)) -> Resu
     run_impl(&ctx, opts, cmd).await
 }
 
-async fn run_impl(_ctx: &Context, opts: CommandGlobalOpts, cmd: RunCommand) -> Result<()> {
+async fn run_impl(ctx: &Context, opts: CommandGlobalOpts, cmd: RunCommand) -> Result<()> {
     let path = match cmd.config_path {
         Some(path) => path,
         None => {
@@ -50,6 +73,76 @@ async fn run_impl(_ctx: &Context, opts: CommandGlobalOpts, cmd: RunCommand) -> R
             path
         }
     };
-    parser::ConfigRunner::go(&opts.state, &path)?;
+    if !cmd.watch {
+        return reconcile(ctx, &opts, &path, cmd.dry_run).await;
+    }
+
+    let mut last_modified: Option<SystemTime> = None;
+    loop {
+        let modified = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .context("Failed to read the configuration file")?;
+        if last_modified != Some(modified) {
+            last_modified = Some(modified);
+            // In watch mode a failure is reported and the next change applied again
+            if let Err(e) = reconcile(ctx, &opts, &path, false).await {
+                opts.terminal
+                    .write_line(&fmt_err!("Failed to apply the configuration: {e}"))?;
+            }
+            opts.terminal
+                .write_line(&fmt_info!("Watching {} for changes", path.display()))?;
+        }
+        ctx.sleep(WATCH_INTERVAL).await;
+    }
+}
+
+/// Compute the changes needed to match the configuration file and apply them
+async fn reconcile(
+    ctx: &Context,
+    opts: &CommandGlobalOpts,
+    path: &Path,
+    dry_run: bool,
+) -> Result<()> {
+    let config = ConfigRunner::parse_file(path)?;
+    let names: Vec<&str> = config
+        .nodes()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    let current = CurrentState::fetch(ctx, opts, names.clone()).await?;
+    let mut plan = Plan::compute(config.nodes(), &current)?;
+
+    if plan.is_empty() {
+        opts.terminal
+            .write_line(&fmt_ok!("Nothing to do, the nodes match the configuration"))?;
+        return Ok(());
+    }
+    for action in &plan.actions {
+        opts.terminal.write_line(&fmt_log!("{action}"))?;
+    }
+    if dry_run {
+        if plan.starts_nodes() {
+            opts.terminal.write_line(&fmt_info!(
+                "The resources of the stopped nodes are compared with the configuration once they are started"
+            ))?;
+        }
+        return Ok(());
+    }
+
+    // Stopped nodes restore their resources when they start, so the changes to
+    // those resources are planned from the state of the nodes once they are started
+    let starts_nodes = plan.starts_nodes();
+    plan.apply()?;
+    if starts_nodes {
+        let current = CurrentState::fetch(ctx, opts, names.clone()).await?;
+        plan = Plan::compute(config.nodes(), &current)?;
+        for action in &plan.actions {
+            opts.terminal.write_line(&fmt_log!("{action}"))?;
+        }
+        plan.apply()?;
+    }
+
+    opts.terminal
+        .write_line(&fmt_ok!("The nodes match the configuration"))?;
     Ok(())
 }
@@ -0,0 +1,512 @@
+use crate::run::parser::{binary_path, NodeConfig};
+use crate::run::state::{CurrentState, InletState, NodeResources, NodeStatus, OutletState};
+use crate::Result;
+use anyhow::anyhow;
+use ockam_abac::Expr;
+use ockam_api::resources;
+use ockam_multiaddr::MultiAddr;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::net::SocketAddr;
+use tracing::debug;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Create,
+    Start,
+    Update,
+    Delete,
+}
+
+/// A change to apply to a node or one of its resources, by running
+/// one or more commands
+#[derive(Debug)]
+pub struct PlannedAction {
+    pub change: Change,
+    pub id: String,
+    commands: Vec<Vec<String>>,
+}
+
+impl Display for PlannedAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let change = match self.change {
+            Change::Create => "+ create",
+            Change::Start => "> start",
+            Change::Update => "~ update",
+            Change::Delete => "- delete",
+        };
+        write!(f, "{change} {}", self.id)
+    }
+}
+
+/// The list of changes bringing the current nodes in line with a configuration
+///
+/// The configuration is authoritative for the nodes it declares: inlets,
+/// outlets, relays and policies of those nodes which are not in the
+/// configuration are deleted. Nodes which are not declared are left untouched.
+#[derive(Debug, Default)]
+pub struct Plan {
+    pub actions: Vec<PlannedAction>,
+}
+
+impl Plan {
+    pub fn compute(nodes: &[(String, NodeConfig)], current: &CurrentState) -> Result<Self> {
+        let mut plan = Plan::default();
+        let empty = NodeResources::default();
+        for (name, node) in nodes {
+            let node_resources = match current.node(name) {
+                NodeStatus::Missing => {
+                    let mut args = vec!["node", "create", name.as_str()];
+                    if let Some(enrollment_token) = &node.enrollment_token {
+                        args.push("--enrollment-token");
+                        args.push(enrollment_token.as_str());
+                    }
+                    plan.push(Change::Create, format!("node/{name}"), vec![args]);
+                    &empty
+                }
+                // A node restores its resources when it starts, their changes
+                // can only be planned once the node is running again
+                NodeStatus::Stopped => {
+                    plan.push(
+                        Change::Start,
+                        format!("node/{name}"),
+                        vec![vec!["node", "start", name.as_str()]],
+                    );
+                    continue;
+                }
+                NodeStatus::Running(resources) => resources,
+            };
+            plan.compute_node(name, node, node_resources, current)?;
+        }
+        Ok(plan)
+    }
+
+    fn compute_node(
+        &mut self,
+        node_name: &str,
+        node: &NodeConfig,
+        current: &NodeResources,
+        state: &CurrentState,
+    ) -> Result<()> {
+        // TODO: all commands should support both `/node/{name}` and `{name}` formats.
+        let at = format!("/node/{node_name}");
+        let at = at.as_str();
+        let inlets = node.tcp_inlets.iter().flatten().collect::<BTreeMap<_, _>>();
+        let outlets = node
+            .tcp_outlets
+            .iter()
+            .flatten()
+            .collect::<BTreeMap<_, _>>();
+        let relays = node.forwarders.iter().flatten().collect::<BTreeMap<_, _>>();
+
+        // Deletions come first, so that the resources they hold (e.g. a bound
+        // address) can be re-used by the created ones
+        for alias in current.inlets.keys().filter(|a| !inlets.contains_key(a)) {
+            let delete = vec!["tcp-inlet", "delete", alias.as_str(), "--node", at];
+            self.push(
+                Change::Delete,
+                format!("inlet/{node_name}/{alias}"),
+                vec![delete],
+            );
+        }
+        for alias in current.outlets.keys().filter(|a| !outlets.contains_key(a)) {
+            let delete = vec!["tcp-outlet", "delete", alias.as_str(), "--node", at];
+            self.push(
+                Change::Delete,
+                format!("outlet/{node_name}/{alias}"),
+                vec![delete],
+            );
+        }
+        for name in current.relays.keys().filter(|n| !relays.contains_key(n)) {
+            let delete = vec!["relay", "delete", name.as_str(), "--at", at];
+            self.push(
+                Change::Delete,
+                format!("relay/{node_name}/{name}"),
+                vec![delete],
+            );
+        }
+
+        // Policies are set for all the inlets, or all the outlets, of a node
+        let inlets_policy = single_policy(
+            node_name,
+            "inlets",
+            inlets.values().map(|i| &i.access_control),
+        )?;
+        let outlets_policy = single_policy(
+            node_name,
+            "outlets",
+            outlets.values().map(|o| &o.access_control),
+        )?;
+        for (resource, desired) in [
+            (resources::INLET.to_string(), inlets_policy),
+            (resources::OUTLET.to_string(), outlets_policy),
+        ] {
+            let id = format!("policy/{node_name}/{resource}");
+            match (current.policies.get(&resource), desired) {
+                (Some(_), None) => {
+                    let delete = vec![
+                        "policy",
+                        "delete",
+                        "--at",
+                        at,
+                        "--resource",
+                        resource.as_str(),
+                        "--action",
+                        "handle_message",
+                    ];
+                    self.push(Change::Delete, id, vec![delete]);
+                }
+                (current_expression, Some(expression)) => {
+                    let change = match current_expression {
+                        None => Change::Create,
+                        Some(e) if e != &expression => Change::Update,
+                        Some(_) => continue,
+                    };
+                    let create = vec![
+                        "policy",
+                        "create",
+                        "--at",
+                        at,
+                        "--resource",
+                        resource.as_str(),
+                        "--expression",
+                        expression.as_str(),
+                    ];
+                    self.push(change, id, vec![create]);
+                }
+                (None, None) => {}
+            }
+        }
+
+        for (alias, outlet) in outlets {
+            let create = vec![
+                "tcp-outlet",
+                "create",
+                "--at",
+                at,
+                "--from",
+                outlet.from.as_str(),
+                "--to",
+                outlet.to.as_str(),
+                "--alias",
+                alias.as_str(),
+            ];
+            let id = format!("outlet/{node_name}/{alias}");
+            match current.outlets.get(alias) {
+                None => self.push(Change::Create, id, vec![create]),
+                Some(OutletState { from, to })
+                    if !same_address(&outlet.from, from) || !same_address(&outlet.to, to) =>
+                {
+                    let delete = vec!["tcp-outlet", "delete", alias.as_str(), "--node", at];
+                    self.push(Change::Update, id, vec![delete, create]);
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (alias, inlet) in inlets {
+            let create = vec![
+                "tcp-inlet",
+                "create",
+                "--at",
+                at,
+                "--from",
+                inlet.from.as_str(),
+                "--to",
+                inlet.to.as_str(),
+                "--alias",
+                alias.as_str(),
+            ];
+            let id = format!("inlet/{node_name}/{alias}");
+            match current.inlets.get(alias) {
+                None => self.push(Change::Create, id, vec![create]),
+                Some(InletState { from, to })
+                    if !same_address(&inlet.from, from)
+                        || !same_route(state.resolve(&inlet.to), to) =>
+                {
+                    let delete = vec!["tcp-inlet", "delete", alias.as_str(), "--node", at];
+                    self.push(Change::Update, id, vec![delete, create]);
+                }
+                Some(_) => {}
+            }
+        }
+
+        for (name, relay) in relays {
+            let create = vec![
+                "relay",
+                "create",
+                name.as_str(),
+                "--to",
+                at,
+                "--at",
+                relay.at.as_str(),
+            ];
+            let id = format!("relay/{node_name}/{name}");
+            // A name without any `/` is the name of a node, as for `relay create --at`
+            let relay_at = match relay.at.contains('/') {
+                true => relay.at.clone(),
+                false => format!("/node/{}", relay.at),
+            };
+            match current.relays.get(name) {
+                None => self.push(Change::Create, id, vec![create]),
+                Some(current_at) if !same_route(state.resolve(&relay_at), current_at) => {
+                    let delete = vec!["relay", "delete", name.as_str(), "--at", at];
+                    self.push(Change::Update, id, vec![delete, create]);
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, change: Change, id: String, commands: Vec<Vec<&str>>) {
+        let commands = commands
+            .into_iter()
+            .map(|args| args.into_iter().map(String::from).collect())
+            .collect();
+        self.actions.push(PlannedAction {
+            change,
+            id,
+            commands,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Return true if some stopped nodes are started by this plan
+    pub fn starts_nodes(&self) -> bool {
+        self.actions.iter().any(|a| a.change == Change::Start)
+    }
+
+    /// Run the commands of each action, stopping at the first failure
+    pub fn apply(self) -> Result<()> {
+        for action in self.actions {
+            for args in &action.commands {
+                debug!("Running command: {} {}", binary_path(), args.join(" "));
+                // If a command fails it will show the appropriate error in its subshell.
+                if duct::cmd(binary_path(), args).run().is_err() {
+                    return Err(anyhow!("Failed to apply {}", action.id).into());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return the normalized expression shared by all the inlets, or outlets, of a node
+fn single_policy<'a>(
+    node_name: &str,
+    kind: &str,
+    access_controls: impl Iterator<Item = &'a Option<String>>,
+) -> Result<Option<String>> {
+    let mut policy: Option<String> = None;
+    for access_control in access_controls.flatten() {
+        let expression = Expr::try_from(access_control.as_str())
+            .map_err(|e| anyhow!("Invalid access control '{access_control}': {e}"))?
+            .to_string();
+        match &policy {
+            Some(p) if p != &expression => {
+                return Err(anyhow!(
+                    "The {kind} of node {node_name} must all have the same access control"
+                )
+                .into())
+            }
+            _ => policy = Some(expression),
+        }
+    }
+    Ok(policy)
+}
+
+/// Compare socket addresses by value, and other addresses as written
+fn same_address(configured: &str, current: &str) -> bool {
+    match (
+        configured.parse::<SocketAddr>(),
+        current.parse::<SocketAddr>(),
+    ) {
+        (Ok(configured), Ok(current)) => configured == current,
+        _ => configured.trim_start_matches("/service/") == current,
+    }
+}
+
+/// Compare a configured route, resolved as it is when the resource is created, with
+/// the route of the resource. A route which isn't recorded by the node can't be compared
+fn same_route(configured: Option<MultiAddr>, current: &Option<MultiAddr>) -> bool {
+    match current {
+        Some(current) => configured.as_ref() == Some(current),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn node_config(config: &str) -> NodeConfig {
+        serde_yaml::from_str(config).unwrap()
+    }
+
+    fn ids(plan: &Plan) -> Vec<String> {
+        plan.actions.iter().map(|a| a.to_string()).collect()
+    }
+
+    const CONFIG: &str = r#"
+        tcp-outlets:
+          db:
+            from: /service/outlet
+            to: '127.0.0.1:5432'
+            access_control: '(= subject.component "app")'
+        forwarders:
+          db:
+            at: /project/default
+    "#;
+
+    #[test]
+    fn create_missing_node_and_resources() {
+        let nodes = vec![("n1".to_string(), node_config(CONFIG))];
+        let plan = Plan::compute(&nodes, &CurrentState::default()).unwrap();
+        assert_eq!(
+            ids(&plan),
+            vec![
+                "+ create node/n1",
+                "+ create policy/n1/tcp-outlet",
+                "+ create outlet/n1/db",
+                "+ create relay/n1/db",
+            ]
+        );
+    }
+
+    #[test]
+    fn nothing_to_do_when_the_node_matches() {
+        let nodes = vec![("n1".to_string(), node_config(CONFIG))];
+        let mut current = CurrentState::default();
+        let mut resources = NodeResources::default();
+        resources.outlets.insert(
+            "db".to_string(),
+            OutletState {
+                from: "outlet".to_string(),
+                to: "127.0.0.1:5432".to_string(),
+            },
+        );
+        resources.relays.insert(
+            "db".to_string(),
+            Some(MultiAddr::from_str("/project/default").unwrap()),
+        );
+        resources.policies.insert(
+            "tcp-outlet".to_string(),
+            Expr::try_from(r#"(= subject.component "app")"#)
+                .unwrap()
+                .to_string(),
+        );
+        current.insert("n1", NodeStatus::Running(resources));
+
+        let plan = Plan::compute(&nodes, &current).unwrap();
+        assert!(plan.is_empty(), "{:?}", ids(&plan));
+    }
+
+    #[test]
+    fn update_and_delete_changed_resources() {
+        let nodes = vec![("n1".to_string(), node_config(CONFIG))];
+        let mut current = CurrentState::default();
+        let mut resources = NodeResources::default();
+        resources.inlets.insert(
+            "web".to_string(),
+            InletState {
+                from: "127.0.0.1:8080".to_string(),
+                to: Some(MultiAddr::from_str("/service/outlet").unwrap()),
+            },
+        );
+        resources.outlets.insert(
+            "db".to_string(),
+            OutletState {
+                from: "outlet".to_string(),
+                to: "127.0.0.1:5433".to_string(),
+            },
+        );
+        resources.relays.insert(
+            "db".to_string(),
+            Some(MultiAddr::from_str("/project/default").unwrap()),
+        );
+        resources
+            .policies
+            .insert("tcp-inlet".to_string(), "true".to_string());
+        current.insert("n1", NodeStatus::Running(resources));
+
+        let plan = Plan::compute(&nodes, &current).unwrap();
+        assert_eq!(
+            ids(&plan),
+            vec![
+                "- delete inlet/n1/web",
+                "- delete policy/n1/tcp-inlet",
+                "+ create policy/n1/tcp-outlet",
+                "~ update outlet/n1/db",
+            ]
+        );
+    }
+
+    #[test]
+    fn update_resources_with_a_changed_route() {
+        let config = r#"
+            tcp-inlets:
+              web:
+                from: '127.0.0.1:8080'
+                to: /project/default/service/forward_to_web/secure/api/service/outlet
+            forwarders:
+              db:
+                at: /project/other
+        "#;
+        let nodes = vec![("n1".to_string(), node_config(config))];
+        let mut current = CurrentState::default();
+        let mut resources = NodeResources::default();
+        resources.inlets.insert(
+            "web".to_string(),
+            InletState {
+                from: "127.0.0.1:8080".to_string(),
+                to: Some(MultiAddr::from_str("/project/default/service/outlet").unwrap()),
+            },
+        );
+        resources.relays.insert(
+            "db".to_string(),
+            Some(MultiAddr::from_str("/project/default").unwrap()),
+        );
+        current.insert("n1", NodeStatus::Running(resources));
+
+        let plan = Plan::compute(&nodes, &current).unwrap();
+        assert_eq!(
+            ids(&plan),
+            vec!["~ update inlet/n1/web", "~ update relay/n1/db"]
+        );
+    }
+
+    #[test]
+    fn start_stopped_node() {
+        let nodes = vec![("n1".to_string(), node_config(CONFIG))];
+        let mut current = CurrentState::default();
+        current.insert("n1", NodeStatus::Stopped);
+
+        // the resources restored by the node are only known once it is started
+        let plan = Plan::compute(&nodes, &current).unwrap();
+        assert_eq!(ids(&plan), vec!["> start node/n1"]);
+        assert!(plan.starts_nodes());
+    }
+
+    #[test]
+    fn reject_conflicting_access_controls() {
+        let config = r#"
+            tcp-inlets:
+              a:
+                from: '127.0.0.1:8080'
+                to: /service/outlet
+                access_control: '(= subject.component "a")'
+              b:
+                from: '127.0.0.1:8081'
+                to: /service/outlet
+                access_control: '(= subject.component "b")'
+        "#;
+        let nodes = vec![("n1".to_string(), node_config(config))];
+        assert!(Plan::compute(&nodes, &CurrentState::default()).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::util::Rpc;
+use crate::{CommandGlobalOpts, Result};
+use ockam::Context;
+use ockam_abac::Resource;
+use ockam_api::cli_state::{NodeResourceKind, StateDirTrait, StateItemTrait};
+use ockam_api::nodes::models::forwarder::{CreateForwarder, ForwarderInfo};
+use ockam_api::nodes::models::policy::PolicyList;
+use ockam_api::nodes::models::portal::{CreateInlet, InletList, OutletList};
+use ockam_api::RELAY_ADDRESS_PREFIX;
+use ockam_api::{actions, resources};
+use ockam_core::api::Request;
+use ockam_core::Address;
+use ockam_multiaddr::proto::Node;
+use ockam_multiaddr::{Code, MultiAddr, Protocol};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Resources of a node which are managed by `ockam run`
+#[derive(Debug, Default)]
+pub struct NodeResources {
+    pub inlets: BTreeMap<String, InletState>,
+    pub outlets: BTreeMap<String, OutletState>,
+    /// Route at which the relay was created, if it is recorded by the node, by relay name
+    pub relays: BTreeMap<String, Option<MultiAddr>>,
+    /// Expression of the `handle_message` policy, by resource
+    pub policies: BTreeMap<String, String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct InletState {
+    /// Bind address of the inlet
+    pub from: String,
+    /// Route to the outlet, if it is recorded by the node
+    pub to: Option<MultiAddr>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct OutletState {
+    /// Worker address of the outlet
+    pub from: String,
+    /// Address of the TCP service
+    pub to: String,
+}
+
+#[derive(Debug)]
+pub enum NodeStatus {
+    Missing,
+    Stopped,
+    Running(NodeResources),
+}
+
+/// Nodes and resources as currently known by the `CliState` and the nodes' APIs
+#[derive(Debug, Default)]
+pub struct CurrentState {
+    nodes: BTreeMap<String, NodeStatus>,
+    /// Address of the default TCP listener, by node name
+    node_addresses: BTreeMap<String, MultiAddr>,
+}
+
+impl CurrentState {
+    pub async fn fetch<'a>(
+        ctx: &Context,
+        opts: &CommandGlobalOpts,
+        node_names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Self> {
+        let mut nodes = BTreeMap::new();
+        for name in node_names {
+            let status = if !opts.state.nodes.exists(name) {
+                NodeStatus::Missing
+            } else if !opts.state.nodes.get(name)?.is_running() {
+                NodeStatus::Stopped
+            } else {
+                NodeStatus::Running(fetch_resources(ctx, opts, name).await?)
+            };
+            nodes.insert(name.to_string(), status);
+        }
+        let mut node_addresses = BTreeMap::new();
+        for node in opts.state.nodes.list()? {
+            let listener = node.config().setup().default_tcp_listener();
+            if let Ok(address) = listener.and_then(|l| Ok(l.maddr()?)) {
+                node_addresses.insert(node.name().to_string(), address);
+            }
+        }
+        Ok(Self {
+            nodes,
+            node_addresses,
+        })
+    }
+
+    pub fn node(&self, name: &str) -> &NodeStatus {
+        self.nodes.get(name).unwrap_or(&NodeStatus::Missing)
+    }
+
+    /// Replace the `/node/{name}` parts of a route with the address of those nodes,
+    /// as the commands creating inlets and relays do. Return None if the route is
+    /// invalid or refers to a node which doesn't exist
+    pub fn resolve(&self, route: &str) -> Option<MultiAddr> {
+        let route = MultiAddr::from_str(route).ok()?;
+        let mut resolved = MultiAddr::default();
+        for proto in route.iter() {
+            match proto.code() {
+                Node::CODE => {
+                    let name = proto.cast::<Node>()?;
+                    let address = self.node_addresses.get(&*name)?;
+                    resolved.try_extend(address).ok()?
+                }
+                _ => resolved.push_back_value(&proto).ok()?,
+            }
+        }
+        Some(resolved)
+    }
+
+    #[cfg(test)]
+    pub fn insert(&mut self, name: &str, status: NodeStatus) {
+        self.nodes.insert(name.to_string(), status);
+    }
+}
+
+async fn fetch_resources(
+    ctx: &Context,
+    opts: &CommandGlobalOpts,
+    node_name: &str,
+) -> Result<NodeResources> {
+    let mut node_resources = NodeResources::default();
+
+    // The routes given to inlets and relays are only known from the requests which
+    // created them, as recorded in the node setup
+    let mut inlet_routes = BTreeMap::new();
+    let mut relay_routes = BTreeMap::new();
+    let node_state = opts.state.nodes.get(node_name)?;
+    for resource in node_state.config().setup().resources() {
+        match resource.kind {
+            NodeResourceKind::Inlet => {
+                if let Ok(request) = minicbor::decode::<CreateInlet>(&resource.body) {
+                    inlet_routes.insert(resource.name.clone(), request.outlet_addr().clone());
+                }
+            }
+            NodeResourceKind::Forwarder => {
+                if let Ok(request) = minicbor::decode::<CreateForwarder>(&resource.body) {
+                    relay_routes.insert(resource.name.clone(), request.address().clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut rpc = Rpc::background(ctx, opts, node_name)?;
+    rpc.request(Request::get("/node/inlet")).await?;
+    for inlet in rpc.parse_response::<InletList>()?.list {
+        node_resources.inlets.insert(
+            inlet.alias.to_string(),
+            InletState {
+                from: inlet.bind_addr.to_string(),
+                to: inlet_routes.remove(inlet.alias.as_ref()),
+            },
+        );
+    }
+
+    let mut rpc = Rpc::background(ctx, opts, node_name)?;
+    rpc.request(Request::get("/node/outlet")).await?;
+    for outlet in rpc.parse_response::<OutletList>()?.list {
+        node_resources.outlets.insert(
+            outlet.alias.to_string(),
+            OutletState {
+                from: Address::from_string(outlet.worker_addr.as_ref())
+                    .address()
+                    .to_string(),
+                to: outlet
+                    .unix_path
+                    .as_deref()
+                    .unwrap_or(&outlet.tcp_addr)
+                    .to_string(),
+            },
+        );
+    }
+
+    let mut rpc = Rpc::background(ctx, opts, node_name)?;
+    rpc.request(Request::get("/node/forwarder")).await?;
+    for relay in rpc.parse_response::<Vec<ForwarderInfo>>()? {
+        if let Some(name) = relay.remote_address().strip_prefix(RELAY_ADDRESS_PREFIX) {
+            node_resources.relays.insert(
+                name.to_string(),
+                relay_routes.remove(relay.remote_address()),
+            );
+        }
+    }
+
+    for resource in [resources::INLET, resources::OUTLET] {
+        if let Some(expression) = fetch_policy(ctx, opts, node_name, &resource).await? {
+            node_resources
+                .policies
+                .insert(resource.to_string(), expression);
+        }
+    }
+
+    Ok(node_resources)
+}
+
+async fn fetch_policy(
+    ctx: &Context,
+    opts: &CommandGlobalOpts,
+    node_name: &str,
+    resource: &Resource,
+) -> Result<Option<String>> {
+    let mut rpc = Rpc::background(ctx, opts, node_name)?;
+    rpc.request(Request::get(format!("/policy/{resource}")))
+        .await?;
+    let policies: PolicyList = rpc.parse_response()?;
+    Ok(policies
+        .expressions()
+        .iter()
+        .find(|(action, _)| action == &actions::HANDLE_MESSAGE)
+        .map(|(_, expression)| expression.to_string()))
+}
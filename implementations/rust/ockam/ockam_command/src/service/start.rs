@@ -54,6 +54,25 @@ pub enum StartSubCommand {
         #[arg(long)]
         project: String,
     },
+    Stream {
+        #[arg(long, default_value_t = stream_default_addr())]
+        addr: String,
+
+        #[arg(long, default_value_t = stream_index_default_addr())]
+        index_addr: String,
+
+        /// Size in bytes after which a new segment file is started
+        #[arg(long)]
+        segment_size: Option<u64>,
+
+        /// Remove messages older than this number of seconds
+        #[arg(long)]
+        max_age_secs: Option<u64>,
+
+        /// Remove the oldest messages while a stream takes more than this number of bytes
+        #[arg(long)]
+        max_size: Option<u64>,
+    },
 }
 
 fn identity_default_addr() -> String {
@@ -76,6 +95,14 @@ fn authenticator_default_addr() -> String {
     DefaultAddress::DIRECT_AUTHENTICATOR.to_string()
 }
 
+fn stream_default_addr() -> String {
+    DefaultAddress::STREAM_SERVICE.to_string()
+}
+
+fn stream_index_default_addr() -> String {
+    DefaultAddress::STREAM_INDEX_SERVICE.to_string()
+}
+
 impl StartCommand {
     pub fn run(self, options: CommandGlobalOpts) {
         node_rpc(rpc, (options, self));
@@ -124,6 +151,18 @@ async fn run_impl(
             start_authenticator_service(ctx, &opts, node_name, &addr, &project, Some(&tcp)).await?;
             addr
         }
+        StartSubCommand::Stream {
+            addr,
+            index_addr,
+            segment_size,
+            max_age_secs,
+            max_size,
+        } => {
+            let req =
+                api::start_stream_service(&addr, &index_addr, segment_size, max_age_secs, max_size);
+            start_service_impl(ctx, &opts, node_name, "Stream", req, Some(&tcp)).await?;
+            addr
+        }
     };
 
     opts.terminal.write_line(&fmt_ok!(
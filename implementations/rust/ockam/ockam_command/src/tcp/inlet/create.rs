@@ -2,7 +2,7 @@ use crate::node::{default_node_name, node_name_parser};
 use crate::policy::{add_default_project_policy, has_policy};
 use crate::tcp::util::alias_parser;
 use crate::terminal::OckamColor;
-use crate::util::parsers::socket_addr_parser;
+use crate::util::parsers::{duration_parser, socket_addr_parser};
 use crate::util::{
     bind_to_port_check, exitcode, extract_address_value, find_available_port, node_rpc,
     process_nodes_multiaddr, RpcBuilder,
@@ -17,11 +17,13 @@ use ockam_abac::Resource;
 use ockam_api::cli_state::{StateDirTrait, StateItemTrait};
 use ockam_api::nodes::models::portal::CreateInlet;
 use ockam_api::nodes::models::portal::InletStatus;
+use ockam_api::nodes::models::session::SessionRetryOptions;
 use ockam_core::api::Request;
 use ockam_core::route;
 use ockam_multiaddr::proto::Project;
 use ockam_multiaddr::{MultiAddr, Protocol as _};
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::thread::sleep;
 use std::time::Duration;
@@ -39,6 +41,15 @@ pub struct CreateCommand {
     #[arg(long, display_order = 900, id = "SOCKET_ADDRESS", default_value_t = default_from_addr(), value_parser = socket_addr_parser)]
     from: SocketAddr,
 
+    /// Path of a Unix domain socket on which to accept connections, instead of a TCP address.
+    #[arg(
+        long,
+        display_order = 900,
+        id = "SOCKET_PATH",
+        conflicts_with = "SOCKET_ADDRESS"
+    )]
+    from_unix: Option<PathBuf>,
+
     /// Route to a tcp outlet.
     #[arg(long, display_order = 900, id = "ROUTE", default_value_t = default_to_addr())]
     to: MultiAddr,
@@ -58,6 +69,26 @@ pub struct CreateCommand {
     /// Time to wait before retrying to connect to outlet (ms).
     #[arg(long, display_order = 900, id = "RETRY", default_value = "20000")]
     retry_wait_ms: u64,
+    /// Interval between two pings of the connection to the outlet, e.g. `10s` or `1m`, at least `1s`.
+    /// Interval between two pings of the connection to the outlet, e.g. `10s` or `1m`.
+    #[arg(long, display_order = 901, value_parser = duration_parser)]
+    ping_interval: Option<Duration>,
+
+    /// Number of unanswered pings after which the connection to the outlet is re-established.
+    #[arg(long, display_order = 901)]
+    max_missed_pings: Option<u32>,
+
+    /// Delay before the first attempt to re-establish the connection, doubled after each failed attempt.
+    #[arg(long, display_order = 901, value_parser = duration_parser)]
+    reconnect_initial_delay: Option<Duration>,
+
+    /// Maximum delay between two attempts to re-establish the connection.
+    #[arg(long, display_order = 901, value_parser = duration_parser)]
+    reconnect_max_delay: Option<Duration>,
+
+    /// Number of failed attempts after which the connection is not re-established anymore.
+    #[arg(long, display_order = 901)]
+    reconnect_max_attempts: Option<u32>,
 }
 
 fn default_from_addr() -> SocketAddr {
@@ -65,6 +96,45 @@ fn default_from_addr() -> SocketAddr {
     SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), port)
 }
 
+impl CreateCommand {
+    /// Local address the inlet accepts connections at, either a socket address or a path
+    fn listen_address(&self) -> String {
+        match &self.from_unix {
+            Some(path) => path.display().to_string(),
+            None => self.from.to_string(),
+        }
+    }
+
+    /// Options for the session monitoring the connection to the outlet, if any was set
+    fn session_options(&self) -> Option<SessionRetryOptions> {
+        if self.ping_interval.is_none()
+            && self.max_missed_pings.is_none()
+            && self.reconnect_initial_delay.is_none()
+            && self.reconnect_max_delay.is_none()
+            && self.reconnect_max_attempts.is_none()
+        {
+            return None;
+        }
+        let mut options = SessionRetryOptions::new();
+        if let Some(interval) = self.ping_interval {
+            options = options.with_ping_interval(interval);
+        }
+        if let Some(max) = self.max_missed_pings {
+            options = options.with_max_missed_pings(max);
+        }
+        if let Some(delay) = self.reconnect_initial_delay {
+            options = options.with_initial_backoff(delay);
+        }
+        if let Some(delay) = self.reconnect_max_delay {
+            options = options.with_max_backoff(delay);
+        }
+        if let Some(max) = self.reconnect_max_attempts {
+            options = options.with_max_attempts(max);
+        }
+        Some(options)
+    }
+}
+
 fn default_to_addr() -> MultiAddr {
     MultiAddr::from_str("/project/default/service/forward_to_default/secure/api/service/outlet")
         .expect("Failed to parse default multiaddr")
@@ -87,7 +157,7 @@ async fn rpc(ctx: Context, (opts, mut cmd): (CommandGlobalOpts, CreateCommand))
     let progress_bar = opts.terminal.progress_spinner();
     let send_req = async {
         // Check if the port is used by some other services or process
-        if !bind_to_port_check(&cmd.from) {
+        if cmd.from_unix.is_none() && !bind_to_port_check(&cmd.from) {
             return Err(crate::error::Error::new(
                 exitcode::IOERR,
                 anyhow!("Another process is listening on the provided port!"),
@@ -135,6 +205,12 @@ async fn rpc(ctx: Context, (opts, mut cmd): (CommandGlobalOpts, CreateCommand))
                     payload.set_alias(a)
                 }
                 payload.set_wait_ms(cmd.connection_wait_ms);
+                if let Some(path) = cmd.from_unix.as_ref() {
+                    payload.set_unix_path(path.display().to_string())
+                }
+                if let Some(options) = cmd.session_options() {
+                    payload.set_session_options(options)
+                }
 
                 Request::post("/node/inlet").body(payload)
             };
@@ -170,8 +246,7 @@ async fn rpc(ctx: Context, (opts, mut cmd): (CommandGlobalOpts, CreateCommand))
         ),
         format!(
             "Hosting TCP Socket at {}...",
-            &cmd.from
-                .to_string()
+            &cmd.listen_address()
                 .color(OckamColor::PrimaryResource.color())
         ),
         format!(
@@ -198,8 +273,7 @@ async fn rpc(ctx: Context, (opts, mut cmd): (CommandGlobalOpts, CreateCommand))
         .plain(
             fmt_ok!(
                 "TCP Inlet {} on node {} is now sending traffic\n",
-                &cmd.from
-                    .to_string()
+                &cmd.listen_address()
                     .color(OckamColor::PrimaryResource.color()),
                 &node.to_string().color(OckamColor::PrimaryResource.color())
             ) + &fmt_log!(
@@ -6,8 +6,10 @@ use crate::Result;
 use clap::Args;
 use ockam::{Context, Route};
 use ockam_api::nodes::models::portal::InletStatus;
+use ockam_api::nodes::models::session::SessionStatus;
 use ockam_api::route_to_multiaddr;
 use ockam_core::api::{Request, RequestBuilder};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Delete a TCP Inlet
 #[derive(Clone, Debug, Args)]
@@ -33,6 +35,7 @@ pub async fn run_impl(
     (options, cmd): (CommandGlobalOpts, ShowCommand),
 ) -> crate::Result<()> {
     let node_name = extract_address_value(&cmd.node_opts.api_node)?;
+    let alias = cmd.alias.clone();
     let mut rpc = Rpc::background(&ctx, &options, &node_name)?;
     rpc.request(make_api_request(cmd)?).await?;
     rpc.is_ok()?;
@@ -47,9 +50,45 @@ pub async fn run_impl(
             println!("  To Outlet Address: {ma}");
         }
     }
+
+    // Inlets to a local outlet have no session
+    let mut rpc = Rpc::background(&ctx, &options, &node_name)?;
+    rpc.request(Request::get(format!("/node/inlet/{alias}/session")))
+        .await?;
+    if rpc.is_ok().is_ok() {
+        print_session(&rpc.parse_response::<SessionStatus>()?);
+    }
     Ok(())
 }
 
+fn print_session(session: &SessionStatus) {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    println!("  Session:");
+    if session.given_up {
+        println!("    Status: {} (not reconnecting anymore)", session.status);
+    } else {
+        println!("    Status: {}", session.status);
+    }
+    println!("    Route: {}", session.ping_route);
+    println!(
+        "    Failed Reconnection Attempts: {}",
+        session.failed_attempts
+    );
+    if !session.history.is_empty() {
+        println!("    History:");
+    }
+    for event in &session.history {
+        let ago = now.saturating_sub(event.timestamp);
+        match &event.details {
+            Some(details) => println!("      - {ago}s ago: {} ({details})", event.kind),
+            None => println!("      - {ago}s ago: {}", event.kind),
+        }
+    }
+}
+
 /// Construct a request to show a tcp inlet
 fn make_api_request<'a>(cmd: ShowCommand) -> Result<RequestBuilder<'a>> {
     let alias = cmd.alias;
@@ -19,6 +19,7 @@ use tokio::try_join;
 
 use ockam_api::cli_state::{StateDirTrait, StateItemTrait};
 use std::net::SocketAddr;
+use std::path::PathBuf;
 
 /// Create TCP Outlets
 #[derive(Clone, Debug, Args)]
@@ -32,8 +33,17 @@ pub struct CreateCommand {
     from: String,
 
     /// TCP address to send raw tcp traffic.
-    #[arg(long, display_order = 902, id = "SOCKET_ADDRESS", value_parser = socket_addr_parser)]
-    to: SocketAddr,
+    #[arg(long, display_order = 902, id = "SOCKET_ADDRESS", value_parser = socket_addr_parser, required_unless_present = "SOCKET_PATH")]
+    to: Option<SocketAddr>,
+
+    /// Path of a Unix domain socket to send raw traffic to, instead of a TCP address.
+    #[arg(
+        long,
+        display_order = 902,
+        id = "SOCKET_PATH",
+        conflicts_with = "SOCKET_ADDRESS"
+    )]
+    to_unix: Option<PathBuf>,
 
     /// Assign a name to this outlet.
     #[arg(long, display_order = 900, id = "ALIAS", value_parser = alias_parser)]
@@ -46,6 +56,17 @@ impl CreateCommand {
     }
 }
 
+impl CreateCommand {
+    /// Target the outlet sends traffic to, either a socket address or a path
+    fn target_address(&self) -> String {
+        match (&self.to_unix, &self.to) {
+            (Some(path), _) => path.display().to_string(),
+            (None, Some(addr)) => addr.to_string(),
+            (None, None) => String::new(),
+        }
+    }
+}
+
 fn default_from_addr() -> String {
     "/service/outlet".to_string()
 }
@@ -71,6 +92,7 @@ pub async fn run_impl(
         }
     }
 
+    let to = cmd.target_address();
     let mut rpc = Rpc::background(&ctx, &opts, &node)?;
     let is_finished: Mutex<bool> = Mutex::new(false);
 
@@ -113,9 +135,7 @@ pub async fn run_impl(
         .plain(fmt_ok!(
             "{} is now sending TCP traffic to {}",
             &node.to_string().color(OckamColor::PrimaryResource.color()),
-            &cmd.to
-                .to_string()
-                .color(OckamColor::PrimaryResource.color())
+            &to.color(OckamColor::PrimaryResource.color())
         ))
         .machine(machine)
         .json(json)
@@ -126,10 +146,13 @@ pub async fn run_impl(
 
 /// Construct a request to create a tcp outlet
 fn make_api_request<'a>(cmd: CreateCommand) -> crate::Result<RequestBuilder<'a, CreateOutlet<'a>>> {
-    let tcp_addr = cmd.to.to_string();
+    let tcp_addr = cmd.to.map(|to| to.to_string()).unwrap_or_default();
     let worker_addr = cmd.from;
     let alias = cmd.alias.map(|a| a.into());
-    let payload = CreateOutlet::new(tcp_addr, worker_addr, alias);
+    let mut payload = CreateOutlet::new(tcp_addr, worker_addr, alias);
+    if let Some(path) = cmd.to_unix {
+        payload.set_unix_path(path.display().to_string());
+    }
     let request = Request::post("/node/outlet").body(payload);
     Ok(request)
 }
@@ -43,7 +43,10 @@ async fn run_impl(
         let addr = route_to_multiaddr(&route![outlet.worker_addr.to_string()])
             .ok_or_else(|| ApiError::generic("Invalid Outlet Address"))?;
         println!("  From Outlet: {addr}");
-        println!("  To TCP: {}", outlet.tcp_addr);
+        match &outlet.unix_path {
+            Some(path) => println!("  To Unix socket: {path}"),
+            None => println!("  To TCP: {}", outlet.tcp_addr),
+        }
     }
     Ok(())
 }
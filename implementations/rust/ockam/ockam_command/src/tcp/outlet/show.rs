@@ -45,7 +45,10 @@ pub async fn run_impl(
     let addr = route_to_multiaddr(&route![outlet_to_show.worker_addr.to_string()])
         .ok_or_else(|| ApiError::generic("Invalid Outlet Address"))?;
     println!("  From Outlet: {addr}");
-    println!("  To TCP: {}", outlet_to_show.tcp_addr);
+    match &outlet_to_show.unix_path {
+        Some(path) => println!("  To Unix socket: {path}"),
+        None => println!("  To TCP: {}", outlet_to_show.tcp_addr),
+    }
     Ok(())
 }
 
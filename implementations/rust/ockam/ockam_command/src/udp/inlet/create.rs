@@ -0,0 +1,121 @@
+use crate::node::{default_node_name, node_name_parser};
+use crate::policy::{add_default_project_policy, has_policy};
+use crate::tcp::util::alias_parser;
+use crate::terminal::OckamColor;
+use crate::util::parsers::socket_addr_parser;
+use crate::util::{extract_address_value, node_rpc, process_nodes_multiaddr, Rpc};
+use crate::{fmt_log, fmt_ok, CommandGlobalOpts, Result};
+use anyhow::anyhow;
+use clap::Args;
+use colorful::Colorful;
+use ockam::identity::IdentityIdentifier;
+use ockam::Context;
+use ockam_abac::Resource;
+use ockam_api::cli_state::{StateDirTrait, StateItemTrait};
+use ockam_api::nodes::models::portal::{CreateInlet, InletStatus};
+use ockam_core::api::Request;
+use ockam_core::route;
+use ockam_multiaddr::proto::Project;
+use ockam_multiaddr::{MultiAddr, Protocol as _};
+use std::net::SocketAddr;
+
+/// Create UDP Inlets
+#[derive(Clone, Debug, Args)]
+pub struct CreateCommand {
+    /// Node on which to start the udp inlet.
+    #[arg(long, display_order = 900, id = "NODE", default_value_t = default_node_name(), value_parser = node_name_parser)]
+    at: String,
+
+    /// Address on which to receive datagrams.
+    #[arg(long, display_order = 900, id = "SOCKET_ADDRESS", value_parser = socket_addr_parser)]
+    from: SocketAddr,
+
+    /// Route to a udp outlet.
+    #[arg(long, display_order = 900, id = "ROUTE")]
+    to: MultiAddr,
+
+    /// Authorized identity for secure channel connection
+    #[arg(long, name = "AUTHORIZED", display_order = 900)]
+    authorized: Option<IdentityIdentifier>,
+
+    /// Assign a name to this inlet.
+    #[arg(long, display_order = 900, id = "ALIAS", value_parser = alias_parser)]
+    alias: Option<String>,
+
+    /// Time to wait for the outlet to be available (ms).
+    #[arg(long, display_order = 900, id = "WAIT", default_value = "5000")]
+    connection_wait_ms: u64,
+}
+
+impl CreateCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(rpc, (options, self));
+    }
+}
+
+async fn rpc(ctx: Context, (opts, mut cmd): (CommandGlobalOpts, CreateCommand)) -> Result<()> {
+    opts.terminal.write_line(&fmt_log!("Creating UDP Inlet"))?;
+    cmd.to = process_nodes_multiaddr(&cmd.to, &opts.state)?;
+    let node = extract_address_value(&cmd.at)?;
+
+    let project = opts
+        .state
+        .nodes
+        .get(&node)?
+        .config()
+        .setup()
+        .project
+        .to_owned();
+    let resource = Resource::new("udp-inlet");
+    if let Some(p) = project {
+        if !has_policy(&node, &ctx, &opts, &resource).await? {
+            add_default_project_policy(&node, &ctx, &opts, p, &resource).await?;
+        }
+    }
+
+    let mut payload = if cmd.to.matches(0, &[Project::CODE.into()]) {
+        if cmd.authorized.is_some() {
+            return Err(anyhow!("--authorized can not be used with project addresses").into());
+        }
+        CreateInlet::via_project(cmd.from, cmd.to.clone(), route![], route![])
+    } else {
+        CreateInlet::to_node(
+            cmd.from,
+            cmd.to.clone(),
+            route![],
+            route![],
+            cmd.authorized.clone(),
+        )
+    };
+    if let Some(a) = cmd.alias.as_ref() {
+        payload.set_alias(a)
+    }
+    payload.set_wait_ms(cmd.connection_wait_ms);
+
+    let mut rpc = Rpc::background(&ctx, &opts, &node)?;
+    rpc.request(Request::post("/node/udp/inlet").body(payload))
+        .await?;
+    let inlet = rpc.parse_response::<InletStatus>()?;
+    let bind_addr = inlet.bind_addr.to_string();
+    let json = serde_json::to_string_pretty(&inlet)?;
+
+    opts.terminal
+        .stdout()
+        .plain(
+            fmt_ok!(
+                "UDP Inlet {} on node {} is now sending datagrams\n",
+                &bind_addr.clone().color(OckamColor::PrimaryResource.color()),
+                &node.to_string().color(OckamColor::PrimaryResource.color())
+            ) + &fmt_log!(
+                "to the outlet at {}",
+                &cmd.to
+                    .to_string()
+                    .color(OckamColor::PrimaryResource.color())
+            ),
+        )
+        .machine(&bind_addr)
+        .json(json)
+        .write_line()?;
+
+    Ok(())
+}
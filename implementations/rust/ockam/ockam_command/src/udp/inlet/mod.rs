@@ -0,0 +1,40 @@
+mod create;
+mod delete;
+mod list;
+
+use crate::{docs, CommandGlobalOpts};
+use clap::{Args, Subcommand};
+use create::CreateCommand;
+use delete::DeleteCommand;
+use list::ListCommand;
+
+const AFTER_LONG_HELP: &str = include_str!("../static/inlet/after_long_help.txt");
+
+/// Manage UDP Inlets
+#[derive(Clone, Debug, Args)]
+#[command(
+    arg_required_else_help = true,
+    subcommand_required = true,
+    after_long_help = docs::after_help(AFTER_LONG_HELP)
+)]
+pub struct UdpInletCommand {
+    #[command(subcommand)]
+    subcommand: UdpInletSubCommand,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum UdpInletSubCommand {
+    Create(CreateCommand),
+    Delete(DeleteCommand),
+    List(ListCommand),
+}
+
+impl UdpInletCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        match self.subcommand {
+            UdpInletSubCommand::Create(c) => c.run(options),
+            UdpInletSubCommand::Delete(c) => c.run(options),
+            UdpInletSubCommand::List(c) => c.run(options),
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub(crate) mod inlet;
+pub(crate) mod outlet;
@@ -0,0 +1,92 @@
+use crate::node::{default_node_name, node_name_parser};
+use crate::policy::{add_default_project_policy, has_policy};
+use crate::tcp::util::alias_parser;
+use crate::terminal::OckamColor;
+use crate::util::parsers::socket_addr_parser;
+use crate::util::{extract_address_value, node_rpc, Rpc};
+use crate::{fmt_log, fmt_ok, CommandGlobalOpts};
+use clap::Args;
+use colorful::Colorful;
+use ockam::Context;
+use ockam_abac::Resource;
+use ockam_api::cli_state::{StateDirTrait, StateItemTrait};
+use ockam_api::nodes::models::portal::{CreateOutlet, OutletStatus};
+use ockam_core::api::Request;
+use std::net::SocketAddr;
+
+/// Create UDP Outlets
+#[derive(Clone, Debug, Args)]
+pub struct CreateCommand {
+    /// Node on which to start the udp outlet.
+    #[arg(long, display_order = 900, id = "NODE", default_value_t = default_node_name(), value_parser = node_name_parser)]
+    at: String,
+
+    /// Address of the udp outlet.
+    #[arg(long, display_order = 901, id = "OUTLET_ADDRESS", default_value_t = default_from_addr())]
+    from: String,
+
+    /// UDP address to send datagrams to.
+    #[arg(long, display_order = 902, id = "SOCKET_ADDRESS", value_parser = socket_addr_parser)]
+    to: SocketAddr,
+
+    /// Assign a name to this outlet.
+    #[arg(long, display_order = 900, id = "ALIAS", value_parser = alias_parser)]
+    alias: Option<String>,
+}
+
+impl CreateCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(run_impl, (options, self))
+    }
+}
+
+fn default_from_addr() -> String {
+    "/service/udp-outlet".to_string()
+}
+
+async fn run_impl(
+    ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, CreateCommand),
+) -> crate::Result<()> {
+    opts.terminal.write_line(&fmt_log!("Creating UDP Outlet"))?;
+    let node = extract_address_value(&cmd.at)?;
+    let project = opts
+        .state
+        .nodes
+        .get(&node)?
+        .config()
+        .setup()
+        .project
+        .to_owned();
+    let resource = Resource::new("udp-outlet");
+    if let Some(p) = project {
+        if !has_policy(&node, &ctx, &opts, &resource).await? {
+            add_default_project_policy(&node, &ctx, &opts, p, &resource).await?;
+        }
+    }
+
+    let worker_addr = extract_address_value(&cmd.from)?;
+    let payload = CreateOutlet::new(cmd.to.to_string(), worker_addr, cmd.alias.map(|a| a.into()));
+
+    let mut rpc = Rpc::background(&ctx, &opts, &node)?;
+    rpc.request(Request::post("/node/udp/outlet").body(payload))
+        .await?;
+    let outlet = rpc.parse_response::<OutletStatus>()?;
+    let machine = outlet.worker_address()?;
+    let json = serde_json::to_string_pretty(&outlet)?;
+
+    opts.terminal
+        .stdout()
+        .plain(fmt_ok!(
+            "{} is now sending datagrams to {}",
+            &node.to_string().color(OckamColor::PrimaryResource.color()),
+            &cmd.to
+                .to_string()
+                .color(OckamColor::PrimaryResource.color())
+        ))
+        .machine(machine)
+        .json(json)
+        .write_line()?;
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use crate::node::NodeOpts;
+use crate::util::{exitcode, extract_address_value, node_rpc, Rpc};
+use crate::CommandGlobalOpts;
+use anyhow::anyhow;
+use clap::Args;
+use ockam_api::nodes::models;
+use ockam_core::api::Request;
+
+/// Retrieve UDP outlets information on Node
+#[derive(Args, Clone, Debug)]
+pub struct ListCommand {
+    #[command(flatten)]
+    node: NodeOpts,
+}
+
+impl ListCommand {
+    pub fn run(self, options: CommandGlobalOpts) {
+        node_rpc(run_impl, (options, self))
+    }
+}
+
+async fn run_impl(
+    ctx: ockam::Context,
+    (options, command): (CommandGlobalOpts, ListCommand),
+) -> crate::Result<()> {
+    let node_name = extract_address_value(&command.node.api_node)?;
+    let mut rpc = Rpc::background(&ctx, &options, &node_name)?;
+    rpc.request(Request::get("/node/udp/outlet")).await?;
+    let response = rpc.parse_response::<models::portal::OutletList>()?;
+
+    if response.list.is_empty() {
+        return Err(crate::Error::new(
+            exitcode::IOERR,
+            anyhow!("No UDP Outlets found on this node!"),
+        ));
+    }
+
+    for outlet in response.list.iter() {
+        println!("Outlet:");
+        println!("  Alias: {}", outlet.alias);
+        println!("  From Outlet: {}", outlet.worker_addr);
+        println!("  To UDP: {}", outlet.tcp_addr);
+    }
+    Ok(())
+}
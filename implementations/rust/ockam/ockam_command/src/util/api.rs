@@ -16,7 +16,8 @@ use ockam_api::cloud::{BareCloudRequestWrapper, CloudRequestWrapper};
 use ockam_api::config::cli::TrustContextConfig;
 use ockam_api::nodes::models::services::{
     StartAuthenticatedServiceRequest, StartAuthenticatorRequest, StartCredentialsService,
-    StartIdentityServiceRequest, StartOktaIdentityProviderRequest, StartVerifierService,
+    StartIdentityServiceRequest, StartOktaIdentityProviderRequest, StartStreamServiceRequest,
+    StartVerifierService,
 };
 use ockam_api::nodes::*;
 use ockam_api::DefaultAddress;
@@ -80,6 +81,11 @@ pub(crate) fn list_secure_channels() -> RequestBuilder<'static, ()> {
     Request::get("/node/secure_channel")
 }
 
+/// Construct a request builder to rotate the root key of the node identity
+pub(crate) fn rotate_identity() -> RequestBuilder<'static, ()> {
+    Request::post("/node/identity/actions/rotate")
+}
+
 /// Construct a request builder to list all workers on the given node
 pub(crate) fn list_workers() -> RequestBuilder<'static, ()> {
     Request::get("/node/workers")
@@ -161,6 +167,21 @@ pub(crate) fn start_verifier_service(addr: &str) -> RequestBuilder<'static, Star
     Request::post(node_service(DefaultAddress::VERIFIER)).body(payload)
 }
 
+/// Construct a request to start a Stream Service and its Index Service
+pub(crate) fn start_stream_service<'a>(
+    addr: &'a str,
+    index_addr: &'a str,
+    segment_size: Option<u64>,
+    max_age_secs: Option<u64>,
+    max_size: Option<u64>,
+) -> RequestBuilder<'static, StartStreamServiceRequest<'a>> {
+    let payload = StartStreamServiceRequest::new(addr, index_addr)
+        .with_segment_size(segment_size)
+        .with_max_age_secs(max_age_secs)
+        .with_max_size(max_size);
+    Request::post(node_service(DefaultAddress::STREAM_SERVICE)).body(payload)
+}
+
 /// Construct a request to start a Credential Service
 pub(crate) fn start_credentials_service<'a>(
     public_identity: &'a str,
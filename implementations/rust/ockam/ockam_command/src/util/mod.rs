@@ -12,14 +12,15 @@ use tracing::{debug, error, trace};
 
 pub use config::*;
 use ockam::{
-    Address, Context, MessageSendReceiveOptions, NodeBuilder, Route, TcpConnectionOptions,
-    TcpTransport,
+    Address, Context, MessagePriority, MessageSendReceiveOptions, NodeBuilder, Route,
+    TcpConnectionOptions, TcpTransport,
 };
 use ockam_api::cli_state::{CliState, StateDirTrait, StateItemTrait};
 use ockam_api::config::lookup::{InternetAddress, LookupMeta};
 use ockam_api::nodes::NODEMANAGER_ADDR;
 use ockam_core::api::{RequestBuilder, Response, Status};
 
+use ockam_core::compat::sync::Arc;
 use ockam_core::flow_control::FlowControls;
 use ockam_core::DenyAll;
 use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Project, Service, Space, Tcp};
@@ -27,6 +28,7 @@ use ockam_multiaddr::{
     proto::{self, Node},
     MultiAddr, Protocol,
 };
+use ockam_node::metrics::MetricsRecorder;
 
 use crate::util::output::Output;
 use crate::{node::util::start_embedded_node, EncodeFormat};
@@ -150,7 +152,9 @@ impl<'a> Rpc<'a> {
         T: Encode<()>,
     {
         let route = self.route_impl(self.ctx, &self.flow_controls).await?;
-        let options = MessageSendReceiveOptions::new().with_flow_control(&self.flow_controls);
+        let options = MessageSendReceiveOptions::new()
+            .with_flow_control(&self.flow_controls)
+            .with_priority(MessagePriority::High);
         self.buf = self
             .ctx
             .send_and_receive_extended::<Vec<u8>>(route.clone(), req.to_vec()?, options)
@@ -173,7 +177,8 @@ impl<'a> Rpc<'a> {
         let route = self.route_impl(self.ctx, &self.flow_controls).await?;
         let options = MessageSendReceiveOptions::new()
             .with_timeout(timeout)
-            .with_flow_control(&self.flow_controls);
+            .with_flow_control(&self.flow_controls)
+            .with_priority(MessagePriority::High);
         self.buf = self
             .ctx
             .send_and_receive_extended::<Vec<u8>>(route.clone(), req.to_vec()?, options)
@@ -422,7 +427,27 @@ where
     Fut: core::future::Future<Output = crate::Result<T>> + Send + 'static,
     T: Send + 'static,
 {
-    let (mut ctx, mut executor) = NodeBuilder::new().no_logging().build();
+    embedded_node_with_metrics_that_is_not_stopped(f, a, None)
+}
+
+/// Same as [`embedded_node_that_is_not_stopped`], recording the metrics
+/// of the node with the given recorder
+pub fn embedded_node_with_metrics_that_is_not_stopped<A, F, Fut, T>(
+    f: F,
+    a: A,
+    recorder: Option<Arc<dyn MetricsRecorder>>,
+) -> crate::Result<T>
+where
+    A: Send + Sync + 'static,
+    F: FnOnce(Context, A) -> Fut + Send + Sync + 'static,
+    Fut: core::future::Future<Output = crate::Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    let mut builder = NodeBuilder::new().no_logging();
+    if let Some(recorder) = recorder {
+        builder = builder.with_metrics_recorder(recorder);
+    }
+    let (mut ctx, mut executor) = builder.build();
     executor.execute(async move {
         let child_ctx = ctx
             .new_detached(
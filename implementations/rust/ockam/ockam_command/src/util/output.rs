@@ -2,6 +2,7 @@ use anyhow::Context;
 use cli_table::{Cell, Style, Table};
 use core::fmt::Write;
 use ockam::identity::credential::Credential;
+use ockam_api::authenticator::direct::types::EnrollmentToken;
 use ockam_api::cloud::project::Project;
 
 use ockam_api::nodes::models::portal::OutletStatus;
@@ -206,20 +207,62 @@ impl Output for ShowSecureChannelResponse<'_> {
 
 impl Output for OutletStatus<'_> {
     fn output(&self) -> Result<String> {
+        let destination = match &self.unix_path {
+            Some(path) => format!("Unix Socket:    {path}"),
+            None => format!("TCP Address:    {}", self.tcp_addr),
+        };
         let output = format!(
             r#"
 Outlet {}:
-    TCP Address:    {}
+    {}
     Worker Address: {}
 "#,
             self.alias,
-            self.tcp_addr,
+            destination,
             self.worker_address()?
         );
 
         Ok(output)
     }
 }
+impl Output for EnrollmentToken {
+    fn output(&self) -> Result<String> {
+        let mut w = String::new();
+        write!(w, "Token:")?;
+        write!(w, "\n  Id: {}", self.id())?;
+        write!(w, "\n  Attributes:")?;
+        for (k, v) in self.attributes() {
+            write!(w, "\n    {k}: {v}")?;
+        }
+        write!(w, "\n  Created by: {}", self.created_by())?;
+        write!(
+            w,
+            "\n  Expires: {}",
+            human_readable_time(self.expires_at().unix_time())
+        )?;
+        write!(
+            w,
+            "\n  Remaining uses: {}/{}",
+            self.remaining_uses(),
+            self.usage_count()
+        )?;
+        Ok(w)
+    }
+}
+
+impl Output for Vec<EnrollmentToken> {
+    fn output(&self) -> Result<String> {
+        if self.is_empty() {
+            return Ok("No enrollment tokens found".to_string());
+        }
+        let tokens = self
+            .iter()
+            .map(|token| token.output())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(tokens.join("\n"))
+    }
+}
+
 impl Output for Credential {
     fn output(&self) -> Result<String> {
         Ok(self.to_string())
@@ -231,3 +274,11 @@ impl Output for Vec<u8> {
         Ok(hex::encode(self))
     }
 }
+
+/// Format a unix timestamp as a date, falling back to the raw value if it is out of range
+pub fn human_readable_time(time: u64) -> String {
+    match time::OffsetDateTime::from_unix_timestamp(time as i64) {
+        Ok(time) => time.to_string(),
+        Err(_) => format!("{time} (unix time)"),
+    }
+}
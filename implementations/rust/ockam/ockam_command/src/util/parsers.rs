@@ -1,6 +1,7 @@
 use crate::Result;
 use anyhow::anyhow;
 use std::net::{Ipv4Addr, SocketAddr};
+use std::time::Duration;
 
 /// Helper fn for parsing ip and port from user input
 /// It can parse a string containing either an `ip:port` pair or just a `port`
@@ -30,11 +31,36 @@ pub(crate) fn socket_addr_parser(input: &str) -> Result<SocketAddr> {
     }
 }
 
+/// Helper fn for parsing a duration from user input
+/// It can parse a number followed by a unit: `s` for seconds, `m` for minutes,
+/// `h` for hours or `d` for days. A number without a unit is a number of seconds.
+pub(crate) fn duration_parser(input: &str) -> Result<Duration> {
+    let (value, unit) = match input.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => input.split_at(i),
+        None => (input, "s"),
+    };
+    let value: u64 = value
+        .parse()
+        .map_err(|_| anyhow!("Invalid duration {}", input))?;
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(anyhow!("Invalid duration unit in {}", input).into()),
+    };
+    value
+        .checked_mul(multiplier)
+        .map(Duration::from_secs)
+        .ok_or_else(|| anyhow!("Duration {} is too large", input).into())
+}
+
 #[cfg(test)]
 mod tests {
     use ockam_core::compat::net::{IpAddr, Ipv4Addr, SocketAddr};
 
-    use crate::util::parsers::socket_addr_parser;
+    use crate::util::parsers::{duration_parser, socket_addr_parser};
+    use std::time::Duration;
 
     #[test]
     fn test_parse_bootstrap_server() {
@@ -72,4 +98,19 @@ mod tests {
         let invalid_input = "192,166,0.1:9999";
         assert!(socket_addr_parser(invalid_input).is_err());
     }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(duration_parser("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(duration_parser("30s").unwrap(), Duration::from_secs(30));
+        assert_eq!(duration_parser("10m").unwrap(), Duration::from_secs(600));
+        assert_eq!(duration_parser("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(duration_parser("7d").unwrap(), Duration::from_secs(604800));
+
+        assert!(duration_parser("").is_err());
+        assert!(duration_parser("h").is_err());
+        assert!(duration_parser("10w").is_err());
+        assert!(duration_parser("1h30m").is_err());
+        assert!(duration_parser("-1s").is_err());
+    }
 }
@@ -21,6 +21,11 @@ pub struct CreateCommand {
 
     #[arg(long, default_value = "false")]
     aws_kms: bool,
+
+    /// Encrypt the vault at rest with the key provided by
+    /// the OCKAM_VAULT_KEY or OCKAM_VAULT_PASSPHRASE environment variables
+    #[arg(long, default_value = "false", conflicts_with = "aws_kms")]
+    encrypted: bool,
 }
 
 impl CreateCommand {
@@ -41,8 +46,13 @@ async fn run_impl(
     opts: CommandGlobalOpts,
     cmd: CreateCommand,
 ) -> crate::Result<()> {
-    let CreateCommand { name, aws_kms, .. } = cmd;
-    let config = cli_state::VaultConfig::new(aws_kms)?;
+    let CreateCommand {
+        name,
+        aws_kms,
+        encrypted,
+        ..
+    } = cmd;
+    let config = cli_state::VaultConfig::new(aws_kms, encrypted)?;
     if !opts.state.vaults.is_empty()? {
         opts.terminal.write_line(&fmt_info!(
             "This is the first vault to be created in this environment. It will be set as the default vault"
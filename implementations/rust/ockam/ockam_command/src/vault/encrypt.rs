@@ -0,0 +1,49 @@
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::Context;
+use ockam_api::cli_state::traits::StateDirTrait;
+
+use crate::util::node_rpc;
+use crate::{fmt_ok, CommandGlobalOpts};
+
+/// Encrypt an existing vault at rest
+///
+/// The key is provided by the OCKAM_VAULT_KEY or OCKAM_VAULT_PASSPHRASE environment variables,
+/// and must then be provided every time the vault is used.
+#[derive(Clone, Debug, Args)]
+pub struct EncryptCommand {
+    /// Name of the vault to encrypt
+    name: String,
+}
+
+impl EncryptCommand {
+    pub fn run(self, opts: CommandGlobalOpts) {
+        node_rpc(rpc, (opts, self));
+    }
+}
+
+async fn rpc(
+    mut ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, EncryptCommand),
+) -> crate::Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(
+    _ctx: &mut Context,
+    opts: CommandGlobalOpts,
+    cmd: EncryptCommand,
+) -> crate::Result<()> {
+    let name = cmd.name;
+    let mut state = opts.state.vaults.get(&name)?;
+    state.encrypt().await?;
+
+    opts.terminal
+        .stdout()
+        .plain(fmt_ok!("Vault '{name}' is now encrypted"))
+        .machine(&name)
+        .json(serde_json::json!({ "vault": { "name": &name } }))
+        .write_line()?;
+    Ok(())
+}
@@ -2,7 +2,9 @@ mod attach_key;
 mod create;
 mod default;
 mod delete;
+mod encrypt;
 mod list;
+mod rekey;
 mod show;
 
 use crate::error::Error;
@@ -10,7 +12,9 @@ use crate::vault::attach_key::AttachKeyCommand;
 use crate::vault::create::CreateCommand;
 use crate::vault::default::DefaultCommand;
 use crate::vault::delete::DeleteCommand;
+use crate::vault::encrypt::EncryptCommand;
 use crate::vault::list::ListCommand;
+use crate::vault::rekey::RekeyCommand;
 use crate::vault::show::ShowCommand;
 use crate::{docs, CommandGlobalOpts};
 
@@ -40,6 +44,8 @@ pub enum VaultSubcommand {
     Delete(DeleteCommand),
     List(ListCommand),
     Default(DefaultCommand),
+    Encrypt(EncryptCommand),
+    Rekey(RekeyCommand),
 }
 
 impl VaultCommand {
@@ -51,6 +57,8 @@ impl VaultCommand {
             VaultSubcommand::List(cmd) => cmd.run(opts),
             VaultSubcommand::Delete(cmd) => cmd.run(opts),
             VaultSubcommand::Default(cmd) => cmd.run(opts),
+            VaultSubcommand::Encrypt(cmd) => cmd.run(opts),
+            VaultSubcommand::Rekey(cmd) => cmd.run(opts),
         }
     }
 }
@@ -0,0 +1,53 @@
+use clap::Args;
+use colorful::Colorful;
+
+use ockam::Context;
+use ockam_api::cli_state::traits::StateDirTrait;
+use ockam_api::cli_state::{VaultState, OCKAM_VAULT_NEW_KEY, OCKAM_VAULT_NEW_PASSPHRASE};
+
+use crate::util::node_rpc;
+use crate::{fmt_ok, CommandGlobalOpts};
+
+/// Encrypt a vault with a new key
+///
+/// The current key is provided by the OCKAM_VAULT_KEY or OCKAM_VAULT_PASSPHRASE environment variables,
+/// the new one by the OCKAM_VAULT_NEW_KEY or OCKAM_VAULT_NEW_PASSPHRASE environment variables.
+/// Nodes using the vault must be stopped before it is re-keyed.
+#[derive(Clone, Debug, Args)]
+pub struct RekeyCommand {
+    /// Name of the vault to re-key
+    name: String,
+}
+
+impl RekeyCommand {
+    pub fn run(self, opts: CommandGlobalOpts) {
+        node_rpc(rpc, (opts, self));
+    }
+}
+
+async fn rpc(
+    mut ctx: Context,
+    (opts, cmd): (CommandGlobalOpts, RekeyCommand),
+) -> crate::Result<()> {
+    run_impl(&mut ctx, opts, cmd).await
+}
+
+async fn run_impl(
+    _ctx: &mut Context,
+    opts: CommandGlobalOpts,
+    cmd: RekeyCommand,
+) -> crate::Result<()> {
+    let name = cmd.name;
+    let state = opts.state.vaults.get(&name)?;
+    let new_key =
+        VaultState::storage_key_from_env(OCKAM_VAULT_NEW_KEY, OCKAM_VAULT_NEW_PASSPHRASE)?;
+    state.rekey(new_key).await?;
+
+    opts.terminal
+        .stdout()
+        .plain(fmt_ok!("Vault '{name}' has been re-keyed"))
+        .machine(&name)
+        .json(serde_json::json!({ "vault": { "name": &name } }))
+        .write_line()?;
+    Ok(())
+}
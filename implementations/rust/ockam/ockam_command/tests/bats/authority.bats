@@ -68,3 +68,104 @@ teardown() {
   assert_success
   assert_output --partial "m3_member"
 }
+
+@test "authority - manage the members of a project" {
+  PROJECT_JSON_PATH="$OCKAM_HOME/project-authority.json"
+
+  run "$OCKAM" identity create authority
+  run "$OCKAM" identity create enroller
+  run "$OCKAM" identity create m1
+  run "$OCKAM" identity create m2
+
+  unset OCKAM_LOG
+  enroller_identifier=$($OCKAM identity show enroller)
+  authority_identity_full=$($OCKAM identity show --full --encoding hex authority)
+  m1_identifier=$($OCKAM identity show m1)
+  m2_identifier=$($OCKAM identity show m2)
+
+  trusted="{\"$enroller_identifier\": {\"project_id\": \"1\", \"trust_context_id\": \"1\", \"ockam-role\": \"enroller\"}}"
+  run "$OCKAM" authority create --tcp-listener-address=127.0.0.1:4200 --project-identifier 1 --trusted-identities "$trusted"
+  assert_success
+  sleep 1 # wait for authority to start TCP listener
+
+  echo "{\"id\": \"1\",
+  \"name\" : \"default\",
+  \"identity\" : \"P6c20e814b56579306f55c64e8747e6c1b4a53d9a3f4ca83c252cc2fbfc72fa94\",
+  \"access_route\" : \"/dnsaddr/127.0.0.1/tcp/4000/service/api\",
+  \"authority_access_route\" : \"/dnsaddr/127.0.0.1/tcp/4200/service/api\",
+  \"authority_identity\" : \"$authority_identity_full\"}" >"$PROJECT_JSON_PATH"
+
+  run "$OCKAM" project ticket --identity enroller --project-path "$PROJECT_JSON_PATH" --member $m1_identifier --attribute sample_attr=m1_member
+  assert_success
+  run "$OCKAM" project ticket --identity enroller --project-path "$PROJECT_JSON_PATH" --member $m2_identifier --attribute sample_attr=m2_member --member-ttl 1h
+  assert_success
+
+  run "$OCKAM" project member list --identity enroller --project-path "$PROJECT_JSON_PATH"
+  assert_success
+  assert_output --partial "$m1_identifier"
+  assert_output --partial "$m2_identifier"
+
+  run "$OCKAM" project member show $m2_identifier --identity enroller --project-path "$PROJECT_JSON_PATH"
+  assert_success
+  assert_output --partial "m2_member"
+  assert_output --partial "Expires"
+
+  run "$OCKAM" project member update $m1_identifier --attribute sample_attr=m1_updated --identity enroller --project-path "$PROJECT_JSON_PATH"
+  assert_success
+  run "$OCKAM" project authenticate --project-path "$PROJECT_JSON_PATH" --identity m1
+  assert_success
+  assert_output --partial "m1_updated"
+
+  run "$OCKAM" project member delete $m2_identifier --identity enroller --project-path "$PROJECT_JSON_PATH"
+  assert_success
+  run "$OCKAM" project member show $m2_identifier --identity enroller --project-path "$PROJECT_JSON_PATH"
+  assert_failure
+}
+
+@test "authority - multi-use enrollment tokens" {
+  PROJECT_JSON_PATH="$OCKAM_HOME/project-authority.json"
+
+  run "$OCKAM" identity create authority
+  run "$OCKAM" identity create enroller
+  run "$OCKAM" identity create m1
+  run "$OCKAM" identity create m2
+  run "$OCKAM" identity create m3
+
+  unset OCKAM_LOG
+  enroller_identifier=$($OCKAM identity show enroller)
+  authority_identity_full=$($OCKAM identity show --full --encoding hex authority)
+
+  trusted="{\"$enroller_identifier\": {\"project_id\": \"1\", \"trust_context_id\": \"1\", \"ockam-role\": \"enroller\"}}"
+  run "$OCKAM" authority create --tcp-listener-address=127.0.0.1:4200 --project-identifier 1 --trusted-identities "$trusted"
+  assert_success
+  sleep 1 # wait for authority to start TCP listener
+
+  echo "{\"id\": \"1\",
+  \"name\" : \"default\",
+  \"identity\" : \"P6c20e814b56579306f55c64e8747e6c1b4a53d9a3f4ca83c252cc2fbfc72fa94\",
+  \"access_route\" : \"/dnsaddr/127.0.0.1/tcp/4000/service/api\",
+  \"authority_access_route\" : \"/dnsaddr/127.0.0.1/tcp/4200/service/api\",
+  \"authority_identity\" : \"$authority_identity_full\"}" >"$PROJECT_JSON_PATH"
+
+  # A token can be used twice, then it is removed
+  token=$($OCKAM project ticket --identity enroller --project-path "$PROJECT_JSON_PATH" --attribute sample_attr=shared --usage-count 2 --expires-in 1h)
+  run "$OCKAM" project ticket --list --identity enroller --project-path "$PROJECT_JSON_PATH"
+  assert_success
+  assert_output --partial "Remaining uses: 2/2"
+
+  run "$OCKAM" project authenticate $token --identity m1
+  assert_success
+  assert_output --partial "shared"
+  run "$OCKAM" project authenticate $token --identity m2
+  assert_success
+  run "$OCKAM" project authenticate $token --identity m3
+  assert_failure
+
+  # A revoked token cannot be used
+  token=$($OCKAM project ticket --identity enroller --project-path "$PROJECT_JSON_PATH")
+  token_id=$($OCKAM project ticket --list --identity enroller --project-path "$PROJECT_JSON_PATH" | grep "Id:" | awk '{print $2}')
+  run "$OCKAM" project ticket --revoke $token_id --identity enroller --project-path "$PROJECT_JSON_PATH"
+  assert_success
+  run "$OCKAM" project authenticate $token --identity m3
+  assert_failure
+}
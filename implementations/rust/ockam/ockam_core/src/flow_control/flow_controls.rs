@@ -5,9 +5,11 @@ use crate::compat::vec::Vec;
 use crate::flow_control::{FlowControlId, FlowControlPolicy};
 use crate::Address;
 
-// TODO: Consider integrating this into Routing for better UX + to allow removing
-// entries from that storage
 /// Storage for all Flow Control-related data
+///
+/// Every node owns one instance of [`FlowControls`], which is shared with all its
+/// `Context`s. The node router calls [`FlowControls::cleanup_address`] whenever a
+/// worker or a processor is stopped, so that entries don't outlive their owners.
 #[derive(Clone, Debug, Default)]
 pub struct FlowControls {
     // All known consumers
@@ -123,6 +125,73 @@ impl FlowControls {
             .collect()
     }
 
+    /// Remove all Flow Control information related to the given [`Address`]
+    ///
+    /// The [`Address`] is removed as a Consumer from every Flow Control, its Producer
+    /// and Spawner records are dropped, together with every additional [`Address`]
+    /// pointing to it. Flow Controls that are left without Consumers are removed.
+    pub fn cleanup_address(&self, address: &Address) {
+        let mut consumers = self.consumers.write().unwrap();
+        for info in consumers.values_mut() {
+            info.0.remove(address);
+        }
+        consumers.retain(|_, info| !info.0.is_empty());
+        drop(consumers);
+
+        let mut producers = self.producers.write().unwrap();
+        producers.remove(address);
+        drop(producers);
+
+        let mut producers_additional_addresses =
+            self.producers_additional_addresses.write().unwrap();
+        producers_additional_addresses.remove(address);
+        producers_additional_addresses.retain(|_, producer| producer != address);
+        drop(producers_additional_addresses);
+
+        let mut spawners = self.spawners.write().unwrap();
+        spawners.remove(address);
+    }
+
+    /// Get all known Consumers grouped by their [`FlowControlId`]
+    pub fn get_all_consumers(&self) -> BTreeMap<FlowControlId, ConsumersInfo> {
+        self.consumers.read().unwrap().clone()
+    }
+
+    /// Get all known Producers
+    pub fn get_all_producers(&self) -> BTreeMap<Address, ProducerInfo> {
+        self.producers.read().unwrap().clone()
+    }
+
+    /// Get all known Spawners
+    pub fn get_all_spawners(&self) -> BTreeMap<Address, FlowControlId> {
+        self.spawners.read().unwrap().clone()
+    }
+
+    /// Prints debug information regarding all known Flow Controls
+    pub fn debug_all(&self) {
+        debug!("Flow Controls:");
+        for (address, flow_control_id) in self.get_all_spawners() {
+            debug!(
+                "    Spawner: {} -> {:?}",
+                address.address(),
+                flow_control_id
+            );
+        }
+        for (address, producer) in self.get_all_producers() {
+            debug!("    Producer: {} -> {:?}", address.address(), producer);
+        }
+        for (flow_control_id, consumers) in self.get_all_consumers() {
+            for (address, policy) in consumers.0 {
+                debug!(
+                    "    Consumer: {:?} -> {} ({:?})",
+                    flow_control_id,
+                    address.address(),
+                    policy
+                );
+            }
+        }
+    }
+
     /// Prints debug information regarding Flow Control for the provided address
     #[allow(dead_code)]
     pub fn debug_address(&self, address: &Address) {
@@ -157,6 +226,13 @@ impl FlowControls {
 #[derive(Default, Clone, Debug)]
 pub struct ConsumersInfo(pub(super) BTreeMap<Address, FlowControlPolicy>);
 
+impl ConsumersInfo {
+    /// Consumers' [`Address`]es along with their [`FlowControlPolicy`]
+    pub fn consumers(&self) -> &BTreeMap<Address, FlowControlPolicy> {
+        &self.0
+    }
+}
+
 /// Producer information
 #[derive(Clone, Debug)]
 pub struct ProducerInfo {
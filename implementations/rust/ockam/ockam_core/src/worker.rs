@@ -29,6 +29,15 @@ pub trait Worker: Send + 'static {
         Ok(())
     }
 
+    /// Override restart behaviour.
+    ///
+    /// Called instead of [`Self::initialize`] when a supervised worker is
+    /// restarted after a failure, to re-initialise its state. Messages which
+    /// are still in the worker mailbox are handled after this call.
+    async fn restart(&mut self, context: &mut Self::Context) -> Result<()> {
+        self.initialize(context).await
+    }
+
     /// Try to open and handle a typed message.
     async fn handle_message(
         &mut self,
@@ -304,6 +304,11 @@ impl Attributes {
         self.attrs.iter()
     }
 
+    /// Only keep the attributes whose name satisfies the predicate
+    pub(crate) fn retain(&mut self, mut f: impl FnMut(&str) -> bool) {
+        self.attrs.retain(|k, _| f(k))
+    }
+
     //TODO: review the credential' attributes types.   They are references and has lifetimes,
     //etc,  but in reality this is always just deserizalided (either from wire or from
     //storage), so imho all that just add to the complexity without gaining much
@@ -332,7 +337,8 @@ impl Timestamp {
             .map(|d| Timestamp(d.as_secs()))
     }
 
-    pub(crate) fn add_seconds(&self, seconds: u64) -> Self {
+    /// Return a timestamp located a number of seconds after this one
+    pub fn add_seconds(&self, seconds: u64) -> Self {
         Timestamp(self.0.saturating_add(seconds))
     }
 
@@ -1,7 +1,12 @@
+use crate::credential::{CredentialData, Verified};
 use crate::credentials::credentials_retriever::CredentialsRetriever;
+use crate::credentials::revocation::{CachedRevocationList, RevocationListRetriever};
 use crate::{
     Credential, Credentials, IdentitiesReader, Identity, IdentityError, IdentityIdentifier,
 };
+use core::time::Duration;
+use ockam_core::compat::collections::BTreeSet;
+use ockam_core::compat::string::String;
 use ockam_core::compat::sync::Arc;
 use ockam_core::Result;
 use ockam_node::Context;
@@ -13,6 +18,8 @@ pub struct AuthorityService {
     credentials: Arc<dyn Credentials>,
     identifier: IdentityIdentifier,
     own_credential: Option<Arc<dyn CredentialsRetriever>>,
+    revocation_list: Option<CachedRevocationList>,
+    allowed_attributes: Option<BTreeSet<String>>,
 }
 
 impl AuthorityService {
@@ -28,9 +35,67 @@ impl AuthorityService {
             credentials,
             identifier,
             own_credential,
+            revocation_list: None,
+            allowed_attributes: None,
         }
     }
 
+    /// Only trust this authority for the given attribute names.
+    /// By default an authority is trusted for all the attributes it asserts
+    pub fn with_allowed_attributes(
+        mut self,
+        attributes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_attributes = Some(attributes.into_iter().map(|a| a.into()).collect());
+        self
+    }
+
+    /// Return the identifier of the Authority
+    pub fn identifier(&self) -> &IdentityIdentifier {
+        &self.identifier
+    }
+
+    /// Return the attribute names this authority is trusted for, if it is restricted
+    pub fn allowed_attributes(&self) -> Option<&BTreeSet<String>> {
+        self.allowed_attributes.as_ref()
+    }
+
+    /// Check credentials against the revocation list published by this authority.
+    /// The list is retrieved again once it is older than the refresh interval
+    pub fn with_revocation_list(
+        mut self,
+        retriever: Arc<dyn RevocationListRetriever>,
+        refresh_interval: Duration,
+    ) -> Self {
+        self.revocation_list = Some(CachedRevocationList::new(
+            retriever,
+            refresh_interval,
+            self.credentials.clone(),
+            self.identifier.clone(),
+        ));
+        self
+    }
+
+    /// Return the interval after which the revocation list of this authority is
+    /// retrieved again, if it publishes one
+    pub fn revocation_list_refresh_interval(&self) -> Option<Duration> {
+        self.revocation_list.as_ref().map(|l| l.refresh_interval())
+    }
+
+    /// Retrieve the revocation list of this authority again if it is too old, which
+    /// removes the stored attributes of the subjects it newly revokes.
+    /// The revocation list is retrieved on behalf of `for_identity`
+    pub async fn refresh_revocation_list(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+    ) -> Result<()> {
+        if let Some(revocation_list) = &self.revocation_list {
+            revocation_list.get(ctx, for_identity).await?;
+        }
+        Ok(())
+    }
+
     /// Return the Public Identity of the Authority
     pub async fn identity(&self) -> Result<Identity> {
         self.identities_reader.get_identity(&self.identifier).await
@@ -51,6 +116,52 @@ impl AuthorityService {
         self.credentials
             .verify_credential(for_identity, &[self.identity().await?], credential.clone())
             .await?;
+        self.check_revocation(ctx, for_identity, &credential)
+            .await?;
         Ok(credential)
     }
+
+    /// Verify that a credential was issued to `subject` by this authority and has not been revoked.
+    /// The attributes which this authority is not trusted for are removed from the returned data
+    pub async fn verify_credential(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+        subject: &IdentityIdentifier,
+        credential: Credential,
+    ) -> Result<CredentialData<Verified>> {
+        let mut credential_data = self
+            .credentials
+            .verify_credential(subject, &[self.identity().await?], credential.clone())
+            .await?;
+        self.check_revocation(ctx, for_identity, &credential)
+            .await?;
+
+        if let Some(allowed_attributes) = &self.allowed_attributes {
+            credential_data
+                .attributes
+                .retain(|name| allowed_attributes.contains(name));
+        }
+        Ok(credential_data)
+    }
+
+    /// Return an error if the credential has been revoked by this authority.
+    /// The revocation list is retrieved on behalf of `for_identity` when it needs to be refreshed
+    pub async fn check_revocation(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+        credential: &Credential,
+    ) -> Result<()> {
+        if let Some(revocation_list) = &self.revocation_list {
+            if revocation_list
+                .get(ctx, for_identity)
+                .await?
+                .is_revoked(credential)?
+            {
+                return Err(IdentityError::CredentialRevoked.into());
+            }
+        }
+        Ok(())
+    }
 }
@@ -32,6 +32,21 @@ pub trait Credentials: Send + Sync + 'static {
         authorities: &[Identity],
         credential: Credential,
     ) -> Result<()>;
+
+    /// Store the attributes of an already verified credential sent by a specific identity
+    async fn receive_verified_credential(
+        &self,
+        sender: &IdentityIdentifier,
+        credential_data: CredentialData<Verified>,
+    ) -> Result<()>;
+
+    /// Remove the stored attributes of the given subjects which were attested by `issuer`,
+    /// for example because their credentials have been revoked
+    async fn forget_attested_attributes(
+        &self,
+        issuer: &IdentityIdentifier,
+        subjects: &[IdentityIdentifier],
+    ) -> Result<()>;
 }
 
 #[async_trait]
@@ -102,7 +117,15 @@ impl Credentials for Identities {
         let credential_data = self
             .verify_credential(sender, authorities, credential)
             .await?;
+        self.receive_verified_credential(sender, credential_data)
+            .await
+    }
 
+    async fn receive_verified_credential(
+        &self,
+        sender: &IdentityIdentifier,
+        credential_data: CredentialData<Verified>,
+    ) -> Result<()> {
         self.identities_repository
             .put_attributes(
                 sender,
@@ -117,4 +140,19 @@ impl Credentials for Identities {
 
         Ok(())
     }
+
+    async fn forget_attested_attributes(
+        &self,
+        issuer: &IdentityIdentifier,
+        subjects: &[IdentityIdentifier],
+    ) -> Result<()> {
+        for subject in subjects {
+            if let Some(entry) = self.identities_repository.get_attributes(subject).await? {
+                if entry.attested_by().as_ref() == Some(issuer) {
+                    self.identities_repository.delete(subject).await?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
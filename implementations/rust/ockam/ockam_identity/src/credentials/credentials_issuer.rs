@@ -6,14 +6,18 @@ use ockam_core::compat::boxed::Box;
 use ockam_core::compat::string::String;
 use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
 use ockam_core::flow_control::FlowControls;
-use ockam_core::{api, Result, Route, Routed, Worker};
+use ockam_core::{api, Error, Result, Route, Routed, Worker};
 use ockam_node::{Context, RpcClient};
 
 use crate::alloc::string::ToString;
-use crate::credential::Credential;
+use crate::credential::{Credential, Timestamp, MAX_CREDENTIAL_VALIDITY};
 use crate::identity::IdentityIdentifier;
-use crate::{CredentialData, Identities, IdentitySecureChannelLocalInfo, PROJECT_MEMBER_SCHEMA};
+use crate::{
+    AuthorityRevocationList, CredentialData, Identities, IdentitySecureChannelLocalInfo,
+    RevocationList, PROJECT_MEMBER_SCHEMA,
+};
 
 /// Legacy id for a trust context, it used to be 'project_id', not it is the more general 'trust_context_id'
 /// TODO: DEPRECATE - Removing PROJECT_ID attribute in favor of TRUST_CONTEXT_ID
@@ -27,6 +31,7 @@ pub struct CredentialsIssuer {
     identities: Arc<Identities>,
     issuer: IdentityIdentifier,
     trust_context: String,
+    revocation_list: Option<AuthorityRevocationList>,
 }
 
 impl CredentialsIssuer {
@@ -40,9 +45,23 @@ impl CredentialsIssuer {
             identities,
             issuer,
             trust_context,
+            revocation_list: None,
         })
     }
 
+    /// Publish the revocation list of the authority
+    pub fn with_revocation_list(mut self, revocation_list: AuthorityRevocationList) -> Self {
+        self.revocation_list = Some(revocation_list);
+        self
+    }
+
+    async fn revocation_list(&self) -> Result<RevocationList> {
+        match &self.revocation_list {
+            Some(revocation_list) => revocation_list.get().await,
+            None => Ok(RevocationList::default()),
+        }
+    }
+
     async fn issue_credential(&self, from: &IdentityIdentifier) -> Result<Option<Credential>> {
         match self
             .identities
@@ -52,6 +71,19 @@ impl CredentialsIssuer {
             .await?
         {
             Some(entry) => {
+                // The credential must not outlive the membership of its subject
+                let validity = match entry.expires() {
+                    Some(expires) => {
+                        let now = Timestamp::now().ok_or_else(|| {
+                            Error::new(Origin::Core, Kind::Internal, "invalid system time")
+                        })?;
+                        expires
+                            .elapsed(now)
+                            .unwrap_or_default()
+                            .min(MAX_CREDENTIAL_VALIDITY)
+                    }
+                    None => MAX_CREDENTIAL_VALIDITY,
+                };
                 let crd = entry
                     .attrs()
                     .iter()
@@ -61,7 +93,8 @@ impl CredentialsIssuer {
                         |crd, (a, v)| crd.with_attribute(a, v),
                     )
                     .with_attribute(LEGACY_ID, self.trust_context.as_bytes()) // TODO: DEPRECATE - Removing PROJECT_ID attribute in favor of TRUST_CONTEXT_ID
-                    .with_attribute(TRUST_CONTEXT_ID, self.trust_context.as_bytes());
+                    .with_attribute(TRUST_CONTEXT_ID, self.trust_context.as_bytes())
+                    .valid_for(validity);
                 Ok(Some(
                     self.identities
                         .credentials()
@@ -105,6 +138,10 @@ impl Worker for CredentialsIssuer {
                         Err(error) => api::internal_error(&req, &error.to_string()).to_vec()?,
                     }
                 }
+                (Some(Method::Get), "/revocations") => match self.revocation_list().await {
+                    Ok(list) => Response::ok(req.id()).body(list).to_vec()?,
+                    Err(error) => api::internal_error(&req, &error.to_string()).to_vec()?,
+                },
                 _ => api::unknown_path(&req).to_vec()?,
             };
             c.send(m.return_route(), res).await
@@ -145,6 +182,11 @@ impl CredentialsIssuerClient {
         self.client.request(&Request::post("/")).await
     }
 
+    /// Return the list of credentials revoked by the issuer
+    pub async fn revocation_list(&self) -> Result<RevocationList> {
+        self.client.request(&Request::get("/revocations")).await
+    }
+
     /// Specify the flow controls to use for the RpcClient
     pub fn with_flow_controls(self, flow_controls: &FlowControls) -> Self {
         Self {
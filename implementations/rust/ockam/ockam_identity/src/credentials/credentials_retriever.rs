@@ -1,7 +1,7 @@
 use core::time::Duration;
 
 use serde::{Deserialize, Serialize};
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 use ockam_core::compat::boxed::Box;
 use ockam_core::compat::sync::Arc;
@@ -10,8 +10,8 @@ use ockam_core::{async_trait, route, Address, Result, Route};
 use ockam_node::Context;
 
 use crate::{
-    Credential, CredentialsIssuerClient, IdentityIdentifier, SecureChannelOptions, SecureChannels,
-    TrustMultiIdentifiersPolicy,
+    Credential, CredentialsIssuerClient, IdentityIdentifier, RevocationList,
+    RevocationListRetriever, SecureChannelOptions, SecureChannels, TrustMultiIdentifiersPolicy,
 };
 
 /// Trait for retrieving a credential for a given identity
@@ -71,14 +71,14 @@ impl RemoteCredentialsRetriever {
     }
 }
 
-#[async_trait]
-impl CredentialsRetriever for RemoteCredentialsRetriever {
-    async fn retrieve(
+impl RemoteCredentialsRetriever {
+    /// Create a client for the credentials issuer, over a secure channel started by `for_identity`.
+    /// The address of the secure channel is returned as well
+    async fn issuer_client(
         &self,
         ctx: &Context,
         for_identity: &IdentityIdentifier,
-    ) -> Result<Credential> {
-        debug!("Getting credential from : {}", &self.issuer.route);
+    ) -> Result<(Address, CredentialsIssuerClient)> {
         let resolved_route = ctx
             .resolve_transport_route(&self.flow_controls, self.issuer.route.clone())
             .await?;
@@ -108,16 +108,49 @@ impl CredentialsRetriever for RemoteCredentialsRetriever {
 
         debug!("Created secure channel to project authority");
 
-        let client =
-            CredentialsIssuerClient::new(route![sc, self.issuer.service_address.clone()], ctx)
-                .await?
-                .with_flow_controls(&self.flow_controls);
+        let client = CredentialsIssuerClient::new(
+            route![sc.clone(), self.issuer.service_address.clone()],
+            ctx,
+        )
+        .await?
+        .with_flow_controls(&self.flow_controls);
+        Ok((sc, client))
+    }
+}
 
+#[async_trait]
+impl CredentialsRetriever for RemoteCredentialsRetriever {
+    async fn retrieve(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+    ) -> Result<Credential> {
+        debug!("Getting credential from : {}", &self.issuer.route);
+        let (_, client) = self.issuer_client(ctx, for_identity).await?;
         let credential = client.credential().await?;
         Ok(credential)
     }
 }
 
+#[async_trait]
+impl RevocationListRetriever for RemoteCredentialsRetriever {
+    async fn retrieve_revocation_list(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+    ) -> Result<RevocationList> {
+        debug!("Getting revocation list from : {}", &self.issuer.route);
+        let (sc, client) = self.issuer_client(ctx, for_identity).await?;
+        let revocation_list = client.revocation_list().await;
+
+        // the revocation list is refreshed periodically, so we don't keep the secure channel
+        if let Err(e) = self.secure_channels.stop_secure_channel(ctx, &sc).await {
+            warn!("could not stop the secure channel {sc} to the authority: {e}");
+        }
+        revocation_list
+    }
+}
+
 /// Information necessary to connect to a remote credential retriever
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteCredentialsRetrieverInfo {
@@ -5,14 +5,21 @@ use crate::identity::Identity;
 use crate::secure_channel::IdentitySecureChannelLocalInfo;
 use crate::{IdentityIdentifier, TrustContext};
 use async_trait::async_trait;
+use core::time::Duration;
 use minicbor::Decoder;
 use ockam_core::api::{Request, Response, Status};
 use ockam_core::compat::boxed::Box;
 use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec;
 use ockam_core::errcode::{Kind, Origin};
-use ockam_core::{Address, AllowAll, Error, Mailboxes, Result, Route};
+use ockam_core::{
+    Address, AllowAll, AllowSourceAddress, DenyAll, Error, Mailbox, Mailboxes, Result, Route,
+};
 use ockam_node::api::{request, request_with_local_info};
-use ockam_node::{Context, MessageSendReceiveOptions, WorkerBuilder};
+use ockam_node::{Context, DelayedEvent, MessageSendReceiveOptions, WorkerBuilder};
+
+/// Minimum interval between two periodic retrievals of the revocation lists
+const MIN_REVOCATION_LISTS_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
 
 /// This trait allows an identity to send its credential to another identity
 /// located at the end of a secure channel route
@@ -30,6 +37,20 @@ pub trait CredentialsServer: Send + Sync {
         options: MessageSendReceiveOptions,
     ) -> Result<()>;
 
+    /// Present credential to other party, route shall use secure channel. Other party is expected
+    /// to present its credential in response, otherwise this call errors.
+    /// The credential received in response is verified against the authorities of a trust context,
+    /// possibly retrieving their revocation lists on behalf of `identifier`.
+    async fn present_credential_mutual_with_trust_context(
+        &self,
+        ctx: &Context,
+        route: Route,
+        trust_context: &TrustContext,
+        identifier: &IdentityIdentifier,
+        credential: Credential,
+        options: MessageSendReceiveOptions,
+    ) -> Result<()>;
+
     /// Present credential to other party, route shall use secure channel
     async fn present_credential(
         &self,
@@ -67,47 +88,34 @@ impl CredentialsServer for CredentialsServerModule {
         credential: Credential,
         options: MessageSendReceiveOptions,
     ) -> Result<()> {
-        let path = "actions/present_mutual";
-        let (buf, local_info) = request_with_local_info(
-            ctx,
-            "credential",
-            None,
-            route,
-            Request::post(path).body(credential),
-            options,
-        )
-        .await?;
-
-        let their_id = IdentitySecureChannelLocalInfo::find_info_from_list(&local_info)?
-            .their_identity_id()
-            .clone();
-
-        let mut dec = Decoder::new(&buf);
-        let res: Response = dec.decode()?;
-        match res.status() {
-            Some(Status::Ok) => {}
-            Some(s) => {
-                return Err(Error::new(
-                    Origin::Application,
-                    Kind::Invalid,
-                    format!("credential presentation failed: {}", s),
-                ));
-            }
-            _ => {
-                return Err(Error::new(
-                    Origin::Application,
-                    Kind::Invalid,
-                    "credential presentation failed",
-                ));
-            }
-        }
-
-        let credential: Credential = dec.decode()?;
+        let (their_id, credential) = self
+            .exchange_credentials(ctx, route, credential, options)
+            .await?;
         self.credentials
             .receive_presented_credential(&their_id, authorities, credential)
-            .await?;
+            .await
+    }
 
-        Ok(())
+    /// Present credential to other party, route shall use secure channel. Other party is expected
+    /// to present its credential in response, which is verified with the trust context
+    async fn present_credential_mutual_with_trust_context(
+        &self,
+        ctx: &Context,
+        route: Route,
+        trust_context: &TrustContext,
+        identifier: &IdentityIdentifier,
+        credential: Credential,
+        options: MessageSendReceiveOptions,
+    ) -> Result<()> {
+        let (their_id, credential) = self
+            .exchange_credentials(ctx, route, credential, options)
+            .await?;
+        let credential_data = trust_context
+            .verify_credential(ctx, identifier, &their_id, credential)
+            .await?;
+        self.credentials
+            .receive_verified_credential(&their_id, credential_data)
+            .await
     }
 
     /// Present credential to other party, route shall use secure channel
@@ -149,23 +157,40 @@ impl CredentialsServer for CredentialsServerModule {
         address: Address,
         present_back: bool,
     ) -> Result<()> {
-        let worker = CredentialsServerWorker::new(
+        let main_mailbox = Mailbox::new(
+            address,
+            Arc::new(AllowAll), // We check for Identity secure channel inside the worker
+            Arc::new(AllowAll), // FIXME: @ac Allow to respond anywhere using return_route
+        );
+        let mut additional_mailboxes = vec![];
+
+        // The revocation lists are retrieved periodically, so that the attributes of
+        // revoked subjects are removed even when no credential is presented
+        let refresh_interval = trust_context.revocation_lists_refresh_interval();
+        let mut worker = CredentialsServerWorker::new(
             self.credentials.clone(),
             trust_context,
             identifier,
             present_back,
         );
+        if let Some(refresh_interval) = refresh_interval {
+            let refresh_address = Address::random_tagged("CredentialsServer.revocations");
+            let refresh = DelayedEvent::create(ctx, refresh_address.clone(), vec![]).await?;
+            additional_mailboxes.push(Mailbox::new(
+                refresh_address.clone(),
+                Arc::new(AllowSourceAddress(refresh.address())),
+                Arc::new(DenyAll),
+            ));
+            worker = worker.with_revocation_lists_refresh(
+                refresh_address,
+                refresh,
+                refresh_interval.max(MIN_REVOCATION_LISTS_REFRESH_INTERVAL),
+            );
+        }
 
-        WorkerBuilder::with_mailboxes(
-            Mailboxes::main(
-                address,
-                Arc::new(AllowAll), // We check for Identity secure channel inside the worker
-                Arc::new(AllowAll), // FIXME: @ac Allow to respond anywhere using return_route
-            ),
-            worker,
-        )
-        .start(ctx)
-        .await?;
+        WorkerBuilder::with_mailboxes(Mailboxes::new(main_mailbox, additional_mailboxes), worker)
+            .start(ctx)
+            .await?;
 
         Ok(())
     }
@@ -176,4 +201,51 @@ impl CredentialsServerModule {
     pub fn new(credentials: Arc<dyn Credentials>) -> Self {
         Self { credentials }
     }
+
+    /// Send our credential with a request for a mutual presentation and return the
+    /// credential presented in response, with the identifier of the other party
+    async fn exchange_credentials(
+        &self,
+        ctx: &Context,
+        route: Route,
+        credential: Credential,
+        options: MessageSendReceiveOptions,
+    ) -> Result<(IdentityIdentifier, Credential)> {
+        let path = "actions/present_mutual";
+        let (buf, local_info) = request_with_local_info(
+            ctx,
+            "credential",
+            None,
+            route,
+            Request::post(path).body(credential),
+            options,
+        )
+        .await?;
+
+        let their_id =
+            IdentitySecureChannelLocalInfo::find_info_from_list(&local_info)?.their_identity_id();
+
+        let mut dec = Decoder::new(&buf);
+        let res: Response = dec.decode()?;
+        match res.status() {
+            Some(Status::Ok) => {}
+            Some(s) => {
+                return Err(Error::new(
+                    Origin::Application,
+                    Kind::Invalid,
+                    format!("credential presentation failed: {}", s),
+                ));
+            }
+            _ => {
+                return Err(Error::new(
+                    Origin::Application,
+                    Kind::Invalid,
+                    "credential presentation failed",
+                ));
+            }
+        }
+
+        let credential: Credential = dec.decode()?;
+        Ok((their_id, credential))
+    }
 }
@@ -1,3 +1,4 @@
+use core::time::Duration;
 use minicbor::Decoder;
 use tracing::{debug, error, info, trace, warn};
 
@@ -5,8 +6,8 @@ use ockam_core::api::{Error, Id, Request, Response, ResponseBuilder, Status};
 use ockam_core::async_trait;
 use ockam_core::compat::boxed::Box;
 use ockam_core::compat::{string::ToString, sync::Arc, vec::Vec};
-use ockam_core::{Result, Routed, Worker};
-use ockam_node::Context;
+use ockam_core::{Address, Result, Routed, Worker};
+use ockam_node::{Context, DelayedEvent};
 
 use crate::credential::Credential;
 use crate::credentials::Credentials;
@@ -22,6 +23,14 @@ pub struct CredentialsServerWorker {
     trust_context: TrustContext,
     identifier: IdentityIdentifier,
     present_back: bool,
+    revocation_lists_refresh: Option<RevocationListsRefresh>,
+}
+
+/// Periodic retrieval of the revocation lists of the trust context
+struct RevocationListsRefresh {
+    address: Address,
+    event: DelayedEvent<Vec<u8>>,
+    interval: Duration,
 }
 
 impl CredentialsServerWorker {
@@ -36,11 +45,68 @@ impl CredentialsServerWorker {
             trust_context,
             identifier,
             present_back,
+            revocation_lists_refresh: None,
         }
     }
+
+    /// Retrieve the revocation lists of the trust context every `interval`, when
+    /// receiving the event sent to `address`
+    pub(crate) fn with_revocation_lists_refresh(
+        mut self,
+        address: Address,
+        event: DelayedEvent<Vec<u8>>,
+        interval: Duration,
+    ) -> Self {
+        self.revocation_lists_refresh = Some(RevocationListsRefresh {
+            address,
+            event,
+            interval,
+        });
+        self
+    }
 }
 
 impl CredentialsServerWorker {
+    /// Schedule the next retrieval of the revocation lists
+    async fn schedule_revocation_lists_refresh(&mut self) -> Result<()> {
+        if let Some(refresh) = &mut self.revocation_lists_refresh {
+            refresh.event.schedule(refresh.interval).await?;
+        }
+        Ok(())
+    }
+
+    /// Retrieve the revocation lists which are too old, which removes the attributes
+    /// of the subjects they newly revoke
+    async fn refresh_revocation_lists(&self, ctx: &Context) {
+        for authority in self.trust_context.authority_services() {
+            if let Err(e) = authority
+                .refresh_revocation_list(ctx, &self.identifier)
+                .await
+            {
+                warn!(
+                    "could not refresh the revocation list of {}: {e}",
+                    authority.identifier()
+                );
+            }
+        }
+    }
+
+    /// Verify a presented credential against the trust context and store its attributes
+    async fn receive_presented_credential(
+        &self,
+        ctx: &Context,
+        sender: &IdentityIdentifier,
+        credential: Credential,
+    ) -> Result<()> {
+        let credential_data = self
+            .trust_context
+            .verify_credential(ctx, &self.identifier, sender, credential)
+            .await?;
+        self.credentials
+            .receive_verified_credential(sender, credential_data)
+            .await
+    }
+
     async fn handle_request(
         &mut self,
         ctx: &mut Context,
@@ -78,12 +144,7 @@ impl CredentialsServerWorker {
                 let credential: Credential = dec.decode()?;
 
                 let res = self
-                    .credentials
-                    .receive_presented_credential(
-                        &sender,
-                        self.trust_context.authorities().await?.as_slice(),
-                        credential,
-                    )
+                    .receive_presented_credential(ctx, &sender, credential)
                     .await;
 
                 match res {
@@ -109,12 +170,7 @@ impl CredentialsServerWorker {
 
                 info!("presented credential {}", credential);
                 let res = self
-                    .credentials
-                    .receive_presented_credential(
-                        &sender,
-                        self.trust_context.authorities().await?.as_slice(),
-                        credential,
-                    )
+                    .receive_presented_credential(ctx, &sender, credential)
                     .await;
 
                 if let Err(err) = res {
@@ -169,11 +225,22 @@ impl Worker for CredentialsServerWorker {
     type Message = Vec<u8>;
     type Context = Context;
 
+    async fn initialize(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+        self.schedule_revocation_lists_refresh().await
+    }
+
     async fn handle_message(
         &mut self,
         ctx: &mut Self::Context,
         msg: Routed<Self::Message>,
     ) -> Result<()> {
+        if let Some(refresh) = &self.revocation_lists_refresh {
+            if msg.msg_addr() == refresh.address {
+                self.refresh_revocation_lists(ctx).await;
+                return self.schedule_revocation_lists_refresh().await;
+            }
+        }
+
         let mut dec = Decoder::new(msg.as_body());
         let req: Request = match dec.decode() {
             Ok(r) => r,
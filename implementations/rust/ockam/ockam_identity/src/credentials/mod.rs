@@ -5,6 +5,7 @@ mod credentials_issuer;
 mod credentials_retriever;
 mod credentials_server;
 mod credentials_server_worker;
+mod revocation;
 mod trust_context;
 
 pub use authority_service::*;
@@ -12,4 +13,5 @@ pub use credentials::*;
 pub use credentials_issuer::*;
 pub use credentials_retriever::*;
 pub use credentials_server::*;
+pub use revocation::*;
 pub use trust_context::*;
@@ -0,0 +1,379 @@
+use core::time::Duration;
+
+use minicbor::{Decode, Encode};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::string::{String, ToString};
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Error, Result};
+use ockam_node::compat::asynchronous::{Mutex, RwLock};
+use ockam_node::Context;
+
+use crate::credential::{
+    Credential, CredentialData, Timestamp, Unverified, MAX_CREDENTIAL_VALIDITY,
+};
+use crate::credentials::Credentials;
+use crate::identities::Storage;
+use crate::identity::IdentityIdentifier;
+
+/// Default interval after which a revocation list is retrieved again from its authority
+pub const DEFAULT_REVOCATION_LIST_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Storage namespace used to persist the revocation list of an authority
+const REVOCATIONS_NAMESPACE: &str = "revocations";
+/// Storage key used to persist the revocation list of an authority
+const REVOCATION_LIST_KEY: &str = "revocation_list";
+
+/// List of credentials withdrawn by an authority before their expiration
+///
+/// Credentials can be revoked individually, using the hash of their signed data, or for a
+/// subject, in which case all the credentials issued to that subject until the time of the
+/// revocation are revoked. Since credentials are timestamped with a precision of one second,
+/// a credential issued during the second of a subject revocation is revoked as well.
+///
+/// Entries are pruned once all the credentials they revoke have expired.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode)]
+#[rustfmt::skip]
+#[cbor(map)]
+pub struct RevocationList {
+    /// Revoked subjects, with the time of their revocation
+    #[n(1)] subjects: BTreeMap<IdentityIdentifier, Timestamp>,
+    /// Hex-encoded SHA-256 hashes of the data of revoked credentials, with their expiration time
+    #[n(2)] credentials: BTreeMap<String, Timestamp>,
+}
+
+impl RevocationList {
+    /// Revoke all the credentials issued to a subject until `now`
+    pub fn revoke_subject(&mut self, subject: IdentityIdentifier, now: Timestamp) {
+        self.subjects.insert(subject, now);
+    }
+
+    /// Revoke a specific credential
+    pub fn revoke_credential(&mut self, credential: &Credential) -> Result<()> {
+        let data = CredentialData::<Unverified>::try_from(credential.unverified_data())?;
+        self.credentials
+            .insert(Self::credential_hash(credential), data.expires);
+        Ok(())
+    }
+
+    /// Remove the entries which cannot revoke a valid credential anymore:
+    /// the credentials which have expired, and the subjects revoked for
+    /// longer than the maximum validity of a credential
+    pub fn prune(&mut self, now: Timestamp) {
+        self.subjects.retain(|_, revoked_at| {
+            revoked_at.add_seconds(MAX_CREDENTIAL_VALIDITY.as_secs()) > now
+        });
+        self.credentials.retain(|_, expires| *expires > now);
+    }
+
+    /// Return the subjects which have been revoked, with the time of their revocation
+    pub fn subjects(&self) -> &BTreeMap<IdentityIdentifier, Timestamp> {
+        &self.subjects
+    }
+
+    /// Return the hashes of the credentials which have been revoked, with their expiration time
+    pub fn credentials(&self) -> &BTreeMap<String, Timestamp> {
+        &self.credentials
+    }
+
+    /// Return true if the list does not revoke anything
+    pub fn is_empty(&self) -> bool {
+        self.subjects.is_empty() && self.credentials.is_empty()
+    }
+
+    /// Return true if the credential is part of this list, either directly or
+    /// because it was issued to a revoked subject until its revocation
+    pub fn is_revoked(&self, credential: &Credential) -> Result<bool> {
+        if self
+            .credentials
+            .contains_key(&Self::credential_hash(credential))
+        {
+            return Ok(true);
+        }
+        let data = CredentialData::<Unverified>::try_from(credential.unverified_data())?;
+        Ok(self
+            .subjects
+            .get(data.unverified_subject())
+            .map(|revoked_at| data.created <= *revoked_at)
+            .unwrap_or(false))
+    }
+
+    /// Return the hash identifying a credential in a revocation list
+    pub fn credential_hash(credential: &Credential) -> String {
+        hex::encode(Sha256::digest(credential.unverified_data()))
+    }
+}
+
+/// Trait for retrieving the revocation list published by an authority
+#[async_trait]
+pub trait RevocationListRetriever: Send + Sync + 'static {
+    /// Retrieve the current revocation list, on behalf of a given identity
+    async fn retrieve_revocation_list(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+    ) -> Result<RevocationList>;
+}
+
+/// Revocation list maintained by an authority and persisted in a storage
+#[derive(Clone)]
+pub struct AuthorityRevocationList {
+    storage: Arc<dyn Storage>,
+    lock: Arc<Mutex<()>>,
+}
+
+impl AuthorityRevocationList {
+    /// Create a new revocation list persisted in the given storage
+    pub fn new(storage: Arc<dyn Storage>) -> Self {
+        Self {
+            storage,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Return the current revocation list, without the entries which have become useless
+    pub async fn get(&self) -> Result<RevocationList> {
+        let mut list = match self
+            .storage
+            .get(REVOCATIONS_NAMESPACE, REVOCATION_LIST_KEY)
+            .await?
+        {
+            Some(bytes) => minicbor::decode(bytes.as_slice())?,
+            None => RevocationList::default(),
+        };
+        list.prune(now()?);
+        Ok(list)
+    }
+
+    /// Revoke all the credentials issued to a subject until now
+    pub async fn revoke_subject(&self, subject: &IdentityIdentifier) -> Result<()> {
+        let now = now()?;
+        self.update(|list| {
+            list.revoke_subject(subject.clone(), now);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Revoke a specific credential
+    pub async fn revoke_credential(&self, credential: &Credential) -> Result<()> {
+        self.update(|list| list.revoke_credential(credential)).await
+    }
+
+    /// Update the persisted list, pruning it at the same time
+    async fn update(&self, f: impl FnOnce(&mut RevocationList) -> Result<()>) -> Result<()> {
+        let _guard = self.lock.lock().await;
+        let mut list = self.get().await?;
+        f(&mut list)?;
+        self.storage
+            .set(
+                REVOCATIONS_NAMESPACE,
+                REVOCATION_LIST_KEY.to_string(),
+                minicbor::to_vec(&list)?,
+            )
+            .await
+    }
+}
+
+fn now() -> Result<Timestamp> {
+    Timestamp::now()
+        .ok_or_else(|| Error::new(Origin::Identity, Kind::Invalid, "invalid system time"))
+}
+
+#[async_trait]
+impl RevocationListRetriever for AuthorityRevocationList {
+    async fn retrieve_revocation_list(
+        &self,
+        _ctx: &Context,
+        _for_identity: &IdentityIdentifier,
+    ) -> Result<RevocationList> {
+        self.get().await
+    }
+}
+
+/// Revocation list retrieved from an authority and cached for a configurable interval
+///
+/// Every time the list is retrieved, the attributes stored for the subjects it newly revokes
+/// are removed, since they were verified with a list which did not revoke them yet. On the
+/// first retrieval, the attributes of all the revoked subjects are removed, so a subject
+/// enrolled again after its revocation has to present its new credential again.
+#[derive(Clone)]
+pub(crate) struct CachedRevocationList {
+    retriever: Arc<dyn RevocationListRetriever>,
+    refresh_interval: Duration,
+    credentials: Arc<dyn Credentials>,
+    issuer: IdentityIdentifier,
+    cached: Arc<RwLock<Option<(RevocationList, Timestamp)>>>,
+}
+
+impl CachedRevocationList {
+    pub(crate) fn new(
+        retriever: Arc<dyn RevocationListRetriever>,
+        refresh_interval: Duration,
+        credentials: Arc<dyn Credentials>,
+        issuer: IdentityIdentifier,
+    ) -> Self {
+        Self {
+            retriever,
+            refresh_interval,
+            credentials,
+            issuer,
+            cached: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Return the interval after which the list is retrieved again
+    pub(crate) fn refresh_interval(&self) -> Duration {
+        self.refresh_interval
+    }
+
+    /// Return the cached revocation list, refreshing it first if it is too old.
+    /// If the list cannot be refreshed the previous one is used, if there is one.
+    pub(crate) async fn get(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+    ) -> Result<RevocationList> {
+        let now = Timestamp::now();
+        if let (Some((list, retrieved_at)), Some(now)) = (self.cached.read().await.as_ref(), now) {
+            if now
+                .elapsed(*retrieved_at)
+                .map(|elapsed| elapsed < self.refresh_interval)
+                .unwrap_or(false)
+            {
+                return Ok(list.clone());
+            }
+        }
+
+        let mut cached = self.cached.write().await;
+        match self
+            .retriever
+            .retrieve_revocation_list(ctx, for_identity)
+            .await
+        {
+            Ok(list) => {
+                debug!("retrieved a revocation list");
+                let previous = cached.as_ref().map(|(previous, _)| previous);
+                let revoked_subjects: Vec<IdentityIdentifier> = list
+                    .subjects()
+                    .iter()
+                    .filter(|(subject, revoked_at)| {
+                        previous.and_then(|p| p.subjects().get(*subject)) != Some(*revoked_at)
+                    })
+                    .map(|(subject, _)| subject.clone())
+                    .collect();
+                // the list is only cached once the attributes are removed, so that
+                // it is done again on the next retrieval otherwise
+                if !revoked_subjects.is_empty() {
+                    self.credentials
+                        .forget_attested_attributes(&self.issuer, &revoked_subjects)
+                        .await?;
+                }
+                if let Some(now) = now {
+                    *cached = Some((list.clone(), now));
+                }
+                Ok(list)
+            }
+            Err(e) => match cached.as_ref() {
+                Some((list, _)) => {
+                    warn!("could not refresh the revocation list, using the cached one: {e}");
+                    Ok(list.clone())
+                }
+                None => Err(e),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::identities::InMemoryStorage;
+    use crate::CredentialData;
+
+    fn credential(subject: &IdentityIdentifier, created: Timestamp) -> Credential {
+        let mut data = CredentialData::builder(subject.clone(), subject.clone())
+            .build()
+            .unwrap();
+        data.created = created;
+        Credential::new(minicbor::to_vec(data).unwrap(), vec![])
+    }
+
+    #[test]
+    fn test_revoke_subject() {
+        let subject = IdentityIdentifier::from_key_id("subject");
+        let other = IdentityIdentifier::from_key_id("other");
+        let now = Timestamp::now().unwrap();
+        let mut list = RevocationList::default();
+        list.revoke_subject(subject.clone(), now.add_seconds(10));
+
+        assert!(list.is_revoked(&credential(&subject, now)).unwrap());
+        assert!(list
+            .is_revoked(&credential(&subject, now.add_seconds(9)))
+            .unwrap());
+        // a credential issued during the second of the revocation is revoked too
+        assert!(list
+            .is_revoked(&credential(&subject, now.add_seconds(10)))
+            .unwrap());
+        assert!(!list
+            .is_revoked(&credential(&subject, now.add_seconds(11)))
+            .unwrap());
+        assert!(!list.is_revoked(&credential(&other, now)).unwrap());
+    }
+
+    #[test]
+    fn test_revoke_credential() {
+        let subject = IdentityIdentifier::from_key_id("subject");
+        let now = Timestamp::now().unwrap();
+        let revoked = credential(&subject, now);
+        let mut list = RevocationList::default();
+        list.revoke_credential(&revoked).unwrap();
+
+        assert!(list.is_revoked(&revoked).unwrap());
+        assert!(!list
+            .is_revoked(&credential(&subject, now.add_seconds(1)))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_prune() {
+        let subject = IdentityIdentifier::from_key_id("subject");
+        let other = IdentityIdentifier::from_key_id("other");
+        let now = Timestamp::now().unwrap();
+        let validity = MAX_CREDENTIAL_VALIDITY.as_secs();
+        let revoked = credential(&subject, now);
+        let mut list = RevocationList::default();
+        list.revoke_subject(subject.clone(), now);
+        list.revoke_subject(other.clone(), now.add_seconds(10));
+        list.revoke_credential(&revoked).unwrap();
+
+        list.prune(now.add_seconds(validity - 1));
+        assert_eq!(list.subjects().len(), 2);
+        assert_eq!(list.credentials().len(), 1);
+
+        // the revoked credential expires with the maximum validity
+        list.prune(now.add_seconds(validity + 1));
+        assert!(!list.subjects().contains_key(&subject));
+        assert!(list.subjects().contains_key(&other));
+        assert!(list.credentials().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_authority_revocation_list_is_persisted() -> Result<()> {
+        let storage = InMemoryStorage::create();
+        let subject = IdentityIdentifier::from_key_id("subject");
+        let revocations = AuthorityRevocationList::new(storage.clone());
+        assert!(revocations.get().await?.is_empty());
+
+        revocations.revoke_subject(&subject).await?;
+
+        let list = AuthorityRevocationList::new(storage).get().await?;
+        assert!(list.subjects().contains_key(&subject));
+        Ok(())
+    }
+}
@@ -1,23 +1,37 @@
+use core::time::Duration;
 use ockam_core::compat::string::String;
 use ockam_core::compat::vec::Vec;
 use ockam_core::Result;
 
-use crate::{AuthorityService, Identity, IdentityError};
+use ockam_node::Context;
+
+use crate::credential::{Credential, CredentialData, Unverified, Verified};
+use crate::{AuthorityService, Identity, IdentityError, IdentityIdentifier};
 
 /// A trust context defines which authorities are trusted to attest to which attributes, within a context.
-/// Our first implementation assumes that there is only one authority and it is trusted to attest to all attributes within this context.
+/// Several authorities can be trusted at once, for example during the migration from one
+/// authority node to another. Each of them can be restricted to a set of attributes.
 #[derive(Clone)]
 pub struct TrustContext {
     /// This is the ID of the trust context; which is primarily used for ABAC policies
     id: String,
-    /// Authority capable of retrieving credentials
-    authority: Option<AuthorityService>,
+    /// Authorities trusted in this context. The first one is capable of retrieving our own credentials
+    authorities: Vec<AuthorityService>,
 }
 
 impl TrustContext {
     /// Create a new Trust Context
     pub fn new(id: String, authority: Option<AuthorityService>) -> Self {
-        Self { id, authority }
+        Self {
+            id,
+            authorities: authority.into_iter().collect(),
+        }
+    }
+
+    /// Trust an additional authority in this Trust Context
+    pub fn with_authority(mut self, authority: AuthorityService) -> Self {
+        self.authorities.push(authority);
+        self
     }
 
     /// Return the ID of the Trust Context
@@ -25,15 +39,82 @@ impl TrustContext {
         &self.id
     }
 
-    /// Return the Authority of the Trust Context
+    /// Return the main Authority of the Trust Context, used to retrieve our own credential
     pub fn authority(&self) -> Result<&AuthorityService> {
-        self.authority
-            .as_ref()
+        self.authorities
+            .first()
             .ok_or_else(|| IdentityError::UnknownAuthority.into())
     }
 
+    /// Return all the Authorities of the Trust Context
+    pub fn authority_services(&self) -> &[AuthorityService] {
+        &self.authorities
+    }
+
+    /// Return the shortest interval after which the revocation list of an authority of
+    /// this Trust Context is retrieved again, if any authority publishes one
+    pub fn revocation_lists_refresh_interval(&self) -> Option<Duration> {
+        self.authorities
+            .iter()
+            .filter_map(|a| a.revocation_list_refresh_interval())
+            .min()
+    }
+
     /// Return the authority identities attached to this trust context
     pub async fn authorities(&self) -> Result<Vec<Identity>> {
-        Ok(vec![self.authority()?.identity().await?])
+        if self.authorities.is_empty() {
+            return Err(IdentityError::UnknownAuthority.into());
+        }
+        let mut identities = Vec::with_capacity(self.authorities.len());
+        for authority in &self.authorities {
+            identities.push(authority.identity().await?);
+        }
+        Ok(identities)
+    }
+
+    /// Verify a credential issued to `subject` by one of the authorities of this trust context.
+    /// The returned data only contains the attributes that the issuing authority is trusted for.
+    /// The revocation list of the authority is retrieved on behalf of `for_identity` if necessary
+    pub async fn verify_credential(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+        subject: &IdentityIdentifier,
+        credential: Credential,
+    ) -> Result<CredentialData<Verified>> {
+        let issuer = CredentialData::<Unverified>::try_from(credential.unverified_data())?
+            .unverified_issuer()
+            .clone();
+        self.authority_for(&issuer)?
+            .verify_credential(ctx, for_identity, subject, credential)
+            .await
+    }
+
+    /// Return an error if the credential has been revoked by its issuing authority
+    pub async fn check_revocation(
+        &self,
+        ctx: &Context,
+        for_identity: &IdentityIdentifier,
+        credential: &Credential,
+    ) -> Result<()> {
+        let issuer = CredentialData::<Unverified>::try_from(credential.unverified_data())?
+            .unverified_issuer()
+            .clone();
+        match self.authority_for(&issuer) {
+            Ok(authority) => {
+                authority
+                    .check_revocation(ctx, for_identity, credential)
+                    .await
+            }
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Return the authority with a given identifier
+    fn authority_for(&self, identifier: &IdentityIdentifier) -> Result<&AuthorityService> {
+        self.authorities
+            .iter()
+            .find(|a| a.identifier() == identifier)
+            .ok_or_else(|| IdentityError::UnknownAuthority.into())
     }
 }
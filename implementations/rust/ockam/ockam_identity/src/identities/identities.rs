@@ -1,9 +1,10 @@
 use crate::identities::{IdentitiesKeys, IdentitiesRepository, IdentitiesVault};
 use crate::{
     Credentials, CredentialsServer, CredentialsServerModule, IdentitiesBuilder, IdentitiesCreation,
-    IdentitiesReader, IdentitiesStorage,
+    IdentitiesReader, IdentitiesStorage, Identity, IdentityIdentifier,
 };
 use ockam_core::compat::sync::Arc;
+use ockam_core::Result;
 use ockam_vault::Vault;
 
 /// This struct supports all the services related to identities
@@ -51,6 +52,20 @@ impl Identities {
     pub fn credentials_server(&self) -> Arc<dyn CredentialsServer> {
         Arc::new(CredentialsServerModule::new(self.credentials()))
     }
+
+    /// Rotate the root key of a persisted identity and store its new change history.
+    ///
+    /// The identifier of the identity does not change. The new change history is sent
+    /// to peers during the next secure channel handshakes, and credentials issued
+    /// by this identity from now on are signed with the new key.
+    pub async fn rotate_root_key(&self, identifier: &IdentityIdentifier) -> Result<Identity> {
+        let mut identity = self.repository().get_identity(identifier).await?;
+        self.identities_keys()
+            .rotate_root_key(&mut identity)
+            .await?;
+        self.repository().update_identity(&identity).await?;
+        Ok(identity)
+    }
 }
 
 impl Identities {
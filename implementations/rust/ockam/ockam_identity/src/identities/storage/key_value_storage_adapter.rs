@@ -0,0 +1,99 @@
+use crate::Storage;
+use core::ops::Bound;
+use ockam_core::async_trait;
+use ockam_core::compat::{
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use ockam_core::Result;
+use ockam_node::{KeyRange, KeyValueStorage};
+
+/// Key of an entry: a namespace and an identifier
+pub type StorageKey = (String, String);
+
+/// Implementation of [`Storage`] on top of a [`KeyValueStorage`]
+///
+/// This allows identities and their attributes to be stored in any key
+/// value storage backend, for example a `SqliteKeyValueStorage`.
+/// Entries are keyed by namespace first, so that listing the identifiers
+/// of a namespace only scans the entries of that namespace.
+#[derive(Clone)]
+pub struct KeyValueStorageAdapter {
+    storage: Arc<dyn KeyValueStorage<StorageKey, Vec<u8>>>,
+}
+
+impl KeyValueStorageAdapter {
+    /// Constructor
+    pub fn new(storage: Arc<dyn KeyValueStorage<StorageKey, Vec<u8>>>) -> Self {
+        Self { storage }
+    }
+
+    /// Constructor
+    pub fn create(storage: Arc<dyn KeyValueStorage<StorageKey, Vec<u8>>>) -> Arc<Self> {
+        Arc::new(Self::new(storage))
+    }
+
+    /// Return the underlying key value storage
+    pub fn key_value_storage(&self) -> Arc<dyn KeyValueStorage<StorageKey, Vec<u8>>> {
+        self.storage.clone()
+    }
+}
+
+#[async_trait]
+impl Storage for KeyValueStorageAdapter {
+    async fn get(&self, id: &str, namespace: &str) -> Result<Option<Vec<u8>>> {
+        self.storage
+            .get(&(namespace.to_string(), id.to_string()))
+            .await
+    }
+
+    async fn set(&self, id: &str, namespace: String, val: Vec<u8>) -> Result<()> {
+        self.storage.put((namespace, id.to_string()), val).await
+    }
+
+    async fn del(&self, id: &str, namespace: &str) -> Result<()> {
+        self.storage
+            .delete(&(namespace.to_string(), id.to_string()))
+            .await?;
+        Ok(())
+    }
+
+    async fn keys(&self, namespace: &str) -> Result<Vec<String>> {
+        // (namespace + "\0", "") is the smallest key greater than all the keys of the namespace
+        let range = KeyRange::new(
+            Bound::Included((namespace.to_string(), String::new())),
+            Bound::Excluded((format!("{namespace}\0"), String::new())),
+        );
+        Ok(self
+            .storage
+            .range(range)
+            .await?
+            .into_iter()
+            .map(|((_, id), _)| id)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ockam_node::InMemoryKeyValueStorage;
+
+    #[tokio::test]
+    async fn test_keys_are_scoped_by_namespace() -> Result<()> {
+        let storage = KeyValueStorageAdapter::new(InMemoryKeyValueStorage::create());
+        storage.set("id1", "attributes".into(), vec![1]).await?;
+        storage.set("id2", "attributes".into(), vec![2]).await?;
+        storage.set("id3", "attributes_2".into(), vec![3]).await?;
+        storage.set("id4", "attribute".into(), vec![4]).await?;
+
+        assert_eq!(storage.keys("attributes").await?, vec!["id1", "id2"]);
+        assert_eq!(storage.get("id3", "attributes_2").await?, Some(vec![3]));
+
+        storage.del("id1", "attributes").await?;
+        assert_eq!(storage.keys("attributes").await?, vec!["id2"]);
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Storage;
+use crate::{Storage, StorageKey};
 use core::str;
 use lmdb::{Cursor, Database, Environment, Transaction};
 use ockam_core::async_trait;
@@ -9,11 +9,12 @@ use ockam_core::compat::vec::Vec;
 use ockam_core::errcode::{Kind, Origin};
 use ockam_core::{Error, Result};
 use ockam_node::tokio::task::{self, JoinError};
+use ockam_node::{KeyValueStorage, WriteBatch};
 use std::fmt;
 use std::path::Path;
 use tokio_retry::strategy::{jitter, FixedInterval};
 use tokio_retry::Retry;
-use tracing::debug;
+use tracing::{debug, warn};
 
 /// Storage using the LMDB database
 #[derive(Clone)]
@@ -88,6 +89,45 @@ impl LmdbStorage {
         };
         task::spawn_blocking(t).await.map_err(map_join_err)?
     }
+
+    /// Return all the raw entries of the database
+    pub async fn entries(&self) -> Result<Vec<(String, Vec<u8>)>> {
+        let d = self.clone();
+        let t = move || {
+            let r = d.env.begin_ro_txn().map_err(map_lmdb_err)?;
+            let mut cursor = r.open_ro_cursor(d.map).map_err(map_lmdb_err)?;
+            cursor
+                .iter()
+                .map(|r| {
+                    let (k, v) = r.map_err(map_lmdb_err)?;
+                    let k = str::from_utf8(k)
+                        .map_err(|e| Error::new(Origin::Application, Kind::Io, e))?;
+                    Ok((k.to_string(), v.to_vec()))
+                })
+                .collect()
+        };
+        task::spawn_blocking(t).await.map_err(map_join_err)?
+    }
+
+    /// Copy all the entries of this database to a key value storage, in a single batch,
+    /// and return the number of copied entries.
+    ///
+    /// The resulting key value storage can then be used as a [`Storage`] with a
+    /// [`KeyValueStorageAdapter`](crate::KeyValueStorageAdapter).
+    pub async fn migrate_to(&self, to: &dyn KeyValueStorage<StorageKey, Vec<u8>>) -> Result<usize> {
+        let mut batch = WriteBatch::new();
+        for (key, value) in self.entries().await? {
+            match key.rsplit_once(':') {
+                Some((id, namespace)) => {
+                    batch.put((namespace.to_string(), id.to_string()), value);
+                }
+                None => warn!("skipping the LMDB entry {key} which has no namespace"),
+            }
+        }
+        let count = batch.len();
+        to.write_batch(batch).await?;
+        Ok(count)
+    }
 }
 
 #[async_trait]
@@ -140,3 +180,33 @@ fn map_join_err(err: JoinError) -> Error {
 fn map_lmdb_err(err: lmdb::Error) -> Error {
     Error::new(Origin::Application, Kind::Io, err)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::KeyValueStorageAdapter;
+    use ockam_core::compat::rand::random;
+    use ockam_node::InMemoryKeyValueStorage;
+
+    #[tokio::test]
+    async fn test_migrate_to_key_value_storage() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("lmdb-{}", random::<u64>()));
+        let lmdb = LmdbStorage::new(&path).await?;
+        lmdb.set("id1", "attributes".into(), vec![1]).await?;
+        lmdb.set("id2", "attributes".into(), vec![2]).await?;
+        lmdb.set("id1", "identities".into(), vec![3]).await?;
+
+        let kv = InMemoryKeyValueStorage::create();
+        assert_eq!(lmdb.migrate_to(kv.as_ref()).await?, 3);
+
+        let storage = KeyValueStorageAdapter::new(kv);
+        assert_eq!(
+            storage.keys("attributes").await?,
+            lmdb.keys("attributes").await?
+        );
+        assert_eq!(storage.get("id1", "identities").await?, Some(vec![3]));
+
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 mod attributes_entry;
 mod identities_repository;
+mod key_value_storage_adapter;
 /// LMDB implementation of the Storage trait
 #[cfg(feature = "std")]
 pub mod lmdb_storage;
@@ -8,6 +9,7 @@ mod storage;
 
 pub use attributes_entry::*;
 pub use identities_repository::*;
+pub use key_value_storage_adapter::*;
 
 #[cfg(feature = "std")]
 pub use lmdb_storage::*;
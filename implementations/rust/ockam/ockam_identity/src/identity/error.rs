@@ -46,6 +46,8 @@ pub enum IdentityError {
     SecureChannelNotFound,
     /// FlowControls setup inconsistency
     FlowControlsInconsistency,
+    /// `Credential` was revoked by its authority
+    CredentialRevoked,
 }
 
 impl ockam_core::compat::error::Error for IdentityError {}
@@ -15,6 +15,7 @@ use crate::{
 };
 use core::time::Duration;
 use ockam_core::compat::boxed::Box;
+use ockam_core::compat::string::ToString;
 use ockam_core::compat::sync::Arc;
 use ockam_core::compat::vec::Vec;
 use ockam_core::Result;
@@ -28,6 +29,16 @@ use ockam_node::{Context, MessageReceiveOptions, WorkerBuilder};
 use ockam_vault::Signature;
 use tracing::{debug, info, warn};
 
+/// Number of secure channel handshakes which completed successfully
+pub const SECURE_CHANNEL_HANDSHAKES: &str = "ockam_secure_channel_handshakes_total";
+/// Number of secure channel handshakes which failed
+pub const SECURE_CHANNEL_HANDSHAKE_FAILURES: &str = "ockam_secure_channel_handshake_failures_total";
+
+fn record_handshake(ctx: &Context, name: &'static str, role: &Role) {
+    ctx.metrics()
+        .increment_counter(name, &[("role", role.str().to_string())], 1);
+}
+
 pub(crate) struct DecryptorWorker {
     state: Option<State>,
 }
@@ -345,6 +356,8 @@ impl IdentityExchangeState {
             .start(ctx)
             .await?;
 
+        record_handshake(ctx, SECURE_CHANNEL_HANDSHAKES, &self.role);
+
         info!(
             "Initialized SecureChannel {} at local: {}, remote: {}",
             self.role.str(),
@@ -613,9 +626,14 @@ impl Worker for DecryptorWorker {
                     Role::Initiator => None,
                     Role::Responder => state.initial_responder_payload.take(),
                 };
-                state
+                let role = state.role.clone();
+                let result = state
                     .handle_key_exchange(ctx, init_payload.as_deref())
-                    .await?
+                    .await;
+                if result.is_err() {
+                    record_handshake(ctx, SECURE_CHANNEL_HANDSHAKE_FAILURES, &role);
+                }
+                result?
             }
             _ => {
                 return Err(IdentityError::InvalidSecureChannelInternalState.into());
@@ -663,8 +681,10 @@ impl Worker for DecryptorWorker {
         let new_state = match state {
             State::KeyExchange(state) => {
                 if msg_addr == state.addresses.decryptor_remote {
+                    let role = state.role.clone();
                     let result = state.handle_key_exchange_msg(ctx, msg).await;
                     if result.is_err() {
+                        record_handshake(ctx, SECURE_CHANNEL_HANDSHAKE_FAILURES, &role);
                         if let Err(err) = ctx.stop_worker(msg_addr.clone()).await {
                             warn!("cannot stop decryptor: {err} using address {msg_addr}");
                         }
@@ -678,8 +698,10 @@ impl Worker for DecryptorWorker {
             }
             State::IdentityExchange(state) => {
                 if msg_addr == state.addresses.decryptor_remote {
+                    let role = state.role.clone();
                     let result = state.handle_exchange_identity(ctx, msg).await;
                     if result.is_err() {
+                        record_handshake(ctx, SECURE_CHANNEL_HANDSHAKE_FAILURES, &role);
                         if let Err(err) = ctx.stop_worker(msg_addr.clone()).await {
                             warn!("cannot stop decryptor: {err} using address {msg_addr}");
                         }
@@ -22,6 +22,7 @@ pub(crate) use addresses::*;
 pub use api::*;
 pub(crate) use common::*;
 pub(crate) use decryptor_worker::*;
+pub use decryptor_worker::{SECURE_CHANNEL_HANDSHAKES, SECURE_CHANNEL_HANDSHAKE_FAILURES};
 pub(crate) use listener::*;
 pub use local_info::*;
 pub use options::*;
@@ -5,8 +5,8 @@ use ockam_core::{route, Address, AllowAll, Any, DenyAll, Mailboxes, Result, Rout
 use ockam_identity::secure_channels::secure_channels;
 use ockam_identity::{
     DecryptionResponse, EncryptionRequest, EncryptionResponse, IdentityAccessControlBuilder,
-    IdentitySecureChannelLocalInfo, SecureChannelListenerOptions, SecureChannelOptions,
-    TrustEveryonePolicy, TrustIdentifierPolicy,
+    IdentityHistoryComparison, IdentitySecureChannelLocalInfo, SecureChannelListenerOptions,
+    SecureChannelOptions, TrustEveryonePolicy, TrustIdentifierPolicy,
 };
 use ockam_node::{Context, WorkerBuilder};
 use tokio::time::sleep;
@@ -77,6 +77,94 @@ async fn test_channel(ctx: &mut Context) -> Result<()> {
     ctx.stop().await
 }
 
+#[ockam_macros::test]
+async fn test_channel_propagates_rotated_identity(ctx: &mut Context) -> Result<()> {
+    let alice_secure_channels = secure_channels();
+    let bob_secure_channels = secure_channels();
+
+    let alice = alice_secure_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?;
+    let bob = bob_secure_channels
+        .identities()
+        .identities_creation()
+        .create_identity()
+        .await?;
+
+    bob_secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &bob.identifier(),
+            "bob_listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+
+    let mut child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+
+    // once a message went through a channel, Bob has received Alice's identity
+    let channel = alice_secure_channels
+        .create_secure_channel(
+            ctx,
+            &alice.identifier(),
+            route!["bob_listener"],
+            SecureChannelOptions::new(),
+        )
+        .await?;
+    child_ctx
+        .send(route![channel.clone(), "child"], "Hello, Bob!".to_string())
+        .await?;
+    child_ctx.receive::<String>().await?;
+    alice_secure_channels
+        .stop_secure_channel(ctx, &channel)
+        .await?;
+
+    let known = bob_secure_channels
+        .identities()
+        .repository()
+        .get_identity(&alice.identifier())
+        .await?;
+    assert_eq!(known.compare(&alice), IdentityHistoryComparison::Equal);
+
+    let rotated = alice_secure_channels
+        .identities()
+        .rotate_root_key(&alice.identifier())
+        .await?;
+    assert_eq!(rotated.identifier(), alice.identifier());
+    assert_eq!(rotated.compare(&alice), IdentityHistoryComparison::Newer);
+
+    // the next handshake updates the copy of Alice's identity known by Bob
+    let channel = alice_secure_channels
+        .create_secure_channel(
+            ctx,
+            &alice.identifier(),
+            route!["bob_listener"],
+            SecureChannelOptions::new(),
+        )
+        .await?;
+    child_ctx
+        .send(route![channel, "child"], "Hello again, Bob!".to_string())
+        .await?;
+    child_ctx.receive::<String>().await?;
+
+    let known = bob_secure_channels
+        .identities()
+        .repository()
+        .get_identity(&alice.identifier())
+        .await?;
+    assert_eq!(known.compare(&rotated), IdentityHistoryComparison::Equal);
+
+    ctx.stop().await
+}
+
 #[ockam_macros::test]
 async fn test_channel_send_multiple_messages_both_directions(ctx: &mut Context) -> Result<()> {
     let secure_channels = secure_channels();
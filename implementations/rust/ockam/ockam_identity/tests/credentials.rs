@@ -6,8 +6,9 @@ use ockam_core::{async_trait, AllowAll, Any, DenyAll, Mailboxes};
 use ockam_core::{route, Result, Routed, Worker};
 use ockam_identity::secure_channels::secure_channels;
 use ockam_identity::{
-    AuthorityService, CredentialAccessControl, CredentialData, CredentialsMemoryRetriever,
-    SecureChannelListenerOptions, SecureChannelOptions, TrustContext, TrustIdentifierPolicy,
+    AuthorityRevocationList, AuthorityService, CredentialAccessControl, CredentialData,
+    CredentialsMemoryRetriever, InMemoryStorage, SecureChannelListenerOptions,
+    SecureChannelOptions, TrustContext, TrustIdentifierPolicy,
 };
 use ockam_node::{Context, MessageSendReceiveOptions, WorkerBuilder};
 
@@ -92,6 +93,183 @@ async fn full_flow_oneway(ctx: &mut Context) -> Result<()> {
     ctx.stop().await
 }
 
+#[ockam_macros::test]
+async fn full_flow_oneway_revoked_credential(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities = secure_channels.identities();
+    let identities_creation = identities.identities_creation();
+    let identities_repository = identities.repository();
+    let credentials = identities.credentials();
+    let credentials_service = identities.credentials_server();
+
+    let authority = identities_creation.create_identity().await?;
+    let server = identities_creation.create_identity().await?;
+    let client = identities_creation.create_identity().await?;
+
+    secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &server.identifier(),
+            "listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+
+    let revocation_list = AuthorityRevocationList::new(InMemoryStorage::create());
+    let trust_context = TrustContext::new(
+        "test_trust_context_id".to_string(),
+        Some(
+            AuthorityService::new(
+                secure_channels.identities().identities_reader(),
+                secure_channels.identities().credentials(),
+                authority.identifier(),
+                None,
+            )
+            .with_revocation_list(Arc::new(revocation_list.clone()), Duration::ZERO),
+        ),
+    );
+
+    credentials_service
+        .start(
+            ctx,
+            trust_context,
+            server.identifier(),
+            "credential_exchange".into(),
+            false,
+        )
+        .await?;
+
+    let channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            &client.identifier(),
+            route!["listener"],
+            SecureChannelOptions::new()
+                .with_trust_policy(TrustIdentifierPolicy::new(server.identifier().clone())),
+        )
+        .await?;
+
+    let credential_data = CredentialData::builder(client.identifier(), authority.identifier())
+        .with_attribute("is_superuser", b"true")
+        .build()?;
+
+    let credential = credentials
+        .issue_credential(&authority.identifier(), credential_data)
+        .await?;
+
+    // the credentials issued during the second of the revocation are revoked too
+    revocation_list.revoke_subject(&client.identifier()).await?;
+
+    let res = credentials_service
+        .present_credential(
+            ctx,
+            route![channel, "credential_exchange"],
+            credential,
+            MessageSendReceiveOptions::new(),
+        )
+        .await;
+    assert!(res.is_err());
+
+    let attrs = identities_repository
+        .get_attributes(&client.identifier())
+        .await?;
+    assert!(attrs.is_none());
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn full_flow_oneway_several_authorities(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities = secure_channels.identities();
+    let identities_creation = identities.identities_creation();
+    let identities_repository = identities.repository();
+    let credentials = identities.credentials();
+    let credentials_service = identities.credentials_server();
+
+    let authority1 = identities_creation.create_identity().await?;
+    let authority2 = identities_creation.create_identity().await?;
+    let server = identities_creation.create_identity().await?;
+    let client = identities_creation.create_identity().await?;
+
+    secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &server.identifier(),
+            "listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+
+    let trust_context = TrustContext::new(
+        "test_trust_context_id".to_string(),
+        Some(AuthorityService::new(
+            identities.identities_reader(),
+            credentials.clone(),
+            authority1.identifier(),
+            None,
+        )),
+    )
+    .with_authority(
+        AuthorityService::new(
+            identities.identities_reader(),
+            credentials.clone(),
+            authority2.identifier(),
+            None,
+        )
+        .with_allowed_attributes(["is_user"]),
+    );
+
+    credentials_service
+        .start(
+            ctx,
+            trust_context,
+            server.identifier(),
+            "credential_exchange".into(),
+            false,
+        )
+        .await?;
+
+    let channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            &client.identifier(),
+            route!["listener"],
+            SecureChannelOptions::new()
+                .with_trust_policy(TrustIdentifierPolicy::new(server.identifier().clone())),
+        )
+        .await?;
+
+    // the second authority is only trusted for the 'is_user' attribute
+    let credential_data = CredentialData::builder(client.identifier(), authority2.identifier())
+        .with_attribute("is_user", b"true")
+        .with_attribute("is_superuser", b"true")
+        .build()?;
+
+    let credential = credentials
+        .issue_credential(&authority2.identifier(), credential_data)
+        .await?;
+
+    credentials_service
+        .present_credential(
+            ctx,
+            route![channel, "credential_exchange"],
+            credential,
+            MessageSendReceiveOptions::new(),
+        )
+        .await?;
+
+    let attrs = identities_repository
+        .get_attributes(&client.identifier())
+        .await?
+        .unwrap();
+
+    assert_eq!(attrs.attrs().get("is_user").unwrap().as_slice(), b"true");
+    assert!(attrs.attrs().get("is_superuser").is_none());
+
+    ctx.stop().await
+}
+
 #[ockam_macros::test]
 async fn full_flow_twoway(ctx: &mut Context) -> Result<()> {
     let secure_channels = secure_channels();
@@ -156,10 +334,11 @@ async fn full_flow_twoway(ctx: &mut Context) -> Result<()> {
         .await?;
 
     credentials_service
-        .present_credential_mutual(
+        .present_credential_mutual_with_trust_context(
             ctx,
             route![channel, "credential_exchange"],
-            trust_context.authorities().await?.as_slice(),
+            &trust_context,
+            &client2.identifier(),
             credential,
             MessageSendReceiveOptions::new(),
         )
@@ -294,6 +473,162 @@ async fn access_control(ctx: &mut Context) -> Result<()> {
     ctx.stop().await
 }
 
+#[ockam_macros::test]
+async fn access_control_revoked_subject(ctx: &mut Context) -> Result<()> {
+    let secure_channels = secure_channels();
+    let identities = secure_channels.identities();
+    let identities_creation = identities.identities_creation();
+    let identities_repository = identities.repository();
+    let credentials = identities.credentials();
+    let credentials_service = identities.credentials_server();
+
+    let authority = identities_creation.create_identity().await?;
+    let server = identities_creation.create_identity().await?;
+    let client = identities_creation.create_identity().await?;
+
+    secure_channels
+        .create_secure_channel_listener(
+            ctx,
+            &server.identifier(),
+            "listener",
+            SecureChannelListenerOptions::new(),
+        )
+        .await?;
+
+    let revocation_list = AuthorityRevocationList::new(InMemoryStorage::create());
+    let trust_context = TrustContext::new(
+        "test_trust_context_id".to_string(),
+        Some(
+            AuthorityService::new(
+                identities.identities_reader(),
+                credentials.clone(),
+                authority.identifier(),
+                None,
+            )
+            .with_revocation_list(Arc::new(revocation_list.clone()), Duration::ZERO),
+        ),
+    );
+
+    credentials_service
+        .start(
+            ctx,
+            trust_context,
+            server.identifier(),
+            "credential_exchange".into(),
+            false,
+        )
+        .await?;
+
+    let channel = secure_channels
+        .create_secure_channel(
+            ctx,
+            &client.identifier(),
+            route!["listener"],
+            SecureChannelOptions::new()
+                .with_trust_policy(TrustIdentifierPolicy::new(server.identifier().clone())),
+        )
+        .await?;
+
+    let credential_data = CredentialData::builder(client.identifier(), authority.identifier())
+        .with_attribute("is_superuser", b"true")
+        .build()?;
+    let credential = credentials
+        .issue_credential(&authority.identifier(), credential_data)
+        .await?;
+
+    let counter = Arc::new(AtomicI8::new(0));
+    let worker = CountingWorker {
+        msgs_count: counter.clone(),
+    };
+    let required_attributes = vec![("is_superuser".to_string(), b"true".to_vec())];
+    let access_control =
+        CredentialAccessControl::new(&required_attributes, identities_repository.clone());
+    WorkerBuilder::with_access_control(
+        Arc::new(access_control),
+        Arc::new(DenyAll),
+        "counter",
+        worker,
+    )
+    .start(ctx)
+    .await?;
+
+    let child_ctx = ctx
+        .new_detached_with_mailboxes(Mailboxes::main(
+            "child",
+            Arc::new(AllowAll),
+            Arc::new(AllowAll),
+        ))
+        .await?;
+
+    credentials_service
+        .present_credential(
+            ctx,
+            route![channel.clone(), "credential_exchange"],
+            credential,
+            MessageSendReceiveOptions::new(),
+        )
+        .await?;
+
+    child_ctx
+        .send(route![channel.clone(), "counter"], "Hello".to_string())
+        .await?;
+    ctx.sleep(Duration::from_millis(100)).await;
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+    // the stored attributes are removed once the revocation list is retrieved again,
+    // without the client presenting its credential again
+    revocation_list.revoke_subject(&client.identifier()).await?;
+    ctx.sleep(Duration::from_millis(1500)).await;
+    assert!(identities_repository
+        .get_attributes(&client.identifier())
+        .await?
+        .is_none());
+
+    child_ctx
+        .send(route![channel, "counter"], "Hello".to_string())
+        .await?;
+    ctx.sleep(Duration::from_millis(100)).await;
+    assert_eq!(counter.load(Ordering::Relaxed), 1);
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn credentials_are_issued_again_after_authority_key_rotation(
+    ctx: &mut Context,
+) -> Result<()> {
+    let identities = secure_channels().identities();
+    let identities_creation = identities.identities_creation();
+    let credentials = identities.credentials();
+
+    let authority = identities_creation.create_identity().await?;
+    let client = identities_creation.create_identity().await?;
+
+    let credential_data = CredentialData::builder(client.identifier(), authority.identifier())
+        .with_attribute("is_superuser", b"true")
+        .build()?;
+    let old_credential = credentials
+        .issue_credential(&authority.identifier(), credential_data.clone())
+        .await?;
+
+    let authority = identities.rotate_root_key(&authority.identifier()).await?;
+
+    // a credential signed with the previous root key is not accepted anymore
+    assert!(credentials
+        .verify_credential(&client.identifier(), &[authority.clone()], old_credential)
+        .await
+        .is_err());
+
+    let new_credential = credentials
+        .issue_credential(&authority.identifier(), credential_data)
+        .await?;
+    credentials
+        .verify_credential(&client.identifier(), &[authority], new_credential)
+        .await?;
+
+    ctx.stop().await
+}
+
 struct CountingWorker {
     msgs_count: Arc<AtomicI8>,
 }
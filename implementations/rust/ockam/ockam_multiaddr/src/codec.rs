@@ -1,17 +1,71 @@
 use super::{Buffer, Checked, Code, Codec, Protocol};
-use crate::proto::{DnsAddr, Node, Project, Secure, Service, Space, Tcp, Worker};
+use crate::proto::{
+    DnsAddr, Node, Project, Relay, Secure, Service, Space, Tcp, Udp, Unix, Worker, Ws,
+};
 use crate::{Error, ProtoValue};
 use core::fmt;
 use unsigned_varint::decode;
 
 pub struct StdCodec;
 
+/// Code of the secure WebSocket protocol, which is reserved but not supported
+pub(crate) const WSS_CODE: Code = Code::new(478);
+
+/// Prefix of the secure WebSocket protocol, which is reserved but not supported
+pub(crate) const WSS_PREFIX: &str = "wss";
+
+fn wss_unsupported() -> Error {
+    Error::message("secure WebSockets (/wss) are not supported, use /ws instead")
+}
+
+/// Is the given string the prefix of a protocol of the `StdCodec`?
+pub(crate) fn is_std_prefix(s: &str) -> bool {
+    matches!(s, "ip4" | "ip6")
+        || [
+            DnsAddr::PREFIX,
+            Tcp::PREFIX,
+            Udp::PREFIX,
+            Ws::PREFIX,
+            WSS_PREFIX,
+            Unix::PREFIX,
+            Relay::PREFIX,
+            Worker::PREFIX,
+            Service::PREFIX,
+            Node::PREFIX,
+            Project::PREFIX,
+            Space::PREFIX,
+            Secure::PREFIX,
+        ]
+        .contains(&s)
+}
+
 impl Codec for StdCodec {
     fn split_str<'a>(
         &self,
-        _prefix: &str,
+        prefix: &str,
         input: &'a str,
     ) -> Result<(Checked<&'a str>, &'a str), Error> {
+        match prefix {
+            // protocols without value
+            Ws::PREFIX => return Ok((Checked(""), input)),
+            WSS_PREFIX => return Err(wss_unsupported()),
+            // a path extends up to the next segment naming a protocol, the
+            // segments of the path named after a protocol are percent-encoded
+            Unix::PREFIX => {
+                let mut offset = 0;
+                while let Some(p) = input[offset..].find('/') {
+                    let at = offset + p;
+                    let next = input[at + 1..].split('/').next().unwrap_or("");
+                    if at > 0 && is_std_prefix(next) {
+                        let (x, y) = input.split_at(at);
+                        return Ok((Checked(x), y));
+                    }
+                    offset = at + 1;
+                }
+                return Ok((Checked(input), ""));
+            }
+            _ => {}
+        }
         if let Some(p) = input.find('/') {
             let (x, y) = input.split_at(p);
             Ok((Checked(x), y))
@@ -42,20 +96,24 @@ impl Codec for StdCodec {
                 let (x, y) = input.split_at(16);
                 Ok((Checked(x), y))
             }
-            Tcp::CODE => {
+            c @ Tcp::CODE | c @ Udp::CODE => {
                 if input.len() < 2 {
-                    return Err(Error::required_bytes(Tcp::CODE, 2));
+                    return Err(Error::required_bytes(c, 2));
                 }
                 let (x, y) = input.split_at(2);
                 Ok((Checked(x), y))
             }
+            Ws::CODE => Ok((Checked(&input[..0]), input)),
+            WSS_CODE => Err(wss_unsupported()),
             c @ Worker::CODE
             | c @ DnsAddr::CODE
             | c @ Service::CODE
             | c @ Node::CODE
             | c @ Project::CODE
             | c @ Space::CODE
-            | c @ Secure::CODE => {
+            | c @ Secure::CODE
+            | c @ Relay::CODE
+            | c @ Unix::CODE => {
                 let (len, input) = decode::usize(input)?;
                 if input.len() < len {
                     return Err(Error::required_bytes(c, len));
@@ -81,6 +139,10 @@ impl Codec for StdCodec {
             Project::CODE => Project::read_bytes(input).is_ok(),
             Space::CODE => Space::read_bytes(input).is_ok(),
             Secure::CODE => Secure::read_bytes(input).is_ok(),
+            Udp::CODE => Udp::read_bytes(input).is_ok(),
+            Ws::CODE => Ws::read_bytes(input).is_ok(),
+            Unix::CODE => Unix::read_bytes(input).is_ok(),
+            Relay::CODE => Relay::read_bytes(input).is_ok(),
             _ => false,
         }
     }
@@ -99,6 +161,11 @@ impl Codec for StdCodec {
             Project::CODE => Project::read_bytes(val.data())?.write_bytes(buf),
             Space::CODE => Space::read_bytes(val.data())?.write_bytes(buf),
             Secure::CODE => Secure::read_bytes(val.data())?.write_bytes(buf),
+            Udp::CODE => Udp::read_bytes(val.data())?.write_bytes(buf),
+            Ws::CODE => Ws::read_bytes(val.data())?.write_bytes(buf),
+            WSS_CODE => return Err(wss_unsupported()),
+            Unix::CODE => Unix::read_bytes(val.data())?.write_bytes(buf),
+            Relay::CODE => Relay::read_bytes(val.data())?.write_bytes(buf),
             code => return Err(Error::unregistered(code)),
         }
         Ok(())
@@ -153,6 +220,23 @@ impl Codec for StdCodec {
                 Secure::read_str(value)?.write_bytes(buf);
                 Ok(())
             }
+            Udp::PREFIX => {
+                Udp::read_str(value)?.write_bytes(buf);
+                Ok(())
+            }
+            Ws::PREFIX => {
+                Ws::read_str(value)?.write_bytes(buf);
+                Ok(())
+            }
+            WSS_PREFIX => Err(wss_unsupported()),
+            Unix::PREFIX => {
+                Unix::read_str(value)?.write_bytes(buf);
+                Ok(())
+            }
+            Relay::PREFIX => {
+                Relay::read_str(value)?.write_bytes(buf);
+                Ok(())
+            }
             _ => Err(Error::unregistered_prefix(prefix)),
         }
     }
@@ -206,6 +290,23 @@ impl Codec for StdCodec {
                 Secure::read_bytes(value)?.write_str(f)?;
                 Ok(())
             }
+            Udp::CODE => {
+                Udp::read_bytes(value)?.write_str(f)?;
+                Ok(())
+            }
+            Ws::CODE => {
+                Ws::read_bytes(value)?.write_str(f)?;
+                Ok(())
+            }
+            WSS_CODE => Err(wss_unsupported()),
+            Unix::CODE => {
+                Unix::read_bytes(value)?.write_str(f)?;
+                Ok(())
+            }
+            Relay::CODE => {
+                Relay::read_bytes(value)?.write_str(f)?;
+                Ok(())
+            }
             _ => Err(Error::unregistered(code)),
         }
     }
@@ -1,6 +1,9 @@
 use super::{Buffer, Checked, Code, Protocol};
 use crate::Error;
 use alloc::borrow::Cow;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::fmt;
 use core::ops::Deref;
 use core::str::{self, FromStr};
@@ -153,6 +156,191 @@ impl Protocol<'_> for Tcp {
     }
 }
 
+/// A UDP port number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Udp(pub u16);
+
+impl Udp {
+    pub fn new(v: u16) -> Self {
+        Udp(v)
+    }
+}
+
+impl Deref for Udp {
+    type Target = u16;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Protocol<'_> for Udp {
+    const CODE: Code = Code::new(273);
+    const PREFIX: &'static str = "udp";
+
+    fn read_str(input: Checked<&str>) -> Result<Self, Error> {
+        u16::from_str(&input).map(Udp).map_err(Error::message)
+    }
+
+    fn read_bytes(input: Checked<&[u8]>) -> Result<Self, Error> {
+        let mut b = [0; 2];
+        b.copy_from_slice(&input);
+        Ok(Udp(u16::from_be_bytes(b)))
+    }
+
+    fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        write!(f, "/{}/{}", Self::PREFIX, self.0)?;
+        Ok(())
+    }
+
+    fn write_bytes(&self, buf: &mut dyn Buffer) {
+        let mut b = encode::u32_buffer();
+        let uvi = encode::u32(Self::CODE.into(), &mut b);
+        buf.extend_with(uvi);
+        buf.extend_with(&self.0.to_be_bytes())
+    }
+}
+
+/// A Unix domain socket path.
+///
+/// The path is always absolute and its textual form omits the leading
+/// '/', e.g. `/unix/tmp/ockam.sock` denotes the socket at `/tmp/ockam.sock`.
+/// In a textual multi-address the path extends up to the next segment
+/// which is a protocol prefix, e.g. `/unix/tmp/ockam.sock/service/api`.
+/// The segments of the path which are named after a protocol are written
+/// with their first character percent-encoded, as well as any `%`, e.g.
+/// `/unix/srv/%73ervice/api.sock` denotes the socket at `/srv/service/api.sock`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Unix<'a>(Cow<'a, str>);
+
+impl<'a> Unix<'a> {
+    pub fn new<S: Into<Cow<'a, str>>>(path: S) -> Self {
+        let path = path.into();
+        if path.starts_with('/') {
+            Unix(path)
+        } else {
+            Unix(Cow::Owned(format!("/{path}")))
+        }
+    }
+}
+
+impl Deref for Unix<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<'a> Protocol<'a> for Unix<'a> {
+    const CODE: Code = Code::new(400);
+    const PREFIX: &'static str = "unix";
+
+    fn read_str(input: Checked<&'a str>) -> Result<Self, Error> {
+        if input.is_empty() {
+            return Err(Error::message("empty unix socket path"));
+        }
+        if !input.contains('%') {
+            return Ok(Unix::new(input.0));
+        }
+        Ok(Unix::new(percent_decode(&input)?))
+    }
+
+    fn read_bytes(input: Checked<&'a [u8]>) -> Result<Self, Error> {
+        let s = str::from_utf8(&input).map_err(Error::message)?;
+        if !s.starts_with('/') {
+            return Err(Error::message("unix socket path is not absolute"));
+        }
+        Ok(Unix(Cow::Borrowed(s)))
+    }
+
+    fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+        write!(f, "/{}", Self::PREFIX)?;
+        for segment in self.0.split('/').skip(1) {
+            let segment = segment.replace('%', "%25");
+            match segment.chars().next() {
+                Some(c) if crate::codec::is_std_prefix(&segment) => {
+                    write!(f, "/%{:02X}{}", c as u32, &segment[1..])?
+                }
+                _ => write!(f, "/{segment}")?,
+            }
+        }
+        Ok(())
+    }
+
+    fn write_bytes(&self, buf: &mut dyn Buffer) {
+        let mut b = encode::u32_buffer();
+        let uvi = encode::u32(Self::CODE.into(), &mut b);
+        buf.extend_with(uvi);
+        let mut b = encode::usize_buffer();
+        let uvi = encode::usize(self.0.len(), &mut b);
+        buf.extend_with(uvi);
+        buf.extend_with(self.0.as_bytes())
+    }
+}
+
+/// Decode the `%XX` sequences of a textual unix socket path
+fn percent_decode(input: &str) -> Result<String, Error> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut rest = input.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == b'%' {
+            let hex = tail
+                .get(..2)
+                .and_then(|h| str::from_utf8(h).ok())
+                .and_then(|h| u8::from_str_radix(h, 16).ok())
+                .ok_or_else(|| Error::message("invalid percent-encoding in unix socket path"))?;
+            bytes.push(hex);
+            rest = &tail[2..];
+        } else {
+            bytes.push(b);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(Error::message)
+}
+
+macro_rules! gen_flag_proto {
+    ($t:ident, $c:literal, $p:literal) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $t;
+
+        impl Protocol<'_> for $t {
+            const CODE: Code = Code::new($c);
+            const PREFIX: &'static str = $p;
+
+            fn read_str(input: Checked<&str>) -> Result<Self, Error> {
+                if input.is_empty() {
+                    Ok($t)
+                } else {
+                    Err(Error::message(concat!("/", $p, " has no value")))
+                }
+            }
+
+            fn read_bytes(input: Checked<&[u8]>) -> Result<Self, Error> {
+                if input.is_empty() {
+                    Ok($t)
+                } else {
+                    Err(Error::message(concat!("/", $p, " has no value")))
+                }
+            }
+
+            fn write_str(&self, f: &mut fmt::Formatter) -> Result<(), Error> {
+                write!(f, "/{}", Self::PREFIX)?;
+                Ok(())
+            }
+
+            fn write_bytes(&self, buf: &mut dyn Buffer) {
+                let mut b = encode::u32_buffer();
+                let uvi = encode::u32(Self::CODE.into(), &mut b);
+                buf.extend_with(uvi)
+            }
+        }
+    };
+}
+
+gen_flag_proto!(Ws, 477, "ws");
+
 macro_rules! gen_str_proto {
     ($t:ident, $c:literal, $p:literal) => {
         #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -210,3 +398,4 @@ gen_str_proto!(Node, 72526, "node");
 gen_str_proto!(Project, 82526, "project");
 gen_str_proto!(Space, 92526, "space");
 gen_str_proto!(Secure, 99526, "secure");
+gen_str_proto!(Relay, 112526, "relay");
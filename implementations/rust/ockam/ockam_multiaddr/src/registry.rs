@@ -1,6 +1,8 @@
 use super::{Code, Codec, Protocol};
-use crate::codec::StdCodec;
-use crate::proto::{DnsAddr, Node, Project, Secure, Service, Space, Tcp, Worker};
+use crate::codec::{StdCodec, WSS_CODE, WSS_PREFIX};
+use crate::proto::{
+    DnsAddr, Node, Project, Relay, Secure, Service, Space, Tcp, Udp, Unix, Worker, Ws,
+};
 use alloc::collections::btree_map::BTreeMap;
 use alloc::sync::Arc;
 use core::fmt;
@@ -38,6 +40,12 @@ impl Default for Registry {
         r.register(Space::CODE, Space::PREFIX, std_codec.clone());
         #[allow(clippy::redundant_clone)]
         r.register(Secure::CODE, Secure::PREFIX, std_codec.clone());
+        r.register(Udp::CODE, Udp::PREFIX, std_codec.clone());
+        r.register(Ws::CODE, Ws::PREFIX, std_codec.clone());
+        r.register(WSS_CODE, WSS_PREFIX, std_codec.clone());
+        r.register(Unix::CODE, Unix::PREFIX, std_codec.clone());
+        #[allow(clippy::redundant_clone)]
+        r.register(Relay::CODE, Relay::PREFIX, std_codec.clone());
         #[cfg(feature = "std")]
         r.register(
             crate::proto::Ip4::CODE,
@@ -1,5 +1,7 @@
 use core::fmt;
-use ockam_multiaddr::proto::{DnsAddr, Ip4, Ip6, Node, Project, Secure, Service, Space, Tcp};
+use ockam_multiaddr::proto::{
+    DnsAddr, Ip4, Ip6, Node, Project, Relay, Secure, Service, Space, Tcp, Udp, Unix, Ws,
+};
 use ockam_multiaddr::{Code, Match, MultiAddr, Protocol};
 use quickcheck::{quickcheck, Arbitrary, Gen};
 use rand::distributions::{Alphanumeric, DistString};
@@ -132,6 +134,22 @@ quickcheck! {
                         addr.push_back(Space::new("space")).unwrap();
                         prot.push_back(Space::CODE);
                     }
+                    Udp::CODE => {
+                        addr.push_back(Udp::new(0)).unwrap();
+                        prot.push_back(Udp::CODE);
+                    }
+                    Ws::CODE => {
+                        addr.push_back(Ws).unwrap();
+                        prot.push_back(Ws::CODE);
+                    }
+                    Relay::CODE => {
+                        addr.push_back(Relay::new("relay")).unwrap();
+                        prot.push_back(Relay::CODE);
+                    }
+                    Unix::CODE => {
+                        addr.push_back(Unix::new("/tmp/ockam.sock")).unwrap();
+                        prot.push_back(Unix::CODE);
+                    }
                     _ => unreachable!()
                 }
             }
@@ -153,6 +171,10 @@ const PROTOS: &[Code] = &[
     Node::CODE,
     Project::CODE,
     Space::CODE,
+    Udp::CODE,
+    Ws::CODE,
+    Relay::CODE,
+    Unix::CODE,
 ];
 
 impl Arbitrary for Addr {
@@ -169,6 +191,10 @@ impl Arbitrary for Addr {
                 Project::CODE => a.push_back(Project::new(gen_string())).unwrap(),
                 Space::CODE => a.push_back(Space::new(gen_string())).unwrap(),
                 Node::CODE => a.push_back(Node::new(gen_string())).unwrap(),
+                Udp::CODE => a.push_back(Udp::new(u16::arbitrary(g))).unwrap(),
+                Ws::CODE => a.push_back(Ws).unwrap(),
+                Relay::CODE => a.push_back(Relay::new(gen_string())).unwrap(),
+                Unix::CODE => a.push_back(Unix::new(gen_string())).unwrap(),
                 _ => unreachable!(),
             }
         }
@@ -176,6 +202,85 @@ impl Arbitrary for Addr {
     }
 }
 
+#[test]
+fn transport_protocols() {
+    let ma = MultiAddr::from_str("/ip4/127.0.0.1/udp/4000/service/api").unwrap();
+    let codes = ma.iter().map(|p| p.code()).collect::<Vec<_>>();
+    assert_eq!(codes, vec![Ip4::CODE, Udp::CODE, Service::CODE]);
+    assert_eq!(
+        ma.iter().nth(1).unwrap().cast::<Udp>(),
+        Some(Udp::new(4000))
+    );
+
+    let ma = MultiAddr::from_str("/dnsaddr/localhost/tcp/4000/ws/relay/blue/service/api").unwrap();
+    let codes = ma.iter().map(|p| p.code()).collect::<Vec<_>>();
+    assert_eq!(
+        codes,
+        vec![
+            DnsAddr::CODE,
+            Tcp::CODE,
+            Ws::CODE,
+            Relay::CODE,
+            Service::CODE
+        ]
+    );
+    assert_eq!(
+        ma.to_string(),
+        "/dnsaddr/localhost/tcp/4000/ws/relay/blue/service/api"
+    );
+
+    let ma = MultiAddr::from_str("/unix/tmp/ockam/node.sock").unwrap();
+    let unix = ma.first().unwrap();
+    assert_eq!(&*unix.cast::<Unix>().unwrap(), "/tmp/ockam/node.sock");
+    assert_eq!(ma.to_string(), "/unix/tmp/ockam/node.sock");
+    assert_eq!(ma, MultiAddr::try_from(ma.as_ref()).unwrap());
+
+    let ma = MultiAddr::from_str("/unix/tmp/ockam/node.sock/relay/blue/service/api").unwrap();
+    let codes = ma.iter().map(|p| p.code()).collect::<Vec<_>>();
+    assert_eq!(codes, vec![Unix::CODE, Relay::CODE, Service::CODE]);
+    assert_eq!(
+        ma.to_string(),
+        "/unix/tmp/ockam/node.sock/relay/blue/service/api"
+    );
+
+    let mut ma = MultiAddr::default();
+    ma.push_back(Service::new("api")).unwrap();
+    ma.push_back(Unix::new("/tmp/node.sock")).unwrap();
+    assert_eq!(ma.to_string(), "/service/api/unix/tmp/node.sock");
+
+    // path segments named after a protocol are percent-encoded
+    let mut ma = MultiAddr::default();
+    ma.push_back(Unix::new("/var/run/node/100%.sock")).unwrap();
+    ma.push_back(Service::new("api")).unwrap();
+    assert_eq!(
+        ma.to_string(),
+        "/unix/var/run/%6Eode/100%25.sock/service/api"
+    );
+    assert_eq!(ma, MultiAddr::from_str(&ma.to_string()).unwrap());
+
+    let ma = MultiAddr::from_str("/unix/srv/%73ervice/api.sock/service/api").unwrap();
+    let unix = ma.first().unwrap();
+    assert_eq!(&*unix.cast::<Unix>().unwrap(), "/srv/service/api.sock");
+    let codes = ma.iter().map(|p| p.code()).collect::<Vec<_>>();
+    assert_eq!(codes, vec![Unix::CODE, Service::CODE]);
+
+    assert!(MultiAddr::from_str("/unix/tmp/%2").is_err());
+    assert!(MultiAddr::from_str("/unix").is_err());
+    assert!(MultiAddr::from_str("/ip4/127.0.0.1/udp/http").is_err());
+}
+
+#[test]
+fn secure_websockets_are_rejected() {
+    let err = MultiAddr::from_str("/dnsaddr/localhost/tcp/443/wss/service/api").unwrap_err();
+    assert!(err.to_string().contains("/wss"));
+    assert!(MultiAddr::from_str("/unix/tmp/node.sock/wss").is_err());
+
+    // the reserved code 478 followed by a service
+    let mut bytes = vec![0xde, 0x03];
+    bytes.extend_from_slice(MultiAddr::from_str("/service/api").unwrap().as_ref());
+    assert!(MultiAddr::try_from(bytes.as_slice()).is_err());
+}
+
 /// An operation to perform on a MultiAddr.
 #[derive(Debug, Copy, Clone)]
 enum Op {
@@ -1,11 +1,24 @@
+use crate::tokio::sync::mpsc::channel;
+
 /// Sender used to send payload messages
 pub type MessageSender<T> = crate::tokio::sync::mpsc::Sender<T>;
 /// Receiver used to receive payload messages
 pub type MessageReceiver<T> = crate::tokio::sync::mpsc::Receiver<T>;
 
+/// Default capacity of the mailbox of a worker
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 16;
+
+/// Default capacity of the router channel
+pub const DEFAULT_ROUTER_CAPACITY: usize = 64;
+
 /// Create message channel
 pub fn message_channel<T>() -> (MessageSender<T>, MessageReceiver<T>) {
-    crate::tokio::sync::mpsc::channel(16)
+    message_channel_with_capacity(DEFAULT_MAILBOX_CAPACITY)
+}
+
+/// Create message channel with the given capacity (at least 1)
+pub fn message_channel_with_capacity<T>(capacity: usize) -> (MessageSender<T>, MessageReceiver<T>) {
+    channel(capacity.max(1))
 }
 
 /// Router sender
@@ -15,7 +28,12 @@ pub type RouterReceiver<T> = crate::tokio::sync::mpsc::Receiver<T>;
 
 /// Create router channel
 pub fn router_channel<T>() -> (RouterSender<T>, RouterReceiver<T>) {
-    crate::tokio::sync::mpsc::channel(64)
+    router_channel_with_capacity(DEFAULT_ROUTER_CAPACITY)
+}
+
+/// Create router channel with the given capacity (at least 1)
+pub fn router_channel_with_capacity<T>(capacity: usize) -> (RouterSender<T>, RouterReceiver<T>) {
+    channel(capacity.max(1))
 }
 
 // TODO: Consider replacing with oneshot
@@ -27,5 +45,5 @@ pub type SmallReceiver<T> = crate::tokio::sync::mpsc::Receiver<T>;
 
 /// Create small channel (size 1)
 pub fn small_channel<T>() -> (SmallSender<T>, SmallReceiver<T>) {
-    crate::tokio::sync::mpsc::channel(1)
+    channel(1)
 }
@@ -2,6 +2,7 @@ use core::time::Duration;
 
 use ockam_core::compat::collections::HashMap;
 use ockam_core::compat::{boxed::Box, sync::Arc, sync::RwLock, vec::Vec};
+use ockam_core::flow_control::FlowControls;
 use ockam_core::{
     errcode::{Kind, Origin},
     Address, AsyncTryClone, DenyAll, Error, IncomingAccessControl, Mailboxes,
@@ -10,7 +11,9 @@ use ockam_core::{
 use ockam_transport_core::Transport;
 
 use crate::async_drop::AsyncDrop;
-use crate::channel_types::{message_channel, small_channel, SmallReceiver, SmallSender};
+use crate::channel_types::{small_channel, SmallReceiver, SmallSender};
+use crate::mailbox::{mailbox_channel, MailboxOptions};
+use crate::metrics::Metrics;
 use crate::tokio::{self, runtime::Handle};
 use crate::{debugger, Context};
 use crate::{error::*, relay::CtrlSignal, router::SenderPair, NodeMessage};
@@ -60,8 +63,11 @@ impl Context {
         mailboxes: Mailboxes,
         async_drop_sender: Option<AsyncDropSender>,
         transports: Arc<RwLock<HashMap<TransportType, Arc<dyn Transport>>>>,
+        flow_controls: FlowControls,
+        metrics: Metrics,
+        mailbox_options: MailboxOptions,
     ) -> (Self, SenderPair, SmallReceiver<CtrlSignal>) {
-        let (mailbox_tx, receiver) = message_channel();
+        let (mailbox_tx, receiver) = mailbox_channel(mailboxes.main_address(), mailbox_options);
         let (ctrl_tx, ctrl_rx) = small_channel();
         (
             Self {
@@ -70,8 +76,9 @@ impl Context {
                 mailboxes,
                 receiver,
                 async_drop_sender,
-                mailbox_count: Arc::new(0.into()),
                 transports,
+                flow_controls,
+                metrics,
             },
             SenderPair {
                 msgs: mailbox_tx,
@@ -84,6 +91,7 @@ impl Context {
     pub(crate) fn copy_with_mailboxes(
         &self,
         mailboxes: Mailboxes,
+        mailbox_options: MailboxOptions,
     ) -> (Context, SenderPair, SmallReceiver<CtrlSignal>) {
         Context::new(
             self.runtime().clone(),
@@ -91,6 +99,9 @@ impl Context {
             mailboxes,
             None,
             self.transports.clone(),
+            self.flow_controls.clone(),
+            self.metrics.clone(),
+            mailbox_options,
         )
     }
 
@@ -105,6 +116,9 @@ impl Context {
             mailboxes,
             Some(drop_sender),
             self.transports.clone(),
+            self.flow_controls.clone(),
+            self.metrics.clone(),
+            MailboxOptions::default(),
         )
     }
 
@@ -178,8 +192,7 @@ impl Context {
         let (ctx, sender, _) = self.copy_with_mailboxes_detached(mailboxes, drop_sender);
 
         // Create a "detached relay" and register it with the router
-        let (msg, mut rx) =
-            NodeMessage::start_worker(addresses, sender, true, Arc::clone(&self.mailbox_count));
+        let (msg, mut rx) = NodeMessage::start_worker(addresses, sender, true);
         self.sender
             .send(msg)
             .await
@@ -206,7 +219,7 @@ mod tests {
 
         // after a copy with new mailboxes the list of transports should be intact
         let mailboxes = Mailboxes::new(Mailbox::deny_all("address"), vec![]);
-        let (copy, _, _) = ctx.copy_with_mailboxes(mailboxes.clone());
+        let (copy, _, _) = ctx.copy_with_mailboxes(mailboxes.clone(), MailboxOptions::default());
         assert!(copy.is_transport_registered(transport.transport_type()));
 
         // after a detached copy with new mailboxes the list of transports should be intact
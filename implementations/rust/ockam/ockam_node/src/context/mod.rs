@@ -14,15 +14,17 @@ pub use stop_env::*;
 pub use transports::*;
 pub use worker_lifecycle::*;
 
-use crate::channel_types::{SmallReceiver, SmallSender};
+use crate::channel_types::SmallSender;
+use crate::mailbox::MailboxReceiver;
+use crate::metrics::Metrics;
 use crate::tokio::runtime::Handle;
 use crate::{error::*, NodeMessage};
 #[cfg(feature = "std")]
 use core::fmt::{Debug, Formatter};
-use core::sync::atomic::AtomicUsize;
 use ockam_core::compat::collections::HashMap;
 use ockam_core::compat::{string::String, sync::Arc, sync::RwLock, vec::Vec};
-use ockam_core::{Address, Mailboxes, RelayMessage, Result, TransportType};
+use ockam_core::flow_control::FlowControls;
+use ockam_core::{Address, Mailboxes, Result, TransportType};
 use ockam_transport_core::Transport;
 
 /// A default timeout in seconds
@@ -33,11 +35,14 @@ pub struct Context {
     mailboxes: Mailboxes,
     sender: SmallSender<NodeMessage>,
     rt: Handle,
-    receiver: SmallReceiver<RelayMessage>,
+    receiver: MailboxReceiver,
     async_drop_sender: Option<AsyncDropSender>,
-    mailbox_count: Arc<AtomicUsize>,
     /// List of transports used to resolve external addresses to local workers in routes
     transports: Arc<RwLock<HashMap<TransportType, Arc<dyn Transport>>>>,
+    /// Flow controls shared by all Contexts on this node
+    flow_controls: FlowControls,
+    /// Metrics shared by all Contexts on this node
+    metrics: Metrics,
 }
 
 /// This trait can be used to integrate transports into a node
@@ -63,9 +68,20 @@ impl Context {
         &self.rt
     }
 
-    /// Return mailbox_count clone
-    pub(crate) fn mailbox_count(&self) -> Arc<AtomicUsize> {
-        self.mailbox_count.clone()
+    /// Return the [`FlowControls`] shared by all workers on this node
+    ///
+    /// Flow Control entries are removed automatically when the workers and
+    /// processors they belong to are stopped.
+    pub fn flow_controls(&self) -> &FlowControls {
+        &self.flow_controls
+    }
+
+    /// Return the [`Metrics`] handle of this node
+    ///
+    /// Metrics are disabled unless a recorder was configured when
+    /// building the node, see [`NodeBuilder`](crate::NodeBuilder).
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
     }
 
     /// Return a reference to sender
@@ -136,6 +152,28 @@ impl Context {
         Ok(())
     }
 
+    /// This function is called by Relay to indicate a supervised worker is
+    /// waiting to be restarted, or was restarted
+    #[cfg(feature = "std")]
+    pub(crate) async fn set_faulty(&self, faulty: bool) -> Result<()> {
+        self.sender
+            .send(NodeMessage::SetFaulty(self.address(), faulty))
+            .await
+            .map_err(NodeError::from_send_err)?;
+        Ok(())
+    }
+
+    /// This function is called by Relay to restart the other workers of
+    /// the cluster of a failed worker
+    #[cfg(feature = "std")]
+    pub(crate) async fn restart_cluster(&self) -> Result<()> {
+        self.sender
+            .send(NodeMessage::RestartCluster(self.address()))
+            .await
+            .map_err(NodeError::from_send_err)?;
+        Ok(())
+    }
+
     /// Wait for a particular address to become "ready"
     pub async fn wait_for<A: Into<Address>>(&mut self, addr: A) -> Result<()> {
         let (msg, mut reply) = NodeMessage::get_ready(addr.into());
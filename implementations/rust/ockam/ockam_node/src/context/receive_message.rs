@@ -1,10 +1,10 @@
-use core::sync::atomic::Ordering;
 use core::time::Duration;
 
+use ockam_core::compat::string::ToString;
 use ockam_core::{Message, RelayMessage, Result, Routed};
 
-use crate::debugger;
 use crate::tokio::time::timeout;
+use crate::{debugger, metrics};
 use crate::{error::*, parser};
 use crate::{Context, DEFAULT_TIMEOUT};
 
@@ -64,7 +64,13 @@ impl Context {
                 trace!("{}: received new message!", self.address());
 
                 // First we update the mailbox fill metrics
-                self.mailbox_count.fetch_sub(1, Ordering::Acquire);
+                if self.metrics().is_enabled() {
+                    self.metrics().set_gauge(
+                        metrics::MAILBOX_DEPTH,
+                        &[("address", self.address().to_string())],
+                        self.receiver.depth() as f64,
+                    );
+                }
 
                 msg
             }) {
@@ -1,6 +1,6 @@
 use crate::channel_types::small_channel;
 use crate::context::MessageWait;
-use crate::{debugger, Context, MessageReceiveOptions, DEFAULT_TIMEOUT};
+use crate::{debugger, Context, MessagePriority, MessageReceiveOptions, DEFAULT_TIMEOUT};
 use crate::{error::*, NodeMessage};
 use core::time::Duration;
 use ockam_core::compat::{sync::Arc, vec::Vec};
@@ -16,6 +16,7 @@ use ockam_core::{LocalInfo, Mailbox};
 pub struct MessageSendReceiveOptions {
     flow_controls: Option<FlowControls>,
     message_wait: MessageWait,
+    priority: MessagePriority,
 }
 
 impl Default for MessageSendReceiveOptions {
@@ -30,6 +31,7 @@ impl MessageSendReceiveOptions {
         Self {
             flow_controls: None,
             message_wait: MessageWait::Timeout(Duration::from_secs(DEFAULT_TIMEOUT)),
+            priority: MessagePriority::Normal,
         }
     }
 
@@ -50,6 +52,12 @@ impl MessageSendReceiveOptions {
         self.flow_controls = Some(flow_controls.clone());
         self
     }
+
+    /// Send the message with the given priority, see [`Context::send_with_priority`]
+    pub fn with_priority(mut self, priority: MessagePriority) -> Self {
+        self.priority = priority;
+        self
+    }
 }
 
 impl Context {
@@ -120,7 +128,9 @@ impl Context {
 
         let mut child_ctx = self.new_detached_with_mailboxes(mailboxes).await?;
 
-        child_ctx.send(route, msg).await?;
+        child_ctx
+            .send_with_priority(route, msg, options.priority)
+            .await?;
         child_ctx
             .receive_extended::<M>(
                 MessageReceiveOptions::new().with_message_wait(options.message_wait),
@@ -200,6 +210,32 @@ impl Context {
             .await
     }
 
+    /// Send a message to an address or via a fully-qualified route
+    /// with the given [`MessagePriority`]
+    ///
+    /// High priority messages are received before the normal messages
+    /// waiting in the mailbox of a worker started with
+    /// [`WorkerBuilder::with_priority_lane`](crate::WorkerBuilder::with_priority_lane).
+    /// The priority is kept when the message is forwarded unchanged to
+    /// another local worker, but not across transports.
+    pub async fn send_with_priority<R, M>(
+        &self,
+        route: R,
+        msg: M,
+        priority: MessagePriority,
+    ) -> Result<()>
+    where
+        R: Into<Route>,
+        M: Message + Send + 'static,
+    {
+        let local_info = match priority {
+            MessagePriority::Normal => Vec::new(),
+            MessagePriority::High => vec![MessagePriority::high()],
+        };
+        self.send_from_address_impl(route.into(), msg, self.address(), local_info)
+            .await
+    }
+
     /// Send a message to an address or via a fully-qualified route
     ///
     /// Routes can be constructed from a set of [`Address`]es, or via
@@ -286,10 +322,7 @@ impl Context {
         }
 
         // Send the packed user message with associated route
-        sender
-            .send(relay_msg)
-            .await
-            .map_err(NodeError::from_send_err)?;
+        sender.send(relay_msg, &self.metrics).await?;
 
         Ok(())
     }
@@ -373,10 +406,7 @@ impl Context {
         }
 
         // Forward the message
-        sender
-            .send(relay_msg)
-            .await
-            .map_err(NodeError::from_send_err)?;
+        sender.send(relay_msg, &self.metrics).await?;
 
         Ok(())
     }
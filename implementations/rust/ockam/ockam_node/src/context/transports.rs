@@ -89,7 +89,7 @@ mod tests {
         let transport = Arc::new(SomeTransport());
         ctx.register_transport(transport.clone());
 
-        let flow_controls = FlowControls::default();
+        let flow_controls = ctx.flow_controls().clone();
 
         // resolve a route with known transports
         let result = ctx
@@ -111,9 +111,10 @@ mod tests {
 
     #[ockam_macros::test(crate = "crate")]
     async fn test_resolve_route_only_single_hop_is_allowed(ctx: &mut Context) -> Result<()> {
+        let flow_controls = ctx.flow_controls().clone();
         let result = ctx
             .resolve_transport_route(
-                &FlowControls::default(),
+                &flow_controls,
                 route![
                     (TransportType::new(1), "address1"),
                     (LOCAL, "address2"),
@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use crate::SupervisionOptions;
 use crate::{Context, NodeError, NodeMessage, NodeReason};
 use crate::{ProcessorBuilder, WorkerBuilder};
 use ockam_core::compat::sync::Arc;
@@ -73,6 +75,36 @@ impl Context {
         Ok(())
     }
 
+    /// Start a new supervised worker instance at the given address
+    ///
+    /// The worker is restarted according to the [`SupervisionOptions`]
+    /// when its `handle_message` function fails, see
+    /// [`Worker::restart`](ockam_core::Worker::restart) to re-initialise
+    /// its state.
+    #[cfg(feature = "std")]
+    pub async fn start_supervised_worker<NM, NW>(
+        &self,
+        address: impl Into<Address>,
+        worker: NW,
+        supervision: SupervisionOptions,
+        incoming: impl IncomingAccessControl,
+        outgoing: impl OutgoingAccessControl,
+    ) -> Result<()>
+    where
+        NM: Message + Send + 'static,
+        NW: Worker<Context = Context, Message = NM>,
+    {
+        WorkerBuilder::with_mailboxes(
+            Mailboxes::main(address, Arc::new(incoming), Arc::new(outgoing)),
+            worker,
+        )
+        .with_supervision(supervision)
+        .start(self)
+        .await?;
+
+        Ok(())
+    }
+
     /// Start a new processor instance at the given address
     ///
     /// A processor is an asynchronous piece of code that runs a
@@ -1,21 +1,23 @@
 // use crate::message::BaseMessage;
 
-use crate::channel_types::SmallSender;
+use crate::channel_types::{SmallSender, DEFAULT_ROUTER_CAPACITY};
+use crate::metrics::Metrics;
 use crate::{
     router::{Router, SenderPair},
     tokio::runtime::{Handle, Runtime},
     NodeMessage,
 };
 use core::future::Future;
+use ockam_core::flow_control::FlowControls;
 use ockam_core::{Address, Result};
 
-#[cfg(feature = "metrics")]
-use crate::metrics::Metrics;
-
 // This import is available on emebedded but we don't use the metrics
 // collector, thus don't need it in scope.
-#[cfg(feature = "metrics")]
+#[cfg(feature = "std")]
+use crate::metrics::MetricsCollector;
+#[cfg(feature = "std")]
 use core::sync::atomic::{AtomicBool, Ordering};
+use ockam_core::compat::sync::Arc;
 
 #[cfg(feature = "std")]
 use ockam_core::{
@@ -33,23 +35,11 @@ pub struct Executor {
     rt: Runtime,
     /// Main worker and application router
     router: Router,
-    /// Metrics collection endpoint
-    #[cfg(feature = "metrics")]
-    metrics: Arc<Metrics>,
 }
 
 impl Default for Executor {
     fn default() -> Self {
-        let rt = Runtime::new().unwrap();
-        let router = Router::new();
-        #[cfg(feature = "metrics")]
-        let metrics = Metrics::new(&rt, router.get_metrics_readout());
-        Self {
-            rt,
-            router,
-            #[cfg(feature = "metrics")]
-            metrics,
-        }
+        Self::with_options(Self::default_metrics(), DEFAULT_ROUTER_CAPACITY)
     }
 }
 
@@ -59,11 +49,43 @@ impl Executor {
         Executor::default()
     }
 
+    /// Metrics used when no recorder is configured: metrics are recorded
+    /// only when the `OCKAM_METRICS_PATH` environment variable is set
+    pub(crate) fn default_metrics() -> Metrics {
+        #[cfg(feature = "std")]
+        {
+            Metrics::from_env()
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            Metrics::disabled()
+        }
+    }
+
+    /// Create a new Ockam node [`Executor`] instance recording metrics
+    /// with the given handle, and whose router accepts up to
+    /// `router_capacity` pending commands
+    pub(crate) fn with_options(metrics: Metrics, router_capacity: usize) -> Self {
+        let rt = Runtime::new().unwrap();
+        let router = Router::new(metrics, router_capacity);
+        Self { rt, router }
+    }
+
     /// Get access to the internal message sender
     pub(crate) fn sender(&self) -> SmallSender<NodeMessage> {
         self.router.sender()
     }
 
+    /// Get access to the [`FlowControls`] shared by this node
+    pub(crate) fn flow_controls(&self) -> &FlowControls {
+        self.router.flow_controls()
+    }
+
+    /// Get access to the [`Metrics`] shared by this node
+    pub(crate) fn metrics(&self) -> &Metrics {
+        self.router.metrics()
+    }
+
     /// Get access to the underlying async runtime (by default `tokio`)
     pub(crate) fn runtime(&self) -> &Handle {
         self.rt.handle()
@@ -89,10 +111,13 @@ impl Executor {
         F::Output: Send + 'static,
     {
         // Spawn the metrics collector first
-        #[cfg(feature = "metrics")]
         let alive = Arc::new(AtomicBool::from(true));
-        #[cfg(feature = "metrics")]
-        self.rt.spawn(self.metrics.clone().run(alive.clone()));
+        let collector = MetricsCollector::new(
+            self.metrics().clone(),
+            #[cfg(feature = "metrics")]
+            self.rt.handle().clone(),
+        );
+        self.rt.spawn(collector.run(alive.clone()));
 
         // Spawn user code second
         let join_body = self.rt.spawn(future);
@@ -101,8 +126,7 @@ impl Executor {
         self.rt.block_on(self.router.run())?;
 
         // Shut down metrics collector
-        #[cfg(feature = "metrics")]
-        alive.fetch_or(true, Ordering::Acquire);
+        alive.store(false, Ordering::Release);
 
         // Last join user code
         let res = self
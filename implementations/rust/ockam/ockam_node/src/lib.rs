@@ -37,8 +37,7 @@ pub mod compat;
 /// MPSC channel type aliases
 pub mod channel_types;
 
-#[cfg(feature = "metrics")]
-mod metrics;
+pub mod metrics;
 
 /// Api helpers
 pub mod api;
@@ -51,6 +50,7 @@ mod context;
 mod delayed;
 mod error;
 mod executor;
+mod mailbox;
 mod messages;
 mod node;
 mod parser;
@@ -61,16 +61,21 @@ mod rpc_client;
 
 /// Support for storing persistent values
 pub mod storage;
+#[cfg(feature = "std")]
+pub mod supervisor;
 mod worker_builder;
 
 pub use context::*;
 pub use delayed::*;
 pub use error::*;
 pub use executor::*;
+pub use mailbox::{MailboxSender, MessagePriority, MESSAGE_PRIORITY_IDENTIFIER};
 pub use messages::*;
 pub use processor_builder::ProcessorBuilder;
 pub use rpc_client::*;
 pub use storage::*;
+#[cfg(feature = "std")]
+pub use supervisor::{Backoff, RestartStrategy, SupervisionOptions};
 pub use worker_builder::WorkerBuilder;
 
 pub use node::{NodeBuilder, NullWorker};
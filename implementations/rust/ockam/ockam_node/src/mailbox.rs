@@ -0,0 +1,282 @@
+//! Mailboxes of workers and processors
+//!
+//! Every worker receives its messages through a bounded channel, whose
+//! capacity can be set with
+//! [`WorkerBuilder::with_mailbox_capacity`](crate::WorkerBuilder::with_mailbox_capacity).
+//! When the channel is full, senders wait until the worker has handled
+//! some of its messages.
+//!
+//! A worker can also have a priority lane, a second channel used for
+//! the messages sent with [`MessagePriority::High`]. Those messages are
+//! always received before the ones waiting in the normal lane, so that
+//! control traffic (heartbeats, API requests, ...) is not delayed by
+//! bulk payloads.
+//!
+//! The priority is carried by a `LocalInfo` of the message, which is not
+//! sent by transports nor kept by secure channels. A message received from
+//! another node is then in the normal lane, whatever priority it was sent
+//! with, until a worker of this node sends it again with a high priority.
+//!
+//! The number of messages waiting in a mailbox, across both lanes, is
+//! tracked by the mailbox itself and reported as the
+//! [`MAILBOX_DEPTH`](crate::metrics::MAILBOX_DEPTH) gauge.
+
+use crate::channel_types::{
+    message_channel_with_capacity, MessageReceiver, MessageSender, DEFAULT_MAILBOX_CAPACITY,
+};
+use crate::error::NodeError;
+use crate::metrics::{self, Metrics};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use ockam_core::compat::string::ToString;
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::Vec;
+use ockam_core::{Address, LocalInfo, LocalMessage, RelayMessage, Result};
+
+/// Message priority LocalInfo unique Identifier
+pub const MESSAGE_PRIORITY_IDENTIFIER: &str = "MESSAGE_PRIORITY_IDENTIFIER";
+
+/// Priority of a message in the mailbox of its recipient
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessagePriority {
+    /// The message is delivered in the order it was sent
+    Normal,
+    /// The message is delivered before the normal messages, if the
+    /// recipient has a priority lane
+    High,
+}
+
+impl MessagePriority {
+    /// Encode `MessagePriority::High` as a general `LocalInfo`
+    pub fn high() -> LocalInfo {
+        LocalInfo::new(MESSAGE_PRIORITY_IDENTIFIER.into(), Vec::new())
+    }
+
+    /// Return the priority of a `LocalMessage`
+    pub fn of(local_msg: &LocalMessage) -> Self {
+        if local_msg
+            .local_info()
+            .iter()
+            .any(|x| x.type_identifier() == MESSAGE_PRIORITY_IDENTIFIER)
+        {
+            Self::High
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// Capacities of the mailbox of a worker or processor
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MailboxOptions {
+    capacity: usize,
+    priority_capacity: Option<usize>,
+}
+
+impl Default for MailboxOptions {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_MAILBOX_CAPACITY,
+            priority_capacity: None,
+        }
+    }
+}
+
+impl MailboxOptions {
+    pub(crate) fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn with_priority_lane(mut self, capacity: usize) -> Self {
+        self.priority_capacity = Some(capacity);
+        self
+    }
+}
+
+/// Create the channels of the mailbox of the worker or processor with the given primary address
+pub(crate) fn mailbox_channel(
+    address: Address,
+    options: MailboxOptions,
+) -> (MailboxSender, MailboxReceiver) {
+    let (normal_tx, normal_rx) = message_channel_with_capacity(options.capacity);
+    let (priority_tx, priority_rx) = match options.priority_capacity {
+        Some(capacity) => {
+            let (tx, rx) = message_channel_with_capacity(capacity);
+            (Some(tx), Some(rx))
+        }
+        None => (None, None),
+    };
+    let depth = Arc::new(AtomicUsize::new(0));
+    (
+        MailboxSender {
+            address,
+            normal: normal_tx,
+            priority: priority_tx,
+            depth: depth.clone(),
+        },
+        MailboxReceiver {
+            normal: normal_rx,
+            priority: priority_rx,
+            depth,
+        },
+    )
+}
+
+/// Sender to the mailbox of a worker or processor
+#[derive(Clone, Debug)]
+pub struct MailboxSender {
+    address: Address,
+    normal: MessageSender<RelayMessage>,
+    priority: Option<MessageSender<RelayMessage>>,
+    depth: Arc<AtomicUsize>,
+}
+
+impl MailboxSender {
+    /// Send a message to the lane matching its priority
+    ///
+    /// High priority messages go to the normal lane when the recipient
+    /// has no priority lane.
+    ///
+    /// This is the only place where messages are enqueued, so it records
+    /// the routed messages and the depth of the mailbox, whether the
+    /// sender was just resolved by the router or cached by the caller.
+    pub(crate) async fn send(&self, relay_msg: RelayMessage, metrics: &Metrics) -> Result<()> {
+        let sender = match (
+            &self.priority,
+            MessagePriority::of(relay_msg.local_message()),
+        ) {
+            (Some(priority), MessagePriority::High) => priority,
+            _ => &self.normal,
+        };
+
+        // Count the message before it can be received, so that the
+        // receiving side never decrements a message that was not counted yet
+        let depth = self.depth.fetch_add(1, Ordering::AcqRel) + 1;
+        if metrics.is_enabled() {
+            let labels = [("address", self.address.to_string())];
+            metrics.increment_counter(metrics::MESSAGES_ROUTED, &labels, 1);
+            metrics.set_gauge(metrics::MAILBOX_DEPTH, &labels, depth as f64);
+        }
+
+        #[cfg(feature = "std")]
+        let res = {
+            use crate::tokio::sync::mpsc::error::{SendError, TrySendError};
+            match sender.try_send(relay_msg) {
+                Ok(()) => Ok(()),
+                Err(TrySendError::Full(relay_msg)) => {
+                    if metrics.is_enabled() {
+                        metrics.increment_counter(
+                            metrics::MAILBOX_SENDS_BLOCKED,
+                            &[("address", self.address.to_string())],
+                            1,
+                        );
+                    }
+                    trace!("Mailbox of {} is full, waiting", self.address);
+                    sender.send(relay_msg).await
+                }
+                Err(TrySendError::Closed(relay_msg)) => Err(SendError(relay_msg)),
+            }
+        };
+        #[cfg(not(feature = "std"))]
+        let res = sender.send(relay_msg).await;
+
+        res.map_err(|e| {
+            let depth = decrement(&self.depth);
+            if metrics.is_enabled() {
+                let labels = [("address", self.address.to_string())];
+                metrics.increment_counter(metrics::MESSAGES_DROPPED, &labels, 1);
+                metrics.set_gauge(metrics::MAILBOX_DEPTH, &labels, depth as f64);
+            }
+            NodeError::from_send_err(e)
+        })
+    }
+}
+
+/// Decrement the depth of a mailbox without going below zero, and return the new depth
+fn decrement(depth: &AtomicUsize) -> usize {
+    match depth.fetch_update(Ordering::AcqRel, Ordering::Acquire, |d| d.checked_sub(1)) {
+        Ok(previous) => previous - 1,
+        Err(_) => 0,
+    }
+}
+
+/// Receiver of the mailbox of a worker or processor
+pub(crate) struct MailboxReceiver {
+    normal: MessageReceiver<RelayMessage>,
+    // Priority lanes are only available with `std`, see `MailboxOptions::with_priority_lane`
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    priority: Option<MessageReceiver<RelayMessage>>,
+    depth: Arc<AtomicUsize>,
+}
+
+impl MailboxReceiver {
+    /// Receive the next message, taking the priority lane first
+    ///
+    /// Returns `None` once all the senders were dropped and both lanes are empty.
+    pub(crate) async fn recv(&mut self) -> Option<RelayMessage> {
+        let msg = self.recv_any_lane().await;
+        if msg.is_some() {
+            decrement(&self.depth);
+        }
+        msg
+    }
+
+    /// Number of messages waiting in the mailbox
+    pub(crate) fn depth(&self) -> usize {
+        self.depth.load(Ordering::Acquire)
+    }
+
+    async fn recv_any_lane(&mut self) -> Option<RelayMessage> {
+        #[cfg(feature = "std")]
+        if let Some(priority) = &mut self.priority {
+            return crate::tokio::select! {
+                biased;
+                Some(msg) = priority.recv() => Some(msg),
+                msg = self.normal.recv() => msg,
+            };
+        }
+        self.normal.recv().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::PrometheusRecorder;
+    use ockam_core::{route, TransportMessage};
+
+    fn relay_message(destination: &Address) -> RelayMessage {
+        let transport_message =
+            TransportMessage::v1(route![destination.clone()], route![], Vec::new());
+        RelayMessage::new(
+            "sender".into(),
+            destination.clone(),
+            LocalMessage::new(transport_message, Vec::new()),
+        )
+    }
+
+    #[tokio::test]
+    async fn cached_senders_update_the_mailbox_depth() -> Result<()> {
+        let recorder = Arc::new(PrometheusRecorder::new());
+        let metrics = Metrics::new(recorder.clone());
+        let address: Address = "worker".into();
+        let (sender, mut receiver) = mailbox_channel(address.clone(), MailboxOptions::default());
+
+        // a sender kept by the caller instead of being resolved by the router again
+        let cached = sender.clone();
+        sender.send(relay_message(&address), &metrics).await?;
+        cached.send(relay_message(&address), &metrics).await?;
+        cached.send(relay_message(&address), &metrics).await?;
+        assert_eq!(receiver.depth(), 3);
+        let text = recorder.render();
+        assert!(text.contains("ockam_node_mailbox_depth{address=\"0#worker\"} 3\n"));
+        assert!(text.contains("ockam_node_messages_routed_total{address=\"0#worker\"} 3\n"));
+
+        for expected in [2, 1, 0] {
+            assert!(receiver.recv().await.is_some());
+            assert_eq!(receiver.depth(), expected);
+        }
+        Ok(())
+    }
+}
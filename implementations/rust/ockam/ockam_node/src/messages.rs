@@ -1,11 +1,12 @@
-use crate::channel_types::{small_channel, MessageSender, SmallReceiver, SmallSender};
+use crate::channel_types::{small_channel, SmallReceiver, SmallSender};
 use crate::{
     error::{NodeError, NodeReason, RouterReason, WorkerReason},
     router::SenderPair,
+    MailboxSender,
 };
-use core::{fmt, sync::atomic::AtomicUsize};
-use ockam_core::compat::{string::String, sync::Arc, vec::Vec};
-use ockam_core::{Address, Error, RelayMessage, Result, TransportType};
+use core::fmt;
+use ockam_core::compat::{string::String, vec::Vec};
+use ockam_core::{Address, Error, Result, TransportType};
 
 /// Messages sent from the Node to the Executor
 #[derive(Debug)]
@@ -18,8 +19,6 @@ pub enum NodeMessage {
         senders: SenderPair,
         /// A detached context/ "worker" runs no relay state
         detached: bool,
-        /// A mechanism to read channel fill-state for a worker
-        mailbox_count: Arc<AtomicUsize>,
         /// Reply channel for command confirmation
         reply: SmallSender<NodeReplyResult>,
     },
@@ -47,6 +46,10 @@ pub enum NodeMessage {
     SetReady(Address),
     /// Check whether an address has been marked as "ready"
     CheckReady(Address, SmallSender<NodeReplyResult>),
+    /// Mark a supervised worker as faulty while it is being restarted, or as running again
+    SetFaulty(Address, bool),
+    /// Restart the supervised workers in the cluster of a failed worker
+    RestartCluster(Address),
 }
 
 impl fmt::Display for NodeMessage {
@@ -65,6 +68,8 @@ impl fmt::Display for NodeMessage {
             NodeMessage::Router(_, _, _) => write!(f, "Router"),
             NodeMessage::SetReady(_) => write!(f, "SetReady"),
             NodeMessage::CheckReady(_, _) => write!(f, "CheckReady"),
+            NodeMessage::SetFaulty(_, _) => write!(f, "SetFaulty"),
+            NodeMessage::RestartCluster(_) => write!(f, "RestartCluster"),
         }
     }
 }
@@ -82,7 +87,6 @@ impl NodeMessage {
         addrs: Vec<Address>,
         senders: SenderPair,
         detached: bool,
-        mailbox_count: Arc<AtomicUsize>,
     ) -> (Self, SmallReceiver<NodeReplyResult>) {
         let (reply, rx) = small_channel();
         (
@@ -90,7 +94,6 @@ impl NodeMessage {
                 addrs,
                 senders,
                 detached,
-                mailbox_count,
                 reply,
             },
             rx,
@@ -169,7 +172,7 @@ pub enum RouterReply {
         /// The address a message is being sent to
         addr: Address,
         /// The relay sender
-        sender: MessageSender<RelayMessage>,
+        sender: MailboxSender,
     },
     /// Indicate the 'ready' state of an address
     State(bool),
@@ -260,12 +263,12 @@ impl RouterReply {
     }
 
     /// Return [RouterReply::Sender] for the given information
-    pub fn sender(addr: Address, sender: MessageSender<RelayMessage>) -> NodeReplyResult {
+    pub fn sender(addr: Address, sender: MailboxSender) -> NodeReplyResult {
         Ok(RouterReply::Sender { addr, sender })
     }
 
     /// Consume the wrapper and return [RouterReply::Sender]
-    pub fn take_sender(self) -> Result<(Address, MessageSender<RelayMessage>)> {
+    pub fn take_sender(self) -> Result<(Address, MailboxSender)> {
         match self {
             Self::Sender { addr, sender } => Ok((addr, sender)),
             _ => Err(NodeError::NodeState(NodeReason::Unknown).internal()),
@@ -0,0 +1,70 @@
+use super::Metrics;
+use crate::tokio::time;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+
+#[cfg(feature = "metrics")]
+use crate::tokio::runtime::Handle;
+
+/// Busy time of each runtime worker since the last collection, in percent
+#[cfg(feature = "metrics")]
+pub const TOKIO_WORKER_BUSY: &str = "ockam_node_tokio_worker_busy_percent";
+
+/// Interval between two collections
+const COLLECTION_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Periodically samples runtime metrics and flushes the recorder of a node
+pub(crate) struct MetricsCollector {
+    metrics: Metrics,
+    #[cfg(feature = "metrics")]
+    rt: Handle,
+}
+
+impl MetricsCollector {
+    /// Create a new collector with access to the runtime
+    pub(crate) fn new(metrics: Metrics, #[cfg(feature = "metrics")] rt: Handle) -> Self {
+        Self {
+            metrics,
+            #[cfg(feature = "metrics")]
+            rt,
+        }
+    }
+
+    /// Spawned by the Executor to periodically collect metrics
+    pub(crate) async fn run(self, alive: Arc<AtomicBool>) {
+        if !self.metrics.is_enabled() {
+            debug!("Metrics collection disabled");
+            return;
+        }
+
+        #[cfg(feature = "metrics")]
+        let mut busy_ms = Vec::new();
+
+        while alive.load(Ordering::Relaxed) {
+            #[cfg(feature = "metrics")]
+            self.collect_runtime_metrics(&mut busy_ms);
+
+            self.metrics.flush();
+            time::sleep(COLLECTION_INTERVAL).await;
+        }
+
+        debug!("Metrics collector shutting down...");
+        self.metrics.flush();
+    }
+
+    #[cfg(feature = "metrics")]
+    fn collect_runtime_metrics(&self, acc_ms: &mut Vec<u128>) {
+        let m = self.rt.metrics();
+        let workers = m.num_workers();
+        acc_ms.resize(workers, 0);
+        for (wid, acc) in acc_ms.iter_mut().enumerate() {
+            let raw_ms = m.worker_total_busy_duration(wid).as_millis();
+            let diff_ms = raw_ms - *acc;
+            let percent = 100.0 * diff_ms as f64 / COLLECTION_INTERVAL.as_millis() as f64;
+            *acc = raw_ms;
+            self.metrics
+                .set_gauge(TOKIO_WORKER_BUSY, &[("worker", wid.to_string())], percent);
+        }
+    }
+}
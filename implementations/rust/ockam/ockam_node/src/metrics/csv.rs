@@ -0,0 +1,111 @@
+use super::registry::{labels, Registry, Value};
+use super::{Label, MetricsRecorder};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// A recorder appending the current value of all the series to a CSV file
+/// each time it is flushed
+///
+/// Each line starts with the number of milliseconds elapsed since the
+/// creation of the recorder, followed by `series=value` columns, quoted
+/// when their labels contain commas or quotes.
+/// Histograms are written as their `_count` and `_sum` series.
+pub struct CsvRecorder {
+    registry: Registry,
+    file: Mutex<File>,
+    start: Instant,
+}
+
+impl CsvRecorder {
+    /// Create a recorder writing to the given file, truncating it if it exists
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(path)?;
+        file.write_all(b"Elapsed time (ms),Metrics (series=value)\n")?;
+        Ok(Self {
+            registry: Registry::default(),
+            file: Mutex::new(file),
+            start: Instant::now(),
+        })
+    }
+
+    fn line(&self) -> String {
+        let mut line = self.start.elapsed().as_millis().to_string();
+        for (key, value) in self.registry.snapshot() {
+            let l = labels(&key.labels, None);
+            match value {
+                Value::Counter(c) => push_field(&mut line, &format!("{}{l}={c}", key.name)),
+                Value::Gauge(g) => push_field(&mut line, &format!("{}{l}={g}", key.name)),
+                Value::Histogram { sum, count, .. } => {
+                    push_field(&mut line, &format!("{}_count{l}={count}", key.name));
+                    push_field(&mut line, &format!("{}_sum{l}={sum}", key.name))
+                }
+            }
+        }
+        line.push('\n');
+        line
+    }
+}
+
+/// Append a column to a CSV line, quoting it if it contains a separator, a quote or a newline
+fn push_field(line: &mut String, field: &str) {
+    line.push(',');
+    if field.contains([',', '"', '\n', '\r']) {
+        line.push('"');
+        line.push_str(&field.replace('"', "\"\""));
+        line.push('"');
+    } else {
+        line.push_str(field);
+    }
+}
+
+impl MetricsRecorder for CsvRecorder {
+    fn increment_counter(&self, name: &'static str, labels: &[Label], value: u64) {
+        self.registry.increment_counter(name, labels, value)
+    }
+
+    fn set_gauge(&self, name: &'static str, labels: &[Label], value: f64) {
+        self.registry.set_gauge(name, labels, value)
+    }
+
+    fn add_to_gauge(&self, name: &'static str, labels: &[Label], delta: f64) {
+        self.registry.add_to_gauge(name, labels, delta)
+    }
+
+    fn record_histogram(&self, name: &'static str, labels: &[Label], value: f64) {
+        self.registry.record_histogram(name, labels, value)
+    }
+
+    fn remove_series(&self, label: &Label) {
+        self.registry.remove_series(label)
+    }
+
+    fn flush(&self) {
+        let line = self.line();
+        if let Err(e) = self.file.lock().unwrap().write_all(line.as_bytes()) {
+            warn!("Failed to write metrics: {e}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_with_separators_are_quoted() {
+        let mut line = String::new();
+        push_field(&mut line, "messages_total=3");
+        push_field(&mut line, r#"messages_total{worker="a,b"}=3"#);
+        assert_eq!(
+            line,
+            r#",messages_total=3,"messages_total{worker=""a,b""}=3""#
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Metrics collected by a node and its transports
+//!
+//! Components record counters, gauges and histograms through the
+//! [`Metrics`] handle returned by [`Context::metrics`](crate::Context::metrics),
+//! which forwards them to the [`MetricsRecorder`] configured on the
+//! [`NodeBuilder`](crate::NodeBuilder). Two recorders are provided:
+//!
+//! * [`PrometheusRecorder`], which aggregates the metrics in memory and
+//!   renders them in the Prometheus text format,
+//! * [`CsvRecorder`], which periodically appends the current values to a
+//!   CSV file. It is used when the `OCKAM_METRICS_PATH` environment
+//!   variable is set and no other recorder is configured.
+
+#[cfg(feature = "std")]
+mod collector;
+#[cfg(feature = "std")]
+mod csv;
+mod registry;
+
+#[cfg(feature = "std")]
+pub(crate) use collector::*;
+#[cfg(feature = "std")]
+pub use csv::*;
+pub use registry::*;
+
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::Arc;
+
+/// Number of messages routed to an address
+pub const MESSAGES_ROUTED: &str = "ockam_node_messages_routed_total";
+/// Number of messages waiting in the mailbox of an address
+pub const MAILBOX_DEPTH: &str = "ockam_node_mailbox_depth";
+/// Number of messages whose sender had to wait because the mailbox of
+/// their recipient was full
+pub const MAILBOX_SENDS_BLOCKED: &str = "ockam_node_mailbox_sends_blocked_total";
+/// Number of messages dropped because their recipient stopped
+pub const MESSAGES_DROPPED: &str = "ockam_node_messages_dropped_total";
+/// Time spent by the router to handle a command, in seconds
+pub const ROUTER_COMMAND_DURATION: &str = "ockam_node_router_command_duration_seconds";
+/// Number of addresses registered on the router
+pub const ROUTER_ADDRESSES: &str = "ockam_node_router_addresses";
+/// Number of worker clusters registered on the router
+pub const ROUTER_CLUSTERS: &str = "ockam_node_router_clusters";
+/// Number of restarts of a supervised worker
+pub const WORKER_RESTARTS: &str = "ockam_node_worker_restarts_total";
+
+/// A metric label, as a pair of a name and a value
+pub type Label = (&'static str, String);
+
+/// A backend receiving the metrics recorded on a node
+///
+/// A series is identified by the name of a metric and its labels.
+pub trait MetricsRecorder: Send + Sync + 'static {
+    /// Add a value to a counter
+    fn increment_counter(&self, name: &'static str, labels: &[Label], value: u64);
+
+    /// Set the value of a gauge
+    fn set_gauge(&self, name: &'static str, labels: &[Label], value: f64);
+
+    /// Add a value, possibly negative, to a gauge
+    fn add_to_gauge(&self, name: &'static str, labels: &[Label], delta: f64);
+
+    /// Record an observation in a histogram
+    fn record_histogram(&self, name: &'static str, labels: &[Label], value: f64);
+
+    /// Remove all the series having the given label, for example when
+    /// the worker they describe has been stopped
+    fn remove_series(&self, _label: &Label) {}
+
+    /// Called periodically by the node, to let recorders export their data
+    fn flush(&self) {}
+}
+
+/// Handle to the [`MetricsRecorder`] of a node
+///
+/// When metrics are disabled all the operations are no-ops, and callers
+/// can use [`Metrics::is_enabled`] to avoid computing labels altogether.
+#[derive(Clone)]
+pub struct Metrics {
+    recorder: Option<Arc<dyn MetricsRecorder>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+impl Metrics {
+    /// Create a handle forwarding metrics to the given recorder
+    pub fn new(recorder: Arc<dyn MetricsRecorder>) -> Self {
+        Self {
+            recorder: Some(recorder),
+        }
+    }
+
+    /// Create a handle discarding all metrics
+    pub fn disabled() -> Self {
+        Self { recorder: None }
+    }
+
+    /// Create a handle using a [`CsvRecorder`] if the `OCKAM_METRICS_PATH`
+    /// environment variable is set, and discarding all metrics otherwise
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Self {
+        match ockam_core::env::get_env::<String>("OCKAM_METRICS_PATH") {
+            Ok(Some(path)) => match CsvRecorder::create(&path) {
+                Ok(recorder) => Self::new(Arc::new(recorder)),
+                Err(e) => {
+                    warn!("Metrics collection disabled, cannot open {path}: {e}");
+                    Self::disabled()
+                }
+            },
+            _ => Self::disabled(),
+        }
+    }
+
+    /// Return true if metrics are forwarded to a recorder
+    pub fn is_enabled(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Return the recorder, if any
+    pub fn recorder(&self) -> Option<&Arc<dyn MetricsRecorder>> {
+        self.recorder.as_ref()
+    }
+
+    /// Add a value to a counter
+    pub fn increment_counter(&self, name: &'static str, labels: &[Label], value: u64) {
+        if let Some(r) = &self.recorder {
+            r.increment_counter(name, labels, value)
+        }
+    }
+
+    /// Set the value of a gauge
+    pub fn set_gauge(&self, name: &'static str, labels: &[Label], value: f64) {
+        if let Some(r) = &self.recorder {
+            r.set_gauge(name, labels, value)
+        }
+    }
+
+    /// Add a value, possibly negative, to a gauge
+    pub fn add_to_gauge(&self, name: &'static str, labels: &[Label], delta: f64) {
+        if let Some(r) = &self.recorder {
+            r.add_to_gauge(name, labels, delta)
+        }
+    }
+
+    /// Record an observation in a histogram
+    pub fn record_histogram(&self, name: &'static str, labels: &[Label], value: f64) {
+        if let Some(r) = &self.recorder {
+            r.record_histogram(name, labels, value)
+        }
+    }
+
+    /// Remove all the series having the given label
+    pub fn remove_series(&self, label: &Label) {
+        if let Some(r) = &self.recorder {
+            r.remove_series(label)
+        }
+    }
+
+    /// Let the recorder export its data
+    pub fn flush(&self) {
+        if let Some(r) = &self.recorder {
+            r.flush()
+        }
+    }
+}
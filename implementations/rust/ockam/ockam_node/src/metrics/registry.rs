@@ -0,0 +1,271 @@
+use super::{Label, MetricsRecorder};
+use core::fmt::Write;
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::Mutex;
+use ockam_core::compat::vec::Vec;
+
+/// Upper bounds of the histogram buckets, suitable for durations in seconds
+pub const DEFAULT_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// A series is a metric with a given set of labels
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct SeriesKey {
+    pub(crate) name: &'static str,
+    pub(crate) labels: Vec<Label>,
+}
+
+impl SeriesKey {
+    fn new(name: &'static str, labels: &[Label]) -> Self {
+        Self {
+            name,
+            labels: labels.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Value {
+    Counter(u64),
+    Gauge(f64),
+    Histogram {
+        /// Number of observations per bucket, the last one being `+Inf`
+        buckets: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+/// In-memory store of the current value of each series
+pub(crate) struct Registry {
+    series: Mutex<BTreeMap<SeriesKey, Value>>,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            series: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl Registry {
+    fn update(&self, key: SeriesKey, f: impl FnOnce(&mut Value), init: impl FnOnce() -> Value) {
+        let mut series = self.series.lock().unwrap();
+        f(series.entry(key).or_insert_with(init))
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<(SeriesKey, Value)> {
+        let series = self.series.lock().unwrap();
+        series.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+    }
+}
+
+impl MetricsRecorder for Registry {
+    fn increment_counter(&self, name: &'static str, labels: &[Label], value: u64) {
+        self.update(
+            SeriesKey::new(name, labels),
+            |v| match v {
+                Value::Counter(c) => *c = c.saturating_add(value),
+                other => *other = Value::Counter(value),
+            },
+            || Value::Counter(0),
+        )
+    }
+
+    fn set_gauge(&self, name: &'static str, labels: &[Label], value: f64) {
+        self.update(
+            SeriesKey::new(name, labels),
+            |v| *v = Value::Gauge(value),
+            || Value::Gauge(0.0),
+        )
+    }
+
+    fn add_to_gauge(&self, name: &'static str, labels: &[Label], delta: f64) {
+        self.update(
+            SeriesKey::new(name, labels),
+            |v| match v {
+                Value::Gauge(g) => *g += delta,
+                other => *other = Value::Gauge(delta),
+            },
+            || Value::Gauge(0.0),
+        )
+    }
+
+    fn record_histogram(&self, name: &'static str, labels: &[Label], value: f64) {
+        let empty = || Value::Histogram {
+            buckets: vec![0; DEFAULT_BUCKETS.len() + 1],
+            sum: 0.0,
+            count: 0,
+        };
+        self.update(
+            SeriesKey::new(name, labels),
+            |v| {
+                if !matches!(v, Value::Histogram { .. }) {
+                    *v = empty()
+                }
+                if let Value::Histogram {
+                    buckets,
+                    sum,
+                    count,
+                } = v
+                {
+                    let i = DEFAULT_BUCKETS
+                        .iter()
+                        .position(|b| value <= *b)
+                        .unwrap_or(DEFAULT_BUCKETS.len());
+                    buckets[i] += 1;
+                    *sum += value;
+                    *count += 1;
+                }
+            },
+            empty,
+        )
+    }
+
+    fn remove_series(&self, label: &Label) {
+        let mut series = self.series.lock().unwrap();
+        series.retain(|k, _| !k.labels.contains(label))
+    }
+}
+
+/// A recorder keeping metrics in memory and rendering them in the
+/// [Prometheus text format](https://prometheus.io/docs/instrumenting/exposition_formats/)
+#[derive(Default)]
+pub struct PrometheusRecorder {
+    registry: Registry,
+}
+
+impl PrometheusRecorder {
+    /// Create a new, empty, recorder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Render the current value of all the series
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut current = None;
+        for (key, value) in self.registry.snapshot() {
+            if current != Some(key.name) {
+                let typ = match value {
+                    Value::Counter(_) => "counter",
+                    Value::Gauge(_) => "gauge",
+                    Value::Histogram { .. } => "histogram",
+                };
+                let _ = writeln!(out, "# TYPE {} {typ}", key.name);
+                current = Some(key.name);
+            }
+            match value {
+                Value::Counter(c) => {
+                    let _ = writeln!(out, "{}{} {c}", key.name, labels(&key.labels, None));
+                }
+                Value::Gauge(g) => {
+                    let _ = writeln!(out, "{}{} {g}", key.name, labels(&key.labels, None));
+                }
+                Value::Histogram {
+                    buckets,
+                    sum,
+                    count,
+                } => {
+                    let mut cumulative = 0;
+                    for (i, n) in buckets.iter().enumerate() {
+                        cumulative += n;
+                        let le = match DEFAULT_BUCKETS.get(i) {
+                            Some(b) => format!("{b}"),
+                            None => String::from("+Inf"),
+                        };
+                        let l = labels(&key.labels, Some(&le));
+                        let _ = writeln!(out, "{}_bucket{l} {cumulative}", key.name);
+                    }
+                    let l = labels(&key.labels, None);
+                    let _ = writeln!(out, "{}_sum{l} {sum}", key.name);
+                    let _ = writeln!(out, "{}_count{l} {count}", key.name);
+                }
+            }
+        }
+        out
+    }
+}
+
+impl MetricsRecorder for PrometheusRecorder {
+    fn increment_counter(&self, name: &'static str, labels: &[Label], value: u64) {
+        self.registry.increment_counter(name, labels, value)
+    }
+
+    fn set_gauge(&self, name: &'static str, labels: &[Label], value: f64) {
+        self.registry.set_gauge(name, labels, value)
+    }
+
+    fn add_to_gauge(&self, name: &'static str, labels: &[Label], delta: f64) {
+        self.registry.add_to_gauge(name, labels, delta)
+    }
+
+    fn record_histogram(&self, name: &'static str, labels: &[Label], value: f64) {
+        self.registry.record_histogram(name, labels, value)
+    }
+
+    fn remove_series(&self, label: &Label) {
+        self.registry.remove_series(label)
+    }
+}
+
+/// Format labels as `{name="value",...}`, or nothing if there are none
+pub(crate) fn labels(labels: &[Label], le: Option<&str>) -> String {
+    let mut out = String::new();
+    let all = labels
+        .iter()
+        .map(|(k, v)| (*k, v.as_str()))
+        .chain(le.map(|le| ("le", le)));
+    for (i, (k, v)) in all.enumerate() {
+        out.push(if i == 0 { '{' } else { ',' });
+        let _ = write!(out, "{k}=\"");
+        for c in v.chars() {
+            match c {
+                '\\' => out.push_str("\\\\"),
+                '"' => out.push_str("\\\""),
+                '\n' => out.push_str("\\n"),
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+    if !out.is_empty() {
+        out.push('}');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::MetricsRecorder;
+
+    #[test]
+    fn render_prometheus_text_format() {
+        let r = PrometheusRecorder::new();
+        let a = [("address", String::from("a\"b"))];
+        r.increment_counter("messages_total", &a, 2);
+        r.increment_counter("messages_total", &a, 1);
+        r.add_to_gauge("connections", &[], 1.0);
+        r.add_to_gauge("connections", &[], 1.0);
+        r.add_to_gauge("connections", &[], -1.0);
+        r.record_histogram("latency_seconds", &[], 0.02);
+        r.record_histogram("latency_seconds", &[], 20.0);
+
+        let text = r.render();
+        assert!(
+            text.contains("# TYPE messages_total counter\nmessages_total{address=\"a\\\"b\"} 3\n")
+        );
+        assert!(text.contains("# TYPE connections gauge\nconnections 1\n"));
+        assert!(text.contains("latency_seconds_bucket{le=\"0.01\"} 0\n"));
+        assert!(text.contains("latency_seconds_bucket{le=\"0.025\"} 1\n"));
+        assert!(text.contains("latency_seconds_bucket{le=\"+Inf\"} 2\n"));
+        assert!(text.contains("latency_seconds_count 2\n"));
+
+        r.remove_series(&a[0]);
+        assert!(!r.render().contains("messages_total"));
+    }
+}
@@ -1,6 +1,8 @@
 use ockam_core::compat::sync::Arc;
 use ockam_core::{Address, AllowAll, Mailbox, Mailboxes};
 
+use crate::channel_types::DEFAULT_ROUTER_CAPACITY;
+use crate::metrics::{Metrics, MetricsRecorder};
 use crate::{debugger, Context, Executor};
 
 /// A minimal worker implementation that does nothing
@@ -18,6 +20,8 @@ impl ockam_core::Worker for NullWorker {
 /// builder API to customise the underlying node that is created.
 pub struct NodeBuilder {
     logging: bool,
+    metrics: Option<Metrics>,
+    router_capacity: usize,
 }
 
 impl Default for NodeBuilder {
@@ -29,12 +33,41 @@ impl Default for NodeBuilder {
 impl NodeBuilder {
     /// Create a node
     pub fn new() -> Self {
-        Self { logging: true }
+        Self {
+            logging: true,
+            metrics: None,
+            router_capacity: DEFAULT_ROUTER_CAPACITY,
+        }
     }
 
     /// Disable logging on this node
     pub fn no_logging(self) -> Self {
-        Self { logging: false }
+        Self {
+            logging: false,
+            ..self
+        }
+    }
+
+    /// Record the metrics of this node with the given recorder
+    ///
+    /// By default, metrics are only recorded when the `OCKAM_METRICS_PATH`
+    /// environment variable is set, in which case they are written to the
+    /// CSV file it names.
+    pub fn with_metrics_recorder(self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        Self {
+            metrics: Some(Metrics::new(recorder)),
+            ..self
+        }
+    }
+
+    /// Set the number of commands (worker starts, route resolutions, ...)
+    /// which can be queued for the router before their senders wait.
+    /// The default is [`DEFAULT_ROUTER_CAPACITY`]
+    pub fn with_router_capacity(self, router_capacity: usize) -> Self {
+        Self {
+            router_capacity,
+            ..self
+        }
     }
 
     /// Consume this builder and yield a new Ockam Node
@@ -46,7 +79,8 @@ impl NodeBuilder {
 
         info!("Initializing ockam node");
 
-        let mut exe = Executor::new();
+        let metrics = self.metrics.unwrap_or_else(Executor::default_metrics);
+        let mut exe = Executor::with_options(metrics, self.router_capacity);
         let addr: Address = "app".into();
 
         // The root application worker needs a mailbox and relay to accept
@@ -60,6 +94,9 @@ impl NodeBuilder {
             ),
             None,
             Default::default(),
+            exe.flow_controls().clone(),
+            exe.metrics().clone(),
+            Default::default(),
         );
 
         debugger::log_inherit_context("NODE", &ctx, &ctx);
@@ -1,5 +1,6 @@
 use crate::debugger;
 use crate::error::{NodeError, NodeReason};
+use crate::mailbox::MailboxOptions;
 use crate::{relay::ProcessorRelay, Context, NodeMessage};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{
@@ -21,6 +22,7 @@ use ockam_core::{
 pub struct ProcessorBuilder<P> {
     mailboxes: Mailboxes,
     processor: P,
+    mailbox_options: MailboxOptions,
 }
 
 impl<P> ProcessorBuilder<P> {
@@ -47,10 +49,7 @@ where
             outgoing_access_control,
         );
 
-        Self {
-            mailboxes,
-            processor,
-        }
+        Self::with_mailboxes(mailboxes, processor)
     }
 
     /// Create a processor which uses the access control from the given
@@ -59,9 +58,27 @@ where
         Self {
             mailboxes,
             processor,
+            mailbox_options: MailboxOptions::default(),
         }
     }
 
+    /// Set the number of messages which can wait in the mailbox of the
+    /// processor before their senders have to wait.
+    /// The default is [`DEFAULT_MAILBOX_CAPACITY`](crate::channel_types::DEFAULT_MAILBOX_CAPACITY)
+    pub fn with_mailbox_capacity(mut self, capacity: usize) -> Self {
+        self.mailbox_options = self.mailbox_options.with_capacity(capacity);
+        self
+    }
+
+    /// Give the processor a priority lane holding up to `capacity` messages.
+    /// Messages sent with [`MessagePriority::High`](crate::MessagePriority::High)
+    /// are then received before the other messages of the mailbox
+    #[cfg(feature = "std")]
+    pub fn with_priority_lane(mut self, capacity: usize) -> Self {
+        self.mailbox_options = self.mailbox_options.with_priority_lane(capacity);
+        self
+    }
+
     /// Consume this builder and start a new Ockam [`Processor`] from the given context
     #[inline]
     pub async fn start(self, context: &Context) -> Result<Address> {
@@ -76,7 +93,7 @@ where
         let main_address = mailboxes.main_address().clone();
 
         // Pass it to the context
-        let (ctx, sender, ctrl_rx) = context.copy_with_mailboxes(mailboxes);
+        let (ctx, sender, ctrl_rx) = context.copy_with_mailboxes(mailboxes, self.mailbox_options);
 
         debugger::log_inherit_context("PROCESSOR", context, &ctx);
 
@@ -11,4 +11,6 @@ pub enum CtrlSignal {
     Interrupt,
     /// Interrupt current message execution and shut down
     InterruptStop,
+    /// Restart a supervised worker because another worker of its cluster failed
+    Restart,
 }
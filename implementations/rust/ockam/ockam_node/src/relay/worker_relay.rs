@@ -1,9 +1,19 @@
 use crate::channel_types::SmallReceiver;
+#[cfg(feature = "std")]
+use crate::error::{NodeError, WorkerReason};
 use crate::relay::CtrlSignal;
+#[cfg(feature = "std")]
+use crate::supervisor::{RestartStrategy, Supervisor};
 use crate::tokio::runtime::Handle;
 use crate::{parser, Context};
 use core::marker::PhantomData;
+#[cfg(feature = "std")]
+use futures::FutureExt;
+#[cfg(feature = "std")]
+use ockam_core::compat::string::ToString;
 use ockam_core::{Message, RelayMessage, Result, Routed, Worker};
+#[cfg(feature = "std")]
+use std::panic::AssertUnwindSafe;
 
 /// Worker relay machinery
 ///
@@ -17,6 +27,8 @@ where
 {
     worker: W,
     ctx: Context,
+    #[cfg(feature = "std")]
+    supervisor: Option<Supervisor>,
     _phantom: PhantomData<M>,
 }
 
@@ -25,10 +37,16 @@ where
     W: Worker<Context = Context, Message = M>,
     M: Message + Send + 'static,
 {
-    pub fn new(worker: W, ctx: Context) -> Self {
+    pub fn new(
+        worker: W,
+        ctx: Context,
+        #[cfg(feature = "std")] supervisor: Option<Supervisor>,
+    ) -> Self {
         Self {
             worker,
             ctx,
+            #[cfg(feature = "std")]
+            supervisor,
             _phantom: PhantomData,
         }
     }
@@ -79,6 +97,18 @@ where
 
         // Call the worker handle function - pass errors up
         let routed = Self::wrap_direct_message(relay_msg)?;
+
+        // A panic of a supervised worker is handled as an error so that
+        // the worker can be restarted
+        #[cfg(feature = "std")]
+        if self.supervisor.is_some() {
+            AssertUnwindSafe(self.worker.handle_message(&mut self.ctx, routed))
+                .catch_unwind()
+                .await
+                .map_err(|_| NodeError::WorkerState(WorkerReason::Faulty).internal())??;
+            return Ok(true);
+        }
+
         self.worker.handle_message(&mut self.ctx, routed).await?;
 
         // Signal to the outer loop that we would like to run again
@@ -107,33 +137,44 @@ where
 
         #[cfg(feature = "std")]
         loop {
-            crate::tokio::select! {
+            // Some(true) if the worker failed, Some(false) if its cluster failed
+            let restart = crate::tokio::select! {
                 result = self.recv_message() => {
                     match result {
                         // Successful message handling -- keep running
-                        Ok(true) => {},
+                        Ok(true) => None,
                         // Successful message handling -- stop now
                         Ok(false) => {
                             break;
                         },
-                        // An error occurred -- log and continue
+                        // An error occurred -- log and restart the worker if it is supervised
                         Err(e) => {
                             #[cfg(feature = "debugger")]
                             error!("Error encountered during '{}' message handling: {:?}", address, e);
                             #[cfg(not(feature = "debugger"))]
                             error!("Error encountered during '{}' message handling: {}", address, e);
+                            Some(true)
                         }
                     }
                 },
                 result = ctrl_rx.recv() => {
-                    if result.is_some() {
-                        debug!("Relay received shutdown signal, terminating!");
-                        break;
+                    match result {
+                        Some(CtrlSignal::Restart) => Some(false),
+                        Some(_) => {
+                            debug!("Relay received shutdown signal, terminating!");
+                            break;
+                        }
+                        // We are stopping
+                        None => None,
                     }
-
-                    // We are stopping
                 }
             };
+
+            if let Some(failed) = restart {
+                if !self.restart(failed).await {
+                    break;
+                }
+            }
         }
         #[cfg(not(feature = "std"))]
         loop {
@@ -171,8 +212,79 @@ where
         }
     }
 
+    /// Restart a supervised worker after a failure, either of the worker
+    /// itself or of another worker in its cluster
+    ///
+    /// Return false if the worker must be stopped
+    #[cfg(feature = "std")]
+    async fn restart(&mut self, failed: bool) -> bool {
+        let supervisor = match &mut self.supervisor {
+            Some(supervisor) => supervisor,
+            // Unsupervised workers keep running after a failure
+            None => return true,
+        };
+        let address = self.ctx.address();
+
+        // Only the failures of the worker itself count towards its maximum number of
+        // restarts, a worker restarted with its cluster is restarted right away
+        let delay = if failed {
+            match supervisor.next_restart() {
+                Some(delay) => Some(delay),
+                None => {
+                    error!("Worker '{}' failed too many times, stopping it", address);
+                    return false;
+                }
+            }
+        } else {
+            None
+        };
+
+        if failed && supervisor.strategy() == RestartStrategy::OneForAll {
+            if let Err(e) = self.ctx.restart_cluster().await {
+                warn!(
+                    "Failed to restart the cluster of worker '{}': {}",
+                    address, e
+                );
+            }
+        }
+
+        if let Err(e) = self.ctx.set_faulty(true).await {
+            warn!("Failed to mark worker '{}' as faulty: {}", address, e);
+        }
+        match delay {
+            Some(delay) => {
+                info!("Restarting worker '{}' in {:?}", address, delay);
+                self.ctx.sleep(delay).await;
+            }
+            None => info!("Restarting worker '{}' with its cluster", address),
+        }
+
+        if let Err(e) = self.worker.restart(&mut self.ctx).await {
+            error!("Failure during '{}' worker restart: {}", address, e);
+        }
+        self.ctx.metrics().increment_counter(
+            crate::metrics::WORKER_RESTARTS,
+            &[("address", address.to_string())],
+            1,
+        );
+
+        if let Err(e) = self.ctx.set_faulty(false).await {
+            warn!("Failed to mark worker '{}' as running: {}", address, e);
+        }
+        true
+    }
+
     /// Build and spawn a new worker relay, returning a send handle to it
-    pub(crate) fn init(rt: &Handle, worker: W, ctx: Context, ctrl_rx: SmallReceiver<CtrlSignal>) {
+    pub(crate) fn init(
+        rt: &Handle,
+        worker: W,
+        ctx: Context,
+        ctrl_rx: SmallReceiver<CtrlSignal>,
+        #[cfg(feature = "std")] supervisor: Option<Supervisor>,
+    ) {
+        #[cfg(feature = "std")]
+        let relay = WorkerRelay::<W, M>::new(worker, ctx, supervisor);
+        #[cfg(not(feature = "std"))]
         let relay = WorkerRelay::<W, M>::new(worker, ctx);
         rt.spawn(relay.run(ctrl_rx));
     }
@@ -7,25 +7,24 @@ mod stop_processor;
 mod stop_worker;
 mod utils;
 
-#[cfg(feature = "metrics")]
-use std::sync::atomic::AtomicUsize;
-
 use record::{AddressMeta, AddressRecord, InternalMap};
 use state::{NodeState, RouterState};
 
-use crate::channel_types::{router_channel, MessageSender, RouterReceiver, SmallSender};
+use crate::channel_types::{router_channel_with_capacity, RouterReceiver, SmallSender};
+use crate::metrics::Metrics;
 use crate::{
     error::{NodeError, NodeReason},
     relay::CtrlSignal,
-    NodeMessage, NodeReplyResult, RouterReply, ShutdownType,
+    MailboxSender, NodeMessage, NodeReplyResult, RouterReply, ShutdownType,
 };
-use ockam_core::compat::{collections::BTreeMap, sync::Arc};
-use ockam_core::{Address, RelayMessage, Result, TransportType};
+use ockam_core::compat::{collections::BTreeMap, string::ToString};
+use ockam_core::flow_control::FlowControls;
+use ockam_core::{Address, Result, TransportType};
 
 /// A pair of senders to a worker relay
 #[derive(Debug)]
 pub struct SenderPair {
-    pub msgs: MessageSender<RelayMessage>,
+    pub msgs: MailboxSender,
     pub ctrl: SmallSender<CtrlSignal>,
 }
 
@@ -47,6 +46,10 @@ pub struct Router {
     external: BTreeMap<TransportType, Address>,
     /// Receiver for messages from node
     receiver: Option<RouterReceiver<NodeMessage>>,
+    /// Flow controls shared with every Context on this node
+    flow_controls: FlowControls,
+    /// Metrics shared with every Context on this node
+    metrics: Metrics,
 }
 
 enum RouteType {
@@ -63,19 +66,27 @@ fn determine_type(next: &Address) -> RouteType {
 }
 
 impl Router {
-    pub fn new() -> Self {
-        let (sender, receiver) = router_channel();
+    pub fn new(metrics: Metrics, capacity: usize) -> Self {
+        let (sender, receiver) = router_channel_with_capacity(capacity);
+        let flow_controls = FlowControls::default();
         Self {
             state: RouterState::new(sender),
-            map: InternalMap::default(),
+            map: InternalMap::new(flow_controls.clone(), metrics.clone()),
             external: BTreeMap::new(),
             receiver: Some(receiver),
+            flow_controls,
+            metrics,
         }
     }
 
-    #[cfg(feature = "metrics")]
-    pub(crate) fn get_metrics_readout(&self) -> (Arc<AtomicUsize>, Arc<AtomicUsize>) {
-        self.map.get_metrics()
+    /// Get the [`FlowControls`] shared by this node
+    pub fn flow_controls(&self) -> &FlowControls {
+        &self.flow_controls
+    }
+
+    /// Get the [`Metrics`] shared by this node
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
     }
 
     /// Get the router receiver
@@ -92,7 +103,6 @@ impl Router {
                 vec![addr.clone()],
                 senders.msgs,
                 senders.ctrl,
-                Arc::new(0.into()), // don't track for app worker (yet?)
                 AddressMeta {
                     processor: false,
                     detached: true,
@@ -145,15 +155,7 @@ impl Router {
     }
 
     async fn handle_msg(&mut self, msg: NodeMessage) -> Result<bool> {
-        #[cfg(feature = "metrics")]
-        self.map.update_metrics(); // Possibly remove this from the hot path?
-
         use NodeMessage::*;
-        #[cfg(feature = "metrics")]
-        trace!(
-            "Current router alloc: {} addresses",
-            self.map.get_addr_count()
-        );
         match msg {
             // Successful router registration command
             Router(tt, addr, sender) if !self.external.contains_key(&tt) => {
@@ -180,9 +182,8 @@ impl Router {
                 addrs,
                 senders,
                 detached,
-                mailbox_count,
                 ref reply,
-            } => start_worker::exec(self, addrs, senders, detached, mailbox_count, reply).await?,
+            } => start_worker::exec(self, addrs, senders, detached, reply).await?,
             StopWorker(ref addr, ref detached, ref reply) => {
                 stop_worker::exec(self, addr, *detached, reply).await?
             }
@@ -224,11 +225,7 @@ impl Router {
 
             StopAck(addr) if self.state.running() => {
                 trace!("Received shutdown ACK for address {}", addr);
-                if let Some(rec) = self.map.internal.remove(&addr) {
-                    rec.address_set().iter().for_each(|addr| {
-                        self.map.addr_map.remove(addr);
-                    });
-                }
+                self.map.remove_record(&addr);
             }
 
             StopAck(addr) => {
@@ -284,6 +281,24 @@ impl Router {
                 }
             }
 
+            SetFaulty(addr, faulty) => {
+                trace!("Marking address {} as faulty: {}", addr, faulty);
+                match self.map.internal.get_mut(&addr) {
+                    Some(record) => record.set_faulty(faulty),
+                    None => warn!("Failed to set address {} as faulty: no such worker", addr),
+                }
+            }
+
+            #[cfg(feature = "std")]
+            RestartCluster(addr) => {
+                debug!("Restarting the cluster of address {}", addr);
+                for record in self.map.cluster_peers(&addr) {
+                    record.restart();
+                }
+            }
+            #[cfg(not(feature = "std"))]
+            RestartCluster(_) => {}
+
             // Handle route/ sender requests
             SenderReq(ref addr, ref reply) => match determine_type(addr) {
                 RouteType::Internal => utils::resolve(self, addr, reply).await?,
@@ -302,7 +317,12 @@ impl Router {
     async fn run_inner(&mut self) -> Result<()> {
         while let Some(msg) = self.get_recv()?.recv().await {
             let msg_str = format!("{}", msg);
-            match self.handle_msg(msg).await {
+            #[cfg(feature = "std")]
+            let started = std::time::Instant::now();
+            let result = self.handle_msg(msg).await;
+            #[cfg(feature = "std")]
+            self.record_command_metrics(&msg_str, started);
+            match result {
                 Ok(should_break) => {
                     if should_break {
                         // We drop the receiver end here
@@ -318,4 +338,27 @@ impl Router {
 
         Ok(())
     }
+
+    /// Record the duration of a command and the size of the address maps
+    #[cfg(feature = "std")]
+    fn record_command_metrics(&self, command: &str, started: std::time::Instant) {
+        if !self.metrics.is_enabled() {
+            return;
+        }
+        self.metrics.record_histogram(
+            crate::metrics::ROUTER_COMMAND_DURATION,
+            &[("command", command.to_string())],
+            started.elapsed().as_secs_f64(),
+        );
+        self.metrics.set_gauge(
+            crate::metrics::ROUTER_ADDRESSES,
+            &[],
+            self.map.internal.len() as f64,
+        );
+        self.metrics.set_gauge(
+            crate::metrics::ROUTER_CLUSTERS,
+            &[],
+            self.map.cluster_count() as f64,
+        );
+    }
 }
@@ -1,18 +1,19 @@
-use crate::channel_types::{MessageSender, SmallSender};
+use crate::channel_types::SmallSender;
+use crate::metrics::Metrics;
 use crate::relay::CtrlSignal;
 use crate::{
     error::{NodeError, NodeReason},
-    NodeReplyResult, RouterReply,
+    MailboxSender, NodeReplyResult, RouterReply,
 };
-use core::sync::atomic::{AtomicUsize, Ordering};
 use ockam_core::{
     compat::{
         collections::{BTreeMap, BTreeSet},
         string::String,
-        sync::Arc,
+        string::ToString,
         vec::Vec,
     },
-    Address, RelayMessage, Result,
+    flow_control::FlowControls,
+    Address, Result,
 };
 
 /// Address states and associated logic
@@ -28,26 +29,25 @@ pub struct InternalMap {
     clusters: BTreeMap<String, BTreeSet<Address>>,
     /// Track stop information
     stopping: BTreeSet<Address>,
-    /// Metrics collection and sharing
-    #[cfg(feature = "metrics")]
-    metrics: (Arc<AtomicUsize>, Arc<AtomicUsize>),
+    /// Flow controls of the node, cleaned up when addresses are removed
+    flow_controls: FlowControls,
+    /// Metrics of the node, whose series are removed with their addresses
+    metrics: Metrics,
 }
 
 impl InternalMap {
-    #[cfg(feature = "metrics")]
-    pub(super) fn update_metrics(&self) {
-        self.metrics.0.store(self.internal.len(), Ordering::Release);
-        self.metrics.1.store(self.clusters.len(), Ordering::Release);
-    }
-
-    #[cfg(feature = "metrics")]
-    pub(super) fn get_metrics(&self) -> (Arc<AtomicUsize>, Arc<AtomicUsize>) {
-        (Arc::clone(&self.metrics.0), Arc::clone(&self.metrics.1))
+    pub(super) fn new(flow_controls: FlowControls, metrics: Metrics) -> Self {
+        Self {
+            flow_controls,
+            metrics,
+            ..Default::default()
+        }
     }
 
-    #[cfg(feature = "metrics")]
-    pub(super) fn get_addr_count(&self) -> usize {
-        self.metrics.0.load(Ordering::Acquire)
+    /// Return the number of clusters
+    #[cfg(feature = "std")]
+    pub(super) fn cluster_count(&self) -> usize {
+        self.clusters.len()
     }
 
     /// Add an address to a particular cluster
@@ -108,6 +108,25 @@ impl InternalMap {
         )
     }
 
+    /// Return the records of the other workers in the cluster of an address
+    #[cfg(feature = "std")]
+    pub(super) fn cluster_peers(&mut self, primary: &Address) -> Vec<&mut AddressRecord> {
+        let addrs = match self.clusters.values().find(|addrs| addrs.contains(primary)) {
+            Some(addrs) => addrs.clone(),
+            None => return vec![],
+        };
+        self.internal
+            .iter_mut()
+            .filter(|(addr, rec)| {
+                *addr != primary
+                    && addrs.contains(*addr)
+                    && !rec.meta.processor
+                    && !rec.meta.detached
+            })
+            .map(|(_, rec)| rec)
+            .collect()
+    }
+
     /// Mark this address as "having started to stop"
     pub(super) fn init_stop(&mut self, addr: Address) {
         self.stopping.insert(addr);
@@ -145,9 +164,24 @@ impl InternalMap {
     /// Permanently free all remaining resources associated to a particular address
     pub(super) fn free_address(&mut self, primary: Address) {
         self.stopping.remove(&primary);
-        if let Some(record) = self.internal.remove(&primary) {
-            for addr in record.address_set {
-                self.addr_map.remove(&addr);
+        self.remove_record(&primary);
+    }
+
+    /// Remove the record of a primary address, together with all its addresses
+    pub(super) fn remove_record(&mut self, primary: &Address) -> Option<AddressRecord> {
+        let record = self.internal.remove(primary)?;
+        self.forget_addresses(&record.address_set);
+        Some(record)
+    }
+
+    /// Stop resolving the given addresses, and remove the Flow Control
+    /// information and metrics related to them
+    pub(super) fn forget_addresses(&mut self, addrs: &[Address]) {
+        for addr in addrs {
+            self.addr_map.remove(addr);
+            self.flow_controls.cleanup_address(addr);
+            if self.metrics.is_enabled() {
+                self.metrics.remove_series(&("address", addr.to_string()));
             }
         }
     }
@@ -163,19 +197,18 @@ pub struct AddressMeta {
 #[derive(Debug)]
 pub struct AddressRecord {
     address_set: Vec<Address>,
-    sender: Option<MessageSender<RelayMessage>>,
+    sender: Option<MailboxSender>,
     ctrl_tx: SmallSender<CtrlSignal>,
     state: AddressState,
     ready: ReadyState,
     meta: AddressMeta,
-    msg_count: Arc<AtomicUsize>,
 }
 
 impl AddressRecord {
     pub fn address_set(&self) -> &[Address] {
         &self.address_set
     }
-    pub fn sender(&self) -> MessageSender<RelayMessage> {
+    pub fn sender(&self) -> MailboxSender {
         self.sender.clone().expect("No such sender!")
     }
     pub fn sender_drop(&mut self) {
@@ -183,9 +216,8 @@ impl AddressRecord {
     }
     pub fn new(
         address_set: Vec<Address>,
-        sender: MessageSender<RelayMessage>,
+        sender: MailboxSender,
         ctrl_tx: SmallSender<CtrlSignal>,
-        msg_count: Arc<AtomicUsize>,
         meta: AddressMeta,
     ) -> Self {
         AddressRecord {
@@ -194,15 +226,10 @@ impl AddressRecord {
             ctrl_tx,
             state: AddressState::Running,
             ready: ReadyState::Initialising(vec![]),
-            msg_count,
             meta,
         }
     }
 
-    pub fn increment_msg_count(&self) {
-        self.msg_count.fetch_add(1, Ordering::Acquire);
-    }
-
     /// Signal this worker to stop -- it will no longer be able to receive messages
     pub async fn stop(&mut self) -> Result<()> {
         if self.meta.processor {
@@ -217,6 +244,31 @@ impl AddressRecord {
         Ok(())
     }
 
+    /// Ask a supervised worker to restart
+    ///
+    /// The signal is dropped if the worker already has a pending signal,
+    /// since the worker is then either restarting or stopping.
+    #[cfg(feature = "std")]
+    pub fn restart(&self) {
+        if let Err(e) = self.ctrl_tx.try_send(CtrlSignal::Restart) {
+            debug!("Restart signal not sent to {:?}: {}", self.address_set, e);
+        }
+    }
+
+    /// Mark a running worker as faulty, or a faulty worker as running again
+    pub fn set_faulty(&mut self, faulty: bool) {
+        match (&self.state, faulty) {
+            (AddressState::Running, true) => self.state = AddressState::Faulty,
+            (AddressState::Faulty, false) => self.state = AddressState::Running,
+            _ => {}
+        }
+    }
+
+    /// Return the current state of this record
+    pub fn state(&self) -> &AddressState {
+        &self.state
+    }
+
     /// Check the integrity of this record
     pub fn check(&self) -> bool {
         self.state == AddressState::Running
@@ -252,7 +304,6 @@ pub enum AddressState {
     /// The runner was signalled to shut-down (running `shutdown()`)
     Stopping,
     /// The runner has experienced an error and is waiting for supervisor intervention
-    #[allow(unused)]
     Faulty,
 }
 
@@ -6,7 +6,7 @@ use crate::{
 };
 #[cfg(feature = "std")]
 use ockam_core::env::get_env;
-use ockam_core::{compat::sync::Arc, Address, Result};
+use ockam_core::{Address, Result};
 
 /// Execute a `StartWorker` command
 pub(super) async fn exec(
@@ -39,12 +39,6 @@ async fn start(
         vec![addr.clone()],
         msgs,
         ctrl,
-        // We don't keep track of the mailbox count for processors
-        // because, while they are able to send and receive messages
-        // via their mailbox, most likely this metric is going to be
-        // irrelevant.  We may want to re-visit this decision in the
-        // future, if the way processors are used changes.
-        Arc::new(0.into()),
         AddressMeta {
             processor: true,
             detached: false,
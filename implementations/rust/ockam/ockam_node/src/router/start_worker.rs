@@ -4,13 +4,9 @@ use crate::{
     error::{NodeError, NodeReason},
     NodeReplyResult, RouterReason, RouterReply,
 };
-use core::sync::atomic::AtomicUsize;
 #[cfg(feature = "std")]
 use ockam_core::env::get_env;
-use ockam_core::{
-    compat::{sync::Arc, vec::Vec},
-    Address, Result,
-};
+use ockam_core::{compat::vec::Vec, Address, Result};
 
 /// Execute a `StartWorker` command
 pub(super) async fn exec(
@@ -18,11 +14,10 @@ pub(super) async fn exec(
     addrs: Vec<Address>,
     senders: SenderPair,
     detached: bool,
-    metrics: Arc<AtomicUsize>,
     reply: &SmallSender<NodeReplyResult>,
 ) -> Result<()> {
     match router.state.node_state() {
-        NodeState::Running => start(router, addrs, senders, detached, metrics, reply).await,
+        NodeState::Running => start(router, addrs, senders, detached, reply).await,
         NodeState::Stopping(_) => reject(reply).await,
         NodeState::Dead => unreachable!(),
     }?;
@@ -34,7 +29,6 @@ async fn start(
     addrs: Vec<Address>,
     senders: SenderPair,
     detached: bool,
-    metrics: Arc<AtomicUsize>,
     reply: &SmallSender<NodeReplyResult>,
 ) -> Result<()> {
     let primary_addr = addrs
@@ -54,7 +48,6 @@ async fn start(
         addrs.clone(),
         msgs,
         ctrl,
-        metrics,
         AddressMeta {
             processor: false,
             detached,
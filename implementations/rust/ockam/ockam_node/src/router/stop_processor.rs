@@ -13,8 +13,9 @@ pub(super) async fn exec(
 ) -> Result<()> {
     trace!("Stopping processor '{}'", main_addr);
 
-    // First check if the processor exists
-    let mut record = match router.map.internal.remove(main_addr) {
+    // First check if the processor exists, and remove it with all its addresses,
+    // Flow Control information and metrics
+    let mut record = match router.map.remove_record(main_addr) {
         Some(proc) => proc,
         None => {
             reply
@@ -26,9 +27,6 @@ pub(super) async fn exec(
         }
     };
 
-    // Remove  main address from addr_map too
-    router.map.addr_map.remove(main_addr);
-
     // Then send processor shutdown signal
     record.stop().await?;
 
@@ -27,9 +27,9 @@ pub(super) async fn exec(
         }
     };
 
-    // Get the internal address record
-    let record = match router.map.internal.get_mut(&primary_address) {
-        Some(r) => r,
+    // Get the addresses of the internal address record
+    let address_set = match router.map.internal.get(&primary_address) {
+        Some(r) => r.address_set().to_vec(),
         None => {
             // Actually should not happen
             reply
@@ -41,10 +41,9 @@ pub(super) async fn exec(
         }
     };
 
-    // Remove all secondary addresses
-    for addr in record.address_set().iter() {
-        router.map.addr_map.remove(addr);
-    }
+    // Remove all secondary addresses, with the Flow Control information
+    // and metrics related to the worker
+    router.map.forget_addresses(&address_set);
 
     reply
         .send(RouterReply::ok())
@@ -57,7 +56,9 @@ pub(super) async fn exec(
     // For detached workers (i.e. Context's without a mailbox relay
     // running) we simply drop the record
     if !detached {
-        record.sender_drop();
+        if let Some(record) = router.map.internal.get_mut(&primary_address) {
+            record.sender_drop();
+        }
     } else {
         router.map.free_address(primary_address);
     }
@@ -1,3 +1,4 @@
+use super::record::AddressState;
 use super::Router;
 use crate::channel_types::SmallSender;
 use crate::{
@@ -33,9 +34,12 @@ pub(super) async fn resolve(
     match router.map.internal.get(&primary_address) {
         Some(record) if record.check() => {
             trace!("{} OK", base);
-            record.increment_msg_count();
             reply.send(RouterReply::sender(addr.clone(), record.sender()))
         }
+        Some(record) if record.state() == &AddressState::Faulty => {
+            trace!("{} REJECTED; worker faulty", base);
+            reply.send(RouterReply::worker_rejected(WorkerReason::Faulty))
+        }
         Some(_) => {
             trace!("{} REJECTED; worker shutting down", base);
             reply.send(RouterReply::worker_rejected(WorkerReason::Shutdown))
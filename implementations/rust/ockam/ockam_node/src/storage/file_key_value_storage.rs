@@ -1,6 +1,9 @@
-use crate::{FileValueStorage, InMemoryKeyValueStorage, KeyValueStorage, ValueStorage};
+use crate::{
+    FileValueStorage, InMemoryKeyValueStorage, KeyRange, KeyValueStorage, ValueStorage, WriteBatch,
+};
 use ockam_core::compat::boxed::Box;
 use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::vec::Vec;
 use ockam_core::{async_trait, Result};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -14,7 +17,7 @@ pub struct FileKeyValueStorage<K, V> {
 }
 
 impl<
-        K: Ord + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+        K: Clone + Ord + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
         V: Default + Serialize + for<'de> Deserialize<'de> + Clone + Send + Sync + 'static,
     > FileKeyValueStorage<K, V>
 {
@@ -71,6 +74,25 @@ impl<
         self.file_storage.modify_value(f).await?;
         self.cache.delete(key).await
     }
+
+    /// Read the entries in the given range from the file.
+    /// The cache is not used since it might only contain some of the entries
+    async fn range(&self, range: KeyRange<K>) -> Result<Vec<(K, V)>> {
+        let f = move |map: BTreeMap<K, V>| Ok(range.select(&map));
+        self.file_storage.read_value(f).await
+    }
+
+    /// Apply all the operations of the batch with a single write of the file,
+    /// then update the cache
+    async fn write_batch(&self, batch: WriteBatch<K, V>) -> Result<()> {
+        let b = batch.clone();
+        let f = move |mut map: BTreeMap<K, V>| {
+            b.clone().apply_to(&mut map);
+            Ok(map)
+        };
+        self.file_storage.update_value(f).await?;
+        self.cache.write_batch(batch).await
+    }
 }
 
 #[cfg(test)]
@@ -100,6 +122,27 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_file_key_value_storage_range_and_batch() -> Result<()> {
+        let path = create_temp_file();
+        let storage: FileKeyValueStorage<u8, Value> =
+            FileKeyValueStorage::create(path.as_path()).await?;
+        storage.put(1, Value(10)).await?;
+
+        let mut batch = WriteBatch::new();
+        batch.put(2, Value(20)).put(3, Value(30)).delete(1);
+        storage.write_batch(batch).await?;
+        assert_eq!(storage.get(&1).await?, None);
+
+        // a new storage on the same file sees all the entries
+        let storage: FileKeyValueStorage<u8, Value> =
+            FileKeyValueStorage::create(path.as_path()).await?;
+        let all = storage.range(KeyRange::all()).await?;
+        assert_eq!(all, vec![(2, Value(20)), (3, Value(30))]);
+
+        Ok(())
+    }
+
     pub fn create_temp_file() -> PathBuf {
         let dir = std::env::temp_dir();
         let mut rng = thread_rng();
@@ -1,6 +1,6 @@
-use crate::KeyValueStorage;
+use crate::{KeyRange, KeyValueStorage, WriteBatch};
 use ockam_core::compat::collections::BTreeMap;
-use ockam_core::compat::{boxed::Box, sync::Arc, sync::RwLock};
+use ockam_core::compat::{boxed::Box, sync::Arc, sync::RwLock, vec::Vec};
 use ockam_core::{async_trait, Result};
 
 /// In memory implementation of a key / value storage
@@ -18,7 +18,7 @@ impl<K, V> Default for InMemoryKeyValueStorage<K, V> {
 }
 
 #[async_trait]
-impl<K: Ord + Send + Sync + 'static, V: Clone + Send + Sync + 'static> KeyValueStorage<K, V>
+impl<K: Clone + Ord + Send + Sync + 'static, V: Clone + Send + Sync + 'static> KeyValueStorage<K, V>
     for InMemoryKeyValueStorage<K, V>
 {
     async fn put(&self, key: K, value: V) -> Result<()> {
@@ -37,9 +37,20 @@ impl<K: Ord + Send + Sync + 'static, V: Clone + Send + Sync + 'static> KeyValueS
         let mut storage = self.storage.write().unwrap();
         Ok(storage.remove(key))
     }
+
+    async fn range(&self, range: KeyRange<K>) -> Result<Vec<(K, V)>> {
+        let storage = self.storage.read().unwrap();
+        Ok(range.select(&storage))
+    }
+
+    async fn write_batch(&self, batch: WriteBatch<K, V>) -> Result<()> {
+        let mut storage = self.storage.write().unwrap();
+        batch.apply_to(&mut storage);
+        Ok(())
+    }
 }
 
-impl<K: Ord + Sync + Send + 'static, V: Clone + Send + Sync + 'static>
+impl<K: Clone + Ord + Sync + Send + 'static, V: Clone + Send + Sync + 'static>
     InMemoryKeyValueStorage<K, V>
 {
     /// Create a new in-memory key / value storage
@@ -77,6 +88,25 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_range_and_batch() -> Result<()> {
+        let storage = InMemoryKeyValueStorage::<u8, Value>::create();
+        storage.put(1, Value(10)).await?;
+
+        // all the operations of a batch are applied
+        let mut batch = WriteBatch::new();
+        batch.put(2, Value(20)).put(3, Value(30)).delete(1);
+        storage.write_batch(batch).await?;
+
+        let all = storage.range(KeyRange::all()).await?;
+        assert_eq!(all, vec![(2, Value(20)), (3, Value(30))]);
+
+        let some = storage.range(KeyRange::starting_at(3)).await?;
+        assert_eq!(some, vec![(3, Value(30))]);
+
+        Ok(())
+    }
+
     #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Debug, Clone)]
     struct Value(u8);
 }
@@ -0,0 +1,151 @@
+use core::ops::{Bound, RangeBounds};
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::string::String;
+use ockam_core::compat::vec::Vec;
+
+/// A range of keys, used to scan a [`KeyValueStorage`](crate::KeyValueStorage)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyRange<K> {
+    start: Bound<K>,
+    end: Bound<K>,
+}
+
+impl<K> KeyRange<K> {
+    /// Create a range with the given bounds
+    pub fn new(start: Bound<K>, end: Bound<K>) -> Self {
+        Self { start, end }
+    }
+
+    /// Range containing all the keys
+    pub fn all() -> Self {
+        Self::new(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    /// Range containing all the keys greater or equal to `start`
+    pub fn starting_at(start: K) -> Self {
+        Self::new(Bound::Included(start), Bound::Unbounded)
+    }
+
+    /// Range containing all the keys between `start` included and `end` excluded
+    pub fn between(start: K, end: K) -> Self {
+        Self::new(Bound::Included(start), Bound::Excluded(end))
+    }
+}
+
+impl<K: Ord> KeyRange<K> {
+    /// Return true if the key is part of this range
+    pub fn contains(&self, key: &K) -> bool {
+        RangeBounds::contains(self, key)
+    }
+
+    /// Return true if no key can be part of this range
+    pub fn is_empty(&self) -> bool {
+        match (&self.start, &self.end) {
+            (Bound::Included(s), Bound::Included(e)) => s > e,
+            (Bound::Included(s), Bound::Excluded(e))
+            | (Bound::Excluded(s), Bound::Included(e))
+            | (Bound::Excluded(s), Bound::Excluded(e)) => s >= e,
+            _ => false,
+        }
+    }
+
+    /// Return the entries of a map which are part of this range, ordered by key
+    pub fn select<V: Clone>(&self, map: &BTreeMap<K, V>) -> Vec<(K, V)>
+    where
+        K: Clone,
+    {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        map.range((self.start_bound(), self.end_bound()))
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+impl KeyRange<String> {
+    /// Range containing all the keys starting with `prefix`
+    pub fn prefix(prefix: &str) -> Self {
+        let end = match prefix_end(prefix) {
+            Some(end) => Bound::Excluded(end),
+            None => Bound::Unbounded,
+        };
+        Self::new(Bound::Included(prefix.into()), end)
+    }
+}
+
+impl<K> RangeBounds<K> for KeyRange<K> {
+    fn start_bound(&self) -> Bound<&K> {
+        bound_ref(&self.start)
+    }
+
+    fn end_bound(&self) -> Bound<&K> {
+        bound_ref(&self.end)
+    }
+}
+
+/// Borrow the key of a bound (`Bound::as_ref` is only available from Rust 1.65)
+fn bound_ref<K>(bound: &Bound<K>) -> Bound<&K> {
+    match bound {
+        Bound::Included(k) => Bound::Included(k),
+        Bound::Excluded(k) => Bound::Excluded(k),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+/// Return the smallest string which is greater than all the strings starting with `prefix`,
+/// if there is one
+fn prefix_end(prefix: &str) -> Option<String> {
+    let mut chars: Vec<char> = prefix.chars().collect();
+    while let Some(last) = chars.pop() {
+        let next = match last {
+            '\u{D7FF}' => Some('\u{E000}'),
+            c => char::from_u32(c as u32 + 1),
+        };
+        if let Some(next) = next {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Bound;
+
+    #[test]
+    fn test_prefix_range() {
+        let map: BTreeMap<String, u8> = ["a", "ab", "abc", "abd", "ac", "b"]
+            .iter()
+            .enumerate()
+            .map(|(i, k)| (k.to_string(), i as u8))
+            .collect();
+
+        let keys = |r: KeyRange<String>| -> Vec<String> {
+            r.select(&map).into_iter().map(|(k, _)| k).collect()
+        };
+        assert_eq!(keys(KeyRange::prefix("ab")), ["ab", "abc", "abd"]);
+        assert_eq!(
+            keys(KeyRange::prefix("")),
+            map.keys().cloned().collect::<Vec<_>>()
+        );
+        assert_eq!(keys(KeyRange::prefix("z")), Vec::<String>::new());
+
+        let r = KeyRange::prefix("a\u{10FFFF}");
+        assert_eq!(r.end_bound(), Bound::Excluded(&"b".to_string()));
+    }
+
+    #[test]
+    fn test_empty_range() {
+        let map: BTreeMap<u8, u8> = (0..10).map(|i| (i, i)).collect();
+        assert!(KeyRange::between(5, 5).select(&map).is_empty());
+        assert!(KeyRange::new(Bound::Excluded(5), Bound::Excluded(5))
+            .select(&map)
+            .is_empty());
+        assert!(KeyRange::between(7, 2).select(&map).is_empty());
+        assert_eq!(KeyRange::between(2, 4).select(&map), [(2, 2), (3, 3)]);
+        assert_eq!(KeyRange::starting_at(8).select(&map), [(8, 8), (9, 9)]);
+    }
+}
@@ -1,5 +1,8 @@
+use crate::{KeyRange, WriteBatch, WriteOperation};
 use ockam_core::compat::boxed::Box;
-use ockam_core::{async_trait, Result};
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Error, Result};
 
 /// This trait defines a key/value storage
 #[async_trait]
@@ -12,4 +15,94 @@ pub trait KeyValueStorage<K, V>: Sync + Send + 'static {
 
     /// Delete a value and return it if found
     async fn delete(&self, key: &K) -> Result<Option<V>>;
+
+    /// Return all the entries having a key in the given range, ordered by key.
+    /// Use [`KeyRange::all`] to iterate over all the entries
+    ///
+    /// The default implementation returns an error, for storages which cannot be scanned
+    async fn range(&self, _range: KeyRange<K>) -> Result<Vec<(K, V)>>
+    where
+        K: Send + 'async_trait,
+    {
+        Err(Error::new(
+            Origin::Node,
+            Kind::Unsupported,
+            "this key value storage does not support range scans",
+        ))
+    }
+
+    /// Apply all the operations of a batch atomically.
+    /// If an error occurs, none of the operations is applied
+    ///
+    /// The default implementation applies the operations one by one, so it is not
+    /// atomic: storages which support transactions should override it
+    async fn write_batch(&self, batch: WriteBatch<K, V>) -> Result<()>
+    where
+        K: Send + 'async_trait,
+        V: Send + 'async_trait,
+    {
+        for operation in batch.into_operations() {
+            match operation {
+                WriteOperation::Put(key, value) => self.put(key, value).await?,
+                WriteOperation::Delete(key) => {
+                    self.delete(&key).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Copy all the entries of a storage to another storage, in a single batch,
+/// and return the number of copied entries.
+///
+/// This can be used to migrate the data of a storage to a different backend,
+/// for example from a [`FileKeyValueStorage`](crate::FileKeyValueStorage) to a
+/// `SqliteKeyValueStorage`.
+pub async fn migrate_key_value_storage<K: Send + 'static, V: Send + 'static>(
+    from: &dyn KeyValueStorage<K, V>,
+    to: &dyn KeyValueStorage<K, V>,
+) -> Result<usize> {
+    let entries = from.range(KeyRange::all()).await?;
+    let count = entries.len();
+    to.write_batch(entries.into_iter().collect()).await?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryKeyValueStorage;
+
+    /// A storage only implementing the required methods
+    struct PointStorage(InMemoryKeyValueStorage<u8, u8>);
+
+    #[async_trait]
+    impl KeyValueStorage<u8, u8> for PointStorage {
+        async fn put(&self, key: u8, value: u8) -> Result<()> {
+            self.0.put(key, value).await
+        }
+
+        async fn get(&self, key: &u8) -> Result<Option<u8>> {
+            self.0.get(key).await
+        }
+
+        async fn delete(&self, key: &u8) -> Result<Option<u8>> {
+            self.0.delete(key).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_implementations() -> Result<()> {
+        let storage = PointStorage(InMemoryKeyValueStorage::default());
+        storage.put(1, 1).await?;
+        let mut batch = WriteBatch::new();
+        batch.put(2, 2).delete(1);
+        storage.write_batch(batch).await?;
+
+        assert_eq!(storage.get(&1).await?, None);
+        assert_eq!(storage.get(&2).await?, Some(2));
+        assert!(storage.range(KeyRange::all()).await.is_err());
+        Ok(())
+    }
 }
@@ -12,17 +12,35 @@ mod in_memory_key_value_storage;
 /// In memory implementation of a value storage
 mod in_memory_value_storage;
 
+/// Range of keys to scan in a key value storage
+mod key_range;
+
 /// Trait defining the functions for a key value storage
 mod key_value_storage;
 
+/// Encoding of keys preserving their order
+mod ordered_key;
+
+/// SQLite implementation of a key value storage
+#[cfg(feature = "sqlite")]
+mod sqlite_key_value_storage;
+
 /// Trait defining the functions for a value storage
 mod value_storage;
 
+/// Atomic list of modifications of a key value storage
+mod write_batch;
+
 #[cfg(feature = "std")]
 pub use file_key_value_storage::*;
 #[cfg(feature = "std")]
 pub use file_value_storage::*;
 pub use in_memory_key_value_storage::*;
 pub use in_memory_value_storage::*;
+pub use key_range::*;
 pub use key_value_storage::*;
+pub use ordered_key::*;
+#[cfg(feature = "sqlite")]
+pub use sqlite_key_value_storage::*;
 pub use value_storage::*;
+pub use write_batch::*;
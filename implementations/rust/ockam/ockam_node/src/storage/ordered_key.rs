@@ -0,0 +1,176 @@
+use ockam_core::compat::string::String;
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{Error, Result};
+
+/// A key which can be encoded as bytes sorting in the same order as the keys themselves
+///
+/// This lets a storage compare encoded keys byte by byte, for example in SQL, to
+/// scan a [`KeyRange`](crate::KeyRange) without decoding all the keys.
+///
+/// Encodings are self-delimiting so that the encoding of a tuple is the
+/// concatenation of the encodings of its elements:
+///
+///  - integers are encoded in big-endian order, with the sign bit flipped for signed integers
+///  - strings and byte vectors have their `0x00` bytes escaped as `0x00 0xFF`, and are
+///    terminated by `0x00 0x01`
+pub trait OrderedKey: Ord + Sized {
+    /// Append the encoding of this key to a buffer
+    fn encode_ordered(&self, buffer: &mut Vec<u8>);
+
+    /// Decode a key from the start of a buffer and advance the buffer past its encoding
+    fn decode_ordered(buffer: &mut &[u8]) -> Result<Self>;
+
+    /// Return the encoding of this key
+    fn to_ordered_bytes(&self) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        self.encode_ordered(&mut buffer);
+        buffer
+    }
+
+    /// Decode a key which must use all the given bytes
+    fn from_ordered_bytes(mut bytes: &[u8]) -> Result<Self> {
+        let key = Self::decode_ordered(&mut bytes)?;
+        if bytes.is_empty() {
+            Ok(key)
+        } else {
+            Err(invalid_key("trailing bytes after the key"))
+        }
+    }
+}
+
+macro_rules! unsigned_ordered_key {
+    ($($t:ty),*) => {$(
+        impl OrderedKey for $t {
+            fn encode_ordered(&self, buffer: &mut Vec<u8>) {
+                buffer.extend_from_slice(&self.to_be_bytes());
+            }
+
+            fn decode_ordered(buffer: &mut &[u8]) -> Result<Self> {
+                let bytes = take(buffer, core::mem::size_of::<$t>())?;
+                Ok(<$t>::from_be_bytes(bytes.try_into().unwrap()))
+            }
+        }
+    )*};
+}
+
+macro_rules! signed_ordered_key {
+    ($($t:ty => $u:ty),*) => {$(
+        impl OrderedKey for $t {
+            fn encode_ordered(&self, buffer: &mut Vec<u8>) {
+                ((*self as $u) ^ (1 << (<$u>::BITS - 1))).encode_ordered(buffer)
+            }
+
+            fn decode_ordered(buffer: &mut &[u8]) -> Result<Self> {
+                Ok((<$u>::decode_ordered(buffer)? ^ (1 << (<$u>::BITS - 1))) as $t)
+            }
+        }
+    )*};
+}
+
+unsigned_ordered_key!(u8, u16, u32, u64, u128);
+signed_ordered_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+impl OrderedKey for Vec<u8> {
+    fn encode_ordered(&self, buffer: &mut Vec<u8>) {
+        for b in self {
+            buffer.push(*b);
+            if *b == 0 {
+                buffer.push(0xFF);
+            }
+        }
+        buffer.extend_from_slice(&[0x00, 0x01]);
+    }
+
+    fn decode_ordered(buffer: &mut &[u8]) -> Result<Self> {
+        let mut bytes = Vec::new();
+        loop {
+            match take(buffer, 1)?[0] {
+                0x00 => match take(buffer, 1)?[0] {
+                    0xFF => bytes.push(0x00),
+                    0x01 => return Ok(bytes),
+                    _ => return Err(invalid_key("invalid escape sequence")),
+                },
+                b => bytes.push(b),
+            }
+        }
+    }
+}
+
+impl OrderedKey for String {
+    // UTF-8 preserves the order of the code points
+    fn encode_ordered(&self, buffer: &mut Vec<u8>) {
+        self.as_bytes().to_vec().encode_ordered(buffer)
+    }
+
+    fn decode_ordered(buffer: &mut &[u8]) -> Result<Self> {
+        String::from_utf8(Vec::decode_ordered(buffer)?)
+            .map_err(|e| Error::new(Origin::Node, Kind::Serialization, e))
+    }
+}
+
+impl<A: OrderedKey, B: OrderedKey> OrderedKey for (A, B) {
+    fn encode_ordered(&self, buffer: &mut Vec<u8>) {
+        self.0.encode_ordered(buffer);
+        self.1.encode_ordered(buffer);
+    }
+
+    fn decode_ordered(buffer: &mut &[u8]) -> Result<Self> {
+        Ok((A::decode_ordered(buffer)?, B::decode_ordered(buffer)?))
+    }
+}
+
+/// Split the first `n` bytes of a buffer
+fn take<'a>(buffer: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    if buffer.len() < n {
+        return Err(invalid_key("the key is truncated"));
+    }
+    let (head, tail) = buffer.split_at(n);
+    *buffer = tail;
+    Ok(head)
+}
+
+fn invalid_key(reason: &'static str) -> Error {
+    Error::new(Origin::Node, Kind::Serialization, reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::fmt::Debug;
+
+    fn assert_order_is_preserved<K: OrderedKey + Debug>(mut keys: Vec<K>) {
+        keys.sort();
+        let encoded: Vec<Vec<u8>> = keys.iter().map(|k| k.to_ordered_bytes()).collect();
+        for (key, bytes) in keys.iter().zip(encoded.iter()) {
+            assert_eq!(&K::from_ordered_bytes(bytes).unwrap(), key);
+        }
+        assert!(encoded.windows(2).all(|w| w[0] < w[1]), "{keys:?}");
+    }
+
+    #[test]
+    fn test_ordered_encodings() {
+        assert_order_is_preserved(vec![u64::MAX, 0, 255, 256, 1 << 40]);
+        assert_order_is_preserved(vec![i32::MIN, -256, -1, 0, 1, i32::MAX]);
+        assert_order_is_preserved::<String>(
+            ["", "a", "a\0", "a\0b", "ab", "b", "é", "\u{10FFFF}"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+        );
+        assert_order_is_preserved::<(String, String)>(
+            [("a", "z"), ("a", ""), ("ab", ""), ("a\0", "a"), ("b", "a")]
+                .into_iter()
+                .map(|(a, b)| (a.into(), b.into()))
+                .collect(),
+        );
+    }
+
+    #[test]
+    fn test_invalid_encodings() {
+        assert!(String::from_ordered_bytes(b"abc").is_err());
+        assert!(String::from_ordered_bytes(b"a\0\x02").is_err());
+        assert!(u16::from_ordered_bytes(&[1]).is_err());
+        assert!(u16::from_ordered_bytes(&[1, 2, 3]).is_err());
+    }
+}
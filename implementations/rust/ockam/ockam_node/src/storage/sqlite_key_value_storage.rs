@@ -0,0 +1,322 @@
+use crate::tokio::task::{self, JoinError};
+use crate::{KeyRange, KeyValueStorage, OrderedKey, WriteBatch, WriteOperation};
+use core::ops::{Bound, RangeBounds};
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::string::String;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::compat::vec::Vec;
+use ockam_core::errcode::{Kind, Origin};
+use ockam_core::{async_trait, Error, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::path::Path;
+
+/// Key value storage backed by a SQLite database
+///
+/// Entries are stored in a single table, so that each operation only reads
+/// or writes the entries it needs, instead of the whole data set. Batches
+/// are applied in a single SQLite transaction.
+///
+/// Keys are stored with their [`OrderedKey`] encoding, so that range scans
+/// are answered by the primary key index. Values are stored as JSON.
+pub struct SqliteKeyValueStorage<K, V> {
+    connection: Arc<Mutex<Connection>>,
+    _phantom_data: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V> Clone for SqliteKeyValueStorage<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            connection: self.connection.clone(),
+            _phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<K, V> SqliteKeyValueStorage<K, V> {
+    /// Open the database at the given path, creating it if necessary
+    pub async fn create(path: &Path) -> Result<Self> {
+        let path = path.to_path_buf();
+        let connection = task::spawn_blocking(move || -> Result<Connection> {
+            let connection = Connection::open(path).map_err(map_sqlite_err)?;
+            Self::init(&connection)?;
+            Ok(connection)
+        })
+        .await
+        .map_err(map_join_err)??;
+        Ok(Self::from_connection(connection))
+    }
+
+    /// Create a storage backed by a database living in memory, mostly useful for tests
+    pub fn in_memory() -> Result<Self> {
+        let connection = Connection::open_in_memory().map_err(map_sqlite_err)?;
+        Self::init(&connection)?;
+        Ok(Self::from_connection(connection))
+    }
+
+    fn from_connection(connection: Connection) -> Self {
+        Self {
+            connection: Arc::new(Mutex::new(connection)),
+            _phantom_data: PhantomData,
+        }
+    }
+
+    fn init(connection: &Connection) -> Result<()> {
+        // The WAL journal mode lets readers from other processes access the
+        // database while it is being written
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(map_sqlite_err)?;
+        connection
+            .execute(
+                "CREATE TABLE IF NOT EXISTS entries (key BLOB PRIMARY KEY, value TEXT NOT NULL)",
+                [],
+            )
+            .map_err(map_sqlite_err)?;
+        Ok(())
+    }
+
+    /// Run a function using the database connection, on a thread where blocking is allowed
+    async fn with_connection<R: Send + 'static>(
+        &self,
+        f: impl FnOnce(&mut Connection) -> Result<R> + Send + 'static,
+    ) -> Result<R> {
+        let connection = self.connection.clone();
+        task::spawn_blocking(move || {
+            let mut connection = connection.lock().unwrap();
+            f(&mut connection)
+        })
+        .await
+        .map_err(map_join_err)?
+    }
+}
+
+#[async_trait]
+impl<
+        K: OrderedKey + Send + Sync + 'static,
+        V: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+    > KeyValueStorage<K, V> for SqliteKeyValueStorage<K, V>
+{
+    async fn put(&self, key: K, value: V) -> Result<()> {
+        let (key, value) = (key.to_ordered_bytes(), encode(&value)?);
+        self.with_connection(move |c| {
+            c.execute(
+                "INSERT OR REPLACE INTO entries (key, value) VALUES (?1, ?2)",
+                params![key, value],
+            )
+            .map_err(map_sqlite_err)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get(&self, key: &K) -> Result<Option<V>> {
+        let key = key.to_ordered_bytes();
+        let value: Option<String> = self
+            .with_connection(move |c| {
+                c.query_row(
+                    "SELECT value FROM entries WHERE key = ?1",
+                    params![key],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(map_sqlite_err)
+            })
+            .await?;
+        value.map(|v| decode(&v)).transpose()
+    }
+
+    async fn delete(&self, key: &K) -> Result<Option<V>> {
+        let key = key.to_ordered_bytes();
+        let value: Option<String> = self
+            .with_connection(move |c| {
+                let tx = c.transaction().map_err(map_sqlite_err)?;
+                let value = tx
+                    .query_row(
+                        "SELECT value FROM entries WHERE key = ?1",
+                        params![key],
+                        |row| row.get(0),
+                    )
+                    .optional()
+                    .map_err(map_sqlite_err)?;
+                tx.execute("DELETE FROM entries WHERE key = ?1", params![key])
+                    .map_err(map_sqlite_err)?;
+                tx.commit().map_err(map_sqlite_err)?;
+                Ok(value)
+            })
+            .await?;
+        value.map(|v| decode(&v)).transpose()
+    }
+
+    async fn range(&self, range: KeyRange<K>) -> Result<Vec<(K, V)>> {
+        if range.is_empty() {
+            return Ok(Vec::new());
+        }
+        // Encoded keys are compared with memcmp, which preserves the order of the keys
+        let mut conditions = Vec::new();
+        let mut bounds = Vec::new();
+        for (bound, included, excluded) in [
+            (range.start_bound(), ">=", ">"),
+            (range.end_bound(), "<=", "<"),
+        ] {
+            let (operator, key) = match bound {
+                Bound::Included(key) => (included, key),
+                Bound::Excluded(key) => (excluded, key),
+                Bound::Unbounded => continue,
+            };
+            bounds.push(key.to_ordered_bytes());
+            conditions.push(format!("key {operator} ?{}", bounds.len()));
+        }
+        let mut query = String::from("SELECT key, value FROM entries");
+        if !conditions.is_empty() {
+            query.push_str(" WHERE ");
+            query.push_str(&conditions.join(" AND "));
+        }
+        query.push_str(" ORDER BY key");
+
+        let rows: Vec<(Vec<u8>, String)> = self
+            .with_connection(move |c| {
+                let mut statement = c.prepare(&query).map_err(map_sqlite_err)?;
+                let rows = statement
+                    .query_map(rusqlite::params_from_iter(bounds), |row| {
+                        Ok((row.get(0)?, row.get(1)?))
+                    })
+                    .map_err(map_sqlite_err)?;
+                rows.collect::<rusqlite::Result<_>>()
+                    .map_err(map_sqlite_err)
+            })
+            .await?;
+
+        rows.into_iter()
+            .map(|(key, value)| Ok((K::from_ordered_bytes(&key)?, decode(&value)?)))
+            .collect()
+    }
+
+    async fn write_batch(&self, batch: WriteBatch<K, V>) -> Result<()> {
+        let mut operations = Vec::with_capacity(batch.len());
+        for operation in batch.into_operations() {
+            operations.push(match operation {
+                WriteOperation::Put(k, v) => (k.to_ordered_bytes(), Some(encode(&v)?)),
+                WriteOperation::Delete(k) => (k.to_ordered_bytes(), None),
+            })
+        }
+        self.with_connection(move |c| {
+            let tx = c.transaction().map_err(map_sqlite_err)?;
+            for (key, value) in operations {
+                match value {
+                    Some(value) => tx.execute(
+                        "INSERT OR REPLACE INTO entries (key, value) VALUES (?1, ?2)",
+                        params![key, value],
+                    ),
+                    None => tx.execute("DELETE FROM entries WHERE key = ?1", params![key]),
+                }
+                .map_err(map_sqlite_err)?;
+            }
+            // the transaction is rolled back if it is dropped before being committed
+            tx.commit().map_err(map_sqlite_err)
+        })
+        .await
+    }
+}
+
+fn encode<T: Serialize>(t: &T) -> Result<String> {
+    serde_json::to_string(t).map_err(|e| Error::new(Origin::Node, Kind::Serialization, e))
+}
+
+fn decode<T: for<'de> Deserialize<'de>>(s: &str) -> Result<T> {
+    serde_json::from_str(s).map_err(|e| Error::new(Origin::Node, Kind::Serialization, e))
+}
+
+fn map_join_err(err: JoinError) -> Error {
+    Error::new(Origin::Application, Kind::Io, err)
+}
+
+fn map_sqlite_err(err: rusqlite::Error) -> Error {
+    Error::new(Origin::Application, Kind::Io, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::file_key_value_storage::tests::create_temp_file;
+    use crate::{migrate_key_value_storage, FileKeyValueStorage};
+
+    #[tokio::test]
+    async fn test_sqlite_key_value_storage() -> Result<()> {
+        let path = create_temp_file();
+        let storage = SqliteKeyValueStorage::<String, Value>::create(&path).await?;
+
+        storage.put("a".into(), Value(1)).await?;
+        assert_eq!(storage.get(&"a".into()).await?, Some(Value(1)));
+        assert_eq!(storage.get(&"b".into()).await?, None);
+
+        assert_eq!(storage.delete(&"a".into()).await?, Some(Value(1)));
+        assert_eq!(storage.delete(&"a".into()).await?, None);
+
+        let mut batch = WriteBatch::new();
+        batch
+            .put("member/1".into(), Value(1))
+            .put("member/2".into(), Value(2))
+            .put("policy/1".into(), Value(3))
+            .delete("member/1".into());
+        storage.write_batch(batch).await?;
+
+        // the data is persisted
+        let storage = SqliteKeyValueStorage::<String, Value>::create(&path).await?;
+        let members = storage.range(KeyRange::prefix("member/")).await?;
+        assert_eq!(members, vec![("member/2".into(), Value(2))]);
+        assert_eq!(storage.range(KeyRange::all()).await?.len(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_range_bounds_are_applied_in_key_order() -> Result<()> {
+        let storage = SqliteKeyValueStorage::<u32, Value>::in_memory()?;
+        let mut batch = WriteBatch::new();
+        for k in [1, 9, 10, 100, 256] {
+            batch.put(k, Value(k as u8));
+        }
+        storage.write_batch(batch).await?;
+
+        fn keys(entries: Vec<(u32, Value)>) -> Vec<u32> {
+            entries.into_iter().map(|(k, _)| k).collect()
+        }
+        assert_eq!(
+            keys(storage.range(KeyRange::between(9, 100)).await?),
+            vec![9, 10]
+        );
+        assert_eq!(
+            keys(storage.range(KeyRange::starting_at(10)).await?),
+            vec![10, 100, 256]
+        );
+        assert_eq!(
+            keys(
+                storage
+                    .range(KeyRange::new(Bound::Excluded(1), Bound::Included(256)))
+                    .await?
+            ),
+            vec![9, 10, 100, 256]
+        );
+        assert!(storage.range(KeyRange::between(10, 10)).await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_from_file_storage() -> Result<()> {
+        let file = FileKeyValueStorage::<u8, Value>::create(&create_temp_file()).await?;
+        file.put(1, Value(1)).await?;
+        file.put(2, Value(2)).await?;
+
+        let sqlite = SqliteKeyValueStorage::<u8, Value>::in_memory()?;
+        assert_eq!(migrate_key_value_storage(&file, &sqlite).await?, 2);
+        assert_eq!(
+            sqlite.range(KeyRange::all()).await?,
+            vec![(1, Value(1)), (2, Value(2))]
+        );
+        Ok(())
+    }
+
+    #[derive(Serialize, Deserialize, Default, PartialEq, Eq, Debug, Clone)]
+    struct Value(u8);
+}
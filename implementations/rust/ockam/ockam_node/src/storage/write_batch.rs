@@ -0,0 +1,95 @@
+use ockam_core::compat::collections::BTreeMap;
+use ockam_core::compat::vec::Vec;
+
+/// A single modification of a [`KeyValueStorage`](crate::KeyValueStorage)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WriteOperation<K, V> {
+    /// Store a value for a key
+    Put(K, V),
+    /// Delete the value of a key
+    Delete(K),
+}
+
+/// A list of modifications which are applied atomically by
+/// [`KeyValueStorage::write_batch`](crate::KeyValueStorage::write_batch)
+///
+/// Operations are applied in the order they were added to the batch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteBatch<K, V> {
+    operations: Vec<WriteOperation<K, V>>,
+}
+
+impl<K, V> Default for WriteBatch<K, V> {
+    fn default() -> Self {
+        Self {
+            operations: Vec::new(),
+        }
+    }
+}
+
+impl<K, V> WriteBatch<K, V> {
+    /// Create an empty batch
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the storage of a value to the batch
+    pub fn put(&mut self, key: K, value: V) -> &mut Self {
+        self.operations.push(WriteOperation::Put(key, value));
+        self
+    }
+
+    /// Add the deletion of a value to the batch
+    pub fn delete(&mut self, key: K) -> &mut Self {
+        self.operations.push(WriteOperation::Delete(key));
+        self
+    }
+
+    /// Return the number of operations in the batch
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Return true if the batch does not contain any operation
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Return the operations of the batch
+    pub fn operations(&self) -> &[WriteOperation<K, V>] {
+        &self.operations
+    }
+
+    /// Return the operations of the batch
+    pub fn into_operations(self) -> Vec<WriteOperation<K, V>> {
+        self.operations
+    }
+
+    /// Apply all the operations of the batch to a map
+    pub fn apply_to(self, map: &mut BTreeMap<K, V>)
+    where
+        K: Ord,
+    {
+        for operation in self.operations {
+            match operation {
+                WriteOperation::Put(k, v) => {
+                    map.insert(k, v);
+                }
+                WriteOperation::Delete(k) => {
+                    map.remove(&k);
+                }
+            }
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for WriteBatch<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self {
+            operations: iter
+                .into_iter()
+                .map(|(k, v)| WriteOperation::Put(k, v))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! Supervision of workers
+//!
+//! A worker started with [`SupervisionOptions`] is restarted when its
+//! `handle_message` function returns an error or panics: the node waits
+//! for a backoff delay, then calls
+//! [`Worker::restart`](ockam_core::Worker::restart) so that the worker
+//! can re-initialise its state, and keeps handling the messages of its
+//! mailbox. While it is waiting to be restarted the worker is marked as
+//! faulty and new messages sent to it are rejected.
+//!
+//! When a worker fails more than the configured number of times within
+//! a time window it is stopped. The restarts of a worker caused by the
+//! failures of other workers of its cluster are not counted, and happen
+//! without any delay.
+
+use core::time::Duration;
+use ockam_core::compat::collections::VecDeque;
+use std::time::Instant;
+
+/// Which workers are restarted when a supervised worker fails
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Only the failed worker is restarted
+    OneForOne,
+    /// The failed worker and all the supervised workers of its cluster
+    /// (see [`Context::set_cluster`](crate::Context::set_cluster)) are restarted
+    OneForAll,
+}
+
+/// Delay before restarting a failed worker
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    /// Wait the same delay before every restart
+    pub fn fixed(delay: Duration) -> Self {
+        Self {
+            initial: delay,
+            max: delay,
+        }
+    }
+
+    /// Double the delay for every restart in the current window,
+    /// starting from `initial` and up to `max`
+    pub fn exponential(initial: Duration, max: Duration) -> Self {
+        Self { initial, max }
+    }
+
+    /// Return the delay before a restart, given the number of previous
+    /// restarts in the current window
+    pub fn delay(&self, previous_restarts: usize) -> Duration {
+        let factor = 1u32
+            .checked_shl(previous_restarts as u32)
+            .unwrap_or(u32::MAX);
+        self.initial
+            .checked_mul(factor)
+            .unwrap_or(self.max)
+            .min(self.max)
+    }
+}
+
+/// Supervision configuration of a worker
+#[derive(Clone, Debug)]
+pub struct SupervisionOptions {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    window: Duration,
+    backoff: Backoff,
+}
+
+impl Default for SupervisionOptions {
+    fn default() -> Self {
+        Self::one_for_one()
+    }
+}
+
+impl SupervisionOptions {
+    /// Restart only the failed worker, at most 3 times per minute
+    pub fn one_for_one() -> Self {
+        Self {
+            strategy: RestartStrategy::OneForOne,
+            max_restarts: 3,
+            window: Duration::from_secs(60),
+            backoff: Backoff::exponential(Duration::from_millis(100), Duration::from_secs(10)),
+        }
+    }
+
+    /// Restart the failed worker and the supervised workers of its cluster,
+    /// at most 3 times per minute
+    pub fn one_for_all() -> Self {
+        Self {
+            strategy: RestartStrategy::OneForAll,
+            ..Self::one_for_one()
+        }
+    }
+
+    /// Stop the worker when it needs more than `max_restarts` restarts within `window`
+    pub fn with_max_restarts(mut self, max_restarts: usize, window: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.window = window;
+        self
+    }
+
+    /// Set the delay before restarting a failed worker
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Restart strategy
+    pub fn strategy(&self) -> RestartStrategy {
+        self.strategy
+    }
+
+    /// Maximum number of restarts within the window
+    pub fn max_restarts(&self) -> usize {
+        self.max_restarts
+    }
+
+    /// Window in which restarts are counted
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Delay before restarting a failed worker
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+}
+
+/// Restart history of a supervised worker
+pub(crate) struct Supervisor {
+    options: SupervisionOptions,
+    restarts: VecDeque<Instant>,
+}
+
+impl Supervisor {
+    pub(crate) fn new(options: SupervisionOptions) -> Self {
+        Self {
+            options,
+            restarts: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn strategy(&self) -> RestartStrategy {
+        self.options.strategy
+    }
+
+    /// Record a restart and return the delay to wait before it,
+    /// or `None` if the worker was restarted too many times and must be stopped
+    pub(crate) fn next_restart(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        while let Some(oldest) = self.restarts.front() {
+            if now.duration_since(*oldest) > self.options.window {
+                self.restarts.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.restarts.len() >= self.options.max_restarts {
+            return None;
+        }
+
+        let delay = self.options.backoff.delay(self.restarts.len());
+        self.restarts.push_back(now);
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delay() {
+        let backoff = Backoff::exponential(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay(2), Duration::from_millis(400));
+        assert_eq!(backoff.delay(4), Duration::from_secs(1));
+        assert_eq!(backoff.delay(64), Duration::from_secs(1));
+
+        let backoff = Backoff::fixed(Duration::from_millis(50));
+        assert_eq!(backoff.delay(3), Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_max_restarts() {
+        let options = SupervisionOptions::one_for_one()
+            .with_max_restarts(2, Duration::from_secs(60))
+            .with_backoff(Backoff::fixed(Duration::ZERO));
+        let mut supervisor = Supervisor::new(options);
+        assert!(supervisor.next_restart().is_some());
+        assert!(supervisor.next_restart().is_some());
+        assert!(supervisor.next_restart().is_none());
+    }
+}
@@ -1,5 +1,8 @@
 use crate::debugger;
 use crate::error::{NodeError, NodeReason};
+use crate::mailbox::MailboxOptions;
+#[cfg(feature = "std")]
+use crate::supervisor::{SupervisionOptions, Supervisor};
 use crate::{relay::WorkerRelay, Context, NodeMessage};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{
@@ -22,6 +25,9 @@ use ockam_core::{
 pub struct WorkerBuilder<W> {
     mailboxes: Mailboxes,
     worker: W,
+    mailbox_options: MailboxOptions,
+    #[cfg(feature = "std")]
+    supervision: Option<SupervisionOptions>,
 }
 
 impl<W> WorkerBuilder<W> {
@@ -49,13 +55,44 @@ where
             outgoing_access_control,
         );
 
-        Self { mailboxes, worker }
+        Self::with_mailboxes(mailboxes, worker)
     }
 
     /// Create a worker which uses the access control from the given
     /// [`Mailboxes`]
     pub fn with_mailboxes(mailboxes: Mailboxes, worker: W) -> Self {
-        Self { mailboxes, worker }
+        Self {
+            mailboxes,
+            worker,
+            mailbox_options: MailboxOptions::default(),
+            #[cfg(feature = "std")]
+            supervision: None,
+        }
+    }
+
+    /// Set the number of messages which can wait in the mailbox of the
+    /// worker before their senders have to wait.
+    /// The default is [`DEFAULT_MAILBOX_CAPACITY`](crate::channel_types::DEFAULT_MAILBOX_CAPACITY)
+    pub fn with_mailbox_capacity(mut self, capacity: usize) -> Self {
+        self.mailbox_options = self.mailbox_options.with_capacity(capacity);
+        self
+    }
+
+    /// Give the worker a priority lane holding up to `capacity` messages.
+    /// Messages sent with [`MessagePriority::High`](crate::MessagePriority::High)
+    /// are then handled before the other messages of the mailbox
+    #[cfg(feature = "std")]
+    pub fn with_priority_lane(mut self, capacity: usize) -> Self {
+        self.mailbox_options = self.mailbox_options.with_priority_lane(capacity);
+        self
+    }
+
+    /// Restart the worker according to the given options when it fails
+    /// to handle a message
+    #[cfg(feature = "std")]
+    pub fn with_supervision(mut self, supervision: SupervisionOptions) -> Self {
+        self.supervision = Some(supervision);
+        self
     }
 
     /// Consume this builder and start a new Ockam [`Worker`] from the given context
@@ -73,16 +110,24 @@ where
         let main_address = mailboxes.main_address().clone();
 
         // Pass it to the context
-        let (ctx, sender, ctrl_rx) = context.copy_with_mailboxes(mailboxes);
+        let (ctx, sender, ctrl_rx) = context.copy_with_mailboxes(mailboxes, self.mailbox_options);
 
         debugger::log_inherit_context("WORKER", context, &ctx);
 
         // Then initialise the worker message relay
+        #[cfg(feature = "std")]
+        WorkerRelay::<W, M>::init(
+            context.runtime(),
+            self.worker,
+            ctx,
+            ctrl_rx,
+            self.supervision.map(Supervisor::new),
+        );
+        #[cfg(not(feature = "std"))]
         WorkerRelay::<W, M>::init(context.runtime(), self.worker, ctx, ctrl_rx);
 
         // Send start request to router
-        let (msg, mut rx) =
-            NodeMessage::start_worker(addresses, sender, false, context.mailbox_count());
+        let (msg, mut rx) = NodeMessage::start_worker(addresses, sender, false);
         context
             .sender()
             .send(msg)
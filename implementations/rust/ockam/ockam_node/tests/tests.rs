@@ -3,12 +3,17 @@ use core::time::Duration;
 use ockam_core::compat::{
     boxed::Box,
     string::{String, ToString},
-    sync::Arc,
+    sync::{Arc, Mutex},
+    vec::Vec,
 };
+use ockam_core::flow_control::FlowControlPolicy;
 use ockam_core::{async_trait, Address, AllowAll, Any, Decodable, DenyAll, Message, LOCAL};
 use ockam_core::{route, Processor, Result, Routed, Worker};
 use ockam_node::compat::futures::FutureExt;
-use ockam_node::{Context, MessageReceiveOptions, NodeBuilder};
+use ockam_node::{
+    Backoff, Context, MessagePriority, MessageReceiveOptions, MessageSendReceiveOptions,
+    NodeBuilder, SupervisionOptions, WorkerBuilder,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicI8, AtomicU32};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -564,3 +569,275 @@ async fn starting_worker_with_dup_address_should_fail(ctx: &mut Context) -> Resu
         .is_err());
     ctx.stop().await
 }
+
+#[ockam_macros::test]
+async fn stopping_worker_should_cleanup_flow_controls(ctx: &mut Context) -> Result<()> {
+    let flow_controls = ctx.flow_controls().clone();
+    let flow_control_id = flow_controls.generate_id();
+    let spawner_flow_control_id = flow_controls.generate_id();
+
+    ctx.start_worker("flow_control_worker", DummyWorker, DenyAll, DenyAll)
+        .await?;
+    ctx.start_worker("flow_control_spawner", DummyWorker, DenyAll, DenyAll)
+        .await?;
+
+    let worker: Address = "flow_control_worker".into();
+    let spawner: Address = "flow_control_spawner".into();
+    let alias: Address = "flow_control_worker_alias".into();
+    flow_controls.add_spawner(&spawner, &spawner_flow_control_id);
+    flow_controls.add_producer(
+        &worker,
+        &flow_control_id,
+        Some(&spawner_flow_control_id),
+        vec![alias.clone()],
+    );
+    flow_controls.add_consumer(
+        &worker,
+        &spawner_flow_control_id,
+        FlowControlPolicy::SpawnerAllowMultipleMessages,
+    );
+
+    assert!(flow_controls
+        .find_flow_control_with_producer_address(&alias)
+        .is_some());
+    assert_eq!(flow_controls.get_all_consumers().len(), 1);
+
+    ctx.stop_worker("flow_control_worker").await?;
+
+    assert!(flow_controls
+        .get_flow_control_with_producer(&worker)
+        .is_none());
+    assert!(flow_controls
+        .find_flow_control_with_producer_address(&alias)
+        .is_none());
+    assert!(flow_controls
+        .get_flow_controls_with_consumer(&worker)
+        .is_empty());
+    assert!(flow_controls.get_all_consumers().is_empty());
+    assert!(flow_controls
+        .get_flow_control_with_spawner(&spawner)
+        .is_some());
+
+    ctx.stop_worker("flow_control_spawner").await?;
+    assert!(flow_controls.get_all_spawners().is_empty());
+
+    ctx.stop().await
+}
+
+struct FailingWorker {
+    restarts: Arc<AtomicU32>,
+    cluster: Option<&'static str>,
+}
+
+#[async_trait]
+impl Worker for FailingWorker {
+    type Context = Context;
+    type Message = String;
+
+    async fn initialize(&mut self, context: &mut Context) -> Result<()> {
+        if let Some(cluster) = self.cluster {
+            context.set_cluster(cluster).await?;
+        }
+        Ok(())
+    }
+
+    async fn restart(&mut self, _context: &mut Context) -> Result<()> {
+        self.restarts.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        match msg.as_body().as_str() {
+            "error" => Err(ockam_core::Error::new(
+                ockam_core::errcode::Origin::Node,
+                ockam_core::errcode::Kind::Internal,
+                "failing worker",
+            )),
+            "panic" => panic!("failing worker"),
+            _ => ctx.send(msg.return_route(), msg.body()).await,
+        }
+    }
+}
+
+#[ockam_macros::test]
+async fn supervised_worker_is_restarted_after_failures(ctx: &mut Context) -> Result<()> {
+    let supervision = SupervisionOptions::one_for_one()
+        .with_max_restarts(2, Duration::from_secs(60))
+        .with_backoff(Backoff::fixed(Duration::from_millis(10)));
+    let restarts = Arc::new(AtomicU32::new(0));
+    let worker = FailingWorker {
+        restarts: restarts.clone(),
+        cluster: None,
+    };
+    ctx.start_supervised_worker("failing", worker, supervision, AllowAll, AllowAll)
+        .await?;
+
+    // the worker fails once with an error, then with a panic
+    ctx.send("failing", "error".to_string()).await?;
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(restarts.load(Ordering::Relaxed), 1);
+    ctx.send("failing", "panic".to_string()).await?;
+    sleep(Duration::from_millis(100)).await;
+    assert_eq!(restarts.load(Ordering::Relaxed), 2);
+
+    // the worker keeps handling messages after being restarted
+    let reply: String = ctx.send_and_receive("failing", "hello".to_string()).await?;
+    assert_eq!(reply, "hello");
+
+    // the worker is stopped once it failed too many times
+    ctx.send("failing", "error".to_string()).await?;
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(restarts.load(Ordering::Relaxed), 2);
+    assert!(!ctx.list_workers().await?.contains(&"failing".into()));
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn one_for_all_supervision_restarts_the_cluster(ctx: &mut Context) -> Result<()> {
+    let supervision =
+        SupervisionOptions::one_for_all().with_backoff(Backoff::fixed(Duration::from_millis(10)));
+    let restarts = Arc::new(AtomicU32::new(0));
+    for address in ["failing_1", "failing_2"] {
+        let worker = FailingWorker {
+            restarts: restarts.clone(),
+            cluster: Some("failing_cluster"),
+        };
+        WorkerBuilder::with_access_control(Arc::new(AllowAll), Arc::new(AllowAll), address, worker)
+            .with_supervision(supervision.clone())
+            .start(ctx)
+            .await?;
+        ctx.wait_for(address).await?;
+    }
+
+    ctx.send("failing_1", "error".to_string()).await?;
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(restarts.load(Ordering::Relaxed), 2);
+
+    let reply: String = ctx
+        .send_and_receive("failing_2", "hello".to_string())
+        .await?;
+    assert_eq!(reply, "hello");
+
+    ctx.stop().await
+}
+
+#[ockam_macros::test]
+async fn restarts_with_the_cluster_are_not_counted(ctx: &mut Context) -> Result<()> {
+    let supervision = SupervisionOptions::one_for_all()
+        .with_max_restarts(1, Duration::from_secs(60))
+        .with_backoff(Backoff::fixed(Duration::from_millis(10)));
+    let restarts = Arc::new(AtomicU32::new(0));
+    for address in ["counted_1", "counted_2"] {
+        let worker = FailingWorker {
+            restarts: restarts.clone(),
+            cluster: Some("counted_cluster"),
+        };
+        WorkerBuilder::with_access_control(Arc::new(AllowAll), Arc::new(AllowAll), address, worker)
+            .with_supervision(supervision.clone())
+            .start(ctx)
+            .await?;
+        ctx.wait_for(address).await?;
+    }
+
+    // the second worker is restarted with the first one, without using its own restart
+    ctx.send("counted_1", "error".to_string()).await?;
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(restarts.load(Ordering::Relaxed), 2);
+
+    ctx.send("counted_2", "error".to_string()).await?;
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(restarts.load(Ordering::Relaxed), 4);
+    assert!(ctx.list_workers().await?.contains(&"counted_2".into()));
+
+    ctx.stop().await
+}
+
+struct RecordingWorker {
+    received: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait]
+impl Worker for RecordingWorker {
+    type Context = Context;
+    type Message = String;
+
+    async fn handle_message(&mut self, _ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        let body = msg.body();
+        // give the other messages the time to be queued in the mailbox
+        if body == "slow" {
+            sleep(Duration::from_millis(200)).await;
+        }
+        self.received.lock().unwrap().push(body);
+        Ok(())
+    }
+}
+
+#[ockam_macros::test]
+async fn priority_messages_are_received_first(ctx: &mut Context) -> Result<()> {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let worker = RecordingWorker {
+        received: received.clone(),
+    };
+    WorkerBuilder::with_access_control(Arc::new(AllowAll), Arc::new(AllowAll), "recording", worker)
+        .with_mailbox_capacity(4)
+        .with_priority_lane(1)
+        .start(ctx)
+        .await?;
+
+    ctx.send("recording", "slow".to_string()).await?;
+    sleep(Duration::from_millis(50)).await;
+    ctx.send("recording", "normal 1".to_string()).await?;
+    ctx.send("recording", "normal 2".to_string()).await?;
+    ctx.send_with_priority("recording", "high".to_string(), MessagePriority::High)
+        .await?;
+    sleep(Duration::from_millis(400)).await;
+
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec!["slow", "high", "normal 1", "normal 2"]
+    );
+
+    ctx.stop().await
+}
+
+struct PriorityEchoer;
+
+#[ockam_core::worker]
+impl Worker for PriorityEchoer {
+    type Context = Context;
+    type Message = String;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        let priority = MessagePriority::of(msg.local_message());
+        ctx.send_with_priority(msg.return_route(), format!("{priority:?}"), priority)
+            .await
+    }
+}
+
+#[ockam_macros::test]
+async fn send_and_receive_keeps_the_priority(ctx: &mut Context) -> Result<()> {
+    WorkerBuilder::with_access_control(
+        Arc::new(AllowAll),
+        Arc::new(AllowAll),
+        "priority_echoer",
+        PriorityEchoer,
+    )
+    .with_priority_lane(1)
+    .start(ctx)
+    .await?;
+
+    let options = MessageSendReceiveOptions::new().with_priority(MessagePriority::High);
+    let reply = ctx
+        .send_and_receive_extended::<String>("priority_echoer", String::new(), options)
+        .await?
+        .body();
+    assert_eq!(reply, "High");
+
+    let reply: String = ctx
+        .send_and_receive("priority_echoer", String::new())
+        .await?;
+    assert_eq!(reply, "Normal");
+
+    ctx.stop().await
+}
@@ -0,0 +1,147 @@
+use crate::TransportError;
+use ockam_core::compat::sync::Arc;
+use ockam_core::compat::vec::vec;
+use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
+use ockam_core::{Address, AllowAll, IncomingAccessControl, OutgoingAccessControl, Result};
+
+/// Access control of the sender and receiver of a connection
+pub struct ConnectionAccessControl {
+    /// Access control of the messages sent over the connection
+    pub sender_incoming_access_control: Arc<dyn IncomingAccessControl>,
+    /// Access control of the messages received from the connection
+    pub receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+}
+
+impl ConnectionAccessControl {
+    fn allow_all() -> Self {
+        Self {
+            sender_incoming_access_control: Arc::new(AllowAll),
+            receiver_outgoing_access_control: Arc::new(AllowAll),
+        }
+    }
+}
+
+/// Flow control of a connection initiated by this node
+///
+/// Shared by the connection options of the transports: when the receiver of
+/// the connection is marked as a producer, the messages it receives can only
+/// reach the consumers of its [`FlowControlId`].
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionFlowControl {
+    producer: Option<(FlowControls, FlowControlId)>,
+}
+
+impl ConnectionFlowControl {
+    /// Don't restrict the messages received from the connection
+    pub fn insecure() -> Self {
+        Self { producer: None }
+    }
+
+    /// Mark the receiver of the connection as a Producer for the given [`FlowControlId`]
+    pub fn producer(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
+        Self {
+            producer: Some((flow_controls.clone(), flow_control_id.clone())),
+        }
+    }
+
+    /// Register the receiver of a new connection as a producer, if necessary
+    pub fn setup(&self, receiver_address: &Address, sender_address: &Address) {
+        if let Some((flow_controls, flow_control_id)) = &self.producer {
+            flow_controls.add_producer(
+                receiver_address,
+                flow_control_id,
+                None,
+                vec![sender_address.clone()],
+            );
+        }
+    }
+
+    /// Create the access control of the sender and receiver of a new connection
+    pub fn access_control(self) -> ConnectionAccessControl {
+        match self.producer {
+            Some((flow_controls, flow_control_id)) => {
+                ConnectionAccessControl {
+                    sender_incoming_access_control: Arc::new(AllowAll),
+                    receiver_outgoing_access_control: Arc::new(
+                        FlowControlOutgoingAccessControl::new(flow_controls, flow_control_id, None),
+                    ),
+                }
+            }
+            None => ConnectionAccessControl::allow_all(),
+        }
+    }
+}
+
+/// Flow control of the connections accepted by a listener
+///
+/// Shared by the listener options of the transports: when the listener is
+/// marked as a spawner, each accepted connection gets a fresh random
+/// [`FlowControlId`], still marked with the [`FlowControlId`] of the listener.
+#[derive(Clone, Debug, Default)]
+pub struct ListenerFlowControl {
+    spawner: Option<(FlowControls, FlowControlId)>,
+}
+
+impl ListenerFlowControl {
+    /// Don't restrict the messages received from the accepted connections
+    pub fn insecure() -> Self {
+        Self { spawner: None }
+    }
+
+    /// Mark the listener as a Spawner with the given [`FlowControlId`]
+    pub fn spawner(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
+        Self {
+            spawner: Some((flow_controls.clone(), flow_control_id.clone())),
+        }
+    }
+
+    /// Register the worker or processor spawning the connections, if necessary
+    pub fn add_spawner(&self, address: &Address) {
+        if let Some((flow_controls, flow_control_id)) = &self.spawner {
+            flow_controls.add_spawner(address, flow_control_id);
+        }
+    }
+
+    /// Register the receiver of an accepted connection as a producer, if necessary,
+    /// and return its [`FlowControlId`]
+    pub fn setup(
+        &self,
+        receiver_address: &Address,
+        sender_address: &Address,
+    ) -> Option<FlowControlId> {
+        let (flow_controls, listener_flow_control_id) = self.spawner.as_ref()?;
+        let flow_control_id = flow_controls.generate_id();
+        flow_controls.add_producer(
+            receiver_address,
+            &flow_control_id,
+            Some(listener_flow_control_id),
+            vec![sender_address.clone()],
+        );
+
+        Some(flow_control_id)
+    }
+
+    /// Create the access control of the sender and receiver of an accepted connection,
+    /// given the [`FlowControlId`] returned by [`ListenerFlowControl::setup`]
+    pub fn access_control(
+        &self,
+        flow_control_id: Option<FlowControlId>,
+    ) -> Result<ConnectionAccessControl> {
+        match (&self.spawner, flow_control_id) {
+            (Some((flow_controls, listener_flow_control_id)), Some(flow_control_id)) => {
+                Ok(ConnectionAccessControl {
+                    sender_incoming_access_control: Arc::new(AllowAll),
+                    receiver_outgoing_access_control: Arc::new(
+                        FlowControlOutgoingAccessControl::new(
+                            flow_controls.clone(),
+                            flow_control_id,
+                            Some(listener_flow_control_id.clone()),
+                        ),
+                    ),
+                })
+            }
+            (None, None) => Ok(ConnectionAccessControl::allow_all()),
+            _ => Err(TransportError::FlowControlInconsistency.into()),
+        }
+    }
+}
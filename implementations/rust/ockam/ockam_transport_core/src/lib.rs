@@ -14,7 +14,9 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod error;
+mod flow_control;
 mod transport;
 
 pub use error::TransportError;
+pub use flow_control::*;
 pub use transport::*;
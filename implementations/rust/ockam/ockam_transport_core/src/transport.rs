@@ -1,6 +1,8 @@
 use ockam_core::compat::boxed::Box;
+use ockam_core::compat::format;
+use ockam_core::errcode::{Kind, Origin};
 use ockam_core::flow_control::FlowControls;
-use ockam_core::{async_trait, Address, Result, TransportType};
+use ockam_core::{async_trait, Address, Error, Result, TransportType};
 
 /// Generic representation of a Transport
 /// At minimum, a Transport must be able
@@ -19,3 +21,15 @@ pub trait Transport: Send + Sync + 'static {
         address: Address,
     ) -> Result<Address>;
 }
+
+/// Error returned when a transport is asked to resolve an address of another transport
+pub fn unsupported_address_error(transport_name: &str, address: &Address) -> Error {
+    Error::new(
+        Origin::Transport,
+        Kind::NotFound,
+        format!(
+            "this address can not be resolved by a {} transport {}",
+            transport_name, address
+        ),
+    )
+}
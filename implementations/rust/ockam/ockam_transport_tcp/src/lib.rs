@@ -18,11 +18,13 @@ extern crate core;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod framing;
 mod options;
 mod portal;
 mod registry;
 mod transport;
 
+pub use framing::*;
 use ockam_core::TransportType;
 pub use options::*;
 pub use portal::*;
@@ -34,5 +36,12 @@ pub(crate) use workers::*;
 
 pub(crate) const CLUSTER_NAME: &str = "_internals.transport.tcp";
 
+/// Number of bytes written to TCP connections, including framing
+pub const TCP_BYTES_SENT: &str = "ockam_tcp_bytes_sent_total";
+/// Number of bytes read from TCP connections, including framing
+pub const TCP_BYTES_RECEIVED: &str = "ockam_tcp_bytes_received_total";
+/// Number of open portal connections, labelled by portal type
+pub const TCP_PORTAL_CONNECTIONS: &str = "ockam_tcp_portal_connections";
+
 /// Transport type for TCP addresses
 pub const TCP: TransportType = TransportType::new(1);
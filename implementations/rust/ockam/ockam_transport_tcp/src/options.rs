@@ -1,18 +1,14 @@
 use crate::workers::Addresses;
-use ockam_core::compat::sync::Arc;
-use ockam_core::flow_control::{FlowControlId, FlowControlOutgoingAccessControl, FlowControls};
-use ockam_core::{AllowAll, IncomingAccessControl, OutgoingAccessControl, Result};
-use ockam_transport_core::TransportError;
-
-pub(crate) struct TcpConnectionAccessControl {
-    pub sender_incoming_access_control: Arc<dyn IncomingAccessControl>,
-    pub receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
-}
+use crate::{FramingOptions, TcpFraming};
+use ockam_core::flow_control::{FlowControlId, FlowControls};
+use ockam_core::Result;
+use ockam_transport_core::{ConnectionAccessControl, ConnectionFlowControl, ListenerFlowControl};
 
 /// Trust Options for a TCP connection
 #[derive(Clone, Debug)]
 pub struct TcpConnectionOptions {
-    pub(crate) producer_flow_control: Option<(FlowControls, FlowControlId)>,
+    pub(crate) flow_control: ConnectionFlowControl,
+    pub(crate) framing: FramingOptions,
 }
 
 impl TcpConnectionOptions {
@@ -21,7 +17,8 @@ impl TcpConnectionOptions {
     /// Should only be used for testing purposes
     pub fn insecure() -> Self {
         Self {
-            producer_flow_control: None,
+            flow_control: ConnectionFlowControl::insecure(),
+            framing: FramingOptions::default(),
         }
     }
 
@@ -31,50 +28,48 @@ impl TcpConnectionOptions {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
-            producer_flow_control: None,
+            flow_control: ConnectionFlowControl::insecure(),
+            framing: FramingOptions::default(),
         }
     }
 
     /// Mark this Tcp Receivers as a Producer for a given [`FlowControlId`]
     pub fn as_producer(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
         Self {
-            producer_flow_control: Some((flow_controls.clone(), flow_control_id.clone())),
+            flow_control: ConnectionFlowControl::producer(flow_controls, flow_control_id),
+            framing: FramingOptions::default(),
         }
     }
 
+    /// Use the given [`TcpFraming`] version for this connection.
+    /// The other side of the connection must use the same version
+    pub fn with_framing(mut self, framing: TcpFraming) -> Self {
+        self.framing.framing = framing;
+        self
+    }
+
+    /// Set the maximum size of a message sent or received over this connection.
+    /// Incoming messages exceeding this size close the connection, outgoing ones are rejected
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.framing.max_message_size = max_message_size;
+        self
+    }
+
     pub(crate) fn setup_flow_control(&self, addresses: &Addresses) {
-        if let Some((flow_controls, flow_control_id)) = &self.producer_flow_control {
-            flow_controls.add_producer(
-                addresses.receiver_address(),
-                flow_control_id,
-                None,
-                vec![addresses.sender_address().clone()],
-            );
-        }
+        self.flow_control
+            .setup(addresses.receiver_address(), addresses.sender_address())
     }
 
-    pub(crate) fn create_access_control(self) -> TcpConnectionAccessControl {
-        match self.producer_flow_control {
-            Some((flow_controls, flow_control_id)) => {
-                TcpConnectionAccessControl {
-                    sender_incoming_access_control: Arc::new(AllowAll),
-                    receiver_outgoing_access_control: Arc::new(
-                        FlowControlOutgoingAccessControl::new(flow_controls, flow_control_id, None),
-                    ),
-                }
-            }
-            None => TcpConnectionAccessControl {
-                sender_incoming_access_control: Arc::new(AllowAll),
-                receiver_outgoing_access_control: Arc::new(AllowAll),
-            },
-        }
+    pub(crate) fn create_access_control(self) -> ConnectionAccessControl {
+        self.flow_control.access_control()
     }
 }
 
 /// Trust Options for a TCP listener
 #[derive(Debug)]
 pub struct TcpListenerOptions {
-    pub(crate) spawner_flow_controls: Option<(FlowControls, FlowControlId)>,
+    pub(crate) flow_control: ListenerFlowControl,
+    pub(crate) framing: FramingOptions,
 }
 
 impl TcpListenerOptions {
@@ -83,7 +78,8 @@ impl TcpListenerOptions {
     /// Should only be used for testing purposes
     pub fn insecure() -> Self {
         Self {
-            spawner_flow_controls: None,
+            flow_control: ListenerFlowControl::insecure(),
+            framing: FramingOptions::default(),
         }
     }
 
@@ -93,7 +89,8 @@ impl TcpListenerOptions {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
-            spawner_flow_controls: None,
+            flow_control: ListenerFlowControl::insecure(),
+            framing: FramingOptions::default(),
         }
     }
 
@@ -102,49 +99,34 @@ impl TcpListenerOptions {
     /// with Spawner's [`FlowControlId`]
     pub fn as_spawner(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
         Self {
-            spawner_flow_controls: Some((flow_controls.clone(), flow_control_id.clone())),
+            flow_control: ListenerFlowControl::spawner(flow_controls, flow_control_id),
+            framing: FramingOptions::default(),
         }
     }
 
+    /// Use the given [`TcpFraming`] version for accepted connections.
+    /// The other side of the connection must use the same version
+    pub fn with_framing(mut self, framing: TcpFraming) -> Self {
+        self.framing.framing = framing;
+        self
+    }
+
+    /// Set the maximum size of a message sent or received over accepted connections.
+    /// Incoming messages exceeding this size close the connection, outgoing ones are rejected
+    pub fn with_max_message_size(mut self, max_message_size: usize) -> Self {
+        self.framing.max_message_size = max_message_size;
+        self
+    }
+
     pub(crate) fn setup_flow_control(&self, addresses: &Addresses) -> Option<FlowControlId> {
-        if let Some((flow_controls, listener_flow_control_id)) = &self.spawner_flow_controls {
-            let flow_control_id = flow_controls.generate_id();
-
-            flow_controls.add_producer(
-                addresses.receiver_address(),
-                &flow_control_id,
-                Some(listener_flow_control_id),
-                vec![addresses.sender_address().clone()],
-            );
-
-            Some(flow_control_id)
-        } else {
-            None
-        }
+        self.flow_control
+            .setup(addresses.receiver_address(), addresses.sender_address())
     }
 
     pub(crate) fn create_access_control(
         &self,
         flow_control_id: Option<FlowControlId>,
-    ) -> Result<TcpConnectionAccessControl> {
-        match (&self.spawner_flow_controls, flow_control_id) {
-            (Some((flow_controls, listener_flow_control_id)), Some(flow_control_id)) => {
-                Ok(TcpConnectionAccessControl {
-                    sender_incoming_access_control: Arc::new(AllowAll),
-                    receiver_outgoing_access_control: Arc::new(
-                        FlowControlOutgoingAccessControl::new(
-                            flow_controls.clone(),
-                            flow_control_id,
-                            Some(listener_flow_control_id.clone()),
-                        ),
-                    ),
-                })
-            }
-            (None, None) => Ok(TcpConnectionAccessControl {
-                sender_incoming_access_control: Arc::new(AllowAll),
-                receiver_outgoing_access_control: Arc::new(AllowAll),
-            }),
-            _ => Err(TransportError::FlowControlInconsistency.into()),
-        }
+    ) -> Result<ConnectionAccessControl> {
+        self.flow_control.access_control(flow_control_id)
     }
 }
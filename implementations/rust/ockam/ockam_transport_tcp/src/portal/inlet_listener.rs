@@ -1,29 +1,41 @@
 use crate::portal::addresses::{Addresses, PortalType};
-use crate::{TcpInletOptions, TcpPortalWorker, TcpRegistry};
+use crate::{split_tcp_stream, PortalPeer, TcpInletOptions, TcpPortalWorker, TcpRegistry};
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::{async_trait, compat::boxed::Box, DenyAll};
 use ockam_core::{Address, Processor, Result, Route};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
 use tokio::net::TcpListener;
-use tracing::{debug, error};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tracing::{debug, error, warn};
+
+/// Local socket accepting the connections of a portal inlet
+enum InletListener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener, PathBuf),
+}
 
 /// A TCP Portal Inlet listen processor
 ///
 /// TCP Portal Inlet listen processors are created by `TcpTransport`
 /// after a call is made to
-/// [`TcpTransport::create_inlet`](crate::TcpTransport::create_inlet).
+/// [`TcpTransport::create_inlet`](crate::TcpTransport::create_inlet)
+/// or [`TcpTransport::create_unix_inlet`](crate::TcpTransport::create_unix_inlet).
 pub(crate) struct TcpInletListenProcessor {
     registry: TcpRegistry,
-    inner: TcpListener,
+    inner: InletListener,
     outlet_listener_route: Route,
     options: TcpInletOptions,
 }
 
 impl TcpInletListenProcessor {
-    pub fn new(
+    fn new(
         registry: TcpRegistry,
-        inner: TcpListener,
+        inner: InletListener,
         outlet_listener_route: Route,
         options: TcpInletOptions,
     ) -> Self {
@@ -54,13 +66,74 @@ impl TcpInletListenProcessor {
             }
         };
         let socket_addr = inner.local_addr().map_err(TransportError::from)?;
-        let processor = Self::new(registry, inner, outlet_listener_route, options);
+        let processor = Self::new(
+            registry,
+            InletListener::Tcp(inner),
+            outlet_listener_route,
+            options,
+        );
 
         ctx.start_processor(processor_address.clone(), processor, DenyAll, DenyAll)
             .await?;
 
         Ok((socket_addr, processor_address))
     }
+
+    /// Start a new `TcpInletListenProcessor` accepting connections on a Unix domain socket
+    #[cfg(unix)]
+    pub(crate) async fn start_unix(
+        ctx: &Context,
+        registry: TcpRegistry,
+        outlet_listener_route: Route,
+        path: PathBuf,
+        options: TcpInletOptions,
+    ) -> Result<Address> {
+        let processor_address = Address::random_tagged("TcpInletListenProcessor");
+
+        debug!("Binding TcpPortalListenerWorker to {}", path.display());
+        remove_stale_socket(&path);
+        let inner = match UnixListener::bind(&path) {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!(path = %path.display(), %err, "could not bind to path");
+                return Err(TransportError::from(err).into());
+            }
+        };
+        let processor = Self::new(
+            registry,
+            InletListener::Unix(inner, path),
+            outlet_listener_route,
+            options,
+        );
+
+        ctx.start_processor(processor_address.clone(), processor, DenyAll, DenyAll)
+            .await?;
+
+        Ok(processor_address)
+    }
+}
+
+/// Remove the socket file left behind by a listener which was not shut down, for example
+/// when its node crashed. A socket still accepting connections, or a file which is not a
+/// socket, is kept and binding the path fails
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) {
+    use std::os::unix::fs::FileTypeExt;
+
+    let is_socket = std::fs::symlink_metadata(path)
+        .map(|metadata| metadata.file_type().is_socket())
+        .unwrap_or(false);
+    if !is_socket {
+        return;
+    }
+    if let Err(err) = std::os::unix::net::UnixStream::connect(path) {
+        if err.kind() == std::io::ErrorKind::ConnectionRefused {
+            debug!(path = %path.display(), "removing a stale socket file");
+            if let Err(err) = std::fs::remove_file(path) {
+                warn!(path = %path.display(), %err, "could not remove the stale socket file");
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -77,6 +150,14 @@ impl Processor for TcpInletListenProcessor {
         self.registry
             .remove_inlet_listener_processor(&ctx.address());
 
+        // Unlike TCP ports, socket files outlive their listener
+        #[cfg(unix)]
+        if let InletListener::Unix(_, path) = &self.inner {
+            if let Err(err) = std::fs::remove_file(path) {
+                warn!(path = %path.display(), %err, "could not remove the socket file");
+            }
+        }
+
         Ok(())
     }
 
@@ -87,7 +168,21 @@ impl Processor for TcpInletListenProcessor {
         self.options
             .setup_flow_control(&addresses, outlet_listener_route.next()?)?;
 
-        let (stream, peer) = self.inner.accept().await.map_err(TransportError::from)?;
+        let (stream, peer) = match &self.inner {
+            InletListener::Tcp(listener) => {
+                let (stream, peer) = listener.accept().await.map_err(TransportError::from)?;
+                (split_tcp_stream(stream), PortalPeer::Tcp(peer))
+            }
+            #[cfg(unix)]
+            InletListener::Unix(listener, path) => {
+                let (stream, _) = listener.accept().await.map_err(TransportError::from)?;
+                // Unix clients are usually unnamed, use the listening path to identify them
+                (
+                    crate::split_unix_stream(stream),
+                    PortalPeer::Unix(path.clone()),
+                )
+            }
+        };
         TcpPortalWorker::start_new_inlet(
             ctx,
             self.registry.clone(),
@@ -2,12 +2,16 @@ mod addresses;
 mod inlet_listener;
 pub mod options;
 mod outlet_listener;
+mod peer;
 mod portal_message;
 mod portal_receiver;
 mod portal_worker;
+mod window;
 
 pub(crate) use inlet_listener::*;
 pub(crate) use outlet_listener::*;
+pub(crate) use peer::*;
 pub use portal_message::*;
 pub(crate) use portal_receiver::*;
 pub(crate) use portal_worker::*;
+pub(crate) use window::*;
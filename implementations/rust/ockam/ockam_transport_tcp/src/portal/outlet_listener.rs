@@ -1,26 +1,26 @@
 use crate::portal::addresses::{Addresses, PortalType};
-use crate::{PortalMessage, TcpOutletOptions, TcpPortalWorker, TcpRegistry};
+use crate::{PortalMessage, PortalPeer, TcpOutletOptions, TcpPortalWorker, TcpRegistry};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{async_trait, Address, DenyAll, Mailboxes, Result, Routed, Worker};
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
-use std::net::SocketAddr;
 use tracing::debug;
 
 /// A TCP Portal Outlet listen worker
 ///
 /// TCP Portal Outlet listen workers are created by `TcpTransport`
 /// after a call is made to
-/// [`TcpTransport::create_outlet`](crate::TcpTransport::create_outlet).
+/// [`TcpTransport::create_outlet`](crate::TcpTransport::create_outlet)
+/// or [`TcpTransport::create_unix_outlet`](crate::TcpTransport::create_unix_outlet).
 pub(crate) struct TcpOutletListenWorker {
     registry: TcpRegistry,
-    peer: SocketAddr,
+    peer: PortalPeer,
     options: TcpOutletOptions,
 }
 
 impl TcpOutletListenWorker {
     /// Create a new `TcpOutletListenWorker`
-    fn new(registry: TcpRegistry, peer: SocketAddr, options: TcpOutletOptions) -> Self {
+    fn new(registry: TcpRegistry, peer: PortalPeer, options: TcpOutletOptions) -> Self {
         Self {
             registry,
             peer,
@@ -32,7 +32,7 @@ impl TcpOutletListenWorker {
         ctx: &Context,
         registry: TcpRegistry,
         address: Address,
-        peer: SocketAddr,
+        peer: PortalPeer,
         options: TcpOutletOptions,
     ) -> Result<()> {
         let access_control = options.incoming_access_control.clone();
@@ -108,7 +108,7 @@ impl Worker for TcpOutletListenWorker {
         TcpPortalWorker::start_new_outlet(
             ctx,
             self.registry.clone(),
-            self.peer,
+            self.peer.clone(),
             return_route.clone(),
             addresses.clone(),
             self.options.incoming_access_control.clone(),
@@ -0,0 +1,66 @@
+use core::fmt;
+use ockam_core::compat::{boxed::Box, net::SocketAddr};
+use ockam_core::Result;
+use ockam_transport_core::TransportError;
+#[cfg(unix)]
+use std::path::PathBuf;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
+
+/// Read half of the stream between a portal and its local peer
+pub(crate) type PortalReadHalf = Box<dyn AsyncRead + Send + Sync + Unpin>;
+/// Write half of the stream between a portal and its local peer
+pub(crate) type PortalWriteHalf = Box<dyn AsyncWrite + Send + Sync + Unpin>;
+
+/// Local side of a portal: the TCP socket or the Unix domain socket
+/// that the raw stream is read from and written to
+#[derive(Clone, Debug)]
+pub(crate) enum PortalPeer {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl PortalPeer {
+    /// Connect to the peer and split the resulting stream
+    pub(crate) async fn connect(&self) -> Result<(PortalReadHalf, PortalWriteHalf)> {
+        match self {
+            PortalPeer::Tcp(addr) => {
+                let stream = TcpStream::connect(addr)
+                    .await
+                    .map_err(TransportError::from)?;
+                Ok(split_tcp_stream(stream))
+            }
+            #[cfg(unix)]
+            PortalPeer::Unix(path) => {
+                let stream = UnixStream::connect(path)
+                    .await
+                    .map_err(TransportError::from)?;
+                Ok(split_unix_stream(stream))
+            }
+        }
+    }
+}
+
+impl fmt::Display for PortalPeer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortalPeer::Tcp(addr) => write!(f, "{}", addr),
+            #[cfg(unix)]
+            PortalPeer::Unix(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+pub(crate) fn split_tcp_stream(stream: TcpStream) -> (PortalReadHalf, PortalWriteHalf) {
+    let (rx, tx) = stream.into_split();
+    (Box::new(rx), Box::new(tx))
+}
+
+#[cfg(unix)]
+pub(crate) fn split_unix_stream(stream: UnixStream) -> (PortalReadHalf, PortalWriteHalf) {
+    let (rx, tx) = stream.into_split();
+    (Box::new(rx), Box::new(tx))
+}
@@ -13,14 +13,29 @@ pub enum PortalMessage {
     Disconnect,
     /// Message with binary payload
     Payload(Vec<u8>),
+    /// Allow the other side to send that many more `Payload` messages
+    Credit(u32),
+    /// The other side won't send any more payload, the write side of the
+    /// connection should be shut down
+    WriteClose,
+    /// The other side can't write received payload anymore, the read side
+    /// of the connection should be closed
+    ReadClose,
 }
 
 /// An internal message type for a Portal
-#[derive(Serialize, Deserialize, Message)]
+#[derive(Serialize, Deserialize, Message, Clone)]
 pub enum PortalInternalMessage {
     /// Connection was dropped
     Disconnect,
+    /// Read side of the connection reached end of stream
+    ReadClosed,
+    /// Both sides of the connection were closed, the worker can be stopped
+    Stop,
 }
 
 ///Maximum allowed size for a payload
 pub const MAX_PAYLOAD_SIZE: usize = 48 * 1024;
+
+/// Number of `Payload` messages a portal allows the other side to have in flight
+pub const PORTAL_CREDIT_WINDOW: u32 = 64;
@@ -1,10 +1,10 @@
 use crate::portal::portal_message::MAX_PAYLOAD_SIZE;
-use crate::{PortalInternalMessage, PortalMessage, TcpRegistry};
+use crate::{PortalInternalMessage, PortalMessage, PortalReadHalf, PortalWindow, TcpRegistry};
 use ockam_core::compat::vec::Vec;
 use ockam_core::{async_trait, Encodable, LocalMessage, Route, TransportMessage};
 use ockam_core::{route, Address, Processor, Result};
 use ockam_node::Context;
-use tokio::{io::AsyncReadExt, net::tcp::OwnedReadHalf};
+use tokio::io::AsyncReadExt;
 use tracing::{error, warn};
 
 /// A TCP Portal receiving message processor
@@ -15,18 +15,20 @@ use tracing::{error, warn};
 pub(crate) struct TcpPortalRecvProcessor {
     registry: TcpRegistry,
     buf: Vec<u8>,
-    read_half: OwnedReadHalf,
+    read_half: PortalReadHalf,
     sender_address: Address,
     onward_route: Route,
+    window: PortalWindow,
 }
 
 impl TcpPortalRecvProcessor {
     /// Create a new `TcpPortalRecvProcessor`
     pub fn new(
         registry: TcpRegistry,
-        read_half: OwnedReadHalf,
+        read_half: PortalReadHalf,
         sender_address: Address,
         onward_route: Route,
+        window: PortalWindow,
     ) -> Self {
         Self {
             registry,
@@ -34,8 +36,34 @@ impl TcpPortalRecvProcessor {
             read_half,
             sender_address,
             onward_route,
+            window,
         }
     }
+
+    /// Notify Sender that the connection was dropped
+    async fn notify_disconnection(&self, ctx: &Context) {
+        if let Err(err) = ctx
+            .send(
+                route![self.sender_address.clone()],
+                PortalInternalMessage::Disconnect,
+            )
+            .await
+        {
+            warn!(
+                "Error notifying Tcp Portal Sender about dropped connection {}",
+                err
+            );
+        }
+    }
+
+    async fn forward(&self, ctx: &Context, msg: PortalMessage) -> Result<()> {
+        let msg = TransportMessage::v1(
+            self.onward_route.clone(),
+            self.sender_address.clone(),
+            msg.encode()?,
+        );
+        ctx.forward(LocalMessage::new(msg, vec![])).await
+    }
 }
 
 #[async_trait]
@@ -56,49 +84,52 @@ impl Processor for TcpPortalRecvProcessor {
     }
 
     async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
+        // Don't read from the connection until the other side is ready to accept more payload
+        self.window.acquire().await;
+
         self.buf.clear();
 
         let _len = match self.read_half.read_buf(&mut self.buf).await {
             Ok(len) => len,
             Err(err) => {
-                error!("Tcp Portal connection read failed with error: {}", err);
+                error!("Portal connection read failed with error: {}", err);
+                self.notify_disconnection(ctx).await;
                 return Ok(false);
             }
         };
 
         if self.buf.is_empty() {
-            // Notify Sender that connection was closed
+            // Peers which never granted credits don't know about half-closed
+            // connections, close the whole connection for them
+            if !self.window.is_enabled() {
+                self.notify_disconnection(ctx).await;
+                return Ok(false);
+            }
+
+            // The peer won't write anymore, let the other side shut down its write side
+            // while the data flowing in the opposite direction keeps going
+            self.forward(ctx, PortalMessage::WriteClose).await?;
+
             if let Err(err) = ctx
                 .send(
                     route![self.sender_address.clone()],
-                    PortalInternalMessage::Disconnect,
+                    PortalInternalMessage::ReadClosed,
                 )
                 .await
             {
                 warn!(
-                    "Error notifying Tcp Portal Sender about dropped connection {}",
+                    "Error notifying Tcp Portal Sender about closed connection {}",
                     err
                 );
             }
 
-            let msg = TransportMessage::v1(
-                self.onward_route.clone(),
-                self.sender_address.clone(),
-                PortalMessage::Disconnect.encode()?,
-            );
-            ctx.forward(LocalMessage::new(msg, vec![])).await?;
-
             return Ok(false);
         }
 
         // Loop just in case buf was extended (should not happen though)
         for chunk in self.buf.chunks(MAX_PAYLOAD_SIZE) {
-            let msg = TransportMessage::v1(
-                self.onward_route.clone(),
-                self.sender_address.clone(),
-                PortalMessage::Payload(chunk.to_vec()).encode()?,
-            );
-            ctx.forward(LocalMessage::new(msg, vec![])).await?;
+            self.forward(ctx, PortalMessage::Payload(chunk.to_vec()))
+                .await?;
         }
 
         Ok(true)
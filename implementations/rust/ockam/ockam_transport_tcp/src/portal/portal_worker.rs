@@ -1,17 +1,18 @@
 use crate::portal::addresses::{Addresses, PortalType};
-use crate::{PortalInternalMessage, PortalMessage, TcpPortalRecvProcessor, TcpRegistry};
+use crate::{
+    PortalInternalMessage, PortalMessage, PortalPeer, PortalReadHalf, PortalWindow,
+    PortalWriteHalf, TcpPortalRecvProcessor, TcpRegistry, PORTAL_CREDIT_WINDOW,
+};
 use core::time::Duration;
-use ockam_core::compat::{boxed::Box, net::SocketAddr, sync::Arc};
+use ockam_core::compat::{boxed::Box, string::ToString, sync::Arc, vec::Vec};
 use ockam_core::{
-    async_trait, AllowAll, AllowOnwardAddresses, AllowSourceAddress, Decodable, DenyAll,
+    async_trait, AllowAll, AllowOnwardAddresses, AllowSourceAddresses, Decodable, DenyAll,
     IncomingAccessControl, Mailbox, Mailboxes,
 };
 use ockam_core::{Any, Result, Route, Routed, Worker};
-use ockam_node::{Context, ProcessorBuilder, WorkerBuilder};
+use ockam_node::{Context, DelayedEvent, ProcessorBuilder, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use tokio::io::AsyncWriteExt;
-use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
-use tokio::net::TcpStream;
 use tracing::{debug, info, trace, warn};
 
 /// Enumerate all `TcpPortalWorker` states
@@ -37,22 +38,43 @@ enum State {
 pub(crate) struct TcpPortalWorker {
     registry: TcpRegistry,
     state: State,
-    write_half: Option<OwnedWriteHalf>,
-    read_half: Option<OwnedReadHalf>,
-    peer: SocketAddr,
+    write_half: Option<PortalWriteHalf>,
+    read_half: Option<PortalReadHalf>,
+    peer: PortalPeer,
     addresses: Addresses,
     remote_route: Option<Route>,
     is_disconnecting: bool,
+    is_counted: bool,
     portal_type: PortalType,
+    window: PortalWindow,
+    unacknowledged: u32,
+    read_closed: bool,
+    write_closed: bool,
+    stop_event: DelayedEvent<PortalInternalMessage>,
 }
 
 impl TcpPortalWorker {
+    /// Count this connection in the portal connections gauge, or remove it
+    /// from the gauge if it was counted
+    fn update_connections_gauge(&mut self, ctx: &Context, connected: bool) {
+        if self.is_counted == connected {
+            return;
+        }
+        self.is_counted = connected;
+        let delta = if connected { 1.0 } else { -1.0 };
+        ctx.metrics().add_to_gauge(
+            crate::TCP_PORTAL_CONNECTIONS,
+            &[("type", self.portal_type.str().to_string())],
+            delta,
+        );
+    }
+
     /// Start a new `TcpPortalWorker` of type [`TypeName::Inlet`]
     pub(super) async fn start_new_inlet(
         ctx: &Context,
         registry: TcpRegistry,
-        stream: TcpStream,
-        peer: SocketAddr,
+        stream: (PortalReadHalf, PortalWriteHalf),
+        peer: PortalPeer,
         ping_route: Route,
         addresses: Addresses,
         access_control: Arc<dyn IncomingAccessControl>,
@@ -74,7 +96,7 @@ impl TcpPortalWorker {
     pub(super) async fn start_new_outlet(
         ctx: &Context,
         registry: TcpRegistry,
-        peer: SocketAddr,
+        peer: PortalPeer,
         pong_route: Route,
         addresses: Addresses,
         access_control: Arc<dyn IncomingAccessControl>,
@@ -97,9 +119,9 @@ impl TcpPortalWorker {
     async fn start(
         ctx: &Context,
         registry: TcpRegistry,
-        peer: SocketAddr,
+        peer: PortalPeer,
         state: State,
-        stream: Option<TcpStream>,
+        stream: Option<(PortalReadHalf, PortalWriteHalf)>,
         addresses: Addresses,
         portal_type: PortalType,
         access_control: Arc<dyn IncomingAccessControl>,
@@ -112,13 +134,15 @@ impl TcpPortalWorker {
         );
 
         let (rx, tx) = match stream {
-            Some(s) => {
-                let (rx, tx) = s.into_split();
-                (Some(rx), Some(tx))
-            }
+            Some((rx, tx)) => (Some(rx), Some(tx)),
             None => (None, None),
         };
 
+        let stop_event =
+            DelayedEvent::create(ctx, addresses.internal.clone(), PortalInternalMessage::Stop)
+                .await?;
+        let stop_event_source = stop_event.address();
+
         let worker = Self {
             registry,
             state,
@@ -128,12 +152,21 @@ impl TcpPortalWorker {
             addresses: addresses.clone(),
             remote_route: None,
             is_disconnecting: false,
+            is_counted: false,
             portal_type,
+            window: PortalWindow::new(),
+            unacknowledged: 0,
+            read_closed: false,
+            write_closed: false,
+            stop_event,
         };
 
         let internal_mailbox = Mailbox::new(
             addresses.internal,
-            Arc::new(AllowSourceAddress(addresses.receiver)),
+            Arc::new(AllowSourceAddresses(vec![
+                addresses.receiver,
+                stop_event_source,
+            ])),
             Arc::new(DenyAll),
         );
 
@@ -144,10 +177,14 @@ impl TcpPortalWorker {
         );
 
         // start worker
+        // A full window of payload, along with the control messages and the payload sent
+        // before the first credit was granted, must fit in the mailbox. Otherwise a stalled
+        // connection would also block the transport connection carrying the portal
         WorkerBuilder::with_mailboxes(
             Mailboxes::new(internal_mailbox, vec![remote_mailbox]),
             worker,
         )
+        .with_mailbox_capacity(2 * PORTAL_CREDIT_WINDOW as usize)
         .start(ctx)
         .await?;
 
@@ -156,7 +193,6 @@ impl TcpPortalWorker {
 }
 
 enum DisconnectionReason {
-    FailedTx,
     FailedRx,
     Remote,
 }
@@ -175,6 +211,7 @@ impl TcpPortalWorker {
                 rx,
                 self.addresses.internal.clone(),
                 onward_route,
+                self.window.clone(),
             );
 
             let mailbox = Mailbox::new(
@@ -254,9 +291,6 @@ impl TcpPortalWorker {
         self.is_disconnecting = true;
 
         match reason {
-            DisconnectionReason::FailedTx => {
-                self.notify_remote_about_disconnection(ctx).await?;
-            }
             DisconnectionReason::FailedRx => {
                 self.notify_remote_about_disconnection(ctx).await?;
                 self.stop_receiver(ctx).await?;
@@ -277,6 +311,135 @@ impl TcpPortalWorker {
         Ok(())
     }
 
+    /// Allow the other side to send a full window of payload
+    async fn send_initial_credit(&self, ctx: &Context, route: Route) -> Result<()> {
+        ctx.send_from_address(
+            route,
+            PortalMessage::Credit(PORTAL_CREDIT_WINDOW),
+            self.addresses.remote.clone(),
+        )
+        .await
+    }
+
+    /// Give credits back to the other side once half of the window was consumed
+    async fn acknowledge_payload(&mut self, ctx: &Context) -> Result<()> {
+        self.unacknowledged += 1;
+        if self.unacknowledged < PORTAL_CREDIT_WINDOW / 2 {
+            return Ok(());
+        }
+
+        if let Some(remote_route) = &self.remote_route {
+            ctx.send_from_address(
+                remote_route.clone(),
+                PortalMessage::Credit(self.unacknowledged),
+                self.addresses.remote.clone(),
+            )
+            .await?;
+        }
+        self.unacknowledged = 0;
+
+        Ok(())
+    }
+
+    /// Write a payload received from the other side to the connection
+    async fn handle_payload(&mut self, ctx: &Context, payload: Vec<u8>) -> Result<()> {
+        if !self.write_closed {
+            let tx = match &mut self.write_half {
+                Some(tx) => tx,
+                None => return Err(TransportError::PortalInvalidState.into()),
+            };
+
+            if let Err(err) = tx.write_all(&payload).await {
+                warn!(
+                    "Failed to send message to peer {} with error: {}",
+                    self.peer, err
+                );
+                self.close_write_side(ctx).await?;
+                return Ok(());
+            }
+        }
+
+        self.acknowledge_payload(ctx).await
+    }
+
+    /// The connection can't be written to anymore. Ask the other side to stop
+    /// reading, while data flowing in the opposite direction keeps going
+    async fn close_write_side(&mut self, ctx: &Context) -> Result<()> {
+        // Peers which never granted credits don't know about half-closed
+        // connections, close the whole connection for them
+        if !self.window.is_enabled() {
+            return self
+                .start_disconnection(ctx, DisconnectionReason::FailedRx)
+                .await;
+        }
+
+        self.write_closed = true;
+        self.write_half = None;
+
+        if let Some(remote_route) = &self.remote_route {
+            ctx.send_from_address(
+                remote_route.clone(),
+                PortalMessage::ReadClose,
+                self.addresses.remote.clone(),
+            )
+            .await?;
+        }
+
+        self.finish_if_closed().await
+    }
+
+    /// The other side won't send more payload, shut down the write side of the connection
+    async fn handle_write_close(&mut self) -> Result<()> {
+        if let Some(mut tx) = self.write_half.take() {
+            if let Err(err) = tx.shutdown().await {
+                debug!(
+                    "Failed to shut down write side of connection to {}: {}",
+                    self.peer, err
+                );
+            }
+        }
+        self.write_closed = true;
+
+        self.finish_if_closed().await
+    }
+
+    /// The other side can't deliver our payload anymore, stop reading from the connection
+    async fn handle_read_close(&mut self, ctx: &Context) -> Result<()> {
+        if !self.read_closed {
+            self.read_closed = true;
+            let _ = ctx.stop_processor(self.addresses.receiver.clone()).await;
+        }
+
+        self.finish_if_closed().await
+    }
+
+    /// Schedule the stop of the worker once both directions of the connection were closed
+    async fn finish_if_closed(&mut self) -> Result<()> {
+        if !(self.read_closed && self.write_closed) {
+            return Ok(());
+        }
+
+        self.is_disconnecting = true;
+
+        // Credits for our last payload may still be on their way from the other side,
+        // let them arrive before the worker is stopped
+        // TODO: Remove when we have better way to handle race condition
+        self.stop_event.schedule(Duration::from_secs(1)).await
+    }
+
+    /// Stop the worker after both directions of the connection were closed
+    async fn stop_after_close(&mut self, ctx: &Context) -> Result<()> {
+        ctx.stop_worker(self.addresses.internal.clone()).await?;
+
+        info!(
+            "{:?} at: {} stopped after both sides closed the connection",
+            self.portal_type.str(),
+            self.addresses.internal
+        );
+
+        Ok(())
+    }
+
     async fn handle_send_ping(&self, ctx: &Context, ping_route: Route) -> Result<State> {
         // Force creation of Outlet on the other side
         ctx.send_from_address(
@@ -299,12 +462,10 @@ impl TcpPortalWorker {
             self.addresses.remote.clone(),
         )
         .await?;
+        self.send_initial_credit(ctx, pong_route.clone()).await?;
 
         if self.write_half.is_none() {
-            let stream = TcpStream::connect(self.peer)
-                .await
-                .map_err(TransportError::from)?;
-            let (rx, tx) = stream.into_split();
+            let (rx, tx) = self.peer.connect().await?;
             self.write_half = Some(tx);
             self.read_half = Some(rx);
 
@@ -344,12 +505,14 @@ impl Worker for TcpPortalWorker {
         }
 
         self.registry.add_portal_worker(&self.addresses.remote);
+        self.update_connections_gauge(ctx, true);
 
         Ok(())
     }
 
-    async fn shutdown(&mut self, _ctx: &mut Self::Context) -> Result<()> {
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
         self.registry.remove_portal_worker(&self.addresses.remote);
+        self.update_connections_gauge(ctx, false);
 
         Ok(())
     }
@@ -358,6 +521,14 @@ impl Worker for TcpPortalWorker {
     // across the TcpStream to our friend
     async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
         if self.is_disconnecting {
+            // Only the scheduled stop is handled while disconnecting
+            if msg.msg_addr() == self.addresses.internal {
+                if let Ok(PortalInternalMessage::Stop) =
+                    PortalInternalMessage::decode(msg.payload())
+                {
+                    self.stop_after_close(ctx).await?;
+                }
+            }
             return Ok(());
         }
 
@@ -388,6 +559,7 @@ impl Worker for TcpPortalWorker {
                 }
 
                 self.start_receiver(ctx, return_route.clone()).await?;
+                self.send_initial_credit(ctx, return_route.clone()).await?;
 
                 debug!("Inlet at: {} received pong", self.addresses.internal);
 
@@ -414,6 +586,18 @@ impl Worker for TcpPortalWorker {
                             self.start_disconnection(ctx, DisconnectionReason::FailedRx)
                                 .await?;
                         }
+                        PortalInternalMessage::ReadClosed => {
+                            debug!(
+                                "Tcp stream was half-closed for {:?} at: {}",
+                                self.portal_type.str(),
+                                self.addresses.internal
+                            );
+                            self.read_closed = true;
+                            self.finish_if_closed().await?;
+                        }
+                        PortalInternalMessage::Stop => {
+                            return Err(TransportError::PortalInvalidState.into());
+                        }
                     }
                 } else {
                     trace!(
@@ -427,24 +611,16 @@ impl Worker for TcpPortalWorker {
 
                     match msg {
                         PortalMessage::Payload(payload) => {
-                            if let Some(tx) = &mut self.write_half {
-                                match tx.write_all(&payload).await {
-                                    Ok(()) => {}
-                                    Err(err) => {
-                                        warn!(
-                                            "Failed to send message to peer {} with error: {}",
-                                            self.peer, err
-                                        );
-                                        self.start_disconnection(
-                                            ctx,
-                                            DisconnectionReason::FailedTx,
-                                        )
-                                        .await?;
-                                    }
-                                }
-                            } else {
-                                return Err(TransportError::PortalInvalidState.into());
-                            }
+                            self.handle_payload(ctx, payload).await?;
+                        }
+                        PortalMessage::Credit(credits) => {
+                            self.window.grant(credits);
+                        }
+                        PortalMessage::WriteClose => {
+                            self.handle_write_close().await?;
+                        }
+                        PortalMessage::ReadClose => {
+                            self.handle_read_close(ctx).await?;
                         }
                         PortalMessage::Disconnect => {
                             self.start_disconnection(ctx, DisconnectionReason::Remote)
@@ -0,0 +1,54 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+use ockam_core::compat::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Credits granted by the other side of a portal
+///
+/// The window is shared between a `TcpPortalWorker`, which receives
+/// [`PortalMessage::Credit`](crate::PortalMessage::Credit), and its
+/// `TcpPortalRecvProcessor`, which spends one credit per sent payload.
+/// Until the first credit is granted the window is not enforced, so that
+/// intermediaries re-framing the stream can opt out by not forwarding credits.
+///
+/// Credits were introduced together with half-closed connections, so a peer
+/// which granted credits is also known to understand
+/// [`PortalMessage::WriteClose`](crate::PortalMessage::WriteClose) and
+/// [`PortalMessage::ReadClose`](crate::PortalMessage::ReadClose). Other peers
+/// are sent a [`PortalMessage::Disconnect`](crate::PortalMessage::Disconnect)
+/// instead, which closes both directions of the connection.
+#[derive(Clone)]
+pub(crate) struct PortalWindow {
+    enabled: Arc<AtomicBool>,
+    credits: Arc<Semaphore>,
+}
+
+impl PortalWindow {
+    pub(crate) fn new() -> Self {
+        Self {
+            enabled: Arc::new(AtomicBool::new(false)),
+            credits: Arc::new(Semaphore::new(0)),
+        }
+    }
+
+    /// Add credits granted by the other side
+    pub(crate) fn grant(&self, credits: u32) {
+        self.enabled.store(true, Ordering::Release);
+        self.credits.add_permits(credits as usize);
+    }
+
+    /// Return true if the other side granted credits, and then supports half-closed connections
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Acquire)
+    }
+
+    /// Wait until a payload can be sent and spend one credit
+    pub(crate) async fn acquire(&self) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if let Ok(permit) = self.credits.acquire().await {
+            permit.forget();
+        }
+    }
+}
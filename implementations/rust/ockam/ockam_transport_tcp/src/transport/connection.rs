@@ -29,6 +29,7 @@ impl TcpTransport {
         let addresses = Addresses::generate(ConnectionRole::Initiator);
 
         options.setup_flow_control(&addresses);
+        let framing = options.framing;
         let access_control = options.create_access_control();
 
         TcpSendWorker::start(
@@ -37,6 +38,7 @@ impl TcpTransport {
             write_half,
             &addresses,
             socket,
+            framing,
             access_control.sender_incoming_access_control,
         )
         .await?;
@@ -47,6 +49,7 @@ impl TcpTransport {
             read_half,
             &addresses,
             socket,
+            framing,
             access_control.receiver_outgoing_access_control,
         )
         .await?;
@@ -2,11 +2,10 @@ use core::str::FromStr;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
-use ockam_core::errcode::{Kind, Origin};
 use ockam_core::flow_control::FlowControls;
-use ockam_core::{async_trait, Address, AsyncTryClone, Error, Result, TransportType};
+use ockam_core::{async_trait, Address, AsyncTryClone, Result, TransportType};
 use ockam_node::Context;
-use ockam_transport_core::Transport;
+use ockam_transport_core::{unsupported_address_error, Transport};
 
 use crate::{TcpConnectionOptions, TcpRegistry, TcpTransport, TCP};
 
@@ -69,14 +68,7 @@ impl Transport for TcpTransport {
             };
             Ok(self.connect(address.address().to_string(), options).await?)
         } else {
-            Err(Error::new(
-                Origin::Transport,
-                Kind::NotFound,
-                format!(
-                    "this address can not be resolved by a TCP transport {}",
-                    address
-                ),
-            ))
+            Err(unsupported_address_error("TCP", &address))
         }
     }
 }
@@ -97,7 +89,7 @@ mod tests {
 
         let resolved = tcp
             .resolve_address(
-                &FlowControls::default(),
+                ctx.flow_controls(),
                 Address::new(TCP, local_address.clone()),
             )
             .await?;
@@ -112,7 +104,7 @@ mod tests {
 
         // trying to resolve the address a second time should still work
         let _route = tcp
-            .resolve_address(&FlowControls::default(), Address::new(TCP, local_address))
+            .resolve_address(ctx.flow_controls(), Address::new(TCP, local_address))
             .await?;
 
         ctx.stop().await
@@ -123,7 +115,7 @@ mod tests {
         let tcp = TcpTransport::create(ctx).await?;
         let result = tcp
             .resolve_address(
-                &FlowControls::default(),
+                ctx.flow_controls(),
                 Address::new(TCP, "www.google.com:80"),
             )
             .await
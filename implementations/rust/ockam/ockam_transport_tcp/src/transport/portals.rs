@@ -1,8 +1,10 @@
 use crate::portal::TcpInletListenProcessor;
 use crate::transport::common::{parse_socket_addr, resolve_peer};
-use crate::{TcpInletOptions, TcpOutletListenWorker, TcpOutletOptions, TcpTransport};
+use crate::{PortalPeer, TcpInletOptions, TcpOutletListenWorker, TcpOutletOptions, TcpTransport};
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::{Address, Result, Route};
+#[cfg(unix)]
+use std::path::Path;
 
 impl TcpTransport {
     /// Create Tcp Inlet that listens on bind_addr, transforms Tcp stream into Ockam Routable
@@ -88,7 +90,7 @@ impl TcpTransport {
             &self.ctx,
             self.registry.clone(),
             address.into(),
-            peer_addr,
+            PortalPeer::Tcp(peer_addr),
             options,
         )
         .await?;
@@ -113,4 +115,71 @@ impl TcpTransport {
         self.ctx.stop_worker(addr).await?;
         Ok(())
     }
+
+    /// Create a Portal Inlet that listens on a Unix domain socket at `path`.
+    /// It behaves like an Inlet created with [`TcpTransport::create_inlet`] and can be
+    /// paired with any Outlet, the socket file is removed when the Inlet is stopped.
+    ///
+    /// ```rust
+    /// use ockam_transport_tcp::{TcpInletOptions, TcpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::{Result, route};
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let route_path = route!["outlet"];
+    ///
+    /// let tcp = TcpTransport::create(&ctx).await?;
+    /// let inlet = tcp.create_unix_inlet("/tmp/inlet.sock", route_path, TcpInletOptions::new()).await?;
+    /// # tcp.stop_inlet(inlet).await?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(unix)]
+    pub async fn create_unix_inlet(
+        &self,
+        path: impl AsRef<Path>,
+        outlet_route: impl Into<Route>,
+        options: TcpInletOptions,
+    ) -> Result<Address> {
+        TcpInletListenProcessor::start_unix(
+            &self.ctx,
+            self.registry.clone(),
+            outlet_route.into(),
+            path.as_ref().to_path_buf(),
+            options,
+        )
+        .await
+    }
+
+    /// Create a Portal Outlet Listener at address, that connects to the Unix domain socket
+    /// at `path` for every new Inlet connection. It behaves like an Outlet created with
+    /// [`TcpTransport::create_outlet`] and can be stopped with [`TcpTransport::stop_outlet`].
+    ///
+    /// ```rust
+    /// use ockam_transport_tcp::{TcpOutletOptions, TcpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    ///
+    /// let tcp = TcpTransport::create(&ctx).await?;
+    /// tcp.create_unix_outlet("outlet", "/var/run/docker.sock", TcpOutletOptions::new()).await?;
+    /// # tcp.stop_outlet("outlet").await?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(unix)]
+    pub async fn create_unix_outlet(
+        &self,
+        address: impl Into<Address>,
+        path: impl AsRef<Path>,
+        options: TcpOutletOptions,
+    ) -> Result<()> {
+        TcpOutletListenWorker::start(
+            &self.ctx,
+            self.registry.clone(),
+            address.into(),
+            PortalPeer::Unix(path.as_ref().to_path_buf()),
+            options,
+        )
+        .await?;
+
+        Ok(())
+    }
 }
@@ -32,9 +32,7 @@ impl TcpListenProcessor {
         let saddr = inner.local_addr().map_err(TransportError::from)?;
 
         let address = Address::random_tagged("TcpListenProcessor");
-        if let Some((flow_controls, flow_control_id)) = &options.spawner_flow_controls {
-            flow_controls.add_spawner(&address, flow_control_id);
-        }
+        options.flow_control.add_spawner(&address);
 
         let processor = Self {
             registry,
@@ -90,6 +88,7 @@ impl Processor for TcpListenProcessor {
             write_half,
             &addresses,
             peer,
+            self.options.framing,
             access_control.sender_incoming_access_control,
         )
         .await?;
@@ -101,6 +100,7 @@ impl Processor for TcpListenProcessor {
             read_half,
             &addresses,
             peer,
+            self.options.framing,
             access_control.receiver_outgoing_access_control,
         )
         .await?;
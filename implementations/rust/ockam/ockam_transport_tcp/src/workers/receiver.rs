@@ -1,5 +1,5 @@
 use crate::workers::Addresses;
-use crate::{TcpRegistry, TcpSendWorkerMsg};
+use crate::{FramingOptions, TcpRegistry, TcpSendWorkerMsg};
 use ockam_core::compat::net::SocketAddr;
 use ockam_core::compat::sync::Arc;
 use ockam_core::{
@@ -24,6 +24,7 @@ pub(crate) struct TcpRecvProcessor {
     read_half: OwnedReadHalf,
     peer: SocketAddr,
     addresses: Addresses,
+    framing: FramingOptions,
 }
 
 impl TcpRecvProcessor {
@@ -33,12 +34,14 @@ impl TcpRecvProcessor {
         read_half: OwnedReadHalf,
         peer: SocketAddr,
         addresses: Addresses,
+        framing: FramingOptions,
     ) -> Self {
         Self {
             registry,
             read_half,
             peer,
             addresses,
+            framing,
         }
     }
 
@@ -48,9 +51,10 @@ impl TcpRecvProcessor {
         read_half: OwnedReadHalf,
         addresses: &Addresses,
         peer: SocketAddr,
+        framing: FramingOptions,
         receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
     ) -> Result<()> {
-        let receiver = TcpRecvProcessor::new(registry, read_half, peer, addresses.clone());
+        let receiver = TcpRecvProcessor::new(registry, read_half, peer, addresses.clone(), framing);
 
         let mailbox = Mailbox::new(
             addresses.receiver_address().clone(),
@@ -70,6 +74,16 @@ impl TcpRecvProcessor {
 
         Ok(())
     }
+
+    /// Notify the sender that the connection is closed
+    async fn notify_connection_closed(&self, ctx: &Context) -> Result<()> {
+        ctx.send_from_address(
+            self.addresses.sender_internal_address().clone(),
+            TcpSendWorkerMsg::ConnectionClosed,
+            self.addresses.receiver_internal_address().clone(),
+        )
+        .await
+    }
 }
 
 #[async_trait]
@@ -104,7 +118,7 @@ impl Processor for TcpRecvProcessor {
     async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
         // Run in a loop until TcpWorkerPair::stop() is called
         // First read a message length header...
-        let len = match self.read_half.read_u16().await {
+        let len = match self.framing.read_length(&mut self.read_half).await {
             Ok(len) => len,
             Err(_e) => {
                 info!(
@@ -112,13 +126,7 @@ impl Processor for TcpRecvProcessor {
                     self.peer
                 );
 
-                // Notify sender tx is closed
-                ctx.send_from_address(
-                    self.addresses.sender_internal_address().clone(),
-                    TcpSendWorkerMsg::ConnectionClosed,
-                    self.addresses.receiver_internal_address().clone(),
-                )
-                .await?;
+                self.notify_connection_closed(ctx).await?;
 
                 return Ok(false);
             }
@@ -126,8 +134,22 @@ impl Processor for TcpRecvProcessor {
 
         trace!("Received message header for {} bytes", len);
 
+        // Refuse to allocate a buffer for a message which is too large
+        if len > self.framing.max_message_size() {
+            error!(
+                "Message of length {} from peer '{}' exceeds the maximum message size of {} bytes; dropping stream",
+                len,
+                self.peer,
+                self.framing.max_message_size()
+            );
+
+            self.notify_connection_closed(ctx).await?;
+
+            return Ok(false);
+        }
+
         // Allocate a buffer of that size
-        let mut buf = vec![0; len as usize];
+        let mut buf = vec![0; len];
 
         // Then read into the buffer
         match self.read_half.read_exact(&mut buf).await {
@@ -138,6 +160,12 @@ impl Processor for TcpRecvProcessor {
             }
         }
 
+        ctx.metrics().increment_counter(
+            crate::TCP_BYTES_RECEIVED,
+            &[],
+            (self.framing.header_size() + len) as u64,
+        );
+
         // Deserialize the message now
         let mut msg = TransportMessage::decode(&buf).map_err(|_| TransportError::RecvBadMessage)?;
 
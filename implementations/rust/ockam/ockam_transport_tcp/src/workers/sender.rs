@@ -1,5 +1,5 @@
 use crate::workers::Addresses;
-use crate::TcpRegistry;
+use crate::{FramingOptions, TcpRegistry};
 use cfg_if::cfg_if;
 use core::time::Duration;
 use ockam_core::{
@@ -7,10 +7,7 @@ use ockam_core::{
     compat::{net::SocketAddr, sync::Arc},
     AllowSourceAddress, DenyAll, IncomingAccessControl,
 };
-use ockam_core::{
-    Any, Decodable, Encodable, Mailbox, Mailboxes, Message, Result, Routed, TransportMessage,
-    Worker,
-};
+use ockam_core::{Any, Decodable, Mailbox, Mailboxes, Message, Result, Routed, Worker};
 use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use serde::{Deserialize, Serialize};
@@ -52,6 +49,7 @@ pub(crate) struct TcpSendWorker {
     write_half: OwnedWriteHalf,
     peer: SocketAddr,
     addresses: Addresses,
+    framing: FramingOptions,
     rx_should_be_stopped: bool,
 }
 
@@ -62,12 +60,14 @@ impl TcpSendWorker {
         write_half: OwnedWriteHalf,
         peer: SocketAddr,
         addresses: Addresses,
+        framing: FramingOptions,
     ) -> Self {
         Self {
             registry,
             write_half,
             peer,
             addresses,
+            framing,
             rx_should_be_stopped: true,
         }
     }
@@ -82,10 +82,11 @@ impl TcpSendWorker {
         write_half: OwnedWriteHalf,
         addresses: &Addresses,
         peer: SocketAddr,
+        framing: FramingOptions,
         sender_incoming_access_control: Arc<dyn IncomingAccessControl>,
     ) -> Result<()> {
         trace!("Creating new TCP worker pair");
-        let sender_worker = Self::new(registry, write_half, peer, addresses.clone());
+        let sender_worker = Self::new(registry, write_half, peer, addresses.clone(), framing);
 
         let main_mailbox = Mailbox::new(
             addresses.sender_address().clone(),
@@ -203,7 +204,16 @@ impl Worker for TcpSendWorker {
             // knows what to do with the incoming message
             msg.onward_route.step()?;
             // Create a message buffer with prepended length
-            let msg = prepare_message(msg)?;
+            let msg = match self.framing.prepare_message(msg) {
+                Ok(msg) => msg,
+                Err(e) => {
+                    warn!(
+                        "Dropping message to peer {} which can't be framed: {}",
+                        self.peer, e
+                    );
+                    return Err(e);
+                }
+            };
 
             if self.write_half.write_all(msg.as_slice()).await.is_err() {
                 warn!("Failed to send message to peer {}", self.peer);
@@ -211,32 +221,11 @@ impl Worker for TcpSendWorker {
 
                 return Ok(());
             }
+
+            ctx.metrics()
+                .increment_counter(crate::TCP_BYTES_SENT, &[], msg.len() as u64);
         }
 
         Ok(())
     }
 }
-
-/// Helper that creates a length-prefixed buffer containing the given
-/// `TransportMessage`'s payload
-///
-/// The length-prefix is encoded as a big-endian 16-bit unsigned
-/// integer.
-fn prepare_message(msg: TransportMessage) -> Result<Vec<u8>> {
-    let mut msg_buf = msg.encode().map_err(|_| TransportError::SendBadMessage)?;
-
-    // Create a buffer that includes the message length in big endian
-    let mut len = (msg_buf.len() as u16).to_be_bytes().to_vec();
-
-    // Fun fact: reversing a vector in place, appending the length,
-    // and then reversing it again is faster for large message sizes
-    // than adding the large chunk of data.
-    //
-    // https://play.rust-lang.org/?version=stable&mode=release&edition=2018&gist=8669a640004ac85c7be38b19e3e73dcb
-    msg_buf.reverse();
-    len.reverse();
-    msg_buf.append(&mut len);
-    msg_buf.reverse();
-
-    Ok(msg_buf)
-}
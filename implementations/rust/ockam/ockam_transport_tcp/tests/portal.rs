@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -119,6 +121,127 @@ async fn portal__reverse_flow__should_succeed(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__half_closed_connection__should_keep_other_direction(
+    ctx: &mut Context,
+) -> Result<()> {
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let (inlet_addr, listener) = setup(ctx).await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        // The client shut down its write side, so we receive an end of stream
+        let mut request = vec![];
+        stream.read_to_end(&mut request).await.unwrap();
+        assert_eq!(request, payload1);
+
+        write_binary(&mut stream, payload2).await;
+    });
+
+    // Wait till listener is up
+    tokio::time::sleep(Duration::from_millis(250)).await;
+
+    let mut stream = TcpStream::connect(inlet_addr).await.unwrap();
+    write_binary(&mut stream, payload1).await;
+    stream.shutdown().await.unwrap();
+
+    let mut response = vec![];
+    stream.read_to_end(&mut response).await.unwrap();
+    assert_eq!(response, payload2);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 60000)]
+async fn portal__slow_reader__should_apply_backpressure(ctx: &mut Context) -> Result<()> {
+    const TOTAL: usize = 64 * 1024 * 1024;
+
+    let tcp = TcpTransport::create(ctx).await?;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let bind_address = listener.local_addr().unwrap().to_string();
+    tcp.create_outlet("outlet", bind_address, TcpOutletOptions::new())
+        .await?;
+
+    // Carry the portal over a TCP connection between two transports, which is
+    // shared by all the connections of the portal
+    let (socket_address, _) = tcp.listen("127.0.0.1:0", TcpListenerOptions::new()).await?;
+    let connection = tcp
+        .connect(socket_address.to_string(), TcpConnectionOptions::new())
+        .await?;
+    let (inlet_addr, _) = tcp
+        .create_inlet(
+            "127.0.0.1:0",
+            route![connection, "outlet"],
+            TcpInletOptions::new(),
+        )
+        .await?;
+    let inlet_addr = inlet_addr.to_string();
+
+    let written = Arc::new(AtomicUsize::new(0));
+    let mut stream = TcpStream::connect(inlet_addr.clone()).await.unwrap();
+    let writer = {
+        let written = written.clone();
+        tokio::spawn(async move {
+            let chunk = vec![7u8; 64 * 1024];
+            for _ in 0..TOTAL / chunk.len() {
+                stream.write_all(&chunk).await.unwrap();
+                written.fetch_add(chunk.len(), Ordering::SeqCst);
+            }
+            stream.shutdown().await.unwrap();
+        })
+    };
+
+    let (mut server_stream, _) = listener.accept().await.unwrap();
+
+    // Nobody reads on the outlet side, so the portal must stop accepting data
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let written_before = written.load(Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert_eq!(written.load(Ordering::SeqCst), written_before);
+    assert!(written_before < TOTAL, "Writer should be blocked");
+
+    // The stalled connection doesn't prevent other connections from making progress
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+    let mut other_stream = TcpStream::connect(inlet_addr).await.unwrap();
+    let (mut other_server_stream, _) = listener.accept().await.unwrap();
+    write_binary(&mut other_stream, payload1).await;
+    read_assert_binary(&mut other_server_stream, payload1).await;
+    write_binary(&mut other_server_stream, payload2).await;
+    read_assert_binary(&mut other_stream, payload2).await;
+
+    let mut received = 0;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let len = server_stream.read(&mut buf).await.unwrap();
+        if len == 0 {
+            break;
+        }
+        received += len;
+    }
+    assert_eq!(received, TOTAL);
+    assert!(writer.await.is_ok());
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
 #[allow(non_snake_case)]
 #[ockam_macros::test(timeout = 15000)]
 async fn portal__tcp_connection_with_flow_controls__should_succeed(
@@ -282,3 +405,92 @@ async fn portal__tcp_connection_with_invalid_message_flow__should_not_succeed(
 
     Ok(())
 }
+
+#[cfg(unix)]
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__unix_inlet_and_outlet__should_succeed(ctx: &mut Context) -> Result<()> {
+    use ockam_core::compat::rand::random_string;
+    use tokio::net::{UnixListener, UnixStream};
+
+    let payload1 = generate_binary();
+    let payload2 = generate_binary();
+
+    let tmp = std::env::temp_dir();
+    let outlet_path = tmp.join(format!("ockam-outlet-{}.sock", random_string()));
+    let inlet_path = tmp.join(format!("ockam-inlet-{}.sock", random_string()));
+
+    let tcp = TcpTransport::create(ctx).await?;
+
+    let listener = UnixListener::bind(&outlet_path).unwrap();
+    tcp.create_unix_outlet("outlet", &outlet_path, TcpOutletOptions::new())
+        .await?;
+    let inlet = tcp
+        .create_unix_inlet(&inlet_path, route!["outlet"], TcpInletOptions::new())
+        .await?;
+
+    let handle = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+
+        let mut payload = [0u8; LENGTH];
+        stream.read_exact(&mut payload).await.unwrap();
+        assert_eq!(payload, payload1);
+        stream.write_all(&payload2).await.unwrap();
+    });
+
+    let mut stream = UnixStream::connect(&inlet_path).await.unwrap();
+    stream.write_all(&payload1).await.unwrap();
+    let mut payload = [0u8; LENGTH];
+    stream.read_exact(&mut payload).await.unwrap();
+    assert_eq!(payload, payload2);
+
+    let res = handle.await;
+    assert!(res.is_ok());
+
+    // The socket file is removed together with the inlet
+    tcp.stop_inlet(inlet).await?;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(!inlet_path.exists());
+
+    let _ = std::fs::remove_file(&outlet_path);
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 5000)]
+async fn portal__unix_inlet_on_stale_socket__should_succeed(ctx: &mut Context) -> Result<()> {
+    use ockam_core::compat::rand::random_string;
+    use tokio::net::UnixStream;
+
+    let inlet_path = std::env::temp_dir().join(format!("ockam-inlet-{}.sock", random_string()));
+
+    // A listener which is not shut down leaves its socket file behind
+    drop(std::os::unix::net::UnixListener::bind(&inlet_path).unwrap());
+    assert!(inlet_path.exists());
+
+    let tcp = TcpTransport::create(ctx).await?;
+    let inlet = tcp
+        .create_unix_inlet(&inlet_path, route!["outlet"], TcpInletOptions::new())
+        .await?;
+    assert!(UnixStream::connect(&inlet_path).await.is_ok());
+
+    // A socket still accepting connections is not replaced
+    assert!(tcp
+        .create_unix_inlet(&inlet_path, route!["outlet"], TcpInletOptions::new())
+        .await
+        .is_err());
+
+    tcp.stop_inlet(inlet).await?;
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
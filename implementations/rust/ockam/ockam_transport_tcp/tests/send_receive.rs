@@ -2,7 +2,7 @@ use ockam_core::compat::rand::{self, Rng};
 use ockam_core::compat::sync::Arc;
 use ockam_core::{route, AllowAll, Mailboxes, Result, Routed, Worker};
 use ockam_node::{Context, WorkerBuilder};
-use ockam_transport_tcp::{TcpConnectionOptions, TcpListenerOptions, TcpTransport};
+use ockam_transport_tcp::{TcpConnectionOptions, TcpFraming, TcpListenerOptions, TcpTransport};
 
 pub struct Echoer;
 
@@ -54,3 +54,46 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
 
     Ok(())
 }
+
+#[ockam_macros::test]
+async fn send_receive_large_message(ctx: &mut Context) -> Result<()> {
+    let transport = TcpTransport::create(ctx).await?;
+    let (listener_address, _) = transport
+        .listen(
+            "127.0.0.1:0",
+            TcpListenerOptions::new().with_framing(TcpFraming::V2),
+        )
+        .await?;
+    WorkerBuilder::with_mailboxes(
+        Mailboxes::main("echoer", Arc::new(AllowAll), Arc::new(AllowAll)),
+        Echoer,
+    )
+    .start(ctx)
+    .await?;
+
+    let addr = transport
+        .connect(
+            listener_address.to_string(),
+            TcpConnectionOptions::new().with_framing(TcpFraming::V2),
+        )
+        .await?;
+
+    // A message which can't be framed with a 16-bit length prefix
+    let msg: String = rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(200_000)
+        .map(char::from)
+        .collect();
+
+    let reply = ctx
+        .send_and_receive::<String>(route![addr, "echoer"], msg.clone())
+        .await?;
+
+    assert_eq!(reply, msg, "Should receive the same message");
+
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
@@ -1,12 +1,15 @@
 use ockam::route;
 use ockam_core::Result;
 use ockam_node::Context;
-use ockam_transport_udp::{UdpTransport, UDP};
+use ockam_transport_udp::{UdpConnectionOptions, UdpTransport};
 
 #[ockam_macros::node]
 async fn main(mut ctx: Context) -> Result<()> {
-    let _udp = UdpTransport::create(&ctx).await?;
-    let r = route![(UDP, "localhost:8000"), "echoer"];
+    let udp = UdpTransport::create(&ctx).await?;
+    let connection = udp
+        .connect("localhost:8000", UdpConnectionOptions::new())
+        .await?;
+    let r = route![connection, "echoer"];
     // Wait to receive a reply and print it.
     let reply: String = ctx.send_and_receive(r, "Hello Ockam!".to_string()).await?;
 
@@ -1,12 +1,13 @@
 use ockam_core::{AllowAll, Result, Routed, Worker};
 use ockam_node::Context;
-use ockam_transport_udp::UdpTransport;
+use ockam_transport_udp::{UdpListenerOptions, UdpTransport};
 use tracing::debug;
 
 #[ockam_macros::node]
 async fn main(ctx: Context) -> Result<()> {
     let udp = UdpTransport::create(&ctx).await?;
-    udp.listen("127.0.0.1:8000").await?;
+    udp.listen("127.0.0.1:8000", UdpListenerOptions::new())
+        .await?;
     ctx.start_worker("echoer", Echoer, AllowAll, AllowAll)
         .await?;
     Ok(())
@@ -59,7 +59,7 @@ use ockam::{
 };
 use ockam_core::{route, AllowAll, Error, Result};
 use ockam_node::Context;
-use ockam_transport_udp::{UdpHolePuncher, UdpTransport, UDP};
+use ockam_transport_udp::{UdpHolePuncher, UdpListenerOptions, UdpTransport, UDP};
 use rand::Rng;
 use std::ops::Range;
 use tracing::{error, info};
@@ -100,9 +100,10 @@ async fn do_main(ctx: &mut Context) -> Result<()> {
     );
 
     // Create transport, echoer service and puncher
-    UdpTransport::create(ctx).await?;
+    let udp = UdpTransport::create(ctx).await?;
+    let (_, sender) = udp.listen("0.0.0.0:0", UdpListenerOptions::new()).await?;
     ctx.start_worker(ECHOER, Echoer, AllowAll, AllowAll).await?;
-    let rendezvous_route = route![(UDP, rendezvous_addr), RENDEZVOUS];
+    let rendezvous_route = route![sender, (UDP, rendezvous_addr), RENDEZVOUS];
     let mut puncher = UdpHolePuncher::create(ctx, &this_name, &that_name, rendezvous_route).await?;
     info!("Puncher address = {:?}", puncher.address());
 
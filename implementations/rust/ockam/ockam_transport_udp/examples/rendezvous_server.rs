@@ -1,6 +1,6 @@
 use ockam_core::Result;
 use ockam_node::Context;
-use ockam_transport_udp::{UdpRendezvousService, UdpTransport};
+use ockam_transport_udp::{UdpListenerOptions, UdpRendezvousService, UdpTransport};
 use tracing::debug;
 
 #[ockam_macros::node]
@@ -14,7 +14,7 @@ async fn main(ctx: Context) -> Result<()> {
     UdpRendezvousService::start(&ctx, "rendezvous").await?;
 
     let udp = UdpTransport::create(&ctx).await?;
-    udp.listen(addr).await?;
+    udp.listen(addr, UdpListenerOptions::new()).await?;
 
     // Don't stop context/node. Run forever.
     Ok(())
@@ -28,14 +28,15 @@ use ockam_node::Context;
 /// ```rust
 /// # use {ockam_node::Context, ockam_core::{Result, route}};
 /// # async fn test(ctx: &mut Context) -> Result<()> {
-/// use ockam_transport_udp::{UdpHolePuncher, UdpTransport, UDP};
+/// use ockam_transport_udp::{UdpHolePuncher, UdpListenerOptions, UdpTransport, UDP};
 ///
-/// // Create transport
-/// UdpTransport::create(ctx).await?;
+/// // Create transport and bind a local socket
+/// let udp = UdpTransport::create(ctx).await?;
+/// let (_, sender) = udp.listen("0.0.0.0:0", UdpListenerOptions::new()).await?;
 ///
 /// // Create a NAT hole from us 'alice' to them 'bob' using
 /// // the Rendezvous service 'zurg' at public IP address `192.168.1.10:4000`
-/// let rendezvous_route = route![(UDP, "192.168.1.10:4000"), "zurg"];
+/// let rendezvous_route = route![sender, (UDP, "192.168.1.10:4000"), "zurg"];
 /// let mut puncher = UdpHolePuncher::create(ctx, "alice", "bob", rendezvous_route).await?;
 ///
 /// // Note: For this to work, 'bob' will likewise need to create a hole thru to us
@@ -62,6 +63,10 @@ pub struct UdpHolePuncher {
 
 impl UdpHolePuncher {
     /// Create a new UDP NAT Hole Puncher
+    ///
+    /// The `rendezvous_route` must start with the sender of a socket bound with
+    /// [`UdpTransport::listen`](crate::UdpTransport::listen), followed by the UDP address
+    /// of the Rendezvous service. The same socket is used to reach the peer.
     pub async fn create<S: AsRef<str>, R: Into<Route>>(
         ctx: &mut Context,
         puncher_name: S,
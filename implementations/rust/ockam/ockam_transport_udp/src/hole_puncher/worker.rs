@@ -2,8 +2,8 @@ use crate::hole_puncher::message::PunchMessage;
 use crate::rendezvous_service::{RendezvousRequest, RendezvousResponse};
 use crate::PunchError;
 use ockam_core::{
-    Address, AllowAll, Any, Decodable, Encodable, LocalMessage, Mailbox, Mailboxes, Result, Route,
-    Routed, Worker,
+    route, Address, AllowAll, Any, Decodable, Encodable, LocalMessage, Mailbox, Mailboxes, Result,
+    Route, Routed, Worker,
 };
 use ockam_node::{Context, DelayedEvent, MessageSendReceiveOptions, WorkerBuilder};
 use std::sync::Arc;
@@ -274,6 +274,10 @@ impl UdpHolePunchWorker {
 
             // Query Rendezvous service
             if let Ok(peer_route) = self.rendezvous_query(ctx).await {
+                // Reach the peer from the same local socket as the Rendezvous service,
+                // this is the socket which has a hole open in our NAT
+                let local_sender = self.rendezvous_route.next()?.clone();
+                let peer_route = route![local_sender, peer_route];
                 self.peer_route = Some(peer_route.clone());
 
                 // Ping peer
@@ -7,19 +7,34 @@
 //
 // Then, run a client that sends a hello message to the server
 // with command `cargo run --example client`
-use ockam_core::TransportType;
+use ockam_core::{Result, TransportType};
+use ockam_transport_core::TransportError;
+use std::net::{SocketAddr, ToSocketAddrs};
 
 pub use hole_puncher::{PunchError, UdpHolePuncher};
+pub use options::*;
+pub use portal::*;
+pub use registry::*;
 pub use rendezvous_service::UdpRendezvousService;
 pub use transport::UdpTransport;
 pub use transport::UdpTransportExtension;
 
 mod hole_puncher;
+mod options;
+mod portal;
+mod registry;
 mod rendezvous_service;
-mod router;
 mod transport;
 mod workers;
 
 pub const UDP: TransportType = TransportType::new(2);
 
 pub const CLUSTER_NAME: &str = "_internals.transport.udp";
+
+/// Resolve a peer address, which can be a hostname, to an IPv4 `SocketAddr`
+fn resolve_peer(peer: String) -> Result<SocketAddr> {
+    peer.to_socket_addrs()
+        .map_err(|_| TransportError::InvalidAddress)?
+        .find(SocketAddr::is_ipv4)
+        .ok_or_else(|| TransportError::InvalidAddress.into())
+}
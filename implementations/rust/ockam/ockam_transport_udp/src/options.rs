@@ -0,0 +1,99 @@
+use crate::workers::Addresses;
+use ockam_core::flow_control::{FlowControlId, FlowControls};
+use ockam_core::Result;
+use ockam_transport_core::{ConnectionAccessControl, ConnectionFlowControl, ListenerFlowControl};
+
+/// Trust Options for a UDP connection to a single peer
+#[derive(Clone, Debug)]
+pub struct UdpConnectionOptions {
+    pub(crate) flow_control: ConnectionFlowControl,
+}
+
+impl UdpConnectionOptions {
+    /// This constructor is insecure, because outgoing messages from such connections will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    pub fn insecure() -> Self {
+        Self {
+            flow_control: ConnectionFlowControl::insecure(),
+        }
+    }
+
+    /// This constructor is insecure, because outgoing messages from such connection will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            flow_control: ConnectionFlowControl::insecure(),
+        }
+    }
+
+    /// Mark this Udp Receiver as a Producer for a given [`FlowControlId`]
+    pub fn as_producer(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
+        Self {
+            flow_control: ConnectionFlowControl::producer(flow_controls, flow_control_id),
+        }
+    }
+
+    pub(crate) fn setup_flow_control(&self, addresses: &Addresses) {
+        self.flow_control
+            .setup(addresses.receiver_address(), addresses.sender_address())
+    }
+
+    pub(crate) fn create_access_control(self) -> ConnectionAccessControl {
+        self.flow_control.access_control()
+    }
+}
+
+/// Trust Options for a UDP listener
+///
+/// Since UDP has no connections, all the datagrams received on a listening socket,
+/// whatever their source, are forwarded by the same receiver.
+#[derive(Debug)]
+pub struct UdpListenerOptions {
+    pub(crate) flow_control: ListenerFlowControl,
+}
+
+impl UdpListenerOptions {
+    /// This constructor is insecure, because outgoing messages from such listeners will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    pub fn insecure() -> Self {
+        Self {
+            flow_control: ListenerFlowControl::insecure(),
+        }
+    }
+
+    /// This constructor is insecure, because outgoing messages from such listeners will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            flow_control: ListenerFlowControl::insecure(),
+        }
+    }
+
+    /// Mark this Udp Listener as a Spawner with given [`FlowControlId`].
+    /// NOTE: The receiver of the listening socket gets a fresh random [`FlowControlId`],
+    /// however it is still marked with Spawner's [`FlowControlId`]
+    pub fn as_spawner(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
+        Self {
+            flow_control: ListenerFlowControl::spawner(flow_controls, flow_control_id),
+        }
+    }
+
+    pub(crate) fn setup_flow_control(&self, addresses: &Addresses) -> Option<FlowControlId> {
+        self.flow_control.add_spawner(addresses.sender_address());
+        self.flow_control
+            .setup(addresses.receiver_address(), addresses.sender_address())
+    }
+
+    pub(crate) fn create_access_control(
+        &self,
+        flow_control_id: Option<FlowControlId>,
+    ) -> Result<ConnectionAccessControl> {
+        self.flow_control.access_control(flow_control_id)
+    }
+}
@@ -0,0 +1,237 @@
+use crate::portal::addresses::{Addresses, PortalType};
+use crate::portal::inlet_worker::UdpInletWorker;
+use crate::portal::session::SessionActivity;
+use crate::{UdpInletOptions, UdpPortalMessage, UdpRegistry, MAX_DATAGRAM_SIZE, MAX_PAYLOAD_SIZE};
+use core::fmt::{self, Debug, Formatter};
+use ockam_core::compat::collections::HashMap;
+use ockam_core::compat::sync::{Arc, Mutex};
+use ockam_core::{async_trait, DenyAll, Mailbox, Mailboxes, OutgoingAccessControl, RelayMessage};
+use ockam_core::{Address, Processor, Result, Route};
+use ockam_node::{Context, ProcessorBuilder};
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+use tracing::{debug, error, warn};
+
+/// How often idle sessions are looked for
+const SWEEP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A session between a client of a UDP Inlet and the Outlet
+pub(super) struct InletSession {
+    /// Internal address of the session worker
+    pub(super) worker: Address,
+    pub(super) activity: SessionActivity,
+}
+
+/// Sessions of a UDP Inlet, indexed by the source address of their client
+pub(super) type InletSessions = Arc<Mutex<HashMap<SocketAddr, InletSession>>>;
+
+/// A UDP Portal Inlet listen processor
+///
+/// UDP Portal Inlet listen processors are created by `UdpTransport`
+/// after a call is made to
+/// [`UdpTransport::create_inlet`](crate::UdpTransport::create_inlet).
+///
+/// Every datagram read from the bound socket is forwarded to the session
+/// of its source address, which is created on the first datagram and
+/// closed once it has been idle for longer than the configured timeout.
+pub(crate) struct UdpInletListenProcessor {
+    registry: UdpRegistry,
+    socket: Arc<UdpSocket>,
+    outlet_listener_route: Route,
+    options: UdpInletOptions,
+    sessions: InletSessions,
+    buf: Vec<u8>,
+    last_sweep: Instant,
+}
+
+impl UdpInletListenProcessor {
+    /// Start a new `UdpInletListenProcessor`
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: UdpRegistry,
+        outlet_listener_route: Route,
+        addr: SocketAddr,
+        options: UdpInletOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let processor_address = Address::random_tagged("UdpInletListenProcessor");
+
+        debug!("Binding UdpInletListenProcessor to {}", addr);
+        let socket = match UdpSocket::bind(addr).await {
+            Ok(socket) => socket,
+            Err(err) => {
+                error!(%addr, %err, "could not bind to address");
+                return Err(TransportError::from(err).into());
+            }
+        };
+        let socket_addr = socket.local_addr().map_err(TransportError::from)?;
+
+        let sessions = InletSessions::default();
+        let processor = Self {
+            registry,
+            socket: Arc::new(socket),
+            outlet_listener_route,
+            options,
+            sessions: sessions.clone(),
+            buf: vec![0; MAX_DATAGRAM_SIZE],
+            last_sweep: Instant::now(),
+        };
+
+        // Only sends datagrams to the session workers it spawned
+        let mailbox = Mailbox::new(
+            processor_address.clone(),
+            Arc::new(DenyAll),
+            Arc::new(AllowSessionWorkers(sessions.clone())),
+        );
+        ProcessorBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![]), processor)
+            .start(ctx)
+            .await?;
+
+        Ok((socket_addr, processor_address))
+    }
+
+    /// Return the internal address of the session for `client`, starting it if needed
+    async fn session(&mut self, ctx: &Context, client: SocketAddr) -> Result<Address> {
+        if let Some(session) = self.sessions.lock().unwrap().get(&client) {
+            session.activity.touch();
+            return Ok(session.worker.clone());
+        }
+
+        let addresses = Addresses::generate(PortalType::Inlet);
+        self.options
+            .setup_flow_control(&addresses, self.outlet_listener_route.next()?)?;
+
+        let activity = SessionActivity::new();
+        UdpInletWorker::start(
+            ctx,
+            self.registry.clone(),
+            self.socket.clone(),
+            client,
+            self.outlet_listener_route.clone(),
+            addresses.clone(),
+            ctx.address(),
+            self.options.incoming_access_control.clone(),
+            self.sessions.clone(),
+            activity.clone(),
+        )
+        .await?;
+
+        // A session which failed to start is started again with the next datagram
+        self.sessions.lock().unwrap().insert(
+            client,
+            InletSession {
+                worker: addresses.internal.clone(),
+                activity,
+            },
+        );
+
+        Ok(addresses.internal)
+    }
+
+    /// Stop the sessions which have been idle for too long
+    async fn expire_idle_sessions(&mut self, ctx: &Context) {
+        if self.last_sweep.elapsed() < SWEEP_INTERVAL {
+            return;
+        }
+        self.last_sweep = Instant::now();
+
+        let idle_timeout = self.options.idle_timeout;
+        let expired: Vec<(SocketAddr, Address)> = {
+            let mut sessions = self.sessions.lock().unwrap();
+            let expired: Vec<_> = sessions
+                .iter()
+                .filter(|(_, s)| s.activity.is_idle(idle_timeout))
+                .map(|(client, s)| (*client, s.worker.clone()))
+                .collect();
+            for (client, _) in &expired {
+                sessions.remove(client);
+            }
+            expired
+        };
+
+        for (client, worker) in expired {
+            debug!(%client, "UDP Inlet session expired");
+            let _ = ctx.stop_worker(worker).await;
+        }
+    }
+}
+
+#[async_trait]
+impl Processor for UdpInletListenProcessor {
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.add_inlet_listener_processor(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry
+            .remove_inlet_listener_processor(&ctx.address());
+
+        let workers: Vec<Address> = self
+            .sessions
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, s)| s.worker)
+            .collect();
+        for worker in workers {
+            let _ = ctx.stop_worker(worker).await;
+        }
+
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        let received =
+            tokio::time::timeout(SWEEP_INTERVAL, self.socket.recv_from(&mut self.buf)).await;
+
+        match received {
+            // Nothing received, only look for idle sessions
+            Err(_) => {}
+            Ok(Err(err)) => {
+                warn!(%err, "UDP Inlet failed to receive a datagram");
+            }
+            Ok(Ok((len, client))) if len > MAX_PAYLOAD_SIZE => {
+                warn!(%client, len, "UDP Inlet dropped a datagram larger than {MAX_PAYLOAD_SIZE} bytes");
+            }
+            Ok(Ok((len, client))) => {
+                let datagram = self.buf[..len].to_vec();
+                let worker = self.session(ctx, client).await?;
+                ctx.send(worker, UdpPortalMessage::Datagram(datagram))
+                    .await?;
+            }
+        }
+
+        self.expire_idle_sessions(ctx).await;
+
+        Ok(true)
+    }
+}
+
+/// Allow messages sent to the worker of an Inlet session
+struct AllowSessionWorkers(InletSessions);
+
+impl Debug for AllowSessionWorkers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllowSessionWorkers").finish()
+    }
+}
+
+#[async_trait]
+impl OutgoingAccessControl for AllowSessionWorkers {
+    async fn is_authorized(&self, relay_msg: &RelayMessage) -> Result<bool> {
+        let sessions = self.0.lock().unwrap();
+        if sessions
+            .values()
+            .any(|session| &session.worker == relay_msg.destination())
+        {
+            ockam_core::allow()
+        } else {
+            ockam_core::deny()
+        }
+    }
+}
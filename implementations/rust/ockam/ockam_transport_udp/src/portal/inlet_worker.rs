@@ -0,0 +1,201 @@
+use crate::portal::addresses::Addresses;
+use crate::portal::inlet_listener::InletSessions;
+use crate::portal::session::SessionActivity;
+use crate::{UdpPortalMessage, UdpRegistry};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{
+    async_trait, Address, AllowAll, AllowSourceAddress, Any, Decodable, DenyAll,
+    IncomingAccessControl, Mailbox, Mailboxes, Result, Route, Routed, Worker,
+};
+use ockam_node::{Context, WorkerBuilder};
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+use tokio::net::UdpSocket;
+use tracing::{debug, warn};
+
+/// Maximum number of datagrams kept while waiting for the Outlet to answer
+const MAX_PENDING_DATAGRAMS: usize = 64;
+
+/// A UDP Portal Inlet session worker
+///
+/// Created by the [`UdpInletListenProcessor`](super::UdpInletListenProcessor)
+/// for every new client, it forwards the datagrams of that client to the
+/// Outlet, and the datagrams received from the Outlet back to the client.
+pub(super) struct UdpInletWorker {
+    registry: UdpRegistry,
+    socket: Arc<UdpSocket>,
+    client: SocketAddr,
+    addresses: Addresses,
+    ping_route: Route,
+    remote_route: Option<Route>,
+    pending: Vec<Vec<u8>>,
+    sessions: InletSessions,
+    activity: SessionActivity,
+}
+
+impl UdpInletWorker {
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn start(
+        ctx: &Context,
+        registry: UdpRegistry,
+        socket: Arc<UdpSocket>,
+        client: SocketAddr,
+        ping_route: Route,
+        addresses: Addresses,
+        listener_address: Address,
+        access_control: Arc<dyn IncomingAccessControl>,
+        sessions: InletSessions,
+        activity: SessionActivity,
+    ) -> Result<()> {
+        debug!(
+            %client,
+            "Creating new UDP inlet session at internal: {}, remote: {}",
+            addresses.internal, addresses.remote
+        );
+
+        let worker = Self {
+            registry,
+            socket,
+            client,
+            addresses: addresses.clone(),
+            ping_route,
+            remote_route: None,
+            pending: vec![],
+            sessions,
+            activity,
+        };
+
+        let internal_mailbox = Mailbox::new(
+            addresses.internal,
+            Arc::new(AllowSourceAddress(listener_address)),
+            Arc::new(DenyAll),
+        );
+
+        let remote_mailbox = Mailbox::new(
+            addresses.remote,
+            access_control,
+            Arc::new(AllowAll), // FIXME: @ac Allow to respond anywhere using return_route
+        );
+
+        WorkerBuilder::with_mailboxes(
+            Mailboxes::new(internal_mailbox, vec![remote_mailbox]),
+            worker,
+        )
+        .start(ctx)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn send_to_outlet(&self, ctx: &Context, route: Route, datagram: Vec<u8>) -> Result<()> {
+        ctx.send_from_address(
+            route,
+            UdpPortalMessage::Datagram(datagram),
+            self.addresses.remote.clone(),
+        )
+        .await
+    }
+}
+
+#[async_trait]
+impl Worker for UdpInletWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.add_portal_worker(&self.addresses.remote);
+
+        // Force creation of an Outlet session on the other side
+        ctx.send_from_address(
+            self.ping_route.clone(),
+            UdpPortalMessage::Ping,
+            self.addresses.remote.clone(),
+        )
+        .await
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_portal_worker(&self.addresses.remote);
+
+        // The listener already forgot expired sessions, but not the ones closed by the Outlet
+        if let Ok(mut sessions) = self.sessions.lock() {
+            if sessions
+                .get(&self.client)
+                .map(|s| s.worker == self.addresses.internal)
+                .unwrap_or(false)
+            {
+                sessions.remove(&self.client);
+            }
+        }
+
+        if let Some(remote_route) = self.remote_route.take() {
+            if let Err(err) = ctx
+                .send_from_address(
+                    remote_route,
+                    UdpPortalMessage::Disconnect,
+                    self.addresses.remote.clone(),
+                )
+                .await
+            {
+                debug!(%err, "Could not notify the UDP Outlet about the session closing");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let mut onward_route = msg.onward_route();
+        let recipient = onward_route.step()?;
+        if onward_route.next().is_ok() {
+            return Err(TransportError::UnknownRoute.into());
+        }
+
+        let return_route = msg.return_route();
+        let msg = UdpPortalMessage::decode(msg.payload())?;
+
+        if recipient == self.addresses.internal {
+            let datagram = match msg {
+                UdpPortalMessage::Datagram(datagram) => datagram,
+                _ => return Err(TransportError::Protocol.into()),
+            };
+            match self.remote_route.clone() {
+                Some(remote_route) => self.send_to_outlet(ctx, remote_route, datagram).await?,
+                None if self.pending.len() < MAX_PENDING_DATAGRAMS => self.pending.push(datagram),
+                None => debug!(client = %self.client, "Dropping datagram, UDP Outlet is not ready"),
+            }
+            return Ok(());
+        }
+
+        match msg {
+            UdpPortalMessage::Pong => {
+                if self.remote_route.is_some() {
+                    return Err(TransportError::PortalInvalidState.into());
+                }
+                debug!("UDP Inlet at: {} received pong", self.addresses.internal);
+                for datagram in core::mem::take(&mut self.pending) {
+                    self.send_to_outlet(ctx, return_route.clone(), datagram)
+                        .await?;
+                }
+                self.remote_route = Some(return_route);
+            }
+            UdpPortalMessage::Datagram(datagram) => {
+                if self.remote_route.is_none() {
+                    return Err(TransportError::PortalInvalidState.into());
+                }
+                self.activity.touch();
+                if let Err(err) = self.socket.send_to(&datagram, self.client).await {
+                    warn!(client = %self.client, %err, "Failed to send datagram to UDP Inlet client");
+                }
+            }
+            UdpPortalMessage::Disconnect => {
+                // No need to notify the Outlet back
+                self.remote_route = None;
+                ctx.stop_worker(self.addresses.internal.clone()).await?;
+            }
+            UdpPortalMessage::Ping => return Err(TransportError::Protocol.into()),
+        }
+
+        Ok(())
+    }
+}
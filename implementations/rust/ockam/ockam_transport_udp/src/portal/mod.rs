@@ -0,0 +1,14 @@
+mod addresses;
+mod inlet_listener;
+mod inlet_worker;
+mod options;
+mod outlet_listener;
+mod outlet_worker;
+mod portal_message;
+mod receiver;
+mod session;
+
+pub(crate) use inlet_listener::*;
+pub use options::*;
+pub(crate) use outlet_listener::*;
+pub use portal_message::*;
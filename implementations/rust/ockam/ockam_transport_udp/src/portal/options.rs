@@ -0,0 +1,183 @@
+use crate::portal::addresses::Addresses;
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+use ockam_core::flow_control::{FlowControlId, FlowControlPolicy, FlowControls};
+use ockam_core::{Address, AllowAll, IncomingAccessControl, Result};
+use ockam_transport_core::TransportError;
+
+/// Default time after which a portal session without any datagram is closed
+pub const DEFAULT_PORTAL_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Trust Options for a UDP Inlet
+pub struct UdpInletOptions {
+    pub(super) consumer_flow_controls: Option<FlowControls>,
+    pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) idle_timeout: Duration,
+}
+
+impl UdpInletOptions {
+    /// Default constructor without flow control and Incoming Access Control
+    pub fn new() -> Self {
+        Self {
+            consumer_flow_controls: None,
+            incoming_access_control: Arc::new(AllowAll),
+            idle_timeout: DEFAULT_PORTAL_IDLE_TIMEOUT,
+        }
+    }
+
+    /// Set Incoming Access Control
+    pub fn with_incoming_access_control_impl(
+        mut self,
+        access_control: impl IncomingAccessControl,
+    ) -> Self {
+        self.incoming_access_control = Arc::new(access_control);
+        self
+    }
+
+    /// Set Incoming Access Control
+    pub fn with_incoming_access_control(
+        mut self,
+        access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Self {
+        self.incoming_access_control = access_control;
+        self
+    }
+
+    /// Set the time after which a client that neither sent nor received
+    /// any datagram has its session closed
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Mark that created Inlet sessions are Consumers for the [`FlowControlId`]
+    /// of the next hop of the Outlet route
+    pub fn as_consumer(mut self, flow_controls: &FlowControls) -> Self {
+        self.consumer_flow_controls = Some(flow_controls.clone());
+
+        self
+    }
+
+    pub(super) fn setup_flow_control(&self, addresses: &Addresses, next: &Address) -> Result<()> {
+        if let Some(flow_controls) = &self.consumer_flow_controls {
+            if let Some(flow_control_id) = flow_controls
+                .find_flow_control_with_producer_address(next)
+                .map(|x| x.flow_control_id().clone())
+            {
+                // Allow a sender with corresponding flow_control_id send messages to this address
+                flow_controls.add_consumer(
+                    &addresses.remote,
+                    &flow_control_id,
+                    FlowControlPolicy::ProducerAllowMultiple,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for UdpInletOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(super) struct ConsumerFlowControl {
+    pub(super) flow_controls: FlowControls,
+    pub(super) flow_control_id: FlowControlId,
+    pub(super) flow_control_policy: FlowControlPolicy,
+}
+
+/// Trust Options for a UDP Outlet
+pub struct UdpOutletOptions {
+    pub(super) consumer_flow_control: Option<ConsumerFlowControl>,
+    pub(super) incoming_access_control: Arc<dyn IncomingAccessControl>,
+    pub(super) idle_timeout: Duration,
+}
+
+impl UdpOutletOptions {
+    /// Default constructor without flow control and Incoming Access Control
+    pub fn new() -> Self {
+        Self {
+            consumer_flow_control: None,
+            incoming_access_control: Arc::new(AllowAll),
+            idle_timeout: DEFAULT_PORTAL_IDLE_TIMEOUT,
+        }
+    }
+
+    /// Set Incoming Access Control
+    pub fn with_incoming_access_control_impl(
+        mut self,
+        access_control: impl IncomingAccessControl,
+    ) -> Self {
+        self.incoming_access_control = Arc::new(access_control);
+        self
+    }
+
+    /// Set Incoming Access Control
+    pub fn with_incoming_access_control(
+        mut self,
+        access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Self {
+        self.incoming_access_control = access_control;
+        self
+    }
+
+    /// Set the time after which a session that neither sent nor received
+    /// any datagram is closed, in case the Inlet never closes it
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Mark that this Outlet listener is a Consumer for to the given [`FlowControlId`]
+    /// Also, in this case spawned Outlet sessions will be marked as Consumers with
+    /// [`FlowControlId`] of the message that was used to create the session
+    pub fn as_consumer(
+        mut self,
+        flow_controls: &FlowControls,
+        flow_control_id: &FlowControlId,
+        flow_control_policy: FlowControlPolicy,
+    ) -> Self {
+        self.consumer_flow_control = Some(ConsumerFlowControl {
+            flow_controls: flow_controls.clone(),
+            flow_control_id: flow_control_id.clone(),
+            flow_control_policy,
+        });
+
+        self
+    }
+
+    pub(super) fn setup_flow_control(
+        &self,
+        addresses: &Addresses,
+        producer_flow_control_id: Option<FlowControlId>,
+    ) -> Result<()> {
+        match (&self.consumer_flow_control, producer_flow_control_id) {
+            (Some(consumer_flow_control), Some(producer_flow_control_id)) => {
+                // Allow a sender with corresponding flow_control_id send messages to this address
+                consumer_flow_control.flow_controls.add_consumer(
+                    &addresses.remote,
+                    &producer_flow_control_id,
+                    FlowControlPolicy::ProducerAllowMultiple,
+                );
+            }
+            (None, None) => {}
+            // We act as a consumer in some cases,
+            // but we were reached without flow control, which is fine
+            (Some(_), None) => {}
+            _ => {
+                return Err(TransportError::FlowControlInconsistency.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for UdpOutletOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,121 @@
+use crate::portal::addresses::{Addresses, PortalType};
+use crate::portal::outlet_worker::UdpOutletWorker;
+use crate::{UdpOutletOptions, UdpPortalMessage, UdpRegistry};
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, Address, DenyAll, Mailboxes, Result, Routed, Worker};
+use ockam_node::{Context, WorkerBuilder};
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+use tracing::debug;
+
+/// A UDP Portal Outlet listen worker
+///
+/// UDP Portal Outlet listen workers are created by `UdpTransport`
+/// after a call is made to
+/// [`UdpTransport::create_outlet`](crate::UdpTransport::create_outlet).
+///
+/// Every Inlet session pinging this worker gets its own Outlet session,
+/// with its own local socket, so that the target service can tell clients apart.
+pub(crate) struct UdpOutletListenWorker {
+    registry: UdpRegistry,
+    peer: SocketAddr,
+    options: UdpOutletOptions,
+}
+
+impl UdpOutletListenWorker {
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: UdpRegistry,
+        address: Address,
+        peer: SocketAddr,
+        options: UdpOutletOptions,
+    ) -> Result<()> {
+        let access_control = options.incoming_access_control.clone();
+
+        if let Some(consumer_flow_control) = &options.consumer_flow_control {
+            consumer_flow_control.flow_controls.add_consumer(
+                &address,
+                &consumer_flow_control.flow_control_id,
+                consumer_flow_control.flow_control_policy,
+            );
+        }
+
+        let worker = Self {
+            registry,
+            peer,
+            options,
+        };
+        WorkerBuilder::with_mailboxes(
+            Mailboxes::main(address, access_control, Arc::new(DenyAll)),
+            worker,
+        )
+        .start(ctx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Worker for UdpOutletListenWorker {
+    type Context = Context;
+    type Message = UdpPortalMessage;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.add_outlet_listener_worker(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_outlet_listener_worker(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        ctx: &mut Self::Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        let return_route = msg.return_route();
+        let src_addr = msg.src_addr();
+
+        if let UdpPortalMessage::Ping = msg.body() {
+        } else {
+            return Err(TransportError::Protocol.into());
+        }
+
+        // If the Ping comes from a Producer, the session is added to its flow control,
+        // so that it can receive the datagrams of the Inlet session
+        let flow_control_id =
+            if let Some(consumer_flow_control) = &self.options.consumer_flow_control {
+                consumer_flow_control
+                    .flow_controls
+                    .get_flow_control_with_producer(&src_addr)
+                    .map(|x| x.flow_control_id().clone())
+            } else {
+                None
+            };
+
+        let addresses = Addresses::generate(PortalType::Outlet);
+
+        self.options
+            .setup_flow_control(&addresses, flow_control_id)?;
+
+        UdpOutletWorker::start(
+            ctx,
+            self.registry.clone(),
+            self.peer,
+            return_route,
+            addresses.clone(),
+            self.options.incoming_access_control.clone(),
+            self.options.idle_timeout,
+        )
+        .await?;
+
+        debug!("Created UDP Outlet session at {}", addresses.remote);
+
+        Ok(())
+    }
+}
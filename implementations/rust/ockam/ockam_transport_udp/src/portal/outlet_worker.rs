@@ -0,0 +1,190 @@
+use crate::portal::addresses::Addresses;
+use crate::portal::receiver::UdpPortalRecvProcessor;
+use crate::portal::session::SessionActivity;
+use crate::{UdpPortalMessage, UdpRegistry};
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{
+    async_trait, AllowAll, AllowOnwardAddress, AllowSourceAddress, Any, Decodable, DenyAll,
+    IncomingAccessControl, Mailbox, Mailboxes, Result, Route, Routed, Worker,
+};
+use ockam_node::{Context, ProcessorBuilder, WorkerBuilder};
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+use tokio::net::UdpSocket;
+use tracing::{debug, warn};
+
+/// A UDP Portal Outlet session worker
+///
+/// Created by the [`UdpOutletListenWorker`](super::UdpOutletListenWorker) for
+/// every Inlet session, it owns a local socket connected to the target service.
+pub(super) struct UdpOutletWorker {
+    registry: UdpRegistry,
+    socket: Arc<UdpSocket>,
+    peer: SocketAddr,
+    addresses: Addresses,
+    remote_route: Option<Route>,
+    activity: SessionActivity,
+    idle_timeout: Duration,
+}
+
+impl UdpOutletWorker {
+    pub(super) async fn start(
+        ctx: &Context,
+        registry: UdpRegistry,
+        peer: SocketAddr,
+        pong_route: Route,
+        addresses: Addresses,
+        access_control: Arc<dyn IncomingAccessControl>,
+        idle_timeout: Duration,
+    ) -> Result<()> {
+        let bind_addr = if peer.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket = UdpSocket::bind(bind_addr)
+            .await
+            .map_err(TransportError::from)?;
+        socket.connect(peer).await.map_err(TransportError::from)?;
+
+        let worker = Self {
+            registry,
+            socket: Arc::new(socket),
+            peer,
+            addresses: addresses.clone(),
+            remote_route: Some(pong_route),
+            activity: SessionActivity::new(),
+            idle_timeout,
+        };
+
+        let internal_mailbox = Mailbox::new(
+            addresses.internal,
+            Arc::new(AllowSourceAddress(addresses.receiver)),
+            Arc::new(DenyAll),
+        );
+
+        let remote_mailbox = Mailbox::new(
+            addresses.remote,
+            access_control,
+            Arc::new(AllowAll), // FIXME: @ac Allow to respond anywhere using return_route
+        );
+
+        WorkerBuilder::with_mailboxes(
+            Mailboxes::new(internal_mailbox, vec![remote_mailbox]),
+            worker,
+        )
+        .start(ctx)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Worker for UdpOutletWorker {
+    type Context = Context;
+    type Message = Any;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.add_portal_worker(&self.addresses.remote);
+
+        let receiver = UdpPortalRecvProcessor::new(
+            self.socket.clone(),
+            self.addresses.internal.clone(),
+            self.activity.clone(),
+            self.idle_timeout,
+        );
+        let mailbox = Mailbox::new(
+            self.addresses.receiver.clone(),
+            Arc::new(DenyAll),
+            Arc::new(AllowOnwardAddress(self.addresses.internal.clone())),
+        );
+        ProcessorBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![]), receiver)
+            .start(ctx)
+            .await?;
+
+        if let Some(pong_route) = self.remote_route.clone() {
+            ctx.send_from_address(
+                pong_route,
+                UdpPortalMessage::Pong,
+                self.addresses.remote.clone(),
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_portal_worker(&self.addresses.remote);
+
+        let _ = ctx.stop_processor(self.addresses.receiver.clone()).await;
+
+        if let Some(remote_route) = self.remote_route.take() {
+            if let Err(err) = ctx
+                .send_from_address(
+                    remote_route,
+                    UdpPortalMessage::Disconnect,
+                    self.addresses.remote.clone(),
+                )
+                .await
+            {
+                debug!(%err, "Could not notify the UDP Inlet about the session closing");
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<Any>) -> Result<()> {
+        let mut onward_route = msg.onward_route();
+        let recipient = onward_route.step()?;
+        if onward_route.next().is_ok() {
+            return Err(TransportError::UnknownRoute.into());
+        }
+
+        let msg = UdpPortalMessage::decode(msg.payload())?;
+
+        if recipient == self.addresses.internal {
+            match msg {
+                UdpPortalMessage::Datagram(datagram) => {
+                    if let Some(remote_route) = self.remote_route.clone() {
+                        ctx.send_from_address(
+                            remote_route,
+                            UdpPortalMessage::Datagram(datagram),
+                            self.addresses.remote.clone(),
+                        )
+                        .await?;
+                    }
+                }
+                // The session has been idle for too long
+                UdpPortalMessage::Disconnect => {
+                    debug!("UDP Outlet session at: {} expired", self.addresses.internal);
+                    ctx.stop_worker(self.addresses.internal.clone()).await?;
+                }
+                _ => return Err(TransportError::Protocol.into()),
+            }
+            return Ok(());
+        }
+
+        match msg {
+            UdpPortalMessage::Datagram(datagram) => {
+                self.activity.touch();
+                if let Err(err) = self.socket.send(&datagram).await {
+                    warn!(peer = %self.peer, %err, "Failed to send datagram to UDP Outlet target");
+                }
+            }
+            UdpPortalMessage::Disconnect => {
+                // No need to notify the Inlet back
+                self.remote_route = None;
+                ctx.stop_worker(self.addresses.internal.clone()).await?;
+            }
+            UdpPortalMessage::Ping | UdpPortalMessage::Pong => {
+                return Err(TransportError::Protocol.into())
+            }
+        }
+
+        Ok(())
+    }
+}
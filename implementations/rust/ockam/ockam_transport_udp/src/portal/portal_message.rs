@@ -0,0 +1,28 @@
+use ockam_core::Message;
+use serde::{Deserialize, Serialize};
+
+/// A command message type for a UDP Portal
+///
+/// Unlike TCP Portals, which carry a byte stream, every
+/// [`UdpPortalMessage::Datagram`] carries exactly one datagram.
+#[derive(Serialize, Deserialize, Message, Debug)]
+pub enum UdpPortalMessage {
+    /// First message that an Inlet session sends to the Outlet
+    Ping,
+    /// First message that an Outlet session sends to the Inlet
+    Pong,
+    /// Message to indicate that the session was closed on the other side
+    Disconnect,
+    /// A single datagram
+    Datagram(Vec<u8>),
+}
+
+/// Maximum size of a UDP datagram payload over IPv4
+pub const MAX_DATAGRAM_SIZE: usize = 65_507;
+
+/// Maximum size of a datagram carried by a [`UdpPortalMessage::Datagram`].
+///
+/// Larger datagrams are dropped, so that a portal message still fits in a
+/// message of the TCP transport with its default framing once it is routed
+/// and encrypted.
+pub const MAX_PAYLOAD_SIZE: usize = 48 * 1024;
@@ -0,0 +1,79 @@
+use crate::portal::session::SessionActivity;
+use crate::{UdpPortalMessage, MAX_DATAGRAM_SIZE, MAX_PAYLOAD_SIZE};
+use core::time::Duration;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, Address, Processor, Result};
+use ockam_node::Context;
+use tokio::net::UdpSocket;
+use tracing::warn;
+
+/// A UDP Portal receiving processor
+///
+/// Reads the datagrams sent back by the target service of an Outlet session
+/// and forwards them to the session worker. It also closes the session once
+/// it has been idle for longer than `idle_timeout`.
+pub(super) struct UdpPortalRecvProcessor {
+    socket: Arc<UdpSocket>,
+    buf: Vec<u8>,
+    worker_address: Address,
+    activity: SessionActivity,
+    idle_timeout: Duration,
+}
+
+impl UdpPortalRecvProcessor {
+    pub(super) fn new(
+        socket: Arc<UdpSocket>,
+        worker_address: Address,
+        activity: SessionActivity,
+        idle_timeout: Duration,
+    ) -> Self {
+        Self {
+            socket,
+            buf: vec![0; MAX_DATAGRAM_SIZE],
+            worker_address,
+            activity,
+            idle_timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl Processor for UdpPortalRecvProcessor {
+    type Context = Context;
+
+    async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
+        let received =
+            tokio::time::timeout(self.idle_timeout, self.socket.recv(&mut self.buf)).await;
+
+        match received {
+            Err(_) => {
+                if self.activity.is_idle(self.idle_timeout) {
+                    ctx.send(self.worker_address.clone(), UdpPortalMessage::Disconnect)
+                        .await?;
+                    return Ok(false);
+                }
+            }
+            // A connected socket reports ICMP errors, for example when the
+            // target service is not running yet. UDP clients are expected to retry
+            Ok(Err(err)) => {
+                warn!(%err, "UDP Outlet failed to receive a datagram");
+            }
+            Ok(Ok(len)) if len > MAX_PAYLOAD_SIZE => {
+                warn!(
+                    len,
+                    "UDP Outlet dropped a datagram larger than {MAX_PAYLOAD_SIZE} bytes"
+                );
+            }
+            Ok(Ok(len)) => {
+                self.activity.touch();
+                ctx.send(
+                    self.worker_address.clone(),
+                    UdpPortalMessage::Datagram(self.buf[..len].to_vec()),
+                )
+                .await?;
+            }
+        }
+
+        Ok(true)
+    }
+}
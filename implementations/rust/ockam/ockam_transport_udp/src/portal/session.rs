@@ -0,0 +1,28 @@
+use ockam_core::compat::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Last time a datagram went through a portal session, shared by the
+/// workers and processors of that session
+#[derive(Clone)]
+pub(super) struct SessionActivity(Arc<Mutex<Instant>>);
+
+impl SessionActivity {
+    pub(super) fn new() -> Self {
+        Self(Arc::new(Mutex::new(Instant::now())))
+    }
+
+    /// Record that a datagram went through the session
+    pub(super) fn touch(&self) {
+        if let Ok(mut last) = self.0.lock() {
+            *last = Instant::now();
+        }
+    }
+
+    /// Return true if no datagram went through the session for `timeout`
+    pub(super) fn is_idle(&self, timeout: Duration) -> bool {
+        match self.0.lock() {
+            Ok(last) => last.elapsed() >= timeout,
+            Err(_) => true,
+        }
+    }
+}
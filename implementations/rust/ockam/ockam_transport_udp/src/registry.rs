@@ -0,0 +1,130 @@
+use ockam_core::compat::sync::{Arc, RwLock};
+use ockam_core::Address;
+
+/// Registry of all active workers and processors in UDP Transport to ease their lifecycle management
+#[derive(Default, Clone)]
+pub struct UdpRegistry {
+    registry: Arc<RwLock<InternalRegistry>>,
+}
+
+impl UdpRegistry {
+    pub(crate) fn add_sender_worker(&self, addr: &Address, is_listener: bool) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.add_sender_worker(addr, is_listener);
+        }
+    }
+    pub(crate) fn remove_sender_worker(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.remove_sender_worker(addr);
+        }
+    }
+    pub(crate) fn add_receiver_processor(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.add_receiver_processor(addr);
+        }
+    }
+    pub(crate) fn remove_receiver_processor(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.remove_receiver_processor(addr);
+        }
+    }
+    pub(crate) fn add_inlet_listener_processor(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.inlet_listener_processors.push(addr.clone());
+        }
+    }
+    pub(crate) fn remove_inlet_listener_processor(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.inlet_listener_processors.retain(|x| x != addr);
+        }
+    }
+    pub(crate) fn add_outlet_listener_worker(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.outlet_listener_workers.push(addr.clone());
+        }
+    }
+    pub(crate) fn remove_outlet_listener_worker(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.outlet_listener_workers.retain(|x| x != addr);
+        }
+    }
+    pub(crate) fn add_portal_worker(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.portal_workers.push(addr.clone());
+        }
+    }
+    pub(crate) fn remove_portal_worker(&self, addr: &Address) {
+        if let Ok(mut lock) = self.registry.write() {
+            lock.portal_workers.retain(|x| x != addr);
+        }
+    }
+}
+
+impl UdpRegistry {
+    /// Return [`Address`]es of the sender workers of all active listening sockets
+    pub fn get_all_listeners(&self) -> Vec<Address> {
+        self.registry.read().unwrap().listeners.clone()
+    }
+
+    /// Return [`Address`]es of all active sender workers
+    pub fn get_all_sender_workers(&self) -> Vec<Address> {
+        self.registry.read().unwrap().sender_workers.clone()
+    }
+
+    /// Return [`Address`]es of all active receiver processors
+    pub fn get_all_receiver_processors(&self) -> Vec<Address> {
+        self.registry.read().unwrap().receiver_processors.clone()
+    }
+
+    /// Return [`Address`]es of all active portal inlet listen processors
+    pub fn get_all_inlet_listener_processors(&self) -> Vec<Address> {
+        self.registry
+            .read()
+            .unwrap()
+            .inlet_listener_processors
+            .clone()
+    }
+
+    /// Return [`Address`]es of all active portal outlet listen workers
+    pub fn get_all_outlet_listener_workers(&self) -> Vec<Address> {
+        self.registry
+            .read()
+            .unwrap()
+            .outlet_listener_workers
+            .clone()
+    }
+
+    /// Return [`Address`]es of the workers of all active portal sessions
+    pub fn get_all_portal_workers(&self) -> Vec<Address> {
+        self.registry.read().unwrap().portal_workers.clone()
+    }
+}
+
+#[derive(Default)]
+struct InternalRegistry {
+    listeners: Vec<Address>,
+    sender_workers: Vec<Address>,
+    receiver_processors: Vec<Address>,
+    inlet_listener_processors: Vec<Address>,
+    outlet_listener_workers: Vec<Address>,
+    portal_workers: Vec<Address>,
+}
+
+impl InternalRegistry {
+    fn add_sender_worker(&mut self, addr: &Address, is_listener: bool) {
+        self.sender_workers.push(addr.clone());
+        if is_listener {
+            self.listeners.push(addr.clone());
+        }
+    }
+    fn remove_sender_worker(&mut self, addr: &Address) {
+        self.sender_workers.retain(|x| x != addr);
+        self.listeners.retain(|x| x != addr);
+    }
+    fn add_receiver_processor(&mut self, addr: &Address) {
+        self.receiver_processors.push(addr.clone())
+    }
+    fn remove_receiver_processor(&mut self, addr: &Address) {
+        self.receiver_processors.retain(|x| x != addr);
+    }
+}
@@ -19,7 +19,7 @@ use tracing::{debug, trace, warn};
 /// # Example
 ///
 /// ```rust
-/// use ockam_transport_udp::{UdpTransport, UdpRendezvousService};
+/// use ockam_transport_udp::{UdpListenerOptions, UdpTransport, UdpRendezvousService};
 /// # use ockam_node::Context;
 /// # use ockam_core::Result;
 /// # async fn test(ctx: Context) -> Result<()> {
@@ -27,7 +27,7 @@ use tracing::{debug, trace, warn};
 /// // Start a Rendezvous service with address 'my_rendezvous' and listen on UDP port 4000
 /// UdpRendezvousService::start(&ctx, "my_rendezvous").await?;
 /// let udp = UdpTransport::create(&ctx).await?;
-/// udp.listen("0.0.0.0:4000").await?;
+/// udp.listen("0.0.0.0:4000", UdpListenerOptions::new()).await?;
 /// # Ok(()) }
 /// ```
 pub struct UdpRendezvousService;
@@ -148,7 +148,9 @@ impl Worker for RendezvousWorker {
 mod tests {
     use super::RendezvousWorker;
     use crate::rendezvous_service::{RendezvousRequest, RendezvousResponse};
-    use crate::{UdpRendezvousService, UdpTransport, UDP};
+    use crate::{
+        UdpConnectionOptions, UdpListenerOptions, UdpRendezvousService, UdpTransport, UDP,
+    };
     use ockam_core::errcode::Origin;
     use ockam_core::{route, AllowAll, Error, Result, Route, Routed, TransportType, Worker};
     use ockam_node::Context;
@@ -242,11 +244,16 @@ mod tests {
         // Create transport, start rendezvous service, start echo service and listen
         let transport = UdpTransport::create(ctx).await?;
         UdpRendezvousService::start(ctx, "rendezvous").await?;
-        let rendezvous_route = route![(UDP, bind_addr.to_string()), "rendezvous"];
         ctx.start_worker("echo", EchoUDPAddress, AllowAll, AllowAll)
             .await?;
-        let route_echo = route![(UDP, bind_addr.to_string()), "echo"];
-        transport.listen(bind_addr.to_string()).await?;
+        transport
+            .listen(bind_addr.to_string(), UdpListenerOptions::new())
+            .await?;
+        let sender = transport
+            .connect(bind_addr.to_string(), UdpConnectionOptions::new())
+            .await?;
+        let rendezvous_route = route![sender.clone(), "rendezvous"];
+        let route_echo = route![sender, "echo"];
 
         // Use echo service to find out our UDP sending address
         let send_addr: String = ctx.send_and_receive(route_echo, String::new()).await?;
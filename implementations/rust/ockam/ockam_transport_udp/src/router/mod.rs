@@ -1,6 +0,0 @@
-pub(crate) use handle::UdpRouterHandle;
-pub(crate) use udp_router::UdpRouter;
-
-mod handle;
-mod messages;
-mod udp_router;
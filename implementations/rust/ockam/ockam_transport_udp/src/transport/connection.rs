@@ -0,0 +1,49 @@
+use crate::workers::{Addresses, SocketRole};
+use crate::{resolve_peer, UdpConnectionOptions, UdpTransport};
+use ockam_core::{Address, Result};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+impl UdpTransport {
+    /// Bind a new local socket which exchanges datagrams with a single peer
+    ///
+    /// Returns the address of the worker sending messages to the peer.
+    /// Datagrams received from other peers on that socket are dropped.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpConnectionOptions, UdpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::{route, Result};
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// let addr = udp.connect("127.0.0.1:5000", UdpConnectionOptions::new()).await?;
+    /// ctx.send(route![addr, "echoer"], "Hello".to_string()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect(
+        &self,
+        peer: impl Into<String>,
+        options: UdpConnectionOptions,
+    ) -> Result<Address> {
+        let peer = resolve_peer(peer.into())?;
+
+        let addresses = Addresses::generate(SocketRole::Connection);
+
+        options.setup_flow_control(&addresses);
+        let access_control = options.create_access_control();
+
+        self.bind(
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
+            Some(peer),
+            &addresses,
+            access_control,
+        )
+        .await?;
+
+        Ok(addresses.sender_address().clone())
+    }
+
+    /// Close a socket bound with [`UdpTransport::connect`] given its sender `Address`
+    pub async fn disconnect(&self, address: &Address) -> Result<()> {
+        self.ctx.stop_worker(address.clone()).await
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use ockam_core::flow_control::FlowControls;
+use ockam_core::{async_trait, Address, AsyncTryClone, Result, TransportType};
+use ockam_node::Context;
+use ockam_transport_core::{unsupported_address_error, Transport};
+
+use crate::{UdpConnectionOptions, UdpRegistry, UdpTransport, UDP};
+
+impl UdpTransport {
+    /// Create a new UDP transport for the current node
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::UdpTransport;
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create(ctx: &Context) -> Result<UdpTransport> {
+        let udp = Self {
+            ctx: ctx.async_try_clone().await?,
+            registry: UdpRegistry::default(),
+        };
+        // make the UDP transport available in the list of supported transports for
+        // later address resolution when socket addresses will need to be instantiated as UDP
+        // worker addresses
+        ctx.register_transport(Arc::new(udp.async_try_clone().await?));
+        Ok(udp)
+    }
+}
+
+impl UdpTransport {
+    /// Getter
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+    /// Registry of all active sockets
+    pub fn registry(&self) -> &UdpRegistry {
+        &self.registry
+    }
+}
+
+#[async_trait]
+impl Transport for UdpTransport {
+    fn transport_type(&self) -> TransportType {
+        UDP
+    }
+
+    async fn resolve_address(
+        &self,
+        flow_controls: &FlowControls,
+        address: Address,
+    ) -> Result<Address> {
+        if address.transport_type() == UDP {
+            let id = flow_controls.generate_id();
+            let options = UdpConnectionOptions::as_producer(flow_controls, &id);
+            Ok(self.connect(address.address(), options).await?)
+        } else {
+            Err(unsupported_address_error("UDP", &address))
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::workers::{Addresses, SocketRole};
+use crate::{UdpListenerOptions, UdpTransport};
+use ockam_core::{Address, Result};
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+
+impl UdpTransport {
+    /// Bind a new local socket which exchanges datagrams with any peer
+    ///
+    /// Returns the socket address that this socket is bound to, and the address of its
+    /// sender worker.
+    ///
+    /// Messages sent to the sender worker must have the UDP address of the peer
+    /// right after the sender's address in their onward route. Messages received
+    /// on that socket have the sender's address and the UDP address of the peer
+    /// prepended to their return route.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpListenerOptions, UdpTransport, UDP};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::{route, Result};
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// let (_, sender) = udp.listen("127.0.0.1:8000", UdpListenerOptions::new()).await?;
+    /// ctx.send(route![sender, (UDP, "127.0.0.1:5000"), "echoer"], "Hello".to_string()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn listen(
+        &self,
+        bind_addr: impl AsRef<str>,
+        options: UdpListenerOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let bind_addr = bind_addr
+            .as_ref()
+            .parse()
+            .map_err(|_| TransportError::InvalidAddress)?;
+
+        let addresses = Addresses::generate(SocketRole::Listener);
+
+        let flow_control_id = options.setup_flow_control(&addresses);
+        let access_control = options.create_access_control(flow_control_id)?;
+
+        let local_addr = self
+            .bind(bind_addr, None, &addresses, access_control)
+            .await?;
+
+        Ok((local_addr, addresses.sender_address().clone()))
+    }
+
+    /// Close a socket bound with [`UdpTransport::listen`] given its sender `Address`
+    pub async fn stop_listener(&self, address: &Address) -> Result<()> {
+        self.ctx.stop_worker(address.clone()).await
+    }
+}
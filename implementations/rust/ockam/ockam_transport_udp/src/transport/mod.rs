@@ -0,0 +1,106 @@
+mod connection;
+mod lifecycle;
+mod listener;
+mod portals;
+
+use crate::workers::{Addresses, TransportMessageCodec, UdpRecvProcessor, UdpSendWorker};
+use crate::UdpRegistry;
+use futures_util::StreamExt;
+use ockam_core::{async_trait, AsyncTryClone, Result};
+use ockam_node::{Context, HasContext};
+use ockam_transport_core::{ConnectionAccessControl, TransportError};
+use std::net::SocketAddr;
+use tokio::net::UdpSocket;
+use tokio_util::udp::UdpFramed;
+use tracing::{debug, error};
+
+/// High level management interface for UDP transport
+///
+/// A node will have, at most, one UDP transport running.
+///
+/// To bind a local socket which can exchange datagrams with any peer use
+/// [`udp.listen()`](crate::UdpTransport::listen).
+///
+/// To bind a local socket which exchanges datagrams with a single peer use
+/// [`udp.connect()`](crate::UdpTransport::connect).
+///
+/// ```rust
+/// use ockam_transport_udp::{UdpConnectionOptions, UdpListenerOptions, UdpTransport};
+/// # use ockam_node::Context;
+/// # use ockam_core::Result;
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let udp = UdpTransport::create(&ctx).await?;
+/// udp.listen("127.0.0.1:8000", UdpListenerOptions::new()).await?; // Listen on port 8000
+/// udp.connect("127.0.0.1:5000", UdpConnectionOptions::new()).await?; // And connect to port 5000
+/// # Ok(()) }
+/// ```
+///
+/// This transport only supports IPv4.
+#[derive(AsyncTryClone)]
+#[async_try_clone(crate = "ockam_core")]
+pub struct UdpTransport {
+    ctx: Context,
+    registry: UdpRegistry,
+}
+
+impl UdpTransport {
+    /// Bind a local socket and start its sender and receiver
+    async fn bind(
+        &self,
+        bind_addr: SocketAddr,
+        peer: Option<SocketAddr>,
+        addresses: &Addresses,
+        access_control: ConnectionAccessControl,
+    ) -> Result<SocketAddr> {
+        // This transport only supports IPv4
+        if !bind_addr.is_ipv4() {
+            error!(local_addr = %bind_addr, "This transport only supports IPv4");
+            return Err(TransportError::InvalidAddress.into());
+        }
+
+        // Bind new socket
+        let socket = UdpSocket::bind(bind_addr)
+            .await
+            .map_err(|_| TransportError::InvalidAddress)?;
+        let local_addr = socket.local_addr().map_err(TransportError::from)?;
+
+        // Split socket into sink and stream
+        let (sink, stream) = UdpFramed::new(socket, TransportMessageCodec).split();
+
+        debug!("Creating new sender and receiver for {}", local_addr);
+
+        UdpSendWorker::start(
+            &self.ctx,
+            self.registry.clone(),
+            sink,
+            addresses,
+            peer,
+            access_control.sender_incoming_access_control,
+        )
+        .await?;
+
+        UdpRecvProcessor::start(
+            &self.ctx,
+            self.registry.clone(),
+            stream,
+            addresses,
+            peer,
+            access_control.receiver_outgoing_access_control,
+        )
+        .await?;
+
+        Ok(local_addr)
+    }
+}
+
+/// This trait adds a `create_udp_transport` method to any struct returning a Context.
+/// This is the case for an ockam::Node, so you can write `node.create_udp_transport()`
+#[async_trait]
+pub trait UdpTransportExtension: HasContext {
+    /// Create a UDP transport
+    async fn create_udp_transport(&self) -> Result<UdpTransport> {
+        UdpTransport::create(self.get_context()).await
+    }
+}
+
+impl<A: HasContext> UdpTransportExtension for A {}
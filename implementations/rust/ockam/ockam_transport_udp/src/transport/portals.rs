@@ -0,0 +1,90 @@
+use crate::portal::{UdpInletListenProcessor, UdpOutletListenWorker};
+use crate::{resolve_peer, UdpInletOptions, UdpOutletOptions, UdpTransport};
+use ockam_core::{Address, Result, Route};
+use ockam_transport_core::TransportError;
+use std::net::SocketAddr;
+
+impl UdpTransport {
+    /// Create a UDP Inlet that listens on `bind_addr`, and forwards the datagrams of every
+    /// client to the Outlet at `outlet_route` as a separate session. Datagrams sent back by the
+    /// Outlet are sent to the client of that session. Sessions are closed after being idle for
+    /// the timeout configured in [`UdpInletOptions`].
+    /// Pair of corresponding Inlet and Outlet is called Portal.
+    ///
+    /// Returns the socket address that the Inlet is bound to, and the address of its listener.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpInletOptions, UdpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::{route, Result};
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// let (_, inlet) = udp.create_inlet("127.0.0.1:5353", route!["outlet"], UdpInletOptions::new()).await?;
+    /// # udp.stop_inlet(inlet).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create_inlet(
+        &self,
+        bind_addr: impl AsRef<str>,
+        outlet_route: impl Into<Route>,
+        options: UdpInletOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let bind_addr = bind_addr
+            .as_ref()
+            .parse()
+            .map_err(|_| TransportError::InvalidAddress)?;
+
+        UdpInletListenProcessor::start(
+            &self.ctx,
+            self.registry.clone(),
+            outlet_route.into(),
+            bind_addr,
+            options,
+        )
+        .await
+    }
+
+    /// Stop the UDP Inlet at `addr`, and all its sessions
+    pub async fn stop_inlet(&self, addr: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_processor(addr).await
+    }
+
+    /// Create a UDP Outlet listener at `address`. For every Inlet session it receives a ping
+    /// from, the Outlet binds a new local socket, sends it the datagrams of that session, and
+    /// sends back the datagrams that `peer` answers with.
+    /// Pair of corresponding Inlet and Outlet is called Portal.
+    ///
+    /// ```rust
+    /// use ockam_transport_udp::{UdpOutletOptions, UdpTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let udp = UdpTransport::create(&ctx).await?;
+    /// udp.create_outlet("outlet", "127.0.0.1:53", UdpOutletOptions::new()).await?;
+    /// # udp.stop_outlet("outlet").await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create_outlet(
+        &self,
+        address: impl Into<Address>,
+        peer: impl Into<String>,
+        options: UdpOutletOptions,
+    ) -> Result<()> {
+        let peer = resolve_peer(peer.into())?;
+
+        UdpOutletListenWorker::start(
+            &self.ctx,
+            self.registry.clone(),
+            address.into(),
+            peer,
+            options,
+        )
+        .await
+    }
+
+    /// Stop the UDP Outlet listener at `addr`. Its running sessions are closed
+    /// once they become idle
+    pub async fn stop_outlet(&self, addr: impl Into<Address>) -> Result<()> {
+        self.ctx.stop_worker(addr).await
+    }
+}
@@ -0,0 +1,30 @@
+use crate::workers::SocketRole;
+use ockam_core::Address;
+
+#[derive(Clone, Debug)]
+pub(crate) struct Addresses {
+    /// Used to receive messages from other workers which are then serialized and sent over the wire
+    sender_address: Address,
+    /// Receiver Processor Address
+    receiver_address: Address,
+}
+
+impl Addresses {
+    pub(crate) fn generate(role: SocketRole) -> Self {
+        let role_str = role.str();
+
+        let sender_address = Address::random_tagged(&format!("UdpSendWorker_tx_addr_{}", role_str));
+        let receiver_address = Address::random_tagged(&format!("UdpRecvProcessor_{}", role_str));
+
+        Self {
+            sender_address,
+            receiver_address,
+        }
+    }
+    pub fn sender_address(&self) -> &Address {
+        &self.sender_address
+    }
+    pub fn receiver_address(&self) -> &Address {
+        &self.receiver_address
+    }
+}
@@ -0,0 +1,127 @@
+use super::{Addresses, TransportMessageCodec};
+use crate::{UdpRegistry, UDP};
+use futures_util::stream::SplitStream;
+use futures_util::StreamExt;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{
+    async_trait, route, Address, DenyAll, LocalMessage, Mailbox, Mailboxes, OutgoingAccessControl,
+    Processor, Result,
+};
+use ockam_node::{Context, ProcessorBuilder};
+use std::net::SocketAddr;
+use tokio_util::udp::UdpFramed;
+use tracing::{debug, trace, warn};
+
+/// A receiver for the UDP transport
+///
+/// This processor handles the reception of messages on a
+/// local socket.
+///
+/// When a message is received, the address of the paired sender
+/// ([`UdpSendWorker`](crate::workers::UdpSendWorker)) is injected into the message's
+/// return route so that replies are sent to the sender.
+///
+/// If the socket is connected to a single peer, datagrams coming from other
+/// peers are dropped. Otherwise the UDP address of the datagram's source is
+/// injected into the return route as well.
+pub(crate) struct UdpRecvProcessor {
+    registry: UdpRegistry,
+    /// The read half of the underlying UDP socket.
+    stream: SplitStream<UdpFramed<TransportMessageCodec>>,
+    /// Peer of a connected socket
+    peer: Option<SocketAddr>,
+    addresses: Addresses,
+}
+
+impl UdpRecvProcessor {
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: UdpRegistry,
+        stream: SplitStream<UdpFramed<TransportMessageCodec>>,
+        addresses: &Addresses,
+        peer: Option<SocketAddr>,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let processor = Self {
+            registry,
+            stream,
+            peer,
+            addresses: addresses.clone(),
+        };
+
+        let mailbox = Mailbox::new(
+            addresses.receiver_address().clone(),
+            Arc::new(DenyAll),
+            receiver_outgoing_access_control,
+        );
+        ProcessorBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![]), processor)
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Processor for UdpRecvProcessor {
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry.add_receiver_processor(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_receiver_processor(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
+        debug!("Waiting for incoming UDP datagram...");
+        let (mut msg, addr) = match self.stream.next().await {
+            Some(res) => match res {
+                Ok((msg, addr)) => (msg, addr),
+                Err(e) => {
+                    warn!(
+                        "Failed to read message, will wait for next message: {:?}",
+                        e
+                    );
+                    return Ok(true);
+                }
+            },
+            None => {
+                debug!("No message read, will wait for next message.");
+                return Ok(true);
+            }
+        };
+
+        // Set return route to go directly to paired sender
+        msg.return_route = match self.peer {
+            Some(peer) if peer != addr => {
+                trace!("Dropping datagram from {}, expected peer {}", addr, peer);
+                return Ok(true);
+            }
+            Some(_) => route![self.addresses.sender_address().clone(), msg.return_route],
+            None => route![
+                self.addresses.sender_address().clone(),
+                Address::new(UDP, addr.to_string()),
+                msg.return_route
+            ],
+        };
+
+        debug!(onward_route = %msg.onward_route,
+            return_route = %msg.return_route,
+            "Forwarding UDP message");
+        ctx.forward_from_address(
+            LocalMessage::new(msg, vec![]),
+            self.addresses.receiver_address().clone(),
+        )
+        .await?;
+
+        Ok(true)
+    }
+}
@@ -1,48 +1,82 @@
-use super::TransportMessageCodec;
-use crate::UDP;
+use super::{Addresses, TransportMessageCodec};
+use crate::{UdpRegistry, UDP};
 use futures_util::{stream::SplitSink, SinkExt};
-use ockam_core::{async_trait, Any, Result, Routed, TransportMessage, Worker};
-use ockam_node::Context;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{
+    async_trait, Any, DenyAll, IncomingAccessControl, Mailbox, Mailboxes, Result, Routed,
+    TransportMessage, Worker,
+};
+use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use std::net::{SocketAddr, ToSocketAddrs};
 use tokio_util::udp::UdpFramed;
 use tracing::{error, trace, warn};
 
+/// Role of a local UDP socket
+pub(crate) enum SocketRole {
+    /// The socket sends to, and receives from, a single peer
+    Connection,
+    /// The socket sends to, and receives from, any peer
+    Listener,
+}
+
+impl SocketRole {
+    pub(crate) fn str(&self) -> &'static str {
+        match self {
+            SocketRole::Connection => "connection",
+            SocketRole::Listener => "listener",
+        }
+    }
+}
+
 /// A sender for the UDP transport
 ///
 /// This worker handles the sending of messages on a
-/// local socket. See [`UdpRouter`](crate::router::UdpRouter) for more details.
+/// local socket.
+///
+/// If the socket is connected to a single peer, all messages are sent to that peer.
+/// Otherwise the address following the sender's address in the onward route
+/// must be the UDP address of the peer.
 pub(crate) struct UdpSendWorker {
-    /// The read half of the udnerlying UDP socket.
+    registry: UdpRegistry,
+    /// The write half of the underlying UDP socket.
     sink: SplitSink<UdpFramed<TransportMessageCodec>, (TransportMessage, SocketAddr)>,
+    /// Peer of a connected socket
+    peer: Option<SocketAddr>,
+    addresses: Addresses,
 }
 
 impl UdpSendWorker {
-    /// Create a new `UdpSendWorker`
-    pub(crate) fn new(
+    /// Start a `UdpSendWorker` which sends messages on a bound socket
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: UdpRegistry,
         sink: SplitSink<UdpFramed<TransportMessageCodec>, (TransportMessage, SocketAddr)>,
-    ) -> Self {
-        Self { sink }
-    }
-}
-
-#[async_trait]
-impl Worker for UdpSendWorker {
-    type Message = Any;
-    type Context = Context;
-
-    async fn handle_message(
-        &mut self,
-        _ctx: &mut Context,
-        msg: Routed<Self::Message>,
+        addresses: &Addresses,
+        peer: Option<SocketAddr>,
+        sender_incoming_access_control: Arc<dyn IncomingAccessControl>,
     ) -> Result<()> {
-        // Parse message and remove our address from its routing
-        let mut msg = msg.into_transport_message();
-        msg.onward_route.step()?;
+        let sender = Self {
+            registry,
+            sink,
+            peer,
+            addresses: addresses.clone(),
+        };
 
-        trace!("Sending message to {:?}", msg.onward_route);
+        let mailbox = Mailbox::new(
+            addresses.sender_address().clone(),
+            sender_incoming_access_control,
+            Arc::new(DenyAll),
+        );
+        WorkerBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![]), sender)
+            .start(ctx)
+            .await?;
 
-        // Resolve peer address to IPv4 SocketAddr(s).
+        Ok(())
+    }
+
+    /// Resolve the next hop of the onward route to an IPv4 `SocketAddr`
+    fn resolve_next_hop(msg: &mut TransportMessage) -> Result<SocketAddr> {
         let peer_addr = msg.onward_route.step()?;
 
         if peer_addr.transport_type() != UDP {
@@ -58,23 +92,66 @@ impl Worker for UdpSendWorker {
         let peer_addrs: Vec<_> = peer_addrs.filter(SocketAddr::is_ipv4).collect();
 
         // Try to send to first SocketAddr
-        let addr = match peer_addrs.first() {
-            Some(a) => *a,
+        match peer_addrs.first() {
+            Some(a) => Ok(*a),
             None => {
                 warn!("No IPv4 address resolved for peer {:?}", peer_addr);
-                return Err(TransportError::UnknownRoute.into());
+                Err(TransportError::UnknownRoute.into())
             }
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for UdpSendWorker {
+    type Message = Any;
+    type Context = Context;
+
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry
+            .add_sender_worker(self.addresses.sender_address(), self.peer.is_none());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry
+            .remove_sender_worker(self.addresses.sender_address());
+
+        let _ = ctx
+            .stop_processor(self.addresses.receiver_address().clone())
+            .await;
+
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        _ctx: &mut Context,
+        msg: Routed<Self::Message>,
+    ) -> Result<()> {
+        // Parse message and remove our address from its routing
+        let mut msg = msg.into_transport_message();
+        msg.onward_route.step()?;
+
+        trace!("Sending message to {:?}", msg.onward_route);
+
+        let addr = match self.peer {
+            Some(peer) => peer,
+            None => Self::resolve_next_hop(&mut msg)?,
         };
 
         // Error on conditions that _might_ put the sink
         // into an error state
         if addr.port() == 0 {
-            warn!(peer_addr = %peer_addr, "Will not send to address");
+            warn!(peer_addr = %addr, "Will not send to address");
             return Err(TransportError::InvalidAddress.into());
         }
 
         // Send
-        match self.sink.send((msg.clone(), addr)).await {
+        match self.sink.send((msg, addr)).await {
             Ok(()) => {
                 trace!("Successful send to {}", addr);
                 Ok(())
@@ -0,0 +1,98 @@
+use ockam_core::{route, Result};
+use ockam_node::Context;
+use ockam_transport_udp::{UdpInletOptions, UdpOutletOptions, UdpTransport};
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::UdpSocket;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Start a UDP service answering every datagram with the same datagram
+async fn start_echo_server() -> SocketAddr {
+    let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let addr = socket.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut buf = [0u8; 1024];
+        loop {
+            let (len, peer) = socket.recv_from(&mut buf).await.unwrap();
+            socket.send_to(&buf[..len], peer).await.unwrap();
+        }
+    });
+    addr
+}
+
+async fn send_receive(client: &UdpSocket, inlet: SocketAddr, datagram: &[u8]) {
+    client.send_to(datagram, inlet).await.unwrap();
+    let mut buf = [0u8; 1024];
+    let (len, _) = tokio::time::timeout(TIMEOUT, client.recv_from(&mut buf))
+        .await
+        .expect("Should receive a reply before the timeout")
+        .unwrap();
+    assert_eq!(&buf[..len], datagram);
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 10000)]
+async fn portal__datagrams_of_several_clients__should_be_echoed(ctx: &mut Context) -> Result<()> {
+    let server = start_echo_server().await;
+
+    let udp = UdpTransport::create(ctx).await?;
+    udp.create_outlet("outlet", server.to_string(), UdpOutletOptions::new())
+        .await?;
+    let (inlet, _) = udp
+        .create_inlet("127.0.0.1:0", route!["outlet"], UdpInletOptions::new())
+        .await?;
+
+    let client1 = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let client2 = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+    // Datagrams are kept as they are, and every client gets its own replies
+    send_receive(&client1, inlet, b"first datagram").await;
+    send_receive(&client2, inlet, b"second").await;
+    send_receive(&client1, inlet, b"third").await;
+
+    // One Inlet and one Outlet session per client
+    assert_eq!(udp.registry().get_all_portal_workers().len(), 4);
+
+    ctx.stop().await
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test(timeout = 10000)]
+async fn portal__idle_session__should_expire(ctx: &mut Context) -> Result<()> {
+    let server = start_echo_server().await;
+
+    let udp = UdpTransport::create(ctx).await?;
+    udp.create_outlet("outlet", server.to_string(), UdpOutletOptions::new())
+        .await?;
+    let (inlet, inlet_address) = udp
+        .create_inlet(
+            "127.0.0.1:0",
+            route!["outlet"],
+            UdpInletOptions::new().with_idle_timeout(Duration::from_millis(500)),
+        )
+        .await?;
+
+    let client = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    send_receive(&client, inlet, b"ping").await;
+    assert_eq!(udp.registry().get_all_portal_workers().len(), 2);
+
+    // Both sides of the session are closed once it expires
+    ctx.sleep(Duration::from_millis(2000)).await;
+    assert!(udp.registry().get_all_portal_workers().is_empty());
+
+    // A new session is started for the next datagram
+    send_receive(&client, inlet, b"ping again").await;
+    assert_eq!(udp.registry().get_all_portal_workers().len(), 2);
+
+    udp.stop_inlet(inlet_address).await?;
+    udp.stop_outlet("outlet").await?;
+    ctx.sleep(Duration::from_millis(100)).await;
+    assert!(udp
+        .registry()
+        .get_all_inlet_listener_processors()
+        .is_empty());
+    assert!(udp.registry().get_all_outlet_listener_workers().is_empty());
+
+    ctx.stop().await
+}
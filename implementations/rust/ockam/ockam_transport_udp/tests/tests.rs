@@ -1,7 +1,7 @@
 use ockam_core::compat::rand::{self, Rng};
 use ockam_core::{route, Address, AllowAll, Result, Routed, Worker};
 use ockam_node::{Context, MessageReceiveOptions, MessageSendReceiveOptions};
-use ockam_transport_udp::{UdpTransport, UDP};
+use ockam_transport_udp::{UdpConnectionOptions, UdpListenerOptions, UdpTransport, UDP};
 use std::net::SocketAddr;
 use std::time::Duration;
 use tracing::{debug, error, trace};
@@ -25,12 +25,17 @@ async fn reply_from_correct_server_port(ctx: &mut Context) -> Result<()> {
     {
         ctx.start_worker("echoer", Echoer::new(), AllowAll, AllowAll)
             .await?;
-        transport.listen(bind_addr.to_string()).await?;
+        transport
+            .listen(bind_addr.to_string(), UdpListenerOptions::new())
+            .await?;
     };
 
     // Sender
     {
-        let route = route![(UDP, bind_addr.to_string()), "echoer"];
+        let (_, sender) = transport
+            .listen("127.0.0.1:0", UdpListenerOptions::new())
+            .await?;
+        let route = route![sender, (UDP, bind_addr.to_string()), "echoer"];
         let mut child_ctx = ctx
             .new_detached(Address::random_tagged("App.detached"), AllowAll, AllowAll)
             .await?;
@@ -82,10 +87,15 @@ async fn recover_from_sender_error(ctx: &mut Context) -> Result<()> {
     // Listener
     ctx.start_worker("echoer", Echoer::new(), AllowAll, AllowAll)
         .await?;
-    transport.listen(addr_ok.clone()).await?;
+    transport
+        .listen(addr_ok.clone(), UdpListenerOptions::new())
+        .await?;
+    let (_, sender) = transport
+        .listen("127.0.0.1:0", UdpListenerOptions::new())
+        .await?;
 
     // Send message to try and cause a socket send error
-    let r = route![(UDP, addr_nok), "echoer"];
+    let r = route![sender.clone(), (UDP, addr_nok), "echoer"];
     let res: Result<Routed<String>> = ctx
         .send_and_receive_extended(
             r,
@@ -96,7 +106,7 @@ async fn recover_from_sender_error(ctx: &mut Context) -> Result<()> {
     assert!(res.is_err(), "Expected an error sending");
 
     // Send message to working peer
-    let r = route![(UDP, addr_ok), "echoer"];
+    let r = route![sender, (UDP, addr_ok), "echoer"];
     let res: Result<Routed<String>> = ctx
         .send_and_receive_extended(
             r,
@@ -128,13 +138,18 @@ async fn send_from_same_client_port(ctx: &mut Context) -> Result<()> {
     ctx.start_worker("echoer", Echoer::new(), AllowAll, AllowAll)
         .await?;
     for addr in &bind_addrs {
-        transport.listen(addr.to_string()).await?;
+        transport
+            .listen(addr.to_string(), UdpListenerOptions::new())
+            .await?;
     }
 
-    // Send messages
+    // Send messages from the same local socket
+    let (_, sender) = transport
+        .listen("127.0.0.1:0", UdpListenerOptions::new())
+        .await?;
     for addr in &bind_addrs {
         let msg = String::from("Ockam. Testing. 1, 2, 3...");
-        let r = route![(UDP, addr.to_string()), "echoer"];
+        let r = route![sender.clone(), (UDP, addr.to_string()), "echoer"];
         let reply = ctx
             .send_and_receive_extended::<String>(
                 r,
@@ -167,18 +182,23 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     {
         ctx.start_worker("echoer", Echoer::new(), AllowAll, AllowAll)
             .await?;
-        transport.listen(bind_addr.clone()).await?;
+        transport
+            .listen(bind_addr.clone(), UdpListenerOptions::new())
+            .await?;
     };
 
     // Sender
     {
+        let sender = transport
+            .connect(bind_addr.clone(), UdpConnectionOptions::new())
+            .await?;
         for _ in 0..3 {
             let msg: String = rand::thread_rng()
                 .sample_iter(&rand::distributions::Alphanumeric)
                 .take(256)
                 .map(char::from)
                 .collect();
-            let r = route![(UDP, bind_addr.clone()), "echoer"];
+            let r = route![sender.clone(), "echoer"];
             let reply = ctx
                 .send_and_receive_extended::<String>(
                     r,
@@ -196,6 +216,62 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
     Ok(())
 }
 
+#[ockam_macros::test]
+async fn disconnect_and_stop_listener(ctx: &mut Context) -> Result<()> {
+    // Transport
+    let transport = UdpTransport::create(ctx).await?;
+
+    // Listener
+    ctx.start_worker("echoer", Echoer::new(), AllowAll, AllowAll)
+        .await?;
+    let (bind_addr, listener) = transport
+        .listen("127.0.0.1:0", UdpListenerOptions::new())
+        .await?;
+
+    // Connection
+    let sender = transport
+        .connect(bind_addr.to_string(), UdpConnectionOptions::new())
+        .await?;
+    let reply = ctx
+        .send_and_receive_extended::<String>(
+            route![sender.clone(), "echoer"],
+            String::from("Hola"),
+            MessageSendReceiveOptions::new().with_timeout(TIMEOUT),
+        )
+        .await?
+        .body();
+    assert_eq!(reply, "Hola");
+    assert_eq!(
+        transport.registry().get_all_listeners(),
+        vec![listener.clone()]
+    );
+    assert_eq!(transport.registry().get_all_sender_workers().len(), 2);
+    assert_eq!(transport.registry().get_all_receiver_processors().len(), 2);
+
+    // Disconnect
+    transport.disconnect(&sender).await?;
+    ctx.sleep(Duration::from_millis(100)).await;
+    let res = ctx
+        .send(route![sender, "echoer"], String::from("Hola"))
+        .await;
+    assert!(res.is_err(), "Should not send messages after disconnection");
+    assert_eq!(transport.registry().get_all_sender_workers().len(), 1);
+    assert_eq!(transport.registry().get_all_receiver_processors().len(), 1);
+
+    // Stop listener
+    transport.stop_listener(&listener).await?;
+    ctx.sleep(Duration::from_millis(100)).await;
+    assert!(transport.registry().get_all_listeners().is_empty());
+    assert!(transport.registry().get_all_sender_workers().is_empty());
+    assert!(transport
+        .registry()
+        .get_all_receiver_processors()
+        .is_empty());
+
+    ctx.stop().await?;
+    Ok(())
+}
+
 pub struct Echoer {
     prev_src_addr: Option<String>,
 }
@@ -8,16 +8,20 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate core;
 
-mod router;
+mod options;
+mod registry;
 mod transport;
 mod workers;
 use tokio::net::unix::SocketAddr as TokioSocketAddr;
 use tracing::error;
+
+pub use options::*;
+pub use registry::*;
 pub use transport::*;
 
 use std::os::unix::net::SocketAddr;
 
-use ockam_core::{Address, Result, TransportType};
+use ockam_core::{Result, TransportType};
 use ockam_transport_core::TransportError;
 
 // TODO: Should have documentation or enum for this
@@ -49,26 +53,6 @@ fn std_socket_addr_from_tokio(sock_addr: &TokioSocketAddr) -> Result<SocketAddr>
     Ok(sock)
 }
 
-fn address_from_socket_addr(sock_addr: &SocketAddr) -> Result<Address> {
-    let path = match sock_addr.as_pathname() {
-        Some(p) => p,
-        None => {
-            return Err(TransportError::InvalidAddress.into());
-        }
-    };
-
-    let path_str = match path.to_str() {
-        Some(s) => s,
-        None => {
-            return Err(TransportError::InvalidAddress.into());
-        }
-    };
-
-    let address: Address = format!("{UDS}#{path_str}").into();
-
-    Ok(address)
-}
-
 #[test]
 fn test_parse_socket_address() {
     let result = parse_socket_addr("/tmp/sock");
@@ -0,0 +1,95 @@
+use crate::workers::Addresses;
+use ockam_core::flow_control::{FlowControlId, FlowControls};
+use ockam_core::Result;
+use ockam_transport_core::{ConnectionAccessControl, ConnectionFlowControl, ListenerFlowControl};
+
+/// Trust Options for a UDS connection
+#[derive(Clone, Debug)]
+pub struct UdsConnectionOptions {
+    pub(crate) flow_control: ConnectionFlowControl,
+}
+
+impl UdsConnectionOptions {
+    /// This constructor is insecure, because outgoing messages from such connections will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    pub fn insecure() -> Self {
+        Self {
+            flow_control: ConnectionFlowControl::insecure(),
+        }
+    }
+
+    /// This constructor is insecure, because outgoing messages from such connection will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            flow_control: ConnectionFlowControl::insecure(),
+        }
+    }
+
+    /// Mark this Uds Receivers as a Producer for a given [`FlowControlId`]
+    pub fn as_producer(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
+        Self {
+            flow_control: ConnectionFlowControl::producer(flow_controls, flow_control_id),
+        }
+    }
+
+    pub(crate) fn setup_flow_control(&self, addresses: &Addresses) {
+        self.flow_control
+            .setup(addresses.receiver_address(), addresses.sender_address())
+    }
+
+    pub(crate) fn create_access_control(self) -> ConnectionAccessControl {
+        self.flow_control.access_control()
+    }
+}
+
+/// Trust Options for a UDS listener
+#[derive(Debug)]
+pub struct UdsListenerOptions {
+    pub(crate) flow_control: ListenerFlowControl,
+}
+
+impl UdsListenerOptions {
+    /// This constructor is insecure, because outgoing messages from such connections will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    pub fn insecure() -> Self {
+        Self {
+            flow_control: ListenerFlowControl::insecure(),
+        }
+    }
+
+    /// This constructor is insecure, because outgoing messages from such connections will not be
+    /// restricted and can reach any [`Address`](ockam_core::Address) on this node.
+    /// Should only be used for testing purposes
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            flow_control: ListenerFlowControl::insecure(),
+        }
+    }
+
+    /// Mark this Uds Listener as a Spawner with given [`FlowControlId`].
+    /// NOTE: Spawned connections get fresh random [`FlowControlId`], however they are still marked
+    /// with Spawner's [`FlowControlId`]
+    pub fn as_spawner(flow_controls: &FlowControls, flow_control_id: &FlowControlId) -> Self {
+        Self {
+            flow_control: ListenerFlowControl::spawner(flow_controls, flow_control_id),
+        }
+    }
+
+    pub(crate) fn setup_flow_control(&self, addresses: &Addresses) -> Option<FlowControlId> {
+        self.flow_control
+            .setup(addresses.receiver_address(), addresses.sender_address())
+    }
+
+    pub(crate) fn create_access_control(
+        &self,
+        flow_control_id: Option<FlowControlId>,
+    ) -> Result<ConnectionAccessControl> {
+        self.flow_control.access_control(flow_control_id)
+    }
+}
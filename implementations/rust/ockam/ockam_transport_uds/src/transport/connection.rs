@@ -0,0 +1,70 @@
+use crate::workers::{Addresses, ConnectionRole, UdsRecvProcessor, UdsSendWorker};
+use crate::{parse_socket_addr, UdsConnectionOptions, UdsTransport};
+use ockam_core::{Address, Result};
+
+impl UdsTransport {
+    /// Establish an outgoing UDS connection.
+    ///
+    /// ```rust
+    /// use ockam_transport_uds::{UdsConnectionOptions, UdsTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let uds = UdsTransport::create(&ctx).await?;
+    /// let addr = uds.connect("/tmp/socket-name", UdsConnectionOptions::new()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect(
+        &self,
+        peer: impl AsRef<str>,
+        options: UdsConnectionOptions,
+    ) -> Result<Address> {
+        let peer = parse_socket_addr(peer)?;
+
+        let (read_half, write_half) = UdsSendWorker::connect(&peer).await?;
+
+        let addresses = Addresses::generate(ConnectionRole::Initiator);
+
+        options.setup_flow_control(&addresses);
+        let access_control = options.create_access_control();
+
+        UdsSendWorker::start(
+            &self.ctx,
+            self.registry.clone(),
+            write_half,
+            &addresses,
+            peer.clone(),
+            access_control.sender_incoming_access_control,
+        )
+        .await?;
+
+        UdsRecvProcessor::start(
+            &self.ctx,
+            self.registry.clone(),
+            read_half,
+            &addresses,
+            peer,
+            access_control.receiver_outgoing_access_control,
+        )
+        .await?;
+
+        Ok(addresses.sender_address().clone())
+    }
+
+    /// Interrupt an active UDS connection given its `Address`
+    ///
+    /// ```rust
+    /// use ockam_transport_uds::{UdsConnectionOptions, UdsTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let uds = UdsTransport::create(&ctx).await?;
+    /// let addr = uds.connect("/tmp/socket-name", UdsConnectionOptions::new()).await?;
+    ///
+    /// uds.disconnect(&addr).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn disconnect(&self, address: &Address) -> Result<()> {
+        self.ctx.stop_worker(address.clone()).await
+    }
+}
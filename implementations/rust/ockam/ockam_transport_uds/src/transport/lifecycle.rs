@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use ockam_core::flow_control::FlowControls;
+use ockam_core::{async_trait, Address, AsyncTryClone, Result, TransportType};
+use ockam_node::Context;
+use ockam_transport_core::{unsupported_address_error, Transport};
+
+use crate::{UdsConnectionOptions, UdsRegistry, UdsTransport, UDS};
+
+impl UdsTransport {
+    /// Create a UDS transport
+    ///
+    /// ```rust
+    /// use ockam_transport_uds::UdsTransport;
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let uds = UdsTransport::create(&ctx).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create(ctx: &Context) -> Result<Self> {
+        let uds = Self {
+            ctx: ctx.async_try_clone().await?,
+            registry: UdsRegistry::default(),
+        };
+        // make the UDS transport available in the list of supported transports for
+        // later address resolution when socket paths will need to be instantiated as UDS
+        // worker addresses
+        ctx.register_transport(Arc::new(uds.async_try_clone().await?));
+        Ok(uds)
+    }
+}
+
+impl UdsTransport {
+    /// Getter
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+    /// Registry of all active connections
+    pub fn registry(&self) -> &UdsRegistry {
+        &self.registry
+    }
+}
+
+#[async_trait]
+impl Transport for UdsTransport {
+    fn transport_type(&self) -> TransportType {
+        UDS
+    }
+
+    async fn resolve_address(
+        &self,
+        flow_controls: &FlowControls,
+        address: Address,
+    ) -> Result<Address> {
+        if address.transport_type() == UDS {
+            let id = flow_controls.generate_id();
+            let options = UdsConnectionOptions::as_producer(flow_controls, &id);
+            Ok(self.connect(address.address(), options).await?)
+        } else {
+            Err(unsupported_address_error("UDS", &address))
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use std::os::unix::net::SocketAddr;
+
+use crate::workers::UdsListenProcessor;
+use crate::{parse_socket_addr, UdsListenerOptions, UdsTransport};
+use ockam_core::{Address, Result};
+
+impl UdsTransport {
+    /// Start listening to incoming connections on the given socket path
+    ///
+    /// Returns the socket address that this transport is bound to, and the address of the
+    /// listener processor.
+    ///
+    /// ```rust
+    /// use ockam_transport_uds::{UdsListenerOptions, UdsTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let uds = UdsTransport::create(&ctx).await?;
+    /// uds.listen("/tmp/socket-name", UdsListenerOptions::new()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn listen(
+        &self,
+        bind_addr: impl AsRef<str>,
+        options: UdsListenerOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let bind_addr = parse_socket_addr(bind_addr)?;
+        UdsListenProcessor::start(&self.ctx, self.registry.clone(), bind_addr, options).await
+    }
+
+    /// Interrupt an active UDS listener given its `Address`
+    pub async fn stop_listener(&self, address: &Address) -> Result<()> {
+        self.ctx.stop_processor(address.clone()).await
+    }
+}
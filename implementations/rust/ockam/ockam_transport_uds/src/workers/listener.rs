@@ -1,24 +1,23 @@
 use std::os::unix::net::SocketAddr;
 
-use ockam_core::{
-    async_trait, compat::sync::Arc, Address, AllowSourceAddress, AsyncTryClone, DenyAll, Mailbox,
-    Mailboxes, Processor, Result,
-};
-
-use ockam_node::{Context, ProcessorBuilder, WorkerBuilder};
+use ockam_core::{async_trait, Address, DenyAll, Processor, Result};
+use ockam_node::Context;
 use ockam_transport_core::TransportError;
 use tokio::net::UnixListener;
-use tracing::{debug, error, trace};
+use tracing::{debug, error};
 
-use crate::{router::UdsRouterHandle, std_socket_addr_from_tokio, workers::UdsSendWorker};
+use crate::workers::{Addresses, ConnectionRole, UdsRecvProcessor, UdsSendWorker};
+use crate::{std_socket_addr_from_tokio, UdsListenerOptions, UdsRegistry};
 
 /// A UDS Listener Processor
 ///
 /// UDS Listen processors are created by `UdsTransport`
-/// after a call is made to [`UdsTransport::listen`](crate::transport::UdsTransport)
+/// after a call is made to [`UdsTransport::listen`](crate::UdsTransport::listen)
 pub(crate) struct UdsListenProcessor {
+    registry: UdsRegistry,
     inner: UnixListener,
-    router_handle: UdsRouterHandle,
+    socket_addr: SocketAddr,
+    options: UdsListenerOptions,
 }
 
 impl UdsListenProcessor {
@@ -27,9 +26,10 @@ impl UdsListenProcessor {
     /// Starts a [`Processor`] which listens for incoming connections to accept.
     pub(crate) async fn start(
         ctx: &Context,
-        router_handle: UdsRouterHandle,
+        registry: UdsRegistry,
         addr: SocketAddr,
-    ) -> Result<SocketAddr> {
+        options: UdsListenerOptions,
+    ) -> Result<(SocketAddr, Address)> {
         let path = match addr.as_pathname() {
             Some(p) => p,
             None => {
@@ -42,19 +42,22 @@ impl UdsListenProcessor {
 
         let tokio_sock_addr = inner.local_addr().map_err(TransportError::from)?;
 
-        let std_sock_addr = std_socket_addr_from_tokio(&tokio_sock_addr)?;
+        let socket_addr = std_socket_addr_from_tokio(&tokio_sock_addr)?;
+
+        let address = Address::random_tagged("UdsListenProcessor");
+        options.flow_control.add_spawner(&address);
 
         let processor = Self {
+            registry,
             inner,
-            router_handle,
+            socket_addr: socket_addr.clone(),
+            options,
         };
 
-        let mailbox = Mailbox::deny_all(Address::random_tagged("UdsListenProcessor"));
-        ProcessorBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![]), processor)
-            .start(ctx)
+        ctx.start_processor(address.clone(), processor, DenyAll, DenyAll)
             .await?;
 
-        Ok(std_sock_addr)
+        Ok((socket_addr, address))
     }
 }
 
@@ -63,12 +66,22 @@ impl Processor for UdsListenProcessor {
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
-        ctx.set_cluster(crate::CLUSTER_NAME).await
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry.add_listener_processor(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_listener_processor(&ctx.address());
+
+        Ok(())
     }
 
     /// Listen for and accept incoming UDS connections.
     ///
-    /// Register the peers socket address, and create a worker to communicate with the peer.
+    /// Create a pair of workers to communicate with the peer.
     async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
         debug!("Waiting for incoming UDS connection...");
 
@@ -76,38 +89,36 @@ impl Processor for UdsListenProcessor {
         let (stream, _peer) = self.inner.accept().await.map_err(TransportError::from)?;
         debug!("UDS connection accepted");
 
-        // Create a connection working
-        let handle_clone = self.router_handle.async_try_clone().await?;
-        let local_addr = stream.local_addr().map_err(TransportError::from)?;
-        let std_sock_addr = std_socket_addr_from_tokio(&local_addr)?;
-        let (send_worker, pair) =
-            UdsSendWorker::new_pair(handle_clone, Some(stream), std_sock_addr, vec![]).await?;
-
-        self.router_handle.register(&pair).await?;
-        debug!("UDS connection registered");
-
-        trace! {
-            tx_addr = %pair.tx_addr(),
-            int_addr = %send_worker.internal_addr(),
-            "starting UDS connection worker"
-        };
-
-        let tx_mailbox = Mailbox::new(
-            pair.tx_addr(),
-            Arc::new(AllowSourceAddress(self.router_handle.main_addr().clone())),
-            Arc::new(DenyAll),
-        );
-
-        let internal_mailbox = Mailbox::new(
-            send_worker.internal_addr().clone(),
-            Arc::new(AllowSourceAddress(send_worker.rx_addr().clone())),
-            Arc::new(DenyAll),
-        );
-
-        let mailboxes = Mailboxes::new(tx_mailbox, vec![internal_mailbox]);
-        WorkerBuilder::with_mailboxes(mailboxes, send_worker)
-            .start(ctx)
-            .await?;
+        let addresses = Addresses::generate(ConnectionRole::Responder);
+
+        let flow_control_id = self.options.setup_flow_control(&addresses);
+        let access_control = self
+            .options
+            .create_access_control(flow_control_id.clone())?;
+
+        let (read_half, write_half) = stream.into_split();
+
+        // Worker to receive messages from the Node and send them over the wire
+        UdsSendWorker::start(
+            ctx,
+            self.registry.clone(),
+            write_half,
+            &addresses,
+            self.socket_addr.clone(),
+            access_control.sender_incoming_access_control,
+        )
+        .await?;
+
+        // Processor to receive messages over the wire and forward them to the node
+        UdsRecvProcessor::start(
+            ctx,
+            self.registry.clone(),
+            read_half,
+            &addresses,
+            self.socket_addr.clone(),
+            access_control.receiver_outgoing_access_control,
+        )
+        .await?;
 
         Ok(true)
     }
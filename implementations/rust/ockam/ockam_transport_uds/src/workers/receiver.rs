@@ -1,9 +1,13 @@
-use crate::workers::UdsSendWorkerMsg;
+use std::os::unix::net::SocketAddr;
 
+use crate::workers::{Addresses, UdsSendWorkerMsg};
+use crate::UdsRegistry;
+use ockam_core::compat::sync::Arc;
 use ockam_core::{
-    async_trait, Address, Decodable, LocalMessage, Processor, Result, TransportMessage,
+    async_trait, AllowOnwardAddress, Decodable, DenyAll, LocalMessage, Mailbox, Mailboxes,
+    OutgoingAccessControl, Processor, Result, TransportMessage,
 };
-use ockam_node::Context;
+use ockam_node::{Context, ProcessorBuilder};
 use ockam_transport_core::TransportError;
 use tokio::{io::AsyncReadExt, net::unix::OwnedReadHalf};
 use tracing::{error, info, trace};
@@ -11,25 +15,72 @@ use tracing::{error, info, trace};
 /// A UDS receiving message processor
 ///
 /// Create this processor type by calling
-/// [`UdsSendWorker::start_pair`](crate::workers::UdsSendWorker)
+/// [`UdsRecvProcessor::start`](crate::workers::UdsRecvProcessor::start)
 ///
 /// This half of the worker is created when spawning a new connection
 /// worker pair, and listens for UDS packets which are relayed into
 /// the node messaging system.
 pub(crate) struct UdsRecvProcessor {
-    rx: OwnedReadHalf,
-    peer_addr: Address,
-    sender_internal_address: Address,
+    registry: UdsRegistry,
+    read_half: OwnedReadHalf,
+    peer: SocketAddr,
+    addresses: Addresses,
 }
 
 impl UdsRecvProcessor {
-    pub fn new(rx: OwnedReadHalf, peer_addr: Address, sender_internal_address: Address) -> Self {
+    /// Create a new `UdsRecvProcessor`
+    fn new(
+        registry: UdsRegistry,
+        read_half: OwnedReadHalf,
+        peer: SocketAddr,
+        addresses: Addresses,
+    ) -> Self {
         Self {
-            rx,
-            peer_addr,
-            sender_internal_address,
+            registry,
+            read_half,
+            peer,
+            addresses,
         }
     }
+
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: UdsRegistry,
+        read_half: OwnedReadHalf,
+        addresses: &Addresses,
+        peer: SocketAddr,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let receiver = UdsRecvProcessor::new(registry, read_half, peer, addresses.clone());
+
+        let mailbox = Mailbox::new(
+            addresses.receiver_address().clone(),
+            Arc::new(DenyAll),
+            receiver_outgoing_access_control,
+        );
+        let internal = Mailbox::new(
+            addresses.receiver_internal_address().clone(),
+            Arc::new(DenyAll),
+            Arc::new(AllowOnwardAddress(
+                addresses.sender_internal_address().clone(),
+            )),
+        );
+        ProcessorBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![internal]), receiver)
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Notify the sender that the connection is closed
+    async fn notify_connection_closed(&self, ctx: &Context) -> Result<()> {
+        ctx.send_from_address(
+            self.addresses.sender_internal_address().clone(),
+            UdsSendWorkerMsg::ConnectionClosed,
+            self.addresses.receiver_internal_address().clone(),
+        )
+        .await
+    }
 }
 
 #[async_trait]
@@ -37,28 +88,32 @@ impl Processor for UdsRecvProcessor {
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
-        ctx.set_cluster(crate::CLUSTER_NAME).await
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry.add_receiver_processor(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_receiver_processor(&ctx.address());
+
+        Ok(())
     }
 
     /// Get the next message from the connection if there are any
     /// available and forward it to the next hop in the route.
     async fn process(&mut self, ctx: &mut Context) -> Result<bool> {
-        // Run in a loop until UdsWorkerPair::stop() is called
         // First read a message length header...
-        let len = match self.rx.read_u16().await {
+        let len = match self.read_half.read_u16().await {
             Ok(len) => len,
             Err(_e) => {
                 info!(
-                    "Connection to peer '{}' was closed; dropping stream",
-                    self.peer_addr
+                    "Connection to peer '{:?}' was closed; dropping stream",
+                    self.peer
                 );
 
-                // Notify sender tx is closed
-                ctx.send(
-                    self.sender_internal_address.clone(),
-                    UdsSendWorkerMsg::ConnectionClosed,
-                )
-                .await?;
+                self.notify_connection_closed(ctx).await?;
 
                 return Ok(false);
             }
@@ -70,7 +125,7 @@ impl Processor for UdsRecvProcessor {
         let mut buf = vec![0; len as usize];
 
         // Then read into the buffer
-        match self.rx.read_exact(&mut buf).await {
+        match self.read_half.read_exact(&mut buf).await {
             Ok(_) => {}
             _ => {
                 error!("Failed to receive message of length: {}", len);
@@ -83,19 +138,25 @@ impl Processor for UdsRecvProcessor {
 
         // Heartbeat message
         if msg.onward_route.next().is_err() {
-            trace!("Got heartbeat message from: {}", self.peer_addr);
+            trace!("Got heartbeat message from: {:?}", self.peer);
             return Ok(true);
         }
 
-        // Insert the peer address into the return route so that
+        // Insert the sender address into the return route so that
         // reply routing can be properly resolved
-        msg.return_route.modify().prepend(self.peer_addr.clone());
+        msg.return_route
+            .modify()
+            .prepend(self.addresses.sender_address().clone());
 
         trace!("Message onward route: {}", msg.onward_route);
         trace!("Message return route: {}", msg.return_route);
 
         // Forward the message to the next hop in the route
-        ctx.forward(LocalMessage::new(msg, vec![])).await?;
+        ctx.forward_from_address(
+            LocalMessage::new(msg, vec![]),
+            self.addresses.receiver_address().clone(),
+        )
+        .await?;
 
         Ok(true)
     }
@@ -1,10 +1,13 @@
 use std::os::unix::net::SocketAddr;
 
+use crate::workers::Addresses;
+use crate::UdsRegistry;
+use ockam_core::AllowSourceAddress;
 use ockam_core::{
-    async_trait, compat::sync::Arc, Address, Any, Decodable, DenyAll, Encodable, LocalMessage,
-    LocalOnwardOnly, Mailbox, Mailboxes, Message, Result, Routed, TransportMessage, Worker,
+    async_trait, compat::sync::Arc, Any, Decodable, DenyAll, Encodable, IncomingAccessControl,
+    Mailbox, Mailboxes, Message, Result, Routed, TransportMessage, Worker,
 };
-use ockam_node::{Context, ProcessorBuilder, WorkerBuilder};
+use ockam_node::{Context, WorkerBuilder};
 use ockam_transport_core::TransportError;
 use serde::{Deserialize, Serialize};
 use socket2::SockRef;
@@ -15,234 +18,162 @@ use tokio::{
         UnixStream,
     },
 };
-use tracing::{debug, error, trace, warn};
+use tracing::{debug, error, info, trace, warn};
 
-use crate::router::UdsRouterHandle;
-
-use super::UdsRecvProcessor;
-
-/// Provides the transmit and Socket Addr of a UDS connection
-#[derive(Debug)]
-pub(crate) struct WorkerPair {
-    paths: Vec<String>,
-    peer: SocketAddr,
-    tx_addr: Address,
+#[derive(Serialize, Deserialize, Message, Clone)]
+pub(crate) enum UdsSendWorkerMsg {
+    ConnectionClosed,
 }
 
-impl WorkerPair {
-    /// Returns a reference to the peers pathnames
-    pub fn paths(&self) -> &[String] {
-        &self.paths
-    }
-
-    /// Return a reference to the peers SocketAddr
-    pub fn peer(&self) -> &SocketAddr {
-        &self.peer
-    }
-
-    /// Return a clone of the transmit
-    pub fn tx_addr(&self) -> Address {
-        self.tx_addr.clone()
-    }
+pub(crate) enum ConnectionRole {
+    Initiator,
+    Responder,
 }
 
-#[derive(Serialize, Deserialize, Message, Clone)]
-pub(crate) enum UdsSendWorkerMsg {
-    ConnectionClosed,
+impl ConnectionRole {
+    pub(crate) fn str(&self) -> &'static str {
+        match self {
+            ConnectionRole::Initiator => "initiator",
+            ConnectionRole::Responder => "responder",
+        }
+    }
 }
 
+/// A UDS sending message worker
+///
+/// Create this worker type by calling
+/// [`UdsSendWorker::start`](crate::workers::UdsSendWorker::start)
+///
+/// This half of the worker is created when spawning a new connection
+/// worker pair, and listens for messages from the node message system
+/// to dispatch to a remote peer.
 pub(crate) struct UdsSendWorker {
-    router_handle: UdsRouterHandle,
-    rx: Option<OwnedReadHalf>,
-    tx: Option<OwnedWriteHalf>,
+    registry: UdsRegistry,
+    write_half: OwnedWriteHalf,
     peer: SocketAddr,
-    internal_addr: Address,
-    rx_addr: Address,
+    addresses: Addresses,
     rx_should_be_stopped: bool,
 }
 
 impl UdsSendWorker {
-    /// Create a new [`UdsSendWorker`]
+    /// Create a new `UdsSendWorker`
     fn new(
-        router_handle: UdsRouterHandle,
-        stream: Option<UnixStream>,
+        registry: UdsRegistry,
+        write_half: OwnedWriteHalf,
         peer: SocketAddr,
-        internal_addr: Address,
-        rx_addr: Address,
+        addresses: Addresses,
     ) -> Self {
-        let (rx, tx) = match stream {
-            Some(s) => {
-                let (rx, tx) = s.into_split();
-                (Some(rx), Some(tx))
-            }
-            None => (None, None),
-        };
-
         Self {
-            router_handle,
-            rx,
-            tx,
+            registry,
+            write_half,
             peer,
-            internal_addr,
-            rx_addr,
+            addresses,
             rx_should_be_stopped: true,
         }
     }
 
-    pub(crate) fn internal_addr(&self) -> &Address {
-        &self.internal_addr
-    }
-
-    /// Returns a reference to the [`Receiver Process Address`](ockam_core::Address)
-    pub(crate) fn rx_addr(&self) -> &Address {
-        &self.rx_addr
-    }
-
-    /// Create a ([`UdsSendWorker`],[`WorkerPair`]) without spawning the worker.
-    pub(crate) async fn new_pair(
-        router_handle: UdsRouterHandle,
-        stream: Option<UnixStream>,
-        peer: SocketAddr,
-        pathnames: Vec<String>,
-    ) -> Result<(Self, WorkerPair)> {
-        let role_str = if stream.is_none() {
-            "initiator"
-        } else {
-            "responder"
-        };
-
-        let tx_addr = Address::random_tagged(&format!("UdsSendWorker_tx_addr_{role_str}"));
-        let int_addr = Address::random_tagged(&format!("UdsSendWorker_int_addr_{role_str}"));
-        let rx_addr = Address::random_tagged(&format!("UdsRecvProcessor_{role_str}"));
-        let sender = UdsSendWorker::new(router_handle, stream, peer.clone(), int_addr, rx_addr);
-        Ok((
-            sender,
-            WorkerPair {
-                paths: pathnames,
-                peer,
-                tx_addr,
-            },
-        ))
-    }
-
-    /// Create a ([`UdsSendWorker`],[`WorkerPair`]) while spawning and starting the worker.
-    pub(crate) async fn start_pair(
+    /// Start a `UdsSendWorker` which sends messages over an established connection
+    pub(crate) async fn start(
         ctx: &Context,
-        router_handle: UdsRouterHandle,
-        stream: Option<UnixStream>,
+        registry: UdsRegistry,
+        write_half: OwnedWriteHalf,
+        addresses: &Addresses,
         peer: SocketAddr,
-        hostnames: Vec<String>,
-    ) -> Result<WorkerPair> {
-        let udsrouter_main_addr = router_handle.main_addr().clone();
-
+        sender_incoming_access_control: Arc<dyn IncomingAccessControl>,
+    ) -> Result<()> {
         trace!("Creating new UDS worker pair");
-        let (worker, pair) = Self::new_pair(router_handle, stream, peer, hostnames).await?;
+        let sender_worker = Self::new(registry, write_half, peer, addresses.clone());
 
-        let tx_mailbox = Mailbox::new(
-            pair.tx_addr(),
-            Arc::new(ockam_core::AllowSourceAddress(udsrouter_main_addr)),
-            Arc::new(ockam_core::DenyAll),
+        let main_mailbox = Mailbox::new(
+            addresses.sender_address().clone(),
+            sender_incoming_access_control,
+            Arc::new(DenyAll),
         );
 
         let internal_mailbox = Mailbox::new(
-            worker.internal_addr().clone(),
-            Arc::new(ockam_core::AllowSourceAddress(worker.rx_addr().clone())),
-            Arc::new(ockam_core::DenyAll),
+            addresses.sender_internal_address().clone(),
+            Arc::new(AllowSourceAddress(
+                addresses.receiver_internal_address().clone(),
+            )),
+            Arc::new(DenyAll),
         );
 
-        WorkerBuilder::with_mailboxes(Mailboxes::new(tx_mailbox, vec![internal_mailbox]), worker)
-            .start(ctx)
-            .await?;
+        WorkerBuilder::with_mailboxes(
+            Mailboxes::new(main_mailbox, vec![internal_mailbox]),
+            sender_worker,
+        )
+        .start(ctx)
+        .await?;
 
-        Ok(pair)
+        Ok(())
     }
 
-    async fn stop_and_unregister(&self, ctx: &Context) -> Result<()> {
-        self.router_handle.unregister(ctx.address()).await?;
-
-        ctx.stop_worker(ctx.address()).await?;
+    async fn stop(&self, ctx: &Context) -> Result<()> {
+        ctx.stop_worker(self.addresses.sender_address().clone())
+            .await?;
 
         Ok(())
     }
-}
-
-#[async_trait]
-impl Worker for UdsSendWorker {
-    type Context = Context;
-    type Message = Any;
-
-    /// Connect to the UDS socket.
-    ///
-    /// Spawn a UDS Recceiver worker to processes incoming UDS messages
-    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
-        ctx.set_cluster(crate::CLUSTER_NAME).await?;
 
-        let path = match self.peer.as_pathname() {
+    /// Connect to the UDS socket of a peer
+    pub(crate) async fn connect(peer: &SocketAddr) -> Result<(OwnedReadHalf, OwnedWriteHalf)> {
+        let path = match peer.as_pathname() {
             Some(p) => p,
             None => {
                 debug!("Failed to determine peer path.");
-                self.stop_and_unregister(ctx).await?;
-
                 return Err(TransportError::InvalidAddress.into());
             }
         };
 
-        if self.tx.is_none() {
-            let path_display = path.display();
-            debug!(addr = %path_display, "Connecting");
-
-            let connection = match UnixStream::connect(path).await {
-                Ok(c) => {
-                    debug!(addr = %path_display, "Connected");
-                    c
-                }
-                Err(e) => {
-                    debug!(addr = %path_display, err = %e, "Failed to connect");
-                    self.stop_and_unregister(ctx).await?;
-
-                    return Err(TransportError::from(e).into());
-                }
-            };
-
-            let sock = SockRef::from(&connection);
-
-            // This only enabled the socket to allow keep alive packets
-            // socket2 at this time (01/2023) does not support an automatic interval
-            // keep alive; However as this a Unix Domain Socket, this is less
-            // likely to cause issues
-            if let Err(e) = sock.set_keepalive(true) {
-                error!("Failed to set so_keepalive to true: {}", e);
+        let path_display = path.display();
+        debug!(addr = %path_display, "Connecting");
+        let connection = match UnixStream::connect(path).await {
+            Ok(c) => {
+                debug!(addr = %path_display, "Connected");
+                c
             }
+            Err(e) => {
+                debug!(addr = %path_display, err = %e, "Failed to connect");
+                return Err(TransportError::from(e).into());
+            }
+        };
+
+        let sock = SockRef::from(&connection);
 
-            let (rx, tx) = connection.into_split();
-            self.rx = Some(rx);
-            self.tx = Some(tx);
+        // This only enabled the socket to allow keep alive packets
+        // socket2 at this time (01/2023) does not support an automatic interval
+        // keep alive; However as this a Unix Domain Socket, this is less
+        // likely to cause issues
+        if let Err(e) = sock.set_keepalive(true) {
+            error!("Failed to set so_keepalive to true: {}", e);
         }
 
-        let rx = self.rx.take().ok_or(TransportError::GenericIo)?;
+        Ok(connection.into_split())
+    }
+}
 
-        let receiver = UdsRecvProcessor::new(
-            rx,
-            format!("{}#{}", crate::UDS, path.display()).into(),
-            self.internal_addr.clone(),
-        );
+#[async_trait]
+impl Worker for UdsSendWorker {
+    type Context = Context;
+    type Message = Any;
 
-        let mailbox = Mailbox::new(
-            self.rx_addr().clone(),
-            Arc::new(DenyAll),
-            Arc::new(LocalOnwardOnly),
-        );
+    async fn initialize(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
 
-        ProcessorBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![]), receiver)
-            .start(ctx)
-            .await?;
+        self.registry
+            .add_sender_worker(self.addresses.sender_address());
 
         Ok(())
     }
 
     async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry
+            .remove_sender_worker(self.addresses.sender_address());
+
         if self.rx_should_be_stopped {
-            let _ = ctx.stop_processor(self.rx_addr().clone()).await;
+            let _ = ctx
+                .stop_processor(self.addresses.receiver_address().clone())
+                .await;
         }
 
         Ok(())
@@ -253,37 +184,32 @@ impl Worker for UdsSendWorker {
         ctx: &mut Context,
         msg: Routed<Self::Message>,
     ) -> Result<()> {
-        let tx = match &mut self.tx {
-            Some(tx) => tx,
-            None => return Err(TransportError::PeerNotFound.into()),
-        };
-
         let recipient = msg.msg_addr();
-        if recipient == self.internal_addr {
+        if &recipient == self.addresses.sender_internal_address() {
             let msg = UdsSendWorkerMsg::decode(msg.payload())?;
 
             match msg {
                 UdsSendWorkerMsg::ConnectionClosed => {
-                    warn!("Stopping sender due to closed connection");
+                    info!("Stopping sender due to closed connection {:?}", self.peer);
                     // No need to stop Receiver as it notified us about connection drop and will
                     // stop itself
                     self.rx_should_be_stopped = false;
-                    self.stop_and_unregister(ctx).await?;
+                    self.stop(ctx).await?;
 
                     return Ok(());
                 }
             }
         } else {
-            let mut msg = LocalMessage::decode(msg.payload())?.into_transport_message();
+            let mut msg = msg.into_transport_message();
             // Remove our own address from the route so the other end
             // knows what to do with the incoming message
             msg.onward_route.step()?;
             // Create a message buffer with prepended length
             let msg = prepare_message(msg)?;
 
-            if tx.write_all(msg.as_slice()).await.is_err() {
-                warn!("Failed to send message to peer");
-                self.stop_and_unregister(ctx).await?;
+            if self.write_half.write_all(msg.as_slice()).await.is_err() {
+                warn!("Failed to send message to peer {:?}", self.peer);
+                self.stop(ctx).await?;
 
                 return Ok(());
             }
@@ -0,0 +1,127 @@
+use core::time::Duration;
+use ockam_core::compat::rand::{self, random_string, Rng};
+use ockam_core::{route, AllowAll, Result, Routed, Worker};
+use ockam_node::Context;
+use ockam_transport_uds::{UdsConnectionOptions, UdsListenerOptions, UdsTransport};
+
+pub struct Echoer;
+
+#[ockam_core::worker]
+impl Worker for Echoer {
+    type Message = String;
+    type Context = Context;
+
+    async fn handle_message(&mut self, ctx: &mut Context, msg: Routed<String>) -> Result<()> {
+        ctx.send(msg.return_route(), msg.body()).await
+    }
+}
+
+fn socket_path() -> String {
+    std::env::temp_dir()
+        .join(format!("ockam-uds-{}.sock", random_string()))
+        .to_string_lossy()
+        .to_string()
+}
+
+fn random_message() -> String {
+    rand::thread_rng()
+        .sample_iter(&rand::distributions::Alphanumeric)
+        .take(256)
+        .map(char::from)
+        .collect()
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn uds_lifecycle__disconnect__should_stop_worker(ctx: &mut Context) -> Result<()> {
+    ctx.start_worker("echoer", Echoer, AllowAll, AllowAll)
+        .await?;
+
+    let path = socket_path();
+    let transport = UdsTransport::create(ctx).await?;
+    transport.listen(&path, UdsListenerOptions::new()).await?;
+
+    let tx_address1 = transport
+        .connect(&path, UdsConnectionOptions::new())
+        .await?;
+    let tx_address2 = transport
+        .connect(&path, UdsConnectionOptions::new())
+        .await?;
+
+    let msg1 = random_message();
+    let reply1: String = ctx
+        .send_and_receive(route![tx_address1.clone(), "echoer"], msg1.clone())
+        .await?;
+    assert_eq!(reply1, msg1, "Should receive the same message");
+
+    // Two connections on each side
+    ctx.sleep(Duration::from_millis(100)).await;
+    assert_eq!(transport.registry().get_all_sender_workers().len(), 4);
+    assert_eq!(transport.registry().get_all_listener_processors().len(), 1);
+
+    transport.disconnect(&tx_address1).await?;
+    let res = ctx
+        .send(route![tx_address1.clone(), "echoer"], msg1.clone())
+        .await;
+    assert!(res.is_err(), "Should not send messages after disconnection");
+
+    let msg2 = random_message();
+    let reply2: String = ctx
+        .send_and_receive(route![tx_address2.clone(), "echoer"], msg2.clone())
+        .await?;
+    assert_eq!(reply2, msg2, "Should receive the same message");
+
+    // The responder side of the closed connection stops as well
+    ctx.sleep(Duration::from_millis(100)).await;
+    assert_eq!(transport.registry().get_all_sender_workers().len(), 2);
+
+    let _ = std::fs::remove_file(&path);
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
+
+#[allow(non_snake_case)]
+#[ockam_macros::test]
+async fn uds_lifecycle__stop_listener__should_stop_accepting_connections(
+    ctx: &mut Context,
+) -> Result<()> {
+    ctx.start_worker("echoer", Echoer, AllowAll, AllowAll)
+        .await?;
+
+    let path = socket_path();
+    let transport = UdsTransport::create(ctx).await?;
+    let (_, listener_address) = transport.listen(&path, UdsListenerOptions::new()).await?;
+
+    let tx_address = transport
+        .connect(&path, UdsConnectionOptions::new())
+        .await?;
+
+    transport.stop_listener(&listener_address).await?;
+    ctx.sleep(Duration::from_millis(10)).await;
+    assert!(transport
+        .registry()
+        .get_all_listener_processors()
+        .is_empty());
+
+    let res = transport.connect(&path, UdsConnectionOptions::new()).await;
+    assert!(
+        res.is_err(),
+        "Should not accept connection after listener is stopped"
+    );
+
+    let msg = random_message();
+    let reply: String = ctx
+        .send_and_receive(route![tx_address.clone(), "echoer"], msg.clone())
+        .await?;
+    assert_eq!(reply, msg, "Should receive the same message");
+
+    let _ = std::fs::remove_file(&path);
+    if let Err(e) = ctx.stop().await {
+        println!("Unclean stop: {}", e)
+    }
+
+    Ok(())
+}
@@ -23,7 +23,7 @@
 //!
 //! // Now we can write the main function that will run the previous worker. In this case, our worker will be listening for new connections on port 8000 until the process is manually killed.
 //!
-//! use ockam_transport_websocket::WebSocketTransport;
+//! use ockam_transport_websocket::{WebSocketListenerOptions, WebSocketTransport};
 //! use ockam_core::{AllowAll};
 //! use ockam_node::NodeBuilder;
 //! use ockam_macros::node;
@@ -31,7 +31,7 @@
 //! #[ockam_macros::node(crate = "ockam_node")]
 //! async fn main(mut ctx: Context) -> Result<()> {//!
 //!     let ws = WebSocketTransport::create(&ctx).await?;
-//!     ws.listen("localhost:8000").await?; // Listen on port 8000
+//!     ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?; // Listen on port 8000
 //!
 //!     // Start a worker, of type MyWorker, at address "my_worker"
 //!     ctx.start_worker("my_worker", MyWorker, AllowAll, AllowAll).await?;
@@ -44,18 +44,18 @@
 //! Finally, we can write another node that connects to the node that is hosting the `MyWorker` worker, and we are ready to send and receive messages between them.
 //!
 //! ```rust,no_run
-//! use ockam_transport_websocket::{WebSocketTransport, WS};
+//! use ockam_transport_websocket::{WebSocketConnectionOptions, WebSocketTransport};
 //! use ockam_core::{route, Result};
 //! use ockam_node::Context;
 //! use ockam_macros::node;
 //!
 //! #[ockam_macros::node(crate = "ockam_node")]
 //! async fn main(mut ctx: Context) -> Result<()> {
-//!     use ockam_node::MessageReceiveOptions;
-//! let ws = WebSocketTransport::create(&ctx).await?;
+//!     let ws = WebSocketTransport::create(&ctx).await?;
 //!
-//!     // Define the route to the server's worker.
-//!     let r = route![(WS, "localhost:8000"), "my_worker"];
+//!     // Connect to the server and define the route to the server's worker.
+//!     let connection = ws.connect("localhost:8000", WebSocketConnectionOptions::new()).await?;
+//!     let r = route![connection, "my_worker"];
 //!
 //!     // Now you can send messages to the worker.
 //!     ctx.send(r, "Hello Ockam!".to_string()).await?;
@@ -86,16 +86,17 @@ extern crate core;
 #[macro_use]
 extern crate tracing;
 
-use std::net::SocketAddr;
+use std::net::{SocketAddr, ToSocketAddrs};
 
 use ockam_core::{Result, TransportType};
 use ockam_transport_core::TransportError;
+pub use options::*;
+pub use registry::*;
 pub use transport::*;
 
-use crate::router::{WebSocketRouter, WebSocketRouterHandle};
-
 mod error;
-mod router;
+mod options;
+mod registry;
 mod transport;
 mod workers;
 
@@ -109,3 +110,22 @@ fn parse_socket_addr<S: AsRef<str>>(s: S) -> Result<SocketAddr> {
         .parse()
         .map_err(|_| TransportError::InvalidAddress)?)
 }
+
+/// Return the peer's `SocketAddr` given a plain `String` address,
+/// resolving it if it is a hostname.
+fn resolve_peer(peer: String) -> Result<SocketAddr> {
+    // Try to parse as SocketAddr
+    if let Ok(p) = parse_socket_addr(&peer) {
+        return Ok(p);
+    }
+
+    // Try to resolve hostname
+    if let Ok(mut iter) = peer.to_socket_addrs() {
+        // FIXME: We only take ipv4 for now
+        if let Some(p) = iter.find(|x| x.is_ipv4()) {
+            return Ok(p);
+        }
+    }
+
+    Err(TransportError::InvalidAddress.into())
+}
@@ -0,0 +1,83 @@
+use futures_util::StreamExt;
+
+use crate::error::WebSocketError;
+use crate::workers::{
+    Addresses, ConnectionRole, TcpClientStream, WebSocketRecvProcessor, WebSocketSendWorker,
+};
+use crate::{resolve_peer, WebSocketAddress, WebSocketConnectionOptions, WebSocketTransport};
+use ockam_core::{Address, Result};
+
+impl WebSocketTransport {
+    /// Establish an outgoing WebSocket connection on an existing transport.
+    ///
+    /// Returns the address of the worker sending messages to the peer.
+    ///
+    /// ```rust
+    /// use ockam_transport_websocket::{WebSocketConnectionOptions, WebSocketListenerOptions, WebSocketTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let ws = WebSocketTransport::create(&ctx).await?;
+    /// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?; // Listen on port 8000
+    /// let addr = ws.connect("127.0.0.1:5000", WebSocketConnectionOptions::new()).await?; // and connect to port 5000
+    /// # Ok(()) }
+    /// ```
+    pub async fn connect(
+        &self,
+        peer: impl Into<String>,
+        options: WebSocketConnectionOptions,
+    ) -> Result<Address> {
+        let peer = resolve_peer(peer.into())?;
+
+        debug!("Connecting to {}", peer);
+        let (stream, _) =
+            tokio_tungstenite::connect_async(WebSocketAddress::from(peer).to_string())
+                .await
+                .map_err(WebSocketError::from)?;
+        let (ws_sink, ws_stream) = stream.split();
+
+        let addresses = Addresses::generate(ConnectionRole::Initiator);
+
+        options.setup_flow_control(&addresses);
+        let access_control = options.create_access_control();
+
+        WebSocketSendWorker::<TcpClientStream>::start(
+            &self.ctx,
+            self.registry.clone(),
+            ws_sink,
+            &addresses,
+            peer,
+            access_control.sender_incoming_access_control,
+        )
+        .await?;
+
+        WebSocketRecvProcessor::<TcpClientStream>::start(
+            &self.ctx,
+            self.registry.clone(),
+            ws_stream,
+            &addresses,
+            peer,
+            access_control.receiver_outgoing_access_control,
+        )
+        .await?;
+
+        Ok(addresses.sender_address().clone())
+    }
+
+    /// Interrupt an active WebSocket connection given its sender `Address`
+    ///
+    /// ```rust
+    /// use ockam_transport_websocket::{WebSocketConnectionOptions, WebSocketTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let ws = WebSocketTransport::create(&ctx).await?;
+    /// let addr = ws.connect("127.0.0.1:5000", WebSocketConnectionOptions::new()).await?;
+    ///
+    /// ws.disconnect(&addr).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn disconnect(&self, address: &Address) -> Result<()> {
+        self.ctx.stop_worker(address.clone()).await
+    }
+}
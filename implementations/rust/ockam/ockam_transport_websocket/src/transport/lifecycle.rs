@@ -0,0 +1,64 @@
+use std::sync::Arc;
+
+use ockam_core::flow_control::FlowControls;
+use ockam_core::{async_trait, Address, AsyncTryClone, Result, TransportType};
+use ockam_node::Context;
+use ockam_transport_core::{unsupported_address_error, Transport};
+
+use crate::{WebSocketConnectionOptions, WebSocketRegistry, WebSocketTransport, WS};
+
+impl WebSocketTransport {
+    /// Create a new WebSocket transport for the current node.
+    ///
+    /// ```rust
+    /// use ockam_transport_websocket::WebSocketTransport;
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let ws = WebSocketTransport::create(&ctx).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn create(ctx: &Context) -> Result<WebSocketTransport> {
+        let ws = Self {
+            ctx: ctx.async_try_clone().await?,
+            registry: WebSocketRegistry::default(),
+        };
+        // make the WebSocket transport available in the list of supported transports for
+        // later address resolution when socket addresses will need to be instantiated as
+        // WebSocket worker addresses
+        ctx.register_transport(Arc::new(ws.async_try_clone().await?));
+        Ok(ws)
+    }
+}
+
+impl WebSocketTransport {
+    /// Getter
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+    /// Registry of all active connections
+    pub fn registry(&self) -> &WebSocketRegistry {
+        &self.registry
+    }
+}
+
+#[async_trait]
+impl Transport for WebSocketTransport {
+    fn transport_type(&self) -> TransportType {
+        WS
+    }
+
+    async fn resolve_address(
+        &self,
+        flow_controls: &FlowControls,
+        address: Address,
+    ) -> Result<Address> {
+        if address.transport_type() == WS {
+            let id = flow_controls.generate_id();
+            let options = WebSocketConnectionOptions::as_producer(flow_controls, &id);
+            Ok(self.connect(address.address(), options).await?)
+        } else {
+            Err(unsupported_address_error("WebSocket", &address))
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use std::net::SocketAddr;
+
+use crate::workers::WebSocketListenProcessor;
+use crate::{parse_socket_addr, WebSocketListenerOptions, WebSocketTransport};
+use ockam_core::{Address, Result};
+
+impl WebSocketTransport {
+    /// Start listening to incoming connections on an existing transport.
+    ///
+    /// Returns the local address that this transport is bound to, and the address of the
+    /// listener processor.
+    ///
+    /// This can be useful, for example, when binding to port 0 to figure out
+    /// which port was actually bound.
+    ///
+    /// ```rust
+    /// use ockam_transport_websocket::{WebSocketListenerOptions, WebSocketTransport};
+    /// # use ockam_node::Context;
+    /// # use ockam_core::Result;
+    /// # async fn test(ctx: Context) -> Result<()> {
+    /// let ws = WebSocketTransport::create(&ctx).await?;
+    /// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn listen(
+        &self,
+        bind_addr: impl AsRef<str>,
+        options: WebSocketListenerOptions,
+    ) -> Result<(SocketAddr, Address)> {
+        let bind_addr = parse_socket_addr(bind_addr)?;
+        WebSocketListenProcessor::start(&self.ctx, self.registry.clone(), bind_addr, options).await
+    }
+
+    /// Interrupt an active WebSocket listener given its `Address`
+    pub async fn stop_listener(&self, address: &Address) -> Result<()> {
+        self.ctx.stop_processor(address.clone()).await
+    }
+}
@@ -0,0 +1,112 @@
+mod connection;
+mod lifecycle;
+mod listener;
+
+use core::fmt;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use crate::{parse_socket_addr, WebSocketRegistry, WS};
+use ockam_core::{async_trait, Address, AsyncTryClone, Result};
+use ockam_node::{Context, HasContext};
+
+/// High level management interface for WebSocket transports.
+///
+/// Be aware that only one `WebSocketTransport` can exist per node, as it
+/// registers itself as the transport for the [`WS`](crate::WS) address type.
+///
+/// To listen for incoming connections use
+/// [`ws.listen()`](crate::WebSocketTransport::listen).
+///
+/// To register additional connections on an already initialised
+/// `WebSocketTransport`, use [`ws.connect()`](crate::WebSocketTransport::connect).
+///
+/// ```rust
+/// use ockam_transport_websocket::{WebSocketConnectionOptions, WebSocketListenerOptions, WebSocketTransport};
+/// # use ockam_core::Result;
+/// # use ockam_node::Context;
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let ws = WebSocketTransport::create(&ctx).await?;
+/// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?; // Listen on port 8000
+/// ws.connect("127.0.0.1:5000", WebSocketConnectionOptions::new()).await?; // And connect to port 5000
+/// # Ok(()) }
+/// ```
+///
+/// The same `WebSocketTransport` can also bind to multiple ports.
+///
+/// ```rust
+/// use ockam_transport_websocket::{WebSocketListenerOptions, WebSocketTransport};
+/// # use ockam_core::{Address, Result};
+/// # use ockam_node::Context;
+/// # async fn test(ctx: Context) -> Result<()> {
+/// let ws = WebSocketTransport::create(&ctx).await?;
+/// ws.listen("127.0.0.1:8000", WebSocketListenerOptions::new()).await?; // Listen on port 8000
+/// ws.listen("127.0.0.1:9000", WebSocketListenerOptions::new()).await?; // Listen on port 9000
+/// # Ok(()) }
+/// ```
+#[derive(AsyncTryClone)]
+#[async_try_clone(crate = "ockam_core")]
+pub struct WebSocketTransport {
+    ctx: Context,
+    registry: WebSocketRegistry,
+}
+
+/// This trait adds a `create_web_socket_transport` method to any struct returning a Context.
+/// This is the case for an ockam::Node, so you can write `node.create_web_socket_transport()`
+#[async_trait]
+pub trait WebSocketTransportExtension: HasContext {
+    /// Create a WebSocket transport
+    async fn create_web_socket_transport(&self) -> Result<WebSocketTransport> {
+        WebSocketTransport::create(self.get_context()).await
+    }
+}
+
+impl<A: HasContext> WebSocketTransportExtension for A {}
+
+#[derive(Clone)]
+pub(crate) struct WebSocketAddress {
+    protocol: String,
+    socket_addr: SocketAddr,
+}
+
+impl From<WebSocketAddress> for Address {
+    fn from(other: WebSocketAddress) -> Self {
+        format!("{}#{}", WS, other.socket_addr).into()
+    }
+}
+
+impl From<SocketAddr> for WebSocketAddress {
+    fn from(socket_addr: SocketAddr) -> Self {
+        Self {
+            protocol: "ws".to_string(),
+            socket_addr,
+        }
+    }
+}
+
+impl From<WebSocketAddress> for SocketAddr {
+    fn from(other: WebSocketAddress) -> Self {
+        other.socket_addr
+    }
+}
+
+impl From<&WebSocketAddress> for String {
+    fn from(other: &WebSocketAddress) -> Self {
+        other.to_string()
+    }
+}
+
+impl FromStr for WebSocketAddress {
+    type Err = ockam_core::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let socket_addr = parse_socket_addr(s)?;
+        Ok(WebSocketAddress::from(socket_addr))
+    }
+}
+
+impl fmt::Display for WebSocketAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}://{}", &self.protocol, &self.socket_addr)
+    }
+}
@@ -1,46 +1,55 @@
 use std::net::SocketAddr;
 
+use futures_util::StreamExt;
 use tokio::net::TcpListener;
 
-use ockam_core::{async_trait, Address, AllowAll, Processor, Result};
+use ockam_core::{async_trait, Address, DenyAll, Processor, Result};
 use ockam_node::Context;
 use ockam_transport_core::TransportError;
 
-use crate::{error::WebSocketError, workers::WorkerPair, WebSocketRouterHandle};
+use crate::error::WebSocketError;
+use crate::workers::{
+    Addresses, ConnectionRole, TcpServerStream, WebSocketRecvProcessor, WebSocketSendWorker,
+};
+use crate::{WebSocketListenerOptions, WebSocketRegistry};
 
 /// A worker that runs in the background as a `Processor` waiting for incoming
 /// clients' connections.
 ///
-/// When a new connection is established, a new `WorkerPair` is spawned and
-/// registered by the router.
+/// When a new connection is established, a new pair of sender worker
+/// and receiver processor is spawned.
 pub(crate) struct WebSocketListenProcessor {
+    registry: WebSocketRegistry,
     inner: TcpListener,
-    router_handle: WebSocketRouterHandle,
+    options: WebSocketListenerOptions,
 }
 
 impl WebSocketListenProcessor {
     /// Create and start a new instance bound to the given `addr`.
     pub(crate) async fn start(
         ctx: &Context,
-        router_handle: WebSocketRouterHandle,
+        registry: WebSocketRegistry,
         addr: SocketAddr,
-    ) -> Result<SocketAddr> {
+        options: WebSocketListenerOptions,
+    ) -> Result<(SocketAddr, Address)> {
         debug!("Binding WebSocketListener to {}", addr);
         let inner = TcpListener::bind(addr)
             .await
             .map_err(TransportError::from)?;
         let saddr = inner.local_addr().map_err(TransportError::from)?;
+
+        let address = Address::random_tagged("WebSocketListenProcessor");
+        options.flow_control.add_spawner(&address);
+
         let processor = Self {
+            registry,
             inner,
-            router_handle,
+            options,
         };
-        let waddr = Address::random_tagged("WebSocketListenProcessor");
-        ctx.start_processor(
-            waddr, processor, AllowAll, // FIXME: @ac
-            AllowAll, // FIXME: @ac
-        )
-        .await?;
-        Ok(saddr)
+        ctx.start_processor(address.clone(), processor, DenyAll, DenyAll)
+            .await?;
+
+        Ok((saddr, address))
     }
 }
 
@@ -49,7 +58,17 @@ impl Processor for WebSocketListenProcessor {
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
-        ctx.set_cluster(crate::CLUSTER_NAME).await
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry.add_listener_processor(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_listener_processor(&ctx.address());
+
+        Ok(())
     }
 
     async fn process(&mut self, ctx: &mut Self::Context) -> Result<bool> {
@@ -57,17 +76,50 @@ impl Processor for WebSocketListenProcessor {
 
         // Wait for an incoming connection
         let (tcp_stream, peer) = self.inner.accept().await.map_err(TransportError::from)?;
-        let ws_stream = tokio_tungstenite::accept_async(tcp_stream)
-            .await
-            .map_err(WebSocketError::from)?;
+        let ws_stream = match tokio_tungstenite::accept_async(tcp_stream).await {
+            Ok(ws_stream) => ws_stream,
+            Err(e) => {
+                // A failed handshake with one peer should not stop the listener
+                warn!(
+                    "WebSocket handshake with {} failed: {}",
+                    peer,
+                    WebSocketError::from(e)
+                );
+                return Ok(true);
+            }
+        };
         debug!("TCP connection accepted");
 
-        // Spawn a connection worker for it
-        let pair = WorkerPair::from_server(ctx, ws_stream, peer, vec![]).await?;
+        let addresses = Addresses::generate(ConnectionRole::Responder);
+
+        let flow_control_id = self.options.setup_flow_control(&addresses);
+        let access_control = self
+            .options
+            .create_access_control(flow_control_id.clone())?;
 
-        // Register the connection with the local TcpRouter
-        self.router_handle.register(&pair).await?;
-        debug!("TCP connection registered");
+        let (ws_sink, ws_stream) = ws_stream.split();
+
+        // Worker to receive messages from the Node and send them over the wire
+        WebSocketSendWorker::<TcpServerStream>::start(
+            ctx,
+            self.registry.clone(),
+            ws_sink,
+            &addresses,
+            peer,
+            access_control.sender_incoming_access_control,
+        )
+        .await?;
+
+        // Processor to receive messages over the wire and forward them to the node
+        WebSocketRecvProcessor::<TcpServerStream>::start(
+            ctx,
+            self.registry.clone(),
+            ws_stream,
+            &addresses,
+            peer,
+            access_control.receiver_outgoing_access_control,
+        )
+        .await?;
 
         Ok(true)
     }
@@ -2,17 +2,17 @@ use std::net::SocketAddr;
 
 use futures_util::stream::SplitStream;
 use futures_util::StreamExt;
-use tokio_tungstenite::WebSocketStream;
 
-use crate::WebSocketAddress;
+use crate::workers::{Addresses, AsyncStream, WebSocketSendWorkerMsg, WebSocketStream};
+use crate::WebSocketRegistry;
+use ockam_core::compat::sync::Arc;
 use ockam_core::{
-    async_trait, Address, Decodable, LocalMessage, Processor, Result, TransportMessage,
+    async_trait, AllowOnwardAddress, Decodable, DenyAll, LocalMessage, Mailbox, Mailboxes,
+    OutgoingAccessControl, Processor, Result, TransportMessage,
 };
-use ockam_node::Context;
+use ockam_node::{Context, ProcessorBuilder};
 use ockam_transport_core::TransportError;
 
-use crate::workers::AsyncStream;
-
 /// A WebSocket receiving message worker.
 ///
 /// This half of the worker is created when spawning a new connection
@@ -22,19 +22,58 @@ pub(crate) struct WebSocketRecvProcessor<S>
 where
     S: AsyncStream,
 {
+    registry: WebSocketRegistry,
     ws_stream: SplitStream<WebSocketStream<S>>,
-    peer_addr: Address,
+    peer: SocketAddr,
+    addresses: Addresses,
 }
 
 impl<S> WebSocketRecvProcessor<S>
 where
     S: AsyncStream,
 {
-    pub(crate) fn new(ws_stream: SplitStream<WebSocketStream<S>>, peer: SocketAddr) -> Self {
-        Self {
+    pub(crate) async fn start(
+        ctx: &Context,
+        registry: WebSocketRegistry,
+        ws_stream: SplitStream<WebSocketStream<S>>,
+        addresses: &Addresses,
+        peer: SocketAddr,
+        receiver_outgoing_access_control: Arc<dyn OutgoingAccessControl>,
+    ) -> Result<()> {
+        let receiver = Self {
+            registry,
             ws_stream,
-            peer_addr: WebSocketAddress::from(peer).into(),
-        }
+            peer,
+            addresses: addresses.clone(),
+        };
+
+        let mailbox = Mailbox::new(
+            addresses.receiver_address().clone(),
+            Arc::new(DenyAll),
+            receiver_outgoing_access_control,
+        );
+        let internal = Mailbox::new(
+            addresses.receiver_internal_address().clone(),
+            Arc::new(DenyAll),
+            Arc::new(AllowOnwardAddress(
+                addresses.sender_internal_address().clone(),
+            )),
+        );
+        ProcessorBuilder::with_mailboxes(Mailboxes::new(mailbox, vec![internal]), receiver)
+            .start(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Notify the sender that the connection is closed
+    async fn notify_connection_closed(&self, ctx: &Context) -> Result<()> {
+        ctx.send_from_address(
+            self.addresses.sender_internal_address().clone(),
+            WebSocketSendWorkerMsg::ConnectionClosed,
+            self.addresses.receiver_internal_address().clone(),
+        )
+        .await
     }
 }
 
@@ -46,7 +85,17 @@ where
     type Context = Context;
 
     async fn initialize(&mut self, ctx: &mut Context) -> Result<()> {
-        ctx.set_cluster(crate::CLUSTER_NAME).await
+        ctx.set_cluster(crate::CLUSTER_NAME).await?;
+
+        self.registry.add_receiver_processor(&ctx.address());
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self, ctx: &mut Self::Context) -> Result<()> {
+        self.registry.remove_receiver_processor(&ctx.address());
+
+        Ok(())
     }
 
     /// Get next message from the WebSocket stream if there is
@@ -55,25 +104,30 @@ where
         // Get next message from the stream or abort if the stream is
         // either closed or exhausted.
         let ws_msg = match self.ws_stream.next().await {
-            Some(res) => match res {
-                Ok(ws_msg) => ws_msg,
-                Err(_e) => {
-                    info!(
-                        "Connection to peer '{}' was closed; dropping stream",
-                        self.peer_addr
-                    );
-                    return Ok(false);
-                }
-            },
+            Some(Ok(ws_msg)) => ws_msg,
+            Some(Err(_e)) => {
+                info!(
+                    "Connection to peer '{}' was closed; dropping stream",
+                    self.peer
+                );
+                self.notify_connection_closed(ctx).await?;
+                return Ok(false);
+            }
             None => {
                 info!(
                     "Stream connected to peer '{}' is exhausted; dropping stream",
-                    self.peer_addr
+                    self.peer
                 );
+                self.notify_connection_closed(ctx).await?;
                 return Ok(false);
             }
         };
 
+        // Control frames (ping, pong, close) don't carry Ockam messages
+        if !(ws_msg.is_binary() || ws_msg.is_text()) {
+            return Ok(true);
+        }
+
         // Extract message payload
         let encoded_msg = ws_msg.into_data();
 
@@ -83,19 +137,26 @@ where
 
         // Heartbeat message
         if msg.onward_route.next().is_err() {
-            trace!("Got heartbeat message from: {}", self.peer_addr);
+            trace!("Got heartbeat message from: {}", self.peer);
+            return Ok(true);
         }
 
-        // Insert the peer address into the return route so that
+        // Insert the sender address into the return route so that
         // reply routing can be properly resolved
-        msg.return_route.modify().prepend(self.peer_addr.clone());
+        msg.return_route
+            .modify()
+            .prepend(self.addresses.sender_address().clone());
 
         // Some verbose logging we may want to remove
         trace!("Message onward route: {}", msg.onward_route);
         trace!("Message return route: {}", msg.return_route);
 
         // Forward the message to the next hop in the route
-        ctx.forward(LocalMessage::new(msg, Vec::new())).await?;
+        ctx.forward_from_address(
+            LocalMessage::new(msg, Vec::new()),
+            self.addresses.receiver_address().clone(),
+        )
+        .await?;
 
         Ok(true)
     }
@@ -1,16 +1,26 @@
 use ockam_core::compat::rand::{self, Rng};
 use ockam_core::{route, AllowAll, Result, Routed, Worker};
 use ockam_node::Context;
-use ockam_transport_websocket::{WebSocketTransport, WS};
+use ockam_transport_websocket::{
+    WebSocketConnectionOptions, WebSocketListenerOptions, WebSocketTransport,
+};
 
-#[ignore]
 #[ockam_macros::test]
 async fn send_receive(ctx: &mut Context) -> Result<()> {
     let transport = WebSocketTransport::create(ctx).await?;
-    let listener_address = transport.listen("127.0.0.1:0").await?;
+    let (listener_address, _) = transport
+        .listen("127.0.0.1:0", WebSocketListenerOptions::new())
+        .await?;
     ctx.start_worker("echoer", Echoer, AllowAll, AllowAll)
         .await?;
 
+    let addr = transport
+        .connect(
+            listener_address.to_string(),
+            WebSocketConnectionOptions::new(),
+        )
+        .await?;
+
     // Sender
     {
         let msg: String = rand::thread_rng()
@@ -18,12 +28,17 @@ async fn send_receive(ctx: &mut Context) -> Result<()> {
             .take(256)
             .map(char::from)
             .collect();
-        let r = route![(WS, listener_address.to_string()), "echoer"];
+        let r = route![addr.clone(), "echoer"];
         let reply = ctx.send_and_receive::<String>(r, msg.clone()).await?;
 
         assert_eq!(reply, msg, "Should receive the same message");
     };
 
+    assert_eq!(transport.registry().get_all_sender_workers().len(), 2);
+    transport.disconnect(&addr).await?;
+    let res = ctx.send(route![addr, "echoer"], "hello".to_string()).await;
+    assert!(res.is_err(), "Should not send messages after disconnection");
+
     if let Err(e) = ctx.stop().await {
         println!("Unclean stop: {}", e)
     }
@@ -0,0 +1,387 @@
+use crate::storage::persistent_storage::StoredSecrets;
+use crate::{StoredSecret, VaultError};
+use aes_gcm::aead::{Aead, NewAead, Payload};
+use aes_gcm::Aes256Gcm;
+use argon2::Argon2;
+use ockam_core::compat::boxed::Box;
+use ockam_core::compat::sync::Arc;
+use ockam_core::{async_trait, KeyId, Result};
+use ockam_node::{
+    FileValueStorage, InMemoryKeyValueStorage, KeyRange, KeyValueStorage, ValueStorage, WriteBatch,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::info;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Additional data authenticated with every encrypted vault file
+const AAD: &[u8] = b"ockam_vault_encrypted_storage_v1";
+/// Size of the salt used to derive a key from a passphrase
+const SALT_LENGTH: usize = 16;
+/// Size of the AES-GCM nonce
+const NONCE_LENGTH: usize = 12;
+/// Size of the AES-256 key used to encrypt the vault file
+const KEY_LENGTH: usize = 32;
+
+/// Key used to encrypt a vault file at rest
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub enum VaultStorageKey {
+    /// A passphrase from which an encryption key is derived with Argon2id
+    Passphrase(String),
+    /// A 256 bits encryption key, for example provided by the environment
+    Raw([u8; KEY_LENGTH]),
+}
+
+impl VaultStorageKey {
+    /// Create a key which will be derived from a passphrase
+    pub fn passphrase(passphrase: impl Into<String>) -> Self {
+        VaultStorageKey::Passphrase(passphrase.into())
+    }
+
+    /// Create a key from 32 raw bytes
+    pub fn raw(key: &[u8]) -> Result<Self> {
+        let key: [u8; KEY_LENGTH] = key
+            .try_into()
+            .map_err(|_| VaultError::InvalidAesKeyLength)?;
+        Ok(VaultStorageKey::Raw(key))
+    }
+
+    /// Create a key from 32 raw bytes encoded as hexadecimal
+    pub fn from_hex(key: &str) -> Result<Self> {
+        let mut bytes = hex::decode(key.trim()).map_err(|_| VaultError::InvalidAesKeyLength)?;
+        let key = Self::raw(&bytes);
+        bytes.zeroize();
+        key
+    }
+
+    /// Create a new key derivation function configuration for this key
+    fn new_kdf(&self) -> Kdf {
+        match self {
+            VaultStorageKey::Passphrase(_) => Kdf::Argon2id {
+                salt: hex::encode(rand::random::<[u8; SALT_LENGTH]>()),
+            },
+            VaultStorageKey::Raw(_) => Kdf::Raw,
+        }
+    }
+}
+
+/// Key derivation function used to obtain the file encryption key
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+enum Kdf {
+    Argon2id { salt: String },
+    Raw,
+}
+
+/// Content of an encrypted vault file
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct EncryptedSecrets {
+    kdf: Kdf,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// A vault file is either encrypted or, if it was created by a [`PersistentStorage`](crate::storage::PersistentStorage),
+/// in plaintext. Plaintext files are encrypted in place when they are opened
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(untagged)]
+enum VaultFile {
+    Encrypted(EncryptedSecrets),
+    Plaintext(StoredSecrets),
+}
+
+impl Default for VaultFile {
+    fn default() -> Self {
+        VaultFile::Plaintext(StoredSecrets::default())
+    }
+}
+
+/// Encryption key derived from a [`VaultStorageKey`] for a given key derivation configuration
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+struct SealingKey {
+    key: [u8; KEY_LENGTH],
+    #[zeroize(skip)]
+    kdf: Kdf,
+}
+
+impl SealingKey {
+    fn derive(key: &VaultStorageKey, kdf: &Kdf) -> Result<Self> {
+        let derived = match (key, kdf) {
+            (VaultStorageKey::Passphrase(passphrase), Kdf::Argon2id { salt }) => {
+                let salt = hex::decode(salt).map_err(|_| VaultError::InvalidStorageData)?;
+                let mut derived = [0u8; KEY_LENGTH];
+                Argon2::default()
+                    .hash_password_into(passphrase.as_bytes(), &salt, &mut derived)
+                    .map_err(|_| VaultError::InvalidStorageKey)?;
+                derived
+            }
+            (VaultStorageKey::Raw(raw), Kdf::Raw) => *raw,
+            _ => return Err(VaultError::InvalidStorageKey.into()),
+        };
+        Ok(Self {
+            key: derived,
+            kdf: kdf.clone(),
+        })
+    }
+
+    fn seal(&self, secrets: &StoredSecrets) -> Result<EncryptedSecrets> {
+        let mut plaintext =
+            serde_cbor::to_vec(secrets).map_err(|_| VaultError::InvalidStorageData)?;
+        let nonce = rand::random::<[u8; NONCE_LENGTH]>();
+        let ciphertext = Aes256Gcm::new(&self.key.into()).encrypt(
+            &nonce.into(),
+            Payload {
+                msg: &plaintext,
+                aad: AAD,
+            },
+        );
+        plaintext.zeroize();
+        let ciphertext = ciphertext.map_err(|_| VaultError::AeadAesGcmEncrypt)?;
+
+        Ok(EncryptedSecrets {
+            kdf: self.kdf.clone(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    fn open(&self, encrypted: &EncryptedSecrets) -> Result<StoredSecrets> {
+        if encrypted.kdf != self.kdf {
+            return Err(VaultError::InvalidStorageKey.into());
+        }
+        let nonce: [u8; NONCE_LENGTH] = hex::decode(&encrypted.nonce)
+            .ok()
+            .and_then(|n| n.try_into().ok())
+            .ok_or(VaultError::InvalidStorageData)?;
+        let ciphertext =
+            hex::decode(&encrypted.ciphertext).map_err(|_| VaultError::InvalidStorageData)?;
+
+        let mut plaintext = Aes256Gcm::new(&self.key.into())
+            .decrypt(
+                &nonce.into(),
+                Payload {
+                    msg: &ciphertext,
+                    aad: AAD,
+                },
+            )
+            .map_err(|_| VaultError::InvalidStorageKey)?;
+        let secrets =
+            serde_cbor::from_slice(&plaintext).map_err(|_| VaultError::InvalidStorageData);
+        plaintext.zeroize();
+        Ok(secrets?)
+    }
+
+    fn open_file(&self, file: VaultFile) -> Result<StoredSecrets> {
+        match file {
+            VaultFile::Encrypted(encrypted) => self.open(&encrypted),
+            // the file was replaced by a plaintext file after being opened
+            VaultFile::Plaintext(_) => Err(VaultError::InvalidStorageData.into()),
+        }
+    }
+}
+
+/// Storage for a Vault data backed by a file which is encrypted at rest
+///
+/// The secrets are encrypted with AES-256-GCM, using a key derived from a passphrase with
+/// Argon2id or a raw key. A new nonce is used every time the file is written.
+pub struct EncryptedPersistentStorage {
+    storage: Arc<FileValueStorage<VaultFile>>,
+    cache: Arc<dyn KeyValueStorage<KeyId, StoredSecret>>,
+    key: SealingKey,
+}
+
+impl EncryptedPersistentStorage {
+    /// Create a new encrypted file storage for a Vault, unlocking it with the given key
+    ///
+    /// If the file exists and is not encrypted yet, its content is encrypted in place.
+    /// If the file is already encrypted with a different key an error is returned.
+    pub async fn create(
+        path: &Path,
+        key: VaultStorageKey,
+    ) -> Result<Arc<dyn KeyValueStorage<KeyId, StoredSecret>>> {
+        let storage = Arc::new(FileValueStorage::create(path).await?);
+        let key = Self::unlock(&storage, key).await?;
+        let cache = InMemoryKeyValueStorage::create();
+        Ok(Arc::new(EncryptedPersistentStorage {
+            storage,
+            cache,
+            key,
+        }))
+    }
+
+    /// Encrypt an existing vault file with a new key
+    ///
+    /// No other storage should have the file open while it is being re-keyed, since
+    /// they would keep on using the previous key.
+    pub async fn rekey(path: &Path, key: VaultStorageKey, new_key: VaultStorageKey) -> Result<()> {
+        let storage = FileValueStorage::<VaultFile>::create(path).await?;
+        let t = move |file: VaultFile| -> Result<(VaultFile, ())> {
+            let secrets = match file {
+                VaultFile::Encrypted(encrypted) => {
+                    SealingKey::derive(&key, &encrypted.kdf)?.open(&encrypted)?
+                }
+                VaultFile::Plaintext(secrets) => secrets,
+            };
+            let new_sealing_key = SealingKey::derive(&new_key, &new_key.new_kdf())?;
+            Ok((VaultFile::Encrypted(new_sealing_key.seal(&secrets)?), ()))
+        };
+        storage.modify_value(t).await
+    }
+
+    /// Derive the file encryption key and check that it can decrypt the file.
+    /// A plaintext file gets encrypted with that key
+    async fn unlock(
+        storage: &FileValueStorage<VaultFile>,
+        key: VaultStorageKey,
+    ) -> Result<SealingKey> {
+        let t = move |file: VaultFile| -> Result<(VaultFile, SealingKey)> {
+            match file {
+                VaultFile::Encrypted(encrypted) => {
+                    let sealing_key = SealingKey::derive(&key, &encrypted.kdf)?;
+                    sealing_key.open(&encrypted)?;
+                    Ok((VaultFile::Encrypted(encrypted), sealing_key))
+                }
+                VaultFile::Plaintext(secrets) => {
+                    if !secrets.is_empty() {
+                        info!("encrypting the plaintext vault file in place");
+                    }
+                    let sealing_key = SealingKey::derive(&key, &key.new_kdf())?;
+                    let encrypted = sealing_key.seal(&secrets)?;
+                    Ok((VaultFile::Encrypted(encrypted), sealing_key))
+                }
+            }
+        };
+        storage.modify_value(t).await
+    }
+}
+
+/// An EncryptedPersistentStorage decrypts the full data structure and then
+/// puts or gets the wanted secret
+#[async_trait]
+impl KeyValueStorage<KeyId, StoredSecret> for EncryptedPersistentStorage {
+    async fn put(&self, key_id: KeyId, stored_secret: StoredSecret) -> Result<()> {
+        self.cache
+            .put(key_id.clone(), stored_secret.clone())
+            .await?;
+
+        let key = self.key.clone();
+        let t = move |file: VaultFile| {
+            let mut secrets = key.open_file(file)?;
+            secrets.add_stored_secret(key_id.clone(), stored_secret.clone());
+            Ok(VaultFile::Encrypted(key.seal(&secrets)?))
+        };
+        self.storage.update_value(t).await
+    }
+
+    async fn get(&self, key_id: &KeyId) -> Result<Option<StoredSecret>> {
+        if let Ok(Some(s)) = self.cache.get(key_id).await {
+            return Ok(Some(s));
+        }
+        let k = key_id.clone();
+        let key = self.key.clone();
+        let t = move |file: VaultFile| -> Result<Option<StoredSecret>> {
+            Ok(key.open_file(file)?.get_stored_secret(&k))
+        };
+        self.storage.read_value(t).await
+    }
+
+    async fn delete(&self, key_id: &KeyId) -> Result<Option<StoredSecret>> {
+        self.cache.delete(key_id).await?;
+        let k = key_id.clone();
+        let key = self.key.clone();
+        let t = move |file: VaultFile| -> Result<(VaultFile, Option<StoredSecret>)> {
+            let mut secrets = key.open_file(file)?;
+            let r = secrets.delete_stored_secret(&k);
+            Ok((VaultFile::Encrypted(key.seal(&secrets)?), r))
+        };
+        self.storage.modify_value(t).await
+    }
+
+    async fn range(&self, range: KeyRange<KeyId>) -> Result<Vec<(KeyId, StoredSecret)>> {
+        let key = self.key.clone();
+        let t = move |file: VaultFile| -> Result<Vec<(KeyId, StoredSecret)>> {
+            Ok(key.open_file(file)?.stored_secrets_in(&range))
+        };
+        self.storage.read_value(t).await
+    }
+
+    async fn write_batch(&self, batch: WriteBatch<KeyId, StoredSecret>) -> Result<()> {
+        let b = batch.clone();
+        let key = self.key.clone();
+        let t = move |file: VaultFile| {
+            let mut secrets = key.open_file(file)?;
+            secrets.apply_batch(b.clone());
+            Ok(VaultFile::Encrypted(key.seal(&secrets)?))
+        };
+        self.storage.update_value(t).await?;
+        self.cache.write_batch(batch).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::persistent_storage::tests::create_temp_file;
+    use crate::storage::PersistentStorage;
+    use crate::{Secret, SecretAttributes, VaultSecurityModule};
+
+    #[tokio::test]
+    async fn test_encrypted_persistent_storage() -> Result<()> {
+        let temp_file = create_temp_file();
+        let storage =
+            EncryptedPersistentStorage::create(&temp_file, VaultStorageKey::passphrase("pass"))
+                .await?;
+
+        let (key_id, stored_secret) = create_secret().await?;
+        storage.put(key_id.clone(), stored_secret.clone()).await?;
+
+        // the secret must not be written in plaintext
+        let file_contents = std::fs::read_to_string(&temp_file).unwrap();
+        assert!(!file_contents.contains(&key_id));
+        assert!(!file_contents.contains(&hex::encode(stored_secret.secret().as_ref())));
+
+        // the secret can be read back with the same passphrase
+        let storage =
+            EncryptedPersistentStorage::create(&temp_file, VaultStorageKey::passphrase("pass"))
+                .await?;
+        assert_eq!(storage.get(&key_id).await?, Some(stored_secret));
+
+        // but the file can't be unlocked with another passphrase
+        let wrong =
+            EncryptedPersistentStorage::create(&temp_file, VaultStorageKey::passphrase("wrong"))
+                .await;
+        assert!(wrong.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_and_rekey() -> Result<()> {
+        let temp_file = create_temp_file();
+        let plaintext = PersistentStorage::create(&temp_file).await?;
+        let (key_id, stored_secret) = create_secret().await?;
+        plaintext.put(key_id.clone(), stored_secret.clone()).await?;
+
+        // opening a plaintext file encrypts it in place
+        let raw_key = VaultStorageKey::raw(&[7; 32])?;
+        let storage = EncryptedPersistentStorage::create(&temp_file, raw_key.clone()).await?;
+        assert_eq!(storage.get(&key_id).await?, Some(stored_secret.clone()));
+        let file_contents = std::fs::read_to_string(&temp_file).unwrap();
+        assert!(!file_contents.contains(&key_id));
+
+        // the file can be re-keyed with a passphrase
+        let passphrase = VaultStorageKey::passphrase("new passphrase");
+        EncryptedPersistentStorage::rekey(&temp_file, raw_key.clone(), passphrase.clone()).await?;
+        assert!(EncryptedPersistentStorage::create(&temp_file, raw_key)
+            .await
+            .is_err());
+        let storage = EncryptedPersistentStorage::create(&temp_file, passphrase).await?;
+        assert_eq!(storage.get(&key_id).await?, Some(stored_secret));
+        Ok(())
+    }
+
+    async fn create_secret() -> Result<(KeyId, StoredSecret)> {
+        let secret = Secret::new(vec![1; 32]);
+        let attributes = SecretAttributes::Ed25519;
+        let key_id = VaultSecurityModule::compute_key_id(&secret, &attributes).await?;
+        Ok((key_id, StoredSecret::new(secret, attributes)))
+    }
+}
@@ -1,4 +1,8 @@
 /// Storage of secrets to a file
 mod persistent_storage;
 
+/// Storage of secrets to a file encrypted at rest
+mod encrypted_persistent_storage;
+
+pub use encrypted_persistent_storage::*;
 pub use persistent_storage::*;
@@ -2,7 +2,9 @@ use crate::{constants, Secret, SecretAttributes, SecretType, StoredSecret};
 use ockam_core::compat::boxed::Box;
 use ockam_core::compat::sync::Arc;
 use ockam_core::{async_trait, KeyId, Result};
-use ockam_node::{FileValueStorage, InMemoryKeyValueStorage, KeyValueStorage, ValueStorage};
+use ockam_node::{
+    FileValueStorage, InMemoryKeyValueStorage, KeyRange, KeyValueStorage, ValueStorage, WriteBatch,
+};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -26,7 +28,7 @@ impl PersistentStorage {
 
 /// This struct is serialized to a file in order to persist vault data
 #[derive(Debug, Clone, Default)]
-struct StoredSecrets {
+pub(crate) struct StoredSecrets {
     secrets: BTreeMap<KeyId, StoredSecret>,
 }
 
@@ -41,17 +43,29 @@ struct FileSecret {
 }
 
 impl StoredSecrets {
-    fn add_stored_secret(&mut self, key_id: KeyId, stored_secret: StoredSecret) {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    pub(crate) fn add_stored_secret(&mut self, key_id: KeyId, stored_secret: StoredSecret) {
         self.secrets.insert(key_id, stored_secret);
     }
 
-    fn get_stored_secret(&self, key_id: &KeyId) -> Option<StoredSecret> {
+    pub(crate) fn get_stored_secret(&self, key_id: &KeyId) -> Option<StoredSecret> {
         self.secrets.get(key_id).cloned()
     }
 
-    fn delete_stored_secret(&mut self, key_id: &KeyId) -> Option<StoredSecret> {
+    pub(crate) fn delete_stored_secret(&mut self, key_id: &KeyId) -> Option<StoredSecret> {
         self.secrets.remove(key_id)
     }
+
+    pub(crate) fn stored_secrets_in(&self, range: &KeyRange<KeyId>) -> Vec<(KeyId, StoredSecret)> {
+        range.select(&self.secrets)
+    }
+
+    pub(crate) fn apply_batch(&mut self, batch: WriteBatch<KeyId, StoredSecret>) {
+        batch.apply_to(&mut self.secrets)
+    }
 }
 
 impl Serialize for StoredSecrets {
@@ -209,6 +223,23 @@ impl KeyValueStorage<KeyId, StoredSecret> for PersistentStorage {
         };
         self.storage.modify_value(t).await
     }
+
+    async fn range(&self, range: KeyRange<KeyId>) -> Result<Vec<(KeyId, StoredSecret)>> {
+        let t = move |v: StoredSecrets| -> Result<Vec<(KeyId, StoredSecret)>> {
+            Ok(v.stored_secrets_in(&range))
+        };
+        self.storage.read_value(t).await
+    }
+
+    async fn write_batch(&self, batch: WriteBatch<KeyId, StoredSecret>) -> Result<()> {
+        let b = batch.clone();
+        let t = move |mut v: StoredSecrets| {
+            v.apply_batch(b.clone());
+            Ok(v)
+        };
+        self.storage.update_value(t).await?;
+        self.cache.write_batch(batch).await
+    }
 }
 
 #[cfg(test)]
@@ -1,3 +1,5 @@
+#[cfg(feature = "storage")]
+use crate::storage::VaultStorageKey;
 use crate::{
     AsymmetricVault, Buffer, EphemeralSecretsStore, PersistentSecretsStore, PublicKey, Secret,
     SecretAttributes, SecretsStore, SecretsStoreReader, SecurityModule, Signature, Signer,
@@ -88,6 +90,18 @@ impl Vault {
             .build())
     }
 
+    /// Create a new vault with a persistent storage encrypted at rest
+    #[cfg(feature = "storage")]
+    pub async fn create_with_encrypted_persistent_storage_path(
+        path: &Path,
+        key: VaultStorageKey,
+    ) -> Result<Arc<Vault>> {
+        Ok(Vault::builder()
+            .with_encrypted_persistent_storage_path(path, key)
+            .await?
+            .build())
+    }
+
     /// Create a new vault with a specific storage
     pub fn create_with_persistent_storage(storage: VaultStorage) -> Arc<Vault> {
         Vault::builder().with_persistent_storage(storage).build()
@@ -1,5 +1,5 @@
 #[cfg(feature = "storage")]
-use crate::storage::PersistentStorage;
+use crate::storage::{EncryptedPersistentStorage, PersistentStorage, VaultStorageKey};
 use crate::vault::secrets_store_impl::VaultSecretsStore;
 use crate::{
     AsymmetricVault, Implementation, SecretsStore, SecurityModule, Signer, SymmetricVault, Vault,
@@ -53,6 +53,18 @@ impl VaultBuilder {
         Ok(self.with_persistent_storage(PersistentStorage::create(path).await?))
     }
 
+    /// Set a persistent storage as a file storage with a specific path, encrypted at rest with the given key.
+    /// If the file exists and is not encrypted yet, it is encrypted in place
+    /// Note: this overrides all previously set implementations
+    #[cfg(feature = "storage")]
+    pub async fn with_encrypted_persistent_storage_path(
+        &mut self,
+        path: &Path,
+        key: VaultStorageKey,
+    ) -> Result<&mut Self> {
+        Ok(self.with_persistent_storage(EncryptedPersistentStorage::create(path, key).await?))
+    }
+
     /// Set a persistent storage
     /// Note: this overrides all previously set implementations
     pub fn with_persistent_storage(&mut self, persistent_storage: VaultStorage) -> &mut Self {
@@ -45,6 +45,8 @@ pub enum VaultError {
     StorageError,
     /// Invalid Storage data
     InvalidStorageData,
+    /// The storage key can't decrypt the storage
+    InvalidStorageKey,
 }
 
 impl ockam_core::compat::error::Error for VaultError {}
@@ -73,6 +75,7 @@ impl core::fmt::Display for VaultError {
             Self::InvalidSecretAttributes => write!(f, "invalid secret attributes"),
             Self::StorageError => write!(f, "invalid storage"),
             Self::InvalidStorageData => write!(f, "invalid storage data"),
+            Self::InvalidStorageKey => write!(f, "invalid storage key"),
         }
     }
 }